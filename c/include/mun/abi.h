@@ -8,6 +8,147 @@
  */
 #define MUN_ABI_VERSION 300
 
+/**
+ * Defines the current version of the munlib format
+ */
+#define MUN_FORMAT_VERSION 8
+
+/**
+ * Defines the magic number stored in the [`FormatHeader`], the ASCII
+ * characters `MUN` followed by a nul byte in little-endian order.
+ */
+#define MUN_FORMAT_MAGIC 0x004E554D
+
+/**
+ * The name of the owner of the ABI tag note, see [`AbiTag`]
+ */
+#define MUN_ABI_TAG_NOTE_NAME "Mun"
+
+/**
+ * The type of the ABI tag note, see [`AbiTag`]
+ */
+#define MUN_ABI_TAG_NOTE_TYPE 1
+
+/**
+ * The initialization state of a static that has not been initialized yet.
+ */
+#define MUN_STATIC_UNINITIALIZED 0
+
+/**
+ * The initialization state of a static whose initializer is being evaluated.
+ */
+#define MUN_STATIC_INITIALIZING 1
+
+/**
+ * The initialization state of a static that has been initialized.
+ */
+#define MUN_STATIC_INITIALIZED 2
+
+/**
+ * Represents the optimization level an assembly was compiled with.
+ */
+enum MunOptimizationLevel
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+    /**
+     * No optimizations were applied
+     */
+    None = 0,
+    /**
+     * Only a few optimizations were applied
+     */
+    Less = 1,
+    /**
+     * The default set of optimizations was applied
+     */
+    Default = 2,
+    /**
+     * All optimizations were applied, favoring speed over compile time
+     */
+    Aggressive = 3,
+    /**
+     * Optimizations were applied that minimize the size of the assembly, at
+     * the expense of its speed, like `-Oz`
+     */
+    Size = 4,
+};
+#ifndef __cplusplus
+typedef uint8_t MunOptimizationLevel;
+#endif // __cplusplus
+
+/**
+ * Represents how a function is inlined into its callers.
+ */
+enum MunInlineAttribute
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+    /**
+     * The optimizer decides whether the function is inlined
+     */
+    MunInlineAttribute_None = 0,
+    /**
+     * The function is preferably inlined, i.e. `#[inline]`
+     */
+    MunInlineAttribute_Hint = 1,
+    /**
+     * The function is always inlined, i.e. `#[inline(always)]`
+     */
+    MunInlineAttribute_Always = 2,
+    /**
+     * The function is never inlined, i.e. `#[inline(never)]`
+     */
+    MunInlineAttribute_Never = 3,
+};
+#ifndef __cplusplus
+typedef uint8_t MunInlineAttribute;
+#endif // __cplusplus
+
+/**
+ * Represents what the optimizer favors when optimizing a function.
+ */
+enum MunOptimizeAttribute
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+    /**
+     * The function is optimized like the rest of the assembly
+     */
+    MunOptimizeAttribute_Default = 0,
+    /**
+     * The function is optimized for speed, i.e. `#[optimize(speed)]`
+     */
+    MunOptimizeAttribute_Speed = 1,
+    /**
+     * The function is optimized for size, i.e. `#[optimize(size)]`
+     */
+    MunOptimizeAttribute_Size = 2,
+};
+#ifndef __cplusplus
+typedef uint8_t MunOptimizeAttribute;
+#endif // __cplusplus
+
+/**
+ * Represents the calling convention of a function.
+ */
+enum MunCallingConvention
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+    /**
+     * The C calling convention of the target
+     */
+    MunCallingConvention_C = 0,
+};
+#ifndef __cplusplus
+typedef uint8_t MunCallingConvention;
+#endif // __cplusplus
+
 /**
  * Represents the kind of memory management a struct uses.
  */
@@ -66,6 +207,17 @@ typedef struct MunArrayTypeId {
     const union MunTypeId *element;
 } MunArrayTypeId;
 
+/**
+ * Represents a slice of a specific type. A slice borrows a contiguous
+ * sequence of elements and is passed as a pointer and a length.
+ */
+typedef struct MunSliceTypeId {
+    /**
+     * The element type of the slice
+     */
+    const union MunTypeId *element;
+} MunSliceTypeId;
+
 /**
  * Represents a unique identifier for types. The runtime can use this to lookup
  * the corresponding [`TypeInfo`]. A [`TypeId`] is a key for a [`TypeInfo`].
@@ -90,6 +242,10 @@ enum MunTypeId_Tag
      * Represents an array of a specific type
      */
     Array,
+    /**
+     * Represents a slice of a specific type
+     */
+    Slice,
 };
 #ifndef __cplusplus
 typedef uint8_t MunTypeId_Tag;
@@ -109,6 +265,10 @@ typedef union MunTypeId {
         MunTypeId_Tag array_tag;
         struct MunArrayTypeId array;
     };
+    struct {
+        MunTypeId_Tag slice_tag;
+        struct MunSliceTypeId slice;
+    };
 } MunTypeId;
 
 /**
@@ -119,6 +279,10 @@ typedef struct MunFunctionSignature {
      * Argument types
      */
     const union MunTypeId *arg_types;
+    /**
+     * Argument names, or null if the assembly was compiled without them
+     */
+    const char *const *arg_names;
     /**
      * Optional return type
      */
@@ -144,6 +308,44 @@ typedef struct MunFunctionPrototype {
     struct MunFunctionSignature signature;
 } MunFunctionPrototype;
 
+/**
+ * Represents the effective attributes of a function, including the ones
+ * that were not specified explicitly.
+ */
+typedef struct MunFunctionAttributes {
+    /**
+     * How the function is inlined into its callers
+     */
+    MunInlineAttribute inline_;
+    /**
+     * What the optimizer favors when optimizing the function
+     */
+    MunOptimizeAttribute optimize;
+    /**
+     * The calling convention of the function
+     */
+    MunCallingConvention calling_convention;
+    /**
+     * Whether the function is marked as rarely executed, i.e. `#[cold]`
+     */
+    bool is_cold;
+    /**
+     * Whether the function is exported under its exact name, i.e.
+     * `#[no_mangle]`
+     */
+    bool is_no_mangle;
+    /**
+     * Whether the calls in tail position of the function are tail calls, i.e.
+     * `#[tail_call]`
+     */
+    bool is_tail_call;
+    /**
+     * Whether the function is excluded from instrumentation, i.e.
+     * `#[no_instrument]`
+     */
+    bool is_no_instrument;
+} MunFunctionAttributes;
+
 /**
  * Represents a function definition. A function definition contains the name,
  * type signature, and a pointer to the implementation.
@@ -159,6 +361,10 @@ typedef struct MunFunctionDefinition {
      * Function pointer
      */
     const void *fn_ptr;
+    /**
+     * Function attributes, or null if the assembly was compiled without them
+     */
+    const struct MunFunctionAttributes *attributes;
 } MunFunctionDefinition;
 
 /**
@@ -251,6 +457,54 @@ typedef struct MunTypeDefinition {
     union MunTypeDefinitionData data;
 } MunTypeDefinition;
 
+/**
+ * Represents a function-local static. A static definition contains the name
+ * and type of the static, and pointers to its storage.
+ */
+typedef struct MunStaticDefinition {
+    /**
+     * The name of the static, prefixed with the name of the function that
+     * declares it, e.g. `counter::N`
+     */
+    const char *name;
+    /**
+     * The type of the static
+     */
+    union MunTypeId type_id;
+    /**
+     * Pointer to the value of the static
+     */
+    void *value_ptr;
+    /**
+     * Pointer to the initialization state of the static, or null if the
+     * static is initialized with a constant. The state is one of
+     * [`STATIC_UNINITIALIZED`], [`STATIC_INITIALIZING`] or
+     * [`STATIC_INITIALIZED`].
+     */
+    uint8_t *init_state_ptr;
+} MunStaticDefinition;
+
+/**
+ * Represents a public constant of a module. A constant definition contains
+ * the name and type of the constant, and a pointer to its value, which is
+ * evaluated at compile time and embedded in the assembly.
+ */
+typedef struct MunConstDefinition {
+    /**
+     * The name of the constant, prefixed with the path of the module that
+     * defines it, e.g. `config::MAX`
+     */
+    const char *name;
+    /**
+     * The type of the constant
+     */
+    union MunTypeId type_id;
+    /**
+     * Pointer to the value of the constant
+     */
+    const void *value_ptr;
+} MunConstDefinition;
+
 /**
  * Represents a module declaration.
  */
@@ -267,6 +521,18 @@ typedef struct MunModuleInfo {
      * Module types
      */
     const struct MunTypeDefinition *types;
+    /**
+     * Function-local statics of the module
+     */
+    const struct MunStaticDefinition *statics;
+    /**
+     * Public constants of the module
+     */
+    const struct MunConstDefinition *constants;
+    /**
+     * Names of the `#[init]` functions of the module
+     */
+    const char *const *init_functions;
     /**
      * Number of module functions
      */
@@ -275,6 +541,18 @@ typedef struct MunModuleInfo {
      * Number of module types
      */
     uint32_t num_types;
+    /**
+     * Number of function-local statics
+     */
+    uint32_t num_statics;
+    /**
+     * Number of public constants
+     */
+    uint32_t num_constants;
+    /**
+     * Number of `#[init]` functions
+     */
+    uint32_t num_init_functions;
 } MunModuleInfo;
 
 /**
@@ -322,6 +600,24 @@ typedef struct MunTypeLut {
     uint32_t num_entries;
 } MunTypeLut;
 
+/**
+ * Represents how an assembly was compiled.
+ */
+typedef struct MunBuildInfo {
+    /**
+     * The target triple the assembly was compiled for
+     */
+    const char *target_triple;
+    /**
+     * The optimization level the assembly was compiled with
+     */
+    MunOptimizationLevel optimization_level;
+    /**
+     * Whether the assembly contains debug information
+     */
+    bool has_debug_info;
+} MunBuildInfo;
+
 /**
  * Represents an assembly declaration.
  */
@@ -346,6 +642,109 @@ typedef struct MunAssemblyInfo {
      * Number of dependencies
      */
     uint32_t num_dependencies;
+    /**
+     * Information on how the assembly was compiled
+     */
+    struct MunBuildInfo build_info;
 } MunAssemblyInfo;
 
+/**
+ * Represents the header that every munlib exports through the global symbol
+ * named [`FORMAT_HEADER_NAME`](crate::FORMAT_HEADER_NAME).
+ *
+ * The layout of this struct must never change. It is read before any other
+ * information is retrieved from a munlib, which allows a runtime to reject an
+ * incompatible munlib instead of misinterpreting its contents.
+ */
+typedef struct MunFormatHeader {
+    /**
+     * Magic number that identifies the header
+     */
+    uint32_t magic;
+    /**
+     * Version of the munlib format
+     */
+    uint32_t format_version;
+    /**
+     * Version of the ABI
+     */
+    uint32_t abi_version;
+} MunFormatHeader;
+
+/**
+ * Represents the toolchain that produced a munlib. Every munlib exports it
+ * next to the [`FormatHeader`](crate::FormatHeader) through the global symbol
+ * named [`COMPILER_INFO_NAME`](crate::COMPILER_INFO_NAME).
+ *
+ * Like the format header, this struct can be read without calling into the
+ * munlib or reading any of its other information. Its layout must never
+ * change.
+ */
+typedef struct MunCompilerInfo {
+    /**
+     * The version of the Mun compiler that produced the munlib
+     */
+    const char *compiler_version;
+    /**
+     * The version of LLVM that the compiler used to generate code
+     */
+    const char *llvm_version;
+} MunCompilerInfo;
+
+/**
+ * Represents the ring buffer into which instrumented functions record their
+ * calls. The ring is owned by the runtime, which points the [`CallTrace`] of
+ * every loaded munlib to it.
+ *
+ * On entry, an instrumented function stores its id at `ids[next & mask]` and
+ * increments `next`. The number of entries must therefore be a power of two.
+ * The ring is not synchronized; calls that are made concurrently on multiple
+ * threads can overwrite each other's entries.
+ */
+typedef struct MunCallTraceRing {
+    /**
+     * The entries of the ring, `mask + 1` function ids
+     */
+    uint32_t *ids;
+    /**
+     * The number of entries of the ring minus one
+     */
+    uint64_t mask;
+    /**
+     * The total number of calls that were recorded
+     */
+    uint64_t next;
+} MunCallTraceRing;
+
+/**
+ * Represents the call trace of a munlib that was built with a call trace
+ * ring. Such munlibs export it through the global symbol named
+ * [`CALL_TRACE_NAME`](crate::CALL_TRACE_NAME).
+ */
+typedef struct MunCallTrace {
+    /**
+     * The ring into which instrumented functions record their calls. Until
+     * the runtime sets it, it points to a ring with a single entry inside the
+     * munlib.
+     */
+    MunCallTraceRing *ring;
+    /**
+     * The minimum number of calls that the ring should hold, as requested
+     * when the munlib was built
+     */
+    uint32_t capacity;
+    /**
+     * Number of instrumented functions
+     */
+    uint32_t num_functions;
+    /**
+     * The ids of the instrumented functions, see [`call_trace_id`]
+     */
+    const uint32_t *function_ids;
+    /**
+     * The full names of the instrumented functions
+     */
+    const char *const *function_names;
+} MunCallTrace;
+
 #endif /* MUN_ABI_H_ */
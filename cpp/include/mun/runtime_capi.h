@@ -295,6 +295,18 @@ typedef struct MunArrayInfo {
     const void *_1;
 } MunArrayInfo;
 
+/**
+ * Additional information of a slice [`Type`].
+ *
+ * Ownership of this type lies with the [`Type`] that created this instance. As
+ * long as the original type is not released through [`mun_type_release`] this
+ * type stays alive.
+ */
+typedef struct MunSliceInfo {
+    const void *_0;
+    const void *_1;
+} MunSliceInfo;
+
 /**
  * An enum that defines the kind of type.
  */
@@ -307,6 +319,7 @@ enum MunTypeKind_Tag
     MUN_TYPE_KIND_POINTER,
     MUN_TYPE_KIND_STRUCT,
     MUN_TYPE_KIND_ARRAY,
+    MUN_TYPE_KIND_SLICE,
 };
 #ifndef __cplusplus
 typedef uint8_t MunTypeKind_Tag;
@@ -330,6 +343,10 @@ typedef union MunTypeKind {
         MunTypeKind_Tag array_tag;
         struct MunArrayInfo array;
     };
+    struct {
+        MunTypeKind_Tag slice_tag;
+        struct MunSliceInfo slice;
+    };
 } MunTypeKind;
 
 /**
@@ -729,6 +746,16 @@ struct MunErrorHandle mun_type_pointer_type(struct MunType ty,
  */
 struct MunErrorHandle mun_type_array_type(struct MunType ty, struct MunType *array_ty);
 
+/**
+ * Returns a new [`Type`] that is a slice of the specified type.
+ *
+ * # Safety
+ *
+ * This function results in undefined behavior if the passed in `Type`s have
+ * been deallocated in a previous call to [`mun_type_release`].
+ */
+struct MunErrorHandle mun_type_slice_type(struct MunType ty, struct MunType *slice_ty);
+
 /**
  * Returns information about what kind of type this is.
  *
@@ -761,6 +788,18 @@ struct MunErrorHandle mun_types_destroy(struct MunTypes types);
 struct MunErrorHandle mun_array_type_element_type(struct MunArrayInfo ty,
                                                   struct MunType *element_ty);
 
+/**
+ * Returns the type of the elements referred to by this type. Ownership is
+ * transferred if this function returns successfully.
+ *
+ * # Safety
+ *
+ * This function results in undefined behavior if the passed in `SliceInfo` has
+ * been deallocated by a previous call to [`mun_type_release`].
+ */
+struct MunErrorHandle mun_slice_type_element_type(struct MunSliceInfo ty,
+                                                  struct MunType *element_ty);
+
 /**
  * Returns the type that this instance points to. Ownership is transferred if
  * this function returns successfully.
@@ -1,15 +1,33 @@
+mod logging;
 mod ops;
 
-use std::ffi::OsString;
+use std::{ffi::OsString, path::PathBuf};
 
+use anyhow::anyhow;
 use clap::{Parser, Subcommand};
-use ops::{build, init, language_server, new, start};
+use ops::{build, init, language_server, new, print, start};
 
 #[derive(Parser)]
-#[clap(author, version, about, long_about = None)]
+#[clap(author, version, about, long_about = None, arg_required_else_help = true)]
 struct Args {
     #[clap(subcommand)]
-    command: Command,
+    command: Option<Command>,
+
+    /// Print information about the compiler instead of running a command:
+    /// `target-list` lists the built-in target triples and
+    /// `target-spec=<TRIPLE>` describes a target as JSON.
+    #[clap(long, value_name = "INFO")]
+    print: Option<String>,
+
+    /// Log what the compiler is doing, filtered by a list of directives with
+    /// the syntax of `RUST_LOG`, e.g. `mun_codegen=debug` or `trace`.
+    /// Defaults to the value of `RUST_LOG`.
+    #[clap(long, global = true, value_name = "FILTER")]
+    log: Option<String>,
+
+    /// Write the log to the specified file instead of stderr
+    #[clap(long, global = true, value_name = "PATH")]
+    log_file: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -35,6 +53,9 @@ enum Command {
 pub enum ExitStatus {
     Success,
     Error,
+    /// The process should exit with the specified code, e.g. the value
+    /// returned by the entry point of a program.
+    Code(i32),
 }
 
 impl From<bool> for ExitStatus {
@@ -53,11 +74,16 @@ where
     T: Into<OsString> + Clone,
 {
     let args = Args::parse_from(args);
+    logging::init(args.log.as_deref(), args.log_file.as_deref())?;
+    if let Some(info) = &args.print {
+        return print::print(info);
+    }
     match args.command {
-        Command::Build(args) => build::build(args),
-        Command::LanguageServer(args) => language_server::language_server(args),
-        Command::New(args) => new::new(args),
-        Command::Init(args) => init::init(args),
-        Command::Start(args) => start::start(args),
+        Some(Command::Build(args)) => build::build(args),
+        Some(Command::LanguageServer(args)) => language_server::language_server(args),
+        Some(Command::New(args)) => new::new(args),
+        Some(Command::Init(args)) => init::init(args),
+        Some(Command::Start(args)) => start::start(args),
+        None => Err(anyhow!("no command specified, see `mun --help`")),
     }
 }
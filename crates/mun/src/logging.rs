@@ -0,0 +1,35 @@
+use std::{env, fs::File, path::Path};
+
+use anyhow::anyhow;
+
+/// Installs the logger through which the compiler and the runtime report what
+/// they are doing.
+///
+/// Events are filtered by `filter`, which uses the syntax of `RUST_LOG`, e.g.
+/// `mun_codegen=debug,mun_compiler=trace`, or by `RUST_LOG` itself if no
+/// filter is specified. Without either, logging is disabled and every event
+/// costs no more than a comparison against the maximum log level.
+///
+/// Events are written to the file at `path`, if specified, or to stderr
+/// otherwise.
+pub fn init(filter: Option<&str>, path: Option<&Path>) -> Result<(), anyhow::Error> {
+    let mut builder = pretty_env_logger::formatted_builder();
+    if let Some(filter) = filter {
+        builder.parse_filters(filter);
+    } else if let Ok(filter) = env::var("RUST_LOG") {
+        builder.parse_filters(&filter);
+    }
+
+    if let Some(path) = path {
+        let file = File::create(path)
+            .map_err(|e| anyhow!("could not create log file '{}': {e}", path.display()))?;
+        builder
+            .target(env_logger::Target::Pipe(Box::new(file)))
+            .write_style(env_logger::WriteStyle::Never);
+    }
+
+    // A logger is already installed if several commands are run in the same
+    // process, in which case the existing logger is kept.
+    let _ = builder.try_init();
+    Ok(())
+}
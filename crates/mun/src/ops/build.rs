@@ -1,10 +1,22 @@
 use std::{
+    collections::HashSet,
     env,
+    io::{self, Read},
+    num::{NonZeroU32, NonZeroUsize},
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
 };
 
 use anyhow::anyhow;
-use mun_compiler::{Config, DisplayColor, Target};
+use mun_compiler::{
+    supports_split_debug_info, Config, DebugInfo, DisplayColor, FunctionTimingsRef, LinkerOutput,
+    ProfileGuidedOptimization, RelativePathBuf, Sanitizer, Target,
+};
 use mun_project::MANIFEST_FILENAME;
 
 use crate::ExitStatus;
@@ -16,15 +28,55 @@ pub enum UseColor {
     Auto,
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum OutputKind {
+    Shared,
+    Pie,
+    Relocatable,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum DebugInfoKind {
+    None,
+    LinesOnly,
+    Full,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum SanitizerKind {
+    Address,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum EmitKind {
+    CHeader,
+    RustBindings,
+}
+
 #[derive(clap::Args)]
 pub struct Args {
+    /// Pass `-` to compile the source of a single module read from stdin
+    /// instead of a project. Equivalent to `--stdin`.
+    #[clap(value_parser = ["-"], conflicts_with_all = ["manifest_path", "watch"])]
+    input: Option<String>,
+
     /// Path to the manifest of the project
     #[clap(long)]
     manifest_path: Option<PathBuf>,
 
-    /// Optimization level [0,3]
-    #[clap(long, short = 'O', default_value_t = 2)]
-    opt_level: u8,
+    /// Compile the source of a single module read from stdin instead of a
+    /// project. The outputs are written to the current directory.
+    #[clap(long, conflicts_with_all = ["manifest_path", "watch"])]
+    stdin: bool,
+
+    /// The filename under which the source read from stdin is reported in
+    /// diagnostics. The module name and the output path are derived from it.
+    #[clap(long, default_value = "mod.mun")]
+    stdin_name: String,
+
+    /// Optimization level [0,3], or `z` to minimize code size aggressively
+    #[clap(long, short = 'O', default_value = "2")]
+    opt_level: String,
 
     /// Use color in output
     #[clap(long, value_enum)]
@@ -34,6 +86,10 @@ pub struct Args {
     #[clap(long)]
     emit_ir: bool,
 
+    /// The kind of binary to link the *.munlib as
+    #[clap(long, value_enum, default_value_t = OutputKind::Shared)]
+    output_kind: OutputKind,
+
     /// Run the compiler in watch mode. Watch input files and trigger
     /// recompilation on changes.
     #[clap(long)]
@@ -42,6 +98,140 @@ pub struct Args {
     /// Target for machine code
     #[clap(long, value_parser=parse_target_triple)]
     target: Option<Target>,
+
+    /// Number of threads used to build assemblies in parallel. Defaults to the
+    /// number of available cores.
+    #[clap(long, short = 'j')]
+    jobs: Option<NonZeroUsize>,
+
+    /// Validate the generated LLVM IR with the LLVM verifier before emitting
+    /// machine code. Enabled by default in debug builds of the compiler.
+    #[clap(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    verify_llvm: Option<bool>,
+
+    /// The amount of debug information to emit
+    #[clap(long, value_enum, default_value_t = DebugInfoKind::None)]
+    debug_info: DebugInfoKind,
+
+    /// Compile all modules into a single *.munlib in which functions call each
+    /// other directly instead of through the dispatch table. Modules can then
+    /// no longer be hot reloaded individually.
+    #[clap(long)]
+    static_dispatch: bool,
+
+    /// Emit a *.relocs file next to each *.munlib that lists the references
+    /// from machine code to dispatch table slots and globals
+    #[clap(long)]
+    emit_relocs: bool,
+
+    /// Move the debug information of each *.munlib into a separate *.debug
+    /// file next to it, which debuggers find through a reference in the
+    /// *.munlib. Only supported for ELF targets.
+    #[clap(long)]
+    split_debuginfo: bool,
+
+    /// Record the source location of every heap allocation in the runtime,
+    /// which helps to find leaks. Adds overhead to every allocation, so it is
+    /// meant for debug builds only.
+    #[clap(long)]
+    alloc_debug: bool,
+
+    /// Zero-initialize structs when they are allocated on the heap. Pass
+    /// `--init-structs=false` to only zero their references to other objects,
+    /// which saves work since struct literals assign every field anyway.
+    #[clap(long, num_args = 0..=1, require_equals = true, default_missing_value = "true")]
+    init_structs: Option<bool>,
+
+    /// Measure the execution time of every function in the runtime. Functions
+    /// marked `#[no_instrument]` are excluded. The instrumentation perturbs the
+    /// performance of the code it measures, so it is meant for profiling only.
+    #[clap(long)]
+    instrument_timing: bool,
+
+    /// Report how long generating IR for and optimizing each function takes,
+    /// slowest first, to find functions that are pathologically slow to
+    /// compile. Functions that have been compiling for a long time are reported
+    /// while the build is running.
+    #[clap(long)]
+    timings_per_function: bool,
+
+    /// Record every function call in a ring buffer of the runtime that holds
+    /// the last N calls, which a host can dump after a crash. Functions marked
+    /// `#[no_instrument]` are excluded.
+    #[clap(long, value_name = "N")]
+    call_trace_ring: Option<NonZeroU32>,
+
+    /// Instrument the code with a sanitizer and link its runtime, which is
+    /// searched for in the directory specified by `MUN_SANITIZER_RUNTIME_DIR`.
+    /// The host must load the same runtime, e.g. with `LD_PRELOAD`. Only
+    /// supported for Linux and macOS targets.
+    #[clap(long, value_enum, require_equals = true)]
+    sanitize: Option<SanitizerKind>,
+
+    /// Instrument the code to write a raw profile into DIR when the host
+    /// exits, for a profile-guided optimization build. The profiler runtime is
+    /// searched for in the directory specified by `MUN_SANITIZER_RUNTIME_DIR`.
+    /// Merge the raw profiles with `llvm-profdata merge` and pass the result to
+    /// `--profile-use`. Defaults to the working directory of the host.
+    #[clap(
+        long,
+        value_name = "DIR",
+        num_args = 0..=1,
+        require_equals = true,
+        conflicts_with = "profile_use"
+    )]
+    profile_generate: Option<Option<PathBuf>>,
+
+    /// Optimize the code with a profile that was merged from the raw profiles
+    /// written by a `--profile-generate` build. Functions that changed since
+    /// the profile was generated are reported with a warning.
+    #[clap(long, value_name = "FILE", require_equals = true)]
+    profile_use: Option<PathBuf>,
+
+    /// Remove functions that are not reachable from any `pub`, `#[no_mangle]`
+    /// or `#[reflect]` function from the *.munlib and warn about them.
+    #[clap(long)]
+    gc_functions: bool,
+
+    /// Include the names of function arguments in the reflection information
+    /// of each *.munlib. Omit them to produce smaller release builds.
+    #[clap(long)]
+    reflect_arg_names: bool,
+
+    /// Include the attributes of functions, e.g. `#[inline]` or `#[cold]`, in
+    /// the reflection information of each *.munlib.
+    #[clap(long)]
+    reflect_fn_attributes: bool,
+
+    /// Additional artifacts to write next to each *.munlib. `c-header` writes
+    /// a *.h file that declares the `#[no_mangle]` functions of the assembly.
+    /// `rust-bindings` writes a *.rs file with typed wrappers that invoke its
+    /// functions through the runtime.
+    #[clap(long, value_enum, value_delimiter = ',')]
+    emit: Vec<EmitKind>,
+
+    /// The name of the symbol through which the runtime loads a *.munlib.
+    /// Use a unique name when statically linking several *.munlibs together,
+    /// and pass the same name to the runtime.
+    #[clap(long)]
+    entry_symbol: Option<String>,
+
+    /// Suffix every symbol that a *.munlib exports with a version, such that
+    /// several versions of it can be loaded side by side. Defaults to the
+    /// version of the package if no value is given. The runtime must be told
+    /// the same version.
+    #[clap(long, value_name = "VERSION", num_args = 0..=1, require_equals = true)]
+    symbol_version: Option<Option<String>>,
+
+    /// Treat warnings as errors, such that the build fails if any warnings
+    /// are emitted
+    #[clap(long)]
+    deny_warnings: bool,
+
+    /// The maximum number of errors that are reported per file. The errors
+    /// of each file are reported in order of their position.
+    #[clap(long)]
+    max_errors_per_file: Option<NonZeroUsize>,
 }
 
 fn parse_target_triple(target_triple: &str) -> Result<Target, String> {
@@ -55,12 +245,15 @@ fn parse_target_triple(target_triple: &str) -> Result<Target, String> {
 pub fn build(args: Args) -> Result<ExitStatus, anyhow::Error> {
     log::trace!("starting build");
 
-    let optimization_lvl = match args.opt_level {
-        0 => mun_compiler::OptimizationLevel::None,
-        1 => mun_compiler::OptimizationLevel::Less,
-        2 => mun_compiler::OptimizationLevel::Default,
-        3 => mun_compiler::OptimizationLevel::Aggressive,
-        _ => return Err(anyhow!("Only optimization levels 0-3 are supported")),
+    // Minimizing the size, like `-Oz`, uses the default optimization level for both
+    // the optimizer and the target machine.
+    let (optimization_lvl, minimize_size) = match args.opt_level.as_str() {
+        "0" => (mun_compiler::OptimizationLevel::None, false),
+        "1" => (mun_compiler::OptimizationLevel::Less, false),
+        "2" => (mun_compiler::OptimizationLevel::Default, false),
+        "3" => (mun_compiler::OptimizationLevel::Aggressive, false),
+        "z" => (mun_compiler::OptimizationLevel::Default, true),
+        _ => return Err(anyhow!("Only optimization levels 0-3 and z are supported")),
     };
 
     let display_colors = args
@@ -81,6 +274,130 @@ pub fn build(args: Args) -> Result<ExitStatus, anyhow::Error> {
         })
         .unwrap_or(DisplayColor::Auto);
 
+    let read_stdin = args.stdin || args.input.is_some();
+    let stdin_name = RelativePathBuf::from(args.stdin_name);
+    if read_stdin && !mun_compiler::is_source_file(stdin_name.as_str()) {
+        return Err(anyhow!(
+            "'{}' is not a valid name for a Mun source file",
+            stdin_name
+        ));
+    }
+
+    let target = args
+        .target
+        .unwrap_or_else(|| Target::host_target().expect("unable to determine host target"));
+
+    let sanitizer = args.sanitize.map(|sanitizer| match sanitizer {
+        SanitizerKind::Address => Sanitizer::Address,
+    });
+    if let Some(sanitizer) = sanitizer {
+        if !sanitizer.is_supported_by(&target) {
+            return Err(anyhow!(
+                "{} is not supported for target '{}'",
+                sanitizer,
+                target.llvm_target
+            ));
+        }
+    }
+
+    if args.split_debuginfo && !supports_split_debug_info(&target) {
+        return Err(anyhow!(
+            "splitting debug info is not supported for target '{}'",
+            target.llvm_target
+        ));
+    }
+
+    let pgo = match (args.profile_generate, args.profile_use) {
+        (Some(dir), _) => Some(ProfileGuidedOptimization::generate_in(
+            &dir.unwrap_or_default(),
+        )),
+        (None, Some(path)) => {
+            let pgo = ProfileGuidedOptimization::Use(path.clone());
+            pgo.validate()
+                .map_err(|e| anyhow!("could not use profile '{}': {}", path.display(), e))?;
+            Some(pgo)
+        }
+        (None, None) => None,
+    };
+    if let Some(pgo) = &pgo {
+        if !pgo.is_supported_by(&target) {
+            return Err(anyhow!(
+                "profile generation is not supported for target '{}'",
+                target.llvm_target
+            ));
+        }
+    }
+
+    // Report the timings when the build finishes, also if it fails
+    let function_timings = args.timings_per_function.then(FunctionTimingsRef::default);
+    let _timings_report = function_timings.clone().map(TimingsReport::start);
+
+    let mut compiler_options = Config {
+        target,
+        optimization_lvl,
+        minimize_size,
+        out_dir: None,
+        emit_ir: args.emit_ir,
+        output_kind: match args.output_kind {
+            OutputKind::Shared => LinkerOutput::SharedObject,
+            OutputKind::Pie => LinkerOutput::PositionIndependentExecutable,
+            OutputKind::Relocatable => LinkerOutput::Relocatable,
+        },
+        pass_hook: None,
+        jobs: args.jobs,
+        verify_llvm: args.verify_llvm.unwrap_or(cfg!(debug_assertions)),
+        debug_info: match args.debug_info {
+            DebugInfoKind::None => DebugInfo::None,
+            DebugInfoKind::LinesOnly => DebugInfo::LinesOnly,
+            DebugInfoKind::Full => DebugInfo::Full,
+        },
+        static_dispatch: args.static_dispatch,
+        emit_relocations: args.emit_relocs,
+        split_debug_info: args.split_debuginfo,
+        alloc_debug: args.alloc_debug,
+        init_structs: args.init_structs.unwrap_or(true),
+        instrument_timing: args.instrument_timing,
+        call_trace_ring: args.call_trace_ring,
+        sanitizer,
+        pgo,
+        gc_functions: args.gc_functions,
+        reflect_arg_names: args.reflect_arg_names,
+        reflect_fn_attributes: args.reflect_fn_attributes,
+        function_timings,
+        emit_c_header: args.emit.contains(&EmitKind::CHeader),
+        emit_rust_bindings: args.emit.contains(&EmitKind::RustBindings),
+        entry_symbol: args.entry_symbol,
+        symbol_version: None,
+        deny_warnings: args.deny_warnings,
+        max_errors_per_file: args.max_errors_per_file,
+    };
+
+    if read_stdin {
+        compiler_options.symbol_version = match args.symbol_version {
+            Some(None) => {
+                return Err(anyhow!(
+                    "--symbol-version requires a value when compiling from stdin"
+                ))
+            }
+            version => version.flatten(),
+        };
+
+        let mut contents = String::new();
+        io::stdin()
+            .read_to_string(&mut contents)
+            .map_err(|e| anyhow!("could not read source from stdin: {}", e))?;
+
+        log::info!("compiling {} from stdin", stdin_name);
+
+        return mun_compiler::compile_source(
+            stdin_name,
+            contents,
+            compiler_options,
+            display_colors,
+        )
+        .map(Into::into);
+    }
+
     // Locate the manifest
     let manifest_path = match &args.manifest_path {
         None => {
@@ -104,13 +421,13 @@ pub fn build(args: Args) -> Result<ExitStatus, anyhow::Error> {
 
     log::info!("located build manifest at: {}", manifest_path.display());
 
-    let compiler_options = Config {
-        target: args
-            .target
-            .unwrap_or_else(|| Target::host_target().expect("unable to determine host target")),
-        optimization_lvl,
-        out_dir: None,
-        emit_ir: args.emit_ir,
+    compiler_options.symbol_version = match args.symbol_version {
+        Some(None) => Some(
+            mun_project::Package::from_file(&manifest_path)?
+                .version()
+                .to_string(),
+        ),
+        version => version.flatten(),
     };
 
     if args.watch {
@@ -125,6 +442,57 @@ pub fn build(args: Args) -> Result<ExitStatus, anyhow::Error> {
     .map(Into::into)
 }
 
+/// Reports the time it takes to compile each function. While the build is
+/// running, functions that have been compiling for a long time are reported,
+/// such that a function that makes the compiler hang can be found. When the
+/// report is dropped, a table of all functions is printed, slowest first.
+struct TimingsReport {
+    timings: FunctionTimingsRef,
+    done: Arc<AtomicBool>,
+    watchdog: Option<thread::JoinHandle<()>>,
+}
+
+impl TimingsReport {
+    /// The time after which a function that is still compiling is reported
+    const SLOW_THRESHOLD: Duration = Duration::from_secs(10);
+
+    fn start(timings: FunctionTimingsRef) -> Self {
+        let done = Arc::new(AtomicBool::new(false));
+        let watchdog = {
+            let timings = timings.clone();
+            let done = done.clone();
+            thread::spawn(move || {
+                let mut reported = HashSet::new();
+                while !done.load(Ordering::Acquire) {
+                    for (name, duration) in timings.running() {
+                        if duration >= Self::SLOW_THRESHOLD && reported.insert(name.clone()) {
+                            eprintln!("`{name}` has been compiling for {}s", duration.as_secs());
+                        }
+                    }
+                    thread::park_timeout(Duration::from_secs(1));
+                }
+            })
+        };
+
+        Self {
+            timings,
+            done,
+            watchdog: Some(watchdog),
+        }
+    }
+}
+
+impl Drop for TimingsReport {
+    fn drop(&mut self) {
+        self.done.store(true, Ordering::Release);
+        if let Some(watchdog) = self.watchdog.take() {
+            watchdog.thread().unpark();
+            let _ = watchdog.join();
+        }
+        eprint!("{}", *self.timings);
+    }
+}
+
 /// Find a Mun manifest file in the specified directory or one of its parents.
 fn find_manifest(directory: &Path) -> Option<PathBuf> {
     let mut current_dir = Some(directory);
@@ -0,0 +1,29 @@
+use anyhow::anyhow;
+use mun_compiler::{get_targets, Target, TargetSpec};
+
+use crate::ExitStatus;
+
+/// This method is invoked when the executable is run with the `--print`
+/// argument, indicating that a user requested information about the compiler
+/// instead of running a command.
+pub fn print(info: &str) -> Result<ExitStatus, anyhow::Error> {
+    if info == "target-list" {
+        for triple in get_targets() {
+            println!("{triple}");
+        }
+    } else if let Some(triple) = info.strip_prefix("target-spec=") {
+        let target = Target::search(triple).ok_or_else(|| {
+            anyhow!(
+                "unknown target triple '{triple}', use `mun --print target-list` to list the \
+                 supported targets"
+            )
+        })?;
+        println!("{}", TargetSpec::new(&target).to_json());
+    } else {
+        return Err(anyhow!(
+            "unknown print request '{info}', expected 'target-list' or 'target-spec=<triple>'"
+        ));
+    }
+
+    Ok(ExitStatus::Success)
+}
@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use anyhow::anyhow;
-use mun_runtime::Runtime;
+use mun_runtime::{FunctionDefinition, Marshal, ReturnTypeReflection, Runtime, Type};
 
 use crate::ExitStatus;
 
@@ -15,6 +15,9 @@ pub struct Args {
     entry: String,
 }
 
+/// The name of the function that is the entry point of a Mun program.
+const PROGRAM_ENTRY_POINT: &str = "main";
+
 /// Starts the runtime with the specified library and invokes function `entry`.
 pub fn start(args: Args) -> anyhow::Result<ExitStatus> {
     let builder = Runtime::builder(args.library);
@@ -31,36 +34,110 @@ pub fn start(args: Args) -> anyhow::Result<ExitStatus> {
             )
         })?;
 
+    // The program entry point has stricter requirements than other functions
+    if args.entry == PROGRAM_ENTRY_POINT {
+        return start_program(&runtime, &fn_definition);
+    }
+
     let return_type = &fn_definition.prototype.signature.return_type;
-    if return_type.equals::<bool>() {
-        let result: bool = runtime
-            .invoke(&args.entry, ())
-            .map_err(|e| anyhow!("{}", e))?;
-
-        println!("{result}");
-    } else if return_type.equals::<f64>() {
-        let result: f64 = runtime
-            .invoke(&args.entry, ())
-            .map_err(|e| anyhow!("{}", e))?;
-
-        println!("{result}");
-    } else if return_type.equals::<i64>() {
-        let result: i64 = runtime
-            .invoke(&args.entry, ())
-            .map_err(|e| anyhow!("{}", e))?;
-
-        println!("{result}");
-    } else if return_type.equals::<()>() {
-        #[allow(clippy::unit_arg)]
-        runtime
-            .invoke(&args.entry, ())
-            .map(|_: ()| ExitStatus::Success)
-            .map_err(|e| anyhow!("{}", e))?;
-    } else {
+    match invoke_entry(&runtime, &args.entry, return_type)? {
+        ReturnValue::Unit => {}
+        ReturnValue::Integer(value, _) | ReturnValue::Other(value) => println!("{value}"),
+    }
+    Ok(ExitStatus::Success)
+}
+
+/// Invokes the program entry point. The value returned by an entry point that
+/// returns an integer is used as the exit code of the process, any other value
+/// is printed.
+fn start_program(
+    runtime: &Runtime,
+    fn_definition: &FunctionDefinition,
+) -> anyhow::Result<ExitStatus> {
+    validate_entry_point(fn_definition)?;
+
+    let return_type = &fn_definition.prototype.signature.return_type;
+    match invoke_entry(runtime, PROGRAM_ENTRY_POINT, return_type)? {
+        ReturnValue::Unit => Ok(ExitStatus::Success),
+        ReturnValue::Integer(value, code) => code.map(ExitStatus::Code).ok_or_else(|| {
+            anyhow!(
+                "The value returned by `{PROGRAM_ENTRY_POINT}` is not a valid exit code: {value}"
+            )
+        }),
+        ReturnValue::Other(value) => {
+            println!("{value}");
+            Ok(ExitStatus::Success)
+        }
+    }
+}
+
+/// Validates that the program entry point does not take any arguments.
+fn validate_entry_point(fn_definition: &FunctionDefinition) -> anyhow::Result<()> {
+    let signature = &fn_definition.prototype.signature;
+    if !signature.arg_types.is_empty() {
         return Err(anyhow!(
-            "Only native Mun return types are supported for entry points. Found: {}",
-            return_type.name()
+            "The entry point `{PROGRAM_ENTRY_POINT}` must not take any arguments, but it takes {}",
+            signature.arg_types.len()
         ));
-    };
-    Ok(ExitStatus::Success)
+    }
+
+    Ok(())
+}
+
+/// The value returned by an entry point
+enum ReturnValue {
+    /// The entry point returns `()`
+    Unit,
+    /// The entry point returns an integer. Contains the formatted value and,
+    /// if it fits, the value as an exit code.
+    Integer(String, Option<i32>),
+    /// The formatted value of any other primitive type
+    Other(String),
+}
+
+/// Invokes the entry point `name`, which returns a value of `return_type`. Only
+/// primitive return types are supported.
+fn invoke_entry(runtime: &Runtime, name: &str, return_type: &Type) -> anyhow::Result<ReturnValue> {
+    macro_rules! invoke_primitive {
+        (integers: $($int:ty),*; others: $($other:ty),*) => {
+            $(
+                if return_type.equals::<$int>() {
+                    let value: $int = invoke(runtime, name)?;
+                    #[allow(clippy::useless_conversion)]
+                    let code = i32::try_from(value).ok();
+                    return Ok(ReturnValue::Integer(value.to_string(), code));
+                }
+            )*
+            $(
+                if return_type.equals::<$other>() {
+                    let value: $other = invoke(runtime, name)?;
+                    return Ok(ReturnValue::Other(value.to_string()));
+                }
+            )*
+        };
+    }
+
+    if return_type.equals::<()>() {
+        #[allow(clippy::let_unit_value)]
+        let () = invoke(runtime, name)?;
+        return Ok(ReturnValue::Unit);
+    }
+
+    invoke_primitive!(
+        integers: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize;
+        others: bool, f32, f64
+    );
+
+    Err(anyhow!(
+        "Only primitive Mun return types are supported for entry points. Found: {}",
+        return_type.name()
+    ))
+}
+
+/// Invokes the function `name` without arguments.
+fn invoke<'r, T: ReturnTypeReflection + Marshal<'r> + 'r>(
+    runtime: &'r Runtime,
+    name: &str,
+) -> anyhow::Result<T> {
+    runtime.invoke(name, ()).map_err(|e| anyhow!("{}", e))
 }
@@ -53,6 +53,103 @@ fn mun_emit_ir() {
     assert!(ir_path.is_file());
 }
 
+/// Verifies that the built-in targets can be listed and described.
+#[test]
+fn mun_print_targets() {
+    let args: Vec<OsString> = vec!["mun".into(), "--print".into(), "target-list".into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    let args: Vec<OsString> = vec![
+        "mun".into(),
+        "--print".into(),
+        "target-spec=x86_64-unknown-linux-gnu".into(),
+    ];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    let args: Vec<OsString> = vec![
+        "mun".into(),
+        "--print".into(),
+        "target-spec=riscv64-unknown-none".into(),
+    ];
+    let err = run_with_args(args).unwrap_err();
+    assert!(err.to_string().contains("unknown target triple"));
+}
+
+/// Verifies that the program entry point of a project created with `mun init`
+/// can be started.
+#[test]
+fn mun_init_start() {
+    let project = tempfile::Builder::new()
+        .prefix(PROJECT_NAME)
+        .tempdir()
+        .unwrap();
+
+    let args: Vec<OsString> = vec!["mun".into(), "init".into(), project.path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+    build(project.path(), &[]);
+
+    assert_eq!(start(project.path()).unwrap(), mun::ExitStatus::Success);
+}
+
+/// Verifies that the value returned by the program entry point is used as the
+/// exit code.
+#[test]
+fn mun_start_exit_code() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+    let args: Vec<OsString> = vec!["mun".into(), "new".into(), project_path.as_path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    std::fs::write(
+        project_path.join("src/mod.mun"),
+        "pub fn main() -> i64 { 5 }",
+    )
+    .unwrap();
+    build(&project_path, &[]);
+
+    assert_eq!(start(&project_path).unwrap(), mun::ExitStatus::Code(5));
+}
+
+/// Verifies that an entry point with an unsupported signature is rejected,
+/// while the project itself still builds as a library.
+#[test]
+fn mun_start_invalid_entry_point() {
+    let project_dir = tempfile::Builder::new()
+        .prefix(PROJECT_DIR)
+        .tempdir()
+        .unwrap();
+
+    let project_path = project_dir.path().join(PROJECT_NAME);
+    let args: Vec<OsString> = vec!["mun".into(), "new".into(), project_path.as_path().into()];
+    assert_eq!(run_with_args(args).unwrap(), mun::ExitStatus::Success);
+
+    std::fs::write(
+        project_path.join("src/mod.mun"),
+        "pub fn main(a: i64) -> i64 { a }",
+    )
+    .unwrap();
+    build(&project_path, &[]);
+
+    let err = start(&project_path).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "The entry point `main` must not take any arguments, but it takes 1"
+    );
+}
+
+fn start(project: &Path) -> anyhow::Result<mun::ExitStatus> {
+    let args: Vec<OsString> = vec![
+        OsString::from("mun"),
+        OsString::from("start"),
+        OsString::from(project.join("target/mod.munlib")),
+    ];
+    run_with_args(args)
+}
+
 fn build(project: &Path, args: &[&str]) {
     let args: Vec<OsString> = vec![
         OsString::from("mun"),
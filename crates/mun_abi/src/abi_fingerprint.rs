@@ -0,0 +1,291 @@
+use std::{fmt, fmt::Write, str::FromStr};
+
+use crate::{
+    AssemblyInfo, CallingConvention, ConstDefinition, Guid, ModuleInfo, TypeDefinitionData,
+};
+
+/// A fingerprint of the ABI of a munlib: a hash of the signatures and calling
+/// conventions of its exported functions, the layouts of its types and the
+/// values of its constants. Any change to these changes the fingerprint,
+/// whereas changes to function bodies and optimization hints, like `#[cold]`,
+/// do not.
+///
+/// A host can embed the fingerprint of the munlib it was built against, e.g.
+/// computed by a build script that loads the munlib with `mun_libloader` and
+/// calls [`AbiFingerprint::from_assembly_info`], and have the runtime refuse
+/// to load a munlib with a different fingerprint. The fingerprint is
+/// formatted like a GUID and can be parsed from that format.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct AbiFingerprint(pub Guid);
+
+impl AbiFingerprint {
+    /// Computes the fingerprint from the reflection information of an
+    /// assembly.
+    pub fn from_assembly_info(info: &AssemblyInfo<'_>) -> Self {
+        Self::from_module_info(&info.symbols)
+    }
+
+    /// Computes the fingerprint from the reflection information of a module.
+    /// Functions, types and constants are hashed by name, so the fingerprint
+    /// does not depend on the order in which they are defined.
+    pub fn from_module_info(module: &ModuleInfo<'_>) -> Self {
+        let mut functions: Vec<String> = module
+            .functions()
+            .iter()
+            .map(|function| {
+                let signature = &function.prototype.signature;
+                let mut description = format!("fn {}(", function.prototype.name());
+                for (idx, arg_type) in signature.arg_types().iter().enumerate() {
+                    if idx > 0 {
+                        description.push_str(", ");
+                    }
+                    write!(description, "{arg_type}").unwrap();
+                }
+                description.push(')');
+                if let Some(return_type) = signature.return_type() {
+                    write!(description, " -> {return_type}").unwrap();
+                }
+                // Attributes are only emitted with `--reflect-fn-attributes`. Without
+                // them, functions use the default calling convention.
+                let calling_convention = function
+                    .attributes()
+                    .map_or(CallingConvention::default(), |attributes| {
+                        attributes.calling_convention
+                    });
+                write!(description, " {calling_convention:?}").unwrap();
+                description
+            })
+            .collect();
+        functions.sort();
+
+        let mut types: Vec<String> = module
+            .types()
+            .iter()
+            .map(|ty| {
+                let mut description = format!(
+                    "type {}: size {}, align {}",
+                    ty.name(),
+                    ty.size_in_bits(),
+                    ty.alignment()
+                );
+                let TypeDefinitionData::Struct(s) = &ty.data;
+                write!(description, ", {:?} {{", s.memory_kind).unwrap();
+                for ((name, ty), offset) in
+                    s.field_names().zip(s.field_types()).zip(s.field_offsets())
+                {
+                    write!(description, " {name}: {ty} @ {offset},").unwrap();
+                }
+                description.push_str(" }");
+                description
+            })
+            .collect();
+        types.sort();
+
+        let mut constants: Vec<String> = module
+            .constants()
+            .iter()
+            .map(|constant| {
+                let mut description = format!("const {}: {}", constant.name(), constant.type_id);
+                if let Some(value) = const_value(constant) {
+                    write!(description, " = {value}").unwrap();
+                }
+                description
+            })
+            .collect();
+        constants.sort();
+
+        let mut description = String::new();
+        for line in functions.iter().chain(types.iter()).chain(constants.iter()) {
+            description.push_str(line);
+            description.push('\n');
+        }
+
+        AbiFingerprint(Guid::from_str(&description))
+    }
+}
+
+/// Formats the value of a constant, or returns `None` if the constant is not of
+/// a primitive type.
+fn const_value(constant: &ConstDefinition<'_>) -> Option<String> {
+    macro_rules! format_value {
+        ($($ty:ty),*) => {
+            $(
+                if let Some(value) = constant.value::<$ty>() {
+                    return Some(value.to_string());
+                }
+            )*
+        };
+    }
+
+    format_value!(bool, f32, f64, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+    None
+}
+
+impl fmt::Display for AbiFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// An error that occurs when parsing an [`AbiFingerprint`] that is not
+/// formatted like a GUID, e.g. `01234567-89ab-cdef-0123-456789abcdef`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseAbiFingerprintError;
+
+impl fmt::Display for ParseAbiFingerprintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid ABI fingerprint, expected a GUID")
+    }
+}
+
+impl std::error::Error for ParseAbiFingerprintError {}
+
+impl FromStr for AbiFingerprint {
+    type Err = ParseAbiFingerprintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let groups: Vec<&str> = s.split('-').collect();
+        if groups.iter().map(|group| group.len()).ne([8, 4, 4, 4, 12]) {
+            return Err(ParseAbiFingerprintError);
+        }
+
+        let digits = groups.concat();
+        if !digits.bytes().all(|digit| digit.is_ascii_hexdigit()) {
+            return Err(ParseAbiFingerprintError);
+        }
+        let mut bytes = [0u8; 16];
+        for (idx, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&digits[idx * 2..idx * 2 + 2], 16)
+                .map_err(|_| ParseAbiFingerprintError)?;
+        }
+        Ok(AbiFingerprint(Guid(bytes)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::CString, ptr, slice};
+
+    use super::AbiFingerprint;
+    use crate::{
+        test_utils::{
+            fake_const_definition, fake_fn_prototype, fake_module_info, fake_struct_definition,
+            fake_type_definition, FAKE_CONST_NAME, FAKE_FIELD_NAME, FAKE_MODULE_PATH,
+            FAKE_STRUCT_NAME,
+        },
+        type_id::HasStaticTypeId,
+        FunctionAttributes, FunctionDefinition, StructMemoryKind, TypeDefinitionData,
+    };
+
+    fn fake_function(name: &CString, return_type: bool) -> FunctionDefinition<'_> {
+        FunctionDefinition {
+            prototype: fake_fn_prototype(
+                name,
+                slice::from_ref(i32::type_id()),
+                return_type.then(|| i32::type_id().clone()),
+            ),
+            fn_ptr: ptr::null(),
+            attributes: ptr::null(),
+        }
+    }
+
+    #[test]
+    fn fingerprint_changes_with_abi() {
+        let module_path = CString::new(FAKE_MODULE_PATH).unwrap();
+        let foo = CString::new("foo").unwrap();
+        let bar = CString::new("bar").unwrap();
+
+        let fingerprint = |functions: &[FunctionDefinition<'_>], offset: u16| {
+            let struct_name = CString::new(FAKE_STRUCT_NAME).unwrap();
+            let field_name = CString::new(FAKE_FIELD_NAME).unwrap();
+            let field_names = [field_name.as_ptr()];
+            let field_types = [i32::type_id().clone()];
+            let field_offsets = [offset];
+            let struct_info = fake_struct_definition(
+                &struct_name,
+                &field_names,
+                &field_types,
+                &field_offsets,
+                StructMemoryKind::Gc,
+            );
+            let types = [fake_type_definition(
+                &struct_name,
+                64,
+                4,
+                TypeDefinitionData::Struct(struct_info),
+            )];
+            let module = fake_module_info(&module_path, functions, &types, &[], &[], &[]);
+            AbiFingerprint::from_module_info(&module)
+        };
+
+        let original = fingerprint(&[fake_function(&foo, true), fake_function(&bar, true)], 0);
+        let reordered = fingerprint(&[fake_function(&bar, true), fake_function(&foo, true)], 0);
+        let signature_changed =
+            fingerprint(&[fake_function(&foo, false), fake_function(&bar, true)], 0);
+        let layout_changed =
+            fingerprint(&[fake_function(&foo, true), fake_function(&bar, true)], 4);
+
+        assert_eq!(original, reordered);
+        assert_ne!(original, signature_changed);
+        assert_ne!(original, layout_changed);
+    }
+
+    #[test]
+    fn fingerprint_changes_with_constants() {
+        let module_path = CString::new(FAKE_MODULE_PATH).unwrap();
+        let const_name = CString::new(FAKE_CONST_NAME).unwrap();
+
+        let fingerprint = |value: &i64| {
+            let constants = [fake_const_definition(
+                &const_name,
+                i64::type_id().clone(),
+                value as *const i64 as *const _,
+            )];
+            let module = fake_module_info(&module_path, &[], &[], &[], &constants, &[]);
+            AbiFingerprint::from_module_info(&module)
+        };
+
+        assert_eq!(fingerprint(&10), fingerprint(&10));
+        assert_ne!(fingerprint(&10), fingerprint(&11));
+    }
+
+    #[test]
+    fn fingerprint_ignores_attributes() {
+        let module_path = CString::new(FAKE_MODULE_PATH).unwrap();
+        let foo = CString::new("foo").unwrap();
+
+        let fingerprint = |attributes: Option<&FunctionAttributes>| {
+            let mut function = fake_function(&foo, true);
+            function.attributes =
+                attributes.map_or(ptr::null(), |attributes| attributes as *const _);
+            let module = fake_module_info(&module_path, &[function], &[], &[], &[], &[]);
+            AbiFingerprint::from_module_info(&module)
+        };
+
+        let cold = FunctionAttributes {
+            is_cold: true,
+            ..FunctionAttributes::default()
+        };
+        assert_eq!(fingerprint(None), fingerprint(Some(&cold)));
+        assert_eq!(
+            fingerprint(Some(&FunctionAttributes::default())),
+            fingerprint(Some(&cold))
+        );
+    }
+
+    #[test]
+    fn parse_fingerprint() {
+        let fingerprint = AbiFingerprint(crate::Guid::from_str("fingerprint"));
+        assert_eq!(
+            fingerprint.to_string().parse::<AbiFingerprint>(),
+            Ok(fingerprint)
+        );
+        assert!("".parse::<AbiFingerprint>().is_err());
+        assert!("0123456789abcdef0123456789abcdef"
+            .parse::<AbiFingerprint>()
+            .is_err());
+        assert!("0123456g-89ab-cdef-0123-456789abcdef"
+            .parse::<AbiFingerprint>()
+            .is_err());
+    }
+}
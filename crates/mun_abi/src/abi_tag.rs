@@ -0,0 +1,168 @@
+use std::str;
+
+use crate::{FormatHeader, ABI_VERSION, FORMAT_VERSION};
+
+/// The name of the owner of the ABI tag note, see [`AbiTag`]
+pub const ABI_TAG_NOTE_NAME: &str = "Mun";
+/// The type of the ABI tag note, see [`AbiTag`]
+pub const ABI_TAG_NOTE_TYPE: u32 = 1;
+
+/// Describes the compatibility of a munlib, such that packaging tools can
+/// verify it without loading the munlib. Every munlib carries the tag in a note
+/// section, which for ELF binaries is listed by `readelf -n`.
+///
+/// The note follows the ELF note layout: the sizes of the name and the
+/// description, the type [`ABI_TAG_NOTE_TYPE`], the name
+/// [`ABI_TAG_NOTE_NAME`] and the description. The description contains the
+/// format version and the ABI version, as in the [`FormatHeader`], followed by
+/// the nul-terminated target triple. All integers are 32 bits wide and encoded
+/// in the byte order of the target, and the name and the description are padded
+/// to a multiple of four bytes.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AbiTag {
+    /// Version of the munlib format
+    pub format_version: u32,
+    /// Version of the ABI
+    pub abi_version: u32,
+    /// The LLVM target triple for which the munlib was built
+    pub target_triple: String,
+}
+
+impl AbiTag {
+    /// Returns the tag for munlibs that are generated with the current format
+    /// and ABI versions for the specified target.
+    pub fn current(target_triple: impl Into<String>) -> Self {
+        AbiTag {
+            format_version: FORMAT_VERSION,
+            abi_version: ABI_VERSION,
+            target_triple: target_triple.into(),
+        }
+    }
+
+    /// Returns true if the tag describes the same format and ABI versions as
+    /// `header`.
+    pub fn matches_header(&self, header: &FormatHeader) -> bool {
+        self.format_version == header.format_version && self.abi_version == header.abi_version
+    }
+
+    /// Encodes the tag as a note in the byte order of a little- or big-endian
+    /// target.
+    pub fn to_note(&self, little_endian: bool) -> Vec<u8> {
+        let encode = |value: u32| {
+            if little_endian {
+                value.to_le_bytes()
+            } else {
+                value.to_be_bytes()
+            }
+        };
+
+        let mut name = ABI_TAG_NOTE_NAME.as_bytes().to_vec();
+        name.push(0);
+        let mut desc = Vec::new();
+        desc.extend(encode(self.format_version));
+        desc.extend(encode(self.abi_version));
+        desc.extend(self.target_triple.as_bytes());
+        desc.push(0);
+
+        let mut note = Vec::new();
+        note.extend(encode(name.len() as u32));
+        note.extend(encode(desc.len() as u32));
+        note.extend(encode(ABI_TAG_NOTE_TYPE));
+        note.extend(pad(name));
+        note.extend(pad(desc));
+        note
+    }
+
+    /// Decodes a tag from a note in the byte order of a little- or big-endian
+    /// target. Returns `None` if `note` is not an ABI tag note.
+    pub fn from_note(note: &[u8], little_endian: bool) -> Option<Self> {
+        let decode = |bytes: &[u8]| {
+            let bytes = bytes.try_into().ok()?;
+            Some(if little_endian {
+                u32::from_le_bytes(bytes)
+            } else {
+                u32::from_be_bytes(bytes)
+            })
+        };
+
+        let name_size = decode(note.get(0..4)?)? as usize;
+        let desc_size = decode(note.get(4..8)?)? as usize;
+        if decode(note.get(8..12)?)? != ABI_TAG_NOTE_TYPE {
+            return None;
+        }
+
+        let name = note.get(12..12 + name_size)?;
+        if name.strip_suffix(&[0]) != Some(ABI_TAG_NOTE_NAME.as_bytes()) {
+            return None;
+        }
+
+        let desc_start = 12 + padded_len(name_size);
+        let desc = note.get(desc_start..desc_start + desc_size)?;
+        let target_triple = str::from_utf8(desc.get(8..)?.strip_suffix(&[0])?).ok()?;
+        Some(AbiTag {
+            format_version: decode(desc.get(0..4)?)?,
+            abi_version: decode(desc.get(4..8)?)?,
+            target_triple: target_triple.to_owned(),
+        })
+    }
+}
+
+/// Returns `len` rounded up to a multiple of four.
+fn padded_len(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+/// Pads `bytes` with zeros to a multiple of four bytes.
+fn pad(mut bytes: Vec<u8>) -> Vec<u8> {
+    bytes.resize(padded_len(bytes.len()), 0);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AbiTag;
+    use crate::FormatHeader;
+
+    #[test]
+    fn test_abi_tag_note_layout() {
+        let tag = AbiTag {
+            format_version: 7,
+            abi_version: 300,
+            target_triple: String::from("x86_64"),
+        };
+
+        assert_eq!(
+            tag.to_note(true),
+            [
+                4, 0, 0, 0, // name size
+                15, 0, 0, 0, // description size
+                1, 0, 0, 0, // type
+                b'M', b'u', b'n', 0, // name
+                7, 0, 0, 0, // format version
+                44, 1, 0, 0, // abi version
+                b'x', b'8', b'6', b'_', b'6', b'4', 0, 0, // target triple
+            ]
+        );
+        assert_eq!(&tag.to_note(false)[16..24], [0, 0, 0, 7, 0, 0, 1, 44]);
+    }
+
+    #[test]
+    fn test_abi_tag_roundtrip() {
+        let tag = AbiTag::current("aarch64-apple-darwin");
+        assert!(tag.matches_header(&FormatHeader::current()));
+
+        for little_endian in [true, false] {
+            let note = tag.to_note(little_endian);
+            assert_eq!(note.len() % 4, 0);
+            assert_eq!(AbiTag::from_note(&note, little_endian), Some(tag.clone()));
+        }
+    }
+
+    #[test]
+    fn test_abi_tag_from_other_note() {
+        let mut note = AbiTag::current("x86_64-unknown-linux-gnu").to_note(true);
+        note[12] = b'G';
+        assert_eq!(AbiTag::from_note(&note, true), None);
+        assert_eq!(AbiTag::from_note(&note[..10], true), None);
+    }
+}
@@ -1,6 +1,6 @@
 use std::{ffi::CStr, os::raw::c_char, slice, str};
 
-use crate::{DispatchTable, ModuleInfo, TypeLut};
+use crate::{BuildInfo, DispatchTable, ModuleInfo, TypeLut};
 
 /// Represents an assembly declaration.
 #[repr(C)]
@@ -15,6 +15,8 @@ pub struct AssemblyInfo<'a> {
     pub(crate) dependencies: *const *const c_char,
     /// Number of dependencies
     pub num_dependencies: u32,
+    /// Information on how the assembly was compiled
+    pub build_info: BuildInfo,
 }
 
 impl<'a> AssemblyInfo<'a> {
@@ -44,11 +46,12 @@ impl<'a> serde::Serialize for AssemblyInfo<'a> {
         use itertools::Itertools;
         use serde::ser::SerializeStruct;
 
-        let mut s = serializer.serialize_struct("AssemblyInfo", 4)?;
+        let mut s = serializer.serialize_struct("AssemblyInfo", 5)?;
         s.serialize_field("symbols", &self.symbols)?;
         s.serialize_field("dispatch_table", &self.dispatch_table)?;
         s.serialize_field("type_lut", &self.type_lut)?;
         s.serialize_field("dependencies", &self.dependencies().collect_vec())?;
+        s.serialize_field("build_info", &self.build_info)?;
         s.end()
     }
 }
@@ -57,22 +60,30 @@ impl<'a> serde::Serialize for AssemblyInfo<'a> {
 mod tests {
     use std::ffi::CString;
 
-    use crate::test_utils::{
-        fake_assembly_info, fake_dispatch_table, fake_module_info, fake_type_lut, FAKE_DEPENDENCY,
-        FAKE_MODULE_PATH,
+    use crate::{
+        test_utils::{
+            fake_assembly_info, fake_build_info, fake_dispatch_table, fake_module_info,
+            fake_type_lut, FAKE_DEPENDENCY, FAKE_MODULE_PATH, FAKE_TARGET_TRIPLE,
+        },
+        OptimizationLevel,
     };
 
     #[test]
     fn test_assembly_info_dependencies() {
         let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
-        let module = fake_module_info(&module_path, &[], &[]);
+        let module = fake_module_info(&module_path, &[], &[], &[], &[], &[]);
 
         let dispatch_table = fake_dispatch_table(&[], &mut []);
         let type_lut = fake_type_lut(&[], &mut [], &[]);
 
         let dependency = CString::new(FAKE_DEPENDENCY).expect("Invalid fake dependency.");
         let dependencies = &[dependency.as_ptr()];
-        let assembly = fake_assembly_info(module, dispatch_table, type_lut, dependencies);
+
+        let target_triple = CString::new(FAKE_TARGET_TRIPLE).expect("Invalid fake target triple.");
+        let build_info = fake_build_info(&target_triple, OptimizationLevel::Default, false);
+
+        let assembly =
+            fake_assembly_info(module, dispatch_table, type_lut, dependencies, build_info);
 
         assert_eq!(assembly.dependencies().count(), dependencies.len());
         for (lhs, rhs) in assembly.dependencies().zip([FAKE_DEPENDENCY].iter()) {
@@ -0,0 +1,74 @@
+use std::{ffi::CStr, os::raw::c_char, str};
+
+/// Represents the optimization level an assembly was compiled with.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum OptimizationLevel {
+    /// No optimizations were applied
+    None = 0,
+    /// Only a few optimizations were applied
+    Less = 1,
+    /// The default set of optimizations was applied
+    Default = 2,
+    /// All optimizations were applied, favoring speed over compile time
+    Aggressive = 3,
+    /// Optimizations were applied that minimize the size of the assembly, at
+    /// the expense of its speed, like `-Oz`
+    Size = 4,
+}
+
+/// Represents how an assembly was compiled.
+#[repr(C)]
+pub struct BuildInfo {
+    /// The target triple the assembly was compiled for
+    pub(crate) target_triple: *const c_char,
+    /// The optimization level the assembly was compiled with
+    pub optimization_level: OptimizationLevel,
+    /// Whether the assembly contains debug information
+    pub has_debug_info: bool,
+}
+
+impl BuildInfo {
+    /// Returns the target triple the assembly was compiled for.
+    pub fn target_triple(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(CStr::from_ptr(self.target_triple).to_bytes()) }
+    }
+}
+
+unsafe impl Send for BuildInfo {}
+unsafe impl Sync for BuildInfo {}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for BuildInfo {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("BuildInfo", 3)?;
+        s.serialize_field("target_triple", self.target_triple())?;
+        s.serialize_field("optimization_level", &self.optimization_level)?;
+        s.serialize_field("has_debug_info", &self.has_debug_info)?;
+        s.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CString;
+
+    use super::OptimizationLevel;
+    use crate::test_utils::{fake_build_info, FAKE_TARGET_TRIPLE};
+
+    #[test]
+    fn test_build_info_target_triple() {
+        let target_triple = CString::new(FAKE_TARGET_TRIPLE).expect("Invalid fake target triple.");
+        let build_info = fake_build_info(&target_triple, OptimizationLevel::Aggressive, true);
+
+        assert_eq!(build_info.target_triple(), FAKE_TARGET_TRIPLE);
+        assert_eq!(build_info.optimization_level, OptimizationLevel::Aggressive);
+        assert!(build_info.has_debug_info);
+    }
+}
@@ -0,0 +1,124 @@
+use std::{ffi::CStr, os::raw::c_char, slice, str};
+
+/// Represents the ring buffer into which instrumented functions record their
+/// calls. The ring is owned by the runtime, which points the [`CallTrace`] of
+/// every loaded munlib to it.
+///
+/// On entry, an instrumented function stores its id at `ids[next & mask]` and
+/// increments `next`. The number of entries must therefore be a power of two.
+/// The ring is not synchronized; calls that are made concurrently on multiple
+/// threads can overwrite each other's entries.
+#[repr(C)]
+pub struct CallTraceRing {
+    /// The entries of the ring, `mask + 1` function ids
+    pub ids: *mut u32,
+    /// The number of entries of the ring minus one
+    pub mask: u64,
+    /// The total number of calls that were recorded
+    pub next: u64,
+}
+
+/// Represents the call trace of a munlib that was built with a call trace
+/// ring. Such munlibs export it through the global symbol named
+/// [`CALL_TRACE_NAME`](crate::CALL_TRACE_NAME).
+#[repr(C)]
+pub struct CallTrace {
+    /// The ring into which instrumented functions record their calls. Until
+    /// the runtime sets it, it points to a ring with a single entry inside the
+    /// munlib.
+    pub ring: *mut CallTraceRing,
+    /// The minimum number of calls that the ring should hold, as requested
+    /// when the munlib was built
+    pub capacity: u32,
+    /// Number of instrumented functions
+    pub(crate) num_functions: u32,
+    /// The ids of the instrumented functions, see [`call_trace_id`]
+    pub(crate) function_ids: *const u32,
+    /// The full names of the instrumented functions
+    pub(crate) function_names: *const *const c_char,
+}
+
+impl CallTrace {
+    /// Returns the ids and full names of the instrumented functions.
+    pub fn functions(&self) -> impl Iterator<Item = (u32, &str)> {
+        let (ids, names) = if self.num_functions == 0 {
+            (&[][..], &[][..])
+        } else {
+            unsafe {
+                (
+                    slice::from_raw_parts(self.function_ids, self.num_functions as usize),
+                    slice::from_raw_parts(self.function_names, self.num_functions as usize),
+                )
+            }
+        };
+
+        ids.iter().zip(names).map(|(id, name)| {
+            (*id, unsafe {
+                str::from_utf8_unchecked(CStr::from_ptr(*name).to_bytes())
+            })
+        })
+    }
+}
+
+/// Returns the id under which the function with the full name `name`, e.g.
+/// `foo::bar`, is recorded in a [`CallTraceRing`]. The id is the 32-bit FNV-1a
+/// hash of the name, so it remains the same when a munlib is hot reloaded.
+pub const fn call_trace_id(name: &str) -> u32 {
+    let bytes = name.as_bytes();
+    let mut hash = 0x811c_9dc5u32;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        hash ^= bytes[idx] as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+        idx += 1;
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::CString, os::raw::c_char, ptr};
+
+    use super::{call_trace_id, CallTrace};
+    use crate::test_utils::FAKE_FN_NAME;
+
+    #[test]
+    fn test_call_trace_id() {
+        // Reference values of the 32-bit FNV-1a hash
+        assert_eq!(call_trace_id(""), 0x811c_9dc5);
+        assert_eq!(call_trace_id("a"), 0xe40c_292c);
+        assert_ne!(call_trace_id("foo::bar"), call_trace_id("foo::baz"));
+    }
+
+    #[test]
+    fn test_call_trace_functions() {
+        let name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let ids = [call_trace_id(FAKE_FN_NAME)];
+        let names = [name.as_ptr()];
+        let call_trace = CallTrace {
+            ring: ptr::null_mut(),
+            capacity: 16,
+            num_functions: 1,
+            function_ids: ids.as_ptr(),
+            function_names: names.as_ptr(),
+        };
+
+        assert_eq!(
+            call_trace.functions().collect::<Vec<_>>(),
+            vec![(call_trace_id(FAKE_FN_NAME), FAKE_FN_NAME)]
+        );
+    }
+
+    #[test]
+    fn test_call_trace_functions_none() {
+        let call_trace = CallTrace {
+            ring: ptr::null_mut(),
+            capacity: 16,
+            num_functions: 0,
+            function_ids: ptr::null(),
+            function_names: ptr::null::<*const c_char>(),
+        };
+
+        assert_eq!(call_trace.functions().count(), 0);
+    }
+}
@@ -0,0 +1,65 @@
+use std::{ffi::CStr, os::raw::c_char, str};
+
+/// Represents the toolchain that produced a munlib. Every munlib exports it
+/// next to the [`FormatHeader`](crate::FormatHeader) through the global symbol
+/// named [`COMPILER_INFO_NAME`](crate::COMPILER_INFO_NAME).
+///
+/// Like the format header, this struct can be read without calling into the
+/// munlib or reading any of its other information. Its layout must never
+/// change.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct CompilerInfo {
+    /// The version of the Mun compiler that produced the munlib
+    pub(crate) compiler_version: *const c_char,
+    /// The version of LLVM that the compiler used to generate code
+    pub(crate) llvm_version: *const c_char,
+}
+
+impl CompilerInfo {
+    /// Returns the version of the Mun compiler that produced the munlib.
+    pub fn compiler_version(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(CStr::from_ptr(self.compiler_version).to_bytes()) }
+    }
+
+    /// Returns the version of LLVM that the compiler used to generate code.
+    pub fn llvm_version(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(CStr::from_ptr(self.llvm_version).to_bytes()) }
+    }
+}
+
+unsafe impl Send for CompilerInfo {}
+unsafe impl Sync for CompilerInfo {}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CompilerInfo {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("CompilerInfo", 2)?;
+        s.serialize_field("compiler_version", self.compiler_version())?;
+        s.serialize_field("llvm_version", self.llvm_version())?;
+        s.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CString;
+
+    use crate::test_utils::{fake_compiler_info, FAKE_COMPILER_VERSION, FAKE_LLVM_VERSION};
+
+    #[test]
+    fn test_compiler_info_versions() {
+        let compiler_version =
+            CString::new(FAKE_COMPILER_VERSION).expect("Invalid fake compiler version.");
+        let llvm_version = CString::new(FAKE_LLVM_VERSION).expect("Invalid fake LLVM version.");
+        let compiler_info = fake_compiler_info(&compiler_version, &llvm_version);
+
+        assert_eq!(compiler_info.compiler_version(), FAKE_COMPILER_VERSION);
+        assert_eq!(compiler_info.llvm_version(), FAKE_LLVM_VERSION);
+    }
+}
@@ -0,0 +1,125 @@
+use std::{
+    ffi::{c_void, CStr},
+    os::raw::c_char,
+    str,
+};
+
+use crate::type_id::{HasStaticTypeId, TypeId};
+
+/// Represents a public constant of a module. A constant definition contains
+/// the name and type of the constant, and a pointer to its value, which is
+/// evaluated at compile time and embedded in the assembly.
+#[repr(C)]
+#[derive(Clone)]
+pub struct ConstDefinition<'a> {
+    /// The name of the constant, prefixed with the path of the module that
+    /// defines it, e.g. `config::MAX`
+    pub(crate) name: *const c_char,
+    /// The type of the constant
+    pub type_id: TypeId<'a>,
+    /// Pointer to the value of the constant
+    pub value_ptr: *const c_void,
+}
+
+impl<'a> ConstDefinition<'a> {
+    /// Returns the constant's name.
+    pub fn name(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(CStr::from_ptr(self.name).to_bytes()) }
+    }
+
+    /// Returns the value of the constant if it is of type `T`, or `None`
+    /// otherwise.
+    pub fn value<T: HasStaticTypeId + Copy>(&self) -> Option<T> {
+        if &self.type_id == T::type_id() {
+            // SAFETY: The type of the value matches `T`.
+            Some(unsafe { *self.value_ptr.cast::<T>() })
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<'a> Send for ConstDefinition<'a> {}
+unsafe impl<'a> Sync for ConstDefinition<'a> {}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for ConstDefinition<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("ConstDefinition", 3)?;
+        s.serialize_field("name", self.name())?;
+        s.serialize_field("type_id", &self.type_id)?;
+        if let Some(value) = self.value::<bool>() {
+            s.serialize_field("value", &value)?;
+        } else if let Some(value) = self.value::<f32>() {
+            s.serialize_field("value", &value)?;
+        } else if let Some(value) = self.value::<f64>() {
+            s.serialize_field("value", &value)?;
+        } else if let Some(value) = self.value::<i8>() {
+            s.serialize_field("value", &value)?;
+        } else if let Some(value) = self.value::<i16>() {
+            s.serialize_field("value", &value)?;
+        } else if let Some(value) = self.value::<i32>() {
+            s.serialize_field("value", &value)?;
+        } else if let Some(value) = self.value::<i64>() {
+            s.serialize_field("value", &value)?;
+        } else if let Some(value) = self.value::<i128>() {
+            s.serialize_field("value", &value)?;
+        } else if let Some(value) = self.value::<u8>() {
+            s.serialize_field("value", &value)?;
+        } else if let Some(value) = self.value::<u16>() {
+            s.serialize_field("value", &value)?;
+        } else if let Some(value) = self.value::<u32>() {
+            s.serialize_field("value", &value)?;
+        } else if let Some(value) = self.value::<u64>() {
+            s.serialize_field("value", &value)?;
+        } else if let Some(value) = self.value::<u128>() {
+            s.serialize_field("value", &value)?;
+        } else {
+            s.skip_field("value")?;
+        }
+        s.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CString;
+
+    use crate::{
+        test_utils::{fake_const_definition, FAKE_CONST_NAME},
+        type_id::HasStaticTypeId,
+    };
+
+    #[test]
+    fn test_const_definition_name() {
+        let name = CString::new(FAKE_CONST_NAME).expect("Invalid fake const name.");
+        let value = 10i64;
+        let definition = fake_const_definition(
+            &name,
+            i64::type_id().clone(),
+            &value as *const i64 as *const _,
+        );
+
+        assert_eq!(definition.name(), FAKE_CONST_NAME);
+        assert_eq!(&definition.type_id, i64::type_id());
+    }
+
+    #[test]
+    fn test_const_definition_value() {
+        let name = CString::new(FAKE_CONST_NAME).expect("Invalid fake const name.");
+        let value = 10i64;
+        let definition = fake_const_definition(
+            &name,
+            i64::type_id().clone(),
+            &value as *const i64 as *const _,
+        );
+
+        assert_eq!(definition.value::<i64>(), Some(10));
+        assert_eq!(definition.value::<f64>(), None);
+    }
+}
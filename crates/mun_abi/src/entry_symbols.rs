@@ -0,0 +1,197 @@
+use crate::{
+    CALL_TRACE_NAME, COMPILER_INFO_NAME, FORMAT_HEADER_NAME, GET_INFO_FN_NAME, GET_VERSION_FN_NAME,
+    SET_ALLOCATOR_HANDLE_FN_NAME,
+};
+
+/// The names of the symbols through which a runtime accesses a munlib.
+///
+/// By default a munlib exports the symbols under well-known names. When
+/// several munlibs are statically linked together these names collide, so a
+/// munlib can be built with a custom entry symbol instead. The entry symbol is
+/// the name of the `get_info` function, the names of all other symbols are
+/// prefixed with it. The loader must be told the same entry symbol to find
+/// them.
+///
+/// To load several versions of a munlib side by side, a munlib can also be
+/// built with a version. Every symbol that the munlib exports is then
+/// suffixed with `_v` followed by the version, e.g. `get_info_v1_2_0` for
+/// version `1.2.0`. The loader must be told the same version.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct EntrySymbols {
+    /// The name of the `get_info` function
+    pub get_info: String,
+    /// The name of the `get_version` function
+    pub get_version: String,
+    /// The name of the `set_allocator_handle` function
+    pub set_allocator_handle: String,
+    /// The name of the [`FormatHeader`](crate::FormatHeader) global
+    pub format_header: String,
+    /// The name of the [`CompilerInfo`](crate::CompilerInfo) global
+    pub compiler_info: String,
+    /// The name of the [`CallTrace`](crate::CallTrace) global
+    pub call_trace: String,
+    entry_symbol: String,
+    version: Option<String>,
+}
+
+impl EntrySymbols {
+    /// Returns the symbol names of a munlib that was built with the entry
+    /// symbol `entry_symbol`.
+    pub fn new(entry_symbol: &str) -> Self {
+        Self::with_version(entry_symbol, None)
+    }
+
+    /// Returns the symbol names of a munlib that was built with the entry
+    /// symbol `entry_symbol` and, optionally, the symbol version `version`.
+    /// Characters of the version that cannot occur in a C identifier are
+    /// replaced by underscores.
+    pub fn with_version(entry_symbol: &str, version: Option<&str>) -> Self {
+        let version = version.map(|version| {
+            version
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect::<String>()
+        });
+        let versioned = |symbol: String| match &version {
+            Some(version) => format!("{symbol}_v{version}"),
+            None => symbol,
+        };
+
+        let prefixed = |name: &str| {
+            if entry_symbol == GET_INFO_FN_NAME {
+                name.to_owned()
+            } else {
+                format!("{entry_symbol}_{name}")
+            }
+        };
+
+        EntrySymbols {
+            get_info: versioned(entry_symbol.to_owned()),
+            get_version: versioned(prefixed(GET_VERSION_FN_NAME)),
+            set_allocator_handle: versioned(prefixed(SET_ALLOCATOR_HANDLE_FN_NAME)),
+            format_header: versioned(prefixed(FORMAT_HEADER_NAME)),
+            compiler_info: versioned(prefixed(COMPILER_INFO_NAME)),
+            call_trace: versioned(prefixed(CALL_TRACE_NAME)),
+            entry_symbol: entry_symbol.to_owned(),
+            version,
+        }
+    }
+
+    /// Returns the entry symbol, without the version suffix.
+    pub fn entry_symbol(&self) -> &str {
+        &self.entry_symbol
+    }
+
+    /// Returns the symbol version, if any.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Returns the name under which the munlib exports the symbol `name`:
+    /// `name` suffixed with the symbol version, or `name` itself if the
+    /// munlib is not versioned.
+    pub fn versioned(&self, name: &str) -> String {
+        match &self.version {
+            Some(version) => format!("{name}_v{version}"),
+            None => name.to_owned(),
+        }
+    }
+
+    /// Returns `name` without the version suffix that [`Self::versioned`]
+    /// appends to it.
+    pub fn unversioned<'a>(&self, name: &'a str) -> &'a str {
+        self.version
+            .as_ref()
+            .and_then(|version| name.strip_suffix(version.as_str()))
+            .and_then(|name| name.strip_suffix("_v"))
+            .unwrap_or(name)
+    }
+
+    /// Returns true if `name` is one of these symbol names.
+    pub fn contains(&self, name: &str) -> bool {
+        [
+            &self.get_info,
+            &self.get_version,
+            &self.set_allocator_handle,
+            &self.format_header,
+            &self.compiler_info,
+            &self.call_trace,
+        ]
+        .into_iter()
+        .any(|symbol| symbol == name)
+    }
+
+    /// Returns true if these are the default symbol names.
+    pub fn is_default(&self) -> bool {
+        self.get_info == GET_INFO_FN_NAME
+    }
+}
+
+impl Default for EntrySymbols {
+    fn default() -> Self {
+        Self::new(GET_INFO_FN_NAME)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EntrySymbols;
+    use crate::{CALL_TRACE_NAME, COMPILER_INFO_NAME, FORMAT_HEADER_NAME, GET_INFO_FN_NAME};
+
+    #[test]
+    fn test_entry_symbols_default() {
+        let symbols = EntrySymbols::new(GET_INFO_FN_NAME);
+        assert!(symbols.is_default());
+        assert_eq!(symbols, EntrySymbols::default());
+        assert_eq!(symbols.format_header, FORMAT_HEADER_NAME);
+        assert_eq!(symbols.compiler_info, COMPILER_INFO_NAME);
+        assert_eq!(symbols.call_trace, CALL_TRACE_NAME);
+    }
+
+    #[test]
+    fn test_entry_symbols_custom() {
+        let symbols = EntrySymbols::new("game_scripts");
+        assert!(!symbols.is_default());
+        assert_eq!(symbols.get_info, "game_scripts");
+        assert_eq!(symbols.get_version, "game_scripts_get_version");
+        assert_eq!(
+            symbols.set_allocator_handle,
+            "game_scripts_set_allocator_handle"
+        );
+        assert_eq!(symbols.format_header, "game_scripts_mun_format_header");
+        assert_eq!(symbols.compiler_info, "game_scripts_mun_compiler_info");
+        assert_eq!(symbols.call_trace, "game_scripts_mun_call_trace");
+    }
+
+    #[test]
+    fn test_entry_symbols_versioned() {
+        let symbols = EntrySymbols::with_version(GET_INFO_FN_NAME, Some("1.2.0-beta"));
+        assert!(!symbols.is_default());
+        assert_eq!(symbols.entry_symbol(), GET_INFO_FN_NAME);
+        assert_eq!(symbols.version(), Some("1_2_0_beta"));
+        assert_eq!(symbols.get_info, "get_info_v1_2_0_beta");
+        assert_eq!(symbols.get_version, "get_version_v1_2_0_beta");
+        assert_eq!(symbols.format_header, "mun_format_header_v1_2_0_beta");
+        assert_eq!(
+            symbols.versioned("dispatchTable"),
+            "dispatchTable_v1_2_0_beta"
+        );
+        assert_eq!(
+            symbols.unversioned("dispatchTable_v1_2_0_beta"),
+            "dispatchTable"
+        );
+        assert_eq!(symbols.unversioned("dispatchTable"), "dispatchTable");
+
+        let symbols = EntrySymbols::with_version("game_scripts", Some("2"));
+        assert_eq!(symbols.get_info, "game_scripts_v2");
+        assert_eq!(symbols.call_trace, "game_scripts_mun_call_trace_v2");
+    }
+
+    #[test]
+    fn test_entry_symbols_unversioned() {
+        let symbols = EntrySymbols::new("game_scripts");
+        assert_eq!(symbols.version(), None);
+        assert_eq!(symbols.versioned("dispatchTable"), "dispatchTable");
+        assert_eq!(symbols.unversioned("dispatchTable_v1"), "dispatchTable_v1");
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{ABI_VERSION, FORMAT_MAGIC, FORMAT_VERSION};
+
+/// Represents the header that every munlib exports through the global symbol
+/// named [`FORMAT_HEADER_NAME`](crate::FORMAT_HEADER_NAME).
+///
+/// The layout of this struct must never change. It is read before any other
+/// information is retrieved from a munlib, which allows a runtime to reject an
+/// incompatible munlib instead of misinterpreting its contents.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FormatHeader {
+    /// Magic number that identifies the header
+    pub magic: u32,
+    /// Version of the munlib format
+    pub format_version: u32,
+    /// Version of the ABI
+    pub abi_version: u32,
+}
+
+impl FormatHeader {
+    /// Returns the header for munlibs that are generated with the current
+    /// format and ABI versions.
+    pub const fn current() -> Self {
+        FormatHeader {
+            magic: FORMAT_MAGIC,
+            format_version: FORMAT_VERSION,
+            abi_version: ABI_VERSION,
+        }
+    }
+
+    /// Returns true if the header contains the expected magic number.
+    pub fn has_valid_magic(&self) -> bool {
+        self.magic == FORMAT_MAGIC
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FormatHeader;
+    use crate::{ABI_VERSION, FORMAT_VERSION};
+
+    #[test]
+    fn test_format_header_current() {
+        let header = FormatHeader::current();
+        assert!(header.has_valid_magic());
+        assert_eq!(header.format_version, FORMAT_VERSION);
+        assert_eq!(header.abi_version, ABI_VERSION);
+    }
+
+    #[test]
+    fn test_format_header_invalid_magic() {
+        let header = FormatHeader {
+            magic: 0,
+            ..FormatHeader::current()
+        };
+        assert!(!header.has_valid_magic());
+    }
+}
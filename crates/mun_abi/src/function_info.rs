@@ -17,6 +17,79 @@ pub struct FunctionDefinition<'a> {
     pub prototype: FunctionPrototype<'a>,
     /// Function pointer
     pub fn_ptr: *const c_void,
+    /// Function attributes, or null if the assembly was compiled without them
+    pub attributes: *const FunctionAttributes,
+}
+
+/// Represents how a function is inlined into its callers.
+///
+/// cbindgen:prefix-with-name
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum InlineAttribute {
+    /// The optimizer decides whether the function is inlined
+    #[default]
+    None = 0,
+    /// The function is preferably inlined, i.e. `#[inline]`
+    Hint = 1,
+    /// The function is always inlined, i.e. `#[inline(always)]`
+    Always = 2,
+    /// The function is never inlined, i.e. `#[inline(never)]`
+    Never = 3,
+}
+
+/// Represents what the optimizer favors when optimizing a function.
+///
+/// cbindgen:prefix-with-name
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum OptimizeAttribute {
+    /// The function is optimized like the rest of the assembly
+    #[default]
+    Default = 0,
+    /// The function is optimized for speed, i.e. `#[optimize(speed)]`
+    Speed = 1,
+    /// The function is optimized for size, i.e. `#[optimize(size)]`
+    Size = 2,
+}
+
+/// Represents the calling convention of a function.
+///
+/// cbindgen:prefix-with-name
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum CallingConvention {
+    /// The C calling convention of the target
+    #[default]
+    C = 0,
+}
+
+/// Represents the effective attributes of a function, including the ones
+/// that were not specified explicitly.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FunctionAttributes {
+    /// How the function is inlined into its callers
+    pub inline: InlineAttribute,
+    /// What the optimizer favors when optimizing the function
+    pub optimize: OptimizeAttribute,
+    /// The calling convention of the function
+    pub calling_convention: CallingConvention,
+    /// Whether the function is marked as rarely executed, i.e. `#[cold]`
+    pub is_cold: bool,
+    /// Whether the function is exported under its exact name, i.e.
+    /// `#[no_mangle]`
+    pub is_no_mangle: bool,
+    /// Whether the calls in tail position of the function are tail calls, i.e.
+    /// `#[tail_call]`
+    pub is_tail_call: bool,
+    /// Whether the function is excluded from instrumentation, i.e.
+    /// `#[no_instrument]`
+    pub is_no_instrument: bool,
 }
 
 /// Represents a function prototype. A function prototype contains the name,
@@ -36,12 +109,22 @@ pub struct FunctionPrototype<'a> {
 pub struct FunctionSignature<'a> {
     /// Argument types
     pub arg_types: *const TypeId<'a>,
+    /// Argument names, or null if the assembly was compiled without them
+    pub arg_names: *const *const c_char,
     /// Optional return type
     pub return_type: TypeId<'a>,
     /// Number of argument types
     pub num_arg_types: u16,
 }
 
+impl<'a> FunctionDefinition<'a> {
+    /// Returns the function's attributes, or `None` if the assembly was
+    /// compiled without function attributes.
+    pub fn attributes(&self) -> Option<&FunctionAttributes> {
+        unsafe { self.attributes.as_ref() }
+    }
+}
+
 unsafe impl<'a> Send for FunctionDefinition<'a> {}
 unsafe impl<'a> Sync for FunctionDefinition<'a> {}
 
@@ -65,6 +148,24 @@ impl<'a> FunctionSignature<'a> {
         }
     }
 
+    /// Returns the function's arguments' names, or `None` if the assembly was
+    /// compiled without argument names. Unnamed arguments are named `_`.
+    pub fn arg_names(&self) -> Option<impl Iterator<Item = &str>> {
+        let arg_names = if self.arg_names.is_null() {
+            return None;
+        } else if self.num_arg_types == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.arg_names, self.num_arg_types as usize) }
+        };
+
+        Some(
+            arg_names
+                .iter()
+                .map(|n| unsafe { str::from_utf8_unchecked(CStr::from_ptr(*n).to_bytes()) }),
+        )
+    }
+
     /// Returns the function's return type.
     pub fn return_type(&self) -> Option<TypeId<'a>> {
         if <()>::type_id() == &self.return_type {
@@ -94,9 +195,13 @@ impl<'a> serde::Serialize for FunctionDefinition<'a> {
     {
         use serde::ser::SerializeStruct;
 
-        let mut s = serializer.serialize_struct("FunctionDefinition", 1)?;
+        let mut s = serializer.serialize_struct("FunctionDefinition", 2)?;
         s.serialize_field("prototype", &self.prototype)?;
         s.skip_field("fn_ptr")?;
+        match self.attributes() {
+            Some(attributes) => s.serialize_field("attributes", attributes)?,
+            None => s.skip_field("attributes")?,
+        }
         s.end()
     }
 }
@@ -124,8 +229,13 @@ impl<'a> serde::Serialize for FunctionSignature<'a> {
     {
         use serde::ser::SerializeStruct;
 
-        let mut s = serializer.serialize_struct("FunctionSignature", 2)?;
+        let mut s = serializer.serialize_struct("FunctionSignature", 3)?;
         s.serialize_field("arg_types", self.arg_types())?;
+        if self.arg_names.is_null() {
+            s.skip_field("arg_names")?;
+        } else if let Some(arg_names) = self.arg_names() {
+            s.serialize_field("arg_names", &arg_names.collect::<Vec<_>>())?;
+        }
         s.serialize_field("return_type", &self.return_type())?;
         s.end()
     }
@@ -133,13 +243,43 @@ impl<'a> serde::Serialize for FunctionSignature<'a> {
 
 #[cfg(test)]
 mod tests {
-    use std::ffi::CString;
+    use std::{ffi::CString, os::raw::c_char, ptr};
 
+    use super::{FunctionAttributes, FunctionDefinition, InlineAttribute};
     use crate::{
-        test_utils::{fake_fn_prototype, fake_fn_signature, FAKE_FN_NAME},
+        test_utils::{fake_fn_prototype, fake_fn_signature, FAKE_ARG_NAME, FAKE_FN_NAME},
         type_id::HasStaticTypeId,
     };
 
+    #[test]
+    fn test_fn_definition_attributes_none() {
+        let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let fn_definition = FunctionDefinition {
+            prototype: fake_fn_prototype(&fn_name, &[], None),
+            fn_ptr: ptr::null(),
+            attributes: ptr::null(),
+        };
+
+        assert!(fn_definition.attributes().is_none());
+    }
+
+    #[test]
+    fn test_fn_definition_attributes_some() {
+        let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let attributes = FunctionAttributes {
+            inline: InlineAttribute::Always,
+            is_cold: true,
+            ..FunctionAttributes::default()
+        };
+        let fn_definition = FunctionDefinition {
+            prototype: fake_fn_prototype(&fn_name, &[], None),
+            fn_ptr: ptr::null(),
+            attributes: &attributes,
+        };
+
+        assert_eq!(fn_definition.attributes(), Some(&attributes));
+    }
+
     #[test]
     fn test_fn_prototype_name() {
         let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
@@ -166,6 +306,42 @@ mod tests {
         assert_eq!(fn_signature.arg_types(), arg_types);
     }
 
+    #[test]
+    fn test_fn_signature_arg_names_none() {
+        let type_id = i32::type_id();
+
+        let arg_types = &[type_id.clone()];
+        let fn_signature = fake_fn_signature(arg_types, None);
+
+        assert!(fn_signature.arg_names().is_none());
+    }
+
+    #[test]
+    fn test_fn_signature_arg_names_without_args() {
+        let mut fn_signature = fake_fn_signature(&[], None);
+        assert!(fn_signature.arg_names().is_none());
+
+        let arg_names: &[*const c_char] = &[];
+        fn_signature.arg_names = arg_names.as_ptr();
+        assert_eq!(fn_signature.arg_names().map(Iterator::count), Some(0));
+    }
+
+    #[test]
+    fn test_fn_signature_arg_names_some() {
+        let type_id = i32::type_id();
+        let arg_name = CString::new(FAKE_ARG_NAME).expect("Invalid fake arg name.");
+
+        let arg_types = &[type_id.clone()];
+        let arg_names = &[arg_name.as_ptr()];
+        let mut fn_signature = fake_fn_signature(arg_types, None);
+        fn_signature.arg_names = arg_names.as_ptr();
+
+        assert_eq!(
+            fn_signature.arg_names().map(Iterator::collect::<Vec<_>>),
+            Some(vec![FAKE_ARG_NAME])
+        );
+    }
+
     #[test]
     fn test_fn_signature_return_type_none() {
         let return_type = None;
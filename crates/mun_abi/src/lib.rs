@@ -6,22 +6,45 @@
 
 use std::{ffi::CStr, fmt};
 
+pub use abi_fingerprint::{AbiFingerprint, ParseAbiFingerprintError};
+pub use abi_tag::{AbiTag, ABI_TAG_NOTE_NAME, ABI_TAG_NOTE_TYPE};
 pub use assembly_info::AssemblyInfo;
+pub use build_info::{BuildInfo, OptimizationLevel};
+pub use call_trace::{call_trace_id, CallTrace, CallTraceRing};
+pub use compiler_info::CompilerInfo;
+pub use const_info::ConstDefinition;
 pub use dispatch_table::DispatchTable;
-pub use function_info::{FunctionDefinition, FunctionPrototype, FunctionSignature};
+pub use entry_symbols::EntrySymbols;
+pub use format_header::FormatHeader;
+pub use function_info::{
+    CallingConvention, FunctionAttributes, FunctionDefinition, FunctionPrototype,
+    FunctionSignature, InlineAttribute, OptimizeAttribute,
+};
 pub use module_info::ModuleInfo;
 pub use primitive::PrimitiveType;
+pub use static_info::{
+    StaticDefinition, STATIC_INITIALIZED, STATIC_INITIALIZING, STATIC_UNINITIALIZED,
+};
 pub use struct_info::{StructDefinition, StructMemoryKind};
-pub use type_id::{ArrayTypeId, HasStaticTypeId, PointerTypeId, TypeId};
+pub use type_id::{ArrayTypeId, HasStaticTypeId, PointerTypeId, SliceTypeId, TypeId};
 pub use type_info::{HasStaticTypeName, TypeDefinition, TypeDefinitionData};
 pub use type_lut::TypeLut;
 
 // C bindings can be manually generated by running `cargo gen-abi`.
+mod abi_fingerprint;
+mod abi_tag;
 mod assembly_info;
+mod build_info;
+mod call_trace;
+mod compiler_info;
+mod const_info;
 mod dispatch_table;
+mod entry_symbols;
+mod format_header;
 mod function_info;
 mod module_info;
 mod primitive;
+mod static_info;
 pub mod static_type_map;
 mod struct_info;
 mod type_id;
@@ -34,6 +57,17 @@ mod test_utils;
 /// Defines the current ABI version
 #[allow(clippy::zero_prefixed_literal)]
 pub const ABI_VERSION: u32 = 00_03_00;
+/// Defines the current version of the munlib format
+pub const FORMAT_VERSION: u32 = 10;
+/// Defines the magic number stored in the [`FormatHeader`], the ASCII
+/// characters `MUN` followed by a nul byte in little-endian order.
+pub const FORMAT_MAGIC: u32 = 0x004E554D;
+/// Defines the name for the [`FormatHeader`] global
+pub const FORMAT_HEADER_NAME: &str = "mun_format_header";
+/// Defines the name for the [`CompilerInfo`] global
+pub const COMPILER_INFO_NAME: &str = "mun_compiler_info";
+/// Defines the name for the [`CallTrace`] global
+pub const CALL_TRACE_NAME: &str = "mun_call_trace";
 /// Defines the name for the `get_info` function
 pub const GET_INFO_FN_NAME: &str = "get_info";
 /// Defines the name for the `get_version` function
@@ -1,6 +1,6 @@
 use std::{ffi::CStr, os::raw::c_char, slice, str};
 
-use crate::{FunctionDefinition, TypeDefinition};
+use crate::{ConstDefinition, FunctionDefinition, StaticDefinition, TypeDefinition};
 
 /// Represents a module declaration.
 #[repr(C)]
@@ -11,10 +11,22 @@ pub struct ModuleInfo<'a> {
     pub(crate) functions: *const FunctionDefinition<'a>,
     /// Module types
     pub(crate) types: *const TypeDefinition<'a>,
+    /// Function-local statics of the module
+    pub(crate) statics: *const StaticDefinition<'a>,
+    /// Public constants of the module
+    pub(crate) constants: *const ConstDefinition<'a>,
+    /// Names of the `#[init]` functions of the module
+    pub(crate) init_functions: *const *const c_char,
     /// Number of module functions
     pub num_functions: u32,
     /// Number of module types
     pub num_types: u32,
+    /// Number of function-local statics
+    pub num_statics: u32,
+    /// Number of public constants
+    pub num_constants: u32,
+    /// Number of `#[init]` functions
+    pub num_init_functions: u32,
 }
 
 impl<'a> ModuleInfo<'a> {
@@ -55,6 +67,38 @@ impl<'a> ModuleInfo<'a> {
             unsafe { slice::from_raw_parts(self.types, self.num_types as usize) }
         }
     }
+
+    /// Returns the module's function-local statics.
+    pub fn statics(&self) -> &[StaticDefinition<'a>] {
+        if self.num_statics == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.statics, self.num_statics as usize) }
+        }
+    }
+
+    /// Returns the module's public constants.
+    pub fn constants(&self) -> &[ConstDefinition<'a>] {
+        if self.num_constants == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.constants, self.num_constants as usize) }
+        }
+    }
+
+    /// Returns the names of the module's `#[init]` functions, ordered by name.
+    /// The functions themselves are part of [`ModuleInfo::functions`].
+    pub fn init_functions(&self) -> impl Iterator<Item = &str> {
+        let init_functions = if self.num_init_functions == 0 {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.init_functions, self.num_init_functions as usize) }
+        };
+
+        init_functions
+            .iter()
+            .map(|n| unsafe { str::from_utf8_unchecked(CStr::from_ptr(*n).to_bytes()) })
+    }
 }
 
 unsafe impl<'a> Send for ModuleInfo<'a> {}
@@ -68,10 +112,13 @@ impl<'a> serde::Serialize for ModuleInfo<'a> {
     {
         use serde::ser::SerializeStruct;
 
-        let mut s = serializer.serialize_struct("ModuleInfo", 3)?;
+        let mut s = serializer.serialize_struct("ModuleInfo", 6)?;
         s.serialize_field("path", self.path())?;
         s.serialize_field("functions", self.functions())?;
         s.serialize_field("types", self.types())?;
+        s.serialize_field("statics", self.statics())?;
+        s.serialize_field("constants", self.constants())?;
+        s.serialize_field("init_functions", &self.init_functions().collect::<Vec<_>>())?;
         s.end()
     }
 }
@@ -82,8 +129,9 @@ mod tests {
 
     use crate::{
         test_utils::{
-            fake_fn_prototype, fake_module_info, fake_struct_definition, fake_type_definition,
-            FAKE_FN_NAME, FAKE_MODULE_PATH, FAKE_STRUCT_NAME,
+            fake_const_definition, fake_fn_prototype, fake_module_info, fake_static_definition,
+            fake_struct_definition, fake_type_definition, FAKE_CONST_NAME, FAKE_FN_NAME,
+            FAKE_MODULE_PATH, FAKE_STATIC_NAME, FAKE_STRUCT_NAME,
         },
         type_id::HasStaticTypeId,
         FunctionDefinition, StructMemoryKind, TypeDefinition, TypeDefinitionData,
@@ -92,7 +140,7 @@ mod tests {
     #[test]
     fn test_module_info_path() {
         let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
-        let module = fake_module_info(&module_path, &[], &[]);
+        let module = fake_module_info(&module_path, &[], &[], &[], &[], &[]);
 
         assert_eq!(module.path(), FAKE_MODULE_PATH);
     }
@@ -102,7 +150,7 @@ mod tests {
         let functions = &[];
         let types = &[];
         let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
-        let module = fake_module_info(&module_path, functions, types);
+        let module = fake_module_info(&module_path, functions, types, &[], &[], &[]);
 
         assert_eq!(module.functions().len(), functions.len());
         assert_eq!(module.types().len(), types.len());
@@ -117,6 +165,7 @@ mod tests {
         let fn_info = FunctionDefinition {
             prototype: fn_prototype,
             fn_ptr: ptr::null(),
+            attributes: ptr::null(),
         };
         let functions = &[fn_info];
 
@@ -128,7 +177,7 @@ mod tests {
         let types = [type_info];
 
         let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
-        let module = fake_module_info(&module_path, functions, &types);
+        let module = fake_module_info(&module_path, functions, &types, &[], &[], &[]);
 
         let result_functions = module.functions();
         assert_eq!(result_functions.len(), functions.len());
@@ -156,4 +205,60 @@ mod tests {
             assert_eq!(lhs.field_types(), rhs.field_types());
         }
     }
+
+    #[test]
+    fn test_module_info_statics() {
+        let static_name = CString::new(FAKE_STATIC_NAME).expect("Invalid fake static name.");
+        let mut value = 0i64;
+        let statics = [fake_static_definition(
+            &static_name,
+            i64::type_id().clone(),
+            &mut value as *mut i64 as *mut _,
+            ptr::null_mut(),
+        )];
+
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let module = fake_module_info(&module_path, &[], &[], &statics, &[], &[]);
+
+        let result_statics = module.statics();
+        assert_eq!(result_statics.len(), statics.len());
+        for (lhs, rhs) in result_statics.iter().zip(statics.iter()) {
+            assert_eq!(lhs.name(), rhs.name());
+            assert_eq!(lhs.type_id, rhs.type_id);
+            assert_eq!(lhs.value_ptr, rhs.value_ptr);
+        }
+    }
+
+    #[test]
+    fn test_module_info_constants() {
+        let const_name = CString::new(FAKE_CONST_NAME).expect("Invalid fake const name.");
+        let value = 1.5f64;
+        let constants = [fake_const_definition(
+            &const_name,
+            f64::type_id().clone(),
+            &value as *const f64 as *const _,
+        )];
+
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let module = fake_module_info(&module_path, &[], &[], &[], &constants, &[]);
+
+        let result_constants = module.constants();
+        assert_eq!(result_constants.len(), constants.len());
+        for (lhs, rhs) in result_constants.iter().zip(constants.iter()) {
+            assert_eq!(lhs.name(), rhs.name());
+            assert_eq!(lhs.type_id, rhs.type_id);
+            assert_eq!(lhs.value::<f64>(), Some(1.5));
+        }
+    }
+
+    #[test]
+    fn test_module_info_init_functions() {
+        let fn_name = CString::new(FAKE_FN_NAME).expect("Invalid fake fn name.");
+        let init_functions = [fn_name.as_ptr()];
+
+        let module_path = CString::new(FAKE_MODULE_PATH).expect("Invalid fake module path.");
+        let module = fake_module_info(&module_path, &[], &[], &[], &[], &init_functions);
+
+        assert_eq!(module.init_functions().collect::<Vec<_>>(), [FAKE_FN_NAME]);
+    }
 }
@@ -0,0 +1,86 @@
+use std::{
+    ffi::{c_void, CStr},
+    os::raw::c_char,
+    str,
+};
+
+use crate::type_id::TypeId;
+
+/// The initialization state of a static that has not been initialized yet.
+pub const STATIC_UNINITIALIZED: u8 = 0;
+/// The initialization state of a static whose initializer is being evaluated.
+pub const STATIC_INITIALIZING: u8 = 1;
+/// The initialization state of a static that has been initialized.
+pub const STATIC_INITIALIZED: u8 = 2;
+
+/// Represents a function-local static. A static definition contains the name
+/// and type of the static, and pointers to its storage.
+#[repr(C)]
+#[derive(Clone)]
+pub struct StaticDefinition<'a> {
+    /// The name of the static, prefixed with the name of the function that
+    /// declares it, e.g. `counter::N`
+    pub(crate) name: *const c_char,
+    /// The type of the static
+    pub type_id: TypeId<'a>,
+    /// Pointer to the value of the static
+    pub value_ptr: *mut c_void,
+    /// Pointer to the initialization state of the static, or null if the
+    /// static is initialized with a constant. The state is one of
+    /// [`STATIC_UNINITIALIZED`], [`STATIC_INITIALIZING`] or
+    /// [`STATIC_INITIALIZED`].
+    pub init_state_ptr: *mut u8,
+}
+
+impl<'a> StaticDefinition<'a> {
+    /// Returns the static's name.
+    pub fn name(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(CStr::from_ptr(self.name).to_bytes()) }
+    }
+}
+
+unsafe impl<'a> Send for StaticDefinition<'a> {}
+unsafe impl<'a> Sync for StaticDefinition<'a> {}
+
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for StaticDefinition<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("StaticDefinition", 3)?;
+        s.serialize_field("name", self.name())?;
+        s.serialize_field("type_id", &self.type_id)?;
+        s.serialize_field("has_init_guard", &!self.init_state_ptr.is_null())?;
+        s.skip_field("value_ptr")?;
+        s.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::CString, ptr};
+
+    use crate::{
+        test_utils::{fake_static_definition, FAKE_STATIC_NAME},
+        type_id::HasStaticTypeId,
+    };
+
+    #[test]
+    fn test_static_definition_name() {
+        let name = CString::new(FAKE_STATIC_NAME).expect("Invalid fake static name.");
+        let mut value = 0i64;
+        let definition = fake_static_definition(
+            &name,
+            i64::type_id().clone(),
+            &mut value as *mut i64 as *mut _,
+            ptr::null_mut(),
+        );
+
+        assert_eq!(definition.name(), FAKE_STATIC_NAME);
+        assert_eq!(&definition.type_id, i64::type_id());
+        assert!(definition.init_state_ptr.is_null());
+    }
+}
@@ -1,22 +1,30 @@
 use std::{
-    ffi::{self, CStr},
+    ffi::{self, c_void, CStr},
     os::raw::c_char,
+    ptr,
 };
 
 use crate::{
     type_id::{HasStaticTypeId, TypeId},
-    AssemblyInfo, DispatchTable, FunctionDefinition, FunctionPrototype, FunctionSignature, Guid,
-    ModuleInfo, StructDefinition, StructMemoryKind, TypeDefinition, TypeDefinitionData, TypeLut,
+    AssemblyInfo, BuildInfo, CompilerInfo, ConstDefinition, DispatchTable, FunctionDefinition,
+    FunctionPrototype, FunctionSignature, Guid, ModuleInfo, OptimizationLevel, StaticDefinition,
+    StructDefinition, StructMemoryKind, TypeDefinition, TypeDefinitionData, TypeLut,
 };
 
 pub(crate) const FAKE_TYPE_GUID: Guid =
     Guid([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]);
 pub(crate) const FAKE_TYPE_ID: TypeId<'static> = TypeId::Concrete(FAKE_TYPE_GUID);
+pub(crate) const FAKE_CONST_NAME: &str = "path::to::module::CONST_NAME";
+pub(crate) const FAKE_COMPILER_VERSION: &str = "0.1.0";
 pub(crate) const FAKE_DEPENDENCY: &str = "path/to/dependency.munlib";
+pub(crate) const FAKE_ARG_NAME: &str = "arg_name";
 pub(crate) const FAKE_FIELD_NAME: &str = "field_name";
 pub(crate) const FAKE_FN_NAME: &str = "fn_name";
+pub(crate) const FAKE_LLVM_VERSION: &str = "14.0";
 pub(crate) const FAKE_MODULE_PATH: &str = "path::to::module";
+pub(crate) const FAKE_STATIC_NAME: &str = "fn_name::STATIC_NAME";
 pub(crate) const FAKE_STRUCT_NAME: &str = "StructName";
+pub(crate) const FAKE_TARGET_TRIPLE: &str = "x86_64-unknown-linux-gnu";
 pub(crate) const FAKE_TYPE_NAME: &str = "TypeName";
 
 pub(crate) fn fake_assembly_info<'a>(
@@ -24,6 +32,7 @@ pub(crate) fn fake_assembly_info<'a>(
     dispatch_table: DispatchTable<'a>,
     type_lut: TypeLut<'a>,
     dependencies: &[*const c_char],
+    build_info: BuildInfo,
 ) -> AssemblyInfo<'a> {
     AssemblyInfo {
         symbols,
@@ -31,6 +40,26 @@ pub(crate) fn fake_assembly_info<'a>(
         type_lut,
         dependencies: dependencies.as_ptr(),
         num_dependencies: dependencies.len() as u32,
+        build_info,
+    }
+}
+
+pub(crate) fn fake_build_info(
+    target_triple: &CStr,
+    optimization_level: OptimizationLevel,
+    has_debug_info: bool,
+) -> BuildInfo {
+    BuildInfo {
+        target_triple: target_triple.as_ptr(),
+        optimization_level,
+        has_debug_info,
+    }
+}
+
+pub(crate) fn fake_compiler_info(compiler_version: &CStr, llvm_version: &CStr) -> CompilerInfo {
+    CompilerInfo {
+        compiler_version: compiler_version.as_ptr(),
+        llvm_version: llvm_version.as_ptr(),
     }
 }
 
@@ -68,6 +97,7 @@ pub(crate) fn fake_fn_signature<'a>(
 ) -> FunctionSignature<'a> {
     FunctionSignature {
         arg_types: arg_types.as_ptr(),
+        arg_names: ptr::null(),
         return_type: return_type.unwrap_or_else(|| <()>::type_id().clone()),
         num_arg_types: arg_types.len() as u16,
     }
@@ -88,6 +118,9 @@ pub(crate) fn fake_module_info<'a>(
     path: &CStr,
     functions: &[FunctionDefinition<'a>],
     types: &[TypeDefinition<'a>],
+    statics: &[StaticDefinition<'a>],
+    constants: &[ConstDefinition<'a>],
+    init_functions: &[*const c_char],
 ) -> ModuleInfo<'a> {
     ModuleInfo {
         path: path.as_ptr(),
@@ -95,6 +128,38 @@ pub(crate) fn fake_module_info<'a>(
         num_functions: functions.len() as u32,
         types: types.as_ptr(),
         num_types: types.len() as u32,
+        statics: statics.as_ptr(),
+        num_statics: statics.len() as u32,
+        constants: constants.as_ptr(),
+        num_constants: constants.len() as u32,
+        init_functions: init_functions.as_ptr(),
+        num_init_functions: init_functions.len() as u32,
+    }
+}
+
+pub(crate) fn fake_const_definition<'a>(
+    name: &CStr,
+    type_id: TypeId<'a>,
+    value_ptr: *const c_void,
+) -> ConstDefinition<'a> {
+    ConstDefinition {
+        name: name.as_ptr(),
+        type_id,
+        value_ptr,
+    }
+}
+
+pub(crate) fn fake_static_definition<'a>(
+    name: &CStr,
+    type_id: TypeId<'a>,
+    value_ptr: *mut c_void,
+    init_state_ptr: *mut u8,
+) -> StaticDefinition<'a> {
+    StaticDefinition {
+        name: name.as_ptr(),
+        type_id,
+        value_ptr,
+        init_state_ptr,
     }
 }
 
@@ -21,6 +21,9 @@ pub enum TypeId<'a> {
 
     /// Represents an array of a specific type
     Array(ArrayTypeId<'a>),
+
+    /// Represents a slice of a specific type
+    Slice(SliceTypeId<'a>),
 }
 
 /// Represents a pointer to another type.
@@ -44,6 +47,16 @@ pub struct ArrayTypeId<'a> {
     pub element: &'a TypeId<'a>,
 }
 
+/// Represents a slice of a specific type. A slice borrows a contiguous
+/// sequence of elements and is passed as a pointer and a length.
+#[repr(C)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SliceTypeId<'a> {
+    /// The element type of the slice
+    pub element: &'a TypeId<'a>,
+}
+
 unsafe impl<'a> Send for TypeId<'a> {}
 
 unsafe impl<'a> Sync for TypeId<'a> {}
@@ -66,6 +79,7 @@ impl<'a> fmt::Display for TypeId<'a> {
             TypeId::Concrete(guid) => guid.fmt(f),
             TypeId::Pointer(pointer) => pointer.fmt(f),
             TypeId::Array(array) => array.fmt(f),
+            TypeId::Slice(slice) => slice.fmt(f),
         }
     }
 }
@@ -87,6 +101,12 @@ impl<'a> fmt::Display for ArrayTypeId<'a> {
     }
 }
 
+impl<'a> fmt::Display for SliceTypeId<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "&[{}]", &self.element)
+    }
+}
+
 /// A trait that defines that for a type we can statically return a `TypeId`.
 pub trait HasStaticTypeId {
     /// Returns a reference to the [`TypeInfo`] for the type
@@ -123,7 +143,7 @@ impl<T: HasStaticTypeId + 'static> HasStaticTypeId for *mut T {
 
 #[cfg(test)]
 mod test {
-    use crate::{ArrayTypeId, HasStaticTypeId, PointerTypeId, PrimitiveType, TypeId};
+    use crate::{ArrayTypeId, HasStaticTypeId, PointerTypeId, PrimitiveType, SliceTypeId, TypeId};
 
     #[test]
     fn display() {
@@ -159,5 +179,13 @@ mod test {
             .to_string(),
             format!("[{}]", i32::guid())
         );
+
+        assert_eq!(
+            TypeId::Slice(SliceTypeId {
+                element: i32_type_id
+            })
+            .to_string(),
+            format!("&[{}]", i32::guid())
+        );
     }
 }
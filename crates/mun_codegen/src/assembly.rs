@@ -6,9 +6,12 @@ use inkwell::context::Context;
 use tempfile::NamedTempFile;
 
 use crate::{
-    code_gen::{AssemblyBuilder, CodeGenContext, ObjectFile},
+    code_gen::{
+        split_debug_info, write_relocations, AssemblyBuilder, CodeGenContext, CodeGenerationError,
+        ObjectFile,
+    },
     db::CodeGenDatabase,
-    ModuleGroupId,
+    DebugInfo, ModuleGroupId, ProfileGuidedOptimization,
 };
 
 /// An `Assembly` is a successfully linked module of code from one or more
@@ -16,6 +19,7 @@ use crate::{
 pub struct Assembly<'db, 'ink, 'ctx> {
     code_gen: &'ctx CodeGenContext<'db, 'ink>,
     module: inkwell::module::Module<'ink>,
+    dispatch_table: Vec<String>,
 }
 
 impl<'db, 'ink, 'ctx> Assembly<'db, 'ink, 'ctx> {
@@ -23,8 +27,19 @@ impl<'db, 'ink, 'ctx> Assembly<'db, 'ink, 'ctx> {
     pub fn new(
         code_gen: &'ctx CodeGenContext<'db, 'ink>,
         module: inkwell::module::Module<'ink>,
+        dispatch_table: Vec<String>,
     ) -> Self {
-        Self { code_gen, module }
+        Self {
+            code_gen,
+            module,
+            dispatch_table,
+        }
+    }
+
+    /// Returns the names of the functions in the assembly's dispatch table, in
+    /// order.
+    pub fn dispatch_table(&self) -> &[String] {
+        &self.dispatch_table
     }
 
     /// Tries to convert the assembly into an `ObjectFile`.
@@ -49,18 +64,36 @@ fn build_assembly<'db, 'ink, 'ctx>(
     db: &'db dyn CodeGenDatabase,
     code_gen: &'ctx CodeGenContext<'db, 'ink>,
     module_group_id: ModuleGroupId,
-) -> Assembly<'db, 'ink, 'ctx> {
+) -> Result<Assembly<'db, 'ink, 'ctx>, anyhow::Error> {
     // Setup the code generation context
     let module_partition = db.module_partition();
 
     let module_builder = AssemblyBuilder::new(code_gen, &module_partition, module_group_id);
-    module_builder.build().expect("unable to create assembly")
+    module_builder
+        .build()
+        .map_err(|e| e.context("unable to create assembly"))
+}
+
+/// An error that prevented an assembly from being built. Only the message of
+/// the error is kept, such that the result can be cached by the database.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("{0}")]
+pub struct AssemblyError(String);
+
+impl From<anyhow::Error> for AssemblyError {
+    fn from(error: anyhow::Error) -> Self {
+        Self(format!("{error:#}"))
+    }
 }
 
 /// A `TargetAssembly` is a reference to a Mun library stored on disk.
 #[derive(Debug)]
 pub struct TargetAssembly {
     file: NamedTempFile,
+    relocations: Option<NamedTempFile>,
+    /// The split off debug information, or the error that prevented splitting
+    /// it off.
+    debug_info: Result<Option<NamedTempFile>, CodeGenerationError>,
 }
 
 impl PartialEq for TargetAssembly {
@@ -73,42 +106,130 @@ impl Eq for TargetAssembly {}
 
 impl TargetAssembly {
     pub const EXTENSION: &'static str = "munlib";
+    pub const RELOCATIONS_EXTENSION: &'static str = "relocs";
+    /// The extension of the file that holds the split off debug information
+    /// of the assembly. The assembly refers to this file by name, so it must
+    /// be stored next to the assembly.
+    pub const DEBUG_INFO_EXTENSION: &'static str = "debug";
 
     /// Returns the current location of the assembly
     pub fn path(&self) -> &Path {
         self.file.path()
     }
 
+    /// Returns the current location of the assembly's relocations, if they were
+    /// emitted
+    pub fn relocations_path(&self) -> Option<&Path> {
+        self.relocations.as_ref().map(NamedTempFile::path)
+    }
+
     /// Copies the assembly to the specified location
     pub fn copy_to<P: AsRef<Path>>(&self, destination: P) -> Result<(), std::io::Error> {
         std::fs::copy(self.path(), destination).map(|_| ())
     }
+
+    /// Returns the current location of the assembly's split debug information,
+    /// if it was split off
+    pub fn debug_info_path(&self) -> Option<&Path> {
+        match &self.debug_info {
+            Ok(debug_info) => debug_info.as_ref().map(NamedTempFile::path),
+            Err(_) => None,
+        }
+    }
+
+    /// Copies the assembly's split debug information to the specified
+    /// location, if it was split off. Returns the error that occurred while
+    /// splitting off the debug information, if any.
+    pub fn copy_debug_info_to<P: AsRef<Path>>(&self, destination: P) -> Result<(), anyhow::Error> {
+        match &self.debug_info {
+            Ok(Some(file)) => std::fs::copy(file.path(), destination)
+                .map(|_| ())
+                .map_err(Into::into),
+            Ok(None) => Ok(()),
+            Err(e) => Err(anyhow!("could not split debug info: {e}")),
+        }
+    }
+
+    /// Copies the assembly's relocations to the specified location, if they
+    /// were emitted
+    pub fn copy_relocations_to<P: AsRef<Path>>(
+        &self,
+        destination: P,
+    ) -> Result<(), std::io::Error> {
+        match self.relocations_path() {
+            Some(path) => std::fs::copy(path, destination).map(|_| ()),
+            None => Ok(()),
+        }
+    }
 }
 
 /// Builds an assembly for the specified module.
 pub(crate) fn build_target_assembly(
     db: &dyn CodeGenDatabase,
     module_group: ModuleGroupId,
-) -> Arc<TargetAssembly> {
+) -> Result<Arc<TargetAssembly>, AssemblyError> {
     // Setup the code generation context
     let inkwell_context = Context::create();
     let code_gen_context = CodeGenContext::new(&inkwell_context, db);
 
     // Build an assembly for the module
-    let assembly = build_assembly(db, &code_gen_context, module_group);
+    let assembly = build_assembly(db, &code_gen_context, module_group)?;
 
     // Convert the assembly into an object file
+    let dispatch_table = assembly.dispatch_table().to_vec();
     let obj_file = assembly
         .into_object_file()
-        .expect("unable to create object file");
+        .map_err(|e| e.context("unable to create object file"))?;
+
+    // Write the relocations of the object file to a sidecar file
+    let relocations = if db.emit_relocations() {
+        let relocations = obj_file
+            .relocations(&dispatch_table, &db.entry_symbols())
+            .map_err(|e| e.context("unable to read relocations"))?;
+        let file = NamedTempFile::new().expect("could not create temp file for relocations");
+        write_relocations(&relocations, file.as_file()).expect("could not write relocations");
+        Some(file)
+    } else {
+        None
+    };
 
     // Construct a temporary file for the assembly
     let file = NamedTempFile::new().expect("could not create temp file for shared object");
 
-    // Translate the object file into a shared object
+    // Link the object file into the requested output kind
+    let profiler = matches!(db.pgo(), Some(ProfileGuidedOptimization::Generate(_)));
     obj_file
-        .into_shared_object(file.path())
-        .expect("could not link object file");
+        .link(
+            file.path(),
+            db.linker_output(),
+            &db.entry_symbols(),
+            db.call_trace_ring().is_some(),
+            db.sanitizer(),
+            profiler,
+        )
+        .map_err(|e| e.context("could not link object file"))?;
+
+    // Move the debug information into a separate file that the assembly refers to.
+    // Splitting fails for targets that do not produce ELF binaries, which is
+    // reported when the debug information is written.
+    let debug_info = (db.split_debug_info() && db.debug_info() != DebugInfo::None)
+        .then(|| {
+            let module_partition = db.module_partition();
+            let debug_file_name = module_partition[module_group]
+                .relative_file_path()
+                .with_extension(TargetAssembly::DEBUG_INFO_EXTENSION);
+            let debug_file =
+                NamedTempFile::new().expect("could not create temp file for debug info");
+            split_debug_info(
+                file.path(),
+                debug_file.path(),
+                debug_file_name
+                    .file_name()
+                    .expect("module group must have a filename"),
+            )
+            .map(|()| debug_file)
+        })
+        .transpose();
 
     let target = db.target();
     if target.options.is_like_osx {
@@ -118,7 +239,11 @@ pub(crate) fn build_target_assembly(
             .expect("Failed to sign shared object");
     }
 
-    Arc::new(TargetAssembly { file })
+    Ok(Arc::new(TargetAssembly {
+        file,
+        relocations,
+        debug_info,
+    }))
 }
 
 /// An `AssemblyIr` is a reference to an IR file stored on disk.
@@ -153,13 +278,13 @@ impl AssemblyIr {
 pub(crate) fn build_assembly_ir(
     db: &dyn CodeGenDatabase,
     module_group: ModuleGroupId,
-) -> Arc<AssemblyIr> {
+) -> Result<Arc<AssemblyIr>, AssemblyError> {
     // Setup the code generation context
     let inkwell_context = Context::create();
     let code_gen_context = CodeGenContext::new(&inkwell_context, db);
 
     // Build an assembly for the module
-    let assembly = build_assembly(db, &code_gen_context, module_group);
+    let assembly = build_assembly(db, &code_gen_context, module_group)?;
 
     // Construct a temporary file for the assembly
     let file = NamedTempFile::new().expect("could not create temp file for shared object");
@@ -169,5 +294,5 @@ pub(crate) fn build_assembly_ir(
         .write_ir_to_file(file.path())
         .expect("could not write to temp file");
 
-    Arc::new(AssemblyIr { file })
+    Ok(Arc::new(AssemblyIr { file }))
 }
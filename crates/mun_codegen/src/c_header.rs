@@ -0,0 +1,347 @@
+use std::{collections::HashSet, fmt::Write as _, io::Write, path::Path, sync::Arc};
+
+use mun_abi as abi;
+use mun_hir::{
+    FloatBitness, HasVisibility, HirDatabase, HirDisplay, IntBitness, ModuleDef, Signedness,
+    Struct, StructMemoryKind, Ty, TyKind,
+};
+use tempfile::NamedTempFile;
+
+use crate::{db::CodeGenDatabase, mangle, ModuleGroup, ModuleGroupId};
+
+/// A `CHeader` is a reference to a C header file stored on disk that declares
+/// the C interface of an assembly.
+#[derive(Debug)]
+pub struct CHeader {
+    file: NamedTempFile,
+}
+
+impl PartialEq for CHeader {
+    fn eq(&self, other: &Self) -> bool {
+        self.path().eq(other.path())
+    }
+}
+
+impl Eq for CHeader {}
+
+impl CHeader {
+    pub const EXTENSION: &'static str = "h";
+
+    /// Returns the current location of the header file.
+    pub fn path(&self) -> &Path {
+        self.file.path()
+    }
+
+    /// Copies the header file to the specified location
+    pub fn copy_to<P: AsRef<Path>>(&self, destination: P) -> Result<(), std::io::Error> {
+        std::fs::copy(self.path(), destination).map(|_| ())
+    }
+}
+
+/// Builds a C header file for the specified module group.
+pub(crate) fn build_c_header(
+    db: &dyn CodeGenDatabase,
+    module_group: ModuleGroupId,
+) -> Arc<CHeader> {
+    let module_partition = db.module_partition();
+    let text = gen_c_header(
+        db.upcast(),
+        &module_partition[module_group],
+        &db.entry_symbols(),
+    );
+
+    let mut file = NamedTempFile::new().expect("could not create temp file for C header");
+    file.write_all(text.as_bytes())
+        .expect("could not write to temp file");
+
+    Arc::new(CHeader { file })
+}
+
+/// Generates the contents of a C header file that defines the value structs
+/// and declares the `#[no_mangle]` functions of the module group under the
+/// names that the `entry_symbols` export them with.
+pub(crate) fn gen_c_header(
+    db: &dyn HirDatabase,
+    module_group: &ModuleGroup,
+    entry_symbols: &abi::EntrySymbols,
+) -> String {
+    let mut builder = CHeaderBuilder {
+        db,
+        definitions: String::new(),
+        defined: HashSet::new(),
+    };
+
+    let declarations: Vec<ModuleDef> = module_group
+        .iter()
+        .flat_map(|module| module.declarations(db))
+        .collect();
+
+    for def in &declarations {
+        if let ModuleDef::Struct(s) = def {
+            if s.visibility(db).is_externally_visible() {
+                builder.define_struct(*s);
+            }
+        }
+    }
+
+    let mut functions = String::new();
+    for def in &declarations {
+        let ModuleDef::Function(f) = def else {
+            continue;
+        };
+        if f.is_extern(db) || !f.is_no_mangle(db) {
+            continue;
+        }
+
+        let name = entry_symbols.versioned(&mangle::function_symbol(db, *f));
+        let fn_sig = f.ty(db).callable_sig(db).unwrap();
+        if !fn_sig.marshallable(db) {
+            writeln!(
+                functions,
+                "/* `{name}` is not declared: value structs are passed by value using Mun's \
+                 calling convention, which does not match the C ABI. */"
+            )
+            .unwrap();
+            continue;
+        }
+
+        let params = f
+            .params(db)
+            .iter()
+            .enumerate()
+            .map(|(idx, param)| {
+                let name = param
+                    .name(db)
+                    .map_or_else(|| format!("arg{idx}"), |name| name.to_string());
+                builder.declare(param.ty(), &name)
+            })
+            .collect::<Option<Vec<_>>>();
+        let prototype = params.and_then(|params| {
+            let params = if params.is_empty() {
+                String::from("void")
+            } else {
+                params.join(", ")
+            };
+            builder.declare(fn_sig.ret(), &format!("{name}({params})"))
+        });
+
+        match prototype {
+            Some(prototype) => writeln!(functions, "{prototype};").unwrap(),
+            None => writeln!(
+                functions,
+                "/* `{name}` is not declared: its signature contains types that cannot be \
+                 represented in C. */"
+            )
+            .unwrap(),
+        }
+    }
+
+    let guard = format!("MUN_{}_H_", c_identifier(&module_group.name).to_uppercase());
+    let mut header = String::new();
+    writeln!(
+        header,
+        "/* Generated by the Mun compiler from the `{}` assembly. Do not edit. */",
+        module_group.name
+    )
+    .unwrap();
+    header.push_str("/*\n");
+    match entry_symbols.version() {
+        Some(version) => writeln!(
+            header,
+            " * The declared functions are exported by the assembly under their exact name,\n \
+             * which is suffixed with the symbol version `_v{version}`."
+        )
+        .unwrap(),
+        None => header.push_str(
+            " * The declared functions are exported by the assembly under their exact name.\n",
+        ),
+    }
+    header.push_str(
+        " * Functions that call other Mun functions or allocate memory require the
+ * assembly to be loaded by the Mun runtime.
+ */
+
+",
+    );
+    writeln!(header, "#ifndef {guard}\n#define {guard}\n").unwrap();
+    header.push_str(
+        "#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+#define MUN_STATIC_ASSERT static_assert
+#define MUN_ALIGNOF alignof
+extern \"C\" {
+#else
+#define MUN_STATIC_ASSERT _Static_assert
+#define MUN_ALIGNOF _Alignof
+#endif
+
+",
+    );
+    header.push_str(&builder.definitions);
+    header.push_str(&functions);
+    if !functions.is_empty() {
+        header.push('\n');
+    }
+    writeln!(
+        header,
+        "#ifdef __cplusplus\n}} /* extern \"C\" */\n#endif\n\n#endif /* {guard} */"
+    )
+    .unwrap();
+    header
+}
+
+/// Collects the C definitions of structs in the order in which they have to
+/// be defined.
+struct CHeaderBuilder<'db> {
+    db: &'db dyn HirDatabase,
+    /// The definitions of all structs, every struct is defined before it is
+    /// used by value.
+    definitions: String,
+    /// The structs that have been defined
+    defined: HashSet<Struct>,
+}
+
+impl<'db> CHeaderBuilder<'db> {
+    /// Returns a C declaration of `declarator` with the type `ty`, e.g.
+    /// `int64_t a` or `double (*f)(double)`, or `None` if the type cannot be
+    /// represented in C. Structs that are used by the type are defined first.
+    fn declare(&mut self, ty: &Ty, declarator: &str) -> Option<String> {
+        let declaration = match ty.interned() {
+            TyKind::Bool => format!("bool {declarator}"),
+            TyKind::Int(int_ty) => {
+                let c_ty = match (int_ty.signedness, int_ty.bitness) {
+                    (Signedness::Signed, IntBitness::X8) => "int8_t",
+                    (Signedness::Signed, IntBitness::X16) => "int16_t",
+                    (Signedness::Signed, IntBitness::X32) => "int32_t",
+                    (Signedness::Signed, IntBitness::X64) => "int64_t",
+                    (Signedness::Signed, IntBitness::X128) => "__int128",
+                    (Signedness::Signed, IntBitness::Xsize) => "intptr_t",
+                    (Signedness::Unsigned, IntBitness::X8) => "uint8_t",
+                    (Signedness::Unsigned, IntBitness::X16) => "uint16_t",
+                    (Signedness::Unsigned, IntBitness::X32) => "uint32_t",
+                    (Signedness::Unsigned, IntBitness::X64) => "uint64_t",
+                    (Signedness::Unsigned, IntBitness::X128) => "unsigned __int128",
+                    (Signedness::Unsigned, IntBitness::Xsize) => "uintptr_t",
+                };
+                format!("{c_ty} {declarator}")
+            }
+            TyKind::Float(float_ty) => match float_ty.bitness {
+                FloatBitness::X32 => format!("float {declarator}"),
+                FloatBitness::X64 => format!("double {declarator}"),
+            },
+            TyKind::Struct(s) => {
+                self.define_struct(*s)?;
+                format!("{} {declarator}", struct_name(self.db, *s))
+            }
+            TyKind::Array(element_ty) => format!(
+                "void *{declarator} /* [{}]: opaque handle to an array owned by the Mun runtime */",
+                element_ty.display(self.db)
+            ),
+            // Mutable references are passed as raw pointers that are not managed by the
+            // garbage collector
+            TyKind::Ref(inner_ty) => return self.declare(inner_ty, &format!("*{declarator}")),
+            TyKind::FnPtr(fn_sig) => {
+                let params = fn_sig
+                    .params()
+                    .iter()
+                    .map(|ty| self.declare(ty, ""))
+                    .collect::<Option<Vec<_>>>()?;
+                let params = if params.is_empty() {
+                    String::from("void")
+                } else {
+                    params.join(", ")
+                };
+                return self.declare(fn_sig.ret(), &format!("(*{declarator})({params})"));
+            }
+            TyKind::Tuple(0, _) | TyKind::Never => format!("void {declarator}"),
+            TyKind::Tuple(..)
+            | TyKind::Slice(_)
+            | TyKind::InferenceVar(_)
+            | TyKind::TypeAlias(_)
+            | TyKind::FnDef(..)
+            | TyKind::Unknown => return None,
+        };
+        Some(declaration.trim_end().to_owned())
+    }
+
+    /// Defines the struct `s`, and all structs it depends on, if it wasn't
+    /// defined yet. Value structs are defined as C structs with the same
+    /// layout; garbage collected structs are defined as opaque handles.
+    /// Returns `None` if the struct cannot be represented in C.
+    fn define_struct(&mut self, s: Struct) -> Option<()> {
+        if self.defined.contains(&s) {
+            return Some(());
+        }
+
+        let name = struct_name(self.db, s);
+        if s.data(self.db.upcast()).memory_kind == StructMemoryKind::Gc {
+            writeln!(
+                self.definitions,
+                "/* `{}` is garbage collected: values are opaque handles to objects owned by the \
+                 Mun runtime. */\ntypedef void *{name};\n",
+                s.full_name(self.db)
+            )
+            .unwrap();
+            self.defined.insert(s);
+            return Some(());
+        }
+
+        // C does not allow empty structs
+        let layout = s.layout(self.db)?;
+        let fields = s.fields(self.db);
+        if fields.is_empty() {
+            return None;
+        }
+
+        // Mark the struct as defined before its fields are declared, a recursive value
+        // struct has no layout so we never get here for one.
+        self.defined.insert(s);
+        let fields = fields
+            .into_iter()
+            .map(|field| {
+                let name = field.name(self.db).to_string();
+                let name = if name.starts_with(|c: char| c.is_ascii_digit()) {
+                    format!("_{name}")
+                } else {
+                    name
+                };
+                self.declare(&field.ty(self.db), &name)
+            })
+            .collect::<Option<Vec<_>>>();
+        let Some(fields) = fields else {
+            self.defined.remove(&s);
+            return None;
+        };
+
+        writeln!(self.definitions, "typedef struct {name} {{").unwrap();
+        for field in fields {
+            writeln!(self.definitions, "    {field};").unwrap();
+        }
+        writeln!(self.definitions, "}} {name};").unwrap();
+        writeln!(
+            self.definitions,
+            "MUN_STATIC_ASSERT(sizeof({name}) == {}, \"unexpected size of `{name}`\");",
+            layout.size.bytes()
+        )
+        .unwrap();
+        writeln!(
+            self.definitions,
+            "MUN_STATIC_ASSERT(MUN_ALIGNOF({name}) == {}, \"unexpected alignment of `{name}`\");\n",
+            layout.align.bytes()
+        )
+        .unwrap();
+        Some(())
+    }
+}
+
+/// Returns the name of the C type of a struct, e.g. `foo_Bar` for `foo::Bar`.
+fn struct_name(db: &dyn HirDatabase, s: Struct) -> String {
+    c_identifier(&s.full_name(db))
+}
+
+/// Converts a Mun path into a C identifier.
+fn c_identifier(path: &str) -> String {
+    path.replace("::", "_")
+}
@@ -1,26 +1,99 @@
+use std::collections::HashSet;
+
 pub use assembly_builder::AssemblyBuilder;
 pub use context::CodeGenContext;
 pub use error::CodeGenerationError;
+pub use function_timings::{FunctionTiming, FunctionTimings, FunctionTimingsRef};
+pub(crate) use function_timings::{Phase, PhaseTimer};
 use inkwell::{
+    attributes::{Attribute, AttributeLoc},
     module::Module,
     passes::{PassManager, PassManagerBuilder},
+    values::FunctionValue,
     OptimizationLevel,
 };
 pub(crate) use object_file::ObjectFile;
+pub use pass_hook::{PassHook, PassHookRef};
+pub(crate) use relocations::write_relocations;
+pub use relocations::{Relocation, RelocationTarget};
+pub(crate) use split_debug_info::split_debug_info;
+pub use split_debug_info::supports_split_debug_info;
 
 mod assembly_builder;
 mod context;
 mod error;
+mod function_timings;
 mod object_file;
+mod pass_hook;
+mod relocations;
+mod split_debug_info;
 pub mod symbols;
 
-/// Optimizes the specified LLVM `Module` using the default passes for the given
-/// `OptimizationLevel`.
-fn optimize_module(module: &Module<'_>, optimization_lvl: OptimizationLevel) {
+/// Constructs the `PassManagerBuilder` that configures both the function and
+/// the module passes, such that they agree on the optimization level and on
+/// whether code size is minimized.
+pub(crate) fn create_pass_builder(
+    optimization_lvl: OptimizationLevel,
+    minimize_size: bool,
+) -> PassManagerBuilder {
     let pass_builder = PassManagerBuilder::create();
     pass_builder.set_optimization_level(optimization_lvl);
+    if minimize_size {
+        // Like `-Oz`: no inliner is set, so only `alwaysinline` functions are inlined
+        // by the module passes.
+        pass_builder.set_size_level(2);
+        pass_builder.set_disable_unroll_loops(true);
+    }
+    pass_builder
+}
+
+/// Marks all functions that are defined in `module` to be optimized for
+/// minimal size, except for the `explicit` functions whose `#[optimize]`
+/// attribute takes precedence. Passes that do not consult the size level of the
+/// pass builder, like those of the code generator, check these attributes
+/// instead.
+pub(crate) fn mark_min_size<'ink>(module: &Module<'ink>, explicit: &HashSet<FunctionValue<'ink>>) {
+    let context = module.get_context();
+    let attrs = ["minsize", "optsize"]
+        .map(|name| context.create_enum_attribute(Attribute::get_named_enum_kind_id(name), 0));
+    for function in module.get_functions() {
+        if function.count_basic_blocks() > 0 && !explicit.contains(&function) {
+            for attr in attrs {
+                function.add_attribute(AttributeLoc::Function, attr);
+            }
+        }
+    }
+}
+
+/// Optimizes the specified LLVM `Module` using the default passes for the given
+/// `OptimizationLevel` and the passes added by the optional `pass_hook`.
+fn optimize_module(
+    module: &Module<'_>,
+    optimization_lvl: OptimizationLevel,
+    minimize_size: bool,
+    pass_hook: Option<&dyn PassHook>,
+) {
+    let pass_builder = create_pass_builder(optimization_lvl, minimize_size);
 
     let module_pass_manager = PassManager::create(());
-    pass_builder.populate_module_pass_manager(&module_pass_manager);
+    let default_pipeline = pass_hook.map_or(true, PassHook::use_default_pipeline);
+    if default_pipeline {
+        pass_builder.populate_module_pass_manager(&module_pass_manager);
+        if minimize_size {
+            module_pass_manager.add_always_inliner_pass();
+        }
+    }
+    if let Some(pass_hook) = pass_hook {
+        pass_hook.add_passes(module, &pass_builder, &module_pass_manager);
+    }
+    log::debug!(
+        "running module passes module={} opt_level={:?} minimize_size={} default_pipeline={} \
+         pass_hook={}",
+        module.get_name().to_string_lossy(),
+        optimization_lvl,
+        minimize_size,
+        default_pipeline,
+        pass_hook.is_some()
+    );
     module_pass_manager.run_on(module);
 }
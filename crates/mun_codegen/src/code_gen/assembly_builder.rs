@@ -1,10 +1,14 @@
+use std::time::Instant;
+
 use inkwell::module::{Linkage, Module};
+use mun_hir::{ModuleDef, OptimizeAttr};
 use rustc_hash::FxHashSet;
 
 use crate::{
     assembly::Assembly,
-    code_gen::{optimize_module, symbols, CodeGenContext, CodeGenerationError},
+    code_gen::{mark_min_size, optimize_module, symbols, CodeGenContext, CodeGenerationError},
     ir::{file::gen_file_ir, file_group::gen_file_group_ir},
+    mangle, pgo, sanitizer,
     value::{IrTypeContext, IrValueContext},
     ModuleGroupId, ModulePartition,
 };
@@ -39,6 +43,29 @@ impl<'db, 'ink, 'ctx, 't> AssemblyBuilder<'db, 'ink, 'ctx, 't> {
     /// Constructs an object file.
     pub fn build(self) -> Result<Assembly<'db, 'ink, 'ctx>, anyhow::Error> {
         let module_group = &self.module_group_partition[self.module_group_id];
+        log::debug!(
+            "building assembly module_group={} modules={}",
+            module_group.name,
+            module_group.iter().count()
+        );
+        // `#[no_mangle]` functions are exported under their own name, which must not
+        // clash with the symbols through which the runtime accesses the assembly.
+        let db = self.code_gen.db;
+        let entry_symbols = &self.code_gen.entry_symbols;
+        if let Some(symbol) = module_group
+            .iter()
+            .flat_map(|module| module.declarations(db))
+            .filter_map(|def| match def {
+                ModuleDef::Function(f) if f.is_no_mangle(db) && !f.is_extern(db) => {
+                    Some(mangle::function_symbol(db, f))
+                }
+                _ => None,
+            })
+            .find(|symbol| entry_symbols.contains(&entry_symbols.versioned(symbol)))
+        {
+            return Err(CodeGenerationError::ReservedSymbol(symbol).into());
+        }
+
         let group_ir = gen_file_group_ir(self.code_gen, module_group);
         let file = gen_file_ir(self.code_gen, &group_ir, module_group);
 
@@ -100,19 +127,134 @@ impl<'db, 'ink, 'ctx, 't> AssemblyBuilder<'db, 'ink, 'ctx, 't> {
             &module_group.name,
             &file.function_definitions,
             &file.type_definitions,
+            &file.static_definitions,
+            &file.const_definitions,
+            &file.traced_functions,
             &group_ir.dispatch_table,
             &group_ir.type_table,
             &self.code_gen.hir_types,
             self.code_gen.optimization_level,
+            self.code_gen.minimize_size,
+            self.code_gen.debug_info,
+            self.code_gen.reflect_arg_names,
+            self.code_gen.reflect_fn_attributes,
+            self.code_gen.call_trace_ring,
+            &self.code_gen.entry_symbols,
             dependencies,
         );
 
-        // Optimize the assembly module
-        optimize_module(&self.assembly_module, self.code_gen.optimization_level);
+        // Look up the functions with an explicit `#[optimize]` attribute before
+        // their symbols are versioned.
+        let explicit_optimize = file
+            .function_definitions
+            .iter()
+            .filter(|function| function.optimize(self.code_gen.db) != OptimizeAttr::Default)
+            .filter_map(|function| {
+                self.assembly_module
+                    .get_function(&mangle::function_symbol(self.code_gen.db, *function))
+            })
+            .collect();
+
+        // Suffix the exported symbols with the symbol version, such that several
+        // versions of the assembly can be loaded into the same process.
+        if self.code_gen.entry_symbols.version().is_some() {
+            version_exported_symbols(&self.assembly_module, &self.code_gen.entry_symbols);
+        }
+
+        // Instrument or annotate the code for profile-guided optimization before
+        // it is optimized, like clang does, such that the optimizer takes the
+        // counters or the profile into account. Functions are identified by the
+        // symbol names they have at this point.
+        if let Some(pgo) = &self.code_gen.pgo {
+            log::debug!(
+                "applying profile-guided optimization module_group={} pgo={:?}",
+                module_group.name,
+                pgo
+            );
+            pgo::apply_to_module(&self.assembly_module, &self.code_gen.target_machine, pgo)?;
+        }
+
+        // Optimize the assembly module. The reflection functions are only generated
+        // in the assembly module, so they are marked for size here.
+        if self.code_gen.minimize_size {
+            mark_min_size(&self.assembly_module, &explicit_optimize);
+        }
+        let start = Instant::now();
+        optimize_module(
+            &self.assembly_module,
+            self.code_gen.optimization_level,
+            self.code_gen.minimize_size,
+            self.code_gen.pass_hook.as_deref(),
+        );
+        if let Some(timings) = &self.code_gen.function_timings {
+            timings.record_assembly(&module_group.name, start.elapsed());
+        }
+
+        // Instrument the optimized code, like clang does, such that the
+        // instrumentation does not prevent optimizations.
+        if let Some(sanitizer) = self.code_gen.sanitizer {
+            log::debug!(
+                "instrumenting module_group={} sanitizer={:?}",
+                module_group.name,
+                sanitizer
+            );
+            sanitizer::instrument_module(
+                &self.assembly_module,
+                &self.code_gen.db.target(),
+                &self.code_gen.target_machine,
+                sanitizer,
+            )?;
+        }
+
+        // Validate the optimized module to catch errors in both code generation and
+        // optimization before they turn into crashes during emission.
+        if self.code_gen.verify_llvm {
+            log::debug!("verifying module_group={}", module_group.name);
+            self.assembly_module
+                .verify()
+                .map_err(|e| CodeGenerationError::ModuleVerificationError(e.to_string()))?;
+        }
 
         // Debug print the IR
         //println!("{}", assembly_module.print_to_string().to_string());
 
-        Ok(Assembly::new(self.code_gen, self.assembly_module))
+        let dispatch_table = group_ir
+            .dispatch_table
+            .entries()
+            .iter()
+            .map(|entry| entry.prototype.name.clone())
+            .collect();
+
+        Ok(Assembly::new(
+            self.code_gen,
+            self.assembly_module,
+            dispatch_table,
+        ))
+    }
+}
+
+/// Renames every symbol that is defined and exported by `module` to its
+/// versioned name. The entry symbols are generated with their versioned names
+/// already.
+fn version_exported_symbols(module: &Module<'_>, entry_symbols: &mun_abi::EntrySymbols) {
+    let globals = module
+        .get_functions()
+        .map(|function| function.as_global_value())
+        .chain(module.get_globals());
+    for global in globals {
+        if global.is_declaration()
+            || !matches!(global.get_linkage(), Linkage::External | Linkage::DLLExport)
+        {
+            continue;
+        }
+
+        let name = global.get_name().to_string_lossy().into_owned();
+        if name == "_fltused" || entry_symbols.contains(&name) {
+            continue;
+        }
+
+        global
+            .as_pointer_value()
+            .set_name(&entry_symbols.versioned(&name));
     }
 }
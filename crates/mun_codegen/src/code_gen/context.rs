@@ -1,8 +1,13 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, num::NonZeroU32};
 
 use inkwell::{context::Context, module::Module, targets::TargetMachine, types::StructType};
 
-use crate::{ir::ty::HirTypeCache, CodeGenDatabase};
+use crate::{
+    code_gen::{FunctionTimingsRef, PassHookRef},
+    db::create_target_machine,
+    ir::ty::HirTypeCache,
+    CodeGenDatabase, DebugInfo, ProfileGuidedOptimization, Sanitizer,
+};
 
 pub struct CodeGenContext<'db, 'ink> {
     /// The current LLVM context
@@ -20,21 +25,89 @@ pub struct CodeGenContext<'db, 'ink> {
     /// The optimization level
     pub optimization_level: inkwell::OptimizationLevel,
 
+    /// Whether code size is minimized at the expense of speed
+    pub minimize_size: bool,
+
     /// The target to generate code for
-    pub target_machine: Rc<TargetMachine>,
+    pub target_machine: TargetMachine,
+
+    /// An optional hook that adds custom passes to the optimization pipeline
+    pub pass_hook: Option<PassHookRef>,
+
+    /// Whether to run the LLVM verifier on assemblies after optimization
+    pub verify_llvm: bool,
+
+    /// The amount of debug information to emit
+    pub debug_info: DebugInfo,
+
+    /// Whether to emit the relocations of assemblies
+    pub emit_relocations: bool,
+
+    /// Whether heap allocations record their source location
+    pub alloc_debug: bool,
+
+    /// Whether heap allocated structs are zero-initialized
+    pub init_structs: bool,
+
+    /// Whether functions report their entry and exit to measure their
+    /// execution time
+    pub instrument_timing: bool,
+
+    /// The number of calls that the call trace ring holds, if functions record
+    /// their calls into it
+    pub call_trace_ring: Option<NonZeroU32>,
+
+    /// The sanitizer that instruments the generated code, if any
+    pub sanitizer: Option<Sanitizer>,
+
+    /// The phase of a profile-guided optimization build, if any
+    pub pgo: Option<ProfileGuidedOptimization>,
+
+    /// Whether to omit functions that are not reachable from any exported
+    /// function
+    pub gc_functions: bool,
+
+    /// Whether to emit the argument names of functions
+    pub reflect_arg_names: bool,
+
+    /// Whether to emit the attributes of functions
+    pub reflect_fn_attributes: bool,
+
+    /// Where the time it takes to generate code for each function is
+    /// recorded, if it is measured
+    pub function_timings: Option<FunctionTimingsRef>,
+
+    /// The names under which the assembly exports its entry symbols
+    pub entry_symbols: mun_abi::EntrySymbols,
 }
 
 impl<'db, 'ink> CodeGenContext<'db, 'ink> {
     /// Constructs a new `CodeGenContext` from an LLVM context and a
     /// `CodeGenDatabase`.
     pub fn new(context: &'ink Context, db: &'db dyn CodeGenDatabase) -> Self {
-        let target_machine = db.target_machine().0;
+        let target_machine = create_target_machine(db);
         Self {
             context,
             rust_types: RefCell::new(HashMap::default()),
             hir_types: HirTypeCache::new(context, db.upcast(), target_machine.get_target_data()),
             optimization_level: db.optimization_level(),
+            minimize_size: db.minimize_size(),
             target_machine,
+            pass_hook: db.pass_hook(),
+            verify_llvm: db.verify_llvm(),
+            debug_info: db.debug_info(),
+            emit_relocations: db.emit_relocations(),
+            alloc_debug: db.alloc_debug(),
+            init_structs: db.init_structs(),
+            instrument_timing: db.instrument_timing(),
+            call_trace_ring: db.call_trace_ring(),
+            sanitizer: db.sanitizer(),
+            pgo: db.pgo(),
+            gc_functions: db.gc_functions(),
+            reflect_arg_names: db.reflect_arg_names(),
+            reflect_fn_attributes: db.reflect_fn_attributes(),
+            function_timings: db.function_timings(),
+            entry_symbols: db.entry_symbols(),
             db: db.upcast(),
         }
     }
@@ -47,4 +120,10 @@ impl<'db, 'ink> CodeGenContext<'db, 'ink> {
         module.set_triple(&self.target_machine.get_triple());
         module
     }
+
+    /// Returns true if code is generated for the specified function. Unless
+    /// `gc_functions` is set, this is true for all functions.
+    pub fn is_function_emitted(&self, function: mun_hir::Function) -> bool {
+        !self.gc_functions || self.db.call_graph().is_live(function)
+    }
 }
@@ -1,7 +1,10 @@
 use std::io;
 
+use object::BinaryFormat;
 use thiserror::Error;
 
+use crate::Sanitizer;
+
 #[derive(Debug, Error)]
 pub enum CodeGenerationError {
     #[error("error linking modules: {0}")]
@@ -10,4 +13,22 @@ pub enum CodeGenerationError {
     CouldNotCreateObjectFile(io::Error),
     #[error("error generating machine code")]
     MachineCodeError(String),
+    #[error("error verifying module: {0}")]
+    ModuleVerificationError(String),
+    #[error("error reading relocations: {0}")]
+    CouldNotReadRelocations(String),
+    #[error("error writing split debug info")]
+    CouldNotSplitDebugInfo(io::Error),
+    #[error("error stripping debug info: {0}")]
+    CouldNotStripDebugInfo(String),
+    #[error("split debug info is not supported for {0:?} binaries")]
+    UnsupportedSplitDebugInfo(BinaryFormat),
+    #[error("{0} is not supported for target `{1}`")]
+    UnsupportedSanitizer(Sanitizer, String),
+    #[error("error running sanitizer passes: {0}")]
+    SanitizerError(String),
+    #[error("error running profile-guided optimization passes: {0}")]
+    ProfileError(String),
+    #[error("`#[no_mangle]` function `{0}` clashes with an entry symbol of the assembly")]
+    ReservedSymbol(String),
 }
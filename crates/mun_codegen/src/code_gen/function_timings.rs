@@ -0,0 +1,274 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+/// The phase of code generation that is timed for a function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Phase {
+    /// Generating the IR of the function and its wrapper
+    IrGeneration,
+    /// Running the function passes over the IR of the function and its wrapper
+    Optimization,
+}
+
+/// How long code generation took for a single function.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FunctionTiming {
+    /// The full name of the function, e.g. `foo::bar`
+    pub name: String,
+    /// The time it took to generate the IR of the function
+    pub ir_generation: Duration,
+    /// The time it took to optimize the function
+    pub optimization: Duration,
+    /// Whether code generation failed while the function was processed
+    pub failed: bool,
+}
+
+impl FunctionTiming {
+    /// Returns the total time spent on the function.
+    pub fn total(&self) -> Duration {
+        self.ir_generation + self.optimization
+    }
+}
+
+/// Records how long code generation takes for each function, to find the
+/// functions that are pathologically slow to compile.
+///
+/// The optimization of a function only covers the function passes that run
+/// over it in isolation. The module passes that optimize an assembly as a
+/// whole, e.g. inlining, cannot be attributed to a single function; their
+/// time is recorded per assembly instead.
+#[derive(Debug, Default)]
+pub struct FunctionTimings {
+    functions: Mutex<HashMap<String, FunctionTiming>>,
+    assemblies: Mutex<Vec<(String, Duration)>>,
+    /// The phases that are currently running and when they started
+    running: Mutex<HashMap<(String, Phase), Instant>>,
+}
+
+impl FunctionTimings {
+    /// Starts timing `phase` of the function with the specified name. The
+    /// elapsed time is recorded when the returned timer is dropped, also if
+    /// code generation panics in the meantime.
+    pub(crate) fn start(&self, name: String, phase: Phase) -> PhaseTimer<'_> {
+        let start = Instant::now();
+        self.running.lock().insert((name.clone(), phase), start);
+        PhaseTimer {
+            timings: self,
+            name,
+            phase,
+            start,
+        }
+    }
+
+    /// Returns the functions that are currently being compiled and for how
+    /// long, longest first. This enables finding the function that makes
+    /// code generation hang, before it completes.
+    pub fn running(&self) -> Vec<(String, Duration)> {
+        let now = Instant::now();
+        let mut running: Vec<_> = self
+            .running
+            .lock()
+            .iter()
+            .map(|((name, _), start)| (name.clone(), now - *start))
+            .collect();
+        running.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        running
+    }
+
+    /// Records the time it took to optimize the assembly with the specified
+    /// name as a whole.
+    pub(crate) fn record_assembly(&self, name: &str, duration: Duration) {
+        self.assemblies.lock().push((name.to_owned(), duration));
+    }
+
+    /// Returns the timings of all functions that were compiled, slowest first.
+    pub fn functions(&self) -> Vec<FunctionTiming> {
+        let mut timings: Vec<_> = self.functions.lock().values().cloned().collect();
+        timings.sort_by(|a, b| b.total().cmp(&a.total()).then_with(|| a.name.cmp(&b.name)));
+        timings
+    }
+
+    /// Returns the time it took to optimize each assembly as a whole, slowest
+    /// first.
+    pub fn assemblies(&self) -> Vec<(String, Duration)> {
+        let mut timings = self.assemblies.lock().clone();
+        timings.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        timings
+    }
+
+    fn record(&self, name: String, phase: Phase, duration: Duration, failed: bool) {
+        self.running.lock().remove(&(name.clone(), phase));
+        let mut functions = self.functions.lock();
+        let timing = functions
+            .entry(name)
+            .or_insert_with_key(|name| FunctionTiming {
+                name: name.clone(),
+                ..FunctionTiming::default()
+            });
+        match phase {
+            Phase::IrGeneration => timing.ir_generation += duration,
+            Phase::Optimization => timing.optimization += duration,
+        }
+        timing.failed |= failed;
+    }
+}
+
+/// Formats the timings as a table of functions, slowest first, followed by
+/// the assemblies.
+impl fmt::Display for FunctionTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>12} {:>12} {:>12}  function",
+            "total", "ir gen", "optimize"
+        )?;
+        for timing in self.functions() {
+            writeln!(
+                f,
+                "{:>12} {:>12} {:>12}  {}{}",
+                format!("{:.3?}", timing.total()),
+                format!("{:.3?}", timing.ir_generation),
+                format!("{:.3?}", timing.optimization),
+                timing.name,
+                if timing.failed { " (failed)" } else { "" }
+            )?;
+        }
+        for (name, duration) in self.assemblies() {
+            writeln!(
+                f,
+                "{:>12} {:>12} {:>12}  assembly `{}`",
+                format!("{:.3?}", duration),
+                "",
+                "",
+                name
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Measures a phase of code generation of a function, see
+/// [`FunctionTimings::start`].
+pub(crate) struct PhaseTimer<'t> {
+    timings: &'t FunctionTimings,
+    name: String,
+    phase: Phase,
+    start: Instant,
+}
+
+impl Drop for PhaseTimer<'_> {
+    fn drop(&mut self) {
+        self.timings.record(
+            std::mem::take(&mut self.name),
+            self.phase,
+            self.start.elapsed(),
+            thread::panicking(),
+        );
+    }
+}
+
+/// A shared reference to [`FunctionTimings`]. Two references are equal if they
+/// refer to the same timings, which allows the timings to be stored in the
+/// database.
+#[derive(Clone, Debug, Default)]
+pub struct FunctionTimingsRef(Arc<FunctionTimings>);
+
+impl std::ops::Deref for FunctionTimingsRef {
+    type Target = FunctionTimings;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl PartialEq for FunctionTimingsRef {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for FunctionTimingsRef {}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        panic::{self, AssertUnwindSafe},
+        time::Duration,
+    };
+
+    use super::{FunctionTimings, Phase};
+
+    #[test]
+    fn sorted_slowest_first() {
+        let timings = FunctionTimings::default();
+        timings.record(
+            String::from("fast"),
+            Phase::IrGeneration,
+            Duration::from_millis(1),
+            false,
+        );
+        timings.record(
+            String::from("slow"),
+            Phase::IrGeneration,
+            Duration::from_millis(2),
+            false,
+        );
+        timings.record(
+            String::from("slow"),
+            Phase::Optimization,
+            Duration::from_millis(3),
+            false,
+        );
+
+        let functions = timings.functions();
+        assert_eq!(
+            functions
+                .iter()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>(),
+            ["slow", "fast"]
+        );
+        assert_eq!(functions[0].total(), Duration::from_millis(5));
+    }
+
+    #[test]
+    fn running_phases() {
+        let timings = FunctionTimings::default();
+        let timer = timings.start(String::from("hangs"), Phase::Optimization);
+        assert_eq!(
+            timings
+                .running()
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect::<Vec<_>>(),
+            ["hangs"]
+        );
+
+        drop(timer);
+        assert!(timings.running().is_empty());
+        assert_eq!(timings.functions()[0].name, "hangs");
+    }
+
+    #[test]
+    fn records_failed_phases() {
+        let timings = FunctionTimings::default();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            let _timer = timings.start(String::from("broken"), Phase::Optimization);
+            panic!("optimization failed");
+        }));
+        assert!(result.is_err());
+
+        let functions = timings.functions();
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].name, "broken");
+        assert!(functions[0].failed);
+        assert!(timings.running().is_empty());
+    }
+}
@@ -1,10 +1,16 @@
-use std::{io::Write, path::Path};
+use std::{fs, io::Write, path::Path};
 
 use inkwell::targets::{FileType, TargetMachine};
+use mun_abi as abi;
 use mun_target::spec;
 use tempfile::NamedTempFile;
 
-use crate::{code_gen::CodeGenerationError, linker};
+use crate::{
+    code_gen::{relocations, CodeGenerationError, Relocation},
+    linker::{self, LinkerOutput},
+    pgo,
+    sanitizer::{self, Sanitizer},
+};
 
 pub struct ObjectFile {
     target: spec::Target,
@@ -34,14 +40,66 @@ impl ObjectFile {
         })
     }
 
-    /// Links the object file into a shared object.
-    pub fn into_shared_object(self, output_path: &Path) -> Result<(), anyhow::Error> {
+    /// Returns all references from functions in the object file to slots of
+    /// the dispatch table and to globals. `dispatch_table` contains the
+    /// names of the functions in the dispatch table, in order, and
+    /// `entry_symbols` the symbol names the object file was generated with.
+    pub fn relocations(
+        &self,
+        dispatch_table: &[String],
+        entry_symbols: &abi::EntrySymbols,
+    ) -> Result<Vec<Relocation>, anyhow::Error> {
+        let contents = fs::read(self.obj_file.path())
+            .map_err(CodeGenerationError::CouldNotCreateObjectFile)?;
+        let relocations = relocations::collect_relocations(
+            &contents,
+            dispatch_table,
+            entry_symbols,
+            u64::from(self.target.pointer_width / 8),
+        )
+        .map_err(|e| CodeGenerationError::CouldNotReadRelocations(e.to_string()))?;
+        Ok(relocations)
+    }
+
+    /// Links the object file into a binary of the specified `output` kind,
+    /// which exports the symbols that `entry_symbols` specify, including the
+    /// call trace global if the object file records a `call_trace`. If the
+    /// object file was instrumented with a `sanitizer` or to generate a
+    /// profile, the runtime of the sanitizer or of the profiler is linked as
+    /// well, unless the output is a relocatable object.
+    pub fn link(
+        self,
+        output_path: &Path,
+        output: LinkerOutput,
+        entry_symbols: &abi::EntrySymbols,
+        call_trace: bool,
+        sanitizer: Option<Sanitizer>,
+        profiler: bool,
+    ) -> Result<(), anyhow::Error> {
         // Construct a linker for the target
-        let mut linker = linker::create_with_target(&self.target);
+        let mut linker = linker::create_with_target(&self.target, entry_symbols, call_trace);
         linker.add_object(self.obj_file.path())?;
 
+        if let Some(sanitizer) = sanitizer.filter(|_| output != LinkerOutput::Relocatable) {
+            let runtime = sanitizer::find_runtime_library(sanitizer, &self.target)?;
+            linker.add_sanitizer_runtime(sanitizer, &runtime)?;
+        }
+
+        if profiler && output != LinkerOutput::Relocatable {
+            let runtime = pgo::find_profiler_runtime_library(&self.target)?;
+            linker.add_profiler_runtime(&runtime)?;
+        }
+
         // Link the object
-        linker.build_shared_object(output_path)?;
+        log::debug!(
+            "invoking linker target={} output={:?} path={} sanitizer={:?} profiler={}",
+            self.target.llvm_target,
+            output,
+            output_path.display(),
+            sanitizer,
+            profiler
+        );
+        linker.build(output_path, output)?;
         linker.finalize()?;
 
         Ok(())
@@ -0,0 +1,78 @@
+use std::{fmt, sync::Arc};
+
+use inkwell::{
+    module::Module,
+    passes::{PassManager, PassManagerBuilder},
+};
+
+/// A hook that adds custom LLVM passes to the pipeline that optimizes an
+/// assembly. This enables tooling to insert instrumentation or custom analyses
+/// without modifying the code generator.
+pub trait PassHook: Send + Sync {
+    /// Returns whether the default pipeline for the optimization level should
+    /// be added to the pass manager. If this returns `false`, only the passes
+    /// added by [`PassHook::add_passes`] are run.
+    fn use_default_pipeline(&self) -> bool {
+        true
+    }
+
+    /// Adds passes to `pass_manager`. This is called after the default
+    /// pipeline was added and before the passes are run on `module`. The
+    /// `pass_builder` is configured for the current optimization level.
+    fn add_passes<'ink>(
+        &self,
+        module: &Module<'ink>,
+        pass_builder: &PassManagerBuilder,
+        pass_manager: &PassManager<Module<'ink>>,
+    );
+}
+
+impl<F> PassHook for F
+where
+    F: for<'ink> Fn(&Module<'ink>, &PassManagerBuilder, &PassManager<Module<'ink>>) + Send + Sync,
+{
+    fn add_passes<'ink>(
+        &self,
+        module: &Module<'ink>,
+        pass_builder: &PassManagerBuilder,
+        pass_manager: &PassManager<Module<'ink>>,
+    ) {
+        self(module, pass_builder, pass_manager);
+    }
+}
+
+/// A shared reference to a [`PassHook`]. Two references are equal if they refer
+/// to the same hook, which allows the hook to be stored in the database.
+#[derive(Clone)]
+pub struct PassHookRef(Arc<dyn PassHook>);
+
+impl PassHookRef {
+    /// Constructs a new reference to the specified hook.
+    pub fn new(hook: impl PassHook + 'static) -> Self {
+        Self(Arc::new(hook))
+    }
+}
+
+impl std::ops::Deref for PassHookRef {
+    type Target = dyn PassHook;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl PartialEq for PassHookRef {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for PassHookRef {}
+
+impl fmt::Debug for PassHookRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PassHookRef")
+            .field(&Arc::as_ptr(&self.0))
+            .finish()
+    }
+}
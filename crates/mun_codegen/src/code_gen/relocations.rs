@@ -0,0 +1,239 @@
+use std::{fmt, io};
+
+use mun_abi as abi;
+use object::{
+    elf, macho, Architecture, BinaryFormat, Object, ObjectSection, ObjectSymbol, RelocationKind,
+    RelocationTarget as ObjectRelocationTarget, SectionIndex, SectionKind, SymbolKind,
+};
+
+use crate::mangle::demangle;
+
+/// The name of the global that holds the dispatch table of an assembly.
+const DISPATCH_TABLE_NAME: &str = "dispatchTable";
+
+/// A reference from the machine code of a function to a dispatch table slot or
+/// a global. A loader can use it to relocate or patch the referencing
+/// instruction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Relocation {
+    /// The name of the function that contains the reference. Mangled symbols
+    /// are demangled to the path of the function, e.g. `foo::bar`.
+    pub function: String,
+    /// The offset of the relocated field relative to the start of the
+    /// function.
+    pub offset: u64,
+    /// The kind of the relocation, e.g. `Relative` or `Absolute`.
+    pub kind: String,
+    /// The size of the relocated field in bits.
+    pub size: u8,
+    /// The addend of the relocation.
+    pub addend: i64,
+    /// What the relocation refers to.
+    pub target: RelocationTarget,
+}
+
+/// The target of a [`Relocation`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RelocationTarget {
+    /// A slot in the dispatch table. The index corresponds to the index of the
+    /// prototype in the dispatch table of the assembly's reflection data.
+    DispatchTable { index: usize, function: String },
+    /// A global with the specified name.
+    Global(String),
+}
+
+impl fmt::Display for Relocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t",
+            self.function, self.offset, self.kind, self.size, self.addend
+        )?;
+        match &self.target {
+            RelocationTarget::DispatchTable { index, function } => {
+                write!(f, "dispatch\t{index}\t{function}")
+            }
+            RelocationTarget::Global(name) => write!(f, "global\t{name}"),
+        }
+    }
+}
+
+/// Writes the relocations in the sidecar format: a version header followed by
+/// a line of tab-separated fields for every relocation.
+pub(crate) fn write_relocations(
+    relocations: &[Relocation],
+    mut writer: impl io::Write,
+) -> io::Result<()> {
+    writeln!(writer, "# mun relocations 1")?;
+    writeln!(writer, "# function\toffset\tkind\tsize\taddend\ttarget")?;
+    for relocation in relocations {
+        writeln!(writer, "{relocation}")?;
+    }
+    Ok(())
+}
+
+/// Collects all references from functions in the `object_file` to the dispatch
+/// table and to globals. `dispatch_table` contains the names of the functions
+/// in the dispatch table, in order. The `entry_symbols` determine the version
+/// with which the exported symbols of the object file are suffixed.
+pub(crate) fn collect_relocations(
+    object_file: &[u8],
+    dispatch_table: &[String],
+    entry_symbols: &abi::EntrySymbols,
+    pointer_size: u64,
+) -> Result<Vec<Relocation>, object::Error> {
+    let file = object::File::parse(object_file)?;
+    let dispatch_table_name = entry_symbols.versioned(DISPATCH_TABLE_NAME);
+
+    // Mach-O prefixes all symbol names with an underscore
+    let symbol_name = |name: &'_ str| -> String {
+        match file.format() {
+            BinaryFormat::MachO => name.strip_prefix('_').unwrap_or(name).to_owned(),
+            _ => name.to_owned(),
+        }
+    };
+
+    // Sort the functions by their address, which enables finding the function that
+    // contains a relocation. Not all object file formats record the size of a symbol.
+    let mut functions: Vec<(SectionIndex, u64, String)> = file
+        .symbols()
+        .filter(|symbol| symbol.kind() == SymbolKind::Text && symbol.is_definition())
+        .filter_map(|symbol| {
+            // Mangled functions are reported by their path, like in the dispatch table
+            let name = symbol_name(symbol.name().ok()?);
+            let name = entry_symbols.unversioned(&name).to_owned();
+            let name = demangle(&name).map_or(name, |demangled| demangled.to_string());
+            Some((symbol.section_index()?, symbol.address(), name))
+        })
+        .collect();
+    functions.sort();
+
+    let mut relocations = Vec::new();
+    for section in file.sections() {
+        if section.kind() != SectionKind::Text {
+            continue;
+        }
+
+        let data = section.data()?;
+        for (offset, relocation) in section.relocations() {
+            let ObjectRelocationTarget::Symbol(symbol_index) = relocation.target() else {
+                continue;
+            };
+            let symbol = file.symbol_by_index(symbol_index)?;
+            if !symbol.is_global() || symbol.kind() == SymbolKind::Text {
+                continue;
+            }
+
+            let addend = if relocation.has_implicit_addend() {
+                implicit_addend(data, offset, relocation.size())
+            } else {
+                relocation.addend()
+            };
+
+            // A reference through the GOT loads the address of the dispatch table, its
+            // addend does not identify a slot.
+            let name = symbol_name(symbol.name()?);
+            let target = if name == dispatch_table_name
+                && !is_got_relocation(file.architecture(), relocation.kind())
+            {
+                // PC-relative references are biased by the distance between the relocated
+                // field and the end of the instruction. Rounding up to the next slot
+                // compensates for that.
+                let pointer_size = pointer_size as i64;
+                let index = usize::try_from((addend + pointer_size - 1).div_euclid(pointer_size))
+                    .ok()
+                    .filter(|index| *index < dispatch_table.len());
+                match index {
+                    Some(index) => RelocationTarget::DispatchTable {
+                        index,
+                        function: dispatch_table[index].clone(),
+                    },
+                    None => RelocationTarget::Global(name),
+                }
+            } else {
+                RelocationTarget::Global(name)
+            };
+
+            // Find the function that contains the relocated field
+            let address = section.address() + offset;
+            let Some((_, function_address, function)) =
+                functions.iter().rev().find(|(index, function_address, _)| {
+                    *index == section.index() && *function_address <= address
+                })
+            else {
+                continue;
+            };
+
+            relocations.push(Relocation {
+                function: function.clone(),
+                offset: address - function_address,
+                kind: format!("{:?}", relocation.kind()),
+                size: relocation.size(),
+                addend,
+                target,
+            });
+        }
+    }
+
+    Ok(relocations)
+}
+
+/// Returns true if the relocation refers to the GOT entry of its symbol instead
+/// of to the symbol itself.
+fn is_got_relocation(architecture: Architecture, kind: RelocationKind) -> bool {
+    match (architecture, kind) {
+        (
+            _,
+            RelocationKind::Got
+            | RelocationKind::GotRelative
+            | RelocationKind::GotBaseRelative
+            | RelocationKind::GotBaseOffset,
+        ) => true,
+        (Architecture::X86_64, RelocationKind::Elf(r_type)) => {
+            matches!(
+                r_type,
+                elf::R_X86_64_GOTPCRELX | elf::R_X86_64_REX_GOTPCRELX
+            )
+        }
+        (Architecture::Aarch64, RelocationKind::Elf(r_type)) => matches!(
+            r_type,
+            elf::R_AARCH64_ADR_GOT_PAGE | elf::R_AARCH64_LD64_GOT_LO12_NC
+        ),
+        (Architecture::X86_64, RelocationKind::MachO { value, .. }) => {
+            matches!(
+                value,
+                macho::X86_64_RELOC_GOT_LOAD | macho::X86_64_RELOC_GOT
+            )
+        }
+        (Architecture::Aarch64, RelocationKind::MachO { value, .. }) => matches!(
+            value,
+            macho::ARM64_RELOC_GOT_LOAD_PAGE21 | macho::ARM64_RELOC_GOT_LOAD_PAGEOFF12
+        ),
+        _ => false,
+    }
+}
+
+/// Reads the addend that is stored in the relocated field itself.
+fn implicit_addend(data: &[u8], offset: u64, size: u8) -> i64 {
+    let start = offset as usize;
+    let bytes = |len: usize| -> [u8; 8] {
+        let mut buf = [0u8; 8];
+        if let Some(field) = data.get(start..start + len) {
+            buf[..len].copy_from_slice(field);
+        }
+        buf
+    };
+    match size {
+        8 => i64::from(bytes(1)[0] as i8),
+        16 => {
+            let b = bytes(2);
+            i64::from(i16::from_le_bytes([b[0], b[1]]))
+        }
+        32 => {
+            let b = bytes(4);
+            i64::from(i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        }
+        64 => i64::from_le_bytes(bytes(8)),
+        _ => 0,
+    }
+}
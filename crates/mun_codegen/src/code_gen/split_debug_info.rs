@@ -0,0 +1,155 @@
+use std::{collections::HashSet, fs, path::Path};
+
+use mun_target::spec::{LinkerFlavor, Target};
+use object::{
+    build::{
+        elf::{Builder, SectionData},
+        ByteString,
+    },
+    elf, BinaryFormat, Endianness, Object,
+};
+
+use crate::code_gen::CodeGenerationError;
+
+/// The name of the section through which an ELF binary refers to the file that
+/// holds its debug information.
+const GNU_DEBUGLINK_SECTION_NAME: &[u8] = b".gnu_debuglink";
+
+/// Returns true if the debug information of assemblies for `target` can be
+/// split off, which is only the case for targets that produce ELF binaries.
+pub fn supports_split_debug_info(target: &Target) -> bool {
+    target.options.linker_flavor == LinkerFlavor::Ld
+}
+
+/// Moves the debug information of the linked binary at `binary_path` into a
+/// separate file at `debug_path`. The binary refers to the debug file by its
+/// `debug_file_name` through a `.gnu_debuglink` section, which also records
+/// the checksum of the debug file. Debuggers use both to find and verify the
+/// debug file next to the binary.
+///
+/// The debug file is a copy of the complete binary, which debuggers accept in
+/// place of a file that only contains the debug sections.
+///
+/// Only ELF binaries are supported. On other platforms the debug information
+/// cannot be split off without external tools, like `dsymutil` for Mach-O.
+pub(crate) fn split_debug_info(
+    binary_path: &Path,
+    debug_path: &Path,
+    debug_file_name: &str,
+) -> Result<(), CodeGenerationError> {
+    let binary = fs::read(binary_path).map_err(CodeGenerationError::CouldNotSplitDebugInfo)?;
+
+    let format = object::File::parse(binary.as_slice())
+        .map_err(|e| CodeGenerationError::CouldNotStripDebugInfo(e.to_string()))?
+        .format();
+    if format != BinaryFormat::Elf {
+        return Err(CodeGenerationError::UnsupportedSplitDebugInfo(format));
+    }
+
+    fs::write(debug_path, &binary).map_err(CodeGenerationError::CouldNotSplitDebugInfo)?;
+
+    let stripped = strip_debug_info(&binary, debug_file_name, gnu_debuglink_crc32(&binary))
+        .map_err(|e| CodeGenerationError::CouldNotStripDebugInfo(e.to_string()))?;
+    fs::write(binary_path, stripped).map_err(CodeGenerationError::CouldNotSplitDebugInfo)
+}
+
+/// Removes the DWARF sections from the ELF `binary` and adds a `.gnu_debuglink`
+/// section that refers to the debug file named `debug_file_name`, whose
+/// checksum is `crc`.
+fn strip_debug_info(
+    binary: &[u8],
+    debug_file_name: &str,
+    crc: u32,
+) -> Result<Vec<u8>, object::build::Error> {
+    let mut builder = Builder::read(binary)?;
+
+    let mut deleted_sections = HashSet::new();
+    for section in builder.sections.iter_mut() {
+        if is_debug_section(section.name.as_slice()) {
+            section.delete = true;
+            deleted_sections.insert(section.id());
+        }
+    }
+
+    // Symbols that refer to the removed sections, if any, are removed as well
+    for symbol in builder.symbols.iter_mut() {
+        if symbol
+            .section
+            .map_or(false, |section| deleted_sections.contains(&section))
+        {
+            symbol.delete = true;
+        }
+    }
+
+    let contents = gnu_debuglink_contents(debug_file_name, crc, builder.endian);
+    let debuglink = builder.sections.add();
+    debuglink.name = ByteString::from(GNU_DEBUGLINK_SECTION_NAME);
+    debuglink.sh_type = elf::SHT_PROGBITS;
+    debuglink.sh_addralign = 4;
+    debuglink.data = SectionData::Data(contents.into());
+
+    let mut stripped = Vec::new();
+    builder.write(&mut stripped)?;
+    Ok(stripped)
+}
+
+/// Returns true if the section with the specified name holds debug information.
+fn is_debug_section(name: &[u8]) -> bool {
+    name.starts_with(b".debug_") || name.starts_with(b".zdebug_")
+}
+
+/// Returns the contents of a `.gnu_debuglink` section: the null-terminated file
+/// name of the debug file, padded to a multiple of four bytes, followed by the
+/// checksum of the debug file.
+fn gnu_debuglink_contents(debug_file_name: &str, crc: u32, endian: Endianness) -> Vec<u8> {
+    let mut contents = debug_file_name.as_bytes().to_vec();
+    contents.push(0);
+    contents.resize((contents.len() + 3) & !3, 0);
+    contents.extend_from_slice(&match endian {
+        Endianness::Little => crc.to_le_bytes(),
+        Endianness::Big => crc.to_be_bytes(),
+    });
+    contents
+}
+
+/// Computes the checksum that a `.gnu_debuglink` section records for the debug
+/// file, which is the CRC-32 used by zlib.
+fn gnu_debuglink_crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use object::Endianness;
+
+    use super::{gnu_debuglink_contents, gnu_debuglink_crc32};
+
+    #[test]
+    fn crc32() {
+        assert_eq!(gnu_debuglink_crc32(b""), 0);
+        assert_eq!(gnu_debuglink_crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn debuglink_contents() {
+        assert_eq!(
+            gnu_debuglink_contents("mod.debug", 0x1234_5678, Endianness::Little),
+            b"mod.debug\0\0\0\x78\x56\x34\x12"
+        );
+        assert_eq!(
+            gnu_debuglink_contents("foo.dbg", 0x1234_5678, Endianness::Big),
+            b"foo.dbg\0\x12\x34\x56\x78"
+        );
+    }
+}
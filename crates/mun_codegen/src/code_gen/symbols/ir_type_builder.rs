@@ -43,6 +43,10 @@ impl<'ink, 'a, 'b, 'c> TypeIdBuilder<'ink, 'a, 'b, 'c> {
                 let element = self.get_global_type_id(arr);
                 ir::TypeId::Array(ir::ArrayTypeId { element })
             }
+            TypeIdData::Slice(slice) => {
+                let element = self.get_global_type_id(slice);
+                ir::TypeId::Slice(ir::SliceTypeId { element })
+            }
         }
     }
 
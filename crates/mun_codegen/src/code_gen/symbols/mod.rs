@@ -1,6 +1,8 @@
-use std::{collections::HashSet, convert::TryFrom, ffi::CString};
+use std::{collections::HashSet, convert::TryFrom, ffi::CString, num::NonZeroU32};
 
-use inkwell::{attributes::Attribute, module::Linkage, types::AnyType};
+use inkwell::{
+    attributes::Attribute, module::Linkage, targets::ByteOrdering, types::AnyType, AddressSpace,
+};
 use ir_type_builder::TypeIdBuilder;
 use itertools::Itertools;
 use mun_abi as abi;
@@ -8,27 +10,33 @@ use mun_hir::{HirDatabase, TyKind};
 
 use crate::{
     ir::{
+        debug_info::DebugInfo,
         dispatch_table::{DispatchTable, DispatchableFunction},
+        file::LocalStatic,
         function,
         ty::{guid_from_struct, HirTypeCache},
         type_table::TypeTable,
         types as ir,
     },
+    mangle,
     type_info::HasStaticTypeId,
     value::{
         AsValue, CanInternalize, Global, IrValueContext, IterAsIrValue, SizedValueType, Value,
     },
+    COMPILER_VERSION, LLVM_VERSION,
 };
 
 mod ir_type_builder;
 
 /// Construct a `MunFunctionPrototype` struct for the specified HIR function.
+/// If `reflect_arg_names` is false, the names of the arguments are omitted.
 fn gen_prototype_from_function<'ink>(
     db: &dyn HirDatabase,
     context: &IrValueContext<'ink, '_, '_>,
     function: mun_hir::Function,
     hir_types: &HirTypeCache<'_, 'ink>,
     ir_type_builder: &TypeIdBuilder<'ink, '_, '_, '_>,
+    reflect_arg_names: bool,
 ) -> ir::FunctionPrototype<'ink> {
     let name = function.full_name(db);
 
@@ -53,10 +61,35 @@ fn gen_prototype_from_function<'ink>(
         .map(|ty| ir_type_builder.construct_from_type_id(&hir_types.type_id(ty)))
         .into_const_private_pointer_or_null(format!("fn_sig::<{}>::arg_types", &name), context);
 
+    // Construct an array of the names of the arguments, parameters without a name
+    // are named `_`. The array is never null, such that functions without
+    // arguments also report that their names were reflected.
+    let arg_names = if reflect_arg_names {
+        function
+            .params(db)
+            .iter()
+            .map(|param| {
+                let arg_name = param
+                    .name(db)
+                    .map_or_else(|| String::from("_"), |name| name.to_string());
+                CString::new(arg_name)
+                    .expect("argument name is not a valid CString")
+                    .intern(
+                        format!("fn_sig::<{}>::arg_names.{}", &name, param.index()),
+                        context,
+                    )
+                    .as_value(context)
+            })
+            .into_const_private_pointer(format!("fn_sig::<{}>::arg_names", &name), context)
+    } else {
+        Value::null(context)
+    };
+
     ir::FunctionPrototype {
         name: name_str.as_value(context),
         signature: ir::FunctionSignature {
             arg_types,
+            arg_names,
             return_type,
             num_arg_types: fn_sig.params().len() as u16,
         },
@@ -97,6 +130,7 @@ fn gen_prototype_from_dispatch_entry<'ink>(
         name: name_str.as_value(context),
         signature: ir::FunctionSignature {
             arg_types,
+            arg_names: Value::null(context),
             return_type,
             num_arg_types: function.prototype.arg_types.len() as u16,
         },
@@ -152,6 +186,98 @@ fn get_type_definition_array<'ink>(
         .into_const_private_pointer_or_null("fn.get_info.types", context)
 }
 
+/// Construct a global that holds a reference to all function-local statics,
+/// or a null pointer if there are none.
+fn get_static_definition_array<'ink>(
+    context: &IrValueContext<'ink, '_, '_>,
+    statics: &[LocalStatic],
+    hir_types: &HirTypeCache<'_, 'ink>,
+    ir_type_builder: &TypeIdBuilder<'ink, '_, '_, '_>,
+) -> Value<'ink, *const ir::StaticDefinition<'ink>> {
+    let module = context.module;
+    statics
+        .iter()
+        .sorted_by(|a, b| a.name.cmp(&b.name))
+        .map(|local_static| {
+            // The globals are looked up again because the passed module is a clone of
+            // the module in which they were created.
+            let value_ptr = module
+                .get_global(&local_static.name)
+                .expect("missing global of static")
+                .as_pointer_value();
+            let init_state_ptr = local_static.init_state.as_ref().map_or_else(
+                || Value::null(context),
+                |init_state| {
+                    Value::<*mut u8>::with_cast(
+                        module
+                            .get_global(init_state)
+                            .expect("missing initialization state of static")
+                            .as_pointer_value(),
+                        context,
+                    )
+                },
+            );
+
+            ir::StaticDefinition {
+                name: CString::new(local_static.name.clone())
+                    .expect("static name is not a valid CString")
+                    .intern(format!("static::<{}>::name", local_static.name), context)
+                    .as_value(context),
+                type_id: ir_type_builder
+                    .construct_from_type_id(&hir_types.type_id(&local_static.ty)),
+                value_ptr: Value::<*mut u8>::with_cast(value_ptr, context),
+                init_state_ptr,
+            }
+        })
+        .into_const_private_pointer_or_null("fn.get_info.statics", context)
+}
+
+/// Construct a global that holds a reference to all public constants, or a
+/// null pointer if there are none. The value of each constant is embedded in
+/// a private global.
+fn get_const_definition_array<'ink>(
+    db: &dyn HirDatabase,
+    context: &IrValueContext<'ink, '_, '_>,
+    constants: &[mun_hir::Const],
+    hir_types: &HirTypeCache<'_, 'ink>,
+    ir_type_builder: &TypeIdBuilder<'ink, '_, '_, '_>,
+) -> Value<'ink, *const ir::ConstDefinition<'ink>> {
+    constants
+        .iter()
+        .map(|constant| {
+            let name = constant.full_name(db);
+            let ty = constant.ty(db);
+            let value = hir_types.get_const_value(
+                &ty,
+                constant
+                    .value(db)
+                    .expect("only evaluated constants are reflected"),
+            );
+
+            // A constant that is placed in a section already has a global
+            let value_name = format!("const::<{name}>::value");
+            let value_global = context.module.get_global(&value_name).unwrap_or_else(|| {
+                let value_global = context
+                    .module
+                    .add_global(value.get_type(), None, &value_name);
+                value_global.set_linkage(Linkage::Private);
+                value_global.set_constant(true);
+                value_global.set_initializer(&value);
+                value_global
+            });
+
+            ir::ConstDefinition {
+                name: CString::new(name.clone())
+                    .expect("constant name is not a valid CString")
+                    .intern(format!("const::<{name}>::name"), context)
+                    .as_value(context),
+                type_id: ir_type_builder.construct_from_type_id(&hir_types.type_id(&ty)),
+                value_ptr: Value::<*const u8>::with_cast(value_global.as_pointer_value(), context),
+            }
+        })
+        .into_const_private_pointer_or_null("fn.get_info.constants", context)
+}
+
 fn gen_struct_info<'ink>(
     db: &dyn HirDatabase,
     hir_struct: mun_hir::Struct,
@@ -213,6 +339,36 @@ fn gen_struct_info<'ink>(
     }
 }
 
+/// Construct a `MunFunctionAttributes` struct with the effective attributes of
+/// the specified HIR function. All functions are compiled with the C calling
+/// convention.
+fn gen_function_attributes(
+    db: &dyn HirDatabase,
+    function: mun_hir::Function,
+) -> ir::FunctionAttributes {
+    let inline = match function.inline(db) {
+        mun_hir::InlineAttr::None => abi::InlineAttribute::None,
+        mun_hir::InlineAttr::Hint => abi::InlineAttribute::Hint,
+        mun_hir::InlineAttr::Always => abi::InlineAttribute::Always,
+        mun_hir::InlineAttr::Never => abi::InlineAttribute::Never,
+    };
+    let optimize = match function.optimize(db) {
+        mun_hir::OptimizeAttr::Default => abi::OptimizeAttribute::Default,
+        mun_hir::OptimizeAttr::Speed => abi::OptimizeAttribute::Speed,
+        mun_hir::OptimizeAttr::Size => abi::OptimizeAttribute::Size,
+    };
+
+    ir::FunctionAttributes {
+        inline,
+        optimize,
+        calling_convention: abi::CallingConvention::C,
+        is_cold: function.is_cold(db),
+        is_no_mangle: function.is_no_mangle(db),
+        is_tail_call: function.is_tail_call(db),
+        is_no_instrument: function.is_no_instrument(db),
+    }
+}
+
 /// Construct a global that holds a reference to all functions. e.g.:
 /// `MunFunctionDefinition[] definitions = { ... }`
 fn get_function_definition_array<'ink, 'a>(
@@ -221,32 +377,51 @@ fn get_function_definition_array<'ink, 'a>(
     functions: impl Iterator<Item = &'a mun_hir::Function>,
     hir_types: &HirTypeCache<'_, 'ink>,
     ir_type_builder: &TypeIdBuilder<'ink, '_, '_, '_>,
+    reflect_arg_names: bool,
+    reflect_fn_attributes: bool,
 ) -> Global<'ink, [ir::FunctionDefinition<'ink>]> {
     let module = context.module;
     functions
         .sorted_by_cached_key(|f| f.full_name(db))
         .map(|f| {
-            let name = f.name(db).to_string();
-
             // Get the function from the cloned module and modify the linkage of the
             // function.
-            let value = module
-                // If a wrapper function exists, use that (required for struct types)
-                .get_function(&format!("{name}_wrapper"))
+            // If a wrapper function exists, use that (required for struct types)
+            let wrapper = module.get_function(&mangle::wrapper_symbol(db, *f));
+            let value = wrapper
                 // Otherwise, use the normal function
-                .or_else(|| module.get_function(&name))
+                .or_else(|| module.get_function(&mangle::function_symbol(db, *f)))
                 .unwrap();
-            value.set_linkage(Linkage::Private);
+
+            // `#[no_mangle]` functions remain exported under their own name
+            if wrapper.is_some() || !f.is_no_mangle(db) {
+                value.set_linkage(Linkage::Private);
+            }
 
             // Generate the signature from the function
-            let prototype =
-                gen_prototype_from_function(db, context, *f, hir_types, ir_type_builder);
+            let prototype = gen_prototype_from_function(
+                db,
+                context,
+                *f,
+                hir_types,
+                ir_type_builder,
+                reflect_arg_names,
+            );
+            let attributes = if reflect_fn_attributes {
+                gen_function_attributes(db, *f)
+                    .as_value(context)
+                    .into_const_private_global(format!("fn_attrs::<{}>", f.full_name(db)), context)
+                    .as_value(context)
+            } else {
+                Value::null(context)
+            };
             ir::FunctionDefinition {
                 prototype,
                 fn_ptr: Value::<*const fn()>::with_cast(
                     value.as_global_value().as_pointer_value(),
                     context,
                 ),
+                attributes,
             }
         })
         .into_value(context)
@@ -355,10 +530,19 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
     module_name: &str,
     function_definitions: &HashSet<mun_hir::Function>,
     type_definitions: &HashSet<mun_hir::Ty>,
+    static_definitions: &[LocalStatic],
+    const_definitions: &[mun_hir::Const],
+    traced_functions: &[mun_hir::Function],
     dispatch_table: &DispatchTable<'ink>,
     type_table: &TypeTable<'ink>,
     hir_types: &HirTypeCache<'db, 'ink>,
     optimization_level: inkwell::OptimizationLevel,
+    minimize_size: bool,
+    debug_info: DebugInfo,
+    reflect_arg_names: bool,
+    reflect_fn_attributes: bool,
+    call_trace_ring: Option<NonZeroU32>,
+    entry_symbols: &abi::EntrySymbols,
     dependencies: Vec<String>,
 ) {
     let ir_type_builder = TypeIdBuilder::new(context);
@@ -370,6 +554,8 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
         function_definitions.iter(),
         hir_types,
         &ir_type_builder,
+        reflect_arg_names,
+        reflect_fn_attributes,
     );
 
     // Get the TypeTable global
@@ -382,6 +568,33 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
         &ir_type_builder,
     );
 
+    let num_statics = static_definitions.len() as u32;
+    let statics =
+        get_static_definition_array(context, static_definitions, hir_types, &ir_type_builder);
+
+    let num_constants = const_definitions.len() as u32;
+    let constants =
+        get_const_definition_array(db, context, const_definitions, hir_types, &ir_type_builder);
+
+    // Construct an array of the names of the `#[init]` functions (or null if there
+    // are none)
+    let init_function_names = function_definitions
+        .iter()
+        .filter(|function| function.is_init(db))
+        .map(|function| function.full_name(db))
+        .sorted()
+        .collect::<Vec<_>>();
+    let num_init_functions = init_function_names.len() as u32;
+    let init_functions = init_function_names
+        .into_iter()
+        .map(|name| {
+            CString::new(name.clone())
+                .expect("function name is not a valid CString")
+                .intern(format!("init_fn::<{name}>::name"), context)
+                .as_value(context)
+        })
+        .into_const_private_pointer_or_null("fn.get_info.init_functions", context);
+
     // Construct the module info struct
     let module_info = ir::ModuleInfo {
         path: CString::new(module_name)
@@ -392,6 +605,12 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
         num_functions,
         types,
         num_types,
+        statics,
+        num_statics,
+        constants,
+        num_constants,
+        init_functions,
+        num_init_functions,
     };
 
     // Construct the dispatch table struct
@@ -399,6 +618,8 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
 
     let type_lut = gen_type_lut(context, type_table, &ir_type_builder);
 
+    let build_info = gen_build_info(db, context, optimization_level, minimize_size, debug_info);
+
     // Construct the actual `get_info` function
     gen_get_info_fn(
         db,
@@ -406,21 +627,67 @@ pub(super) fn gen_reflection_ir<'db, 'ink>(
         module_info,
         dispatch_table,
         type_lut,
+        build_info,
         optimization_level,
+        minimize_size,
+        &entry_symbols.get_info,
         dependencies,
     );
-    gen_set_allocator_handle_fn(context);
-    gen_get_version_fn(context);
+    gen_set_allocator_handle_fn(context, &entry_symbols.set_allocator_handle);
+    gen_get_version_fn(context, &entry_symbols.get_version);
+    gen_format_header(context, &entry_symbols.format_header);
+    gen_compiler_info(context, &entry_symbols.compiler_info);
+    gen_abi_tag_note(db, context);
+    if let Some(capacity) = call_trace_ring {
+        gen_call_trace(
+            db,
+            context,
+            capacity,
+            traced_functions,
+            &entry_symbols.call_trace,
+        );
+    }
 }
 
-/// Construct the actual `get_info` function.
+/// Constructs a `MunBuildInfo` struct that describes how the assembly was
+/// compiled.
+fn gen_build_info<'ink>(
+    db: &dyn HirDatabase,
+    context: &IrValueContext<'ink, '_, '_>,
+    optimization_level: inkwell::OptimizationLevel,
+    minimize_size: bool,
+    debug_info: DebugInfo,
+) -> ir::BuildInfo<'ink> {
+    let optimization_level = match optimization_level {
+        _ if minimize_size => abi::OptimizationLevel::Size,
+        inkwell::OptimizationLevel::None => abi::OptimizationLevel::None,
+        inkwell::OptimizationLevel::Less => abi::OptimizationLevel::Less,
+        inkwell::OptimizationLevel::Default => abi::OptimizationLevel::Default,
+        inkwell::OptimizationLevel::Aggressive => abi::OptimizationLevel::Aggressive,
+    };
+
+    ir::BuildInfo {
+        target_triple: CString::new(db.target().llvm_target.to_string())
+            .expect("target triple is not a valid CString")
+            .intern("build_info::target_triple", context)
+            .as_value(context),
+        optimization_level,
+        has_debug_info: debug_info != DebugInfo::None,
+    }
+}
+
+/// Construct the actual `get_info` function, exported as `name`.
+#[allow(clippy::too_many_arguments)]
 fn gen_get_info_fn<'ink>(
     db: &dyn HirDatabase,
     context: &IrValueContext<'ink, '_, '_>,
     module_info: ir::ModuleInfo<'ink>,
     dispatch_table: ir::DispatchTable<'ink>,
     type_lut: ir::TypeLut<'ink>,
+    build_info: ir::BuildInfo<'ink>,
     optimization_level: inkwell::OptimizationLevel,
+    minimize_size: bool,
+    name: &str,
     dependencies: Vec<String>,
 ) {
     let target = db.target();
@@ -445,7 +712,7 @@ fn gen_get_info_fn<'ink>(
     let get_symbols_fn =
         context
             .module
-            .add_function("get_info", get_symbols_type, Some(Linkage::DLLExport));
+            .add_function(name, get_symbols_type, Some(Linkage::DLLExport));
 
     if target.options.is_like_windows {
         let type_attribute = context.context.create_type_attribute(
@@ -490,6 +757,9 @@ fn gen_get_info_fn<'ink>(
     let num_dependencies_addr = builder
         .build_struct_gep(result_ptr, 9, "num_dependencies")
         .expect("could not retrieve `num_dependencies` from result struct");
+    let build_info_addr = builder
+        .build_struct_gep(result_ptr, 11, "build_info")
+        .expect("could not retrieve `build_info` from result struct");
 
     // Assign the struct values one by one.
     builder.build_store(symbols_addr, module_info.as_value(context).value);
@@ -519,6 +789,8 @@ fn gen_get_info_fn<'ink>(
         ),
     );
 
+    builder.build_store(build_info_addr, build_info.as_value(context).value);
+
     // Construct the return statement of the function.
     if target.options.is_like_windows {
         builder.build_return(None);
@@ -527,15 +799,17 @@ fn gen_get_info_fn<'ink>(
     }
 
     // Run the function optimizer on the generate function
-    function::create_pass_manager(context.module, optimization_level).run_on(&get_symbols_fn);
+    function::create_pass_manager(context.module, optimization_level, minimize_size)
+        .run_on(&get_symbols_fn);
 }
 
 /// Generates a method `void set_allocator_handle(void*)` that stores the
 /// argument into the global `allocatorHandle`. This global is used internally
-/// to reference the allocator used by this munlib.
-fn gen_set_allocator_handle_fn(context: &IrValueContext<'_, '_, '_>) {
+/// to reference the allocator used by this munlib. The method is exported as
+/// `name`.
+fn gen_set_allocator_handle_fn(context: &IrValueContext<'_, '_, '_>, name: &str) {
     let set_allocator_handle_fn = context.module.add_function(
-        "set_allocator_handle",
+        name,
         Value::<fn(*const u8)>::get_ir_type(context.type_context),
         Some(Linkage::DLLExport),
     );
@@ -558,9 +832,10 @@ fn gen_set_allocator_handle_fn(context: &IrValueContext<'_, '_, '_>) {
 
 /// Generates a `get_version` method that returns the current abi version.
 /// Specifically, it returns the abi version the function was generated in.
-fn gen_get_version_fn(context: &IrValueContext<'_, '_, '_>) {
+/// The method is exported as `name`.
+fn gen_get_version_fn(context: &IrValueContext<'_, '_, '_>, name: &str) {
     let get_version_fn = context.module.add_function(
-        abi::GET_VERSION_FN_NAME,
+        name,
         Value::<fn() -> u32>::get_ir_type(context.type_context),
         Some(Linkage::DLLExport),
     );
@@ -571,3 +846,155 @@ fn gen_get_version_fn(context: &IrValueContext<'_, '_, '_>) {
 
     builder.build_return(Some(&abi::ABI_VERSION.as_value(context).value));
 }
+
+/// Generates the exported `mun_format_header` global that describes the format
+/// and ABI version of the munlib. A runtime reads this global before anything
+/// else to determine whether it is able to load the munlib. The global is
+/// exported as `name`.
+fn gen_format_header(context: &IrValueContext<'_, '_, '_>, name: &str) {
+    let header = abi::FormatHeader::current();
+    ir::FormatHeader {
+        magic: header.magic,
+        format_version: header.format_version,
+        abi_version: header.abi_version,
+    }
+    .as_value(context)
+    .into_global(name, context, true, Linkage::DLLExport, None);
+}
+
+/// Generates the exported `mun_compiler_info` global that describes the
+/// toolchain that produced the munlib. Like the format header, a runtime can
+/// read it without calling into the munlib. The global is exported as `name`.
+fn gen_compiler_info(context: &IrValueContext<'_, '_, '_>, name: &str) {
+    ir::CompilerInfo {
+        compiler_version: CString::new(COMPILER_VERSION)
+            .expect("compiler version is not a valid CString")
+            .intern("compiler_info::compiler_version", context)
+            .as_value(context),
+        llvm_version: CString::new(LLVM_VERSION)
+            .expect("LLVM version is not a valid CString")
+            .intern("compiler_info::llvm_version", context)
+            .as_value(context),
+    }
+    .as_value(context)
+    .into_global(name, context, true, Linkage::DLLExport, None);
+}
+
+/// Generates a note that tags the munlib with its format and ABI versions and
+/// the target it was built for, see [`abi::AbiTag`]. The note is placed in a
+/// section of its own, such that packaging tools can find it without loading
+/// the munlib; for ELF targets `readelf -n` lists it.
+fn gen_abi_tag_note(db: &dyn HirDatabase, context: &IrValueContext<'_, '_, '_>) {
+    let target = db.target();
+    let little_endian = matches!(
+        context.type_context.target_data.get_byte_ordering(),
+        ByteOrdering::LittleEndian
+    );
+    let note = abi::AbiTag::current(target.llvm_target.to_string())
+        .to_note(little_endian)
+        .as_slice()
+        .as_value(context)
+        .into_const_private_global("mun_abi_tag", context)
+        .value;
+    note.set_section(Some(abi_tag_section_name(&target)));
+    note.set_alignment(4);
+
+    // The note is never referenced, mark it as used to prevent it from being
+    // removed.
+    let ptr_type = context.context.i8_type().ptr_type(AddressSpace::default());
+    let used = context
+        .module
+        .add_global(ptr_type.array_type(1), None, "llvm.used");
+    used.set_linkage(Linkage::Appending);
+    used.set_section(Some("llvm.metadata"));
+    used.set_initializer(&ptr_type.const_array(&[note.as_pointer_value().const_cast(ptr_type)]));
+}
+
+/// Returns the name of the section that contains the ABI tag note. The section
+/// name must follow the conventions of the object format, which is determined
+/// by the linker flavor of the target.
+fn abi_tag_section_name(target: &mun_target::spec::Target) -> &'static str {
+    if target.options.is_like_osx {
+        // Mach-O has no notes, the note is placed in a read-only section.
+        "__TEXT,__mun_abi_tag"
+    } else if target.options.is_like_windows {
+        // The names of COFF image sections are limited to eight characters.
+        ".munabi"
+    } else {
+        // ELF linkers create a note segment from `.note*` sections.
+        ".note.mun.abi-tag"
+    }
+}
+
+/// Generates the exported `mun_call_trace` global through which the runtime
+/// provides the ring into which `traced_functions` record their calls. Until
+/// the runtime sets the ring, the global refers to a ring with a single entry.
+/// The global also maps the ids of the functions back to their names. It is
+/// exported as `name`.
+fn gen_call_trace<'ink>(
+    db: &dyn HirDatabase,
+    context: &IrValueContext<'ink, '_, '_>,
+    capacity: NonZeroU32,
+    traced_functions: &[mun_hir::Function],
+    name: &str,
+) {
+    let placeholder_ids = 0u32.as_value(context).into_global(
+        "call_trace::placeholder_ids",
+        context,
+        false,
+        Linkage::Private,
+        None,
+    );
+    let placeholder_ring = ir::CallTraceRing {
+        ids: Value::<*mut u32>::with_cast(placeholder_ids.value.as_pointer_value(), context),
+        mask: 0,
+        next: 0,
+    }
+    .as_value(context)
+    .into_global(
+        "call_trace::placeholder_ring",
+        context,
+        false,
+        Linkage::Private,
+        None,
+    );
+
+    let names = traced_functions
+        .iter()
+        .map(|f| f.full_name(db))
+        .collect::<Vec<_>>();
+    let call_trace = ir::CallTrace {
+        ring: Value::<*mut ir::CallTraceRing<'_>>::with_cast(
+            placeholder_ring.value.as_pointer_value(),
+            context,
+        ),
+        capacity: capacity.get(),
+        num_functions: names.len().try_into().expect("too many traced functions"),
+        function_ids: names
+            .iter()
+            .map(|name| abi::call_trace_id(name))
+            .into_const_private_pointer_or_null("call_trace::function_ids", context),
+        function_names: names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| {
+                CString::new(name.as_str())
+                    .expect("function name is not a valid CString")
+                    .intern(format!("call_trace::function_name{idx}"), context)
+                    .as_value(context)
+            })
+            .into_const_private_pointer_or_null("call_trace::function_names", context),
+    }
+    .as_value(context);
+
+    // Functions that record their calls refer to the global by name
+    match context.module.get_global(name) {
+        Some(global) => {
+            global.set_initializer(&call_trace.value);
+            global.set_linkage(Linkage::DLLExport);
+        }
+        None => {
+            call_trace.into_global(name, context, false, Linkage::DLLExport, None);
+        }
+    }
+}
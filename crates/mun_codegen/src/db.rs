@@ -1,9 +1,12 @@
-use std::{rc::Rc, sync::Arc};
+use std::{num::NonZeroU32, sync::Arc};
 
-use by_address::ByAddress;
-use inkwell::targets::{CodeModel, InitializationConfig, RelocMode, Target, TargetTriple};
+use inkwell::targets::{InitializationConfig, Target, TargetMachine, TargetTriple};
 
-use crate::{AssemblyIr, ModuleGroupId, ModulePartition, TargetAssembly};
+use crate::{
+    AssemblyError, AssemblyIr, CHeader, DebugInfo, FunctionTimingsRef, LinkerOutput, ModuleGroupId,
+    ModulePartition, PassHookRef, ProfileGuidedOptimization, RustBindings, Sanitizer,
+    TargetAssembly, TargetSpec,
+};
 
 /// The `CodeGenDatabase` enables caching of code generation stages.
 /// Inkwell/LLVM objects are not stored in the cache because they are not
@@ -21,51 +24,162 @@ pub trait CodeGenDatabase:
     #[salsa::input]
     fn optimization_level(&self) -> inkwell::OptimizationLevel;
 
+    /// Set whether the optimizer minimizes the size of assemblies
+    /// aggressively, at the expense of their speed, like `-Oz`
+    #[salsa::input]
+    fn minimize_size(&self) -> bool;
+
+    /// Set the kind of binary that is produced when linking assemblies
+    #[salsa::input]
+    fn linker_output(&self) -> LinkerOutput;
+
+    /// Set the hook that adds custom passes to the optimization pipeline
+    #[salsa::input]
+    fn pass_hook(&self) -> Option<PassHookRef>;
+
+    /// Set whether assemblies are validated with the LLVM verifier after they
+    /// have been optimized
+    #[salsa::input]
+    fn verify_llvm(&self) -> bool;
+
+    /// Set the amount of debug information that is emitted into assemblies
+    #[salsa::input]
+    fn debug_info(&self) -> DebugInfo;
+
+    /// Set whether all modules are compiled into a single assembly, such that
+    /// calls between them are direct calls instead of going through the
+    /// dispatch table
+    #[salsa::input]
+    fn static_dispatch(&self) -> bool;
+
+    /// Set whether a sidecar file is emitted next to each assembly that lists
+    /// the references from machine code to dispatch table slots and globals
+    #[salsa::input]
+    fn emit_relocations(&self) -> bool;
+
+    /// Set whether the debug information of assemblies is moved into a
+    /// separate file that the assembly refers to
+    #[salsa::input]
+    fn split_debug_info(&self) -> bool;
+
+    /// Set whether heap allocations pass the source location of the allocating
+    /// expression to the runtime, which records it
+    #[salsa::input]
+    fn alloc_debug(&self) -> bool;
+
+    /// Set whether structs are zero-initialized when they are allocated on the
+    /// heap. If not, only their references to other objects are zeroed.
+    #[salsa::input]
+    fn init_structs(&self) -> bool;
+
+    /// Set whether functions report their entry and exit to the runtime, which
+    /// measures their execution time
+    #[salsa::input]
+    fn instrument_timing(&self) -> bool;
+
+    /// Set the number of calls that the call trace ring holds, if functions
+    /// record their calls into it
+    #[salsa::input]
+    fn call_trace_ring(&self) -> Option<NonZeroU32>;
+
+    /// Set the sanitizer that instruments the code of assemblies, if any
+    #[salsa::input]
+    fn sanitizer(&self) -> Option<Sanitizer>;
+
+    /// Set the phase of a profile-guided optimization build, if assemblies
+    /// are instrumented to generate a profile or optimized with one
+    #[salsa::input]
+    fn pgo(&self) -> Option<ProfileGuidedOptimization>;
+
+    /// Set whether functions that are not reachable from any exported
+    /// function are omitted from assemblies
+    #[salsa::input]
+    fn gc_functions(&self) -> bool;
+
+    /// Set whether the reflection information of functions includes the
+    /// names of their arguments
+    #[salsa::input]
+    fn reflect_arg_names(&self) -> bool;
+
+    /// Set whether the reflection information of functions includes their
+    /// attributes
+    #[salsa::input]
+    fn reflect_fn_attributes(&self) -> bool;
+
+    /// Set where the time it takes to generate code for each function is
+    /// recorded, if it is measured
+    #[salsa::input]
+    fn function_timings(&self) -> Option<FunctionTimingsRef>;
+
+    /// Set the names under which assemblies export the symbols that the
+    /// runtime uses to load them
+    #[salsa::input]
+    fn entry_symbols(&self) -> mun_abi::EntrySymbols;
+
     /// Returns the current module partition
     #[salsa::invoke(crate::module_partition::build_partition)]
     fn module_partition(&self) -> Arc<ModulePartition>;
 
-    /// Returns the inkwell target machine that completely describes the code
-    /// generation target. All target-specific information should be
-    /// accessible through this interface.
-    fn target_machine(&self) -> ByAddress<Rc<inkwell::targets::TargetMachine>>;
-
-    /// Returns a file containing the IR for the specified module.
+    /// Returns a file containing the IR for the specified module, or the error
+    /// that prevented generating it.
     #[salsa::invoke(crate::assembly::build_assembly_ir)]
-    fn assembly_ir(&self, module_group: ModuleGroupId) -> Arc<AssemblyIr>;
+    fn assembly_ir(&self, module_group: ModuleGroupId) -> Result<Arc<AssemblyIr>, AssemblyError>;
+
+    /// Returns a C header file that declares the C interface of the specified
+    /// module.
+    #[salsa::invoke(crate::c_header::build_c_header)]
+    fn c_header(&self, module_group: ModuleGroupId) -> Arc<CHeader>;
+
+    /// Returns a Rust source file with typed wrappers for the functions of the
+    /// specified module.
+    #[salsa::invoke(crate::rust_bindings::build_rust_bindings)]
+    fn rust_bindings(&self, module_group: ModuleGroupId) -> Arc<RustBindings>;
 
-    /// Returns a fully linked shared object for the specified module.
+    /// Returns a fully linked shared object for the specified module, or the
+    /// error that prevented linking it.
     #[salsa::invoke(crate::assembly::build_target_assembly)]
-    fn target_assembly(&self, module_group: ModuleGroupId) -> Arc<TargetAssembly>;
+    fn target_assembly(
+        &self,
+        module_group: ModuleGroupId,
+    ) -> Result<Arc<TargetAssembly>, AssemblyError>;
 }
 
 /// Constructs the primary interface to the complete machine description for the
 /// target machine. All target-specific information should be accessible through
 /// this interface.
-fn target_machine(db: &dyn CodeGenDatabase) -> ByAddress<Rc<inkwell::targets::TargetMachine>> {
-    // Get the HIR target
-    let target = db.target();
+///
+/// The target machine is not cached in the database because it is not
+/// thread-safe. Every code generation context creates its own instance, which
+/// enables assemblies to be generated in parallel.
+pub(crate) fn create_target_machine(db: &dyn CodeGenDatabase) -> TargetMachine {
+    // Resolve the specification of the HIR target
+    let spec = TargetSpec::new(&db.target());
 
     // Initialize the x86 target
     Target::initialize_x86(&InitializationConfig::default());
     Target::initialize_aarch64(&InitializationConfig::default());
 
     // Retrieve the LLVM target using the specified target.
-    let target_triple = TargetTriple::create(&db.target().llvm_target);
+    let target_triple = TargetTriple::create(&spec.llvm_target);
     let llvm_target = Target::from_triple(&target_triple)
         .expect("could not find llvm target tripple for Mun target");
 
     // Construct target machine for machine code generation
-    let target_machine = llvm_target
+    log::debug!(
+        "creating target machine triple={} cpu={} features={:?} opt_level={:?}",
+        spec.llvm_target,
+        spec.cpu,
+        spec.features,
+        db.optimization_level()
+    );
+    llvm_target
         .create_target_machine(
             &target_triple,
-            &target.options.cpu,
-            &target.options.features,
+            &spec.cpu,
+            &spec.features,
             db.optimization_level(),
-            RelocMode::PIC,
-            CodeModel::Default,
+            spec.reloc_mode,
+            spec.code_model,
         )
-        .expect("could not create llvm target machine");
-
-    ByAddress(Rc::new(target_machine))
+        .expect("could not create llvm target machine")
 }
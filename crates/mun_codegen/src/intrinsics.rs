@@ -21,9 +21,43 @@ intrinsics! {
     /// Allocates memory for the specified `type` in the allocator referred to by `alloc_handle`.
     pub fn new(type_handle: *const ffi::c_void, alloc_handle: *mut ffi::c_void) -> *const *mut ffi::c_void;
 
+    /// Allocates memory for the specified `type` in the allocator referred to by `alloc_handle`
+    /// without zero-initializing it. Only the references to other objects are zeroed.
+    pub fn new_uninit(type_handle: *const ffi::c_void, alloc_handle: *mut ffi::c_void) -> *const *mut ffi::c_void;
+
     /// Allocates memory for an array of the specified `type` in the allocator referred to by
     /// `alloc_handle` with at least enough capacity to hold `length` elements.
     ///
     /// Note that the elements in the array are left uninitialized.
     pub fn new_array(type_handle: *const ffi::c_void, length: usize, alloc_handle: *mut ffi::c_void) -> *const *mut ffi::c_void;
+
+    /// Allocates memory for the specified `type` in the allocator referred to by `alloc_handle`
+    /// and records `location`, a null-terminated string, as the source location of the
+    /// allocation.
+    pub fn new_with_location(type_handle: *const ffi::c_void, alloc_handle: *mut ffi::c_void, location: *const u8) -> *const *mut ffi::c_void;
+
+    /// Allocates memory for an array of the specified `type` in the allocator referred to by
+    /// `alloc_handle` with at least enough capacity to hold `length` elements, and records
+    /// `location`, a null-terminated string, as the source location of the allocation.
+    ///
+    /// Note that the elements in the array are left uninitialized.
+    pub fn new_array_with_location(type_handle: *const ffi::c_void, length: usize, alloc_handle: *mut ffi::c_void, location: *const u8) -> *const *mut ffi::c_void;
+
+    /// Reports that the function named `name`, a null-terminated string, was entered. The
+    /// runtime that owns the allocator referred to by `alloc_handle` starts measuring the
+    /// execution time of the call.
+    pub fn timing_enter(name: *const u8, alloc_handle: *mut ffi::c_void) -> ();
+
+    /// Reports that the function named `name`, a null-terminated string, returns. The runtime
+    /// that owns the allocator referred to by `alloc_handle` adds the execution time of the call
+    /// to the total of the function.
+    pub fn timing_exit(name: *const u8, alloc_handle: *mut ffi::c_void) -> ();
+
+    /// Roots the object referred to by `obj` in the allocator referred to by `alloc_handle`. This
+    /// keeps an array alive while a slice borrows its elements.
+    pub fn root(obj: *const *mut ffi::c_void, alloc_handle: *mut ffi::c_void) -> ();
+
+    /// Unroots the object referred to by `obj` in the allocator referred to by `alloc_handle`,
+    /// which was previously rooted with `root`.
+    pub fn unroot(obj: *const *mut ffi::c_void, alloc_handle: *mut ffi::c_void) -> ();
 }
@@ -1,27 +1,37 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    cmp,
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use inkwell::{
     basic_block::BasicBlock,
     builder::Builder,
     context::Context,
+    intrinsics::Intrinsic as LlvmIntrinsic,
+    module::{Linkage, Module},
     values::{
-        AggregateValueEnum, BasicMetadataValueEnum, BasicValueEnum, CallSiteValue, FloatValue,
-        FunctionValue, GlobalValue, IntValue, PointerValue, StructValue,
+        AggregateValueEnum, BasicMetadataValueEnum, BasicValueEnum, CallSiteValue, CallableValue,
+        FloatValue, FunctionValue, GlobalValue, IntValue, PointerValue, StructValue,
     },
-    AddressSpace, FloatPredicate, IntPredicate,
+    AddressSpace, AtomicOrdering, FloatPredicate, IntPredicate,
 };
 use mun_abi as abi;
 use mun_hir::{
     ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, HirDatabase, HirDisplay, InferenceResult,
-    Literal, LogicOp, Name, Ordering, Pat, PatId, Path, ResolveBitness, Resolver, Statement,
-    TyKind, UnaryOp, ValueNs,
+    LabelId, LinkSection, Literal, LogicOp, Name, Ordering, Pat, PatId, Path, ResolveBitness,
+    Resolver, Signedness, Statement, Ty, TyKind, UnaryOp, ValueNs,
 };
 
 use crate::{
-    intrinsics,
+    intrinsics::{self, Intrinsic},
     ir::{
-        dispatch_table::DispatchTable, ty::HirTypeCache, type_table::TypeTable, RuntimeArrayValue,
-        RuntimeReferenceValue,
+        debug_info::{DebugInfoGenerator, FunctionDebugInfo},
+        dispatch_table::DispatchTable,
+        file::LocalStatic,
+        ty::HirTypeCache,
+        type_table::TypeTable,
+        RuntimeArrayValue, RuntimeReferenceValue,
     },
     module_group::ModuleGroup,
     value::Global,
@@ -30,8 +40,12 @@ use crate::{
 type BreakSources<'ink> = Vec<Option<(BasicValueEnum<'ink>, BasicBlock<'ink>)>>;
 
 struct LoopInfo<'ink> {
+    /// The label of the loop, if it has one
+    label: Option<LabelId>,
     break_values: BreakSources<'ink>,
     exit_block: BasicBlock<'ink>,
+    /// The block that `continue` jumps to
+    continue_block: BasicBlock<'ink>,
 }
 
 #[derive(Clone)]
@@ -39,11 +53,13 @@ pub(crate) struct ExternalGlobals<'ink> {
     pub alloc_handle: Option<GlobalValue<'ink>>,
     pub dispatch_table: Option<GlobalValue<'ink>>,
     pub type_table: Option<Global<'ink, [*const std::ffi::c_void]>>,
+    pub call_trace: Option<GlobalValue<'ink>>,
 }
 
 pub(crate) struct BodyIrGenerator<'db, 'ink, 't> {
     context: &'ink Context,
     db: &'db dyn HirDatabase,
+    module: &'t Module<'ink>,
     body: Arc<Body>,
     infer: Arc<InferenceResult>,
     builder: Builder<'ink>,
@@ -55,10 +71,29 @@ pub(crate) struct BodyIrGenerator<'db, 'ink, 't> {
     dispatch_table: &'t DispatchTable<'ink>,
     type_table: &'t TypeTable<'ink>,
     hir_types: &'t HirTypeCache<'db, 'ink>,
-    active_loop: Option<LoopInfo<'ink>>,
+    /// The loops that enclose the expression that is being generated,
+    /// innermost last
+    active_loops: Vec<LoopInfo<'ink>>,
     hir_function: mun_hir::Function,
     external_globals: ExternalGlobals<'ink>,
     module_group: &'t ModuleGroup,
+    debug_info: Option<(&'t DebugInfoGenerator<'ink>, FunctionDebugInfo<'ink>)>,
+    alloc_debug: bool,
+    init_structs: bool,
+    instrument_timing: bool,
+    timing_name: Option<PointerValue<'ink>>,
+    trace_calls: bool,
+    /// Arrays whose elements are borrowed as a slice by a call that is being
+    /// generated. They are rooted until the call returns.
+    slice_roots: Vec<PointerValue<'ink>>,
+    /// The function-local statics that were generated for the body
+    statics: Vec<LocalStatic>,
+    /// The calls in tail position of a `#[tail_call]` function, which reuse
+    /// the stack frame of the function
+    tail_calls: HashSet<ExprId>,
+    /// The block that recursive tail calls jump to after reassigning the
+    /// parameters of the function
+    tail_call_block: Option<BasicBlock<'ink>>,
 }
 
 impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
@@ -66,6 +101,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
     pub fn new(
         context: &'ink Context,
         db: &'db dyn HirDatabase,
+        module: &'t Module<'ink>,
         function: (mun_hir::Function, FunctionValue<'ink>),
         function_map: &'t HashMap<mun_hir::Function, FunctionValue<'ink>>,
         dispatch_table: &'t DispatchTable<'ink>,
@@ -73,12 +109,22 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         external_globals: ExternalGlobals<'ink>,
         hir_types: &'t HirTypeCache<'db, 'ink>,
         module_group: &'t ModuleGroup,
+        debug_info: Option<(&'t DebugInfoGenerator<'ink>, FunctionDebugInfo<'ink>)>,
+        alloc_debug: bool,
+        init_structs: bool,
+        instrument_timing: bool,
+        trace_calls: bool,
     ) -> Self {
         let (hir_function, ir_function) = function;
 
         // Get the type information from the `mun_hir::Function`
         let body = hir_function.body(db);
         let infer = hir_function.infer(db);
+        let tail_calls = if hir_function.is_tail_call(db) {
+            hir_function.tail_calls(db).into_iter().collect()
+        } else {
+            HashSet::new()
+        };
 
         // Construct a builder for the IR function
         let builder = context.create_builder();
@@ -88,6 +134,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         BodyIrGenerator {
             context,
             db,
+            module,
             body,
             infer,
             builder,
@@ -98,28 +145,53 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             function_map,
             dispatch_table,
             type_table,
-            active_loop: None,
+            active_loops: Vec::new(),
             hir_function,
             external_globals,
             hir_types,
             module_group,
+            debug_info,
+            alloc_debug,
+            init_structs,
+            instrument_timing,
+            timing_name: None,
+            trace_calls,
+            slice_roots: Vec::new(),
+            statics: Vec::new(),
+            tail_calls,
+            tail_call_block: None,
         }
     }
 
+    /// Returns the function-local statics that were generated for the body.
+    pub fn into_statics(self) -> Vec<LocalStatic> {
+        self.statics
+    }
+
     /// Generates IR for the body of the function.
     pub fn gen_fn_body(&mut self) {
+        if let Some((debug_info, function)) = self.debug_info {
+            let line_col = self.hir_function.line_col(self.db);
+            debug_info.set_location(&self.builder, function, line_col);
+        }
+
         // Iterate over all parameters and their type and store them so we can reference
         // them later in code.
         for (i, (pat, _ty)) in self.body.params().iter().enumerate() {
             let body = self.body.clone(); // Avoid borrow issues
 
             match &body[*pat] {
+                Pat::Bind { name } if self.infer[*pat].is_empty() => {
+                    // Unit values are zero-sized and don't need any storage
+                    self.pat_to_name.insert(*pat, name.to_string());
+                }
                 Pat::Bind { name } => {
                     let name = name.to_string();
                     let param = self.fn_value.get_nth_param(i as u32).unwrap();
                     let builder = self.new_alloca_builder();
                     let param_ptr = builder.build_alloca(param.get_type(), &name);
                     builder.build_store(param_ptr, param);
+                    self.gen_variable_debug_info(*pat, &name, Some(i as u32 + 1), param_ptr);
                     self.pat_to_local.insert(*pat, param_ptr);
                     self.pat_to_name.insert(*pat, name);
                 }
@@ -136,6 +208,22 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             }
         }
 
+        self.gen_call_trace();
+        self.gen_timing_call(&intrinsics::timing_enter);
+
+        // Recursive tail calls are compiled as a jump back to the start of the
+        // body, which guarantees that they don't grow the stack
+        let has_recursive_tail_call = self
+            .tail_calls
+            .iter()
+            .any(|&expr| self.is_recursive_call(expr));
+        if has_recursive_tail_call {
+            let tail_call_block = self.context.append_basic_block(self.fn_value, "tail_call");
+            self.builder.build_unconditional_branch(tail_call_block);
+            self.builder.position_at_end(tail_call_block);
+            self.tail_call_block = Some(tail_call_block);
+        }
+
         // Generate code for the body of the function
         let ret_value = self.gen_expr(self.body.body_expr());
 
@@ -152,13 +240,107 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             .clone();
         if !block_ret_type.is_never() {
             if fn_ret_type.is_empty() {
+                self.gen_timing_call(&intrinsics::timing_exit);
                 self.builder.build_return(None);
             } else if let Some(value) = ret_value {
+                self.gen_timing_call(&intrinsics::timing_exit);
                 self.builder.build_return(Some(&value));
             }
         }
     }
 
+    /// Records the entry of the function in the call trace ring of the runtime
+    /// by storing the id of the function at the next position of the ring.
+    /// Does nothing if the function is not instrumented.
+    fn gen_call_trace(&mut self) {
+        let Some(call_trace) = self
+            .external_globals
+            .call_trace
+            .filter(|_| self.trace_calls)
+        else {
+            return;
+        };
+
+        let ring_ptr = self
+            .builder
+            .build_struct_gep(call_trace.as_pointer_value(), 0, "call_trace.ring_ptr")
+            .expect("could not retrieve `ring` from call trace");
+        let ring = self
+            .builder
+            .build_load(ring_ptr, "call_trace.ring")
+            .into_pointer_value();
+        let ids_ptr = self
+            .builder
+            .build_struct_gep(ring, 0, "call_trace.ids_ptr")
+            .expect("could not retrieve `ids` from call trace ring");
+        let mask_ptr = self
+            .builder
+            .build_struct_gep(ring, 1, "call_trace.mask_ptr")
+            .expect("could not retrieve `mask` from call trace ring");
+        let next_ptr = self
+            .builder
+            .build_struct_gep(ring, 2, "call_trace.next_ptr")
+            .expect("could not retrieve `next` from call trace ring");
+
+        let next = self
+            .builder
+            .build_load(next_ptr, "call_trace.next")
+            .into_int_value();
+        let incremented = self.builder.build_int_add(
+            next,
+            next.get_type().const_int(1, false),
+            "call_trace.incremented",
+        );
+        self.builder.build_store(next_ptr, incremented);
+
+        let mask = self
+            .builder
+            .build_load(mask_ptr, "call_trace.mask")
+            .into_int_value();
+        let index = self.builder.build_and(next, mask, "call_trace.index");
+        let ids = self
+            .builder
+            .build_load(ids_ptr, "call_trace.ids")
+            .into_pointer_value();
+        let slot = unsafe { self.builder.build_gep(ids, &[index], "call_trace.slot") };
+
+        let id = abi::call_trace_id(&self.hir_function.full_name(self.db));
+        self.builder.build_store(
+            slot,
+            self.context.i32_type().const_int(u64::from(id), false),
+        );
+    }
+
+    /// Reports the entry or exit of the function to the runtime by calling the
+    /// timing intrinsic `intrinsic`. Does nothing if the function is not
+    /// instrumented.
+    fn gen_timing_call(&mut self, intrinsic: &impl Intrinsic) {
+        if !self.instrument_timing {
+            return;
+        }
+
+        let name = match self.timing_name {
+            Some(name) => name,
+            None => {
+                let name = self
+                    .builder
+                    .build_global_string_ptr(&self.hir_function.full_name(self.db), "fn_name")
+                    .as_pointer_value();
+                self.timing_name = Some(name);
+                name
+            }
+        };
+
+        let timing_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            intrinsic,
+        );
+        let allocator_handle = self.get_allocator_handle_ptr();
+        self.builder
+            .build_call(timing_fn_ptr, &[name.into(), allocator_handle.into()], "");
+    }
+
     pub fn gen_fn_wrapper(&mut self) {
         let fn_sig = self.hir_function.ty(self.db).callable_sig(self.db).unwrap();
         let args: Vec<BasicMetadataValueEnum<'_>> = fn_sig
@@ -202,7 +384,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                     if hir_struct.data(self.db.upcast()).memory_kind
                         == mun_hir::StructMemoryKind::Value
                     {
-                        self.gen_struct_alloc_on_heap(hir_struct, value.into_struct_value())
+                        self.gen_struct_alloc_on_heap(None, hir_struct, value.into_struct_value())
                     } else {
                         value
                     }
@@ -214,9 +396,26 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         }
     }
 
-    /// Generates IR for the specified expression. Dependending on the type of
-    /// expression an IR value is returned.
+    /// Generates IR for the specified expression. If debug information is
+    /// emitted, the generated instructions are attributed to the location of
+    /// the expression in the source file.
     fn gen_expr(&mut self, expr: ExprId) -> Option<inkwell::values::BasicValueEnum<'ink>> {
+        let Some((debug_info, function)) = self.debug_info else {
+            return self.gen_expr_inner(expr);
+        };
+
+        let previous_location = self.builder.get_current_debug_location();
+        if let Some(line_col) = self.hir_function.expr_line_col(self.db, expr) {
+            debug_info.set_location(&self.builder, function, line_col);
+        }
+        let value = self.gen_expr_inner(expr);
+        if let Some(location) = previous_location {
+            self.builder.set_current_debug_location(location);
+        }
+        value
+    }
+
+    fn gen_expr_inner(&mut self, expr: ExprId) -> Option<inkwell::values::BasicValueEnum<'ink>> {
         let body = self.body.clone();
         match &body[expr] {
             Expr::Block {
@@ -229,7 +428,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 Some(self.gen_path_expr(p, expr, &resolver))
             }
             Expr::Literal(lit) => Some(self.gen_literal(lit, expr)),
-            Expr::RecordLit { fields, .. } => Some(self.gen_record_lit(expr, fields)),
+            Expr::RecordLit { fields, spread, .. } => self.gen_record_lit(expr, fields, *spread),
             Expr::BinaryOp { lhs, rhs, op } => {
                 self.gen_binary_op(expr, *lhs, *rhs, op.expect("missing op"))
             }
@@ -242,30 +441,62 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 match self.infer[*callee].as_callable_def() {
                     Some(mun_hir::CallableDef::Function(def)) => {
                         // Get all the arguments
-                        let args: Vec<BasicMetadataValueEnum<'_>> = args
+                        let roots_start = self.slice_roots.len();
+                        let args: Vec<BasicValueEnum<'ink>> = args
                             .iter()
-                            .map(|expr| self.gen_expr(*expr).expect("expected a value").into())
+                            .map(|expr| self.gen_expr(*expr).expect("expected a value"))
                             .collect();
 
-                        self.gen_call(def, &args)
-                            .try_as_basic_value()
-                            .left()
-                            // If the called function is a void function it doesn't return anything.
-                            // If this method (`gen_expr`) returns None we assume the return value
-                            // is `never`. We return a const unit struct here to ensure that at
-                            // least something is returned. This matches with the mun_hir where a
-                            // `nothing` is returned instead of a `never`.
-                            //
-                            // This unit value will also be optimized out.
-                            .or_else(|| match self.infer[expr].interned() {
-                                TyKind::Never => None,
-                                _ => Some(self.context.const_struct(&[], false).into()),
-                            })
+                        // Arrays that are borrowed by the call have to be unrooted after it
+                        // returns, which a tail call never does. Such calls are made as
+                        // normal calls instead.
+                        let borrows_slice = self.slice_roots.len() > roots_start;
+                        if self.tail_calls.contains(&expr) && !borrows_slice {
+                            return if self.is_recursive_call(expr) {
+                                self.gen_recursive_tail_call(expr, &args)
+                            } else {
+                                let args: Vec<BasicMetadataValueEnum<'_>> =
+                                    args.into_iter().map(Into::into).collect();
+                                self.gen_tail_call(expr, |this| this.gen_call(def, &args))
+                            };
+                        }
+
+                        let args: Vec<BasicMetadataValueEnum<'_>> =
+                            args.into_iter().map(Into::into).collect();
+                        let call = self.gen_call(def, &args);
+
+                        // The arrays borrowed by the call no longer have to be kept alive
+                        let roots: Vec<_> = self.slice_roots.drain(roots_start..).collect();
+                        for array_ptr in roots {
+                            self.gen_root_call(&intrinsics::unroot, array_ptr);
+                        }
+
+                        self.call_result(expr, call)
                     }
                     Some(mun_hir::CallableDef::Struct(_)) => {
                         Some(self.gen_named_tuple_lit(expr, args))
                     }
-                    None => panic!("expected a callable expression"),
+                    None => {
+                        // Call through a function pointer
+                        let fn_ptr = self
+                            .gen_expr(*callee)
+                            .expect("expected a function pointer")
+                            .into_pointer_value();
+                        let args: Vec<BasicMetadataValueEnum<'_>> = args
+                            .iter()
+                            .map(|expr| self.gen_expr(*expr).expect("expected a value").into())
+                            .collect();
+
+                        let callable = CallableValue::try_from(fn_ptr)
+                            .expect("Pointer value is not a valid function pointer.");
+                        if self.tail_calls.contains(&expr) {
+                            return self.gen_tail_call(expr, |this| {
+                                this.builder.build_call(callable, &args, "fn_ptr_call")
+                            });
+                        }
+                        let call = self.builder.build_call(callable, &args, "fn_ptr_call");
+                        self.call_result(expr, call)
+                    }
                 }
             }
             Expr::If {
@@ -274,15 +505,35 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 else_branch,
             } => self.gen_if(expr, *condition, *then_branch, *else_branch),
             Expr::Return { expr: ret_expr } => self.gen_return(expr, *ret_expr),
-            Expr::Loop { body } => self.gen_loop(expr, *body),
-            Expr::While { condition, body } => self.gen_while(expr, *condition, *body),
-            Expr::Break { expr: break_expr } => self.gen_break(expr, *break_expr),
+            Expr::Loop { body, label } => self.gen_loop(expr, *body, *label),
+            Expr::While {
+                condition,
+                body,
+                label,
+            } => self.gen_while(expr, *condition, *body, *label),
+            Expr::For {
+                pat,
+                iterable,
+                body,
+                label,
+            } => self.gen_for(expr, *pat, *iterable, *body, *label),
+            Expr::Break {
+                expr: break_expr,
+                label,
+            } => self.gen_break(expr, *break_expr, *label),
+            Expr::Continue { label } => {
+                let continue_block = self.target_loop(*label).continue_block;
+                self.builder.build_unconditional_branch(continue_block);
+                None
+            }
             Expr::Field {
                 expr: receiver_expr,
                 name,
             } => self.gen_field(expr, *receiver_expr, name),
             Expr::Array(exprs) => self.gen_array(expr, exprs).map(Into::into),
+            Expr::Tuple(exprs) => self.gen_tuple(expr, exprs),
             Expr::Index { base, index } => self.gen_index(expr, *base, *index),
+            Expr::Cast { expr: operand, .. } => self.gen_cast(expr, *operand),
             Expr::Missing => unimplemented!("unimplemented expr type {:?}", &body[expr]),
         }
     }
@@ -355,12 +606,20 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
     /// type of the struct.
     fn gen_struct_alloc(
         &mut self,
+        expr: ExprId,
         hir_struct: mun_hir::Struct,
         args: Vec<BasicValueEnum<'ink>>,
     ) -> BasicValueEnum<'ink> {
-        // Construct the struct literal
+        // Construct the struct literal. Fields without a value are zeroed, such that
+        // storing the literal initializes the entire object, even if it was allocated
+        // without zero-initialization.
         let struct_ty = self.hir_types.get_struct_type(hir_struct);
-        let mut value: AggregateValueEnum<'_> = struct_ty.get_undef().into();
+        let is_complete = args.len() == hir_struct.fields(self.db).len();
+        let mut value: AggregateValueEnum<'_> = if is_complete {
+            struct_ty.get_undef().into()
+        } else {
+            struct_ty.const_zero().into()
+        };
         for (i, arg) in args.into_iter().enumerate() {
             value = self
                 .builder
@@ -373,22 +632,42 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             mun_hir::StructMemoryKind::Value => struct_lit.into(),
             mun_hir::StructMemoryKind::Gc => {
                 // TODO: Root memory in GC
-                self.gen_struct_alloc_on_heap(hir_struct, struct_lit)
+                self.gen_struct_alloc_on_heap(Some(expr), hir_struct, struct_lit)
             }
         }
     }
 
+    /// Allocates a struct on the heap. If allocation debugging is enabled,
+    /// the location of `expr` is recorded as the source of the allocation.
     fn gen_struct_alloc_on_heap(
         &mut self,
+        expr: Option<ExprId>,
         hir_struct: mun_hir::Struct,
         struct_lit: StructValue<'_>,
     ) -> BasicValueEnum<'ink> {
         let struct_ir_ty = self.hir_types.get_struct_type(hir_struct);
-        let new_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
-            self.external_globals.dispatch_table,
-            &self.builder,
-            &intrinsics::new,
-        );
+        let location = expr.and_then(|expr| self.gen_alloc_location(expr));
+        let new_fn_ptr = if location.is_some() {
+            self.dispatch_table.gen_intrinsic_lookup(
+                self.external_globals.dispatch_table,
+                &self.builder,
+                &intrinsics::new_with_location,
+            )
+        } else if !self.init_structs {
+            // The struct literal is stored in its entirety below, so there is no need to
+            // zero-initialize the allocation
+            self.dispatch_table.gen_intrinsic_lookup(
+                self.external_globals.dispatch_table,
+                &self.builder,
+                &intrinsics::new_uninit,
+            )
+        } else {
+            self.dispatch_table.gen_intrinsic_lookup(
+                self.external_globals.dispatch_table,
+                &self.builder,
+                &intrinsics::new,
+            )
+        };
 
         let type_info_ptr = self.type_table.gen_type_info_lookup(
             self.context,
@@ -407,14 +686,14 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
 
         let allocator_handle = self.get_allocator_handle_ptr();
 
+        let mut args: Vec<BasicMetadataValueEnum<'ink>> =
+            vec![type_info_ptr.into(), allocator_handle.into()];
+        args.extend(location.map(BasicMetadataValueEnum::from));
+
         // Safety: we can be sure that the new intrinsic returns a reference.
         let untyped_reference = self
             .builder
-            .build_call(
-                new_fn_ptr,
-                &[type_info_ptr.into(), allocator_handle.into()],
-                "ref",
-            )
+            .build_call(new_fn_ptr, &args, "ref")
             .try_as_basic_value()
             .left()
             .unwrap()
@@ -443,20 +722,79 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         reference.into()
     }
 
+    /// Generates a null-terminated string that describes the source location
+    /// of the allocating expression `expr`, e.g. `mod.mun:3:5`. Returns `None`
+    /// if allocation debugging is disabled.
+    fn gen_alloc_location(&self, expr: ExprId) -> Option<PointerValue<'ink>> {
+        if !self.alloc_debug {
+            return None;
+        }
+
+        let line_col = self
+            .hir_function
+            .expr_line_col(self.db, expr)
+            .unwrap_or_else(|| self.hir_function.line_col(self.db));
+        let path = self
+            .db
+            .file_relative_path(self.hir_function.file_id(self.db));
+        let location = format!(
+            "{}:{}:{}",
+            path.as_str(),
+            line_col.line + 1,
+            line_col.col_utf16 + 1
+        );
+
+        Some(
+            self.builder
+                .build_global_string_ptr(&location, "alloc_location")
+                .as_pointer_value(),
+        )
+    }
+
     /// Generates IR for a record literal, e.g. `Foo { a: 1.23, b: 4 }`
     fn gen_record_lit(
         &mut self,
         type_expr: ExprId,
         fields: &[mun_hir::RecordLitField],
-    ) -> BasicValueEnum<'ink> {
+        spread: Option<ExprId>,
+    ) -> Option<BasicValueEnum<'ink>> {
         let struct_ty = self.infer[type_expr].clone();
         let hir_struct = struct_ty.as_struct().unwrap(); // Can only really get here if the type is a struct
-        let fields: Vec<BasicValueEnum<'ink>> = fields
-            .iter()
-            .map(|field| self.gen_expr(field.expr).expect("expected a field value"))
+
+        // The fields are evaluated in the order in which they are written, followed by
+        // the base expression
+        let mut values = Vec::with_capacity(fields.len());
+        for field in fields {
+            values.push((&field.name, self.gen_expr(field.expr)?));
+        }
+        let base = match spread {
+            Some(spread) => {
+                let base = self.gen_expr(spread)?;
+                Some(self.opt_deref_value(spread, base).into_struct_value())
+            }
+            None => None,
+        };
+
+        // Fields that are not written out are copied from the base expression
+        let hir_struct_name = hir_struct.name(self.db);
+        let args = hir_struct
+            .fields(self.db)
+            .into_iter()
+            .map(|field| {
+                let name = field.name(self.db);
+                values
+                    .iter()
+                    .find_map(|(field_name, value)| (**field_name == name).then_some(*value))
+                    .or_else(|| {
+                        let field_ir_name = &format!("{hir_struct_name}.{name}");
+                        self.builder
+                            .build_extract_value(base?, field.index(self.db), field_ir_name)
+                    })
+                    .expect("expected a field value")
+            })
             .collect();
 
-        self.gen_struct_alloc(hir_struct, fields)
+        Some(self.gen_struct_alloc(type_expr, hir_struct, args))
     }
 
     /// Generates IR for a named tuple literal, e.g. `Foo(1.23, 4)`
@@ -468,14 +806,14 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             .map(|expr| self.gen_expr(*expr).expect("expected a field value"))
             .collect();
 
-        self.gen_struct_alloc(hir_struct, args)
+        self.gen_struct_alloc(type_expr, hir_struct, args)
     }
 
     /// Generates IR for a unit struct literal, e.g `Foo`
     fn gen_unit_struct_lit(&mut self, type_expr: ExprId) -> BasicValueEnum<'ink> {
         let struct_ty = self.infer[type_expr].clone();
         let hir_struct = struct_ty.as_struct().unwrap(); // Can only really get here if the type is a struct
-        self.gen_struct_alloc(hir_struct, Vec::new())
+        self.gen_struct_alloc(type_expr, hir_struct, Vec::new())
     }
 
     /// Generates IR for the specified block expression.
@@ -495,6 +833,17 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                         return None;
                     }
                 }
+                Statement::Static {
+                    pat,
+                    initializer,
+                    link_sections,
+                    ..
+                } => {
+                    let section = LinkSection::section_name(link_sections);
+                    if !self.gen_static_statement(*pat, *initializer, section) {
+                        return None;
+                    }
+                }
                 Statement::Expr(expr) => {
                     // No need to generate code after a statement that has a `never` return type.
                     self.gen_expr(*expr)?;
@@ -525,6 +874,36 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         temp_builder
     }
 
+    /// Describes the variable that is bound by `pat` and stored in `ptr`, if
+    /// debug information is emitted.
+    fn gen_variable_debug_info(
+        &self,
+        pat: PatId,
+        name: &str,
+        arg_no: Option<u32>,
+        ptr: PointerValue<'ink>,
+    ) {
+        let Some((debug_info, function)) = self.debug_info else {
+            return;
+        };
+        if let Some(line_col) = self.hir_function.pat_line_col(self.db, pat) {
+            let block = self
+                .builder
+                .get_insert_block()
+                .expect("the builder must be positioned in a block");
+            debug_info.gen_variable(
+                self.db,
+                function,
+                name,
+                arg_no,
+                &self.infer[pat],
+                line_col,
+                ptr,
+                block,
+            );
+        }
+    }
+
     /// Generate IR for a let statement: `let a:int = 3`. Returns `false` if the
     /// initializer of the statement never returns; `true` otherwise.
     fn gen_let_statement(&mut self, pat: PatId, initializer: Option<ExprId>) -> bool {
@@ -540,6 +919,10 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         };
 
         match &self.body[pat] {
+            Pat::Bind { name } if self.infer[pat].is_empty() => {
+                // Unit values are zero-sized and don't need any storage
+                self.pat_to_name.insert(pat, name.to_string());
+            }
             Pat::Bind { name } => {
                 let builder = self.new_alloca_builder();
                 let pat_ty = self.infer[pat].clone();
@@ -548,9 +931,10 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                     .get_basic_type(&pat_ty)
                     .expect("expected basic type");
                 let ptr = builder.build_alloca(ty, &name.to_string());
+                self.gen_variable_debug_info(pat, &name.to_string(), None, ptr);
                 self.pat_to_local.insert(pat, ptr);
                 self.pat_to_name.insert(pat, name.to_string());
-                if !(pat_ty.is_empty() || pat_ty.is_never()) {
+                if !pat_ty.is_never() {
                     if let Some(value) = initializer {
                         self.builder.build_store(ptr, value);
                     };
@@ -562,6 +946,174 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         true
     }
 
+    /// Generates IR for a function-local static: `static mut N: i64 = 0;`. The
+    /// value of the static is stored in a global. If the initializer is a
+    /// literal it is used as the initial value of the global. Otherwise, the
+    /// initializer is evaluated the first time the statement is reached,
+    /// guarded by an atomic initialization state so that only one thread
+    /// evaluates it while other threads wait for it to finish. A recursive
+    /// call that reaches the statement while its initializer is being
+    /// evaluated never finishes.
+    ///
+    /// Returns `false` if the initializer of the statement never returns;
+    /// `true` otherwise.
+    fn gen_static_statement(
+        &mut self,
+        pat: PatId,
+        initializer: ExprId,
+        section: Option<&str>,
+    ) -> bool {
+        let name = match &self.body[pat] {
+            Pat::Bind { name } => name.to_string(),
+            Pat::Wild => String::from("_"),
+            Pat::Missing | Pat::Path(_) => unreachable!(),
+        };
+        let pat_ty = self.infer[pat].clone();
+        let ty = self
+            .hir_types
+            .get_basic_type(&pat_ty)
+            .expect("expected basic type");
+
+        let value_global = self.module.add_global(
+            ty,
+            None,
+            &format!("{}::{}", self.hir_function.full_name(self.db), name),
+        );
+        value_global.set_linkage(Linkage::Internal);
+        value_global.set_section(section);
+        let value_ptr = value_global.as_pointer_value();
+        let value_name = value_ptr.get_name().to_string_lossy().into_owned();
+
+        let init_state = if let Expr::Literal(_) = &self.body[initializer] {
+            let value = self.gen_expr(initializer).expect("literals always return");
+            value_global.set_initializer(&value);
+            None
+        } else {
+            value_global.set_initializer(&ty.const_zero());
+            match self.gen_static_init(value_ptr, &value_name, initializer) {
+                Some(init_state) => Some(init_state),
+                None => return false,
+            }
+        };
+
+        self.pat_to_local.insert(pat, value_ptr);
+        self.pat_to_name.insert(pat, name);
+        self.statics.push(LocalStatic {
+            name: value_name,
+            ty: pat_ty,
+            init_state,
+        });
+        true
+    }
+
+    /// Generates IR that evaluates `initializer` and stores it in the static
+    /// pointed to by `value_ptr`, unless that already happened. Returns the
+    /// name of the global that holds the initialization state of the static,
+    /// or `None` if the initializer never returns.
+    fn gen_static_init(
+        &mut self,
+        value_ptr: PointerValue<'ink>,
+        value_name: &str,
+        initializer: ExprId,
+    ) -> Option<String> {
+        let state_type = self.context.i8_type();
+        let uninitialized = state_type.const_int(abi::STATIC_UNINITIALIZED.into(), false);
+        let initializing = state_type.const_int(abi::STATIC_INITIALIZING.into(), false);
+        let initialized = state_type.const_int(abi::STATIC_INITIALIZED.into(), false);
+
+        let state_global =
+            self.module
+                .add_global(state_type, None, &format!("{value_name}.init_state"));
+        state_global.set_linkage(Linkage::Internal);
+        state_global.set_initializer(&uninitialized);
+        let state_ptr = state_global.as_pointer_value();
+
+        let acquire_block = self
+            .context
+            .append_basic_block(self.fn_value, "static_acquire");
+        let init_block = self
+            .context
+            .append_basic_block(self.fn_value, "static_init");
+        let wait_block = self
+            .context
+            .append_basic_block(self.fn_value, "static_wait");
+        let done_block = self
+            .context
+            .append_basic_block(self.fn_value, "static_done");
+
+        // Fast path: the static has already been initialized
+        let is_initialized = self.gen_static_is_initialized(state_ptr, initialized);
+        self.builder
+            .build_conditional_branch(is_initialized, done_block, acquire_block);
+
+        // Try to become the thread that initializes the static
+        self.builder.position_at_end(acquire_block);
+        let exchange = self
+            .builder
+            .build_cmpxchg(
+                state_ptr,
+                uninitialized,
+                initializing,
+                AtomicOrdering::AcquireRelease,
+                AtomicOrdering::Acquire,
+            )
+            .expect("invalid cmpxchg operands");
+        let acquired = self
+            .builder
+            .build_extract_value(exchange, 1, "acquired")
+            .expect("cmpxchg returns a pair")
+            .into_int_value();
+        self.builder
+            .build_conditional_branch(acquired, init_block, wait_block);
+
+        // Another thread is initializing the static, wait for it to finish
+        self.builder.position_at_end(wait_block);
+        let is_initialized = self.gen_static_is_initialized(state_ptr, initialized);
+        self.builder
+            .build_conditional_branch(is_initialized, done_block, wait_block);
+
+        // Evaluate the initializer and publish its value
+        self.builder.position_at_end(init_block);
+        let Some(value) = self.gen_expr(initializer) else {
+            // The initializer never returns, so neither is the static ever
+            // initialized
+            self.builder.position_at_end(done_block);
+            self.builder.build_unreachable();
+            return None;
+        };
+        self.builder.build_store(value_ptr, value);
+        self.builder
+            .build_store(state_ptr, initialized)
+            .set_atomic_ordering(AtomicOrdering::Release)
+            .expect("stores can be atomic");
+        self.builder.build_unconditional_branch(done_block);
+
+        self.builder.position_at_end(done_block);
+        Some(state_ptr.get_name().to_string_lossy().into_owned())
+    }
+
+    /// Generates IR that atomically loads the initialization state of a
+    /// static and checks whether the static has been initialized.
+    fn gen_static_is_initialized(
+        &self,
+        state_ptr: PointerValue<'ink>,
+        initialized: IntValue<'ink>,
+    ) -> IntValue<'ink> {
+        let state = self.builder.build_load(state_ptr, "init_state");
+        let load = state
+            .as_instruction_value()
+            .expect("a load is an instruction");
+        load.set_alignment(1).expect("loads have an alignment");
+        load.set_atomic_ordering(AtomicOrdering::Acquire)
+            .expect("loads can be atomic");
+        self.builder.build_int_compare(
+            IntPredicate::EQ,
+            state.into_int_value(),
+            initialized,
+            "is_initialized",
+        )
+    }
+
     /// Generates IR for looking up a certain path expression.
     fn gen_path_expr(
         &mut self,
@@ -575,7 +1127,9 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             .0
         {
             ValueNs::LocalBinding(pat) => {
-                if let Some(param) = self.pat_to_param.get(&pat) {
+                if self.infer[pat].is_empty() {
+                    self.gen_empty()
+                } else if let Some(param) = self.pat_to_param.get(&pat) {
                     *param
                 } else if let Some(ptr) = self.pat_to_local.get(&pat) {
                     let name = self.pat_to_name.get(&pat).expect("could not find pat name");
@@ -585,7 +1139,52 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 }
             }
             ValueNs::StructId(_) => self.gen_unit_struct_lit(expr),
-            ValueNs::FunctionId(_) => panic!("unable to generate path expression from a function"),
+            ValueNs::FunctionId(_) => {
+                let function = self.infer[expr]
+                    .as_callable_def()
+                    .and_then(|def| match def {
+                        mun_hir::CallableDef::Function(function) => Some(function),
+                        mun_hir::CallableDef::Struct(_) => None,
+                    })
+                    .expect("expected a function");
+                self.gen_function_address(function).into()
+            }
+            ValueNs::BuiltinConstant(constant) => {
+                let value = constant.value(&self.db.target_data_layout());
+                self.hir_types.get_const_value(&self.infer[expr], value)
+            }
+            ValueNs::ConstId(id) => {
+                let value = mun_hir::Const::from(id)
+                    .value(self.db)
+                    .expect("constant could not be evaluated");
+                self.hir_types.get_const_value(&self.infer[expr], value)
+            }
+        }
+    }
+
+    /// Generates IR for the address of the specified function. The addresses of
+    /// functions that are used as a value are read from the dispatch table,
+    /// which contains the addresses that the runtime updates when functions
+    /// are hot reloaded.
+    fn gen_function_address(&mut self, function: mun_hir::Function) -> PointerValue<'ink> {
+        if self.dispatch_table.contains(function) {
+            self.dispatch_table.gen_function_ptr_lookup(
+                self.db,
+                self.external_globals.dispatch_table,
+                &self.builder,
+                function,
+            )
+        } else {
+            self.function_map
+                .get(&function)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "missing function value for mun_hir function: '{}'",
+                        function.name(self.db),
+                    )
+                })
+                .as_global_value()
+                .as_pointer_value()
         }
     }
 
@@ -622,7 +1221,10 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 .pat_to_local
                 .get(&pat)
                 .expect("unresolved local binding"),
-            ValueNs::FunctionId(_) | ValueNs::StructId(_) => {
+            ValueNs::FunctionId(_)
+            | ValueNs::StructId(_)
+            | ValueNs::BuiltinConstant(_)
+            | ValueNs::ConstId(_) => {
                 panic!("no support for module definitions")
             }
         }
@@ -641,6 +1243,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             TyKind::Bool => self.gen_binary_op_bool(lhs, rhs, op),
             TyKind::Float(_) => self.gen_binary_op_float(lhs, rhs, op),
             TyKind::Int(ty) => self.gen_binary_op_int(lhs, rhs, op, ty.signedness),
+            TyKind::Tuple(0, _) => self.gen_binary_op_unit(rhs, op),
             TyKind::Struct(s) => {
                 if s.data(self.db.upcast()).memory_kind == mun_hir::StructMemoryKind::Value {
                     self.gen_binary_op_value_struct(lhs, rhs, op)
@@ -661,6 +1264,23 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
 
     /// Generates IR to calculate a unary operation on an expression.
     fn gen_unary_op(&mut self, expr: ExprId, op: UnaryOp) -> Option<BasicValueEnum<'ink>> {
+        if op == UnaryOp::AddrOf {
+            // Taking the address of an array borrows its elements as a slice
+            if let Some(element_ty) = self.infer[expr].as_array() {
+                let element_ty = element_ty.clone();
+                return self.gen_slice(expr, &element_ty);
+            }
+
+            // The value of a function path is already the address of the function
+            return self.gen_expr(expr);
+        }
+
+        // A mutable reference is a raw pointer to the referenced value
+        if op == UnaryOp::Deref {
+            let ptr = self.gen_expr(expr)?.into_pointer_value();
+            return Some(self.builder.build_load(ptr, "deref"));
+        }
+
         let ty = &self.infer[expr];
         match ty.interned() {
             TyKind::Float(_) => self.gen_unary_op_float(expr, op),
@@ -670,6 +1290,126 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         }
     }
 
+    /// Generates IR to convert a numeric value to the numeric type of `expr`.
+    /// Like in Rust, floating-point values that are out of the range of an
+    /// integer type saturate to its bounds and NaN converts to zero.
+    fn gen_cast(&mut self, expr: ExprId, operand: ExprId) -> Option<BasicValueEnum<'ink>> {
+        let value = self.gen_expr(operand)?;
+        let value = match (self.infer[operand].interned(), self.infer[expr].interned()) {
+            (TyKind::Int(from), TyKind::Int(to)) => {
+                let value = value.into_int_value();
+                let to_type = self.hir_types.get_int_type(*to);
+                let from_bits = value.get_type().get_bit_width();
+                match from_bits.cmp(&to_type.get_bit_width()) {
+                    cmp::Ordering::Less if from.signedness == Signedness::Signed => {
+                        self.builder.build_int_s_extend(value, to_type, "cast")
+                    }
+                    cmp::Ordering::Less => self.builder.build_int_z_extend(value, to_type, "cast"),
+                    cmp::Ordering::Greater => {
+                        self.builder.build_int_truncate(value, to_type, "cast")
+                    }
+                    cmp::Ordering::Equal => value,
+                }
+                .into()
+            }
+            (TyKind::Int(from), TyKind::Float(to)) => {
+                let value = value.into_int_value();
+                let to_type = self.hir_types.get_float_type(*to);
+                match from.signedness {
+                    Signedness::Signed => self
+                        .builder
+                        .build_signed_int_to_float(value, to_type, "cast"),
+                    Signedness::Unsigned => self
+                        .builder
+                        .build_unsigned_int_to_float(value, to_type, "cast"),
+                }
+                .into()
+            }
+            (TyKind::Float(_), TyKind::Float(to)) => {
+                let to_type = self.hir_types.get_float_type(*to);
+                self.builder
+                    .build_float_cast(value.into_float_value(), to_type, "cast")
+                    .into()
+            }
+            (TyKind::Float(_), TyKind::Int(to)) => {
+                let to_type = self.hir_types.get_int_type(*to);
+                let name = match to.signedness {
+                    Signedness::Signed => "llvm.fptosi.sat",
+                    Signedness::Unsigned => "llvm.fptoui.sat",
+                };
+                let conversion = LlvmIntrinsic::find(name)
+                    .and_then(|intrinsic| {
+                        intrinsic.get_declaration(self.module, &[to_type.into(), value.get_type()])
+                    })
+                    .expect("saturating float to int conversions are supported by LLVM");
+                self.builder
+                    .build_call(conversion, &[value.into()], "cast")
+                    .try_as_basic_value()
+                    .left()
+                    .expect("conversion must return a value")
+            }
+            _ => unreachable!("only numeric values can be cast"),
+        };
+        Some(value)
+    }
+
+    /// Generates IR to borrow the elements of an array as a slice. The array
+    /// is rooted until the call that receives the slice returns, because the
+    /// garbage collector does not know about the slice.
+    fn gen_slice(&mut self, array_expr: ExprId, element_ty: &Ty) -> Option<BasicValueEnum<'ink>> {
+        // Safety: the type of the expression is an array
+        let array = unsafe {
+            RuntimeArrayValue::from_ptr_unchecked(self.gen_expr(array_expr)?.into_pointer_value())
+        };
+
+        let array_ptr: PointerValue<'ink> = array.into();
+        self.gen_root_call(&intrinsics::root, array_ptr);
+        self.slice_roots.push(array_ptr);
+
+        let elements = array.get_elements(&self.builder);
+        let length = self
+            .builder
+            .build_load(array.get_length_ptr(&self.builder), "length");
+
+        let slice_ty = self.hir_types.get_slice_type(element_ty);
+        let slice = self
+            .builder
+            .build_insert_value(slice_ty.get_undef(), elements, 0, "")
+            .expect("could not set `ptr` of slice");
+        let slice = self
+            .builder
+            .build_insert_value(slice, length, 1, "slice")
+            .expect("could not set `len` of slice");
+        Some(slice.into_struct_value().into())
+    }
+
+    /// Generates a call to the `root` or `unroot` intrinsic for the specified
+    /// array.
+    fn gen_root_call(&mut self, intrinsic: &impl Intrinsic, array_ptr: PointerValue<'ink>) {
+        let root_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
+            self.external_globals.dispatch_table,
+            &self.builder,
+            intrinsic,
+        );
+
+        // An object pointer is stored in a `*const *mut std::ffi::c_void` to make it type
+        // agnostic.
+        let untyped_array_ptr = self.builder.build_bitcast(
+            array_ptr,
+            self.context
+                .i8_type()
+                .ptr_type(AddressSpace::default())
+                .ptr_type(AddressSpace::default()),
+            "",
+        );
+        let allocator_handle = self.get_allocator_handle_ptr();
+        self.builder.build_call(
+            root_fn_ptr,
+            &[untyped_array_ptr.into(), allocator_handle.into()],
+            "",
+        );
+    }
+
     /// Generates IR to calculate a unary operation on a floating point value.
     fn gen_unary_op_float(&mut self, expr: ExprId, op: UnaryOp) -> Option<BasicValueEnum<'ink>> {
         let value: FloatValue<'ink> = self
@@ -680,6 +1420,9 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         match op {
             UnaryOp::Neg => Some(self.builder.build_float_neg(value, "neg").into()),
             UnaryOp::Not => unimplemented!("Operator {:?} is not implemented for float", op),
+            UnaryOp::AddrOf | UnaryOp::Deref => {
+                unreachable!("`{:?}` is generated by `gen_unary_op`", op)
+            }
         }
     }
 
@@ -704,7 +1447,9 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 }
             }
             UnaryOp::Not => Some(self.builder.build_not(value, "not").into()),
-            //_ => unimplemented!("Operator {:?} is not implemented for integer", op),
+            UnaryOp::AddrOf | UnaryOp::Deref => {
+                unreachable!("`{:?}` is generated by `gen_unary_op`", op)
+            }
         }
     }
 
@@ -718,6 +1463,9 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         match op {
             UnaryOp::Not => Some(self.builder.build_not(value, "not").into()),
             UnaryOp::Neg => unimplemented!("Operator {:?} is not implemented for boolean", op),
+            UnaryOp::AddrOf | UnaryOp::Deref => {
+                unreachable!("`{:?}` is generated by `gen_unary_op`", op)
+            }
         }
     }
 
@@ -913,6 +1661,21 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         }
     }
 
+    /// Generates IR for a binary operation on unit values. Only assignment is
+    /// supported, which evaluates the right hand side but doesn't store
+    /// anything because unit values are zero-sized.
+    fn gen_binary_op_unit(
+        &mut self,
+        rhs_expr: ExprId,
+        op: BinaryOp,
+    ) -> Option<BasicValueEnum<'ink>> {
+        self.gen_expr(rhs_expr)?;
+        match op {
+            BinaryOp::Assignment { op: None } => Some(self.gen_empty()),
+            _ => unimplemented!("Operator {:?} is not implemented for unit", op),
+        }
+    }
+
     fn gen_arith_bin_op_bool(
         &mut self,
         lhs: IntValue<'ink>,
@@ -1068,6 +1831,12 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
                 name,
             } => self.gen_place_field(expr, *receiver_expr, name),
             Expr::Index { base, index } => self.gen_place_index(expr, *base, *index),
+            Expr::UnaryOp {
+                expr: ref_expr,
+                op: UnaryOp::Deref,
+            } => self
+                .gen_expr(*ref_expr)
+                .map(BasicValueEnum::into_pointer_value),
             _ => unreachable!("invalid place expression"),
         }
     }
@@ -1119,6 +1888,105 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         }
     }
 
+    /// Converts the result of a call into the value of the call expression.
+    fn call_result(&self, expr: ExprId, call: CallSiteValue<'ink>) -> Option<BasicValueEnum<'ink>> {
+        call.try_as_basic_value()
+            .left()
+            // If the called function is a void function it doesn't return anything.
+            // If this method (`gen_expr`) returns None we assume the return value
+            // is `never`. We return a const unit struct here to ensure that at
+            // least something is returned. This matches with the mun_hir where a
+            // `nothing` is returned instead of a `never`.
+            //
+            // This unit value will also be optimized out.
+            .or_else(|| match self.infer[expr].interned() {
+                TyKind::Never => None,
+                _ => Some(self.context.const_struct(&[], false).into()),
+            })
+    }
+
+    /// Returns true if `expr` is a call of the function to itself.
+    fn is_recursive_call(&self, expr: ExprId) -> bool {
+        match &self.body[expr] {
+            Expr::Call { callee, .. } => {
+                self.infer[*callee].as_callable_def()
+                    == Some(mun_hir::CallableDef::Function(self.hir_function))
+            }
+            _ => false,
+        }
+    }
+
+    /// Generates IR for a call in tail position of a `#[tail_call]` function
+    /// to another function. The function is left before the call is made, and
+    /// the result of the call is returned immediately. The call is marked as
+    /// a tail call, which allows LLVM to reuse the stack frame of the caller
+    /// for the callee, whether the callee is called directly or through the
+    /// dispatch table.
+    fn gen_tail_call(
+        &mut self,
+        expr: ExprId,
+        gen_call: impl FnOnce(&mut Self) -> CallSiteValue<'ink>,
+    ) -> Option<BasicValueEnum<'ink>> {
+        self.gen_timing_call(&intrinsics::timing_exit);
+        let call = gen_call(self);
+        call.set_tail_call(true);
+        match call.try_as_basic_value().left() {
+            Some(value) => self.builder.build_return(Some(&value)),
+            None => self.builder.build_return(None),
+        };
+
+        let value = self.call_result(expr, call);
+        self.continue_after_tail_call(expr, value)
+    }
+
+    /// Generates IR for a call in tail position of a `#[tail_call]` function
+    /// to itself. The parameters of the function are reassigned with the
+    /// arguments of the call, after which the body of the function is
+    /// restarted. This guarantees that recursion through tail calls runs in
+    /// constant stack space, regardless of the optimization level.
+    fn gen_recursive_tail_call(
+        &mut self,
+        expr: ExprId,
+        args: &[BasicValueEnum<'ink>],
+    ) -> Option<BasicValueEnum<'ink>> {
+        // All arguments have been evaluated before any parameter is reassigned
+        for ((pat, _), arg) in self.body.params().iter().zip(args) {
+            if let Some(param_ptr) = self.pat_to_local.get(pat) {
+                self.builder.build_store(*param_ptr, *arg);
+            }
+        }
+        let tail_call_block = self
+            .tail_call_block
+            .expect("missing block for recursive tail calls");
+        self.builder.build_unconditional_branch(tail_call_block);
+
+        let value = match self.fn_value.get_type().get_return_type() {
+            Some(ty) => ty.const_zero(),
+            None => self.gen_empty(),
+        };
+        self.continue_after_tail_call(expr, Some(value))
+    }
+
+    /// Positions the builder in an unreachable block after a tail call has
+    /// left the function, so the enclosing expressions can still use the
+    /// value of the call. If the call never returns, there is no value and
+    /// the enclosing expressions are not continued.
+    fn continue_after_tail_call(
+        &mut self,
+        expr: ExprId,
+        value: Option<BasicValueEnum<'ink>>,
+    ) -> Option<BasicValueEnum<'ink>> {
+        if self.infer[expr].is_never() {
+            return None;
+        }
+
+        let block = self
+            .context
+            .append_basic_block(self.fn_value, "after_tail_call");
+        self.builder.position_at_end(block);
+        value
+    }
+
     /// Generates IR for an if statement.
     fn gen_if(
         &mut self,
@@ -1208,8 +2076,11 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
     ) -> Option<BasicValueEnum<'ink>> {
         let ret_value = ret_expr.and_then(|expr| self.gen_expr(expr));
 
-        // Construct a return statement from the returned value of the body
-        if let Some(value) = ret_value {
+        // Construct a return statement from the returned value of the body. A
+        // unit value is returned from a void function.
+        self.gen_timing_call(&intrinsics::timing_exit);
+        let returns_unit = ret_expr.map_or(true, |ret_expr| self.infer[ret_expr].is_empty());
+        if let Some(value) = ret_value.filter(|_| !returns_unit) {
             self.builder.build_return(Some(&value));
         } else {
             self.builder.build_return(None);
@@ -1218,10 +2089,24 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         None
     }
 
+    /// Returns the loop with the specified label, or the innermost loop if
+    /// there is no label.
+    fn target_loop(&mut self, label: Option<LabelId>) -> &mut LoopInfo<'ink> {
+        let loop_info = match label {
+            Some(label) => self
+                .active_loops
+                .iter_mut()
+                .rfind(|loop_info| loop_info.label == Some(label)),
+            None => self.active_loops.last_mut(),
+        };
+        loop_info.expect("`break` or `continue` outside of a loop")
+    }
+
     fn gen_break(
         &mut self,
         _expr: ExprId,
         break_expr: Option<ExprId>,
+        label: Option<LabelId>,
     ) -> Option<BasicValueEnum<'ink>> {
         if let Some(expr) = break_expr {
             // There is an expression
@@ -1231,18 +2116,18 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
 
             // If the expression never returns, we can stop what we're doing.
             if let Some(break_value) = break_value {
-                let loop_info = self.active_loop.as_mut().unwrap();
-                loop_info.break_values.push(Some((
-                    break_value,
-                    self.builder.get_insert_block().unwrap(),
-                )));
+                let insert_block = self.builder.get_insert_block().unwrap();
+                let loop_info = self.target_loop(label);
+                loop_info
+                    .break_values
+                    .push(Some((break_value, insert_block)));
                 self.builder
                     .build_unconditional_branch(loop_info.exit_block);
             }
         } else {
             // If the break expression doesnt contain a break statement. Add a none to the
             // break values.
-            let loop_info = self.active_loop.as_mut().unwrap();
+            let loop_info = self.target_loop(label);
             loop_info.break_values.push(None);
             self.builder
                 .build_unconditional_branch(loop_info.exit_block);
@@ -1254,7 +2139,9 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
     fn gen_loop_block_expr(
         &mut self,
         block: ExprId,
+        label: Option<LabelId>,
         exit_block: BasicBlock<'ink>,
+        continue_block: BasicBlock<'ink>,
     ) -> (
         BasicBlock<'ink>,
         BreakSources<'ink>,
@@ -1262,12 +2149,13 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
     ) {
         // Build a new loop info struct
         let loop_info = LoopInfo {
+            label,
             exit_block,
             break_values: Vec::new(),
+            continue_block,
         };
 
-        // Replace previous loop info
-        let prev_loop = std::mem::replace(&mut self.active_loop, Some(loop_info));
+        self.active_loops.push(loop_info);
 
         // Start generating code inside the loop
         let value = self.gen_expr(block);
@@ -1275,7 +2163,8 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         let LoopInfo {
             exit_block,
             break_values,
-        } = std::mem::replace(&mut self.active_loop, prev_loop).unwrap();
+            ..
+        } = self.active_loops.pop().unwrap();
 
         (exit_block, break_values, value)
     }
@@ -1285,6 +2174,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         _expr: ExprId,
         condition_expr: ExprId,
         body_expr: ExprId,
+        label: Option<LabelId>,
     ) -> Option<BasicValueEnum<'ink>> {
         let context = self.context;
         let cond_block = context.append_basic_block(self.fn_value, "whilecond");
@@ -1296,16 +2186,7 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
 
         // Generate condition block
         self.builder.position_at_end(cond_block);
-        let condition_ir = self
-            .gen_expr(condition_expr)
-            .map(|value| self.opt_deref_value(condition_expr, value));
-        if let Some(condition_ir) = condition_ir {
-            self.builder.build_conditional_branch(
-                condition_ir.into_int_value(),
-                loop_block,
-                exit_block,
-            );
-        } else {
+        if !self.gen_condition_branch(condition_expr, loop_block, exit_block) {
             // If the condition doesn't return a value, we also immediately return without a
             // value. This can happen if the expression is a `never` expression.
             return None;
@@ -1313,7 +2194,8 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
 
         // Generate loop block
         self.builder.position_at_end(loop_block);
-        let (exit_block, _, value) = self.gen_loop_block_expr(body_expr, exit_block);
+        let (exit_block, _, value) =
+            self.gen_loop_block_expr(body_expr, label, exit_block, cond_block);
         if value.is_some() {
             self.builder.build_unconditional_branch(cond_block);
         }
@@ -1324,7 +2206,193 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         Some(self.gen_empty())
     }
 
-    fn gen_loop(&mut self, _expr: ExprId, body_expr: ExprId) -> Option<BasicValueEnum<'ink>> {
+    /// Generates IR for a `for` loop over the elements of an array. The length
+    /// of the array is read once, when the loop is entered. Every element is
+    /// loaded from the array at the start of its iteration, so assignments to
+    /// elements that have not been visited yet are observed by the loop.
+    fn gen_for(
+        &mut self,
+        _expr: ExprId,
+        pat: PatId,
+        iterable_expr: ExprId,
+        body_expr: ExprId,
+        label: Option<LabelId>,
+    ) -> Option<BasicValueEnum<'ink>> {
+        // Safety: type inference ensures that the iterable is an array
+        let array = unsafe {
+            RuntimeArrayValue::from_ptr_unchecked(
+                self.gen_expr(iterable_expr)?.into_pointer_value(),
+            )
+        };
+        let length = self
+            .builder
+            .build_load(array.get_length_ptr(&self.builder), "length")
+            .into_int_value();
+
+        let index_ty = length.get_type();
+        let index_ptr = self.new_alloca_builder().build_alloca(index_ty, "index");
+        self.builder.build_store(index_ptr, index_ty.const_zero());
+
+        // Allocate storage for the element that is bound in every iteration
+        let element_ptr = match &self.body[pat] {
+            Pat::Bind { name } if self.infer[pat].is_empty() => {
+                // Unit values are zero-sized and don't need any storage
+                self.pat_to_name.insert(pat, name.to_string());
+                None
+            }
+            Pat::Bind { name } => {
+                let ty = self
+                    .hir_types
+                    .get_basic_type(&self.infer[pat])
+                    .expect("expected basic type");
+                let ptr = self
+                    .new_alloca_builder()
+                    .build_alloca(ty, &name.to_string());
+                self.gen_variable_debug_info(pat, &name.to_string(), None, ptr);
+                self.pat_to_local.insert(pat, ptr);
+                self.pat_to_name.insert(pat, name.to_string());
+                Some(ptr)
+            }
+            Pat::Wild => None,
+            Pat::Missing | Pat::Path(_) => unreachable!(),
+        };
+
+        let context = self.context;
+        let cond_block = context.append_basic_block(self.fn_value, "forcond");
+        let loop_block = context.append_basic_block(self.fn_value, "for");
+        let step_block = context.append_basic_block(self.fn_value, "forstep");
+        let exit_block = context.append_basic_block(self.fn_value, "afterfor");
+
+        // Insert an explicit fall through from the current block to the condition check
+        self.builder.build_unconditional_branch(cond_block);
+
+        // Generate condition block
+        self.builder.position_at_end(cond_block);
+        let index = self.builder.build_load(index_ptr, "index").into_int_value();
+        let in_bounds =
+            self.builder
+                .build_int_compare(IntPredicate::ULT, index, length, "in_bounds");
+        self.builder
+            .build_conditional_branch(in_bounds, loop_block, exit_block);
+
+        // Generate loop block, which starts by binding the current element
+        self.builder.position_at_end(loop_block);
+        if let Some(element_ptr) = element_ptr {
+            let elements = array.get_elements(&self.builder);
+            let element = unsafe {
+                self.builder.build_gep(
+                    elements,
+                    &[index],
+                    &format!("{}+index", elements.get_name().to_string_lossy()),
+                )
+            };
+            let element = self.builder.build_load(element, "element");
+            self.builder.build_store(element_ptr, element);
+        }
+        let (exit_block, _, value) =
+            self.gen_loop_block_expr(body_expr, label, exit_block, step_block);
+        if value.is_some() {
+            self.builder.build_unconditional_branch(step_block);
+        }
+
+        // Generate step block, which advances to the next element
+        self.builder.position_at_end(step_block);
+        let index = self.builder.build_load(index_ptr, "index").into_int_value();
+        let next_index =
+            self.builder
+                .build_int_nuw_add(index, index_ty.const_int(1, false), "next_index");
+        self.builder.build_store(index_ptr, next_index);
+        self.builder.build_unconditional_branch(cond_block);
+
+        // Generate exit block
+        self.builder.position_at_end(exit_block);
+
+        Some(self.gen_empty())
+    }
+
+    /// Generates IR that branches to `then_block` if the boolean `condition` is
+    /// true and to `else_block` otherwise. Logical operators are lowered to
+    /// branches directly, instead of computing their value first, if skipping
+    /// the evaluation of their right-hand side is unobservable. Returns `false`
+    /// if the condition never returns.
+    fn gen_condition_branch(
+        &mut self,
+        condition: ExprId,
+        then_block: BasicBlock<'ink>,
+        else_block: BasicBlock<'ink>,
+    ) -> bool {
+        let body = self.body.clone();
+        match &body[condition] {
+            Expr::BinaryOp {
+                lhs,
+                rhs,
+                op: Some(BinaryOp::LogicOp(op)),
+            } if self.is_side_effect_free(*rhs) => {
+                let current_block = self.builder.get_insert_block().unwrap();
+                let rhs_block = self.context.insert_basic_block_after(current_block, "cond");
+                let (lhs_then_block, lhs_else_block) = match op {
+                    LogicOp::And => (rhs_block, else_block),
+                    LogicOp::Or => (then_block, rhs_block),
+                };
+                if !self.gen_condition_branch(*lhs, lhs_then_block, lhs_else_block) {
+                    rhs_block
+                        .remove_from_function()
+                        .expect("the rhs block must have a parent");
+                    return false;
+                }
+
+                // Move the block of the right-hand side after the blocks of the left-hand side
+                let current_block = self.builder.get_insert_block().unwrap();
+                rhs_block
+                    .move_after(current_block)
+                    .expect("programmer error, the rhs block is invalid");
+                self.builder.position_at_end(rhs_block);
+                self.gen_condition_branch(*rhs, then_block, else_block)
+            }
+            Expr::UnaryOp {
+                expr,
+                op: UnaryOp::Not,
+            } => self.gen_condition_branch(*expr, else_block, then_block),
+            _ => {
+                let Some(condition_ir) = self
+                    .gen_expr(condition)
+                    .map(|value| self.opt_deref_value(condition, value))
+                else {
+                    return false;
+                };
+                self.builder.build_conditional_branch(
+                    condition_ir.into_int_value(),
+                    then_block,
+                    else_block,
+                );
+                true
+            }
+        }
+    }
+
+    /// Returns true if evaluating the expression has no observable side
+    /// effects, which means that its evaluation can be skipped.
+    fn is_side_effect_free(&self, expr: ExprId) -> bool {
+        match &self.body[expr] {
+            Expr::Path(_) | Expr::Literal(_) => true,
+            Expr::Field { expr, .. } | Expr::UnaryOp { expr, .. } | Expr::Cast { expr, .. } => {
+                self.is_side_effect_free(*expr)
+            }
+            Expr::BinaryOp {
+                lhs,
+                rhs,
+                op: Some(BinaryOp::ArithOp(_) | BinaryOp::CmpOp(_) | BinaryOp::LogicOp(_)),
+            } => self.is_side_effect_free(*lhs) && self.is_side_effect_free(*rhs),
+            _ => false,
+        }
+    }
+
+    fn gen_loop(
+        &mut self,
+        expr: ExprId,
+        body_expr: ExprId,
+        label: Option<LabelId>,
+    ) -> Option<BasicValueEnum<'ink>> {
         let context = self.context;
         let loop_block = context.append_basic_block(self.fn_value, "loop");
         let exit_block = context.append_basic_block(self.fn_value, "exit");
@@ -1334,7 +2402,8 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
 
         // Generate the body of the loop
         self.builder.position_at_end(loop_block);
-        let (exit_block, break_values, value) = self.gen_loop_block_expr(body_expr, exit_block);
+        let (exit_block, break_values, value) =
+            self.gen_loop_block_expr(body_expr, label, exit_block, loop_block);
         if value.is_some() {
             self.builder.build_unconditional_branch(loop_block);
         }
@@ -1351,19 +2420,23 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             // Move the builder to the exit block
             self.builder.position_at_end(exit_block);
 
-            // If the break values contain values, (so there where `break x;` statements),
-            // generate a phi value. This then assumes that all breaks had
-            // values.
-            if let Some(Some((value, _))) = break_values.first() {
-                let phi = self.builder.build_phi(value.get_type(), "exit");
-                for (value, block) in break_values.into_iter().map(Option::unwrap) {
-                    phi.add_incoming(&[(&value, block)]);
+            // If the loop has a value, (so there where `break x;` statements), generate a
+            // phi value from the breaks that carry a value. Type inference ensures
+            // that breaks with and without a value are not mixed in such a loop.
+            let break_values: Vec<_> = break_values.into_iter().flatten().collect();
+            match break_values.first() {
+                Some((value, _)) if !self.infer[expr].is_empty() => {
+                    let phi = self.builder.build_phi(value.get_type(), "exit");
+                    for (value, block) in break_values {
+                        phi.add_incoming(&[(&value, block)]);
+                    }
+                    Some(phi.as_basic_value())
+                }
+                _ => {
+                    // Otherwise, in the case of `break;` (without an expression) or breaks with
+                    // an empty value, the return value is just empty.
+                    Some(self.gen_empty())
                 }
-                Some(phi.as_basic_value())
-            } else {
-                // Otherwise, in the case of `break;` (without an expression) the return value
-                // is just empty.
-                Some(self.gen_empty())
             }
         }
     }
@@ -1457,6 +2530,25 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         )
     }
 
+    /// Generates code to construct a tuple literal. Returns `None` if the code
+    /// generation for one of the elements never returns.
+    fn gen_tuple(&mut self, expr: ExprId, exprs: &[ExprId]) -> Option<BasicValueEnum<'ink>> {
+        let TyKind::Tuple(_, substs) = self.infer[expr].interned() else {
+            panic!("the type of a tuple literal expression must be a Tuple")
+        };
+        let tuple_ty = self.hir_types.get_tuple_type(substs);
+
+        let mut tuple: AggregateValueEnum<'ink> = tuple_ty.get_undef().into();
+        for (idx, expr) in exprs.iter().enumerate() {
+            let value = self.gen_expr(*expr)?;
+            tuple = self
+                .builder
+                .build_insert_value(tuple, value, idx as u32, "")
+                .expect("could not set tuple element");
+        }
+        Some(tuple.into_struct_value().into())
+    }
+
     /// Generates code to construct an array literal at runtime. Returns `None`
     /// if the code generation for the array literal never returns.
     fn gen_array(&mut self, expr: ExprId, exprs: &[ExprId]) -> Option<RuntimeArrayValue<'ink>> {
@@ -1465,11 +2557,20 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
             .as_array()
             .expect("the type of an array literal expression must be an Array");
 
-        let new_array_fn_ptr = self.dispatch_table.gen_intrinsic_lookup(
-            self.external_globals.dispatch_table,
-            &self.builder,
-            &intrinsics::new_array,
-        );
+        let location = self.gen_alloc_location(expr);
+        let new_array_fn_ptr = if location.is_some() {
+            self.dispatch_table.gen_intrinsic_lookup(
+                self.external_globals.dispatch_table,
+                &self.builder,
+                &intrinsics::new_array_with_location,
+            )
+        } else {
+            self.dispatch_table.gen_intrinsic_lookup(
+                self.external_globals.dispatch_table,
+                &self.builder,
+                &intrinsics::new_array,
+            )
+        };
 
         let type_info_ptr = self.type_table.gen_type_info_lookup(
             self.context,
@@ -1496,17 +2597,15 @@ impl<'db, 'ink, 't> BodyIrGenerator<'db, 'ink, 't> {
         // An object pointer adds an extra layer of indirection to allow for hot
         // reloading. To make it struct type agnostic, it is stored in a `*const
         // *mut std::ffi::c_void`.
+        let mut args: Vec<BasicMetadataValueEnum<'ink>> = vec![
+            type_info_ptr.into(),
+            length_value.into(),
+            allocator_handle.into(),
+        ];
+        args.extend(location.map(BasicMetadataValueEnum::from));
         let untyped_array_ptr = self
             .builder
-            .build_call(
-                new_array_fn_ptr,
-                &[
-                    type_info_ptr.into(),
-                    length_value.into(),
-                    allocator_handle.into(),
-                ],
-                "ref",
-            )
+            .build_call(new_array_fn_ptr, &args, "ref")
             .try_as_basic_value()
             .left()
             .unwrap()
@@ -0,0 +1,264 @@
+use inkwell::{
+    basic_block::BasicBlock,
+    builder::Builder,
+    context::Context,
+    debug_info::{
+        debug_metadata_version, AsDIScope, DICompileUnit, DIFile, DIFlags, DIFlagsConstants,
+        DILocation, DISubprogram, DIType, DWARFEmissionKind, DWARFSourceLanguage, DebugInfoBuilder,
+    },
+    module::{FlagBehavior, Module},
+    values::{FunctionValue, PointerValue},
+};
+use mun_hir::{line_index::LineCol, HirDatabase, HirDisplay, Signedness, Ty, TyKind};
+
+use crate::{code_gen::CodeGenContext, module_group::ModuleGroup};
+
+/// DWARF type encodings of the primitive types.
+const DW_ATE_BOOLEAN: u32 = 0x02;
+const DW_ATE_FLOAT: u32 = 0x04;
+const DW_ATE_SIGNED: u32 = 0x05;
+const DW_ATE_UNSIGNED: u32 = 0x08;
+
+/// The amount of debug information that is emitted for an assembly.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DebugInfo {
+    /// No debug information is emitted. This is the default.
+    #[default]
+    None,
+
+    /// Only line tables are emitted. This is enough to map machine code back
+    /// to source lines, e.g. in backtraces, but types and variables are not
+    /// described.
+    LinesOnly,
+
+    /// Line tables are emitted together with descriptions of the parameters
+    /// and local variables of functions.
+    Full,
+}
+
+impl DebugInfo {
+    fn emission_kind(self) -> DWARFEmissionKind {
+        match self {
+            DebugInfo::None => DWARFEmissionKind::None,
+            DebugInfo::LinesOnly => DWARFEmissionKind::LineTablesOnly,
+            DebugInfo::Full => DWARFEmissionKind::Full,
+        }
+    }
+}
+
+/// Generates the debug information of a single LLVM module.
+pub(crate) struct DebugInfoGenerator<'ink> {
+    context: &'ink Context,
+    level: DebugInfo,
+    builder: DebugInfoBuilder<'ink>,
+    compile_unit: DICompileUnit<'ink>,
+}
+
+/// The debug information of a function whose body is being generated.
+#[derive(Clone, Copy)]
+pub(crate) struct FunctionDebugInfo<'ink> {
+    subprogram: DISubprogram<'ink>,
+    file: DIFile<'ink>,
+}
+
+impl<'ink> DebugInfoGenerator<'ink> {
+    /// Constructs a generator that emits debug information of the specified
+    /// level of the code generation context into `module`. Returns `None` if
+    /// no debug information should be emitted.
+    pub fn new(
+        code_gen: &CodeGenContext<'_, 'ink>,
+        module: &Module<'ink>,
+        module_group: &ModuleGroup,
+    ) -> Option<Self> {
+        let level = code_gen.debug_info;
+        if level == DebugInfo::None {
+            return None;
+        }
+
+        let (builder, compile_unit) = module.create_debug_info_builder(
+            true,
+            DWARFSourceLanguage::C,
+            module_group.relative_file_path().as_str(),
+            "",
+            "mun",
+            false,
+            "",
+            0,
+            "",
+            level.emission_kind(),
+            0,
+            false,
+            false,
+            "",
+            "",
+        );
+
+        // Record the version of the debug information format in the module
+        let i32_type = code_gen.context.i32_type();
+        module.add_basic_value_flag(
+            "Debug Info Version",
+            FlagBehavior::Warning,
+            i32_type.const_int(debug_metadata_version() as u64, false),
+        );
+        if code_gen.db.target().options.is_like_windows {
+            module.add_basic_value_flag(
+                "CodeView",
+                FlagBehavior::Warning,
+                i32_type.const_int(1, false),
+            );
+        } else {
+            module.add_basic_value_flag(
+                "Dwarf Version",
+                FlagBehavior::Warning,
+                i32_type.const_int(4, false),
+            );
+        }
+
+        Some(DebugInfoGenerator {
+            context: code_gen.context,
+            level,
+            builder,
+            compile_unit,
+        })
+    }
+
+    /// Attaches a subprogram that describes the specified function to its IR
+    /// function.
+    pub fn gen_function(
+        &self,
+        db: &dyn HirDatabase,
+        hir_function: mun_hir::Function,
+        fn_value: FunctionValue<'ink>,
+    ) -> FunctionDebugInfo<'ink> {
+        let file = self
+            .builder
+            .create_file(db.file_relative_path(hir_function.file_id(db)).as_str(), "");
+        let subroutine_type = self
+            .builder
+            .create_subroutine_type(file, None, &[], DIFlags::ZERO);
+        let line = hir_function.line_col(db).line + 1;
+        let name = hir_function.name(db).to_string();
+        let subprogram = self.builder.create_function(
+            self.compile_unit.as_debug_info_scope(),
+            &name,
+            fn_value.get_name().to_str().ok(),
+            file,
+            line,
+            subroutine_type,
+            false,
+            true,
+            line,
+            DIFlags::ZERO,
+            false,
+        );
+        fn_value.set_subprogram(subprogram);
+
+        FunctionDebugInfo { subprogram, file }
+    }
+
+    /// Sets the location of the instructions that are built by `builder` to
+    /// the specified position in the source file of the function.
+    pub fn set_location(
+        &self,
+        builder: &Builder<'ink>,
+        function: FunctionDebugInfo<'ink>,
+        line_col: LineCol,
+    ) {
+        builder.set_current_debug_location(self.location(function, line_col));
+    }
+
+    /// Describes a parameter or local variable that is stored in `storage`.
+    /// Variables are only described if full debug information is emitted and
+    /// the type of the variable is a primitive type.
+    #[allow(clippy::too_many_arguments)]
+    pub fn gen_variable(
+        &self,
+        db: &dyn HirDatabase,
+        function: FunctionDebugInfo<'ink>,
+        name: &str,
+        arg_no: Option<u32>,
+        ty: &Ty,
+        line_col: LineCol,
+        storage: PointerValue<'ink>,
+        block: BasicBlock<'ink>,
+    ) {
+        if self.level != DebugInfo::Full {
+            return;
+        }
+        let Some(ty) = self.gen_type(db, ty) else {
+            return;
+        };
+
+        let scope = function.subprogram.as_debug_info_scope();
+        let line = line_col.line + 1;
+        let variable = match arg_no {
+            Some(arg_no) => self.builder.create_parameter_variable(
+                scope,
+                name,
+                arg_no,
+                function.file,
+                line,
+                ty,
+                true,
+                DIFlags::ZERO,
+            ),
+            None => self.builder.create_auto_variable(
+                scope,
+                name,
+                function.file,
+                line,
+                ty,
+                true,
+                DIFlags::ZERO,
+                0,
+            ),
+        };
+        self.builder.insert_declare_at_end(
+            storage,
+            Some(variable),
+            None,
+            self.location(function, line_col),
+            block,
+        );
+    }
+
+    /// Resolves all temporary debug information. This must be called after
+    /// all function bodies have been generated.
+    pub fn finalize(&self) {
+        self.builder.finalize();
+    }
+
+    fn location(&self, function: FunctionDebugInfo<'ink>, line_col: LineCol) -> DILocation<'ink> {
+        self.builder.create_debug_location(
+            self.context,
+            line_col.line + 1,
+            line_col.col_utf16 + 1,
+            function.subprogram.as_debug_info_scope(),
+            None,
+        )
+    }
+
+    /// Describes a primitive type. Returns `None` for all other types.
+    fn gen_type(&self, db: &dyn HirDatabase, ty: &Ty) -> Option<DIType<'ink>> {
+        let encoding = match ty.interned() {
+            TyKind::Bool => DW_ATE_BOOLEAN,
+            TyKind::Float(_) => DW_ATE_FLOAT,
+            TyKind::Int(int_ty) => match int_ty.signedness {
+                Signedness::Signed => DW_ATE_SIGNED,
+                Signedness::Unsigned => DW_ATE_UNSIGNED,
+            },
+            _ => return None,
+        };
+        let size_in_bits = ty.layout(db)?.size.bits();
+
+        self.builder
+            .create_basic_type(
+                &ty.display(db).to_string(),
+                size_in_bits,
+                encoding,
+                DIFlags::ZERO,
+            )
+            .ok()
+            .map(|ty| ty.as_type())
+    }
+}
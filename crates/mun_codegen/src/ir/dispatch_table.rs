@@ -8,7 +8,7 @@ use inkwell::{
     module::Module,
     targets::TargetData,
     types::{BasicTypeEnum, FunctionType},
-    values::{BasicValueEnum, CallableValue},
+    values::{BasicValueEnum, CallableValue, PointerValue},
 };
 use mun_hir::{Body, Expr, ExprId, HirDatabase, InferenceResult};
 use rustc_hash::FxHashSet;
@@ -100,6 +100,27 @@ impl<'ink> DispatchTable<'ink> {
         Self::gen_function_lookup_by_index(table_ref, builder, &function_name, index)
     }
 
+    /// Generates a load of the pointer to `function` from the `DispatchTable`.
+    /// Unlike [`DispatchTable::gen_function_lookup`] this returns the pointer
+    /// value itself, which can be passed around as a function pointer.
+    pub fn gen_function_ptr_lookup(
+        &self,
+        db: &dyn HirDatabase,
+        table_ref: Option<inkwell::values::GlobalValue<'ink>>,
+        builder: &inkwell::builder::Builder<'ink>,
+        function: mun_hir::Function,
+    ) -> PointerValue<'ink> {
+        let function_name = function.name(db).to_string();
+
+        // Get the index of the function
+        let index = *self
+            .function_to_idx
+            .get(&function)
+            .expect("unknown function");
+
+        Self::gen_function_ptr_lookup_by_index(table_ref, builder, &function_name, index)
+    }
+
     /// Generates a function lookup through the `DispatchTable`, equivalent to
     /// something along the lines of: `dispatchTable[i]`, where i is the
     /// index of the intrinsic and `dispatchTable` is a struct
@@ -129,6 +150,19 @@ impl<'ink> DispatchTable<'ink> {
         function_name: &str,
         index: usize,
     ) -> CallableValue<'ink> {
+        Self::gen_function_ptr_lookup_by_index(table_ref, builder, function_name, index)
+            .try_into()
+            .expect("Pointer value is not a valid function pointer.")
+    }
+
+    /// Generates a load of the function pointer at `index` from the
+    /// `DispatchTable`.
+    fn gen_function_ptr_lookup_by_index(
+        table_ref: Option<inkwell::values::GlobalValue<'ink>>,
+        builder: &inkwell::builder::Builder<'ink>,
+        function_name: &str,
+        index: usize,
+    ) -> PointerValue<'ink> {
         // Get the internal table reference
         let table_ref = table_ref.expect("no dispatch table defined");
 
@@ -147,8 +181,6 @@ impl<'ink> DispatchTable<'ink> {
         builder
             .build_load(ptr_to_function_ptr, &format!("{function_name}_ptr"))
             .into_pointer_value()
-            .try_into()
-            .expect("Pointer value is not a valid function pointer.")
     }
 
     /// Returns the value that represents the dispatch table in IR or `None` if
@@ -256,19 +288,34 @@ impl<'db, 'ink, 't> DispatchTableBuilder<'db, 'ink, 't> {
         let expr = &body[expr_id];
 
         // If this expression is a call, store it in the dispatch table
-        if let Expr::Call { callee, .. } = expr {
+        if let Expr::Call { callee, args } = expr {
             match infer[*callee].as_callable_def() {
-                Some(mun_hir::CallableDef::Function(def)) => {
-                    if self.module_group.should_runtime_link_fn(self.db, def) {
-                        let fn_module = def.module(self.db);
-                        if !def.is_extern(self.db) && !self.module_group.contains(fn_module) {
-                            self.referenced_modules.insert(fn_module);
-                        }
-                        self.collect_fn_def(def);
-                    }
-                }
+                Some(mun_hir::CallableDef::Function(def)) => self.collect_runtime_linked_fn(def),
                 Some(mun_hir::CallableDef::Struct(_)) => (),
-                None => panic!("expected a callable expression"),
+                None => {
+                    // Calls through a function pointer don't require a dispatch table entry
+                    assert!(
+                        infer[*callee].as_fn_ptr().is_some(),
+                        "expected a callable expression"
+                    );
+                    self.collect_expr(*callee, body, infer);
+                }
+            }
+
+            // A called function is not used as a value, so only the arguments are visited
+            for arg in args.iter() {
+                self.collect_expr(*arg, body, infer);
+            }
+            return;
+        }
+
+        // If a function is used as a value, its address is taken from the dispatch
+        // table. This allows the runtime to update function pointers that are
+        // stored in memory when the function is hot reloaded.
+        if let Expr::Path(_) = expr {
+            if let Some(mun_hir::CallableDef::Function(def)) = infer[expr_id].as_callable_def() {
+                self.collect_runtime_linked_fn(def);
+                self.collect_fn_def(def);
             }
         }
 
@@ -276,6 +323,17 @@ impl<'db, 'ink, 't> DispatchTableBuilder<'db, 'ink, 't> {
         expr.walk_child_exprs(|expr_id| self.collect_expr(expr_id, body, infer));
     }
 
+    /// Collects the specified function if it needs to be linked at runtime.
+    fn collect_runtime_linked_fn(&mut self, def: mun_hir::Function) {
+        if self.module_group.should_runtime_link_fn(self.db, def) {
+            let fn_module = def.module(self.db);
+            if !def.is_extern(self.db) && !self.module_group.contains(fn_module) {
+                self.referenced_modules.insert(fn_module);
+            }
+            self.collect_fn_def(def);
+        }
+    }
+
     /// Collects function call expression from the given expression.
     #[allow(clippy::map_entry)]
     pub fn collect_fn_def(&mut self, function: mun_hir::Function) {
@@ -1,14 +1,21 @@
 use std::collections::{BTreeMap, HashMap, HashSet};
 
-use inkwell::module::Module;
-use mun_hir::{HasVisibility, ModuleDef};
+use inkwell::{
+    module::{Linkage, Module},
+    values::GlobalValue,
+    AddressSpace, GlobalVisibility,
+};
+use mun_hir::{HasVisibility, ModuleDef, OptimizeAttr};
 
 use super::body::ExternalGlobals;
 use crate::{
-    code_gen::CodeGenContext,
-    ir::{body::BodyIrGenerator, file_group::FileGroupIr, function, type_table::TypeTable},
+    code_gen::{mark_min_size, CodeGenContext, Phase, PhaseTimer},
+    ir::{
+        body::BodyIrGenerator, debug_info::DebugInfoGenerator, file_group::FileGroupIr, function,
+        type_table::TypeTable, types as ir,
+    },
     module_group::ModuleGroup,
-    value::Global,
+    value::{Global, IrTypeContext, Value},
 };
 
 /// The IR generated for a single source file.
@@ -20,6 +27,25 @@ pub struct FileIr<'ink> {
     pub function_definitions: HashSet<mun_hir::Function>,
     /// The types defined in this file
     pub type_definitions: HashSet<mun_hir::Ty>,
+    /// The function-local statics defined in this file
+    pub static_definitions: Vec<LocalStatic>,
+    /// The public constants defined in this file, ordered by name
+    pub const_definitions: Vec<mun_hir::Const>,
+    /// The functions that record their calls in the call trace ring, ordered
+    /// by name
+    pub traced_functions: Vec<mun_hir::Function>,
+}
+
+/// A function-local static that is stored in a global of the LLVM module.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LocalStatic {
+    /// The name of the global that holds the value of the static
+    pub name: String,
+    /// The type of the static
+    pub ty: mun_hir::Ty,
+    /// The name of the global that holds the initialization state of the
+    /// static, or `None` if the static is initialized with a constant.
+    pub init_state: Option<String>,
 }
 
 /// Generates IR for the specified file.
@@ -36,18 +62,27 @@ pub(crate) fn gen_file_ir<'ink>(
     // Use a `BTreeMap` to guarantee deterministically ordered output.ures
     let mut functions = HashMap::new();
     let mut type_definitions = HashSet::new();
+    let mut const_definitions = Vec::new();
+    let mut placed_constants = Vec::new();
     let mut wrapper_functions = BTreeMap::new();
-    for def in module_group
+
+    // The prototypes of `#[no_mangle]` functions are generated first to ensure that their
+    // symbols are not renamed by LLVM because another function in the module group has the
+    // same name.
+    let mut declarations = module_group
         .iter()
         .flat_map(|module| module.declarations(code_gen.db))
-    {
+        .collect::<Vec<_>>();
+    declarations
+        .sort_by_key(|def| !matches!(def, ModuleDef::Function(f) if f.is_no_mangle(code_gen.db)));
+    for def in declarations {
         if let ModuleDef::Function(f) = def {
-            if !f.is_extern(code_gen.db) {
+            if !f.is_extern(code_gen.db) && code_gen.is_function_emitted(f) {
                 let fun = function::gen_prototype(code_gen.db, hir_types, f, &llvm_module);
                 functions.insert(f, fun);
 
                 let fn_sig = f.ty(code_gen.db).callable_sig(code_gen.db).unwrap();
-                if f.visibility(code_gen.db).is_externally_visible()
+                if (f.visibility(code_gen.db).is_externally_visible() || f.is_reflect(code_gen.db))
                     && !fn_sig.marshallable(code_gen.db)
                 {
                     let wrapper_fun = function::gen_public_prototype(
@@ -63,36 +98,86 @@ pub(crate) fn gen_file_ir<'ink>(
         if let ModuleDef::Struct(s) = def {
             type_definitions.insert(s.ty(code_gen.db));
         }
+        if let ModuleDef::Const(c) = def {
+            if c.visibility(code_gen.db).is_externally_visible() && c.value(code_gen.db).is_ok() {
+                const_definitions.push(c);
+            }
+            if let Some(section) = c.link_section(code_gen.db) {
+                if let Some(global) = gen_placed_constant(code_gen, &llvm_module, c, &section) {
+                    placed_constants.push(global);
+                }
+            }
+        }
     }
+    gen_compiler_used(code_gen, &llvm_module, &placed_constants);
 
     let external_globals = {
         let alloc_handle = group_ir
             .allocator_handle_type
             .map(|ty| llvm_module.add_global(ty, None, "allocatorHandle"));
-        let dispatch_table = group_ir
-            .dispatch_table
-            .ty()
-            .map(|ty| llvm_module.add_global(ty, None, "dispatchTable"));
+        let dispatch_table = group_ir.dispatch_table.ty().map(|ty| {
+            let global = llvm_module.add_global(ty, None, "dispatchTable");
+            if code_gen.emit_relocations {
+                global.set_visibility(GlobalVisibility::Hidden);
+            }
+            global
+        });
         let type_table = if group_ir.type_table.is_empty() {
             None
         } else {
             Some(llvm_module.add_global(group_ir.type_table.ty(), None, TypeTable::NAME))
         };
+        let call_trace = code_gen.call_trace_ring.map(|_| {
+            let target_data = code_gen.target_machine.get_target_data();
+            let type_context = IrTypeContext {
+                context: code_gen.context,
+                target_data: &target_data,
+                struct_types: &code_gen.rust_types,
+            };
+            llvm_module.add_global(
+                Value::<ir::CallTrace<'_>>::get_ir_type(&type_context),
+                None,
+                &code_gen.entry_symbols.call_trace,
+            )
+        });
         ExternalGlobals {
             alloc_handle,
             dispatch_table,
             type_table: type_table.map(|g| unsafe { Global::from_raw(g) }),
+            call_trace,
         }
     };
 
     // Construct requirements for generating the bodies
-    let fn_pass_manager = function::create_pass_manager(&llvm_module, code_gen.optimization_level);
+    let fn_pass_manager = function::create_pass_manager(
+        &llvm_module,
+        code_gen.optimization_level,
+        code_gen.minimize_size,
+    );
+    let debug_info = DebugInfoGenerator::new(code_gen, &llvm_module, module_group);
 
     // Generate the function bodies
+    let mut static_definitions = Vec::new();
+    let mut traced_functions = Vec::new();
     for (hir_function, llvm_function) in functions.iter() {
+        let _timer = start_timer(code_gen, *hir_function, Phase::IrGeneration);
+        log_function(code_gen, *hir_function, "generating function body");
+        let instrument = !hir_function.is_no_instrument(code_gen.db);
+        let trace_calls = code_gen.call_trace_ring.is_some() && instrument;
+        if trace_calls {
+            traced_functions.push(*hir_function);
+        }
+
+        let fn_debug_info = debug_info.as_ref().map(|debug_info| {
+            (
+                debug_info,
+                debug_info.gen_function(code_gen.db, *hir_function, *llvm_function),
+            )
+        });
         let mut code_gen = BodyIrGenerator::new(
             code_gen.context,
             code_gen.db,
+            &llvm_module,
             (*hir_function, *llvm_function),
             &functions,
             &group_ir.dispatch_table,
@@ -100,16 +185,24 @@ pub(crate) fn gen_file_ir<'ink>(
             external_globals.clone(),
             &code_gen.hir_types,
             module_group,
+            fn_debug_info,
+            code_gen.alloc_debug,
+            code_gen.init_structs,
+            code_gen.instrument_timing && instrument,
+            trace_calls,
         );
 
         code_gen.gen_fn_body();
-        fn_pass_manager.run_on(llvm_function);
+        static_definitions.extend(code_gen.into_statics());
     }
 
     for (hir_function, llvm_function) in wrapper_functions.iter() {
+        let _timer = start_timer(code_gen, *hir_function, Phase::IrGeneration);
+        log_function(code_gen, *hir_function, "generating function wrapper");
         let mut code_gen = BodyIrGenerator::new(
             code_gen.context,
             code_gen.db,
+            &llvm_module,
             (*hir_function, *llvm_function),
             &functions,
             &group_ir.dispatch_table,
@@ -117,9 +210,31 @@ pub(crate) fn gen_file_ir<'ink>(
             external_globals.clone(),
             &code_gen.hir_types,
             module_group,
+            None,
+            false,
+            code_gen.init_structs,
+            false,
+            false,
         );
 
         code_gen.gen_fn_wrapper();
+    }
+
+    // The debug information must be finalized before the functions are optimized
+    if let Some(debug_info) = &debug_info {
+        debug_info.finalize();
+    }
+    if code_gen.minimize_size {
+        let explicit = functions
+            .iter()
+            .filter(|(hir_function, _)| hir_function.optimize(code_gen.db) != OptimizeAttr::Default)
+            .map(|(_, llvm_function)| *llvm_function)
+            .collect();
+        mark_min_size(&llvm_module, &explicit);
+    }
+    for (hir_function, llvm_function) in functions.iter().chain(wrapper_functions.iter()) {
+        let _timer = start_timer(code_gen, *hir_function, Phase::Optimization);
+        log_function(code_gen, *hir_function, "running function passes");
         fn_pass_manager.run_on(llvm_function);
     }
 
@@ -130,9 +245,92 @@ pub(crate) fn gen_file_ir<'ink>(
         .filter(|&f| module_group.should_export_fn(code_gen.db, f))
         .collect();
 
+    traced_functions.sort_by_cached_key(|f| f.full_name(code_gen.db));
+    const_definitions.sort_by_cached_key(|c| c.full_name(code_gen.db));
+
     FileIr {
         llvm_module,
         function_definitions,
         type_definitions,
+        static_definitions,
+        const_definitions,
+        traced_functions,
     }
 }
+
+/// Starts timing `phase` of the specified function, if the time it takes to
+/// generate code for each function is measured.
+fn start_timer<'t>(
+    code_gen: &'t CodeGenContext<'_, '_>,
+    function: mun_hir::Function,
+    phase: Phase,
+) -> Option<PhaseTimer<'t>> {
+    code_gen
+        .function_timings
+        .as_ref()
+        .map(|timings| timings.start(function.full_name(code_gen.db), phase))
+}
+
+/// Logs a trace event about code generation for the specified function,
+/// including the file in which it is defined.
+fn log_function(code_gen: &CodeGenContext<'_, '_>, function: mun_hir::Function, event: &str) {
+    log::trace!(
+        "{event} file_id={} function={}",
+        function.file_id(code_gen.db).0,
+        function.full_name(code_gen.db)
+    );
+}
+
+/// Generates a global that holds the value of a constant with a
+/// `#[link_section]` attribute, placed in `section`. The value of a constant is
+/// otherwise inlined at every use, so it does not occupy a section at all. The
+/// global is also used to reflect the constant, if it is public.
+fn gen_placed_constant<'ink>(
+    code_gen: &CodeGenContext<'_, 'ink>,
+    llvm_module: &Module<'ink>,
+    constant: mun_hir::Const,
+    section: &str,
+) -> Option<GlobalValue<'ink>> {
+    let value = constant.value(code_gen.db).ok()?;
+    let value = code_gen
+        .hir_types
+        .get_const_value(&constant.ty(code_gen.db), value);
+
+    let name = constant.full_name(code_gen.db);
+    let global = llvm_module.add_global(value.get_type(), None, &format!("const::<{name}>::value"));
+    global.set_linkage(Linkage::Internal);
+    global.set_constant(true);
+    global.set_initializer(&value);
+    global.set_section(Some(section));
+    Some(global)
+}
+
+/// Marks `globals` as used, such that the compiler does not remove them even
+/// though they are never referenced. The linker may still discard their
+/// sections, unless it is instructed to keep them.
+fn gen_compiler_used<'ink>(
+    code_gen: &CodeGenContext<'_, 'ink>,
+    llvm_module: &Module<'ink>,
+    globals: &[GlobalValue<'ink>],
+) {
+    if globals.is_empty() {
+        return;
+    }
+
+    let ptr_type = code_gen.context.i8_type().ptr_type(AddressSpace::default());
+    let used = llvm_module.add_global(
+        ptr_type.array_type(globals.len() as u32),
+        None,
+        "llvm.compiler.used",
+    );
+    used.set_linkage(Linkage::Appending);
+    used.set_section(Some("llvm.metadata"));
+    used.set_initializer(
+        &ptr_type.const_array(
+            &globals
+                .iter()
+                .map(|global| global.as_pointer_value().const_cast(ptr_type))
+                .collect::<Vec<_>>(),
+        ),
+    );
+}
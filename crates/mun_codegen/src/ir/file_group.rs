@@ -1,6 +1,8 @@
 use std::collections::BTreeMap;
 
-use inkwell::{module::Module, types::PointerType, values::UnnamedAddress, AddressSpace};
+use inkwell::{
+    module::Module, types::PointerType, values::UnnamedAddress, AddressSpace, GlobalVisibility,
+};
 use mun_hir::{HasVisibility, ModuleDef};
 use rustc_hash::FxHashSet;
 
@@ -50,19 +52,24 @@ pub(crate) fn gen_file_group_ir<'ink>(
         .flat_map(|module| module.declarations(code_gen.db))
     {
         match def {
-            ModuleDef::Function(f) if !f.is_extern(code_gen.db) => {
+            ModuleDef::Function(f)
+                if !f.is_extern(code_gen.db) && code_gen.is_function_emitted(f) =>
+            {
                 intrinsics::collect_fn_body(
                     code_gen.context,
                     code_gen.target_machine.get_target_data(),
                     code_gen.db,
                     &mut intrinsics_map,
                     &mut needs_alloc,
+                    code_gen.alloc_debug,
+                    code_gen.init_structs,
+                    code_gen.instrument_timing && !f.is_no_instrument(code_gen.db),
                     &f.body(code_gen.db),
                     &f.infer(code_gen.db),
                 );
 
                 let fn_sig = f.ty(code_gen.db).callable_sig(code_gen.db).unwrap();
-                if f.visibility(code_gen.db).is_externally_visible()
+                if (f.visibility(code_gen.db).is_externally_visible() || f.is_reflect(code_gen.db))
                     && !fn_sig.marshallable(code_gen.db)
                 {
                     intrinsics::collect_wrapper_body(
@@ -70,6 +77,7 @@ pub(crate) fn gen_file_group_ir<'ink>(
                         code_gen.target_machine.get_target_data(),
                         &mut intrinsics_map,
                         &mut needs_alloc,
+                        code_gen.init_structs,
                     );
                 }
             }
@@ -78,6 +86,8 @@ pub(crate) fn gen_file_group_ir<'ink>(
             | ModuleDef::Struct(_)
             | ModuleDef::PrimitiveType(_)
             | ModuleDef::TypeAlias(_)
+            | ModuleDef::BuiltinConstant(_)
+            | ModuleDef::Const(_)
             | ModuleDef::Function(_) => (),
         }
     }
@@ -98,7 +108,7 @@ pub(crate) fn gen_file_group_ir<'ink>(
     {
         if let ModuleDef::Function(f) = def {
             // Find all functions that must be present in the dispatch table
-            if !f.is_extern(code_gen.db) {
+            if !f.is_extern(code_gen.db) && code_gen.is_function_emitted(f) {
                 let body = f.body(code_gen.db);
                 let infer = f.infer(code_gen.db);
                 dispatch_table_builder.collect_body(&body, &infer);
@@ -108,6 +118,14 @@ pub(crate) fn gen_file_group_ir<'ink>(
 
     let (dispatch_table, referenced_modules) = dispatch_table_builder.build();
 
+    // Hidden visibility makes references to the dispatch table PC-relative instead
+    // of going through the GOT, such that their relocations identify the slot.
+    if code_gen.emit_relocations {
+        if let Some(global) = dispatch_table.global_value() {
+            global.set_visibility(GlobalVisibility::Hidden);
+        }
+    }
+
     let target_data = code_gen.target_machine.get_target_data();
     let type_context = IrTypeContext {
         context: code_gen.context,
@@ -137,10 +155,15 @@ pub(crate) fn gen_file_group_ir<'ink>(
             ModuleDef::Struct(s) => {
                 type_table_builder.collect_struct(s);
             }
-            ModuleDef::Function(f) => {
+            ModuleDef::Function(f) if code_gen.is_function_emitted(f) => {
                 type_table_builder.collect_fn(f);
             }
-            ModuleDef::PrimitiveType(_) | ModuleDef::TypeAlias(_) | ModuleDef::Module(_) => (),
+            ModuleDef::PrimitiveType(_)
+            | ModuleDef::TypeAlias(_)
+            | ModuleDef::BuiltinConstant(_)
+            | ModuleDef::Const(_)
+            | ModuleDef::Module(_)
+            | ModuleDef::Function(_) => (),
         }
     }
 
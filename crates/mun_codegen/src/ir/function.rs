@@ -1,19 +1,24 @@
 use inkwell::{
-    passes::{PassManager, PassManagerBuilder},
+    attributes::{Attribute, AttributeLoc},
+    passes::PassManager,
     values::FunctionValue,
+    DLLStorageClass,
 };
-use mun_hir::HirDatabase;
+use mun_hir::{HirDatabase, InlineAttr, OptimizeAttr};
+use mun_target::spec::Target;
 
-use crate::{ir::ty::HirTypeCache, Module, OptimizationLevel};
+use crate::{
+    code_gen::create_pass_builder, ir::ty::HirTypeCache, mangle, Module, OptimizationLevel,
+};
 
 /// Constructs a `PassManager` to optimize functions for the given optimization
-/// level.
+/// level, which minimizes their size if `minimize_size` is set.
 pub(crate) fn create_pass_manager<'ink>(
     module: &Module<'ink>,
     optimization_lvl: OptimizationLevel,
+    minimize_size: bool,
 ) -> PassManager<FunctionValue<'ink>> {
-    let pass_builder = PassManagerBuilder::create();
-    pass_builder.set_optimization_level(optimization_lvl);
+    let pass_builder = create_pass_builder(optimization_lvl, minimize_size);
 
     let function_pass_manager = PassManager::create(module);
     pass_builder.populate_function_pass_manager(&function_pass_manager);
@@ -33,9 +38,74 @@ pub(crate) fn gen_prototype<'db, 'ink>(
     func: mun_hir::Function,
     module: &Module<'ink>,
 ) -> FunctionValue<'ink> {
-    let name = func.name(db).to_string();
+    let name = mangle::function_symbol(db, func);
     let ir_ty = types.get_function_type(func);
-    module.add_function(&name, ir_ty, None)
+
+    let value = module.add_function(&name, ir_ty, None);
+    // `#[no_mangle]` functions are always exported, regardless of their visibility.
+    // A DLL only exports the symbols that are marked as such.
+    if func.is_no_mangle(db) {
+        value
+            .as_global_value()
+            .set_dll_storage_class(DLLStorageClass::Export);
+    }
+    if func.is_cold(db) {
+        mark_cold(&db.target(), value);
+    }
+    add_optimization_hints(func.inline(db), func.optimize(db), value);
+    value
+}
+
+/// Adds the attributes that correspond to the `#[inline]` and `#[optimize]`
+/// attributes of a function. Optimizing for speed is what the optimizer does
+/// by default, so it does not require an attribute.
+fn add_optimization_hints(inline: InlineAttr, optimize: OptimizeAttr, value: FunctionValue<'_>) {
+    let inline_attr = match inline {
+        InlineAttr::None => None,
+        InlineAttr::Hint => Some("inlinehint"),
+        InlineAttr::Always => Some("alwaysinline"),
+        InlineAttr::Never => Some("noinline"),
+    };
+    let optimize_attr = match optimize {
+        OptimizeAttr::Default | OptimizeAttr::Speed => None,
+        OptimizeAttr::Size => Some("optsize"),
+    };
+
+    let context = value.get_type().get_context();
+    for name in inline_attr.into_iter().chain(optimize_attr) {
+        let attr = context.create_enum_attribute(Attribute::get_named_enum_kind_id(name), 0);
+        value.add_attribute(AttributeLoc::Function, attr);
+    }
+}
+
+/// Marks a function as rarely executed. The optimizer treats calls to the
+/// function as unlikely and the code is placed in a separate section, away from
+/// the code that is executed frequently.
+fn mark_cold(target: &Target, value: FunctionValue<'_>) {
+    let context = value.get_type().get_context();
+    let cold = context.create_enum_attribute(Attribute::get_named_enum_kind_id("cold"), 0);
+    value.add_attribute(AttributeLoc::Function, cold);
+    value
+        .as_global_value()
+        .set_section(Some(cold_section_name(target)));
+}
+
+/// Returns the name of the section that contains cold code. The section name
+/// must follow the conventions of the object format, which is determined by
+/// the linker flavor of the target.
+fn cold_section_name(target: &Target) -> &'static str {
+    if target.options.is_like_osx {
+        // Mach-O sections are specified as `segment,section` and require the
+        // section to be marked as executable explicitly.
+        "__TEXT,__text_cold,regular,pure_instructions"
+    } else if target.options.is_like_windows {
+        // COFF merges grouped sections (`.text$<suffix>`) into `.text`, ordered
+        // by their suffix.
+        ".text$cold"
+    } else {
+        // ELF linkers place `.text.*` sections in the `.text` output section.
+        ".text.cold"
+    }
 }
 
 /// Generates a `FunctionValue` for a `mun_hir::Function` that is usable from
@@ -50,7 +120,7 @@ pub(crate) fn gen_public_prototype<'db, 'ink>(
     func: mun_hir::Function,
     module: &Module<'ink>,
 ) -> FunctionValue<'ink> {
-    let name = format!("{}_wrapper", func.name(db));
+    let name = mangle::wrapper_symbol(db, func);
     let ir_ty = types.get_public_function_type(func);
     module.add_function(&name, ir_ty, None)
 }
@@ -1,7 +1,7 @@
 use std::{collections::BTreeMap, sync::Arc};
 
 use inkwell::{context::Context, targets::TargetData, types::FunctionType};
-use mun_hir::{Body, Expr, ExprId, HirDatabase, InferenceResult, ValueNs};
+use mun_hir::{Body, Expr, ExprId, HirDatabase, InferenceResult, UnaryOp, ValueNs};
 
 use crate::{
     intrinsics::{self, Intrinsic},
@@ -24,6 +24,47 @@ fn collect_intrinsic<'ink>(
         .or_insert_with(|| intrinsic.ir_type(context, target));
 }
 
+/// Stores the type information of the intrinsic that allocates an object in
+/// `entries`. If `alloc_debug` is set, the intrinsic also records the source
+/// location of the allocation. Otherwise, if `init_structs` is not set, the
+/// intrinsic does not zero-initialize the object.
+fn collect_new<'ink>(
+    context: &'ink Context,
+    target: &TargetData,
+    alloc_debug: bool,
+    init_structs: bool,
+    entries: &mut IntrinsicsMap<'ink>,
+) {
+    if alloc_debug {
+        collect_intrinsic(context, target, &intrinsics::new_with_location, entries);
+    } else if !init_structs {
+        collect_intrinsic(context, target, &intrinsics::new_uninit, entries);
+    } else {
+        collect_intrinsic(context, target, &intrinsics::new, entries);
+    }
+}
+
+/// Stores the type information of the intrinsic that allocates an array in
+/// `entries`. If `alloc_debug` is set, the intrinsic also records the source
+/// location of the allocation.
+fn collect_new_array<'ink>(
+    context: &'ink Context,
+    target: &TargetData,
+    alloc_debug: bool,
+    entries: &mut IntrinsicsMap<'ink>,
+) {
+    if alloc_debug {
+        collect_intrinsic(
+            context,
+            target,
+            &intrinsics::new_array_with_location,
+            entries,
+        );
+    } else {
+        collect_intrinsic(context, target, &intrinsics::new_array, entries);
+    }
+}
+
 /// Iterates over all expressions and stores information on which intrinsics
 /// they use in `entries`.
 #[allow(clippy::too_many_arguments)]
@@ -33,6 +74,8 @@ fn collect_expr<'ink>(
     db: &'_ dyn HirDatabase,
     intrinsics: &mut IntrinsicsMap<'ink>,
     needs_alloc: &mut bool,
+    alloc_debug: bool,
+    init_structs: bool,
     expr_id: ExprId,
     body: &Arc<Body>,
     infer: &InferenceResult,
@@ -43,17 +86,20 @@ fn collect_expr<'ink>(
     if let Expr::Call { callee, .. } = expr {
         match infer[*callee].as_callable_def() {
             Some(mun_hir::CallableDef::Struct(_)) => {
-                collect_intrinsic(context, target, &intrinsics::new, intrinsics);
+                collect_new(context, target, alloc_debug, init_structs, intrinsics);
                 // self.collect_intrinsic(module, entries, &intrinsics::drop);
                 *needs_alloc = true;
             }
             Some(mun_hir::CallableDef::Function(_)) => (),
-            None => panic!("expected a callable expression"),
+            None => assert!(
+                infer[*callee].as_fn_ptr().is_some(),
+                "expected a callable expression"
+            ),
         }
     }
 
     if let Expr::RecordLit { .. } = expr {
-        collect_intrinsic(context, target, &intrinsics::new, intrinsics);
+        collect_new(context, target, alloc_debug, init_structs, intrinsics);
         // self.collect_intrinsic(module, entries, &intrinsics::drop);
         *needs_alloc = true;
     }
@@ -63,17 +109,30 @@ fn collect_expr<'ink>(
         if let Some((ValueNs::StructId(_), _)) =
             resolver.resolve_path_as_value_fully(db.upcast(), path)
         {
-            collect_intrinsic(context, target, &intrinsics::new, intrinsics);
+            collect_new(context, target, alloc_debug, init_structs, intrinsics);
             // self.collect_intrinsic( module, entries, &intrinsics::drop);
             *needs_alloc = true;
         }
     }
 
     if let Expr::Array(_) = expr {
-        collect_intrinsic(context, target, &intrinsics::new_array, intrinsics);
+        collect_new_array(context, target, alloc_debug, intrinsics);
         *needs_alloc = true;
     }
 
+    // Borrowing an array as a slice roots the array for the duration of the call
+    if let Expr::UnaryOp {
+        expr: inner,
+        op: UnaryOp::AddrOf,
+    } = expr
+    {
+        if infer[*inner].as_array().is_some() {
+            collect_intrinsic(context, target, &intrinsics::root, intrinsics);
+            collect_intrinsic(context, target, &intrinsics::unroot, intrinsics);
+            *needs_alloc = true;
+        }
+    }
+
     // Recurse further
     expr.walk_child_exprs(|expr_id| {
         collect_expr(
@@ -82,6 +141,8 @@ fn collect_expr<'ink>(
             db,
             intrinsics,
             needs_alloc,
+            alloc_debug,
+            init_structs,
             expr_id,
             body,
             infer,
@@ -89,36 +150,52 @@ fn collect_expr<'ink>(
     });
 }
 
-/// Collects all intrinsics from the specified `body`.
+/// Collects all intrinsics from the specified `body`. If `instrument_timing` is
+/// set, the intrinsics that measure the execution time of the function are
+/// also collected.
+#[allow(clippy::too_many_arguments)]
 pub fn collect_fn_body<'ink>(
     context: &'ink Context,
     target: TargetData,
     db: &dyn HirDatabase,
     intrinsics: &mut IntrinsicsMap<'ink>,
     needs_alloc: &mut bool,
+    alloc_debug: bool,
+    init_structs: bool,
+    instrument_timing: bool,
     body: &Arc<Body>,
     infer: &InferenceResult,
 ) {
+    if instrument_timing {
+        collect_intrinsic(context, &target, &intrinsics::timing_enter, intrinsics);
+        collect_intrinsic(context, &target, &intrinsics::timing_exit, intrinsics);
+        *needs_alloc = true;
+    }
+
     collect_expr(
         context,
         &target,
         db,
         intrinsics,
         needs_alloc,
+        alloc_debug,
+        init_structs,
         body.body_expr(),
         body,
         infer,
     );
 }
 
-/// Collects all intrinsics from a function wrapper body.
+/// Collects all intrinsics from a function wrapper body. Wrappers never record
+/// the source location of their allocations.
 pub fn collect_wrapper_body<'ink>(
     context: &'ink Context,
     target: TargetData,
     intrinsics: &mut IntrinsicsMap<'ink>,
     needs_alloc: &mut bool,
+    init_structs: bool,
 ) {
-    collect_intrinsic(context, &target, &intrinsics::new, intrinsics);
+    collect_new(context, &target, false, init_structs, intrinsics);
     // self.collect_intrinsic(entries, &intrinsics::drop, module);
     *needs_alloc = true;
 }
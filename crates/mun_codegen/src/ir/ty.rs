@@ -7,17 +7,19 @@ use inkwell::{
         AnyTypeEnum, BasicType, BasicTypeEnum, FloatType, FunctionType, IntType, PointerType,
         StructType,
     },
+    values::BasicValueEnum,
     AddressSpace,
 };
 use mun_abi::Guid;
 use mun_hir::{
-    FloatBitness, HirDatabase, HirDisplay, IntBitness, ResolveBitness, Signedness, Ty, TyKind,
+    FloatBitness, FnSig, HirDatabase, HirDisplay, IntBitness, ResolveBitness, Signedness, Ty,
+    TyKind,
 };
 use smallvec::SmallVec;
 
 use crate::{
     ir::IsIrType,
-    type_info::{HasStaticTypeId, TypeId, TypeIdData},
+    type_info::{HasStaticTypeId, PointerTypeId, TypeId, TypeIdData},
 };
 
 /// An object to cache and convert HIR types to Inkwell types.
@@ -166,6 +168,37 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
             .ptr_type(AddressSpace::default())
     }
 
+    /// Returns the IR type of the specified slice type. Slices are passed by
+    /// value as a pointer to the first element and the number of elements:
+    ///
+    /// ```c
+    /// struct Slice {
+    ///     T *ptr;
+    ///     usize_t len;
+    /// }
+    /// ```
+    pub fn get_slice_type(&self, element_ty: &Ty) -> StructType<'ink> {
+        let element_ir_type = self
+            .get_basic_type(element_ty)
+            .expect("could not convert slice element type to basic type");
+        let length_ir_type = self.context.ptr_sized_int_type(&self.target_data, None);
+        self.context.struct_type(
+            &[
+                element_ir_type.ptr_type(AddressSpace::default()).into(),
+                length_ir_type.into(),
+            ],
+            false,
+        )
+    }
+
+    /// Returns the IR type of the specified mutable reference type. A mutable
+    /// reference is passed as a raw pointer to the referenced value.
+    pub fn get_ref_type(&self, inner_ty: &Ty) -> PointerType<'ink> {
+        self.get_basic_type(inner_ty)
+            .expect("could not convert referenced type to basic type")
+            .ptr_type(AddressSpace::default())
+    }
+
     /// Returns the type of the struct that should be used for variables.
     /// Depending on the memory type of the struct this is either a pointer
     /// to a `GCHandle` which holds a pointer to a struct, or, in case of a
@@ -211,7 +244,11 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
 
     /// Returns the type of the specified function definition
     pub fn get_function_type(&self, ty: mun_hir::Function) -> FunctionType<'ink> {
-        let ty = self.db.callable_sig(ty.into());
+        self.get_fn_sig_type(&self.db.callable_sig(ty.into()))
+    }
+
+    /// Returns the type of a function with the specified signature
+    pub fn get_fn_sig_type(&self, ty: &FnSig) -> FunctionType<'ink> {
         let param_tys: Vec<_> = ty
             .params()
             .iter()
@@ -232,6 +269,11 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
         }
     }
 
+    /// Returns the type of a pointer to a function with the specified signature
+    pub fn get_fn_ptr_type(&self, ty: &FnSig) -> PointerType<'ink> {
+        self.get_fn_sig_type(ty).ptr_type(AddressSpace::default())
+    }
+
     /// Returns the type of a specified function definition that is callable
     /// from the outside of the Mun code. This function should be C ABI
     /// compatible.
@@ -268,6 +310,14 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
             TyKind::Struct(struct_ty) => Some(self.get_struct_reference_type(*struct_ty)),
             TyKind::Bool => Some(self.get_bool_type().into()),
             TyKind::Array(element_ty) => Some(self.get_array_reference_type(element_ty).into()),
+            TyKind::Slice(element_ty) => Some(self.get_slice_type(element_ty).into()),
+            TyKind::Ref(inner_ty) => Some(self.get_ref_type(inner_ty).into()),
+            TyKind::FnPtr(sig) => Some(self.get_fn_ptr_type(sig).into()),
+            TyKind::FnDef(mun_hir::CallableDef::Function(fn_ty), _) => Some(
+                self.get_function_type(*fn_ty)
+                    .ptr_type(AddressSpace::default())
+                    .into(),
+            ),
             _ => None,
         }
     }
@@ -284,6 +334,9 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
             TyKind::Struct(struct_ty) => Some(self.get_public_struct_reference_type(*struct_ty)),
             TyKind::Bool => Some(self.get_bool_type().into()),
             TyKind::Array(element_ty) => Some(self.get_array_reference_type(element_ty).into()),
+            TyKind::Slice(element_ty) => Some(self.get_slice_type(element_ty).into()),
+            TyKind::Ref(inner_ty) => Some(self.get_ref_type(inner_ty).into()),
+            TyKind::FnPtr(sig) => Some(self.get_fn_ptr_type(sig).into()),
             _ => None,
         }
     }
@@ -304,10 +357,35 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
             }
             TyKind::Bool => Some(self.get_bool_type().into()),
             TyKind::Array(element_ty) => Some(self.get_array_reference_type(element_ty).into()),
+            TyKind::Slice(element_ty) => Some(self.get_slice_type(element_ty).into()),
+            TyKind::Ref(inner_ty) => Some(self.get_ref_type(inner_ty).into()),
+            TyKind::FnPtr(sig) => Some(self.get_fn_ptr_type(sig).into()),
             _ => None,
         }
     }
 
+    /// Returns the inkwell value of a constant of the specified HIR type
+    /// that was evaluated at compile time.
+    pub fn get_const_value(&self, ty: &Ty, value: mun_hir::ConstValue) -> BasicValueEnum<'ink> {
+        match (ty.interned(), value) {
+            (TyKind::Bool, mun_hir::ConstValue::Bool(value)) => self
+                .get_bool_type()
+                .const_int(u64::from(value), false)
+                .into(),
+            (TyKind::Int(int_ty), mun_hir::ConstValue::Int(value)) => {
+                // The value is split into 64-bit words, least significant first
+                let words = [value as u64, (value >> 64) as u64];
+                self.get_int_type(*int_ty)
+                    .const_int_arbitrary_precision(&words)
+                    .into()
+            }
+            (TyKind::Float(float_ty), mun_hir::ConstValue::Float(value)) => {
+                self.get_float_type(*float_ty).const_float(value).into()
+            }
+            _ => panic!("constant value does not match its type"),
+        }
+    }
+
     /// Returns the empty type
     pub fn get_empty_type(&self) -> StructType<'ink> {
         self.context.struct_type(&[], false)
@@ -355,6 +433,7 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
                 }
             }
             TyKind::Bool => bool::type_id().clone(),
+            TyKind::Tuple(0, _) => <()>::type_id().clone(),
             &TyKind::Struct(s) => self
                 .struct_to_type_id
                 .borrow_mut()
@@ -388,6 +467,25 @@ impl<'db, 'ink> HirTypeCache<'db, 'ink> {
 
                 array_type_id
             }
+            TyKind::Slice(element_ty) => {
+                let element_type_id = self.type_id(element_ty);
+                Arc::new(TypeId {
+                    name: format!("&[{}]", &element_type_id.name),
+                    data: TypeIdData::Slice(element_type_id),
+                })
+            }
+            TyKind::Ref(inner_ty) => {
+                let pointee = self.type_id(inner_ty);
+                Arc::new(TypeId {
+                    name: format!("*mut {}", &pointee.name),
+                    data: TypeIdData::Pointer(PointerTypeId {
+                        pointee,
+                        mutable: true,
+                    }),
+                })
+            }
+            // Function pointers are opaque to the runtime
+            TyKind::FnPtr(_) => <*const std::ffi::c_void>::type_id().clone(),
             _ => unimplemented!("{} unhandled", ty.display(self.db)),
         }
     }
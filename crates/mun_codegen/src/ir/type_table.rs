@@ -154,7 +154,15 @@ impl<'db, 'ink, 't> TypeTableBuilder<'db, 'ink, 't> {
                     self.maybe_collect_fn_signature(hir_fn);
                 }
                 Some(mun_hir::CallableDef::Struct(_)) => (),
-                None => panic!("expected a callable expression"),
+                None => assert!(
+                    infer[*callee].as_fn_ptr().is_some(),
+                    "expected a callable expression"
+                ),
+            }
+        } else if let mun_hir::Expr::Path(_) = expr {
+            // A function that is used as a value
+            if let Some(mun_hir::CallableDef::Function(hir_fn)) = infer[expr_id].as_callable_def() {
+                self.maybe_collect_fn_signature(hir_fn);
             }
         } else if let mun_hir::Expr::Array(..) = expr {
             self.collect_type(self.hir_types.type_id(&infer[expr_id]));
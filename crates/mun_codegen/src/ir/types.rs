@@ -46,12 +46,61 @@ impl<'ink> TransparentValue<'ink> for abi::StructMemoryKind {
     }
 }
 
+impl<'ink> TransparentValue<'ink> for abi::OptimizationLevel {
+    type Target = u8;
+
+    fn as_target_value(&self, context: &IrValueContext<'ink, '_, '_>) -> Value<'ink, Self::Target> {
+        (*self as u8).as_value(context)
+    }
+
+    fn as_bytes_and_ptrs(&self, _: &IrTypeContext<'ink, '_>) -> Vec<BytesOrPtr<'ink>> {
+        vec![vec![*self as u8].into()]
+    }
+}
+
+impl<'ink> TransparentValue<'ink> for abi::InlineAttribute {
+    type Target = u8;
+
+    fn as_target_value(&self, context: &IrValueContext<'ink, '_, '_>) -> Value<'ink, Self::Target> {
+        (*self as u8).as_value(context)
+    }
+
+    fn as_bytes_and_ptrs(&self, _: &IrTypeContext<'ink, '_>) -> Vec<BytesOrPtr<'ink>> {
+        vec![vec![*self as u8].into()]
+    }
+}
+
+impl<'ink> TransparentValue<'ink> for abi::OptimizeAttribute {
+    type Target = u8;
+
+    fn as_target_value(&self, context: &IrValueContext<'ink, '_, '_>) -> Value<'ink, Self::Target> {
+        (*self as u8).as_value(context)
+    }
+
+    fn as_bytes_and_ptrs(&self, _: &IrTypeContext<'ink, '_>) -> Vec<BytesOrPtr<'ink>> {
+        vec![vec![*self as u8].into()]
+    }
+}
+
+impl<'ink> TransparentValue<'ink> for abi::CallingConvention {
+    type Target = u8;
+
+    fn as_target_value(&self, context: &IrValueContext<'ink, '_, '_>) -> Value<'ink, Self::Target> {
+        (*self as u8).as_value(context)
+    }
+
+    fn as_bytes_and_ptrs(&self, _: &IrTypeContext<'ink, '_>) -> Vec<BytesOrPtr<'ink>> {
+        vec![vec![*self as u8].into()]
+    }
+}
+
 #[derive(AsValue)]
 #[repr(u8)]
 pub enum TypeId<'ink> {
     Concrete(abi::Guid),
     Pointer(PointerTypeId<'ink>),
     Array(ArrayTypeId<'ink>),
+    Slice(SliceTypeId<'ink>),
 }
 
 #[derive(AsValue)]
@@ -65,6 +114,11 @@ pub struct ArrayTypeId<'ink> {
     pub element: Global<'ink, TypeId<'ink>>,
 }
 
+#[derive(AsValue)]
+pub struct SliceTypeId<'ink> {
+    pub element: Global<'ink, TypeId<'ink>>,
+}
+
 #[derive(AsValue)]
 pub struct TypeDefinition<'ink> {
     pub name: Value<'ink, *const u8>,
@@ -82,6 +136,7 @@ pub enum TypeDefinitionData<'ink> {
 #[derive(AsValue)]
 pub struct FunctionSignature<'ink> {
     pub arg_types: Value<'ink, *const TypeId<'ink>>,
+    pub arg_names: Value<'ink, *const *const u8>,
     pub return_type: TypeId<'ink>,
     pub num_arg_types: u16,
 }
@@ -96,6 +151,18 @@ pub struct FunctionPrototype<'ink> {
 pub struct FunctionDefinition<'ink> {
     pub prototype: FunctionPrototype<'ink>,
     pub fn_ptr: Value<'ink, *const fn()>,
+    pub attributes: Value<'ink, *const FunctionAttributes>,
+}
+
+#[derive(AsValue)]
+pub struct FunctionAttributes {
+    pub inline: abi::InlineAttribute,
+    pub optimize: abi::OptimizeAttribute,
+    pub calling_convention: abi::CallingConvention,
+    pub is_cold: bool,
+    pub is_no_mangle: bool,
+    pub is_tail_call: bool,
+    pub is_no_instrument: bool,
 }
 
 #[derive(AsValue)]
@@ -108,13 +175,34 @@ pub struct StructDefinition<'ink> {
     pub memory_kind: abi::StructMemoryKind,
 }
 
+#[derive(AsValue)]
+pub struct StaticDefinition<'ink> {
+    pub name: Value<'ink, *const u8>,
+    pub type_id: TypeId<'ink>,
+    pub value_ptr: Value<'ink, *mut u8>,
+    pub init_state_ptr: Value<'ink, *mut u8>,
+}
+
+#[derive(AsValue)]
+pub struct ConstDefinition<'ink> {
+    pub name: Value<'ink, *const u8>,
+    pub type_id: TypeId<'ink>,
+    pub value_ptr: Value<'ink, *const u8>,
+}
+
 #[derive(AsValue)]
 pub struct ModuleInfo<'ink> {
     pub path: Value<'ink, *const u8>,
     pub functions: Value<'ink, *const FunctionDefinition<'ink>>,
     pub types: Value<'ink, *const TypeDefinition<'ink>>,
+    pub statics: Value<'ink, *const StaticDefinition<'ink>>,
+    pub constants: Value<'ink, *const ConstDefinition<'ink>>,
+    pub init_functions: Value<'ink, *const *const u8>,
     pub num_functions: u32,
     pub num_types: u32,
+    pub num_statics: u32,
+    pub num_constants: u32,
+    pub num_init_functions: u32,
 }
 
 #[derive(AsValue)]
@@ -132,6 +220,35 @@ pub struct TypeLut<'ink> {
     pub num_entries: u32,
 }
 
+#[derive(AsValue)]
+pub struct FormatHeader {
+    pub magic: u32,
+    pub format_version: u32,
+    pub abi_version: u32,
+}
+
+#[derive(AsValue)]
+pub struct CompilerInfo<'ink> {
+    pub compiler_version: Value<'ink, *const u8>,
+    pub llvm_version: Value<'ink, *const u8>,
+}
+
+#[derive(AsValue)]
+pub struct CallTraceRing<'ink> {
+    pub ids: Value<'ink, *mut u32>,
+    pub mask: u64,
+    pub next: u64,
+}
+
+#[derive(AsValue)]
+pub struct CallTrace<'ink> {
+    pub ring: Value<'ink, *mut CallTraceRing<'ink>>,
+    pub capacity: u32,
+    pub num_functions: u32,
+    pub function_ids: Value<'ink, *const u32>,
+    pub function_names: Value<'ink, *const *const u8>,
+}
+
 #[derive(AsValue)]
 pub struct AssemblyInfo<'ink> {
     pub symbols: ModuleInfo<'ink>,
@@ -139,4 +256,12 @@ pub struct AssemblyInfo<'ink> {
     pub type_lut: TypeLut<'ink>,
     pub dependencies: Value<'ink, *const *const u8>,
     pub num_dependencies: u32,
+    pub build_info: BuildInfo<'ink>,
+}
+
+#[derive(AsValue)]
+pub struct BuildInfo<'ink> {
+    pub target_triple: Value<'ink, *const u8>,
+    pub optimization_level: abi::OptimizationLevel,
+    pub has_debug_info: bool,
 }
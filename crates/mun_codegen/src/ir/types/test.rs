@@ -35,13 +35,26 @@ fn abi_struct_sizes() {
     test_type_size::<abi::TypeId<'_>, ir::TypeId<'_>>(&type_context);
     test_type_size::<abi::PointerTypeId<'_>, ir::PointerTypeId<'_>>(&type_context);
     test_type_size::<abi::ArrayTypeId<'_>, ir::ArrayTypeId<'_>>(&type_context);
+    test_type_size::<abi::SliceTypeId<'_>, ir::SliceTypeId<'_>>(&type_context);
     test_type_size::<abi::TypeDefinitionData<'_>, ir::TypeDefinitionData<'_>>(&type_context);
     test_type_size::<abi::StructDefinition<'_>, ir::StructDefinition<'_>>(&type_context);
     test_type_size::<abi::TypeDefinition<'_>, ir::TypeDefinition<'_>>(&type_context);
     test_type_size::<abi::FunctionSignature<'_>, ir::FunctionSignature<'_>>(&type_context);
     test_type_size::<abi::FunctionPrototype<'_>, ir::FunctionPrototype<'_>>(&type_context);
+    test_type_size::<abi::InlineAttribute, abi::InlineAttribute>(&type_context);
+    test_type_size::<abi::OptimizeAttribute, abi::OptimizeAttribute>(&type_context);
+    test_type_size::<abi::CallingConvention, abi::CallingConvention>(&type_context);
+    test_type_size::<abi::FunctionAttributes, ir::FunctionAttributes>(&type_context);
+    test_type_size::<abi::StaticDefinition<'_>, ir::StaticDefinition<'_>>(&type_context);
+    test_type_size::<abi::ConstDefinition<'_>, ir::ConstDefinition<'_>>(&type_context);
     test_type_size::<abi::ModuleInfo<'_>, ir::ModuleInfo<'_>>(&type_context);
     test_type_size::<abi::DispatchTable<'_>, ir::DispatchTable<'_>>(&type_context);
     test_type_size::<abi::TypeLut<'_>, ir::TypeLut<'_>>(&type_context);
+    test_type_size::<abi::OptimizationLevel, abi::OptimizationLevel>(&type_context);
+    test_type_size::<abi::BuildInfo, ir::BuildInfo<'_>>(&type_context);
     test_type_size::<abi::AssemblyInfo<'_>, ir::AssemblyInfo<'_>>(&type_context);
+    test_type_size::<abi::FormatHeader, ir::FormatHeader>(&type_context);
+    test_type_size::<abi::CompilerInfo, ir::CompilerInfo>(&type_context);
+    test_type_size::<abi::CallTraceRing, ir::CallTraceRing>(&type_context);
+    test_type_size::<abi::CallTrace, ir::CallTrace>(&type_context);
 }
@@ -1,11 +1,21 @@
 pub use inkwell::{builder::Builder, context::Context, module::Module, OptimizationLevel};
 
 pub use crate::{
-    assembly::{AssemblyIr, TargetAssembly},
-    code_gen::AssemblyBuilder,
+    assembly::{AssemblyError, AssemblyIr, TargetAssembly},
+    c_header::CHeader,
+    code_gen::{
+        supports_split_debug_info, AssemblyBuilder, FunctionTiming, FunctionTimings,
+        FunctionTimingsRef, PassHook, PassHookRef, Relocation, RelocationTarget,
+    },
     db::{CodeGenDatabase, CodeGenDatabaseStorage},
+    ir::debug_info::DebugInfo,
+    linker::LinkerOutput,
     module_group::ModuleGroup,
     module_partition::{ModuleGroupId, ModulePartition},
+    pgo::ProfileGuidedOptimization,
+    rust_bindings::RustBindings,
+    sanitizer::Sanitizer,
+    target_spec::TargetSpec,
 };
 
 /// This library generates machine code from HIR using inkwell which is a safe
@@ -15,12 +25,14 @@ mod db;
 #[macro_use]
 mod ir;
 mod assembly;
+mod c_header;
 
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod test;
 
+pub mod mangle;
 pub mod value;
 
 mod apple;
@@ -28,4 +40,15 @@ pub(crate) mod intrinsics;
 mod linker;
 mod module_group;
 mod module_partition;
+pub mod pgo;
+mod rust_bindings;
+pub mod sanitizer;
+mod target_spec;
 pub(crate) mod type_info;
+
+/// The version of the compiler that is embedded in every generated munlib
+pub const COMPILER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The version of LLVM that is used to generate code. This must match the LLVM
+/// version feature that `inkwell` is built with.
+pub const LLVM_VERSION: &str = "14.0";
@@ -6,9 +6,10 @@ use std::{
 
 use mun_abi as abi;
 use mun_target::{spec, spec::LinkerFlavor};
+use parking_lot::Mutex;
 use thiserror::Error;
 
-use crate::apple::get_apple_sdk_root;
+use crate::{apple::get_apple_sdk_root, Sanitizer};
 
 #[derive(Error, Debug)]
 pub enum LinkerError {
@@ -20,6 +21,21 @@ pub enum LinkerError {
 
     /// Could not locate platform SDK
     PlatformSdkMissing(String),
+
+    /// The linker flavor does not support the requested output kind
+    UnsupportedOutput(LinkerOutput),
+
+    /// Could not locate the runtime library of a sanitizer
+    SanitizerRuntimeMissing(String),
+
+    /// The linker flavor cannot link the runtime of a sanitizer
+    UnsupportedSanitizer(Sanitizer),
+
+    /// Could not locate the runtime library of the profiler
+    ProfilerRuntimeMissing(String),
+
+    /// The linker flavor cannot link the runtime of the profiler
+    UnsupportedProfiler,
 }
 
 impl fmt::Display for LinkerError {
@@ -34,30 +50,128 @@ impl fmt::Display for LinkerError {
             LinkerError::PlatformSdkMissing(err) => {
                 write!(f, "could not find platform sdk: {err}")
             }
+            LinkerError::UnsupportedOutput(output) => {
+                write!(f, "the target linker cannot produce a {output}")
+            }
+            LinkerError::SanitizerRuntimeMissing(err) => write!(f, "{err}"),
+            LinkerError::UnsupportedSanitizer(sanitizer) => {
+                write!(f, "the target linker cannot link the {sanitizer} runtime")
+            }
+            LinkerError::ProfilerRuntimeMissing(err) => write!(f, "{err}"),
+            LinkerError::UnsupportedProfiler => {
+                write!(f, "the target linker cannot link the profiler runtime")
+            }
+        }
+    }
+}
+
+/// The kind of binary the linker produces.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkerOutput {
+    /// A dynamic library that can be loaded by the runtime. This is the
+    /// default.
+    #[default]
+    SharedObject,
+
+    /// A position-independent executable. Symbols are hidden by default, with
+    /// the exception of the symbols required by the runtime.
+    PositionIndependentExecutable,
+
+    /// A relocatable object file that can be linked into another binary.
+    Relocatable,
+}
+
+impl fmt::Display for LinkerOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinkerOutput::SharedObject => write!(f, "shared object"),
+            LinkerOutput::PositionIndependentExecutable => {
+                write!(f, "position-independent executable")
+            }
+            LinkerOutput::Relocatable => write!(f, "relocatable object"),
         }
     }
 }
 
-pub fn create_with_target(target: &spec::Target) -> Box<dyn Linker> {
+/// Returns the symbols that must always be exported from a munlib, regardless
+/// of the default visibility of the output kind, under the names that
+/// `entry_symbols` specify. The call trace global is only defined if the
+/// munlib records a call trace.
+fn exported_symbols(entry_symbols: &abi::EntrySymbols, call_trace: bool) -> Vec<String> {
+    let mut symbols = vec![
+        entry_symbols.format_header.clone(),
+        entry_symbols.get_info.clone(),
+        entry_symbols.get_version.clone(),
+        entry_symbols.set_allocator_handle.clone(),
+        entry_symbols.compiler_info.clone(),
+    ];
+    if call_trace {
+        symbols.push(entry_symbols.call_trace.clone());
+    }
+    symbols
+}
+
+/// Invokes lld with `args`. lld is not reentrant: it keeps global state while
+/// linking. Assemblies can be built on multiple threads, so concurrent
+/// invocations are serialized.
+fn lld_link(flavor: lld_rs::LldFlavor, args: &[String]) -> Result<(), LinkerError> {
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    let _guard = LOCK.lock();
+    lld_rs::link(flavor, args)
+        .ok()
+        .map_err(LinkerError::LinkError)
+}
+
+/// Constructs a linker for `target` that exports the symbols through which
+/// the runtime accesses a munlib under the names that `entry_symbols`
+/// specify.
+pub fn create_with_target(
+    target: &spec::Target,
+    entry_symbols: &abi::EntrySymbols,
+    call_trace: bool,
+) -> Box<dyn Linker> {
+    let exported_symbols = exported_symbols(entry_symbols, call_trace);
     match target.options.linker_flavor {
-        LinkerFlavor::Ld => Box::new(LdLinker::new(target)),
-        LinkerFlavor::Ld64 => Box::new(Ld64Linker::new(target)),
-        LinkerFlavor::Msvc => Box::new(MsvcLinker::new(target)),
+        LinkerFlavor::Ld => Box::new(LdLinker::new(target, exported_symbols)),
+        LinkerFlavor::Ld64 => Box::new(Ld64Linker::new(target, exported_symbols)),
+        LinkerFlavor::Msvc => Box::new(MsvcLinker::new(target, exported_symbols)),
     }
 }
 
 pub trait Linker {
     fn add_object(&mut self, path: &Path) -> Result<(), LinkerError>;
+    fn add_sanitizer_runtime(
+        &mut self,
+        sanitizer: Sanitizer,
+        path: &Path,
+    ) -> Result<(), LinkerError>;
+    fn add_profiler_runtime(&mut self, path: &Path) -> Result<(), LinkerError>;
     fn build_shared_object(&mut self, path: &Path) -> Result<(), LinkerError>;
+    fn build_position_independent_executable(&mut self, path: &Path) -> Result<(), LinkerError>;
+    fn build_relocatable(&mut self, path: &Path) -> Result<(), LinkerError>;
     fn finalize(&mut self) -> Result<(), LinkerError>;
+
+    /// Configures the linker to produce the specified kind of `output` at
+    /// `path`.
+    fn build(&mut self, path: &Path, output: LinkerOutput) -> Result<(), LinkerError> {
+        match output {
+            LinkerOutput::SharedObject => self.build_shared_object(path),
+            LinkerOutput::PositionIndependentExecutable => {
+                self.build_position_independent_executable(path)
+            }
+            LinkerOutput::Relocatable => self.build_relocatable(path),
+        }
+    }
 }
 
 struct LdLinker {
     args: Vec<String>,
+    exported_symbols: Vec<String>,
 }
 
 impl LdLinker {
-    fn new(target: &spec::Target) -> Self {
+    fn new(target: &spec::Target, exported_symbols: Vec<String>) -> Self {
         LdLinker {
             args: target
                 .options
@@ -66,6 +180,7 @@ impl LdLinker {
                 .cloned()
                 .map(Cow::into_owned)
                 .collect(),
+            exported_symbols,
         }
     }
 }
@@ -80,6 +195,31 @@ impl Linker for LdLinker {
         Ok(())
     }
 
+    fn add_sanitizer_runtime(
+        &mut self,
+        _sanitizer: Sanitizer,
+        path: &Path,
+    ) -> Result<(), LinkerError> {
+        // Link against the shared runtime library, and find it in its current
+        // location when the binary is loaded
+        self.add_object(path)?;
+        let dir = path
+            .parent()
+            .and_then(Path::to_str)
+            .ok_or_else(|| LinkerError::PathError(path.to_owned()))?;
+        self.args.push("-rpath".to_owned());
+        self.args.push(dir.to_owned());
+        Ok(())
+    }
+
+    fn add_profiler_runtime(&mut self, path: &Path) -> Result<(), LinkerError> {
+        // The instrumented code does not reference the static runtime library,
+        // so pull in the object that writes the profile when the process exits
+        self.args.push("-u".to_owned());
+        self.args.push("__llvm_profile_runtime".to_owned());
+        self.add_object(path)
+    }
+
     fn build_shared_object(&mut self, path: &Path) -> Result<(), LinkerError> {
         let path_str = path
             .to_str()
@@ -95,20 +235,55 @@ impl Linker for LdLinker {
         Ok(())
     }
 
+    fn build_position_independent_executable(&mut self, path: &Path) -> Result<(), LinkerError> {
+        let path_str = path
+            .to_str()
+            .ok_or_else(|| LinkerError::PathError(path.to_owned()))?;
+
+        // Link as position-independent executable
+        self.args.push("-pie".to_owned());
+
+        // Executables don't export symbols by default, so explicitly export the
+        // symbols required by the runtime.
+        for symbol in &self.exported_symbols {
+            self.args.push(format!("--export-dynamic-symbol={symbol}"));
+        }
+
+        // Specify output path
+        self.args.push("-o".to_owned());
+        self.args.push(path_str.to_owned());
+
+        Ok(())
+    }
+
+    fn build_relocatable(&mut self, path: &Path) -> Result<(), LinkerError> {
+        let path_str = path
+            .to_str()
+            .ok_or_else(|| LinkerError::PathError(path.to_owned()))?;
+
+        // Link as relocatable object
+        self.args.push("-r".to_owned());
+
+        // Specify output path
+        self.args.push("-o".to_owned());
+        self.args.push(path_str.to_owned());
+
+        Ok(())
+    }
+
     fn finalize(&mut self) -> Result<(), LinkerError> {
-        lld_rs::link(lld_rs::LldFlavor::Elf, &self.args)
-            .ok()
-            .map_err(LinkerError::LinkError)
+        lld_link(lld_rs::LldFlavor::Elf, &self.args)
     }
 }
 
 struct Ld64Linker {
     args: Vec<String>,
     target: spec::Target,
+    exported_symbols: Vec<String>,
 }
 
 impl Ld64Linker {
-    fn new(target: &spec::Target) -> Self {
+    fn new(target: &spec::Target, exported_symbols: Vec<String>) -> Self {
         let args = target
             .options
             .pre_link_args
@@ -120,6 +295,7 @@ impl Ld64Linker {
         Ld64Linker {
             args,
             target: target.clone(),
+            exported_symbols,
         }
     }
 
@@ -164,6 +340,30 @@ impl Linker for Ld64Linker {
         Ok(())
     }
 
+    fn add_sanitizer_runtime(
+        &mut self,
+        _sanitizer: Sanitizer,
+        path: &Path,
+    ) -> Result<(), LinkerError> {
+        // Link against the shared runtime library, and find it in its current
+        // location when the binary is loaded
+        self.add_object(path)?;
+        let dir = path
+            .parent()
+            .and_then(Path::to_str)
+            .ok_or_else(|| LinkerError::PathError(path.to_owned()))?;
+        self.args.push("-rpath".to_owned());
+        self.args.push(dir.to_owned());
+        Ok(())
+    }
+
+    fn add_profiler_runtime(&mut self, path: &Path) -> Result<(), LinkerError> {
+        // Mach-O symbols are prefixed with an underscore
+        self.args.push("-u".to_owned());
+        self.args.push("___llvm_profile_runtime".to_owned());
+        self.add_object(path)
+    }
+
     fn build_shared_object(&mut self, path: &Path) -> Result<(), LinkerError> {
         let path_str = path
             .to_str()
@@ -193,19 +393,59 @@ impl Linker for Ld64Linker {
         Ok(())
     }
 
+    fn build_position_independent_executable(&mut self, path: &Path) -> Result<(), LinkerError> {
+        let path_str = path
+            .to_str()
+            .ok_or_else(|| LinkerError::PathError(path.to_owned()))?;
+
+        // Link as position-independent executable
+        self.args.push("-execute".to_owned());
+        self.args.push("-pie".to_owned());
+
+        self.add_apple_sdk()?;
+        self.args.push("-lSystem".to_owned());
+
+        // Ensure that the symbols required by the runtime remain exported. Mach-O
+        // symbols are prefixed with an underscore.
+        for symbol in &self.exported_symbols {
+            self.args.push("-exported_symbol".to_owned());
+            self.args.push(format!("_{symbol}"));
+        }
+
+        // Specify output path
+        self.args.push("-o".to_owned());
+        self.args.push(path_str.to_owned());
+
+        Ok(())
+    }
+
+    fn build_relocatable(&mut self, path: &Path) -> Result<(), LinkerError> {
+        let path_str = path
+            .to_str()
+            .ok_or_else(|| LinkerError::PathError(path.to_owned()))?;
+
+        // Link as relocatable object
+        self.args.push("-r".to_owned());
+
+        // Specify output path
+        self.args.push("-o".to_owned());
+        self.args.push(path_str.to_owned());
+
+        Ok(())
+    }
+
     fn finalize(&mut self) -> Result<(), LinkerError> {
-        lld_rs::link(lld_rs::LldFlavor::MachO, &self.args)
-            .ok()
-            .map_err(LinkerError::LinkError)
+        lld_link(lld_rs::LldFlavor::MachO, &self.args)
     }
 }
 
 struct MsvcLinker {
     args: Vec<String>,
+    exported_symbols: Vec<String>,
 }
 
 impl MsvcLinker {
-    fn new(target: &spec::Target) -> Self {
+    fn new(target: &spec::Target, exported_symbols: Vec<String>) -> Self {
         MsvcLinker {
             args: target
                 .options
@@ -214,6 +454,7 @@ impl MsvcLinker {
                 .cloned()
                 .map(Cow::into_owned)
                 .collect(),
+            exported_symbols,
         }
     }
 }
@@ -228,6 +469,18 @@ impl Linker for MsvcLinker {
         Ok(())
     }
 
+    fn add_sanitizer_runtime(
+        &mut self,
+        sanitizer: Sanitizer,
+        _path: &Path,
+    ) -> Result<(), LinkerError> {
+        Err(LinkerError::UnsupportedSanitizer(sanitizer))
+    }
+
+    fn add_profiler_runtime(&mut self, _path: &Path) -> Result<(), LinkerError> {
+        Err(LinkerError::UnsupportedProfiler)
+    }
+
     fn build_shared_object(&mut self, path: &Path) -> Result<(), LinkerError> {
         let dll_path_str = path
             .to_str()
@@ -239,19 +492,27 @@ impl Linker for MsvcLinker {
 
         self.args.push("/DLL".to_owned());
         self.args.push("/NOENTRY".to_owned());
-        self.args.push(format!("/EXPORT:{}", abi::GET_INFO_FN_NAME));
-        self.args
-            .push(format!("/EXPORT:{}", abi::GET_VERSION_FN_NAME));
-        self.args
-            .push(format!("/EXPORT:{}", abi::SET_ALLOCATOR_HANDLE_FN_NAME));
+        for symbol in &self.exported_symbols {
+            self.args.push(format!("/EXPORT:{symbol}"));
+        }
         self.args.push(format!("/IMPLIB:{dll_lib_path_str}"));
         self.args.push(format!("/OUT:{dll_path_str}"));
         Ok(())
     }
 
+    fn build_position_independent_executable(&mut self, _path: &Path) -> Result<(), LinkerError> {
+        // A munlib has no entry point, which an MSVC executable requires.
+        Err(LinkerError::UnsupportedOutput(
+            LinkerOutput::PositionIndependentExecutable,
+        ))
+    }
+
+    fn build_relocatable(&mut self, _path: &Path) -> Result<(), LinkerError> {
+        // `lld-link` cannot produce relocatable objects.
+        Err(LinkerError::UnsupportedOutput(LinkerOutput::Relocatable))
+    }
+
     fn finalize(&mut self) -> Result<(), LinkerError> {
-        lld_rs::link(lld_rs::LldFlavor::Coff, &self.args)
-            .ok()
-            .map_err(LinkerError::LinkError)
+        lld_link(lld_rs::LldFlavor::Coff, &self.args)
     }
 }
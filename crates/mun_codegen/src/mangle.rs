@@ -0,0 +1,220 @@
+//! The mangling scheme of the symbols that the compiler emits for functions.
+//!
+//! Every function that is not marked `#[no_mangle]` is emitted under a symbol
+//! that encodes the path of the module that defines it and its name. Tools
+//! like debuggers, linkers and profilers can use [`demangle`] to recover the
+//! path of the function, which is also the name under which the function is
+//! stored in the reflection information and the dispatch table.
+//!
+//! A mangled symbol has the following grammar:
+//!
+//! ```text
+//! symbol  := "_MN" kind segment+ "E"
+//! kind    := ""       // the function itself
+//!          | "W"      // the wrapper that marshals its arguments for the runtime
+//! segment := <decimal length> <identifier>
+//! ```
+//!
+//! For instance, the function `foo::bar` is emitted as `_MN3foo3barE` and its
+//! wrapper as `_MNW3foo3barE`. The segments are length-prefixed, so distinct
+//! paths always produce distinct symbols. Mun supports neither overloading nor
+//! generics, which means that the path uniquely identifies a function within
+//! an assembly and the signature does not have to be encoded.
+//!
+//! `#[no_mangle]` functions are exported under their own name instead.
+
+use std::fmt;
+
+use mun_hir::HirDatabase;
+
+/// The prefix of every mangled symbol.
+pub const PREFIX: &str = "_MN";
+
+/// The kind of a mangled symbol.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SymbolKind {
+    /// The function itself.
+    Function,
+    /// The wrapper of a function whose signature cannot be called by the
+    /// runtime directly, e.g. because it passes value structs.
+    Wrapper,
+}
+
+/// The path and kind of a symbol, as recovered by [`demangle`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DemangledSymbol {
+    /// The names of the modules that contain the function, followed by the
+    /// name of the function.
+    pub path: Vec<String>,
+    /// The kind of the symbol.
+    pub kind: SymbolKind,
+}
+
+impl fmt::Display for DemangledSymbol {
+    /// Formats the symbol as the path of the function, e.g. `foo::bar`, which
+    /// is the name that is used for reflection. Wrappers are suffixed with
+    /// `::{wrapper}`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path.join("::"))?;
+        if self.kind == SymbolKind::Wrapper {
+            write!(f, "::{{wrapper}}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Mangles the path of a function into a symbol of the specified kind.
+///
+/// # Panics
+///
+/// Panics if `path` is empty.
+pub fn mangle<S: AsRef<str>>(path: &[S], kind: SymbolKind) -> String {
+    assert!(!path.is_empty(), "cannot mangle an empty path");
+
+    let mut symbol = String::from(PREFIX);
+    if kind == SymbolKind::Wrapper {
+        symbol.push('W');
+    }
+    for segment in path {
+        let segment = segment.as_ref();
+        symbol.push_str(&segment.len().to_string());
+        symbol.push_str(segment);
+    }
+    symbol.push('E');
+    symbol
+}
+
+/// Recovers the path and kind of a symbol that was produced by [`mangle`].
+/// Returns `None` if `symbol` is not a valid mangled symbol, e.g. because it
+/// belongs to a `#[no_mangle]` function.
+pub fn demangle(symbol: &str) -> Option<DemangledSymbol> {
+    let mut rest = symbol.strip_prefix(PREFIX)?;
+    let kind = match rest.strip_prefix('W') {
+        Some(stripped) => {
+            rest = stripped;
+            SymbolKind::Wrapper
+        }
+        None => SymbolKind::Function,
+    };
+
+    let mut path = Vec::new();
+    loop {
+        if rest == "E" && !path.is_empty() {
+            return Some(DemangledSymbol { path, kind });
+        }
+
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        // Leading zeros would make several symbols demangle to the same path
+        if digits == 0 || rest.starts_with('0') {
+            return None;
+        }
+        let len: usize = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+        let segment = rest.get(..len)?;
+        path.push(segment.to_owned());
+        rest = &rest[len..];
+    }
+}
+
+/// Returns the symbol under which the specified function is emitted.
+pub(crate) fn function_symbol(db: &dyn HirDatabase, function: mun_hir::Function) -> String {
+    if function.is_no_mangle(db) {
+        function.name(db).to_string()
+    } else {
+        mangle(&function_path(db, function), SymbolKind::Function)
+    }
+}
+
+/// Returns the symbol under which the wrapper of the specified function is
+/// emitted.
+pub(crate) fn wrapper_symbol(db: &dyn HirDatabase, function: mun_hir::Function) -> String {
+    mangle(&function_path(db, function), SymbolKind::Wrapper)
+}
+
+/// Returns the names of the modules that contain the function, followed by the
+/// name of the function. Joined by `::` this equals `Function::full_name`.
+fn function_path(db: &dyn HirDatabase, function: mun_hir::Function) -> Vec<String> {
+    function
+        .module(db)
+        .path_to_root(db)
+        .into_iter()
+        .filter_map(|module| module.name(db))
+        .chain(std::iter::once(function.name(db)))
+        .map(|name| name.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{demangle, mangle, DemangledSymbol, SymbolKind};
+
+    #[test]
+    fn mangle_function() {
+        assert_eq!(mangle(&["main"], SymbolKind::Function), "_MN4mainE");
+        assert_eq!(
+            mangle(&["foo", "bar"], SymbolKind::Function),
+            "_MN3foo3barE"
+        );
+        assert_eq!(
+            mangle(&["foo", "bar"], SymbolKind::Wrapper),
+            "_MNW3foo3barE"
+        );
+        assert_eq!(
+            mangle(&["a1", "b_234567890"], SymbolKind::Function),
+            "_MN2a111b_234567890E"
+        );
+    }
+
+    #[test]
+    fn demangle_roundtrip() {
+        for (path, kind) in [
+            (vec!["main"], SymbolKind::Function),
+            (vec!["foo", "bar"], SymbolKind::Function),
+            (vec!["foo", "bar"], SymbolKind::Wrapper),
+            (vec!["a1", "b_234567890"], SymbolKind::Function),
+        ] {
+            let symbol = mangle(&path, kind);
+            assert_eq!(
+                demangle(&symbol),
+                Some(DemangledSymbol {
+                    path: path.iter().map(ToString::to_string).collect(),
+                    kind
+                })
+            );
+        }
+
+        assert_eq!(
+            demangle("_MNW3foo3barE").unwrap().to_string(),
+            "foo::bar::{wrapper}"
+        );
+        assert_eq!(demangle("_MN3foo3barE").unwrap().to_string(), "foo::bar");
+    }
+
+    #[test]
+    fn demangle_invalid() {
+        for symbol in [
+            "main",
+            "_MN",
+            "_MNE",
+            "_MN4mainE_",
+            "_MN5mainE",
+            "_MN04mainE",
+            "_MN4main",
+            "_MNX4mainE",
+        ] {
+            assert_eq!(demangle(symbol), None, "{symbol}");
+        }
+    }
+
+    #[test]
+    fn distinct_paths_do_not_collide() {
+        assert_ne!(
+            mangle(&["ab", "c"], SymbolKind::Function),
+            mangle(&["a", "bc"], SymbolKind::Function)
+        );
+        assert_ne!(
+            mangle(&["foo", "bar"], SymbolKind::Function),
+            mangle(&["foo_bar"], SymbolKind::Function)
+        );
+    }
+}
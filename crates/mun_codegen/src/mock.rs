@@ -1,13 +1,13 @@
 use std::sync::Arc;
 
-use mun_hir::{FileId, HirDatabase, SourceDatabase, SourceRoot, SourceRootId};
+use mun_hir::{DefDatabase, FileId, SourceDatabase, SourceRoot, SourceRootId};
 use mun_paths::RelativePathBuf;
 use mun_target::spec::Target;
 use parking_lot::Mutex;
 
 use crate::{
     db::{CodeGenDatabase, CodeGenDatabaseStorage},
-    OptimizationLevel,
+    DebugInfo, LinkerOutput, OptimizationLevel,
 };
 
 /// A mock implementation of the IR database. It can be used to set up a simple
@@ -71,6 +71,25 @@ impl Default for MockDatabase {
             events: Mutex::default(),
         };
         db.set_optimization_level(OptimizationLevel::Default);
+        db.set_minimize_size(false);
+        db.set_linker_output(LinkerOutput::SharedObject);
+        db.set_pass_hook(None);
+        db.set_verify_llvm(true);
+        db.set_debug_info(DebugInfo::None);
+        db.set_static_dispatch(false);
+        db.set_emit_relocations(false);
+        db.set_split_debug_info(false);
+        db.set_alloc_debug(false);
+        db.set_init_structs(true);
+        db.set_instrument_timing(false);
+        db.set_call_trace_ring(None);
+        db.set_sanitizer(None);
+        db.set_pgo(None);
+        db.set_gc_functions(false);
+        db.set_reflect_arg_names(false);
+        db.set_reflect_fn_attributes(false);
+        db.set_function_timings(None);
+        db.set_entry_symbols(mun_abi::EntrySymbols::default());
         db.set_target(Target::host_target().unwrap());
         db
     }
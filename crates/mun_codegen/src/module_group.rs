@@ -88,6 +88,11 @@ impl ModuleGroup {
             return false;
         }
 
+        // Functions that are invoked by name through reflection are always exported
+        if function.is_reflect(db) || function.is_init(db) {
+            return true;
+        }
+
         let vis = function.visibility(db);
         match vis {
             // If the function is publicly accessible it must always be exported
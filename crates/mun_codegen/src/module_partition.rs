@@ -73,10 +73,23 @@ impl Index<ModuleGroupId> for ModulePartition {
 /// Builds a module partition from the contents of the database
 pub(crate) fn build_partition(db: &dyn CodeGenDatabase) -> Arc<ModulePartition> {
     let mut partition = ModulePartition::default();
-    for module in mun_hir::Package::all(db.upcast())
+    let modules = mun_hir::Package::all(db.upcast())
         .into_iter()
-        .flat_map(|package| package.modules(db.upcast()))
-    {
+        .flat_map(|package| package.modules(db.upcast()));
+
+    // With static dispatch all modules are placed in a single group. Calls between
+    // functions in the same group do not go through the dispatch table. Functions
+    // are emitted under symbols that encode the path of their module, so functions
+    // with the same name in different modules do not collide.
+    if db.static_dispatch() {
+        partition.add_group(
+            db.upcast(),
+            ModuleGroup::new(db.upcast(), String::from("mod"), modules),
+        );
+        return Arc::new(partition);
+    }
+
+    for module in modules {
         let name = if module.name(db.upcast()).is_some() {
             module.full_name(db.upcast())
         } else {
@@ -0,0 +1,353 @@
+use std::{
+    env,
+    ffi::CString,
+    fs::File,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use inkwell::{
+    module::{Linkage, Module},
+    passes::PassBuilderOptions,
+    targets::TargetMachine,
+    GlobalVisibility,
+};
+use mun_target::spec;
+use parking_lot::Mutex;
+
+use crate::{code_gen::CodeGenerationError, linker::LinkerError, sanitizer::RUNTIME_DIR_ENV};
+
+/// The magic number with which indexed profiles, as produced by
+/// `llvm-profdata merge`, start.
+const INDEXED_PROFILE_MAGIC: [u8; 8] = *b"\xfflprofi\x81";
+
+/// The name of the global from which the profiler runtime reads the path of
+/// the raw profile it writes.
+const PROFILE_FILENAME_VAR: &str = "__llvm_profile_filename";
+
+/// The phase of a profile-guided optimization (PGO) build.
+///
+/// A PGO build takes two phases. First, assemblies are built with
+/// [`ProfileGuidedOptimization::Generate`], which instruments them to record
+/// how often every block of code is executed. Running representative
+/// workloads writes raw profiles, which are merged into an indexed profile
+/// with `llvm-profdata merge -o mun.profdata *.profraw`. Then, the assemblies
+/// are rebuilt with [`ProfileGuidedOptimization::Use`], which optimizes them
+/// according to the indexed profile, e.g. to lay out hot blocks together and
+/// to inline hot call sites.
+///
+/// Profile records are associated with functions by their symbol name, so
+/// both phases must be built with the same entry symbol and symbol version.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ProfileGuidedOptimization {
+    /// Instrument assemblies to write a raw profile to the specified path when
+    /// the process that loaded them exits. The path may contain the patterns
+    /// that the `LLVM_PROFILE_FILE` environment variable supports, which
+    /// overrides it at runtime.
+    Generate(PathBuf),
+
+    /// Optimize assemblies with the indexed profile at the specified path.
+    Use(PathBuf),
+}
+
+impl ProfileGuidedOptimization {
+    /// The file name pattern of raw profiles. `%m` is replaced by a signature
+    /// of the instrumented binary, such that every assembly writes its own
+    /// profile and profiles of several runs are merged.
+    pub const RAW_PROFILE_FILE_NAME: &'static str = "default_%m.profraw";
+
+    /// Returns the phase that instruments assemblies to write their raw
+    /// profiles into `dir`.
+    pub fn generate_in(dir: &Path) -> Self {
+        ProfileGuidedOptimization::Generate(dir.join(Self::RAW_PROFILE_FILE_NAME))
+    }
+
+    /// Returns true if code for `target` can be instrumented to generate a
+    /// profile, i.e. whether the target linker can link the profiler runtime.
+    pub fn is_supported_by(&self, target: &spec::Target) -> bool {
+        match self {
+            ProfileGuidedOptimization::Generate(_) => {
+                target.options.linker_flavor != spec::LinkerFlavor::Msvc
+            }
+            ProfileGuidedOptimization::Use(_) => true,
+        }
+    }
+
+    /// Checks that the profile that is used, if any, is an indexed profile
+    /// that LLVM can read.
+    ///
+    /// LLVM aborts the process if it fails to read a profile, so every profile
+    /// must be validated before it is used. Profiles that are stale, i.e. that
+    /// were generated from a different version of the code, are valid; LLVM
+    /// warns about the functions whose control flow changed and ignores their
+    /// records.
+    pub fn validate(&self) -> Result<(), io::Error> {
+        match self {
+            ProfileGuidedOptimization::Generate(_) => Ok(()),
+            ProfileGuidedOptimization::Use(path) => validate_profile(path),
+        }
+    }
+}
+
+/// Returns the file name of the static profiler runtime library for `target`.
+pub fn profiler_runtime_library(target: &spec::Target) -> String {
+    if target.options.is_like_osx {
+        String::from("libclang_rt.profile_osx.a")
+    } else {
+        format!("libclang_rt.profile-{}.a", target.arch)
+    }
+}
+
+/// Checks that the file at `path` starts like an indexed profile.
+fn validate_profile(path: &Path) -> Result<(), io::Error> {
+    let mut magic = [0u8; 8];
+    File::open(path)?.read_exact(&mut magic).map_err(|e| {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            io::Error::new(io::ErrorKind::InvalidData, "file is too small")
+        } else {
+            e
+        }
+    })?;
+
+    if magic != INDEXED_PROFILE_MAGIC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not an indexed profile, raw profiles must be merged with `llvm-profdata merge` first",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Instruments or annotates all functions defined in `module` for the phase
+/// `pgo` of a profile-guided optimization build. This must be run before the
+/// module is optimized, such that the optimizer takes the instrumentation or
+/// the profile into account.
+pub(crate) fn apply_to_module(
+    module: &Module<'_>,
+    target_machine: &TargetMachine,
+    pgo: &ProfileGuidedOptimization,
+) -> Result<(), CodeGenerationError> {
+    let passes = match pgo {
+        ProfileGuidedOptimization::Generate(path) => {
+            set_profile_filename(module, path);
+            "pgo-instr-gen,instrprof"
+        }
+        ProfileGuidedOptimization::Use(path) => {
+            pgo.validate().map_err(|e| {
+                CodeGenerationError::ProfileError(format!(
+                    "could not read profile `{}`: {e}",
+                    path.display()
+                ))
+            })?;
+            set_profile_use_path(path)?;
+            "pgo-instr-use"
+        }
+    };
+
+    module
+        .run_passes(passes, target_machine, PassBuilderOptions::create())
+        .map_err(|e| CodeGenerationError::ProfileError(e.to_string()))
+}
+
+/// Defines the global from which the profiler runtime reads the path of the
+/// raw profile.
+fn set_profile_filename(module: &Module<'_>, path: &Path) {
+    let context = module.get_context();
+    let path = context.const_string(path.to_string_lossy().as_bytes(), true);
+    let global = module.add_global(path.get_type(), None, PROFILE_FILENAME_VAR);
+    global.set_initializer(&path);
+    global.set_constant(true);
+    global.set_linkage(Linkage::WeakAny);
+    global.set_visibility(GlobalVisibility::Hidden);
+}
+
+/// Sets the path of the profile that the `pgo-instr-use` pass reads. LLVM
+/// only accepts the path as a command-line option, which is global to the
+/// process and which can only be set once. All assemblies that a process
+/// builds therefore have to use the same profile; using another one is an
+/// error instead of silently using the first.
+fn set_profile_use_path(path: &Path) -> Result<(), CodeGenerationError> {
+    static CURRENT_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+    let mut current_path = CURRENT_PATH.lock();
+    if let Some(current_path) = current_path.as_deref() {
+        return if current_path == path {
+            Ok(())
+        } else {
+            Err(CodeGenerationError::ProfileError(format!(
+                "cannot use profile `{}`, profile `{}` is already used by this process",
+                path.display(),
+                current_path.display()
+            )))
+        };
+    }
+
+    let option = path
+        .to_str()
+        .and_then(|path| CString::new(format!("-pgo-test-profile-file={path}")).ok())
+        .ok_or_else(|| {
+            CodeGenerationError::ProfileError(format!(
+                "profile path `{}` is not valid UTF-8",
+                path.display()
+            ))
+        })?;
+    let program = CString::new("mun").expect("program name is a valid C string");
+    let args = [program.as_ptr(), option.as_ptr()];
+
+    // SAFETY: the arguments are valid C strings that outlive the call, and
+    // concurrent calls are serialized by the lock.
+    unsafe {
+        llvm_sys::support::LLVMParseCommandLineOptions(
+            args.len() as i32,
+            args.as_ptr(),
+            std::ptr::null(),
+        );
+    }
+
+    *current_path = Some(path.to_owned());
+    Ok(())
+}
+
+/// Locates the profiler runtime library for `target` in the directory
+/// specified by the [`RUNTIME_DIR_ENV`] environment variable, which also
+/// contains the runtime libraries of the sanitizers.
+pub(crate) fn find_profiler_runtime_library(target: &spec::Target) -> Result<PathBuf, LinkerError> {
+    let file_name = profiler_runtime_library(target);
+    let path = env::var_os(RUNTIME_DIR_ENV)
+        .map(|dir| PathBuf::from(dir).join(&file_name))
+        .filter(|path| path.is_file());
+    path.ok_or_else(|| {
+        LinkerError::ProfilerRuntimeMissing(format!(
+            "could not find the profiler runtime `{file_name}`, set {RUNTIME_DIR_ENV} to the \
+             directory that contains it"
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use inkwell::{context::Context, module::Module};
+    use mun_target::spec::Target;
+
+    use super::{
+        apply_to_module, profiler_runtime_library, set_profile_use_path, validate_profile,
+        ProfileGuidedOptimization, INDEXED_PROFILE_MAGIC, PROFILE_FILENAME_VAR,
+    };
+    use crate::{db::create_target_machine, mock::MockDatabase};
+
+    /// Creates a module with a function `main` that returns immediately.
+    fn create_module(context: &Context) -> Module<'_> {
+        let module = context.create_module("main");
+        let function = module.add_function("main", context.void_type().fn_type(&[], false), None);
+        let builder = context.create_builder();
+        builder.position_at_end(context.append_basic_block(function, "body"));
+        builder.build_return(None);
+        module
+    }
+
+    /// Writes a file that starts like an indexed profile.
+    fn write_indexed_profile(path: &std::path::Path) {
+        let mut contents = INDEXED_PROFILE_MAGIC.to_vec();
+        contents.extend_from_slice(&[0; 32]);
+        std::fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn profiler_targets() {
+        let linux = Target::search("x86_64-unknown-linux-gnu").unwrap();
+        let macos = Target::search("aarch64-apple-darwin").unwrap();
+        let windows = Target::search("x86_64-pc-windows-msvc").unwrap();
+
+        let generate = ProfileGuidedOptimization::generate_in("profiles".as_ref());
+        assert_eq!(
+            generate,
+            ProfileGuidedOptimization::Generate("profiles/default_%m.profraw".into())
+        );
+        assert!(generate.is_supported_by(&linux));
+        assert!(generate.is_supported_by(&macos));
+        assert!(!generate.is_supported_by(&windows));
+        assert!(ProfileGuidedOptimization::Use("mun.profdata".into()).is_supported_by(&windows));
+
+        assert_eq!(
+            profiler_runtime_library(&linux),
+            "libclang_rt.profile-x86_64.a"
+        );
+        assert_eq!(
+            profiler_runtime_library(&macos),
+            "libclang_rt.profile_osx.a"
+        );
+    }
+
+    #[test]
+    fn validate_indexed_profile() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let missing = dir.path().join("missing.profdata");
+        assert_eq!(
+            validate_profile(&missing).unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+
+        let truncated = dir.path().join("truncated.profdata");
+        std::fs::write(&truncated, &INDEXED_PROFILE_MAGIC[..4]).unwrap();
+        assert_eq!(
+            validate_profile(&truncated).unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+
+        let raw = dir.path().join("default.profraw");
+        std::fs::write(&raw, b"\x81rforpl\xff\x08\x00\x00\x00").unwrap();
+        assert_eq!(
+            validate_profile(&raw).unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+
+        let indexed = dir.path().join("mun.profdata");
+        write_indexed_profile(&indexed);
+        assert!(validate_profile(&indexed).is_ok());
+    }
+
+    #[test]
+    fn apply_generate_to_module() {
+        let db = MockDatabase::default();
+        let target_machine = create_target_machine(&db);
+        let context = Context::create();
+        let module = create_module(&context);
+
+        let pgo = ProfileGuidedOptimization::generate_in("profiles".as_ref());
+        apply_to_module(&module, &target_machine, &pgo).unwrap();
+
+        // The executions of `main` are counted and written to the profile path
+        assert!(module.get_global(PROFILE_FILENAME_VAR).is_some());
+        assert!(module.get_global("__profc_main").is_some());
+    }
+
+    #[test]
+    fn apply_use_rejects_another_profile() {
+        let db = MockDatabase::default();
+        let target_machine = create_target_machine(&db);
+        let context = Context::create();
+        let module = create_module(&context);
+
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("first.profdata");
+        let second = dir.path().join("second.profdata");
+        write_indexed_profile(&first);
+        write_indexed_profile(&second);
+
+        // The profile path is global to the process, so only the first profile can
+        // be used
+        set_profile_use_path(&first).unwrap();
+        set_profile_use_path(&first).unwrap();
+        let err = apply_to_module(
+            &module,
+            &target_machine,
+            &ProfileGuidedOptimization::Use(second),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("is already used by this process"));
+    }
+}
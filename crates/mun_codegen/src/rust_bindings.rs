@@ -0,0 +1,448 @@
+use std::{collections::HashMap, fmt::Write as _, io::Write, path::Path, sync::Arc};
+
+use mun_hir::{
+    FloatBitness, HasVisibility, HirDatabase, IntBitness, ModuleDef, Signedness, Struct,
+    StructMemoryKind, Ty, TyKind,
+};
+use tempfile::NamedTempFile;
+
+use crate::{db::CodeGenDatabase, ModuleGroup, ModuleGroupId};
+
+/// Identifiers that are reserved in Rust and have to be escaped as raw
+/// identifiers.
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "const", "continue", "do", "dyn", "else",
+    "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop", "macro",
+    "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static", "struct",
+    "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where",
+    "while", "yield",
+];
+
+/// A `RustBindings` is a reference to a Rust source file stored on disk that
+/// declares typed wrappers for the functions of an assembly, which invoke
+/// them through the Mun runtime.
+#[derive(Debug)]
+pub struct RustBindings {
+    file: NamedTempFile,
+}
+
+impl PartialEq for RustBindings {
+    fn eq(&self, other: &Self) -> bool {
+        self.path().eq(other.path())
+    }
+}
+
+impl Eq for RustBindings {}
+
+impl RustBindings {
+    pub const EXTENSION: &'static str = "rs";
+
+    /// Returns the current location of the source file.
+    pub fn path(&self) -> &Path {
+        self.file.path()
+    }
+
+    /// Copies the source file to the specified location
+    pub fn copy_to<P: AsRef<Path>>(&self, destination: P) -> Result<(), std::io::Error> {
+        std::fs::copy(self.path(), destination).map(|_| ())
+    }
+}
+
+/// Builds the Rust bindings for the specified module group.
+pub(crate) fn build_rust_bindings(
+    db: &dyn CodeGenDatabase,
+    module_group: ModuleGroupId,
+) -> Arc<RustBindings> {
+    let module_partition = db.module_partition();
+    let text = gen_rust_bindings(db.upcast(), &module_partition[module_group]);
+
+    let mut file = NamedTempFile::new().expect("could not create temp file for Rust bindings");
+    file.write_all(text.as_bytes())
+        .expect("could not write to temp file");
+
+    Arc::new(RustBindings { file })
+}
+
+/// Generates the contents of a Rust module that mirrors the value structs of
+/// the module group as `#[repr(C)]` structs and wraps every function that the
+/// runtime can invoke, i.e. every `pub` or `#[reflect]` function, in a typed
+/// function.
+///
+/// The wrappers invoke functions through `Runtime::invoke`, which checks the
+/// signature of the wrapper against the loaded assembly, so stale bindings
+/// fail with an error instead of causing undefined behavior.
+pub(crate) fn gen_rust_bindings(db: &dyn HirDatabase, module_group: &ModuleGroup) -> String {
+    let mut builder = RustBindingsBuilder {
+        db,
+        definitions: String::new(),
+        mirrored: HashMap::new(),
+    };
+
+    let declarations: Vec<ModuleDef> = module_group
+        .iter()
+        .flat_map(|module| module.declarations(db))
+        .collect();
+
+    let mut structs: Vec<Struct> = declarations
+        .iter()
+        .filter_map(|def| match def {
+            ModuleDef::Struct(s) if s.visibility(db).is_externally_visible() => Some(*s),
+            _ => None,
+        })
+        .collect();
+    structs.sort_by_cached_key(|s| s.full_name(db));
+    for s in structs {
+        if s.data(db.upcast()).memory_kind == StructMemoryKind::Value
+            && builder.mirror_struct(s).is_none()
+        {
+            writeln!(
+                builder.definitions,
+                "// `{}` is not mirrored: its fields cannot be represented in Rust, use a \
+                 `StructRef` to access it.\n",
+                s.full_name(db)
+            )
+            .unwrap();
+        }
+    }
+
+    let mut functions: Vec<_> = declarations
+        .iter()
+        .filter_map(|def| match def {
+            ModuleDef::Function(f)
+                if !f.is_extern(db)
+                    && (f.visibility(db).is_externally_visible() || f.is_reflect(db)) =>
+            {
+                Some(*f)
+            }
+            _ => None,
+        })
+        .collect();
+    functions.sort_by_cached_key(|f| f.full_name(db));
+
+    let mut wrappers = String::new();
+    for f in functions {
+        let name = f.full_name(db);
+        let fn_sig = f.ty(db).callable_sig(db).unwrap();
+
+        let params = f
+            .params(db)
+            .iter()
+            .enumerate()
+            .map(|(idx, param)| {
+                let name = match param.name(db) {
+                    Some(name) if name.to_string() != "runtime" => {
+                        rust_identifier(&name.to_string())
+                    }
+                    _ => format!("arg{idx}"),
+                };
+                invoke_type(param.ty()).map(|ty| (name, ty))
+            })
+            .collect::<Option<Vec<_>>>();
+        let (Some(params), Some(ret_ty)) = (params, invoke_type(fn_sig.ret())) else {
+            writeln!(
+                wrappers,
+                "// `{name}` is not wrapped: its signature contains types that cannot be passed \
+                 to or returned from the Mun runtime.\n"
+            )
+            .unwrap();
+            continue;
+        };
+
+        let args_ty = match params.as_slice() {
+            [(_, ty)] => format!("({ty},)"),
+            params => format!(
+                "({})",
+                params
+                    .iter()
+                    .map(|(_, ty)| ty.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        let args = match params.as_slice() {
+            [(name, _)] => format!("({name},)"),
+            params => format!(
+                "({})",
+                params
+                    .iter()
+                    .map(|(name, _)| name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+
+        // The lifetime of the runtime is only named if values that are owned by the
+        // runtime are passed or returned, otherwise it is elided.
+        let borrows_runtime =
+            ret_ty.contains("'r") || params.iter().any(|(_, ty)| ty.contains("'r"));
+        let (generics, runtime_ty) = if borrows_runtime {
+            ("<'r>", "&'r Runtime")
+        } else {
+            ("", "&Runtime")
+        };
+
+        writeln!(wrappers, "/// Invokes the Mun function `{name}`.").unwrap();
+        write!(
+            wrappers,
+            "pub fn {}{generics}(\n    runtime: {runtime_ty},\n",
+            rust_identifier(&path_identifier(&name))
+        )
+        .unwrap();
+        for (name, ty) in &params {
+            writeln!(wrappers, "    {name}: {ty},").unwrap();
+        }
+        writeln!(
+            wrappers,
+            ") -> Result<{ret_ty}, InvokeErr<'static, {args_ty}>> {{\n    runtime.invoke(\"{name}\", \
+             {args})\n}}\n"
+        )
+        .unwrap();
+    }
+
+    let mut bindings = String::new();
+    writeln!(
+        bindings,
+        "// Generated by the Mun compiler from the `{}` assembly. Do not edit.",
+        module_group.name
+    )
+    .unwrap();
+    bindings.push_str(
+        "//
+// The functions invoke the assembly through a `Runtime` that loaded it. The
+// runtime checks every invocation against the signature in the assembly, so
+// the bindings have to be regenerated when the assembly changes.
+
+#[allow(unused_imports)]
+use mun_runtime::{ArrayRef, InvokeErr, Runtime, StructRef};
+
+",
+    );
+    bindings.push_str(&builder.definitions);
+    bindings.push_str(&wrappers);
+    while bindings.ends_with("\n\n") {
+        bindings.pop();
+    }
+    bindings
+}
+
+/// Collects the Rust definitions of the `#[repr(C)]` mirrors of value
+/// structs.
+struct RustBindingsBuilder<'db> {
+    db: &'db dyn HirDatabase,
+    /// The definitions of all mirrors
+    definitions: String,
+    /// The names of the mirrors of value structs, or `None` if a value struct
+    /// cannot be mirrored.
+    mirrored: HashMap<Struct, Option<String>>,
+}
+
+impl<'db> RustBindingsBuilder<'db> {
+    /// Returns the Rust type of a field of a mirror, or `None` if the field
+    /// refers to a value that is owned by the runtime. Value structs that are
+    /// used by the type are mirrored first.
+    fn field_type(&mut self, ty: &Ty) -> Option<String> {
+        match ty.interned() {
+            TyKind::Struct(s)
+                if s.data(self.db.upcast()).memory_kind == StructMemoryKind::Value =>
+            {
+                self.mirror_struct(*s)
+            }
+            _ => primitive_type(ty).map(String::from),
+        }
+    }
+
+    /// Defines a `#[repr(C)]` mirror of the value struct `s`, and of all value
+    /// structs it depends on, if it wasn't defined yet. Returns the name of
+    /// the mirror, or `None` if the struct cannot be mirrored.
+    fn mirror_struct(&mut self, s: Struct) -> Option<String> {
+        if let Some(name) = self.mirrored.get(&s) {
+            return name.clone();
+        }
+
+        // A recursive value struct has no layout, so we never recurse into a struct
+        // that is being mirrored.
+        self.mirrored.insert(s, None);
+        let layout = s.layout(self.db)?;
+        let fields = s.fields(self.db);
+        if fields.is_empty() {
+            return None;
+        }
+
+        let full_name = s.full_name(self.db);
+        let name = path_identifier(&full_name);
+        let fields = fields
+            .into_iter()
+            .map(|field| {
+                let name = field.name(self.db).to_string();
+                let is_struct = matches!(field.ty(self.db).interned(), TyKind::Struct(_));
+                self.field_type(&field.ty(self.db))
+                    .map(|ty| (name, ty, is_struct))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let is_tuple = fields
+            .iter()
+            .all(|(name, ..)| name.starts_with(|c: char| c.is_ascii_digit()));
+
+        let mut definition = String::new();
+        writeln!(
+            definition,
+            "/// A copy of a value of the Mun value struct `{full_name}`."
+        )
+        .unwrap();
+        definition.push_str("#[repr(C)]\n#[derive(Clone, Copy, Debug, PartialEq)]\n");
+        if full_name.contains("::") {
+            definition.push_str("#[allow(non_camel_case_types)]\n");
+        }
+        if is_tuple {
+            let field_types: Vec<_> = fields
+                .iter()
+                .map(|(_, ty, _)| format!("pub {ty}"))
+                .collect();
+            writeln!(definition, "pub struct {name}({});", field_types.join(", ")).unwrap();
+        } else {
+            writeln!(definition, "pub struct {name} {{").unwrap();
+            for (field_name, ty, _) in &fields {
+                writeln!(definition, "    pub {}: {ty},", rust_identifier(field_name)).unwrap();
+            }
+            definition.push_str("}\n");
+        }
+        writeln!(
+            definition,
+            "\nconst _: () = assert!(std::mem::size_of::<{name}>() == {});\n\
+             const _: () = assert!(std::mem::align_of::<{name}>() == {});",
+            layout.size.bytes(),
+            layout.align.bytes()
+        )
+        .unwrap();
+
+        writeln!(
+            definition,
+            "\nimpl {name} {{\n    \
+             /// Copies the fields of a `{full_name}` that is owned by the runtime.\n    \
+             pub fn from_struct_ref(value: &StructRef<'_>) -> Result<Self, String> {{"
+        )
+        .unwrap();
+        if is_tuple {
+            definition.push_str("        Ok(Self(\n");
+        } else {
+            definition.push_str("        Ok(Self {\n");
+        }
+        for (field_name, ty, is_struct) in &fields {
+            let prefix = if is_tuple {
+                String::new()
+            } else {
+                format!("{}: ", rust_identifier(field_name))
+            };
+            if *is_struct {
+                writeln!(
+                    definition,
+                    "            {prefix}{ty}::from_struct_ref(&value.get::<StructRef<'_>>(\"{field_name}\")?)?,"
+                )
+                .unwrap();
+            } else {
+                writeln!(
+                    definition,
+                    "            {prefix}value.get::<{ty}>(\"{field_name}\")?,"
+                )
+                .unwrap();
+            }
+        }
+        if is_tuple {
+            definition.push_str("        ))\n    }\n\n");
+        } else {
+            definition.push_str("        })\n    }\n\n");
+        }
+
+        writeln!(
+            definition,
+            "    /// Copies the fields into a `{full_name}` that is owned by the runtime.\n    \
+             pub fn to_struct_ref(&self, value: &mut StructRef<'_>) -> Result<(), String> {{"
+        )
+        .unwrap();
+        for (field_name, _, is_struct) in &fields {
+            let field = if is_tuple {
+                field_name.clone()
+            } else {
+                rust_identifier(field_name)
+            };
+            if *is_struct {
+                writeln!(
+                    definition,
+                    "        {{\n            \
+                     let mut field = value.get::<StructRef<'_>>(\"{field_name}\")?;\n            \
+                     self.{field}.to_struct_ref(&mut field)?;\n            \
+                     value.set(\"{field_name}\", field)?;\n        \
+                     }}"
+                )
+                .unwrap();
+            } else {
+                writeln!(
+                    definition,
+                    "        value.set(\"{field_name}\", self.{field})?;"
+                )
+                .unwrap();
+            }
+        }
+        definition.push_str("        Ok(())\n    }\n}\n\n");
+
+        self.definitions.push_str(&definition);
+        self.mirrored.insert(s, Some(name.clone()));
+        Some(name)
+    }
+}
+
+/// Returns the Rust type with which a value of type `ty` is passed to or
+/// returned from the runtime, or `None` if the runtime cannot marshal it.
+/// Structs, both value and garbage collected, and arrays are marshalled as
+/// references to values that are owned by the runtime.
+fn invoke_type(ty: &Ty) -> Option<String> {
+    match ty.interned() {
+        TyKind::Struct(_) => Some(String::from("StructRef<'r>")),
+        TyKind::Array(element_ty) => {
+            invoke_type(element_ty).map(|element_ty| format!("ArrayRef<'r, {element_ty}>"))
+        }
+        TyKind::Tuple(0, _) => Some(String::from("()")),
+        _ => primitive_type(ty).map(String::from),
+    }
+}
+
+/// Returns the Rust type of a primitive Mun type.
+fn primitive_type(ty: &Ty) -> Option<&'static str> {
+    let rust_ty = match ty.interned() {
+        TyKind::Bool => "bool",
+        TyKind::Int(int_ty) => match (int_ty.signedness, int_ty.bitness) {
+            (Signedness::Signed, IntBitness::X8) => "i8",
+            (Signedness::Signed, IntBitness::X16) => "i16",
+            (Signedness::Signed, IntBitness::X32) => "i32",
+            (Signedness::Signed, IntBitness::X64) => "i64",
+            (Signedness::Signed, IntBitness::X128) => "i128",
+            (Signedness::Signed, IntBitness::Xsize) => "isize",
+            (Signedness::Unsigned, IntBitness::X8) => "u8",
+            (Signedness::Unsigned, IntBitness::X16) => "u16",
+            (Signedness::Unsigned, IntBitness::X32) => "u32",
+            (Signedness::Unsigned, IntBitness::X64) => "u64",
+            (Signedness::Unsigned, IntBitness::X128) => "u128",
+            (Signedness::Unsigned, IntBitness::Xsize) => "usize",
+        },
+        TyKind::Float(float_ty) => match float_ty.bitness {
+            FloatBitness::X32 => "f32",
+            FloatBitness::X64 => "f64",
+        },
+        _ => return None,
+    };
+    Some(rust_ty)
+}
+
+/// Converts a Mun path into a Rust identifier, e.g. `foo_Bar` for `foo::Bar`.
+fn path_identifier(path: &str) -> String {
+    path.replace("::", "_")
+}
+
+/// Escapes `name` as a raw identifier if it is a Rust keyword.
+fn rust_identifier(name: &str) -> String {
+    if RUST_KEYWORDS.contains(&name) {
+        format!("r#{name}")
+    } else {
+        name.to_owned()
+    }
+}
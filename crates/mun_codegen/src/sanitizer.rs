@@ -0,0 +1,160 @@
+use std::{env, fmt, path::PathBuf};
+
+use inkwell::{
+    attributes::{Attribute, AttributeLoc},
+    module::Module,
+    passes::PassBuilderOptions,
+    targets::TargetMachine,
+};
+use mun_target::spec;
+
+use crate::{code_gen::CodeGenerationError, linker::LinkerError};
+
+/// The environment variable that specifies the directory that contains the
+/// runtime libraries of the sanitizers, e.g. `lib/clang/14.0.6/lib/linux` of
+/// an LLVM installation.
+pub const RUNTIME_DIR_ENV: &str = "MUN_SANITIZER_RUNTIME_DIR";
+
+/// A sanitizer that instruments the generated code to detect bugs at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sanitizer {
+    /// LLVM's AddressSanitizer, which detects out-of-bounds accesses and
+    /// use-after-free, e.g. of buffers that are provided by the host.
+    ///
+    /// Garbage collected objects are allocated and freed by the runtime
+    /// through the global allocator, whose `malloc` and `free` are intercepted
+    /// by the sanitizer runtime. Objects therefore get redzones, and an access
+    /// through a stale pointer to an object that was collected, or that was
+    /// reallocated when its type changed during a hot reload, is reported as
+    /// use-after-free. The collector itself is not instrumented and only
+    /// follows references that are described by type information, so marking
+    /// does not cause false positives.
+    Address,
+}
+
+impl fmt::Display for Sanitizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sanitizer::Address => write!(f, "AddressSanitizer"),
+        }
+    }
+}
+
+impl Sanitizer {
+    /// Returns true if code for `target` can be instrumented with this
+    /// sanitizer.
+    pub fn is_supported_by(self, target: &spec::Target) -> bool {
+        match self {
+            Sanitizer::Address => target.options.supports_address_sanitizer,
+        }
+    }
+
+    /// Returns the file name of the shared runtime library of the sanitizer for
+    /// `target`.
+    pub fn runtime_library(self, target: &spec::Target) -> String {
+        match self {
+            Sanitizer::Address if target.options.is_like_osx => {
+                String::from("libclang_rt.asan_osx_dynamic.dylib")
+            }
+            Sanitizer::Address => format!("libclang_rt.asan-{}.so", target.arch),
+        }
+    }
+
+    /// Returns the pipeline of LLVM passes that instrument a module.
+    fn passes(self) -> &'static str {
+        match self {
+            Sanitizer::Address => "function(asan),asan-module",
+        }
+    }
+
+    /// Returns the attribute that enables instrumentation of a function.
+    fn function_attribute(self) -> &'static str {
+        match self {
+            Sanitizer::Address => "sanitize_address",
+        }
+    }
+}
+
+/// Instruments all functions defined in `module` with `sanitizer`. The
+/// instrumentation references the symbols of the sanitizer runtime, which must
+/// be linked into the assembly.
+pub(crate) fn instrument_module(
+    module: &Module<'_>,
+    target: &spec::Target,
+    target_machine: &TargetMachine,
+    sanitizer: Sanitizer,
+) -> Result<(), CodeGenerationError> {
+    if !sanitizer.is_supported_by(target) {
+        return Err(CodeGenerationError::UnsupportedSanitizer(
+            sanitizer,
+            target.llvm_target.to_string(),
+        ));
+    }
+
+    // The sanitizer passes only instrument functions that opt in
+    let context = module.get_context();
+    let attribute = context.create_enum_attribute(
+        Attribute::get_named_enum_kind_id(sanitizer.function_attribute()),
+        0,
+    );
+    for function in module.get_functions() {
+        if function.count_basic_blocks() > 0 {
+            function.add_attribute(AttributeLoc::Function, attribute);
+        }
+    }
+
+    module
+        .run_passes(
+            sanitizer.passes(),
+            target_machine,
+            PassBuilderOptions::create(),
+        )
+        .map_err(|e| CodeGenerationError::SanitizerError(e.to_string()))
+}
+
+/// Locates the runtime library of `sanitizer` for `target` in the directory
+/// specified by the [`RUNTIME_DIR_ENV`] environment variable.
+pub(crate) fn find_runtime_library(
+    sanitizer: Sanitizer,
+    target: &spec::Target,
+) -> Result<PathBuf, LinkerError> {
+    let file_name = sanitizer.runtime_library(target);
+    let path = env::var_os(RUNTIME_DIR_ENV)
+        .map(|dir| PathBuf::from(dir).join(&file_name))
+        .filter(|path| path.is_file());
+    path.ok_or_else(|| {
+        LinkerError::SanitizerRuntimeMissing(format!(
+            "could not find the {sanitizer} runtime `{file_name}`, set {RUNTIME_DIR_ENV} to the \
+             directory that contains it"
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use mun_target::spec::Target;
+
+    use super::Sanitizer;
+
+    #[test]
+    fn address_sanitizer_targets() {
+        let linux = Target::search("x86_64-unknown-linux-gnu").unwrap();
+        let macos = Target::search("aarch64-apple-darwin").unwrap();
+        let ios = Target::search("aarch64-apple-ios").unwrap();
+        let windows = Target::search("x86_64-pc-windows-msvc").unwrap();
+
+        assert!(Sanitizer::Address.is_supported_by(&linux));
+        assert!(Sanitizer::Address.is_supported_by(&macos));
+        assert!(!Sanitizer::Address.is_supported_by(&ios));
+        assert!(!Sanitizer::Address.is_supported_by(&windows));
+
+        assert_eq!(
+            Sanitizer::Address.runtime_library(&linux),
+            "libclang_rt.asan-x86_64.so"
+        );
+        assert_eq!(
+            Sanitizer::Address.runtime_library(&macos),
+            "libclang_rt.asan_osx_dynamic.dylib"
+        );
+    }
+}
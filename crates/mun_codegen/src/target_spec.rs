@@ -0,0 +1,180 @@
+use std::fmt::Write;
+
+use inkwell::targets::{CodeModel, RelocMode};
+use mun_target::spec::{LinkerFlavor, Target};
+
+/// The relocation model of generated code. Assemblies are loaded at arbitrary
+/// addresses, so their code must be position independent.
+const RELOC_MODE: RelocMode = RelocMode::PIC;
+
+/// The code model of generated code
+const CODE_MODEL: CodeModel = CodeModel::Default;
+
+/// The resolved specification of a target: everything that is used to
+/// construct the LLVM target machine and the data layout for it. Tools can use
+/// it to validate a target before they invoke a build.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TargetSpec {
+    /// The target triple that is passed to LLVM
+    pub llvm_target: String,
+    /// The data layout that is passed to LLVM
+    pub data_layout: String,
+    /// The name of the architecture
+    pub arch: String,
+    /// The width of a pointer in bits
+    pub pointer_width: u32,
+    /// The CPU that code is generated for
+    pub cpu: String,
+    /// The target features that are enabled
+    pub features: String,
+    /// The relocation model
+    pub reloc_mode: RelocMode,
+    /// The code model
+    pub code_model: CodeModel,
+    /// The flavor of the linker that links assemblies
+    pub linker_flavor: LinkerFlavor,
+}
+
+impl TargetSpec {
+    /// Resolves the specification of `target`.
+    pub fn new(target: &Target) -> Self {
+        TargetSpec {
+            llvm_target: target.llvm_target.to_string(),
+            data_layout: target.data_layout.to_string(),
+            arch: target.arch.to_string(),
+            pointer_width: target.pointer_width,
+            cpu: target.options.cpu.clone(),
+            features: target.options.features.clone(),
+            reloc_mode: RELOC_MODE,
+            code_model: CODE_MODEL,
+            linker_flavor: target.options.linker_flavor,
+        }
+    }
+
+    /// Formats the specification as a JSON object.
+    pub fn to_json(&self) -> String {
+        let reloc_model = match self.reloc_mode {
+            RelocMode::Default => "default",
+            RelocMode::Static => "static",
+            RelocMode::PIC => "pic",
+            RelocMode::DynamicNoPic => "dynamic-no-pic",
+        };
+        let code_model = match self.code_model {
+            CodeModel::Default => "default",
+            CodeModel::JITDefault => "jit-default",
+            CodeModel::Small => "small",
+            CodeModel::Kernel => "kernel",
+            CodeModel::Medium => "medium",
+            CodeModel::Large => "large",
+        };
+        let linker_flavor = match self.linker_flavor {
+            LinkerFlavor::Ld => "ld",
+            LinkerFlavor::Ld64 => "ld64",
+            LinkerFlavor::Msvc => "msvc",
+        };
+
+        let fields = [
+            ("llvm_target", json_string(&self.llvm_target)),
+            ("data_layout", json_string(&self.data_layout)),
+            ("arch", json_string(&self.arch)),
+            ("pointer_width", self.pointer_width.to_string()),
+            ("cpu", json_string(&self.cpu)),
+            ("features", json_string(&self.features)),
+            ("reloc_model", json_string(reloc_model)),
+            ("code_model", json_string(code_model)),
+            ("linker_flavor", json_string(linker_flavor)),
+        ];
+
+        let mut json = String::from("{\n");
+        for (idx, (name, value)) in fields.iter().enumerate() {
+            let separator = if idx + 1 < fields.len() { "," } else { "" };
+            writeln!(json, "  \"{name}\": {value}{separator}").unwrap();
+        }
+        json.push('}');
+        json
+    }
+}
+
+/// Formats `value` as a JSON string literal.
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use mun_hir::DefDatabase;
+    use mun_target::spec::{get_targets, Target};
+
+    use super::{json_string, TargetSpec};
+    use crate::{db::create_target_machine, mock::MockDatabase};
+
+    #[test]
+    fn target_spec_json() {
+        let target = Target::search("x86_64-unknown-linux-gnu").unwrap();
+        let spec = TargetSpec::new(&target);
+        assert_eq!(
+            spec.to_json(),
+            format!(
+                "{{\n  \"llvm_target\": \"x86_64-unknown-linux-gnu\",\n  \"data_layout\": \"{}\",\n  \
+                 \"arch\": \"x86_64\",\n  \"pointer_width\": 64,\n  \"cpu\": \"{}\",\n  \
+                 \"features\": \"{}\",\n  \"reloc_model\": \"pic\",\n  \
+                 \"code_model\": \"default\",\n  \"linker_flavor\": \"ld\"\n}}",
+                target.data_layout, target.options.cpu, target.options.features
+            )
+        );
+    }
+
+    #[test]
+    fn target_spec_matches_target_machine() {
+        let mut db = MockDatabase::default();
+        for triple in get_targets() {
+            let target = Target::search(triple).unwrap();
+            let spec = TargetSpec::new(&target);
+            db.set_target(target);
+
+            let target_machine = create_target_machine(&db);
+            assert_eq!(
+                target_machine.get_triple().as_str().to_str().unwrap(),
+                spec.llvm_target,
+                "triple of {triple}"
+            );
+            assert_eq!(
+                target_machine
+                    .get_target_data()
+                    .get_data_layout()
+                    .as_str()
+                    .to_str()
+                    .unwrap(),
+                spec.data_layout,
+                "data layout of {triple}"
+            );
+            assert_eq!(
+                target_machine.get_cpu().to_str().unwrap(),
+                spec.cpu,
+                "cpu of {triple}"
+            );
+            assert_eq!(
+                target_machine.get_feature_string().to_str().unwrap(),
+                spec.features,
+                "features of {triple}"
+            );
+        }
+    }
+
+    #[test]
+    fn escape_json_strings() {
+        assert_eq!(json_string("+sse2,-avx"), r#""+sse2,-avx""#);
+        assert_eq!(json_string("a\"b\\c\n"), r#""a\"b\\c\u000a""#);
+    }
+}
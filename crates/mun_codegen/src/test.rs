@@ -1,16 +1,32 @@
-use std::cell::RefCell;
+use std::{
+    cell::RefCell,
+    num::NonZeroU32,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
-use inkwell::{context::Context, OptimizationLevel};
+use inkwell::{
+    context::Context,
+    module::Module,
+    passes::{PassManager, PassManagerBuilder},
+    OptimizationLevel,
+};
 use mun_hir::{
-    diagnostics::DiagnosticSink, with_fixture::WithFixture, HirDatabase, SourceDatabase, Upcast,
+    diagnostics::{DiagnosticSink, Severity},
+    with_fixture::WithFixture,
+    DefDatabase, HirDatabase, SourceDatabase, Upcast,
 };
 use mun_target::spec::Target;
+use object::Object;
 
 use crate::{
-    code_gen::{AssemblyBuilder, CodeGenContext},
+    code_gen::{AssemblyBuilder, CodeGenContext, CodeGenerationError},
     ir::{file::gen_file_ir, file_group::gen_file_group_ir},
+    mangle,
     mock::MockDatabase,
-    CodeGenDatabase,
+    CodeGenDatabase, DebugInfo, PassHook, PassHookRef, RelocationTarget, Sanitizer,
 };
 
 #[test]
@@ -380,7 +396,19 @@ macro_rules! test_compare_operator_types {
     };
 }
 
-test_compare_operator_types!(bool, f32, f64, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+test_compare_operator_types!(f32, f64, i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+#[test]
+fn compare_op_bool() {
+    // Booleans can only be compared for equality
+    test_snapshot(
+        "compare_op_bool",
+        r#"
+    pub fn equals(a: bool, b: bool) -> bool { a == b }
+    pub fn not_equal(a: bool, b: bool) -> bool { a != b}
+    "#,
+    );
+}
 
 macro_rules! test_negate_operator_types  {
     ($(
@@ -816,6 +844,46 @@ fn while_expr() {
     );
 }
 
+#[test]
+fn while_expr_logic_condition() {
+    let (mut db, file_id) = MockDatabase::with_single_file(
+        r#"
+        pub fn foo(n: i32, m: i32) -> i32 {
+            while n < 3 && !(m > 5 || m == n) {
+                n += 1;
+            };
+            n
+        }
+        "#,
+    );
+    // Optimizations could fold the branches back into boolean operations
+    db.set_optimization_level(OptimizationLevel::None);
+
+    let module_partition = db.module_partition();
+    let module_group_id = module_partition
+        .group_for_file(file_id)
+        .expect("could not find ModuleGroupId for file");
+    let module_group = &module_partition[module_group_id];
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let group_ir = gen_file_group_ir(&code_gen, module_group);
+    let file_ir = gen_file_ir(&code_gen, &group_ir, module_group)
+        .llvm_module
+        .print_to_string()
+        .to_string();
+
+    // The logical operators are lowered to branches instead of boolean values
+    assert!(!file_ir.contains("and i1"));
+    assert!(!file_ir.contains("or i1"));
+    assert!(!file_ir.contains("xor i1"));
+    assert_eq!(file_ir.matches("br i1").count(), 3);
+
+    AssemblyBuilder::new(&code_gen, &module_partition, module_group_id)
+        .build()
+        .expect("failed to build assembly");
+}
+
 #[test]
 fn struct_test() {
     test_snapshot_unoptimized(
@@ -941,7 +1009,7 @@ fn incremental_compilation() {
 
     {
         let events = db.log_executed(|| {
-            db.target_assembly(module_group_id);
+            db.target_assembly(module_group_id).unwrap();
         });
         assert!(
             format!("{events:?}").contains("package_defs"),
@@ -954,7 +1022,7 @@ fn incremental_compilation() {
 
     {
         let events = db.log_executed(|| {
-            db.target_assembly(module_group_id);
+            db.target_assembly(module_group_id).unwrap();
         });
         println!("events: {events:?}");
         assert!(
@@ -968,6 +1036,1175 @@ fn incremental_compilation() {
     // TODO: Add support for multiple files in a group
 }
 
+#[test]
+fn pass_hook() {
+    struct CountingHook(Arc<AtomicUsize>);
+
+    impl PassHook for CountingHook {
+        fn use_default_pipeline(&self) -> bool {
+            false
+        }
+
+        fn add_passes<'ink>(
+            &self,
+            _module: &Module<'ink>,
+            _pass_builder: &PassManagerBuilder,
+            _pass_manager: &PassManager<Module<'ink>>,
+        ) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let (mut db, file_id) = MockDatabase::with_single_file(
+        r#"
+        pub fn foo() -> i32 {
+            5
+        }
+        "#,
+    );
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    db.set_pass_hook(Some(PassHookRef::new(CountingHook(calls.clone()))));
+
+    let module_group_id = db
+        .module_partition()
+        .group_for_file(file_id)
+        .expect("could not find ModuleGroupId for file");
+    db.assembly_ir(module_group_id).unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn minimize_size() {
+    let (mut db, file_id) = MockDatabase::with_single_file(
+        r#"
+        pub fn checksum(n: i32) -> i32 {
+            let mut sum = 0;
+            let mut i = 0;
+            while i < n {
+                sum = sum * 31 + i * i;
+                i += 1;
+            }
+            sum
+        }
+        "#,
+    );
+    let module_group_id = db
+        .module_partition()
+        .group_for_file(file_id)
+        .expect("could not find ModuleGroupId for file");
+
+    db.set_optimization_level(OptimizationLevel::Aggressive);
+    let aggressive_ir =
+        std::fs::read_to_string(db.assembly_ir(module_group_id).unwrap().path()).unwrap();
+    let aggressive_size = std::fs::metadata(db.target_assembly(module_group_id).unwrap().path())
+        .unwrap()
+        .len();
+
+    db.set_optimization_level(OptimizationLevel::Default);
+    db.set_minimize_size(true);
+    let min_size_ir =
+        std::fs::read_to_string(db.assembly_ir(module_group_id).unwrap().path()).unwrap();
+    let min_size = std::fs::metadata(db.target_assembly(module_group_id).unwrap().path())
+        .unwrap()
+        .len();
+
+    // Both the function passes and the code generator see the size attributes
+    assert!(!aggressive_ir.contains("minsize"));
+    assert!(min_size_ir.contains("minsize optsize"), "{min_size_ir}");
+    assert!(
+        min_size <= aggressive_size,
+        "-Oz: {min_size} bytes, -O3: {aggressive_size} bytes"
+    );
+}
+
+#[test]
+fn minimize_size_keeps_explicit_optimize() {
+    let (mut db, file_id) = MockDatabase::with_single_file(
+        r#"
+        #[no_mangle]
+        pub fn implicit(n: i32) -> i32 { n * 3 + 1 }
+
+        #[no_mangle]
+        #[optimize(speed)]
+        pub fn speed(n: i32) -> i32 { n * 3 + 1 }
+
+        #[no_mangle]
+        #[optimize(size)]
+        pub fn size(n: i32) -> i32 { n * 3 + 1 }
+        "#,
+    );
+    let module_group_id = db
+        .module_partition()
+        .group_for_file(file_id)
+        .expect("could not find ModuleGroupId for file");
+
+    db.set_minimize_size(true);
+    let ir = std::fs::read_to_string(db.assembly_ir(module_group_id).unwrap().path()).unwrap();
+
+    // Returns the attributes of the attribute group of a function
+    let function_attrs = |name: &str| {
+        let group = ir
+            .lines()
+            .find(|line| line.starts_with("define") && line.contains(&format!("@{name}(")))
+            .and_then(|line| {
+                line.split_whitespace()
+                    .rev()
+                    .find(|word| word.starts_with('#'))
+            })
+            .unwrap_or_else(|| panic!("missing attributes of {name}: {ir}"));
+        ir.lines()
+            .find_map(|line| line.strip_prefix(&format!("attributes {group} = ")))
+            .unwrap_or_else(|| panic!("missing attribute group {group}: {ir}"))
+            .to_string()
+    };
+
+    // Only functions without an explicit `#[optimize]` attribute are minimized
+    assert!(function_attrs("implicit").contains("minsize"));
+    assert!(!function_attrs("speed").contains("minsize"));
+    assert!(!function_attrs("speed").contains("optsize"));
+    assert!(!function_attrs("size").contains("minsize"));
+    assert!(function_attrs("size").contains("optsize"));
+}
+
+#[test]
+fn verify_llvm() {
+    /// A hook that adds a function without a terminator to the module, which
+    /// is rejected by the LLVM verifier.
+    struct InvalidFunctionHook;
+
+    impl PassHook for InvalidFunctionHook {
+        fn use_default_pipeline(&self) -> bool {
+            false
+        }
+
+        fn add_passes<'ink>(
+            &self,
+            module: &Module<'ink>,
+            _pass_builder: &PassManagerBuilder,
+            _pass_manager: &PassManager<Module<'ink>>,
+        ) {
+            let context = module.get_context();
+            let function =
+                module.add_function("invalid", context.void_type().fn_type(&[], false), None);
+            context.append_basic_block(function, "body");
+        }
+    }
+
+    let (mut db, file_id) = MockDatabase::with_single_file(
+        r#"
+        pub fn foo() -> i32 {
+            5
+        }
+        "#,
+    );
+    db.set_pass_hook(Some(PassHookRef::new(InvalidFunctionHook)));
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let module_partition = db.module_partition();
+    let module_group_id = module_partition
+        .group_for_file(file_id)
+        .expect("could not find ModuleGroupId for file");
+
+    let error = AssemblyBuilder::new(&code_gen, &module_partition, module_group_id)
+        .build()
+        .err()
+        .expect("expected the verifier to reject the module");
+    assert!(error.to_string().starts_with("error verifying module"));
+
+    db.set_verify_llvm(false);
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    assert!(
+        AssemblyBuilder::new(&code_gen, &module_partition, module_group_id)
+            .build()
+            .is_ok()
+    );
+}
+
+#[test]
+fn debug_info() {
+    let (mut db, file_id) = MockDatabase::with_single_file(
+        r#"
+        pub fn add(a: i32, b: i32) -> i32 {
+            let c = a + b;
+            c
+        }
+        "#,
+    );
+
+    let module_partition = db.module_partition();
+    let module_group_id = module_partition
+        .group_for_file(file_id)
+        .expect("could not find ModuleGroupId for file");
+    let module_group = &module_partition[module_group_id];
+
+    let file_ir = |db: &mut MockDatabase, debug_info: DebugInfo| {
+        db.set_debug_info(debug_info);
+        let llvm_context = Context::create();
+        let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+        let group_ir = gen_file_group_ir(&code_gen, module_group);
+        let ir = gen_file_ir(&code_gen, &group_ir, module_group)
+            .llvm_module
+            .print_to_string()
+            .to_string();
+
+        // Ensure that the debug information passes the LLVM verifier
+        AssemblyBuilder::new(&code_gen, &module_partition, module_group_id)
+            .build()
+            .expect("failed to build assembly");
+        ir
+    };
+
+    let ir = file_ir(&mut db, DebugInfo::None);
+    assert!(!ir.contains("!DICompileUnit"));
+    assert!(!ir.contains("!DILocation"));
+
+    let ir = file_ir(&mut db, DebugInfo::LinesOnly);
+    assert!(ir.contains("emissionKind: LineTablesOnly"));
+    assert!(ir.contains("!DISubprogram(name: \"add\""));
+    assert!(ir.contains("!DILocation(line: 3"));
+    assert!(!ir.contains("!DILocalVariable"));
+
+    let ir = file_ir(&mut db, DebugInfo::Full);
+    assert!(ir.contains("emissionKind: FullDebug"));
+    assert!(ir.contains("!DILocalVariable(name: \"a\", arg: 1"));
+    assert!(ir.contains("!DILocalVariable(name: \"c\""));
+    assert!(ir.contains("!DIBasicType(name: \"i32\", size: 32"));
+}
+
+#[test]
+fn static_dispatch() {
+    let mut db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub fn main() -> i32 {
+        foo::get_value()
+    }
+
+    //- /foo.mun
+    pub(super) fn get_value() -> i32 {
+        3
+    }
+    ",
+    );
+    db.set_static_dispatch(true);
+
+    let module_partition = db.module_partition();
+    let groups = module_partition.iter().collect::<Vec<_>>();
+    assert_eq!(groups.len(), 1);
+    let (module_group_id, module_group) = groups[0];
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let group_ir = gen_file_group_ir(&code_gen, module_group);
+    let file_ir = gen_file_ir(&code_gen, &group_ir, module_group)
+        .llvm_module
+        .print_to_string()
+        .to_string();
+
+    // Calls between the modules no longer go through the dispatch table
+    assert!(group_ir.dispatch_table.entries().is_empty());
+    assert!(!file_ir.contains("dispatchTable"));
+    assert!(file_ir.contains("call i32 @_MN3foo9get_valueE()"));
+
+    AssemblyBuilder::new(&code_gen, &module_partition, module_group_id)
+        .build()
+        .expect("failed to build assembly");
+}
+
+#[test]
+fn static_dispatch_same_function_names() {
+    let mut db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub fn main() -> i32 {
+        get_value() + foo::get_value()
+    }
+
+    fn get_value() -> i32 {
+        2
+    }
+
+    //- /foo.mun
+    pub(super) fn get_value() -> i32 {
+        3
+    }
+    ",
+    );
+    db.set_static_dispatch(true);
+
+    let module_partition = db.module_partition();
+    let (module_group_id, module_group) = module_partition.iter().next().unwrap();
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let group_ir = gen_file_group_ir(&code_gen, module_group);
+    let file_ir = gen_file_ir(&code_gen, &group_ir, module_group)
+        .llvm_module
+        .print_to_string()
+        .to_string();
+
+    // Both functions are emitted under their own symbol
+    assert!(file_ir.contains("call i32 @_MN9get_valueE()"));
+    assert!(file_ir.contains("call i32 @_MN3foo9get_valueE()"));
+    assert!(!file_ir.contains("get_valueE.1"));
+
+    AssemblyBuilder::new(&code_gen, &module_partition, module_group_id)
+        .build()
+        .expect("failed to build assembly");
+}
+
+#[test]
+fn emit_relocations() {
+    let mut db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub fn main() -> i32 {
+        foo::get_value() + foo::get_other_value()
+    }
+
+    //- /foo.mun
+    pub(super) fn get_value() -> i32 {
+        3
+    }
+
+    pub(super) fn get_other_value() -> i32 {
+        4
+    }
+    ",
+    );
+    db.set_emit_relocations(true);
+
+    let module_partition = db.module_partition();
+    let (module_group_id, _) = module_partition
+        .iter()
+        .find(|(_, group)| group.name == "mod")
+        .expect("missing root module group");
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let assembly = AssemblyBuilder::new(&code_gen, &module_partition, module_group_id)
+        .build()
+        .expect("failed to build assembly");
+    let dispatch_table = assembly.dispatch_table().to_vec();
+    assert_eq!(dispatch_table.len(), 2);
+
+    let relocations = assembly
+        .into_object_file()
+        .expect("failed to create object file")
+        .relocations(&dispatch_table, &db.entry_symbols())
+        .expect("failed to read relocations");
+
+    // Both calls load their function pointer from their own slot in the dispatch
+    // table
+    for (index, function) in dispatch_table.iter().enumerate() {
+        assert!(
+            relocations.iter().any(|relocation| {
+                relocation.function == "main"
+                    && relocation.target
+                        == RelocationTarget::DispatchTable {
+                            index,
+                            function: function.clone(),
+                        }
+            }),
+            "missing relocation for slot {index} ({function}): {relocations:#?}"
+        );
+    }
+}
+
+#[test]
+fn symbol_version() {
+    let mut db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    #[no_mangle]
+    pub fn add(a: i64, b: i64) -> i64 {
+        foo::get_value() + a + b
+    }
+
+    //- /foo.mun
+    pub(super) fn get_value() -> i64 {
+        3
+    }
+    ",
+    );
+    db.set_entry_symbols(mun_abi::EntrySymbols::with_version(
+        mun_abi::GET_INFO_FN_NAME,
+        Some("1.2.0"),
+    ));
+
+    let module_partition = db.module_partition();
+    let (module_group_id, module_group) = module_partition
+        .iter()
+        .find(|(_, group)| group.name == "mod")
+        .expect("missing root module group");
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let assembly = AssemblyBuilder::new(&code_gen, &module_partition, module_group_id)
+        .build()
+        .expect("failed to build assembly");
+    let ir_file = tempfile::NamedTempFile::new().unwrap();
+    assembly
+        .write_ir_to_file(ir_file.path())
+        .expect("failed to write IR");
+    let ir = std::fs::read_to_string(ir_file.path()).unwrap();
+
+    // The reflection entry, the dispatch table and the exported functions all
+    // carry the version
+    assert!(ir.contains("@get_info_v1_2_0("));
+    assert!(ir.contains("@get_version_v1_2_0("));
+    assert!(ir.contains("@dispatchTable_v1_2_0 = "));
+    assert!(ir.contains("@add_v1_2_0("));
+    assert!(!ir.contains("@get_info("));
+    assert!(!ir.contains("@dispatchTable = "));
+    assert!(!ir.contains("@add("));
+
+    let header = crate::c_header::gen_c_header(db.upcast(), module_group, &db.entry_symbols());
+    assert!(header.contains("int64_t add_v1_2_0(int64_t a, int64_t b);"));
+}
+
+#[test]
+fn symbol_version_coff_exports() {
+    let mut db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub fn add(a: i64, b: i64) -> i64 {
+        a + b
+    }
+    ",
+    );
+    db.set_target(Target::search("x86_64-pc-windows-msvc").unwrap());
+    db.set_call_trace_ring(NonZeroU32::new(16));
+    db.set_entry_symbols(mun_abi::EntrySymbols::with_version(
+        mun_abi::GET_INFO_FN_NAME,
+        Some("1.2.0"),
+    ));
+
+    let module_partition = db.module_partition();
+    let (module_group_id, _) = module_partition.iter().next().unwrap();
+    let assembly = db.target_assembly(module_group_id).unwrap();
+
+    // A DLL only exports the symbols that the linker is told to export, so the
+    // versioned names must be passed to it
+    let contents = std::fs::read(assembly.path()).unwrap();
+    let dll = object::File::parse(contents.as_slice()).expect("failed to parse DLL");
+    let exports: Vec<String> = dll
+        .exports()
+        .unwrap()
+        .iter()
+        .map(|export| String::from_utf8_lossy(export.name()).into_owned())
+        .collect();
+
+    let entry_symbols = db.entry_symbols();
+    for symbol in [
+        &entry_symbols.get_info,
+        &entry_symbols.get_version,
+        &entry_symbols.set_allocator_handle,
+        &entry_symbols.format_header,
+        &entry_symbols.compiler_info,
+        &entry_symbols.call_trace,
+    ] {
+        assert!(symbol.ends_with("_v1_2_0"), "{symbol}");
+        assert!(
+            exports.contains(symbol),
+            "`{symbol}` is not exported: {exports:?}"
+        );
+    }
+    assert!(!exports
+        .iter()
+        .any(|export| export == mun_abi::GET_INFO_FN_NAME));
+}
+
+#[test]
+fn no_mangle_coff_exports() {
+    let mut db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    #[no_mangle]
+    fn add(a: i64, b: i64) -> i64 {
+        a + b
+    }
+    ",
+    );
+    db.set_target(Target::search("x86_64-pc-windows-msvc").unwrap());
+
+    let module_partition = db.module_partition();
+    let (module_group_id, _) = module_partition.iter().next().unwrap();
+    let assembly = db.target_assembly(module_group_id).unwrap();
+
+    // `#[no_mangle]` functions are exported from the DLL, even if they are private
+    let contents = std::fs::read(assembly.path()).unwrap();
+    let dll = object::File::parse(contents.as_slice()).expect("failed to parse DLL");
+    assert!(dll
+        .exports()
+        .unwrap()
+        .iter()
+        .any(|export| export.name() == b"add"));
+}
+
+#[test]
+fn no_mangle_entry_symbol() {
+    let db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    #[no_mangle]
+    pub fn get_info() -> i64 {
+        1
+    }
+    ",
+    );
+
+    let module_partition = db.module_partition();
+    let (module_group_id, _) = module_partition.iter().next().unwrap();
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let error = AssemblyBuilder::new(&code_gen, &module_partition, module_group_id)
+        .build()
+        .err()
+        .expect("expected a clash with an entry symbol");
+    assert!(
+        matches!(
+            error.downcast_ref::<CodeGenerationError>(),
+            Some(CodeGenerationError::ReservedSymbol(name)) if name == "get_info"
+        ),
+        "{error}"
+    );
+}
+
+#[test]
+fn abi_tag_note() {
+    let db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub fn main() {}
+    ",
+    );
+
+    let module_partition = db.module_partition();
+    let (module_group_id, _) = module_partition.iter().next().unwrap();
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let assembly = AssemblyBuilder::new(&code_gen, &module_partition, module_group_id)
+        .build()
+        .expect("failed to build assembly");
+    let ir_file = tempfile::NamedTempFile::new().unwrap();
+    assembly
+        .write_ir_to_file(ir_file.path())
+        .expect("failed to write IR");
+    let ir = std::fs::read_to_string(ir_file.path()).unwrap();
+
+    // The note is placed in its own section and is retained, even though it is
+    // never referenced
+    let note = ir
+        .lines()
+        .find(|line| line.starts_with("@mun_abi_tag = "))
+        .expect("missing ABI tag note");
+    assert!(note.contains(" section \""));
+    assert!(note.ends_with("align 4"));
+    assert!(ir.contains("@llvm.used = appending global [1 x i8*] [i8* bitcast ("));
+    assert!(ir.contains("@mun_abi_tag to i8*)], section \"llvm.metadata\""));
+}
+
+#[test]
+fn numeric_casts() {
+    let db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub fn widen(a: i32, b: u8) -> i64 {
+        a as i64 + b as i64
+    }
+
+    pub fn narrow(a: i64) -> i8 {
+        a as i8
+    }
+
+    pub fn to_float(a: i64, b: u32) -> f64 {
+        a as f64 + b as f64
+    }
+
+    pub fn to_int(a: f64) -> u16 {
+        a as u16 + (a as f32) as i16 as u16
+    }
+    ",
+    );
+
+    let module_partition = db.module_partition();
+    let (_, module_group) = module_partition.iter().next().unwrap();
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let group_ir = gen_file_group_ir(&code_gen, module_group);
+    let file_ir = gen_file_ir(&code_gen, &group_ir, module_group)
+        .llvm_module
+        .print_to_string()
+        .to_string();
+
+    assert!(file_ir.contains("sext i32 "));
+    assert!(file_ir.contains("zext i8 "));
+    assert!(file_ir.contains("trunc i64 "));
+    assert!(file_ir.contains("sitofp i64 "));
+    assert!(file_ir.contains("uitofp i32 "));
+    assert!(file_ir.contains("fptrunc double "));
+
+    // Float to integer casts saturate instead of producing poison values
+    assert!(file_ir.contains("@llvm.fptoui.sat.i16.f64("));
+    assert!(file_ir.contains("@llvm.fptosi.sat.i16.f32("));
+}
+
+#[test]
+fn alloc_debug() {
+    let mut db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub struct Foo { a: i32 }
+
+    pub fn new_foo() -> Foo {
+        Foo { a: 1 }
+    }
+
+    pub fn new_array() -> [i32] {
+        [1, 2, 3]
+    }
+    ",
+    );
+    db.set_alloc_debug(true);
+
+    let module_partition = db.module_partition();
+    let (_, module_group) = module_partition.iter().next().unwrap();
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let group_ir = gen_file_group_ir(&code_gen, module_group);
+    let file_ir = gen_file_ir(&code_gen, &group_ir, module_group)
+        .llvm_module
+        .print_to_string()
+        .to_string();
+
+    // Allocations pass the source location of the allocating expression
+    let intrinsics = group_ir
+        .dispatch_table
+        .entries()
+        .iter()
+        .map(|entry| entry.prototype.name.as_str())
+        .collect::<Vec<_>>();
+    assert!(intrinsics.contains(&"new_with_location"));
+    assert!(intrinsics.contains(&"new_array_with_location"));
+    assert!(!intrinsics.contains(&"new"));
+    assert!(file_ir.contains(r#"c"mod.mun:4:5\00""#));
+    assert!(file_ir.contains(r#"c"mod.mun:8:5\00""#));
+}
+
+#[test]
+fn uninit_structs() {
+    let mut db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub struct Foo { a: i32 }
+
+    pub fn new_foo() -> Foo {
+        Foo { a: 1 }
+    }
+    ",
+    );
+    db.set_init_structs(false);
+
+    let module_partition = db.module_partition();
+    let (_, module_group) = module_partition.iter().next().unwrap();
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let group_ir = gen_file_group_ir(&code_gen, module_group);
+
+    // Allocations are not zero-initialized
+    let intrinsics = group_ir
+        .dispatch_table
+        .entries()
+        .iter()
+        .map(|entry| entry.prototype.name.as_str())
+        .collect::<Vec<_>>();
+    assert!(intrinsics.contains(&"new_uninit"));
+    assert!(!intrinsics.contains(&"new"));
+}
+
+#[test]
+fn instrument_timing() {
+    let mut db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub fn fib(n: i32) -> i32 {
+        if n <= 1 {
+            return n;
+        }
+        fib(n - 1) + fib(n - 2)
+    }
+
+    #[no_instrument]
+    pub fn untimed() -> i32 {
+        5
+    }
+    ",
+    );
+    db.set_instrument_timing(true);
+
+    let module_partition = db.module_partition();
+    let (_, module_group) = module_partition.iter().next().unwrap();
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let group_ir = gen_file_group_ir(&code_gen, module_group);
+    let file_ir = gen_file_ir(&code_gen, &group_ir, module_group)
+        .llvm_module
+        .print_to_string()
+        .to_string();
+
+    // Instrumented functions report their entry and exit, passing their name
+    let intrinsics = group_ir
+        .dispatch_table
+        .entries()
+        .iter()
+        .map(|entry| entry.prototype.name.as_str())
+        .collect::<Vec<_>>();
+    assert!(intrinsics.contains(&"timing_enter"));
+    assert!(intrinsics.contains(&"timing_exit"));
+    assert!(file_ir.contains(r#"c"fib\00""#));
+    assert!(!file_ir.contains(r#"c"untimed\00""#));
+}
+
+#[test]
+fn call_trace_ring() {
+    let mut db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub fn traced() -> i32 {
+        untraced()
+    }
+
+    #[no_instrument]
+    fn untraced() -> i32 {
+        5
+    }
+    ",
+    );
+    db.set_call_trace_ring(NonZeroU32::new(16));
+
+    let module_partition = db.module_partition();
+    let (_, module_group) = module_partition.iter().next().unwrap();
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let group_ir = gen_file_group_ir(&code_gen, module_group);
+    let file = gen_file_ir(&code_gen, &group_ir, module_group);
+    let file_ir = file.llvm_module.print_to_string().to_string();
+
+    // Only instrumented functions store their id in the ring
+    let traced_id = mun_abi::call_trace_id("traced");
+    assert!(file_ir.contains(&format!("store i32 {}", traced_id as i32)));
+    assert!(!file_ir.contains(&format!(
+        "store i32 {}",
+        mun_abi::call_trace_id("untraced") as i32
+    )));
+    assert!(file_ir.contains("@mun_call_trace = external global"));
+    assert_eq!(
+        file.traced_functions
+            .iter()
+            .map(|f| f.full_name(&db))
+            .collect::<Vec<_>>(),
+        vec!["traced"]
+    );
+}
+
+#[test]
+fn address_sanitizer() {
+    let (mut db, file_id) = MockDatabase::with_single_file(
+        r#"
+        pub fn sum(values: [i32]) -> i32 {
+            values[0] + values[1]
+        }
+        "#,
+    );
+    db.set_target(Target::search("x86_64-unknown-linux-gnu").unwrap());
+    db.set_sanitizer(Some(Sanitizer::Address));
+
+    let module_group_id = db
+        .module_partition()
+        .group_for_file(file_id)
+        .expect("could not find ModuleGroupId for file");
+    let ir = std::fs::read_to_string(db.assembly_ir(module_group_id).unwrap().path()).unwrap();
+
+    // Functions opt in to the instrumentation, which checks loads against the
+    // shadow memory and registers the module with the sanitizer runtime
+    assert!(ir.contains("sanitize_address"), "{ir}");
+    assert!(ir.contains("@__asan_report_load4"), "{ir}");
+    assert!(ir.contains("@__asan_init"), "{ir}");
+}
+
+#[test]
+fn gc_functions() {
+    let mut db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub fn main() -> i32 {
+        countdown(3)
+    }
+
+    fn countdown(n: i32) -> i32 {
+        if n == 0 { 0 } else { countdown(n - 1) + helper() }
+    }
+
+    fn helper() -> i32 { 1 }
+
+    fn unused() -> i32 { dead_end() }
+
+    fn dead_end() -> i32 { unused() }
+    ",
+    );
+    db.set_gc_functions(true);
+
+    let module_partition = db.module_partition();
+    let (_, module_group) = module_partition.iter().next().unwrap();
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let group_ir = gen_file_group_ir(&code_gen, module_group);
+    let file_ir = gen_file_ir(&code_gen, &group_ir, module_group)
+        .llvm_module
+        .print_to_string()
+        .to_string();
+
+    // Functions that are reachable from `main` are kept, including recursive
+    // ones, while the cycle of unreachable functions is removed
+    assert!(file_ir.contains("@_MN4mainE("));
+    assert!(file_ir.contains("@_MN9countdownE("));
+    assert!(file_ir.contains("@_MN6helperE("));
+    assert!(!file_ir.contains("@_MN6unusedE("));
+    assert!(!file_ir.contains("@_MN8dead_endE("));
+}
+
+#[test]
+fn reflect_functions() {
+    let mut db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub fn main() -> i32 { 0 }
+
+    //- /foo.mun
+    #[reflect]
+    fn invoked_by_name() -> i32 { helper() }
+
+    fn helper() -> i32 { 1 }
+
+    fn unused() -> i32 { 2 }
+    ",
+    );
+    db.set_gc_functions(true);
+    db.set_static_dispatch(true);
+
+    let module_partition = db.module_partition();
+    let (_, module_group) = module_partition.iter().next().unwrap();
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let group_ir = gen_file_group_ir(&code_gen, module_group);
+    let file_ir = gen_file_ir(&code_gen, &group_ir, module_group);
+
+    // The private `#[reflect]` function and its callees are retained, and the
+    // function is exported even though its module is part of the same group
+    let mut exported = file_ir
+        .function_definitions
+        .iter()
+        .map(|f| f.name(&db).to_string())
+        .collect::<Vec<_>>();
+    exported.sort();
+    assert_eq!(exported, ["invoked_by_name", "main"]);
+
+    let ir = file_ir.llvm_module.print_to_string().to_string();
+    assert!(ir.contains("@_MN3foo15invoked_by_nameE("));
+    assert!(ir.contains("@_MN3foo6helperE("));
+    assert!(!ir.contains("@_MN3foo6unusedE("));
+}
+
+#[test]
+fn mangled_symbols() {
+    let mut db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub fn main() -> i32 {
+        foo::value() + bar::value()
+    }
+
+    #[no_mangle]
+    pub fn exported() -> i32 { 0 }
+
+    //- /foo.mun
+    pub(super) fn value() -> i32 { 1 }
+
+    //- /bar.mun
+    pub(super) fn value() -> i32 { 2 }
+    ",
+    );
+    db.set_static_dispatch(true);
+
+    let module_partition = db.module_partition();
+    let (_, module_group) = module_partition.iter().next().unwrap();
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let group_ir = gen_file_group_ir(&code_gen, module_group);
+    let ir = gen_file_ir(&code_gen, &group_ir, module_group)
+        .llvm_module
+        .print_to_string()
+        .to_string();
+
+    // Functions with the same name in different modules no longer collide
+    assert!(ir.contains("define i32 @_MN3foo5valueE()"));
+    assert!(ir.contains("define i32 @_MN3bar5valueE()"));
+    assert!(ir.contains("define i32 @exported()"));
+
+    // Every mangled symbol demangles to the name that is used for reflection
+    let functions = module_group
+        .iter()
+        .flat_map(|module| module.declarations(&db))
+        .filter_map(|def| match def {
+            mun_hir::ModuleDef::Function(f) => Some(f),
+            _ => None,
+        });
+    for function in functions {
+        let symbol = mangle::function_symbol(db.upcast(), function);
+        assert!(ir.contains(&format!("@{symbol}(")), "missing {symbol}");
+        match mangle::demangle(&symbol) {
+            Some(demangled) => assert_eq!(demangled.to_string(), function.full_name(&db)),
+            None => assert!(function.is_no_mangle(&db)),
+        }
+    }
+}
+
+#[test]
+fn tail_calls() {
+    let db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    #[tail_call]
+    pub fn count(n: i32, acc: i32) -> i32 {
+        if n == 0 {
+            return acc;
+        }
+        count(n - 1, acc + 1)
+    }
+
+    #[tail_call]
+    pub fn delegate(n: i32, acc: i32) -> i32 {
+        if n == 0 { acc } else { return foo::add(n, acc) }
+    }
+
+    //- /foo.mun
+    pub(super) fn add(n: i32, acc: i32) -> i32 { n + acc }
+    ",
+    );
+
+    let module_partition = db.module_partition();
+    let (_, module_group) = module_partition.iter().next().unwrap();
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let group_ir = gen_file_group_ir(&code_gen, module_group);
+    let ir = gen_file_ir(&code_gen, &group_ir, module_group)
+        .llvm_module
+        .print_to_string()
+        .to_string();
+
+    // A recursive tail call restarts the body instead of calling the function
+    let count = module_group
+        .iter()
+        .flat_map(|module| module.declarations(&db))
+        .find_map(|def| match def {
+            mun_hir::ModuleDef::Function(f) if f.name(&db).to_string() == "count" => Some(f),
+            _ => None,
+        })
+        .unwrap();
+    let symbol = mangle::function_symbol(db.upcast(), count);
+    assert!(!ir.contains(&format!("call i32 @{symbol}(")));
+    assert!(ir.contains("br label %tail_call"));
+
+    // Other tail calls, including those through the dispatch table, are
+    // marked as such and returned immediately
+    let lines = ir.lines().map(str::trim).collect::<Vec<_>>();
+    let tail_call = lines
+        .iter()
+        .position(|line| line.contains("tail call i32"))
+        .expect("missing tail call");
+    assert!(lines[tail_call].contains("= tail call i32 %"));
+    assert!(lines[tail_call + 1].starts_with("ret i32"));
+}
+
+#[test]
+fn pointer_width() {
+    let db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub fn pointer_width() -> u32 {
+        POINTER_WIDTH
+    }
+
+    pub fn size(a: usize) -> usize {
+        a
+    }
+    ",
+    );
+
+    let module_partition = db.module_partition();
+    let (_, module_group) = module_partition.iter().next().unwrap();
+
+    let llvm_context = Context::create();
+    let code_gen = CodeGenContext::new(&llvm_context, db.upcast());
+    let group_ir = gen_file_group_ir(&code_gen, module_group);
+    let ir = gen_file_ir(&code_gen, &group_ir, module_group)
+        .llvm_module
+        .print_to_string()
+        .to_string();
+
+    // `POINTER_WIDTH` and `usize` match the size of the pointers of the target
+    let bits = code_gen
+        .target_machine
+        .get_target_data()
+        .get_pointer_byte_size(None)
+        * 8;
+    assert_eq!(db.target_data_layout().pointer_size.bits(), u64::from(bits));
+    assert!(ir.contains(&format!("ret i32 {bits}")));
+    assert!(ir.contains(&format!("define i{bits} @_MN4sizeE(i{bits}")));
+}
+
+#[test]
+fn c_header() {
+    let db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub struct(value) Vec2 { x: f32, y: f32 }
+    pub struct Handle(i64);
+
+    #[no_mangle]
+    pub fn add(a: i64, b: f64) -> f64 {
+        a as f64 + b
+    }
+
+    #[no_mangle]
+    pub fn handle(h: Handle) -> Handle {
+        h
+    }
+
+    #[no_mangle]
+    pub fn length(v: Vec2) -> f32 {
+        v.x
+    }
+
+    pub fn mangled(a: i32) -> i32 {
+        a
+    }
+    ",
+    );
+
+    let module_partition = db.module_partition();
+    let (_, module_group) = module_partition.iter().next().unwrap();
+    let header = crate::c_header::gen_c_header(db.upcast(), module_group, &db.entry_symbols());
+
+    assert!(header.contains("#ifndef MUN_MOD_H_"));
+    assert!(header.contains("typedef struct Vec2 {\n    float x;\n    float y;\n} Vec2;"));
+    assert!(header.contains("MUN_STATIC_ASSERT(sizeof(Vec2) == 8"));
+    assert!(header.contains("typedef void *Handle;"));
+    assert!(header.contains("double add(int64_t a, double b);"));
+    assert!(header.contains("Handle handle(Handle h);"));
+    assert!(header.contains("/* `length` is not declared"));
+    assert!(!header.contains("mangled"));
+}
+
+#[test]
+fn c_header_out_params() {
+    let db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    #[no_mangle]
+    pub fn div_rem(a: i64, b: i64, rem: &mut i64) -> i64 {
+        *rem = a % b;
+        a / b
+    }
+    ",
+    );
+
+    let module_partition = db.module_partition();
+    let (_, module_group) = module_partition.iter().next().unwrap();
+    let header = crate::c_header::gen_c_header(db.upcast(), module_group, &db.entry_symbols());
+
+    assert!(header.contains("int64_t div_rem(int64_t a, int64_t b, int64_t *rem);"));
+}
+
+#[test]
+fn rust_bindings() {
+    let db = MockDatabase::with_files(
+        r"
+    //- /mod.mun
+    pub struct(value) Vec2 { x: f32, y: f32 }
+    pub struct(value) Line(Vec2, Vec2);
+    pub struct(value) Labeled { id: i32, handle: Handle }
+    pub struct Handle(i64);
+
+    pub fn add(a: i64, b: f64) -> f64 {
+        a as f64 + b
+    }
+
+    pub fn handle(h: Handle) -> Handle {
+        h
+    }
+
+    pub fn sum(values: [i32]) -> i32 {
+        values[0]
+    }
+
+    pub fn unit() {}
+
+    pub fn callback(f: fn(i32) -> i32) -> i32 {
+        f(1)
+    }
+
+    fn private(a: i32) -> i32 {
+        a
+    }
+    ",
+    );
+
+    let module_partition = db.module_partition();
+    let (_, module_group) = module_partition.iter().next().unwrap();
+    let bindings = crate::rust_bindings::gen_rust_bindings(db.upcast(), module_group);
+
+    assert!(bindings.contains("use mun_runtime::{ArrayRef, InvokeErr, Runtime, StructRef};"));
+    assert!(bindings.contains(
+        "#[repr(C)]\n#[derive(Clone, Copy, Debug, PartialEq)]\npub struct Vec2 {\n    pub x: f32,\n    pub y: f32,\n}"
+    ));
+    assert!(bindings.contains("const _: () = assert!(std::mem::size_of::<Vec2>() == 8);"));
+    assert!(bindings.contains("pub struct Line(pub Vec2, pub Vec2);"));
+    assert!(bindings.contains("Vec2::from_struct_ref(&value.get::<StructRef<'_>>(\"0\")?)?,"));
+    assert!(bindings.contains("value.set(\"x\", self.x)?;"));
+    assert!(bindings.contains("// `Labeled` is not mirrored"));
+    assert!(bindings.contains(
+        "pub fn add(\n    runtime: &Runtime,\n    a: i64,\n    b: f64,\n) -> Result<f64, \
+         InvokeErr<'static, (i64, f64)>> {\n    runtime.invoke(\"add\", (a, b))\n}"
+    ));
+    assert!(bindings.contains(
+        "h: StructRef<'r>,\n) -> Result<StructRef<'r>, InvokeErr<'static, (StructRef<'r>,)>>"
+    ));
+    assert!(bindings.contains("pub fn handle<'r>(\n    runtime: &'r Runtime,\n"));
+    assert!(bindings.contains("values: ArrayRef<'r, i32>,"));
+    assert!(bindings.contains(
+        "pub fn unit(\n    runtime: &Runtime,\n) -> Result<(), InvokeErr<'static, ()>> {\n    \
+         runtime.invoke(\"unit\", ())\n}"
+    ));
+    assert!(bindings.contains("// `callback` is not wrapped"));
+    assert!(!bindings.contains("private"));
+}
+
 #[test]
 fn nested_structs() {
     test_snapshot(
@@ -1083,9 +2320,12 @@ fn test_snapshot_with_optimization(name: &str, text: &str, opt: OptimizationLeve
     db.set_optimization_level(opt);
     db.set_target(Target::host_target().unwrap());
 
-    // Build and extra diagnostics
+    // Build and extra diagnostics. Warnings don't prevent code generation.
     let messages = RefCell::new(Vec::new());
     let mut sink = DiagnosticSink::new(|diag| {
+        if diag.severity() != Severity::Error {
+            return;
+        }
         let file_id = diag.source().file_id;
         let line_index = db.line_index(file_id);
         let source_root_id = db.file_source_root(file_id);
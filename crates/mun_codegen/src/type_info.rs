@@ -19,6 +19,7 @@ pub enum TypeIdData {
     Concrete(Guid),
     Pointer(PointerTypeId),
     Array(Arc<TypeId>),
+    Slice(Arc<TypeId>),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
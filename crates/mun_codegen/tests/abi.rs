@@ -14,11 +14,20 @@ fn test_abi_compatibility() {
     pub fn {fn_name}(_: f64) -> i32 {{ 0 }}
     pub fn {fn_name2}() {{
         let a = {struct_name}(1.0, 2.0);
-        let b = [1,2,3]
+        let b = [1,2,3];
+        static mut CALLS: i32 = 0;
+        static mut TOTAL: f64 = 1.0 + 2.0;
     }}
 
     pub struct {struct_name}(f64, f64);
     pub struct(value) {struct_name2} {{ a: i32, b: i32 }};
+
+    pub const MAX: i32 = 10 * 4 + 2;
+    pub const SCALE: f64 = 1.5;
+    const HIDDEN: i32 = 1;
+
+    #[init]
+    fn setup() {{}}
     "#,
     ));
 
@@ -28,7 +37,27 @@ fn test_abi_compatibility() {
         .expect("Failed to load generated Mun library.");
 
     assert_eq!(abi::ABI_VERSION, unsafe { lib.get_abi_version() });
-    insta::assert_ron_snapshot!(unsafe { lib.get_info() },
+    assert_eq!(Some(abi::FormatHeader::current()), unsafe {
+        lib.get_format_header()
+    });
+
+    let compiler_info =
+        unsafe { lib.get_compiler_info() }.expect("munlib does not export compiler info");
+    assert_eq!(
+        compiler_info.compiler_version(),
+        mun_codegen::COMPILER_VERSION
+    );
+    assert_eq!(compiler_info.llvm_version(), mun_codegen::LLVM_VERSION);
+
+    // The target triple depends on the host, so it is checked separately
+    let info = unsafe { lib.get_info() };
+    let host_target =
+        mun_target::spec::Target::host_target().expect("unable to determine host target");
+    assert_eq!(info.build_info.target_triple(), host_target.llvm_target);
+
+    insta::assert_ron_snapshot!(info, {
+        ".build_info.target_triple" => "[target_triple]",
+    },
     @r#"
     AssemblyInfo(
       symbols: ModuleInfo(
@@ -54,6 +83,15 @@ fn test_abi_compatibility() {
               ),
             ),
           ),
+          FunctionDefinition(
+            prototype: FunctionPrototype(
+              name: "setup",
+              signature: FunctionSignature(
+                arg_types: [],
+                return_type: None,
+              ),
+            ),
+          ),
         ],
         types: [
           TypeDefinition(
@@ -99,6 +137,33 @@ fn test_abi_compatibility() {
             )),
           ),
         ],
+        statics: [
+          StaticDefinition(
+            name: "bar::CALLS",
+            type_id: Concrete("17797a74-19d6-3217-d235-954317885bfa"),
+            has_init_guard: false,
+          ),
+          StaticDefinition(
+            name: "bar::TOTAL",
+            type_id: Concrete("60db469c-3f59-4a25-47ad-349fd5922541"),
+            has_init_guard: true,
+          ),
+        ],
+        constants: [
+          ConstDefinition(
+            name: "MAX",
+            type_id: Concrete("17797a74-19d6-3217-d235-954317885bfa"),
+            value: 42,
+          ),
+          ConstDefinition(
+            name: "SCALE",
+            type_id: Concrete("60db469c-3f59-4a25-47ad-349fd5922541"),
+            value: 1.5,
+          ),
+        ],
+        init_functions: [
+          "setup",
+        ],
       ),
       dispatch_table: DispatchTable(
         prototypes: [
@@ -174,6 +239,11 @@ fn test_abi_compatibility() {
         ),
       ],
       dependencies: [],
+      build_info: BuildInfo(
+        target_triple: "[target_triple]",
+        optimization_level: Default,
+        has_debug_info: false,
+      ),
     )
     "#);
 }
@@ -1,8 +1,52 @@
+use mun_abi::{EntrySymbols, GET_INFO_FN_NAME};
 use mun_codegen::{CodeGenDatabase, CodeGenDatabaseStorage};
-use mun_hir::{salsa, HirDatabase, Upcast};
+use mun_hir::{
+    salsa::{
+        self,
+        debug::{DebugQueryTable, TableEntry},
+        Query, SweepStrategy,
+    },
+    BodyQuery, BodyWithSourceMapQuery, DefDatabase, InferQuery, ItemTreeQuery, ParseQuery, Upcast,
+};
 
 use crate::Config;
 
+/// Returns the number of values that are currently memoized by a query.
+macro_rules! memoized_values {
+    ($db:expr, $query:expr) => {
+        $query
+            .in_db($db)
+            .entries::<Vec<TableEntry<_, _>>>()
+            .into_iter()
+            .filter(|entry| entry.value.is_some())
+            .count()
+    };
+}
+
+/// The number of values that are memoized by the queries whose caches are
+/// cleared by [`CompilerDatabase::clear_caches`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheUsage {
+    /// The number of syntax trees of parsed files
+    pub syntax_trees: usize,
+
+    /// The number of item trees of files
+    pub item_trees: usize,
+
+    /// The number of lowered function bodies
+    pub bodies: usize,
+
+    /// The number of type inference results of function bodies
+    pub inference_results: usize,
+}
+
+impl CacheUsage {
+    /// Returns the total number of memoized values
+    pub fn total(&self) -> usize {
+        self.syntax_trees + self.item_trees + self.bodies + self.inference_results
+    }
+}
+
 /// A compiler database is a salsa database that enables increment compilation.
 #[salsa::database(
     mun_hir::SourceDatabaseStorage,
@@ -63,7 +107,66 @@ impl CompilerDatabase {
     pub fn set_config(&mut self, config: &Config) {
         self.set_target(config.target.clone());
         self.set_optimization_level(config.optimization_lvl);
+        self.set_minimize_size(config.minimize_size);
+        self.set_linker_output(config.output_kind);
+        self.set_pass_hook(config.pass_hook.clone());
+        self.set_verify_llvm(config.verify_llvm);
+        self.set_debug_info(config.debug_info);
+        self.set_static_dispatch(config.static_dispatch);
+        self.set_emit_relocations(config.emit_relocations);
+        self.set_split_debug_info(config.split_debug_info);
+        self.set_alloc_debug(config.alloc_debug);
+        self.set_init_structs(config.init_structs);
+        self.set_instrument_timing(config.instrument_timing);
+        self.set_call_trace_ring(config.call_trace_ring);
+        self.set_sanitizer(config.sanitizer);
+        self.set_pgo(config.pgo.clone());
+        self.set_gc_functions(config.gc_functions);
+        self.set_reflect_arg_names(config.reflect_arg_names);
+        self.set_reflect_fn_attributes(config.reflect_fn_attributes);
+        self.set_function_timings(config.function_timings.clone());
+        self.set_entry_symbols(EntrySymbols::with_version(
+            config.entry_symbol.as_deref().unwrap_or(GET_INFO_FN_NAME),
+            config.symbol_version.as_deref(),
+        ));
+    }
+
+    /// Discards the memoized values of the queries that use the most memory:
+    /// syntax trees, item trees, function bodies and type inference results.
+    ///
+    /// This is safe to call at any point in a session. The dependencies of
+    /// the discarded values are retained, so discarded values are recomputed
+    /// on demand and results that depend on them, like assemblies, are only
+    /// regenerated if their inputs changed.
+    pub fn clear_caches(&mut self) {
+        let strategy = SweepStrategy::default()
+            .discard_values()
+            .sweep_all_revisions();
+        ParseQuery.in_db(self).sweep(strategy);
+        ItemTreeQuery.in_db(self).sweep(strategy);
+        BodyWithSourceMapQuery.in_db(self).sweep(strategy);
+        BodyQuery.in_db(self).sweep(strategy);
+        InferQuery.in_db(self).sweep(strategy);
+    }
+
+    /// Returns the number of values that are currently memoized by the
+    /// queries whose caches are cleared by [`CompilerDatabase::clear_caches`].
+    pub fn cache_usage(&self) -> CacheUsage {
+        CacheUsage {
+            syntax_trees: memoized_values!(self, ParseQuery),
+            item_trees: memoized_values!(self, ItemTreeQuery),
+            bodies: memoized_values!(self, BodyQuery),
+            inference_results: memoized_values!(self, InferQuery),
+        }
     }
 }
 
 impl salsa::Database for CompilerDatabase {}
+
+impl salsa::ParallelDatabase for CompilerDatabase {
+    fn snapshot(&self) -> salsa::Snapshot<Self> {
+        salsa::Snapshot::new(CompilerDatabase {
+            storage: self.storage.snapshot(),
+        })
+    }
+}
@@ -1,6 +1,45 @@
+use std::ops::Range;
+
+pub use mun_hir::diagnostics::Severity;
+use mun_paths::RelativePathBuf;
+
+/// A diagnostic of a single file that is not rendered, such that tools can
+/// present it themselves. See [`Driver::file_diagnostics`].
+///
+/// [`Driver::file_diagnostics`]: crate::Driver::file_diagnostics
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiagnostic {
+    /// The byte range in the source code of the file that the diagnostic
+    /// refers to
+    pub range: Range<usize>,
+
+    /// Whether the diagnostic is an error or a warning
+    pub severity: Severity,
+
+    /// The message of the diagnostic
+    pub message: String,
+}
+
+/// The diagnostics of a single file that has any. See
+/// [`Driver::diagnostics_by_file`].
+///
+/// [`Driver::diagnostics_by_file`]: crate::Driver::diagnostics_by_file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiagnostics {
+    /// The path of the file relative to the source directory
+    pub relative_file_path: RelativePathBuf,
+
+    /// The diagnostics of the file, ordered by their position
+    pub diagnostics: Vec<FileDiagnostic>,
+
+    /// The number of errors that were omitted because the file exceeds the
+    /// maximum number of errors per file
+    pub num_omitted_errors: usize,
+}
+
 #[cfg(test)]
 mod tests {
-    use std::io::Cursor;
+    use std::{io::Cursor, num::NonZeroUsize};
 
     use crate::{Config, DisplayColor, Driver, PathOrInline, RelativePathBuf};
 
@@ -39,6 +78,13 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_unresolved_value_suggestion() {
+        insta::assert_snapshot!(compilation_errors(
+            "\n\nstruct Bar { a: i32 }\n\nfn main() {\nlet count = 1;\nlet b = cound;\nlet c = Baz;\n}"
+        ));
+    }
+
     #[test]
     fn test_unresolved_type_error() {
         insta::assert_snapshot!(compilation_errors(
@@ -67,6 +113,20 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_mismatched_numeric_type_error() {
+        insta::assert_snapshot!(compilation_errors(
+            "\n\nfn main() {\nlet a: i64 = 1;\nlet b: i32 = a;\nlet c: i64 = b + 1;\n}"
+        ));
+    }
+
+    #[test]
+    fn test_missing_return_type_error() {
+        insta::assert_snapshot!(compilation_errors(
+            "\n\nfn foo() {\n5\n}\n\nfn bar() {\nif true { 1.0 } else { 2.0 }\n}"
+        ));
+    }
+
     #[test]
     fn test_duplicate_definition_error() {
         insta::assert_snapshot!(compilation_errors(
@@ -88,6 +148,13 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_no_such_field_error() {
+        insta::assert_snapshot!(compilation_errors(
+            "\n\nstruct Foo {\ni: bool\n}\n\nfn main() {\nlet a = Foo { i: false, t: true };\n}"
+        ));
+    }
+
     #[test]
     fn test_free_type_alias_error() {
         insta::assert_snapshot!(compilation_errors("\n\ntype Foo;"));
@@ -107,4 +174,47 @@ mod tests {
     fn test_expected_function() {
         insta::assert_snapshot!(compilation_errors("\n\nfn foo() { let a = 3; a(); }"));
     }
+
+    #[test]
+    fn test_deny_warnings() {
+        let has_errors = |deny_warnings| {
+            let config = Config {
+                deny_warnings,
+                ..Config::default()
+            };
+            let input = PathOrInline::Inline {
+                rel_path: RelativePathBuf::from("main.mun"),
+                contents: "fn main(a: i32) { a + 1; }".to_owned(),
+            };
+            let (driver, _) = Driver::with_file(config, input).unwrap();
+            driver
+                .emit_diagnostics(&mut Cursor::new(Vec::new()), DisplayColor::Disable)
+                .unwrap()
+        };
+
+        assert!(!has_errors(false));
+        assert!(has_errors(true));
+    }
+
+    #[test]
+    fn test_max_errors_per_file() {
+        let config = Config {
+            max_errors_per_file: NonZeroUsize::new(1),
+            ..Config::default()
+        };
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: "fn main() {\nlet b = a;\nlet d = c;\nlet f = e;\n}".to_owned(),
+        };
+        let (driver, _) = Driver::with_file(config, input).unwrap();
+
+        let errors = driver
+            .emit_diagnostics_to_string(DisplayColor::Disable)
+            .unwrap()
+            .expect("expected unresolved value errors");
+        assert!(errors.contains("cannot find value `a`"));
+        assert!(!errors.contains("cannot find value `c`"));
+        assert!(!errors.contains("cannot find value `e`"));
+        assert!(errors.contains("2 more errors in main.mun not shown"));
+    }
 }
@@ -2,7 +2,7 @@ use std::{collections::HashMap, sync::Arc};
 
 use annotate_snippets::{Annotation, AnnotationType, Renderer, Slice, Snippet, SourceAnnotation};
 use mun_diagnostics::DiagnosticForWith;
-use mun_hir::{line_index::LineIndex, FileId, HirDatabase};
+use mun_hir::{diagnostics::Severity, line_index::LineIndex, FileId, HirDatabase};
 use mun_paths::RelativePathBuf;
 use mun_syntax::SyntaxError;
 
@@ -52,6 +52,36 @@ pub(crate) fn emit_syntax_error(
     write!(writer, "{display}")
 }
 
+/// Writes a note that `num_omitted_errors` errors of the specified file were
+/// not emitted because the file exceeds the maximum number of errors per file.
+pub(crate) fn emit_omitted_errors_note(
+    num_omitted_errors: usize,
+    relative_file_path: &str,
+    display_colors: bool,
+    writer: &mut dyn std::io::Write,
+) -> std::io::Result<()> {
+    let plural = if num_omitted_errors == 1 { "" } else { "s" };
+    let label =
+        format!("{num_omitted_errors} more error{plural} in {relative_file_path} not shown");
+    let snippet = Snippet {
+        title: Some(Annotation {
+            id: None,
+            label: Some(&label),
+            annotation_type: AnnotationType::Note,
+        }),
+        footer: vec![],
+        slices: vec![],
+    };
+
+    let renderer = if display_colors {
+        Renderer::styled()
+    } else {
+        Renderer::plain()
+    };
+    let display = renderer.render(snippet);
+    write!(writer, "{display}")
+}
+
 /// Emits all diagnostics that are a result of HIR validation.
 pub(crate) fn emit_hir_diagnostic(
     diagnostic: &dyn mun_hir::Diagnostic,
@@ -60,14 +90,26 @@ pub(crate) fn emit_hir_diagnostic(
     display_colors: bool,
     writer: &mut dyn std::io::Write,
 ) -> std::io::Result<()> {
+    let annotation_type = match diagnostic.severity() {
+        Severity::Error => AnnotationType::Error,
+        Severity::Warning => AnnotationType::Warning,
+    };
     diagnostic.with_diagnostic(db, |diagnostic| {
-        emit_diagnostic(diagnostic, db, file_id, display_colors, writer)
+        emit_diagnostic(
+            diagnostic,
+            annotation_type,
+            db,
+            file_id,
+            display_colors,
+            writer,
+        )
     })
 }
 
 /// Emits a diagnostic by writting a snippet to the specified `writer`.
 fn emit_diagnostic(
     diagnostic: &dyn mun_diagnostics::Diagnostic,
+    annotation_type: AnnotationType,
     db: &impl HirDatabase,
     file_id: FileId,
     display_colors: bool,
@@ -133,13 +175,18 @@ fn emit_diagnostic(
     };
 
     let footer = diagnostic.footer();
+    let suggestions = diagnostic
+        .suggestions()
+        .into_iter()
+        .map(|suggestion| format!("{}: `{}`", suggestion.message, suggestion.replacement))
+        .collect::<Vec<_>>();
 
     // Construct an annotation snippet to be able to emit it.
     let snippet = Snippet {
         title: Some(Annotation {
             id: None,
             label: Some(&title),
-            annotation_type: AnnotationType::Error,
+            annotation_type,
         }),
         slices: annotations
             .iter()
@@ -169,7 +216,7 @@ fn emit_diagnostic(
                                 usize::from(annotation.range.end()) - line_offset,
                             ),
                             label: annotation.message.as_str(),
-                            annotation_type: AnnotationType::Error,
+                            annotation_type,
                         })
                         .collect(),
                     fold: true,
@@ -183,6 +230,11 @@ fn emit_diagnostic(
                 label: Some(footer.as_str()),
                 annotation_type: AnnotationType::Note,
             })
+            .chain(suggestions.iter().map(|suggestion| Annotation {
+                id: None,
+                label: Some(suggestion.as_str()),
+                annotation_type: AnnotationType::Help,
+            }))
             .collect(),
     };
 
@@ -1,16 +1,20 @@
 //! `Driver` is a stateful compiler frontend that enables incremental
 //! compilation by retaining state from previous compilation.
 
-use mun_codegen::{AssemblyIr, CodeGenDatabase, ModuleGroup, TargetAssembly};
+use mun_codegen::{
+    AssemblyIr, CHeader, CodeGenDatabase, ModuleGroup, ModuleGroupId, RustBindings, TargetAssembly,
+};
 use mun_hir::{
-    AstDatabase, DiagnosticSink, FileId, Module, PackageSet, SourceDatabase, SourceRoot,
-    SourceRootId, Upcast,
+    diagnostics::Severity, salsa::ParallelDatabase, AstDatabase, DiagnosticSink, FileId, Module,
+    PackageSet, SourceDatabase, SourceRoot, SourceRootId, Upcast,
 };
 use mun_paths::RelativePathBuf;
 
 use crate::{
-    compute_source_relative_path, db::CompilerDatabase, ensure_package_output_dir, is_source_file,
-    PathOrInline, RelativePath,
+    compute_source_relative_path,
+    db::{CacheUsage, CompilerDatabase},
+    diagnostics::{FileDiagnostic, FileDiagnostics},
+    ensure_package_output_dir, is_source_file, PathOrInline, RelativePath,
 };
 
 mod config;
@@ -20,8 +24,13 @@ use std::{
     collections::HashMap,
     convert::TryInto,
     io::Cursor,
+    num::NonZeroUsize,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
     time::Duration,
 };
 
@@ -29,7 +38,9 @@ use mun_project::{Package, LOCKFILE_NAME};
 use walkdir::WalkDir;
 
 pub use self::{config::Config, display_color::DisplayColor};
-use crate::diagnostics_snippets::{emit_hir_diagnostic, emit_syntax_error};
+use crate::diagnostics_snippets::{
+    emit_hir_diagnostic, emit_omitted_errors_note, emit_syntax_error,
+};
 
 pub const WORKSPACE: SourceRootId = SourceRootId(0);
 
@@ -45,6 +56,11 @@ pub struct Driver {
     module_to_temp_assembly_path: HashMap<Module, PathBuf>,
 
     emit_ir: bool,
+    emit_c_header: bool,
+    emit_rust_bindings: bool,
+    jobs: Option<NonZeroUsize>,
+    deny_warnings: bool,
+    max_errors_per_file: Option<NonZeroUsize>,
 }
 
 impl Driver {
@@ -59,6 +75,11 @@ impl Driver {
             next_file_id: 0,
             module_to_temp_assembly_path: HashMap::default(),
             emit_ir: config.emit_ir,
+            emit_c_header: config.emit_c_header,
+            emit_rust_bindings: config.emit_rust_bindings,
+            jobs: config.jobs,
+            deny_warnings: config.deny_warnings,
+            max_errors_per_file: config.max_errors_per_file,
         }
     }
 
@@ -82,6 +103,7 @@ impl Driver {
         // Store the file information in the database together with the source root
         let file_id = FileId(driver.next_file_id as u32);
         driver.next_file_id += 1;
+        log::debug!("loaded source file file_id={} path={}", file_id.0, rel_path);
         driver.db.set_file_text(file_id, Arc::from(text));
         driver.db.set_file_source_root(file_id, WORKSPACE);
         driver.source_root.insert_file(file_id, rel_path.clone());
@@ -133,6 +155,11 @@ impl Driver {
             })?;
 
             let file_id = driver.alloc_file_id(&relative_path)?;
+            log::debug!(
+                "loaded source file file_id={} path={}",
+                file_id.0,
+                relative_path
+            );
             driver.db.set_file_text(file_id, Arc::from(file_contents));
             driver.db.set_file_source_root(file_id, WORKSPACE);
             driver
@@ -208,7 +235,10 @@ impl Driver {
 
 impl Driver {
     /// Emits all diagnostic messages currently in the database; returns true if
-    /// errors were emitted.
+    /// errors were emitted, or warnings if warnings are denied.
+    ///
+    /// The diagnostics are grouped by file and ordered by their position in
+    /// the file. Files are ordered by their relative path.
     pub fn emit_diagnostics(
         &self,
         writer: &mut dyn std::io::Write,
@@ -217,53 +247,162 @@ impl Driver {
         let emit_colors = display_color.should_enable();
         let mut has_error = false;
 
-        for package in mun_hir::Package::all(self.db.upcast()) {
-            for module in package.modules(self.db.upcast()) {
-                if let Some(file_id) = module.file_id(self.db.upcast()) {
-                    let parse = self.db.parse(file_id);
-                    let source_code = self.db.file_text(file_id);
-                    let relative_file_path = self.db.file_relative_path(file_id);
-                    let line_index = self.db.line_index(file_id);
-
-                    // Emit all syntax diagnostics
-                    for syntax_error in parse.errors().iter() {
-                        emit_syntax_error(
-                            syntax_error,
-                            relative_file_path.as_str(),
-                            &source_code,
-                            &line_index,
-                            emit_colors,
-                            writer,
-                        )?;
-                        has_error = true;
-                    }
+        for (relative_file_path, module, file_id) in self.files_sorted_by_path() {
+            let parse = self.db.parse(file_id);
+            let source_code = self.db.file_text(file_id);
+            let line_index = self.db.line_index(file_id);
+
+            // The diagnostics are rendered into buffers first, such that they can be
+            // emitted in order of their position.
+            let mut rendered = Vec::new();
+
+            // Render all syntax diagnostics
+            for syntax_error in parse.errors().iter() {
+                let mut buffer = Vec::new();
+                emit_syntax_error(
+                    syntax_error,
+                    relative_file_path.as_str(),
+                    &source_code,
+                    &line_index,
+                    emit_colors,
+                    &mut buffer,
+                )?;
+                rendered.push((
+                    usize::from(syntax_error.location().offset()),
+                    Severity::Error,
+                    buffer,
+                ));
+                has_error = true;
+            }
 
-                    // Emit all HIR diagnostics
-                    let mut error = None;
-                    module.diagnostics(
-                        self.db.upcast(),
-                        &mut DiagnosticSink::new(|d| {
-                            has_error = true;
-                            if let Err(e) =
-                                emit_hir_diagnostic(d, &self.db, file_id, emit_colors, writer)
-                            {
-                                error = Some(e);
-                            };
-                        }),
-                    );
-
-                    // If an error occurred when emitting HIR diagnostics, return early with the
-                    // error.
-                    if let Some(e) = error {
-                        return Err(e.into());
-                    }
-                }
+            // Render all HIR diagnostics
+            let mut error = None;
+            let mut sink = DiagnosticSink::new(|d| {
+                has_error |= d.severity() == Severity::Error
+                    || (self.deny_warnings && d.severity() == Severity::Warning);
+                let mut buffer = Vec::new();
+                if let Err(e) = emit_hir_diagnostic(d, &self.db, file_id, emit_colors, &mut buffer)
+                {
+                    error = Some(e);
+                };
+                rendered.push((
+                    usize::from(d.highlight_range().start()),
+                    d.severity(),
+                    buffer,
+                ));
+            });
+            module.diagnostics(self.db.upcast(), &mut sink);
+
+            // Functions that are removed from the assemblies are reported as unused
+            if self.db.gc_functions() {
+                module.unused_function_diagnostics(self.db.upcast(), &mut sink);
+            }
+            drop(sink);
+
+            // If an error occurred when rendering HIR diagnostics, return early with the
+            // error.
+            if let Some(e) = error {
+                return Err(e.into());
+            }
+
+            log::debug!(
+                "checked file file_id={} path={} syntax_errors={} diagnostics={}",
+                file_id.0,
+                relative_file_path,
+                parse.errors().len(),
+                rendered.len()
+            );
+            rendered.sort_by_key(|(offset, ..)| *offset);
+            let (rendered, num_omitted_errors) =
+                self.limit_errors(rendered, |(_, severity, _)| *severity == Severity::Error);
+            for (_, _, buffer) in rendered {
+                writer.write_all(&buffer)?;
+            }
+            if num_omitted_errors > 0 {
+                emit_omitted_errors_note(
+                    num_omitted_errors,
+                    relative_file_path.as_str(),
+                    emit_colors,
+                    writer,
+                )?;
             }
         }
 
         Ok(has_error)
     }
 
+    /// Returns the diagnostics of all files that have any, without rendering
+    /// them, such that tools can present them themselves, e.g. as JSON. The
+    /// files are ordered by their relative path and the diagnostics of each
+    /// file by their position. The number of errors per file is limited in
+    /// the same way as by [`Driver::emit_diagnostics`].
+    pub fn diagnostics_by_file(&self) -> Vec<FileDiagnostics> {
+        self.files_sorted_by_path()
+            .into_iter()
+            .filter_map(|(relative_file_path, _, file_id)| {
+                let diagnostics = self.file_diagnostics(file_id);
+                if diagnostics.is_empty() {
+                    return None;
+                }
+                let (diagnostics, num_omitted_errors) = self
+                    .limit_errors(diagnostics, |diagnostic| {
+                        diagnostic.severity == Severity::Error
+                    });
+                Some(FileDiagnostics {
+                    relative_file_path,
+                    diagnostics,
+                    num_omitted_errors,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the modules that have a file, ordered by the relative path of
+    /// the file to ensure a deterministic order.
+    fn files_sorted_by_path(&self) -> Vec<(RelativePathBuf, Module, FileId)> {
+        let mut files: Vec<_> = mun_hir::Package::all(self.db.upcast())
+            .into_iter()
+            .flat_map(|package| package.modules(self.db.upcast()))
+            .filter_map(|module| {
+                let file_id = module.file_id(self.db.upcast())?;
+                Some((self.db.file_relative_path(file_id), module, file_id))
+            })
+            .collect();
+        files.sort_by(|(lhs, ..), (rhs, ..)| lhs.cmp(rhs));
+        files
+    }
+
+    /// Drops the errors of a single file beyond the configured maximum,
+    /// returning the remaining diagnostics and the number of dropped errors.
+    fn limit_errors<T>(
+        &self,
+        diagnostics: Vec<T>,
+        is_error: impl Fn(&T) -> bool,
+    ) -> (Vec<T>, usize) {
+        let Some(max_errors) = self.max_errors_per_file else {
+            return (diagnostics, 0);
+        };
+
+        let mut num_errors = 0;
+        let mut num_omitted_errors = 0;
+        let diagnostics = diagnostics
+            .into_iter()
+            .filter(|diagnostic| {
+                if !is_error(diagnostic) {
+                    return true;
+                }
+                num_errors += 1;
+                if num_errors > max_errors.get() {
+                    num_omitted_errors += 1;
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect();
+        (diagnostics, num_omitted_errors)
+    }
+
     /// Returns all diagnostics as a human readable string
     pub fn emit_diagnostics_to_string(
         &self,
@@ -281,6 +420,59 @@ impl Driver {
             Ok(None)
         }
     }
+
+    /// Returns the diagnostics of the file with the specified id, ordered by
+    /// their position, without rendering them, such that tools can present
+    /// them themselves.
+    pub fn file_diagnostics(&self, file_id: FileId) -> Vec<FileDiagnostic> {
+        let mut diagnostics: Vec<_> = self
+            .db
+            .parse(file_id)
+            .errors()
+            .iter()
+            .map(|syntax_error| {
+                let location = syntax_error.location();
+                FileDiagnostic {
+                    range: location.offset().into()..location.end_offset().into(),
+                    severity: Severity::Error,
+                    message: syntax_error.to_string(),
+                }
+            })
+            .collect();
+
+        if let Some(module) = Module::from_file(self.db.upcast(), file_id) {
+            let mut sink = DiagnosticSink::new(|d| {
+                let range = d.highlight_range();
+                diagnostics.push(FileDiagnostic {
+                    range: range.start().into()..range.end().into(),
+                    severity: d.severity(),
+                    message: d.message(),
+                });
+            });
+            module.diagnostics(self.db.upcast(), &mut sink);
+
+            // Functions that are removed from the assemblies are reported as unused
+            if self.db.gc_functions() {
+                module.unused_function_diagnostics(self.db.upcast(), &mut sink);
+            }
+        }
+
+        diagnostics.sort_by_key(|diagnostic| diagnostic.range.start);
+        diagnostics
+    }
+}
+
+impl Driver {
+    /// Discards the memoized intermediate results of the compiler to bound
+    /// its memory usage. See [`CompilerDatabase::clear_caches`].
+    pub fn clear_caches(&mut self) {
+        self.db.clear_caches();
+    }
+
+    /// Returns the number of intermediate results that are currently memoized.
+    pub fn cache_usage(&self) -> CacheUsage {
+        self.db.cache_usage()
+    }
 }
 
 impl Driver {
@@ -338,20 +530,100 @@ impl Driver {
     pub fn write_all_assemblies(&mut self, force: bool) -> Result<(), anyhow::Error> {
         let _lock = self.acquire_filesystem_output_lock();
 
+        // Build the assemblies of all module groups before writing them to disk
+        self.build_all_assemblies();
+
         // Create a copy of all current files
         for package in mun_hir::Package::all(self.db.upcast()) {
             for module in package.modules(self.db.upcast()) {
-                if self.emit_ir {
-                    self.write_assembly_ir(module)?;
-                } else {
-                    self.write_target_assembly(module, force)?;
-                }
+                self.write_module_outputs(module, force)?;
             }
         }
 
         Ok(())
     }
 
+    /// Writes only the outputs of the module that corresponds to the specified
+    /// file. If `force` is false, the binary will not be written if there are
+    /// no changes since last time it was written.
+    pub fn write_file_assemblies(
+        &mut self,
+        file_id: FileId,
+        force: bool,
+    ) -> Result<(), anyhow::Error> {
+        let _lock = self.acquire_filesystem_output_lock();
+
+        let module = Module::from_file(self.db.upcast(), file_id)
+            .ok_or_else(|| anyhow::anyhow!("could not find the module of {:?}", file_id))?;
+        self.write_module_outputs(module, force)
+    }
+
+    /// Writes the assembly, or its IR, of the specified module together with
+    /// any additional artifacts that were requested.
+    fn write_module_outputs(&mut self, module: Module, force: bool) -> Result<(), anyhow::Error> {
+        if self.emit_ir {
+            self.write_assembly_ir(module)?;
+        } else {
+            self.write_target_assembly(module, force)?;
+        }
+
+        if self.emit_c_header {
+            self.write_c_header(module)?;
+        }
+
+        if self.emit_rust_bindings {
+            self.write_rust_bindings(module)?;
+        }
+
+        Ok(())
+    }
+
+    /// Builds the assemblies of all module groups on a pool of threads. Every
+    /// thread queries its own snapshot of the database, so concurrent builds
+    /// don't share any mutable state. The results are cached in the database
+    /// from where they are retrieved when the assemblies are written to disk.
+    fn build_all_assemblies(&self) {
+        let module_partition = self.db.module_partition();
+        let module_group_ids: Vec<ModuleGroupId> =
+            module_partition.iter().map(|(id, _)| id).collect();
+
+        let num_threads = self
+            .jobs
+            .or_else(|| thread::available_parallelism().ok())
+            .map_or(1, NonZeroUsize::get)
+            .min(module_group_ids.len());
+        log::debug!(
+            "building assemblies module_groups={} threads={}",
+            module_group_ids.len(),
+            num_threads
+        );
+        if num_threads <= 1 {
+            return;
+        }
+
+        let next_module_group = AtomicUsize::new(0);
+        thread::scope(|scope| {
+            for _ in 0..num_threads {
+                let db = self.db.snapshot();
+                let module_group_ids = &module_group_ids;
+                let next_module_group = &next_module_group;
+                let emit_ir = self.emit_ir;
+                scope.spawn(move || {
+                    while let Some(&module_group_id) =
+                        module_group_ids.get(next_module_group.fetch_add(1, Ordering::Relaxed))
+                    {
+                        // Errors are reported when the assembly is written
+                        if emit_ir {
+                            let _ = db.assembly_ir(module_group_id);
+                        } else {
+                            let _ = db.target_assembly(module_group_id);
+                        }
+                    }
+                });
+            }
+        });
+    }
+
     /// Acquires a filesystem lock on the output directory. This ensures that
     /// multiple instances cannot write to the same output directory and
     /// that the runtime does not start reading before we finished writing.
@@ -387,17 +659,20 @@ impl Driver {
         module: Module,
         force: bool,
     ) -> Result<bool, anyhow::Error> {
-        log::trace!("writing target assembly for {:?}", module);
-
         // Find the module group to which the module belongs
         let module_partition = self.db.module_partition();
         let module_group_id = module_partition
             .group_for_module(module)
             .expect("could not find the module in the module partition");
         let module_group = &module_partition[module_group_id];
+        log::debug!(
+            "writing target assembly module_group={} file_id={:?}",
+            module_group.name,
+            module.file_id(self.db.upcast()).map(|file_id| file_id.0)
+        );
 
         // Get the compiled assembly
-        let assembly = self.db.target_assembly(module_group_id);
+        let assembly = self.db.target_assembly(module_group_id)?;
 
         // Determine the filename of the group
         let assembly_path = self
@@ -416,8 +691,15 @@ impl Driver {
             return Ok(false);
         }
 
-        // It did change or we are forced, so write it to disk
+        // It did change or we are forced, so write it to disk. The debug information is
+        // written first, such that no assembly is written if it could not be split off.
+        assembly.copy_debug_info_to(
+            assembly_path.with_extension(TargetAssembly::DEBUG_INFO_EXTENSION),
+        )?;
         assembly.copy_to(&assembly_path)?;
+        assembly.copy_relocations_to(
+            assembly_path.with_extension(TargetAssembly::RELOCATIONS_EXTENSION),
+        )?;
 
         // Store the information so we maybe don't have to write it next time
         self.module_to_temp_assembly_path
@@ -426,20 +708,81 @@ impl Driver {
         Ok(true)
     }
 
+    /// Generates a C header file for the specified module and stores it in the
+    /// output location.
+    fn write_c_header(&mut self, module: mun_hir::Module) -> Result<(), anyhow::Error> {
+        // Find the module group to which the module belongs
+        let module_partition = self.db.module_partition();
+        let module_group_id = module_partition
+            .group_for_module(module)
+            .expect("could not find the module in the module partition");
+        let module_group = &module_partition[module_group_id];
+        log::debug!(
+            "writing C header module_group={} file_id={:?}",
+            module_group.name,
+            module.file_id(self.db.upcast()).map(|file_id| file_id.0)
+        );
+
+        // Get the generated header
+        let c_header = self.db.c_header(module_group_id);
+
+        // Determine the filename of the group
+        let header_path = self
+            .path_for_module_group(module_group)
+            .with_extension(CHeader::EXTENSION);
+
+        // Write to disk
+        c_header.copy_to(header_path)?;
+
+        Ok(())
+    }
+
+    /// Generates Rust bindings for the specified module and stores them in the
+    /// output location.
+    fn write_rust_bindings(&mut self, module: mun_hir::Module) -> Result<(), anyhow::Error> {
+        // Find the module group to which the module belongs
+        let module_partition = self.db.module_partition();
+        let module_group_id = module_partition
+            .group_for_module(module)
+            .expect("could not find the module in the module partition");
+        let module_group = &module_partition[module_group_id];
+        log::debug!(
+            "writing Rust bindings module_group={} file_id={:?}",
+            module_group.name,
+            module.file_id(self.db.upcast()).map(|file_id| file_id.0)
+        );
+
+        // Get the generated bindings
+        let rust_bindings = self.db.rust_bindings(module_group_id);
+
+        // Determine the filename of the group
+        let bindings_path = self
+            .path_for_module_group(module_group)
+            .with_extension(RustBindings::EXTENSION);
+
+        // Write to disk
+        rust_bindings.copy_to(bindings_path)?;
+
+        Ok(())
+    }
+
     /// Generates IR for the specified module and stores it in the output
     /// location.
     fn write_assembly_ir(&mut self, module: mun_hir::Module) -> Result<(), anyhow::Error> {
-        log::trace!("writing assembly IR for {:?}", module);
-
         // Find the module group to which the module belongs
         let module_partition = self.db.module_partition();
         let module_group_id = module_partition
             .group_for_module(module)
             .expect("could not find the module in the module partition");
         let module_group = &module_partition[module_group_id];
+        log::debug!(
+            "writing assembly IR module_group={} file_id={:?}",
+            module_group.name,
+            module.file_id(self.db.upcast()).map(|file_id| file_id.0)
+        );
 
         // Get the compiled assembly
-        let assembly_ir = self.db.assembly_ir(module_group_id);
+        let assembly_ir = self.db.assembly_ir(module_group_id)?;
 
         // Determine the filename of the group
         let assembly_path = self
@@ -1,6 +1,12 @@
-use std::path::PathBuf;
+use std::{
+    num::{NonZeroU32, NonZeroUsize},
+    path::PathBuf,
+};
 
-pub use mun_codegen::OptimizationLevel;
+pub use mun_codegen::{
+    DebugInfo, FunctionTimingsRef, LinkerOutput, OptimizationLevel, PassHookRef,
+    ProfileGuidedOptimization, Sanitizer,
+};
 use mun_target::spec::Target;
 
 /// Describes all the permanent settings that are used during compilations.
@@ -12,12 +18,145 @@ pub struct Config {
     /// The optimization level to use for the IR generation.
     pub optimization_lvl: OptimizationLevel,
 
+    /// Whether to minimize the size of the generated code aggressively, at the
+    /// expense of its speed. Combined with `OptimizationLevel::Default` this is
+    /// the equivalent of `-Oz`.
+    pub minimize_size: bool,
+
     /// The optional output directory to store all outputs. If no directory is
     /// specified all output is stored in a temporary directory.
     pub out_dir: Option<PathBuf>,
 
     /// Whether or not to emit an IR file instead of a munlib.
     pub emit_ir: bool,
+
+    /// The kind of binary to produce when linking a munlib.
+    pub output_kind: LinkerOutput,
+
+    /// An optional hook that adds custom LLVM passes to the optimization
+    /// pipeline.
+    pub pass_hook: Option<PassHookRef>,
+
+    /// Whether to validate assemblies with the LLVM verifier after
+    /// optimization. Enabled by default in debug builds of the compiler.
+    pub verify_llvm: bool,
+
+    /// The amount of debug information to emit into assemblies.
+    pub debug_info: DebugInfo,
+
+    /// Whether to compile all modules into a single assembly in which calls
+    /// between functions do not go through the dispatch table. Modules can
+    /// then only be hot reloaded together.
+    pub static_dispatch: bool,
+
+    /// Whether to write a `*.relocs` file next to each assembly that lists
+    /// the references from machine code to dispatch table slots and globals.
+    pub emit_relocations: bool,
+
+    /// Whether the debug information of each assembly is moved into a separate
+    /// file next to it, which the assembly refers to by name and checksum.
+    /// Only supported for ELF targets, see `supports_split_debug_info`. For
+    /// other targets writing the assembly fails.
+    pub split_debug_info: bool,
+
+    /// Whether heap allocations pass the source location of the allocating
+    /// expression to the runtime, which records it. Intended for debug builds
+    /// to hunt down leaks, see `Runtime::alloc_report`.
+    pub alloc_debug: bool,
+
+    /// Whether structs are zero-initialized when they are allocated on the
+    /// heap. Struct literals always assign every field, so zeroing is only a
+    /// safety net. Without it, only the references to other objects are
+    /// zeroed, such that the garbage collector never traces uninitialized
+    /// memory. Allocations that record their location with `alloc_debug` are
+    /// always zero-initialized.
+    pub init_structs: bool,
+
+    /// Whether functions report their entry and exit to the runtime, which
+    /// accumulates their execution time, see `Runtime::timings`. This perturbs
+    /// the performance of the instrumented code, so it is intended for
+    /// profiling builds only. Functions marked `#[no_instrument]` are excluded.
+    pub instrument_timing: bool,
+
+    /// The number of calls that the runtime keeps in its call trace ring, if
+    /// any. On entry, every function that is not marked `#[no_instrument]`
+    /// records its id in the ring, such that a host can retrieve the most
+    /// recent calls after a crash, see `Runtime::call_trace`. This costs a few
+    /// instructions per call.
+    pub call_trace_ring: Option<NonZeroU32>,
+
+    /// The sanitizer that instruments the code of assemblies, if any. The
+    /// runtime library of the sanitizer is located through the
+    /// `MUN_SANITIZER_RUNTIME_DIR` environment variable and linked into the
+    /// assemblies. The host process must load the same runtime before any
+    /// instrumented assembly, e.g. with `LD_PRELOAD` or by being built with the
+    /// sanitizer itself. AddressSanitizer is compatible with the garbage
+    /// collector, which allocates objects through the system allocator, so
+    /// accesses to collected objects are reported as use-after-free.
+    pub sanitizer: Option<Sanitizer>,
+
+    /// The phase of a profile-guided optimization build, if any. When
+    /// generating a profile, the profiler runtime is located like the runtime
+    /// of a sanitizer and linked into the assemblies, which write a raw profile
+    /// when the host process exits. When using a profile, functions whose
+    /// control flow changed since the profile was generated are reported with
+    /// a warning and optimized without their profile.
+    pub pgo: Option<ProfileGuidedOptimization>,
+
+    /// Whether functions that are not reachable from any exported (`pub`,
+    /// `#[no_mangle]` or `#[reflect]`) function are omitted from the
+    /// assemblies. Each omitted function is reported with a warning, unless it
+    /// is marked `#[allow(dead_code)]`.
+    pub gc_functions: bool,
+
+    /// Whether the reflection information of functions includes the names of
+    /// their arguments, e.g. for REPLs or binding generators. Omitting them
+    /// reduces the size of assemblies.
+    pub reflect_arg_names: bool,
+
+    /// Whether the reflection information of functions includes their
+    /// effective attributes, e.g. how they are inlined and optimized, for
+    /// tools that inspect assemblies.
+    pub reflect_fn_attributes: bool,
+
+    /// Where the time it takes to generate IR for and optimize each function
+    /// is recorded, if it is measured. Only assemblies that are (re)built are
+    /// measured.
+    pub function_timings: Option<FunctionTimingsRef>,
+
+    /// Whether to write a C header file next to each assembly that declares
+    /// its `#[no_mangle]` functions and value structs.
+    pub emit_c_header: bool,
+
+    /// Whether to write a Rust source file next to each assembly that wraps
+    /// its functions for a host that embeds the runtime.
+    pub emit_rust_bindings: bool,
+
+    /// The name of the entry symbol of assemblies. If no value is specified
+    /// the default `get_info` symbol is used. A custom entry symbol prevents
+    /// symbol collisions when several assemblies are statically linked
+    /// together; the runtime must then be told the same entry symbol.
+    pub entry_symbol: Option<String>,
+
+    /// The version with which every exported symbol of assemblies is
+    /// suffixed, such that several versions of an assembly can be loaded side
+    /// by side. If no value is specified symbols are not versioned.
+    pub symbol_version: Option<String>,
+
+    /// The maximum number of threads that are used to build assemblies in
+    /// parallel. If no value is specified the available parallelism of the
+    /// machine is used.
+    pub jobs: Option<NonZeroUsize>,
+
+    /// Whether warnings are treated as errors, such that a compilation that
+    /// emits warnings fails. Lints that are suppressed with `#[allow(..)]` are
+    /// not emitted and therefore do not fail the compilation.
+    pub deny_warnings: bool,
+
+    /// The maximum number of errors that are reported per file, if any.
+    /// Diagnostics are reported per file in order of their position, so the
+    /// first errors of each file are reported. Warnings are not limited.
+    pub max_errors_per_file: Option<NonZeroUsize>,
 }
 
 impl Default for Config {
@@ -28,8 +167,33 @@ impl Default for Config {
             // triple.
             target: target.unwrap(),
             optimization_lvl: OptimizationLevel::Default,
+            minimize_size: false,
             out_dir: None,
             emit_ir: false,
+            output_kind: LinkerOutput::default(),
+            pass_hook: None,
+            verify_llvm: cfg!(debug_assertions),
+            debug_info: DebugInfo::None,
+            static_dispatch: false,
+            emit_relocations: false,
+            split_debug_info: false,
+            alloc_debug: false,
+            init_structs: true,
+            instrument_timing: false,
+            call_trace_ring: None,
+            sanitizer: None,
+            pgo: None,
+            gc_functions: false,
+            reflect_arg_names: false,
+            reflect_fn_attributes: false,
+            function_timings: None,
+            emit_c_header: false,
+            emit_rust_bindings: false,
+            entry_symbol: None,
+            symbol_version: None,
+            jobs: None,
+            deny_warnings: false,
+            max_errors_per_file: None,
         }
     }
 }
@@ -13,14 +13,18 @@ use std::{
 };
 
 pub use annotate_snippets::AnnotationType;
-pub use mun_codegen::OptimizationLevel;
+pub use mun_codegen::{
+    supports_split_debug_info, DebugInfo, FunctionTiming, FunctionTimings, FunctionTimingsRef,
+    LinkerOutput, OptimizationLevel, PassHook, PassHookRef, ProfileGuidedOptimization, Sanitizer,
+    TargetSpec,
+};
 pub use mun_hir::FileId;
 pub use mun_paths::{RelativePath, RelativePathBuf};
 use mun_project::Package;
-pub use mun_target::spec::Target;
+pub use mun_target::spec::{get_targets, Target};
 
 pub use crate::{
-    db::CompilerDatabase,
+    db::{CacheUsage, CompilerDatabase},
     driver::{Config, DisplayColor, Driver},
 };
 
@@ -105,6 +109,29 @@ pub fn compile_manifest(
     Ok(true)
 }
 
+/// Compiles a single module from source that is not stored on disk, e.g. read
+/// from stdin. `rel_path` is the name under which diagnostics are reported and
+/// from which the module name and output path are derived.
+pub fn compile_source(
+    rel_path: RelativePathBuf,
+    contents: String,
+    config: Config,
+    emit_colors: DisplayColor,
+) -> Result<bool, anyhow::Error> {
+    let (mut driver, file_id) =
+        Driver::with_file(config, PathOrInline::Inline { rel_path, contents })?;
+
+    // Emit diagnostics. If one of the snippets is an error, abort gracefully.
+    if driver.emit_diagnostics(&mut stderr(), emit_colors)? {
+        return Ok(false);
+    };
+
+    // Only write the outputs of the virtual file
+    driver.write_file_assemblies(file_id, false)?;
+
+    Ok(true)
+}
+
 /// Determines the relative path of a file to the source directory.
 pub fn compute_source_relative_path(
     source_dir: &Path,
@@ -128,9 +155,14 @@ pub fn compute_source_relative_path(
 
 #[cfg(test)]
 mod test {
-    use std::path::Path;
+    use std::{num::NonZeroUsize, path::Path};
 
-    use crate::{compute_source_relative_path, is_source_file, RelativePath};
+    use crate::{
+        compute_source_relative_path, diagnostics::Severity, is_source_file,
+        supports_split_debug_info, CacheUsage, Config, DebugInfo, DisplayColor, Driver,
+        LinkerOutput, PathOrInline, ProfileGuidedOptimization, RelativePath, RelativePathBuf,
+        Target,
+    };
 
     #[test]
     fn test_is_source_file() {
@@ -152,4 +184,261 @@ mod test {
             RelativePath::new("foo/bar/main.mun")
         );
     }
+
+    #[test]
+    fn test_virtual_file_outputs() {
+        let out_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            out_dir: Some(out_dir.path().to_path_buf()),
+            emit_c_header: true,
+            emit_rust_bindings: true,
+            ..Config::default()
+        };
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("stdin_module.mun"),
+            contents: "#[no_mangle]\npub fn add(a: i32, b: i32) -> i32 { a + b }".to_owned(),
+        };
+        let (mut driver, file_id) = Driver::with_file(config, input).unwrap();
+        assert_eq!(
+            driver
+                .emit_diagnostics_to_string(DisplayColor::Disable)
+                .unwrap(),
+            None
+        );
+
+        driver.write_file_assemblies(file_id, false).unwrap();
+
+        let assembly_path = driver.assembly_output_path_from_file(file_id);
+        assert_eq!(assembly_path, out_dir.path().join("stdin_module.munlib"));
+        assert!(assembly_path.is_file());
+        assert!(out_dir.path().join("stdin_module.h").is_file());
+        assert!(out_dir.path().join("stdin_module.rs").is_file());
+
+        // The root module without a file is not written
+        assert!(!out_dir.path().join("mod.munlib").exists());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_split_debug_info() {
+        let out_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            out_dir: Some(out_dir.path().to_path_buf()),
+            debug_info: DebugInfo::Full,
+            split_debug_info: true,
+            ..Config::default()
+        };
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("stdin_module.mun"),
+            contents: "pub fn add(a: i32, b: i32) -> i32 { a + b }".to_owned(),
+        };
+        let (mut driver, file_id) = Driver::with_file(config, input).unwrap();
+        driver.write_file_assemblies(file_id, false).unwrap();
+
+        // The assembly refers to the debug file, which holds the DWARF sections
+        let contains = |haystack: &[u8], needle: &[u8]| {
+            haystack
+                .windows(needle.len())
+                .any(|window| window == needle)
+        };
+        let assembly = std::fs::read(out_dir.path().join("stdin_module.munlib")).unwrap();
+        let debug_info = std::fs::read(out_dir.path().join("stdin_module.debug")).unwrap();
+        assert!(contains(&assembly, b".gnu_debuglink"));
+        assert!(contains(&assembly, b"stdin_module.debug\0"));
+        assert!(!contains(&assembly, b".debug_info"));
+        assert!(contains(&debug_info, b".debug_info"));
+    }
+
+    #[test]
+    fn test_split_debug_info_unsupported() {
+        let target = Target::search("x86_64-pc-windows-msvc").unwrap();
+        assert!(!supports_split_debug_info(&target));
+
+        let out_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            target,
+            out_dir: Some(out_dir.path().to_path_buf()),
+            debug_info: DebugInfo::Full,
+            split_debug_info: true,
+            ..Config::default()
+        };
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("stdin_module.mun"),
+            contents: "pub fn add(a: i32, b: i32) -> i32 { a + b }".to_owned(),
+        };
+        let (mut driver, file_id) = Driver::with_file(config, input).unwrap();
+
+        // The error is reported instead of writing an assembly with the debug
+        // information
+        let err = driver.write_file_assemblies(file_id, false).unwrap_err();
+        assert!(err.to_string().contains("could not split debug info"));
+        assert!(!out_dir.path().join("stdin_module.munlib").exists());
+    }
+
+    #[test]
+    fn test_link_error() {
+        let out_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            target: Target::search("x86_64-pc-windows-msvc").unwrap(),
+            out_dir: Some(out_dir.path().to_path_buf()),
+            output_kind: LinkerOutput::Relocatable,
+            ..Config::default()
+        };
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("stdin_module.mun"),
+            contents: "pub fn add(a: i32, b: i32) -> i32 { a + b }".to_owned(),
+        };
+        let (mut driver, file_id) = Driver::with_file(config, input).unwrap();
+
+        // `lld-link` cannot produce relocatable objects, which is reported
+        // instead of panicking
+        let err = driver.write_file_assemblies(file_id, false).unwrap_err();
+        assert!(err.to_string().contains("could not link object file"));
+        assert!(!out_dir.path().join("stdin_module.munlib").exists());
+    }
+
+    #[test]
+    fn test_code_generation_error() {
+        for emit_ir in [false, true] {
+            let out_dir = tempfile::tempdir().unwrap();
+            let config = Config {
+                out_dir: Some(out_dir.path().to_path_buf()),
+                emit_ir,
+                pgo: Some(ProfileGuidedOptimization::Use(
+                    out_dir.path().join("missing.profdata"),
+                )),
+                ..Config::default()
+            };
+            let input = PathOrInline::Inline {
+                rel_path: RelativePathBuf::from("stdin_module.mun"),
+                contents: "pub fn add(a: i32, b: i32) -> i32 { a + b }".to_owned(),
+            };
+            let (mut driver, file_id) = Driver::with_file(config, input).unwrap();
+
+            let err = driver.write_file_assemblies(file_id, false).unwrap_err();
+            assert!(err.to_string().contains("could not read profile"), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_parallel_build() {
+        let package_dir = tempfile::tempdir().unwrap();
+        let manifest_path = package_dir.path().join("mun.toml");
+        std::fs::write(
+            &manifest_path,
+            "[package]\nname=\"foo\"\nversion=\"0.0.0\"\n",
+        )
+        .unwrap();
+        let source_dir = package_dir.path().join("src");
+        std::fs::create_dir(&source_dir).unwrap();
+        std::fs::write(source_dir.join("mod.mun"), "pub fn main() -> i32 { 0 }").unwrap();
+        for idx in 0..8 {
+            std::fs::write(
+                source_dir.join(format!("foo{idx}.mun")),
+                format!("pub fn foo() -> i32 {{ {idx} }}"),
+            )
+            .unwrap();
+        }
+
+        // The assemblies are linked concurrently
+        let out_dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            out_dir: Some(out_dir.path().to_path_buf()),
+            jobs: NonZeroUsize::new(4),
+            ..Config::default()
+        };
+        let (_package, mut driver) = Driver::with_package_path(&manifest_path, config).unwrap();
+        driver.write_all_assemblies(false).unwrap();
+
+        assert!(out_dir.path().join("mod.munlib").is_file());
+        for idx in 0..8 {
+            assert!(out_dir.path().join(format!("foo{idx}.munlib")).is_file());
+        }
+    }
+
+    #[test]
+    fn test_virtual_file_diagnostics() {
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("stdin_module.mun"),
+            contents: "fn main() { let a: i32 = false; }".to_owned(),
+        };
+        let (driver, _) = Driver::with_file(Config::default(), input).unwrap();
+
+        let diagnostics = driver
+            .emit_diagnostics_to_string(DisplayColor::Disable)
+            .unwrap()
+            .expect("expected a mismatched type error");
+        assert!(diagnostics.contains("stdin_module.mun"));
+    }
+
+    #[test]
+    fn test_file_diagnostics() {
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: "fn main() { let a: i32 = false; }".to_owned(),
+        };
+        let (driver, file_id) = Driver::with_file(Config::default(), input).unwrap();
+
+        let diagnostics = driver.file_diagnostics(file_id);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, 25..30);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_diagnostics_by_file() {
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: "fn main() { let a: i32 = false; let b: bool = 1; }\nfn main() {}".to_owned(),
+        };
+        let config = Config {
+            max_errors_per_file: NonZeroUsize::new(2),
+            ..Config::default()
+        };
+        let (driver, _) = Driver::with_file(config, input).unwrap();
+
+        let files = driver.diagnostics_by_file();
+        assert_eq!(files.len(), 1);
+        assert_eq!(
+            files[0].relative_file_path,
+            RelativePathBuf::from("main.mun")
+        );
+        assert_eq!(files[0].num_omitted_errors, 1);
+
+        // The duplicate definition of `main` is reported first by the compiler, but the
+        // diagnostics are ordered by their position.
+        let ranges: Vec<_> = files[0]
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.range.clone())
+            .collect();
+        assert_eq!(ranges, [25..30, 46..47]);
+    }
+
+    #[test]
+    fn test_clear_caches() {
+        let input = PathOrInline::Inline {
+            rel_path: RelativePathBuf::from("main.mun"),
+            contents: "fn main() { let a: i32 = false; }".to_owned(),
+        };
+        let (mut driver, _) = Driver::with_file(Config::default(), input).unwrap();
+
+        let diagnostics = driver
+            .emit_diagnostics_to_string(DisplayColor::Disable)
+            .unwrap()
+            .expect("expected a mismatched type error");
+        assert!(driver.cache_usage().total() > 0);
+
+        driver.clear_caches();
+        assert_eq!(driver.cache_usage(), CacheUsage::default());
+
+        // Discarded values are recomputed with the same result
+        assert_eq!(
+            driver
+                .emit_diagnostics_to_string(DisplayColor::Disable)
+                .unwrap(),
+            Some(diagnostics)
+        );
+        assert!(driver.cache_usage().total() > 0);
+    }
 }
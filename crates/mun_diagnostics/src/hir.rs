@@ -2,11 +2,18 @@
 //! `crate::Diagnostics`.
 
 mod access_unknown_field;
+mod assign_to_immutable_static;
 mod duplicate_definition_error;
 mod expected_function;
 mod exported_private;
+mod incompatible_comparison;
+mod invalid_cast;
+mod mismatched_break_values;
+mod mismatched_numeric_type;
 mod mismatched_type;
 mod missing_fields;
+mod missing_return_type;
+mod no_such_field;
 mod possibly_unitialized_variable;
 mod unresolved_type;
 mod unresolved_value;
@@ -26,6 +33,17 @@ impl<DB: mun_hir::HirDatabase> DiagnosticForWith<DB> for dyn mun_hir::Diagnostic
             f(&unresolved_type::UnresolvedType::new(with, v))
         } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::ExpectedFunction>() {
             f(&expected_function::ExpectedFunction::new(with, v))
+        } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::MismatchedNumericType>() {
+            f(&mismatched_numeric_type::MismatchedNumericType::new(
+                with, v,
+            ))
+        } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::AssignToImmutableStatic>()
+        {
+            f(&assign_to_immutable_static::AssignToImmutableStatic::new(
+                with, v,
+            ))
+        } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::InvalidCast>() {
+            f(&invalid_cast::InvalidCast::new(with, v))
         } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::MismatchedType>() {
             f(&mismatched_type::MismatchedType::new(with, v))
         } else if let Some(v) =
@@ -40,8 +58,21 @@ impl<DB: mun_hir::HirDatabase> DiagnosticForWith<DB> for dyn mun_hir::Diagnostic
             ))
         } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::MissingFields>() {
             f(&missing_fields::MissingFields::new(with, v))
+        } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::MissingReturnType>() {
+            f(&missing_return_type::MissingReturnType::new(with, v))
+        } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::NoSuchField>() {
+            f(&no_such_field::NoSuchField::new(with, v))
         } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::ExportedPrivate>() {
             f(&exported_private::ExportedPrivate::new(with, v))
+        } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::IncompatibleComparison>()
+        {
+            f(&incompatible_comparison::IncompatibleComparison::new(
+                with, v,
+            ))
+        } else if let Some(v) = self.downcast_ref::<mun_hir::diagnostics::MismatchedBreakValues>() {
+            f(&mismatched_break_values::MismatchedBreakValues::new(
+                with, v,
+            ))
         } else {
             f(&GenericHirDiagnostic { diagnostic: self })
         }
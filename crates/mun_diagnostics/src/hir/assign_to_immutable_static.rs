@@ -0,0 +1,70 @@
+use mun_hir::InFile;
+use mun_syntax::TextRange;
+
+use super::HirDiagnostic;
+use crate::{Diagnostic, SecondaryAnnotation, SourceAnnotation, Suggestion};
+
+/// An error that is emitted when a function-local static that is not declared
+/// `mut` is assigned to. Adding `mut` to the declaration of the static fixes
+/// the error.
+///
+/// ```mun
+/// fn counter() -> i64 {
+///     static N: i64 = 0;
+///     N += 1; // cannot assign to immutable static `N`
+///     N
+/// }
+/// ```
+pub struct AssignToImmutableStatic<'db, 'diag, DB: mun_hir::HirDatabase> {
+    _db: &'db DB,
+    diag: &'diag mun_hir::diagnostics::AssignToImmutableStatic,
+    name: String,
+    binding_range: TextRange,
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> Diagnostic for AssignToImmutableStatic<'db, 'diag, DB> {
+    fn range(&self) -> TextRange {
+        self.diag.highlight_range()
+    }
+
+    fn title(&self) -> String {
+        format!("cannot assign to immutable static `{}`", self.name)
+    }
+
+    fn primary_annotation(&self) -> Option<SourceAnnotation> {
+        Some(SourceAnnotation {
+            range: self.diag.highlight_range(),
+            message: "cannot assign to immutable static".to_owned(),
+        })
+    }
+
+    fn secondary_annotations(&self) -> Vec<SecondaryAnnotation> {
+        vec![SecondaryAnnotation {
+            range: InFile::new(self.diag.file, self.binding_range),
+            message: format!("static `{}` declared here", self.name),
+        }]
+    }
+
+    fn suggestions(&self) -> Vec<Suggestion> {
+        vec![Suggestion {
+            message: "consider making this static mutable".to_owned(),
+            range: TextRange::empty(self.binding_range.start()),
+            replacement: "mut ".to_owned(),
+        }]
+    }
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> AssignToImmutableStatic<'db, 'diag, DB> {
+    /// Constructs a new instance of `AssignToImmutableStatic`
+    pub fn new(db: &'db DB, diag: &'diag mun_hir::diagnostics::AssignToImmutableStatic) -> Self {
+        let parse = db.parse(diag.file);
+        let binding = diag.binding.to_node(&parse.syntax_node());
+
+        AssignToImmutableStatic {
+            _db: db,
+            diag,
+            name: binding.text().to_string(),
+            binding_range: binding.text_range(),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use mun_hir::{HirDisplay, InFile, TyKind};
+use mun_syntax::TextRange;
+
+use super::HirDiagnostic;
+use crate::{Diagnostic, SecondaryAnnotation, SourceAnnotation};
+
+/// An error that is emitted when values of incompatible types are compared, or
+/// when values are compared with an operator their type does not support.
+///
+/// ```mun
+/// fn foo(a: i32, b: bool) -> bool {
+///     a == b  // cannot compare `i32` with `bool` using `==`
+/// }
+///
+/// fn bar(a: bool, b: bool) -> bool {
+///     a < b   // cannot compare `bool` with `bool` using `<`
+/// }
+/// ```
+pub struct IncompatibleComparison<'db, 'diag, DB: mun_hir::HirDatabase> {
+    db: &'db DB,
+    diag: &'diag mun_hir::diagnostics::IncompatibleComparison,
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> Diagnostic for IncompatibleComparison<'db, 'diag, DB> {
+    fn range(&self) -> TextRange {
+        self.diag.highlight_range()
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "cannot compare `{}` with `{}` using `{}`",
+            self.diag.lhs_ty.display(self.db),
+            self.diag.rhs_ty.display(self.db),
+            self.diag.op
+        )
+    }
+
+    fn primary_annotation(&self) -> Option<SourceAnnotation> {
+        None
+    }
+
+    fn secondary_annotations(&self) -> Vec<SecondaryAnnotation> {
+        [
+            (&self.diag.lhs, &self.diag.lhs_ty),
+            (&self.diag.rhs, &self.diag.rhs_ty),
+        ]
+        .into_iter()
+        .map(|(ptr, ty)| SecondaryAnnotation {
+            range: InFile::new(self.diag.file, ptr.range()),
+            message: format!("this is of type `{}`", ty.display(self.db)),
+        })
+        .collect()
+    }
+
+    fn footer(&self) -> Vec<String> {
+        if matches!(self.diag.lhs_ty.interned(), TyKind::Bool)
+            && matches!(self.diag.rhs_ty.interned(), TyKind::Bool)
+        {
+            vec!["values of type `bool` can only be compared with `==` and `!=`".to_owned()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> IncompatibleComparison<'db, 'diag, DB> {
+    /// Constructs a new instance of `IncompatibleComparison`
+    pub fn new(db: &'db DB, diag: &'diag mun_hir::diagnostics::IncompatibleComparison) -> Self {
+        IncompatibleComparison { db, diag }
+    }
+}
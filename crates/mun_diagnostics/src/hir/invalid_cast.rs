@@ -0,0 +1,63 @@
+use mun_hir::{HirDisplay, InFile};
+use mun_syntax::{ast, AstNode, TextRange};
+
+use super::HirDiagnostic;
+use crate::{Diagnostic, SecondaryAnnotation, SourceAnnotation};
+
+/// An error that is emitted when a value is cast to a type it cannot be
+/// converted to. Only numeric values can be cast, to other numeric types.
+///
+/// ```mun
+/// # fn main() {
+/// let a = true as i32; // cannot cast `bool` as `i32`
+/// # }
+/// ```
+pub struct InvalidCast<'db, 'diag, DB: mun_hir::HirDatabase> {
+    db: &'db DB,
+    diag: &'diag mun_hir::diagnostics::InvalidCast,
+    operand: Option<TextRange>,
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> Diagnostic for InvalidCast<'db, 'diag, DB> {
+    fn range(&self) -> TextRange {
+        self.diag.highlight_range()
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "cannot cast `{}` as `{}`",
+            self.diag.expr_ty.display(self.db),
+            self.diag.cast_ty.display(self.db)
+        )
+    }
+
+    fn primary_annotation(&self) -> Option<SourceAnnotation> {
+        None
+    }
+
+    fn secondary_annotations(&self) -> Vec<SecondaryAnnotation> {
+        self.operand
+            .map(|range| SecondaryAnnotation {
+                range: InFile::new(self.diag.file, range),
+                message: format!("this is of type `{}`", self.diag.expr_ty.display(self.db)),
+            })
+            .into_iter()
+            .collect()
+    }
+
+    fn footer(&self) -> Vec<String> {
+        vec!["only numeric values can be cast to other numeric types".to_owned()]
+    }
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> InvalidCast<'db, 'diag, DB> {
+    /// Constructs a new instance of `InvalidCast`
+    pub fn new(db: &'db DB, diag: &'diag mun_hir::diagnostics::InvalidCast) -> Self {
+        let parse = db.parse(diag.file);
+        let operand = ast::CastExpr::cast(diag.expr.to_node(&parse.syntax_node()))
+            .and_then(|cast| cast.expr())
+            .map(|expr| expr.syntax().text_range());
+
+        InvalidCast { db, diag, operand }
+    }
+}
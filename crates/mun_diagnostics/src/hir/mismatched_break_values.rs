@@ -0,0 +1,60 @@
+use mun_hir::{HirDisplay, InFile};
+use mun_syntax::TextRange;
+
+use super::HirDiagnostic;
+use crate::{Diagnostic, SecondaryAnnotation, SourceAnnotation};
+
+/// An error that is emitted when the `break` expressions of a `loop` don't
+/// agree on the value of the loop.
+///
+/// ```mun
+/// fn foo(a: bool) -> i32 {
+///     loop {
+///         if a {
+///             break 5
+///         }
+///         break   // `break` with and without a value in the same `loop`
+///     }
+/// }
+/// ```
+pub struct MismatchedBreakValues<'db, 'diag, DB: mun_hir::HirDatabase> {
+    db: &'db DB,
+    diag: &'diag mun_hir::diagnostics::MismatchedBreakValues,
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> Diagnostic for MismatchedBreakValues<'db, 'diag, DB> {
+    fn range(&self) -> TextRange {
+        self.diag.highlight_range()
+    }
+
+    fn title(&self) -> String {
+        self.diag.message()
+    }
+
+    fn primary_annotation(&self) -> Option<SourceAnnotation> {
+        None
+    }
+
+    fn secondary_annotations(&self) -> Vec<SecondaryAnnotation> {
+        self.diag
+            .breaks
+            .iter()
+            .map(|(ptr, ty)| SecondaryAnnotation {
+                range: InFile::new(self.diag.file, ptr.range()),
+                message: match ty {
+                    Some(ty) => {
+                        format!("this breaks with a value of type `{}`", ty.display(self.db))
+                    }
+                    None => "this breaks without a value".to_owned(),
+                },
+            })
+            .collect()
+    }
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> MismatchedBreakValues<'db, 'diag, DB> {
+    /// Constructs a new instance of `MismatchedBreakValues`
+    pub fn new(db: &'db DB, diag: &'diag mun_hir::diagnostics::MismatchedBreakValues) -> Self {
+        MismatchedBreakValues { db, diag }
+    }
+}
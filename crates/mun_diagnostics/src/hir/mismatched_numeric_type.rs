@@ -0,0 +1,108 @@
+use mun_hir::HirDisplay;
+use mun_syntax::{ast, AstNode, SyntaxKind, TextRange};
+
+use super::HirDiagnostic;
+use crate::{Diagnostic, SourceAnnotation, Suggestion};
+
+/// An error that is emitted when a numeric value of one type is used where a
+/// numeric value of another type is expected. Numeric values are never
+/// converted implicitly, not even when the conversion is lossless.
+///
+/// ```mun
+/// fn foo(a: i64, b: u8) {
+///     let x: i32 = a; // expected `i32`, found `i64`, the value may be truncated
+///     let y: i64 = b; // expected `i64`, found `u8`, an explicit cast is required
+/// }
+/// ```
+pub struct MismatchedNumericType<'db, 'diag, DB: mun_hir::HirDatabase> {
+    db: &'db DB,
+    diag: &'diag mun_hir::diagnostics::MismatchedNumericType,
+    suggestion: Option<Suggestion>,
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> Diagnostic for MismatchedNumericType<'db, 'diag, DB> {
+    fn range(&self) -> TextRange {
+        self.diag.highlight_range()
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "expected `{}`, found `{}`",
+            self.diag.expected.display(self.db),
+            self.diag.found.display(self.db)
+        )
+    }
+
+    fn primary_annotation(&self) -> Option<SourceAnnotation> {
+        let message = if self.diag.is_narrowing {
+            format!(
+                "converting `{}` to `{}` may lose information",
+                self.diag.found.display(self.db),
+                self.diag.expected.display(self.db)
+            )
+        } else {
+            "numeric types are not converted implicitly".to_owned()
+        };
+        Some(SourceAnnotation {
+            range: self.diag.highlight_range(),
+            message,
+        })
+    }
+
+    fn suggestions(&self) -> Vec<Suggestion> {
+        self.suggestion.iter().cloned().collect()
+    }
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> MismatchedNumericType<'db, 'diag, DB> {
+    /// Constructs a new instance of `MismatchedNumericType`
+    pub fn new(db: &'db DB, diag: &'diag mun_hir::diagnostics::MismatchedNumericType) -> Self {
+        let parse = db.parse(diag.file);
+        let node = diag.expr.to_node(&parse.syntax_node());
+        let ty = diag.expected.display(db).to_string();
+
+        // A field shorthand (e.g. `Foo { a }`) refers to the field itself, the
+        // cast has to be written out as a full field initializer.
+        let replacement = if let Some(field) = ast::RecordField::cast(node.clone()) {
+            field
+                .name_ref()
+                .map(|name| format!("{name}: {name} as {ty}", name = name.syntax().text()))
+        } else if needs_parentheses(node.kind()) {
+            Some(format!("({}) as {ty}", node.text()))
+        } else {
+            Some(format!("{} as {ty}", node.text()))
+        };
+
+        let suggestion = replacement.map(|replacement| Suggestion {
+            message: if diag.is_narrowing {
+                format!("if truncating the value is intended, cast it to `{ty}`")
+            } else {
+                format!("cast the value to `{ty}`")
+            },
+            range: node.text_range(),
+            replacement,
+        });
+
+        MismatchedNumericType {
+            db,
+            diag,
+            suggestion,
+        }
+    }
+}
+
+/// Returns true if an expression of the specified kind binds less tightly than
+/// `as` and therefore has to be parenthesized before it can be cast.
+fn needs_parentheses(kind: SyntaxKind) -> bool {
+    !matches!(
+        kind,
+        SyntaxKind::PATH_EXPR
+            | SyntaxKind::LITERAL
+            | SyntaxKind::PAREN_EXPR
+            | SyntaxKind::CALL_EXPR
+            | SyntaxKind::FIELD_EXPR
+            | SyntaxKind::INDEX_EXPR
+            | SyntaxKind::PREFIX_EXPR
+            | SyntaxKind::CAST_EXPR
+    )
+}
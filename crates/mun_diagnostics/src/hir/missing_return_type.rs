@@ -0,0 +1,52 @@
+use mun_hir::HirDisplay;
+use mun_syntax::TextRange;
+
+use super::HirDiagnostic;
+use crate::{Diagnostic, SourceAnnotation};
+
+/// An error that is emitted when a function that does not specify a return
+/// type returns a value. Without a return type a function returns `()`.
+///
+/// ```mun
+/// fn foo() {
+///     5   // expected `()`, found `i32`
+/// }
+/// ```
+pub struct MissingReturnType<'db, 'diag, DB: mun_hir::HirDatabase> {
+    db: &'db DB,
+    diag: &'diag mun_hir::diagnostics::MissingReturnType,
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> Diagnostic for MissingReturnType<'db, 'diag, DB> {
+    fn range(&self) -> TextRange {
+        self.diag.highlight_range()
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "expected `()`, found `{}`",
+            self.diag.found.display(self.db)
+        )
+    }
+
+    fn primary_annotation(&self) -> Option<SourceAnnotation> {
+        Some(SourceAnnotation {
+            range: self.diag.highlight_range(),
+            message: "the function has no return type, so it returns `()`".to_owned(),
+        })
+    }
+
+    fn footer(&self) -> Vec<String> {
+        vec![format!(
+            "consider adding a return type: `-> {}`",
+            self.diag.found.display(self.db)
+        )]
+    }
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> MissingReturnType<'db, 'diag, DB> {
+    /// Constructs a new instance of `MissingReturnType`
+    pub fn new(db: &'db DB, diag: &'diag mun_hir::diagnostics::MissingReturnType) -> Self {
+        MissingReturnType { db, diag }
+    }
+}
@@ -0,0 +1,57 @@
+use mun_hir::HirDisplay;
+use mun_syntax::{ast, AstNode, TextRange};
+
+use super::HirDiagnostic;
+use crate::{Diagnostic, SourceAnnotation};
+
+/// An error that is emitted when a struct initializer specifies a field that
+/// doesn't exist.
+///
+/// ```mun
+/// struct Foo {
+///     a: i32,
+/// }
+///
+/// # fn main() {
+///     let a = Foo { a: 3, b: 4 }; // no field `b`
+/// # }
+/// ```
+pub struct NoSuchField<'db, 'diag, DB: mun_hir::HirDatabase> {
+    db: &'db DB,
+    diag: &'diag mun_hir::diagnostics::NoSuchField,
+    location: TextRange,
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> Diagnostic for NoSuchField<'db, 'diag, DB> {
+    fn range(&self) -> TextRange {
+        self.location
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "struct `{}` has no field named `{}`",
+            self.diag.struct_ty.display(self.db),
+            self.diag.name,
+        )
+    }
+
+    fn primary_annotation(&self) -> Option<SourceAnnotation> {
+        Some(SourceAnnotation {
+            range: self.location,
+            message: "unknown field".to_string(),
+        })
+    }
+}
+
+impl<'db, 'diag, DB: mun_hir::HirDatabase> NoSuchField<'db, 'diag, DB> {
+    /// Constructs a new instance of `NoSuchField`
+    pub fn new(db: &'db DB, diag: &'diag mun_hir::diagnostics::NoSuchField) -> Self {
+        let parse = db.parse(diag.file);
+
+        let location = ast::RecordField::cast(diag.field.to_node(&parse.syntax_node()))
+            .and_then(|f| f.name_ref())
+            .map_or_else(|| diag.highlight_range(), |n| n.syntax().text_range());
+
+        NoSuchField { db, diag, location }
+    }
+}
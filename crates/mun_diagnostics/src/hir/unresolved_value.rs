@@ -11,6 +11,9 @@ use crate::{Diagnostic, SourceAnnotation};
 /// let a = b; // Cannot find `b` in this scope.
 /// #}
 /// ```
+///
+/// If a value with a similar name is in scope, it is suggested as an
+/// alternative.
 pub struct UnresolvedValue<'db, 'diag, DB: mun_hir::HirDatabase> {
     _db: &'db DB,
     diag: &'diag mun_hir::diagnostics::UnresolvedValue,
@@ -32,6 +35,14 @@ impl<'db, 'diag, DB: mun_hir::HirDatabase> Diagnostic for UnresolvedValue<'db, '
             message: "not found in this scope".to_owned(),
         })
     }
+
+    fn footer(&self) -> Vec<String> {
+        self.diag
+            .suggestion
+            .iter()
+            .map(|name| format!("a value with a similar name exists: `{name}`"))
+            .collect()
+    }
 }
 
 impl<'db, 'diag, DB: mun_hir::HirDatabase> UnresolvedValue<'db, 'diag, DB> {
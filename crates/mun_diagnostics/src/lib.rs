@@ -32,6 +32,20 @@ pub struct SecondaryAnnotation {
     pub message: String,
 }
 
+/// A machine-applicable edit that resolves a diagnostic by replacing the text
+/// at `range` with `replacement`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Suggestion {
+    /// A human-readable description of the edit
+    pub message: String,
+
+    /// The location in the source that is replaced
+    pub range: TextRange,
+
+    /// The text that replaces the source at `range`
+    pub replacement: String,
+}
+
 /// The base trait for all diagnostics in this crate.
 pub trait Diagnostic {
     /// Returns the primary message of the diagnostic.
@@ -54,6 +68,11 @@ pub trait Diagnostic {
     fn footer(&self) -> Vec<String> {
         Vec::new()
     }
+
+    /// Returns edits that can be applied to the source to fix the diagnostic.
+    fn suggestions(&self) -> Vec<Suggestion> {
+        Vec::new()
+    }
 }
 
 /// When implemented enables requesting `Diagnostic`s for the implementer.
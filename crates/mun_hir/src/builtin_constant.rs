@@ -0,0 +1,59 @@
+use mun_target::abi::{Endian, TargetDataLayout};
+
+use crate::{
+    const_eval::ConstValue,
+    name::{name, Name},
+    IntTy, Ty, TyKind,
+};
+
+/// A constant that is defined by the compiler. The value of a builtin constant
+/// depends on the target that is compiled for, which enables scripts to branch
+/// on target properties. Because the value is known at compile time, unused
+/// branches are eliminated during optimization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuiltinConstant {
+    /// `true` if the target stores multi-byte integers least significant byte
+    /// first.
+    TargetLittleEndian,
+    /// `true` if the target stores multi-byte integers most significant byte
+    /// first.
+    TargetBigEndian,
+    /// The size of a pointer on the target in bits, which is also the size
+    /// of `usize` and `isize`.
+    PointerWidth,
+}
+
+impl BuiltinConstant {
+    pub const ALL: &'static [(Name, BuiltinConstant)] = &[
+        (
+            name![TARGET_LITTLE_ENDIAN],
+            BuiltinConstant::TargetLittleEndian,
+        ),
+        (name![TARGET_BIG_ENDIAN], BuiltinConstant::TargetBigEndian),
+        (name![POINTER_WIDTH], BuiltinConstant::PointerWidth),
+    ];
+
+    /// Returns the type of the constant
+    pub fn ty(self) -> Ty {
+        match self {
+            BuiltinConstant::TargetLittleEndian | BuiltinConstant::TargetBigEndian => {
+                TyKind::Bool.intern()
+            }
+            BuiltinConstant::PointerWidth => TyKind::Int(IntTy::u32()).intern(),
+        }
+    }
+
+    /// Returns the value of the constant for the target described by
+    /// `data_layout`.
+    pub fn value(self, data_layout: &TargetDataLayout) -> ConstValue {
+        match self {
+            BuiltinConstant::TargetLittleEndian => {
+                ConstValue::Bool(data_layout.endian == Endian::Little)
+            }
+            BuiltinConstant::TargetBigEndian => ConstValue::Bool(data_layout.endian == Endian::Big),
+            BuiltinConstant::PointerWidth => {
+                ConstValue::Int(i128::from(data_layout.pointer_size.bits()))
+            }
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! The call graph describes which functions reference each other. It is used
+//! to find the functions that can never be called, because they are not
+//! reachable from any function that is exported from a package.
+
+use std::sync::Arc;
+
+use mun_syntax::{ast::NameOwner, AstNode, SyntaxNodePtr};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    code_model::{AssocItem, Impl},
+    diagnostics::{DiagnosticSink, UnusedFunction},
+    CallableDef, Expr, Function, HasSource, HasVisibility, HirDatabase, Module, ModuleDef, Package,
+};
+
+/// The functions that every function references, either by calling them or by
+/// using them as a value, e.g. to pass them as a function pointer. A function
+/// is *live* if it is reachable from an entry point: a function that is
+/// exported from its package, either because it is `pub`, because it is
+/// `#[no_mangle]`, because it is invoked by name through `#[reflect]` or
+/// because the runtime calls it as an `#[init]` function.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CallGraph {
+    callees: FxHashMap<Function, Vec<Function>>,
+    live: FxHashSet<Function>,
+}
+
+impl CallGraph {
+    pub(crate) fn call_graph_query(db: &dyn HirDatabase) -> Arc<CallGraph> {
+        let mut functions = Vec::new();
+        for package in Package::all(db) {
+            for module in package.modules(db) {
+                functions.extend(
+                    module
+                        .declarations(db)
+                        .into_iter()
+                        .filter_map(|def| match def {
+                            ModuleDef::Function(function) => Some(function),
+                            _ => None,
+                        }),
+                );
+            }
+            for impl_ in Impl::all_in_package(db, package) {
+                functions.extend(impl_.items(db).into_iter().map(|item| match item {
+                    AssocItem::Function(function) => function,
+                }));
+            }
+        }
+
+        let mut callees = FxHashMap::default();
+        let mut worklist = Vec::new();
+        for function in functions {
+            if function.visibility(db).is_externally_visible()
+                || function.is_no_mangle(db)
+                || function.is_reflect(db)
+                || function.is_init(db)
+            {
+                worklist.push(function);
+            }
+            callees.insert(function, collect_callees(db, function));
+        }
+
+        // Walk the graph from the entry points. Recursive functions are only
+        // visited once.
+        let mut live = FxHashSet::default();
+        while let Some(function) = worklist.pop() {
+            if live.insert(function) {
+                worklist.extend(callees.get(&function).into_iter().flatten().copied());
+            }
+        }
+
+        Arc::new(CallGraph { callees, live })
+    }
+
+    /// Returns the functions that are referenced from the body of `function`.
+    pub fn callees(&self, function: Function) -> &[Function] {
+        self.callees.get(&function).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns true if `function` is reachable from an entry point.
+    pub fn is_live(&self, function: Function) -> bool {
+        self.live.contains(&function)
+    }
+}
+
+/// Returns the functions that are referenced by path expressions in the body of
+/// `function`. Both the callee of a call and a function that is used as a
+/// value are path expressions.
+fn collect_callees(db: &dyn HirDatabase, function: Function) -> Vec<Function> {
+    if function.is_extern(db) {
+        return Vec::new();
+    }
+
+    let body = function.body(db);
+    let infer = function.infer(db);
+    let mut callees = Vec::new();
+    for (expr_id, expr) in body.exprs() {
+        if let Expr::Path(_) = expr {
+            if let Some(CallableDef::Function(callee)) = infer[expr_id].as_callable_def() {
+                if !callees.contains(&callee) {
+                    callees.push(callee);
+                }
+            }
+        }
+    }
+    callees
+}
+
+impl Module {
+    /// Reports the functions of this module that are not live, see
+    /// [`CallGraph`]. These functions are never called and can be removed.
+    /// Functions that are marked with `#[allow(dead_code)]` are not reported.
+    pub fn unused_function_diagnostics(self, db: &dyn HirDatabase, sink: &mut DiagnosticSink<'_>) {
+        let call_graph = db.call_graph();
+        for decl in self.declarations(db) {
+            let ModuleDef::Function(function) = decl else {
+                continue;
+            };
+            if function.is_extern(db)
+                || call_graph.is_live(function)
+                || function
+                    .data(db.upcast())
+                    .is_lint_allowed(UnusedFunction::LINT_NAME)
+            {
+                continue;
+            }
+
+            let source = function.source(db.upcast());
+            sink.push(UnusedFunction {
+                name: function.name(db).to_string(),
+                definition: source.map(|src| {
+                    src.name().map_or_else(
+                        || SyntaxNodePtr::new(src.syntax()),
+                        |name| SyntaxNodePtr::new(name.syntax()),
+                    )
+                }),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        diagnostics::DiagnosticSink, mock::MockDatabase, with_fixture::WithFixture, HirDatabase,
+        ModuleDef, Package,
+    };
+
+    #[test]
+    fn unused_functions() {
+        let (db, _file_id) = MockDatabase::with_single_file(
+            r#"
+        pub fn main() -> i32 { fib(10) + helper() }
+
+        fn fib(n: i32) -> i32 { if n <= 1 { n } else { fib(n - 1) + fib(n - 2) } }
+        fn helper() -> i32 { 1 }
+
+        fn ping() { pong() }
+        fn pong() { ping() }
+
+        #[allow(dead_code)]
+        fn reserved() {}
+
+        #[no_mangle]
+        fn exported() {}
+
+        #[reflect]
+        fn reflected() -> i32 { helper() }
+        "#,
+        );
+
+        let call_graph = db.call_graph();
+        let module = Package::all(&db)[0].modules(&db)[0];
+        let main = module
+            .declarations(&db)
+            .into_iter()
+            .find_map(|def| match def {
+                ModuleDef::Function(f) if f.name(&db).to_string() == "main" => Some(f),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(call_graph.callees(main).len(), 2);
+
+        let mut messages = Vec::new();
+        module.unused_function_diagnostics(
+            &db,
+            &mut DiagnosticSink::new(|diag| messages.push(diag.message())),
+        );
+        messages.sort();
+        assert_eq!(
+            messages,
+            [
+                "function `ping` is never used",
+                "function `pong` is never used"
+            ]
+        );
+    }
+}
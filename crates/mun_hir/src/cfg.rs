@@ -0,0 +1,147 @@
+//! Conditional compilation of items with `#[cfg(..)]` attributes. An item is
+//! only kept if the predicates of all its `cfg` attributes hold for the target
+//! that is compiled for.
+
+use std::collections::HashSet;
+
+use mun_syntax::ast::{self, ArgListOwner, AstToken};
+
+/// The name of the attribute that conditionally compiles an item.
+pub(crate) const CFG_ATTR_NAME: &str = "cfg";
+
+/// The set of features that are enabled for a target.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct TargetFeatures {
+    enabled: HashSet<String>,
+}
+
+impl TargetFeatures {
+    /// Parses a feature string the way LLVM interprets it when it is passed to
+    /// the target machine, e.g. `+avx2,-sse4.1`. Features that are prefixed
+    /// with `-` are disabled, all others are enabled. A later occurrence of a
+    /// feature overrides an earlier one.
+    pub fn parse(features: &str) -> Self {
+        let mut enabled = HashSet::new();
+        for feature in features.split(',').map(str::trim) {
+            if let Some(feature) = feature.strip_prefix('-') {
+                enabled.remove(feature);
+            } else {
+                let feature = feature.strip_prefix('+').unwrap_or(feature);
+                if !feature.is_empty() {
+                    enabled.insert(feature.to_owned());
+                }
+            }
+        }
+        TargetFeatures { enabled }
+    }
+
+    /// Returns true if the specified feature is enabled.
+    pub fn is_enabled(&self, feature: &str) -> bool {
+        self.enabled.contains(feature)
+    }
+}
+
+/// A predicate of a `cfg` attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum CfgExpr {
+    /// `target_feature = "avx2"`
+    TargetFeature(String),
+    /// `not(predicate)`
+    Not(Box<CfgExpr>),
+    /// `all(predicate, ..)`
+    All(Vec<CfgExpr>),
+    /// `any(predicate, ..)`
+    Any(Vec<CfgExpr>),
+}
+
+impl CfgExpr {
+    /// Parses the predicate of a `#[cfg(predicate)]` attribute. Returns `None`
+    /// if the attribute does not contain exactly one valid predicate.
+    pub fn from_attr(attr: &ast::Attr) -> Option<Self> {
+        let mut args = attr.arg_list()?.args();
+        match (args.next(), args.next()) {
+            (Some(arg), None) => Self::from_expr(&arg),
+            _ => None,
+        }
+    }
+
+    fn from_expr(expr: &ast::Expr) -> Option<Self> {
+        match expr.kind() {
+            ast::ExprKind::BinExpr(expr) if expr.op_kind() == Some(ast::BinOp::Assign) => {
+                let (Some(lhs), Some(rhs)) = expr.sub_exprs() else {
+                    return None;
+                };
+                if path_expr_name(&lhs)?.as_str() != "target_feature" {
+                    return None;
+                }
+                match rhs.kind() {
+                    ast::ExprKind::Literal(lit) => match lit.kind() {
+                        ast::LiteralKind::String(string) => {
+                            let text = string.text();
+                            let value = text.strip_prefix('"')?.strip_suffix('"')?;
+                            Some(CfgExpr::TargetFeature(value.to_owned()))
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            }
+            ast::ExprKind::CallExpr(call) => {
+                let callee = path_expr_name(&call.expr()?)?;
+                let args = call
+                    .arg_list()?
+                    .args()
+                    .map(|arg| Self::from_expr(&arg))
+                    .collect::<Option<Vec<_>>>()?;
+                match callee.as_str() {
+                    "not" => {
+                        let [arg] = <[CfgExpr; 1]>::try_from(args).ok()?;
+                        Some(CfgExpr::Not(Box::new(arg)))
+                    }
+                    "all" => Some(CfgExpr::All(args)),
+                    "any" => Some(CfgExpr::Any(args)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if the predicate holds for the specified target features.
+    pub fn eval(&self, features: &TargetFeatures) -> bool {
+        match self {
+            CfgExpr::TargetFeature(feature) => features.is_enabled(feature),
+            CfgExpr::Not(expr) => !expr.eval(features),
+            CfgExpr::All(exprs) => exprs.iter().all(|expr| expr.eval(features)),
+            CfgExpr::Any(exprs) => exprs.iter().any(|expr| expr.eval(features)),
+        }
+    }
+}
+
+/// Returns the name of a path expression that consists of a single identifier.
+fn path_expr_name(expr: &ast::Expr) -> Option<String> {
+    match expr.kind() {
+        ast::ExprKind::PathExpr(path_expr) => {
+            let path = path_expr.path()?;
+            if path.qualifier().is_some() {
+                return None;
+            }
+            Some(String::from(path.segment()?.name_ref()?.text()))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TargetFeatures;
+
+    #[test]
+    fn parse_target_features() {
+        let features = TargetFeatures::parse("+avx2, +sse4.1,-sse4.1,fma,+");
+        assert!(features.is_enabled("avx2"));
+        assert!(!features.is_enabled("sse4.1"));
+        assert!(features.is_enabled("fma"));
+        assert!(!features.is_enabled(""));
+    }
+}
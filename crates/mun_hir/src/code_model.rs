@@ -1,5 +1,7 @@
+mod r#const;
 mod function;
 mod r#impl;
+pub(crate) mod link_section;
 mod module;
 mod package;
 pub(crate) mod src;
@@ -9,33 +11,45 @@ mod type_alias;
 use std::sync::Arc;
 
 pub use self::{
-    function::{Function, FunctionData},
+    function::{Function, FunctionData, InlineAttr, OptimizeAttr},
+    link_section::LinkSection,
     module::{Module, ModuleDef},
     package::Package,
+    r#const::{Const, ConstData},
     r#impl::{AssocItem, Impl, ImplData},
     r#struct::{Field, Struct, StructData, StructKind, StructMemoryKind},
     src::HasSource,
     type_alias::{TypeAlias, TypeAliasData},
 };
-use crate::{expr::BodySourceMap, HirDatabase, Name};
+use crate::{expr::BodySourceMap, FileId, HirDatabase, Name};
 
 /// The definitions that have a body.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DefWithBody {
     Function(Function),
+    Const(Const),
 }
-impl_froms!(DefWithBody: Function);
+impl_froms!(DefWithBody: Function, Const);
 
 impl DefWithBody {
     pub fn module(self, db: &dyn HirDatabase) -> Module {
         match self {
             DefWithBody::Function(f) => f.module(db),
+            DefWithBody::Const(c) => c.module(db),
+        }
+    }
+
+    pub fn file_id(self, db: &dyn HirDatabase) -> FileId {
+        match self {
+            DefWithBody::Function(f) => f.file_id(db),
+            DefWithBody::Const(c) => c.file_id(db),
         }
     }
 
     pub fn body_source_map(self, db: &dyn HirDatabase) -> Arc<BodySourceMap> {
         match self {
             DefWithBody::Function(f) => f.body_source_map(db),
+            DefWithBody::Const(c) => c.body_source_map(db),
         }
     }
 }
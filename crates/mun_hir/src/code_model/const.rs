@@ -0,0 +1,182 @@
+use std::{iter::once, sync::Arc};
+
+use mun_syntax::{ast::TypeAscriptionOwner, AstNode, SyntaxNodePtr};
+
+use super::{link_section, LinkSection, Module};
+use crate::{
+    const_eval::{ConstEvalError, ConstValue},
+    diagnostics::{ConstEvalFailed, InvalidConstType},
+    expr::BodySourceMap,
+    has_module::HasModule,
+    ids::{ConstId, Lookup},
+    resolve::HasResolver,
+    type_ref::{LocalTypeRefId, TypeRefMap},
+    visibility::RawVisibility,
+    Body, DefDatabase, DiagnosticSink, FileId, HasSource, HasVisibility, HirDatabase,
+    InferenceResult, Name, Ty, TyKind, Visibility,
+};
+
+/// A constant defined in a module, e.g. `const MAX: i64 = 10;`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Const {
+    pub(crate) id: ConstId,
+}
+
+impl From<ConstId> for Const {
+    fn from(id: ConstId) -> Self {
+        Const { id }
+    }
+}
+
+impl Const {
+    pub fn module(self, db: &dyn HirDatabase) -> Module {
+        self.id.module(db.upcast()).into()
+    }
+
+    pub fn file_id(self, db: &dyn HirDatabase) -> FileId {
+        self.id.lookup(db.upcast()).id.file_id
+    }
+
+    pub fn data(self, db: &dyn DefDatabase) -> Arc<ConstData> {
+        db.const_data(self.id)
+    }
+
+    pub fn name(self, db: &dyn HirDatabase) -> Name {
+        self.data(db.upcast()).name.clone()
+    }
+
+    /// Returns the full name of the constant, including the path of the
+    /// module it is defined in, e.g. `foo::MAX`.
+    pub fn full_name(self, db: &dyn HirDatabase) -> String {
+        itertools::Itertools::intersperse(
+            self.module(db)
+                .path_to_root(db)
+                .into_iter()
+                .filter_map(|module| module.name(db))
+                .chain(once(self.name(db)))
+                .map(|name| name.to_string()),
+            String::from("::"),
+        )
+        .collect()
+    }
+
+    /// Returns the declared type of the constant.
+    pub fn ty(self, db: &dyn HirDatabase) -> Ty {
+        let data = self.data(db.upcast());
+        Ty::from_hir(
+            db,
+            &self.id.resolver(db.upcast()),
+            data.type_ref_map(),
+            data.type_ref_id,
+        )
+        .0
+    }
+
+    /// Returns the name of the section in which the value of the constant is
+    /// placed by a `#[link_section]` attribute, if any.
+    pub fn link_section(self, db: &dyn HirDatabase) -> Option<String> {
+        LinkSection::section_name(&self.data(db.upcast()).link_sections).map(ToOwned::to_owned)
+    }
+
+    /// Returns the value of the constant, which is evaluated at compile time.
+    pub fn value(self, db: &dyn HirDatabase) -> Result<ConstValue, ConstEvalError> {
+        db.const_eval(self)
+    }
+
+    pub fn body(self, db: &dyn HirDatabase) -> Arc<Body> {
+        db.body(self.id.into())
+    }
+
+    pub fn infer(self, db: &dyn HirDatabase) -> Arc<InferenceResult> {
+        db.infer(self.id.into())
+    }
+
+    pub(crate) fn body_source_map(self, db: &dyn HirDatabase) -> Arc<BodySourceMap> {
+        db.body_with_source_map(self.id.into()).1
+    }
+
+    pub fn diagnostics(self, db: &dyn HirDatabase, sink: &mut DiagnosticSink<'_>) {
+        let body = self.body(db);
+        body.add_diagnostics(db, self.into(), sink);
+        let infer = self.infer(db);
+        infer.add_diagnostics(db, self.into(), sink);
+
+        let src = self.source(db.upcast());
+        let file = src.file_id;
+        if !matches!(
+            self.ty(db).interned(),
+            TyKind::Bool | TyKind::Int(_) | TyKind::Float(_) | TyKind::Unknown
+        ) {
+            let type_ref = src
+                .value
+                .ascribed_type()
+                .map_or_else(|| src.value.syntax().clone(), |ty| ty.syntax().clone());
+            sink.push(InvalidConstType {
+                file,
+                type_ref: SyntaxNodePtr::new(&type_ref),
+            });
+        }
+
+        link_section::validate(
+            db,
+            self.module(db).package(),
+            file,
+            &self.data(db.upcast()).link_sections,
+            false,
+            sink,
+        );
+
+        if let Err(error) = self.value(db) {
+            let expr = error
+                .expr()
+                .and_then(|expr| self.body_source_map(db).expr_syntax(expr));
+            if let Some(expr) = expr {
+                sink.push(ConstEvalFailed {
+                    file,
+                    expr: expr
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr()),
+                    error,
+                });
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ConstData {
+    pub name: Name,
+    pub visibility: RawVisibility,
+    pub type_ref_id: LocalTypeRefId,
+    /// The `#[link_section]` attributes of the constant, in order
+    pub link_sections: Vec<LinkSection>,
+    type_ref_map: TypeRefMap,
+}
+
+impl ConstData {
+    pub(crate) fn const_data_query(db: &dyn DefDatabase, id: ConstId) -> Arc<ConstData> {
+        let loc = id.lookup(db);
+        let item_tree = db.item_tree(loc.id.file_id);
+        let konst = &item_tree[loc.id.value];
+        let src = item_tree.source(db, loc.id.value);
+        Arc::new(ConstData {
+            name: konst.name.clone(),
+            visibility: item_tree[konst.visibility].clone(),
+            type_ref_id: konst.type_ref,
+            link_sections: LinkSection::from_attrs(&src),
+            type_ref_map: konst.types.clone(),
+        })
+    }
+
+    pub fn type_ref_map(&self) -> &TypeRefMap {
+        &self.type_ref_map
+    }
+}
+
+impl HasVisibility for Const {
+    fn visibility(&self, db: &dyn HirDatabase) -> Visibility {
+        self.data(db.upcast())
+            .visibility
+            .resolve(db.upcast(), &self.id.resolver(db.upcast()))
+    }
+}
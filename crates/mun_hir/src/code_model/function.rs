@@ -1,18 +1,25 @@
 use std::{iter::once, sync::Arc};
 
-use mun_syntax::{ast, ast::TypeAscriptionOwner};
+use mun_syntax::{
+    ast,
+    ast::{AttrsOwner, TypeAscriptionOwner},
+    AstNode, SyntaxNodePtr,
+};
 
 use super::Module;
 use crate::{
-    expr::{validator::ExprValidator, BodySourceMap},
+    diagnostics::{DuplicateNoMangleSymbol, InvalidInitFunction},
+    expr::{validator::ExprValidator, BodySourceMap, ExprId, PatId},
     has_module::HasModule,
     ids::{FunctionId, Lookup},
+    line_index::LineCol,
+    name::AsName,
     name_resolution::Namespace,
     resolve::HasResolver,
     type_ref::{LocalTypeRefId, TypeRefMap, TypeRefSourceMap},
     visibility::RawVisibility,
-    Body, DefDatabase, DiagnosticSink, FileId, HasSource, HasVisibility, HirDatabase, InFile,
-    InferenceResult, Name, Pat, Ty, Visibility,
+    Body, CallableDef, DefDatabase, DiagnosticSink, Expr, FileId, HasSource, HasVisibility,
+    HirDatabase, InFile, InferenceResult, ModuleDef, Name, Pat, Ty, Visibility,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
@@ -26,6 +33,54 @@ impl From<FunctionId> for Function {
     }
 }
 
+/// How a function should be inlined into its callers, as specified with the
+/// `#[inline]` attribute.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum InlineAttr {
+    /// The optimizer decides whether to inline the function
+    #[default]
+    None,
+    /// `#[inline]`: the function should preferably be inlined
+    Hint,
+    /// `#[inline(always)]`: the function should always be inlined
+    Always,
+    /// `#[inline(never)]`: the function should never be inlined
+    Never,
+}
+
+/// What the optimizer should favor when optimizing a function, as specified
+/// with the `#[optimize]` attribute.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OptimizeAttr {
+    /// The function is optimized like the rest of the assembly
+    #[default]
+    Default,
+    /// `#[optimize(speed)]`: the function is optimized for speed
+    Speed,
+    /// `#[optimize(size)]`: the function is optimized for size
+    Size,
+}
+
+/// Returns the single identifier that is passed to the attribute with the
+/// specified name, e.g. `always` for `#[inline(always)]`. The outer `None`
+/// indicates that the attribute is missing.
+fn attr_ident_arg(src: &ast::FunctionDef, name: &str) -> Option<Option<String>> {
+    let attr = src.attr(name)?;
+    let Some(arg_list) = attr.arg_list() else {
+        return Some(None);
+    };
+    let mut args = arg_list.args();
+    let ident = match (args.next().map(|arg| arg.kind()), args.next()) {
+        (Some(ast::ExprKind::PathExpr(path_expr)), None) => path_expr
+            .path()
+            .filter(|path| path.qualifier().is_none())
+            .and_then(|path| path.segment()?.name_ref())
+            .map(|name_ref| name_ref.text().to_string()),
+        _ => None,
+    };
+    Some(ident)
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct FunctionData {
     name: Name,
@@ -35,6 +90,15 @@ pub struct FunctionData {
     type_ref_map: TypeRefMap,
     type_ref_source_map: TypeRefSourceMap,
     is_extern: bool,
+    is_cold: bool,
+    is_no_mangle: bool,
+    is_no_instrument: bool,
+    is_reflect: bool,
+    is_tail_call: bool,
+    is_init: bool,
+    inline: InlineAttr,
+    optimize: OptimizeAttr,
+    allowed_lints: Vec<Name>,
 }
 
 impl FunctionData {
@@ -62,6 +126,40 @@ impl FunctionData {
 
         let (type_ref_map, type_ref_source_map) = type_ref_builder.finish();
 
+        // Collect the lints that are suppressed by `#[allow(lint, ..)]` attributes
+        let allowed_lints = src
+            .attrs()
+            .filter(|attr| {
+                attr.name_ref()
+                    .map_or(false, |name_ref| name_ref.text() == "allow")
+            })
+            .filter_map(|attr| attr.arg_list())
+            .flat_map(|arg_list| arg_list.args())
+            .filter_map(|arg| match arg.kind() {
+                ast::ExprKind::PathExpr(path_expr) => path_expr.path(),
+                _ => None,
+            })
+            .filter(|path| path.qualifier().is_none())
+            .filter_map(|path| path.segment()?.name_ref())
+            .map(|name_ref| name_ref.as_name())
+            .collect();
+
+        // Arguments that are not recognized leave the default behavior in place
+        let inline = match attr_ident_arg(&src, "inline") {
+            None => InlineAttr::None,
+            Some(None) => InlineAttr::Hint,
+            Some(Some(arg)) => match arg.as_str() {
+                "always" => InlineAttr::Always,
+                "never" => InlineAttr::Never,
+                _ => InlineAttr::Hint,
+            },
+        };
+        let optimize = match attr_ident_arg(&src, "optimize").flatten().as_deref() {
+            Some("speed") => OptimizeAttr::Speed,
+            Some("size") => OptimizeAttr::Size,
+            _ => OptimizeAttr::Default,
+        };
+
         Arc::new(FunctionData {
             name: func.name.clone(),
             params,
@@ -69,6 +167,15 @@ impl FunctionData {
             type_ref_map,
             type_ref_source_map,
             is_extern: func.is_extern,
+            is_cold: func.is_cold,
+            is_no_mangle: func.is_no_mangle,
+            is_no_instrument: func.is_no_instrument,
+            is_reflect: func.is_reflect,
+            is_tail_call: func.is_tail_call,
+            is_init: func.is_init,
+            inline,
+            optimize,
+            allowed_lints,
             visibility: item_tree[func.visibility].clone(),
         })
     }
@@ -101,6 +208,63 @@ impl FunctionData {
     pub fn is_extern(&self) -> bool {
         self.is_extern
     }
+
+    /// Returns true if this function is marked as rarely executed with the
+    /// `#[cold]` attribute.
+    pub fn is_cold(&self) -> bool {
+        self.is_cold
+    }
+
+    /// Returns true if the function is exported under its exact name with the
+    /// `#[no_mangle]` attribute.
+    pub fn is_no_mangle(&self) -> bool {
+        self.is_no_mangle
+    }
+
+    /// Returns true if the function is excluded from instrumentation with the
+    /// `#[no_instrument]` attribute.
+    pub fn is_no_instrument(&self) -> bool {
+        self.is_no_instrument
+    }
+
+    /// Returns true if the function is retained and exported for reflection
+    /// with the `#[reflect]` attribute, even if it is never called.
+    pub fn is_reflect(&self) -> bool {
+        self.is_reflect
+    }
+
+    /// Returns true if the calls in tail position of the function must be
+    /// compiled as tail calls with the `#[tail_call]` attribute.
+    pub fn is_tail_call(&self) -> bool {
+        self.is_tail_call
+    }
+
+    /// Returns true if the function initializes the assembly with the
+    /// `#[init]` attribute. The runtime can call these functions when it warms
+    /// up.
+    pub fn is_init(&self) -> bool {
+        self.is_init
+    }
+
+    /// Returns how the function should be inlined into its callers, as
+    /// specified with the `#[inline]` attribute.
+    pub fn inline(&self) -> InlineAttr {
+        self.inline
+    }
+
+    /// Returns what the optimizer should favor when optimizing the function,
+    /// as specified with the `#[optimize]` attribute.
+    pub fn optimize(&self) -> OptimizeAttr {
+        self.optimize
+    }
+
+    /// Returns true if the lint with the specified name is suppressed for this
+    /// function with an `#[allow(lint)]` attribute.
+    pub fn is_lint_allowed(&self, lint: &str) -> bool {
+        self.allowed_lints
+            .iter()
+            .any(|name| name.as_str() == Some(lint))
+    }
 }
 
 impl Function {
@@ -127,6 +291,35 @@ impl Function {
         self.id.lookup(db.upcast()).id.file_id
     }
 
+    /// Returns the position in the source file at which the definition of the
+    /// function starts.
+    pub fn line_col(self, db: &dyn HirDatabase) -> LineCol {
+        let src = self.source(db.upcast());
+        db.line_index(src.file_id)
+            .line_col(src.value.syntax().text_range().start())
+    }
+
+    /// Returns the position in the source file at which the specified
+    /// expression of the function's body starts, or `None` if the expression
+    /// does not originate from the source file.
+    pub fn expr_line_col(self, db: &dyn HirDatabase, expr: ExprId) -> Option<LineCol> {
+        let src = self.body_source_map(db).expr_syntax(expr)?;
+        let range = src.value.either(
+            |ptr| ptr.syntax_node_ptr().range(),
+            |ptr| ptr.syntax_node_ptr().range(),
+        );
+        Some(db.line_index(src.file_id).line_col(range.start()))
+    }
+
+    /// Returns the position in the source file at which the specified pattern
+    /// of the function's body starts, or `None` if the pattern does not
+    /// originate from the source file.
+    pub fn pat_line_col(self, db: &dyn HirDatabase, pat: PatId) -> Option<LineCol> {
+        let src = self.body_source_map(db).pat_syntax(pat)?;
+        let range = src.value.syntax_node_ptr().range();
+        Some(db.line_index(src.file_id).line_col(range.start()))
+    }
+
     pub fn name(self, db: &dyn HirDatabase) -> Name {
         self.data(db.upcast()).name.clone()
     }
@@ -171,6 +364,56 @@ impl Function {
         db.fn_data(self.id).is_extern
     }
 
+    pub fn is_cold(self, db: &dyn HirDatabase) -> bool {
+        db.fn_data(self.id).is_cold
+    }
+
+    pub fn is_no_mangle(self, db: &dyn HirDatabase) -> bool {
+        db.fn_data(self.id).is_no_mangle
+    }
+
+    pub fn is_no_instrument(self, db: &dyn HirDatabase) -> bool {
+        db.fn_data(self.id).is_no_instrument
+    }
+
+    pub fn is_reflect(self, db: &dyn HirDatabase) -> bool {
+        db.fn_data(self.id).is_reflect
+    }
+
+    pub fn is_tail_call(self, db: &dyn HirDatabase) -> bool {
+        db.fn_data(self.id).is_tail_call
+    }
+
+    pub fn is_init(self, db: &dyn HirDatabase) -> bool {
+        db.fn_data(self.id).is_init
+    }
+
+    pub fn inline(self, db: &dyn HirDatabase) -> InlineAttr {
+        db.fn_data(self.id).inline
+    }
+
+    pub fn optimize(self, db: &dyn HirDatabase) -> OptimizeAttr {
+        db.fn_data(self.id).optimize
+    }
+
+    /// Returns the calls in tail position of the body of the function, whose
+    /// result is returned without any further evaluation. Struct constructors
+    /// are not included, because they are not compiled as calls.
+    pub fn tail_calls(self, db: &dyn HirDatabase) -> Vec<ExprId> {
+        let body = self.body(db);
+        let infer = self.infer(db);
+        body.tail_exprs()
+            .into_iter()
+            .filter(|&expr| match &body[expr] {
+                Expr::Call { callee, .. } => !matches!(
+                    infer[*callee].as_callable_def(),
+                    Some(CallableDef::Struct(_))
+                ),
+                _ => false,
+            })
+            .collect()
+    }
+
     pub(crate) fn body_source_map(self, db: &dyn HirDatabase) -> Arc<BodySourceMap> {
         db.body_with_source_map(self.id.into()).1
     }
@@ -179,9 +422,60 @@ impl Function {
         let body = self.body(db);
         body.add_diagnostics(db, self.into(), sink);
         let infer = self.infer(db);
-        infer.add_diagnostics(db, self, sink);
+        infer.add_diagnostics(db, self.into(), sink);
         let validator = ExprValidator::new(self, db);
         validator.validate_body(sink);
+        self.validate_no_mangle_symbol(db, sink);
+        self.validate_init_signature(db, sink);
+    }
+
+    /// Reports a diagnostic if this is a `#[no_mangle]` function whose symbol
+    /// is already exported by another `#[no_mangle]` function in the package.
+    /// Only the functions that follow the first definition are reported.
+    fn validate_no_mangle_symbol(self, db: &dyn HirDatabase, sink: &mut DiagnosticSink<'_>) {
+        if !self.is_no_mangle(db) {
+            return;
+        }
+
+        let name = self.name(db);
+        let first_definition = self
+            .module(db)
+            .package()
+            .modules(db)
+            .into_iter()
+            .flat_map(|module| module.declarations(db))
+            .find_map(|def| match def {
+                ModuleDef::Function(f) if f.is_no_mangle(db) && f.name(db) == name => Some(f),
+                _ => None,
+            });
+
+        if let Some(first_definition) = first_definition.filter(|&f| f != self) {
+            let syntax_node_ptr = |f: Function| {
+                f.source(db.upcast())
+                    .map(|src| SyntaxNodePtr::new(src.syntax()))
+            };
+            sink.push(DuplicateNoMangleSymbol {
+                name: name.to_string(),
+                first_definition: syntax_node_ptr(first_definition),
+                definition: syntax_node_ptr(self),
+            });
+        }
+    }
+
+    /// Reports a diagnostic if this is an `#[init]` function that the runtime
+    /// cannot call, because it takes arguments, returns a value or is extern.
+    fn validate_init_signature(self, db: &dyn HirDatabase, sink: &mut DiagnosticSink<'_>) {
+        if !self.is_init(db) {
+            return;
+        }
+
+        if self.is_extern(db) || !self.params(db).is_empty() || !self.ret_type(db).is_empty() {
+            sink.push(InvalidInitFunction {
+                definition: self
+                    .source(db.upcast())
+                    .map(|src| SyntaxNodePtr::new(src.syntax())),
+            });
+        }
     }
 }
 
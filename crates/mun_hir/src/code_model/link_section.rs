@@ -0,0 +1,266 @@
+use mun_syntax::{
+    ast::{self, AttrsOwner},
+    AstPtr,
+};
+use mun_target::spec::Target;
+use rustc_hash::FxHashMap;
+
+use crate::{
+    diagnostics::{ConflictingLinkSection, InvalidLinkSection, MalformedLinkSection},
+    expr::{Expr, Statement},
+    DiagnosticSink, FileId, HirDatabase, InFile, ModuleDef, Package,
+};
+
+/// A `#[link_section = "name"]` attribute on a constant or a function-local
+/// static, which places the global that holds its value in the section with
+/// the specified name, e.g. to put configuration data in a flash area of
+/// embedded firmware.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkSection {
+    pub attr: AstPtr<ast::Attr>,
+    /// The name of the section, or `None` if the value of the attribute is not
+    /// a string literal.
+    pub name: Option<String>,
+}
+
+impl LinkSection {
+    pub const ATTR_NAME: &'static str = "link_section";
+
+    /// Returns all `#[link_section]` attributes of `owner`, in order.
+    pub(crate) fn from_attrs(owner: &impl AttrsOwner) -> Vec<LinkSection> {
+        owner
+            .attrs()
+            .filter(|attr| {
+                attr.name_ref()
+                    .map_or(false, |name_ref| name_ref.text() == Self::ATTR_NAME)
+            })
+            .map(|attr| LinkSection::from_ast(&attr))
+            .collect()
+    }
+
+    fn from_ast(attr: &ast::Attr) -> Self {
+        let name = attr.literal().and_then(|lit| match lit.kind() {
+            ast::LiteralKind::String(string) => {
+                let text = string.text();
+                let name = text.strip_prefix('"')?.strip_suffix('"')?;
+                Some(name.to_owned())
+            }
+            _ => None,
+        });
+
+        LinkSection {
+            attr: AstPtr::new(attr),
+            name,
+        }
+    }
+
+    /// Returns the name of the section in which a global is placed by its
+    /// `#[link_section]` attributes, if any. Diagnostics are reported for
+    /// invalid attributes, which are otherwise ignored.
+    pub fn section_name(link_sections: &[LinkSection]) -> Option<&str> {
+        link_sections
+            .first()
+            .and_then(|first| first.name.as_deref())
+    }
+}
+
+/// Reports diagnostics for the `#[link_section]` attributes of a global that
+/// is `writable`, i.e. a static, or read-only, i.e. a constant.
+pub(crate) fn validate(
+    db: &dyn HirDatabase,
+    package: Package,
+    file: FileId,
+    link_sections: &[LinkSection],
+    writable: bool,
+    sink: &mut DiagnosticSink<'_>,
+) {
+    let Some((first, rest)) = link_sections.split_first() else {
+        return;
+    };
+
+    for link_section in link_sections {
+        if link_section.name.is_none() {
+            sink.push(MalformedLinkSection {
+                attr: InFile::new(file, link_section.attr.clone()),
+            });
+        }
+    }
+
+    let Some(name) = &first.name else {
+        return;
+    };
+    let attr = InFile::new(file, first.attr.clone());
+
+    // A global can only be placed in a single section
+    for link_section in rest {
+        if let Some(other) = link_section.name.as_ref().filter(|&other| other != name) {
+            sink.push(ConflictingLinkSection {
+                attr: InFile::new(file, link_section.attr.clone()),
+                message: format!("the global is already placed in section `{name}`, not `{other}`"),
+            });
+        }
+    }
+
+    if let Err(reason) = validate_section_name(&db.target(), name, writable) {
+        sink.push(InvalidLinkSection {
+            attr,
+            name: name.clone(),
+            reason,
+        });
+        return;
+    }
+
+    // Linkers merge the sections of all object files with the same name, so a
+    // section holds either read-only or writable globals across the package.
+    let first_writable = package_sections(db, package).get(name).copied();
+    if first_writable.map_or(false, |first_writable| first_writable != writable) {
+        sink.push(ConflictingLinkSection {
+            attr,
+            message: format!(
+                "section `{name}` already holds {}, it cannot also hold {}",
+                if writable { "constants" } else { "statics" },
+                if writable { "statics" } else { "constants" }
+            ),
+        });
+    }
+}
+
+/// Returns the sections in which the globals of `package` are placed, mapped
+/// to whether the first global that is placed in the section is writable.
+fn package_sections(db: &dyn HirDatabase, package: Package) -> FxHashMap<String, bool> {
+    let mut sections = FxHashMap::default();
+    for def in package
+        .modules(db)
+        .into_iter()
+        .flat_map(|module| module.declarations(db))
+    {
+        match def {
+            ModuleDef::Const(konst) => {
+                if let Some(name) =
+                    LinkSection::section_name(&konst.data(db.upcast()).link_sections)
+                {
+                    sections.entry(name.to_owned()).or_insert(false);
+                }
+            }
+            ModuleDef::Function(function) => {
+                let body = function.body(db);
+                let statics = body
+                    .exprs()
+                    .filter_map(|(_, expr)| match expr {
+                        Expr::Block { statements, .. } => Some(statements),
+                        _ => None,
+                    })
+                    .flatten();
+                for statement in statics {
+                    if let Statement::Static { link_sections, .. } = statement {
+                        if let Some(name) = LinkSection::section_name(link_sections) {
+                            sections.entry(name.to_owned()).or_insert(true);
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    sections
+}
+
+/// Checks that globals can be placed in the section `name` in the object
+/// format of `target`, which is determined by its linker flavor. A section
+/// that a linker would silently merge with incompatible data, or an LLVM
+/// backend would reject, is reported instead.
+pub(crate) fn validate_section_name(
+    target: &Target,
+    name: &str,
+    writable: bool,
+) -> Result<(), String> {
+    if name.is_empty() {
+        return Err(String::from("the section name is empty"));
+    }
+    if name.contains('\0') {
+        return Err(String::from("the section name contains a NUL character"));
+    }
+
+    if target.options.is_like_osx {
+        // Mach-O sections are specified as `segment,section`, optionally followed
+        // by the type and attributes of the section.
+        let mut parts = name.split(',');
+        let (Some(segment), Some(section)) = (parts.next(), parts.next()) else {
+            return Err(String::from(
+                "Mach-O sections must be specified as `segment,section`, e.g. `__DATA,__config`",
+            ));
+        };
+        if segment.is_empty() || section.is_empty() || segment.len() > 16 || section.len() > 16 {
+            return Err(String::from(
+                "Mach-O segment and section names must be between 1 and 16 characters long",
+            ));
+        }
+        if writable && segment == "__TEXT" {
+            return Err(String::from("the `__TEXT` segment is read-only"));
+        }
+    } else if target.options.is_like_windows {
+        // The linker orders sections by the suffix after a `$` and strips it
+        let image_name = name.split('$').next().unwrap_or(name);
+        if image_name.len() > 8 {
+            return Err(String::from(
+                "COFF section names are limited to 8 characters",
+            ));
+        }
+        if writable && [".text", ".rdata"].contains(&image_name) {
+            return Err(format!("the `{image_name}` section is read-only"));
+        }
+    } else {
+        // ELF linkers and LLVM derive the kind of a section from its name
+        for prefix in [".tdata", ".tbss"] {
+            if name.starts_with(prefix) {
+                return Err(format!("`{prefix}` sections are thread-local"));
+            }
+        }
+        if name.starts_with(".bss") {
+            return Err(String::from(
+                "`.bss` sections can only hold zero-initialized data",
+            ));
+        }
+        if writable {
+            for prefix in [".text", ".rodata"] {
+                if name.starts_with(prefix) {
+                    return Err(format!("`{prefix}` sections are read-only"));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use mun_target::spec::Target;
+
+    use super::validate_section_name;
+
+    #[test]
+    fn section_names() {
+        let linux = Target::search("x86_64-unknown-linux-gnu").unwrap();
+        let macos = Target::search("aarch64-apple-darwin").unwrap();
+        let windows = Target::search("x86_64-pc-windows-msvc").unwrap();
+
+        assert!(validate_section_name(&linux, ".config", true).is_ok());
+        assert!(validate_section_name(&linux, ".rodata.config", false).is_ok());
+        assert!(validate_section_name(&linux, ".rodata.config", true).is_err());
+        assert!(validate_section_name(&linux, ".tdata.config", false).is_err());
+        assert!(validate_section_name(&linux, ".bss.config", true).is_err());
+        assert!(validate_section_name(&linux, "", false).is_err());
+
+        assert!(validate_section_name(&macos, "__DATA,__config", true).is_ok());
+        assert!(validate_section_name(&macos, "__TEXT,__config", false).is_ok());
+        assert!(validate_section_name(&macos, "__TEXT,__config", true).is_err());
+        assert!(validate_section_name(&macos, ".config", false).is_err());
+        assert!(validate_section_name(&macos, "__DATA,__a_very_long_section", false).is_err());
+
+        assert!(validate_section_name(&windows, ".config", true).is_ok());
+        assert!(validate_section_name(&windows, ".cfg$b", true).is_ok());
+        assert!(validate_section_name(&windows, ".configuration", true).is_err());
+        assert!(validate_section_name(&windows, ".rdata", true).is_err());
+    }
+}
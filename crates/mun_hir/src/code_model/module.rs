@@ -1,5 +1,6 @@
-use super::{Function, Package, Struct, TypeAlias};
+use super::{Const, Function, Package, Struct, TypeAlias};
 use crate::{
+    builtin_constant::BuiltinConstant,
     ids::{ItemDefinitionId, ModuleId},
     primitive_type::PrimitiveType,
     DiagnosticSink, FileId, HirDatabase, Name,
@@ -99,6 +100,7 @@ impl Module {
                 ModuleDef::Function(f) => f.diagnostics(db, sink),
                 ModuleDef::Struct(s) => s.diagnostics(db, sink),
                 ModuleDef::TypeAlias(t) => t.diagnostics(db, sink),
+                ModuleDef::Const(c) => c.diagnostics(db, sink),
                 _ => (),
             }
         }
@@ -150,6 +152,8 @@ pub enum ModuleDef {
     PrimitiveType(PrimitiveType),
     Struct(Struct),
     TypeAlias(TypeAlias),
+    Const(Const),
+    BuiltinConstant(BuiltinConstant),
 }
 
 impl From<Function> for ModuleDef {
@@ -164,6 +168,12 @@ impl From<PrimitiveType> for ModuleDef {
     }
 }
 
+impl From<BuiltinConstant> for ModuleDef {
+    fn from(t: BuiltinConstant) -> Self {
+        ModuleDef::BuiltinConstant(t)
+    }
+}
+
 impl From<Struct> for ModuleDef {
     fn from(t: Struct) -> Self {
         ModuleDef::Struct(t)
@@ -176,6 +186,12 @@ impl From<TypeAlias> for ModuleDef {
     }
 }
 
+impl From<Const> for ModuleDef {
+    fn from(t: Const) -> Self {
+        ModuleDef::Const(t)
+    }
+}
+
 impl From<Module> for ModuleDef {
     fn from(m: Module) -> Self {
         ModuleDef::Module(m)
@@ -189,7 +205,9 @@ impl From<ItemDefinitionId> for ModuleDef {
             ItemDefinitionId::FunctionId(id) => Function { id }.into(),
             ItemDefinitionId::StructId(id) => Struct { id }.into(),
             ItemDefinitionId::TypeAliasId(id) => TypeAlias { id }.into(),
+            ItemDefinitionId::ConstId(id) => Const { id }.into(),
             ItemDefinitionId::PrimitiveType(id) => id.into(),
+            ItemDefinitionId::BuiltinConstant(id) => id.into(),
         }
     }
 }
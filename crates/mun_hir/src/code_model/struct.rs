@@ -4,7 +4,8 @@ pub use ast::StructMemoryKind;
 use la_arena::{Arena, Idx};
 use mun_syntax::{
     ast,
-    ast::{NameOwner, TypeAscriptionOwner, VisibilityOwner},
+    ast::{AttrsOwner, NameOwner, TypeAscriptionOwner, VisibilityOwner},
+    AstPtr,
 };
 
 use super::Module;
@@ -129,6 +130,9 @@ impl Struct {
         lower.add_diagnostics(db, self.file_id(db), data.type_ref_source_map(), sink);
         let validator = validator::StructValidator::new(self, db, self.file_id(db));
         validator.validate_privacy(sink);
+        validator.validate_slice_types(sink);
+        validator.validate_ref_types(sink);
+        validator.validate_size_assertion(sink);
     }
 }
 
@@ -172,6 +176,41 @@ impl fmt::Display for StructKind {
 /// An identifier for a struct's or tuple's field
 pub type LocalFieldId = Idx<FieldData>;
 
+/// An `#[assert_size(N)]` attribute on a struct, which asserts that the struct
+/// is exactly `N` bytes large on the target that is compiled for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeAssertion {
+    pub attr: AstPtr<ast::Attr>,
+    /// The asserted size in bytes, or `None` if the argument of the attribute
+    /// is not a single integer literal.
+    pub size: Option<u64>,
+}
+
+impl SizeAssertion {
+    pub const ATTR_NAME: &'static str = "assert_size";
+
+    fn from_ast(attr: &ast::Attr) -> Self {
+        let size = attr.arg_list().and_then(|arg_list| {
+            let mut args = arg_list.args();
+            match (args.next().map(|arg| arg.kind()), args.next()) {
+                (Some(ast::ExprKind::Literal(lit)), None) => match lit.kind() {
+                    ast::LiteralKind::IntNumber(int) => match int.split_into_parts() {
+                        (text, None) => text.replace('_', "").parse().ok(),
+                        _ => None,
+                    },
+                    _ => None,
+                },
+                _ => None,
+            }
+        });
+
+        SizeAssertion {
+            attr: AstPtr::new(attr),
+            size,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct StructData {
     pub name: Name,
@@ -179,6 +218,7 @@ pub struct StructData {
     pub fields: Arena<FieldData>,
     pub kind: StructKind,
     pub memory_kind: StructMemoryKind,
+    pub size_assertion: Option<SizeAssertion>,
     type_ref_map: TypeRefMap,
     type_ref_source_map: TypeRefSourceMap,
 }
@@ -195,6 +235,10 @@ impl StructData {
             .map(|s| s.kind())
             .unwrap_or_default();
 
+        let size_assertion = src
+            .attr(SizeAssertion::ATTR_NAME)
+            .map(|attr| SizeAssertion::from_ast(&attr));
+
         let mut type_ref_builder = TypeRefMap::builder();
         let (fields, kind) = match src.kind() {
             ast::StructKind::Record(r) => {
@@ -232,6 +276,7 @@ impl StructData {
             fields,
             kind,
             memory_kind,
+            size_assertion,
             type_ref_map,
             type_ref_source_map,
         })
@@ -1,7 +1,11 @@
 use super::Struct;
 use crate::{
-    diagnostics::ExportedPrivate, resolve::HasResolver, visibility::RawVisibility, DiagnosticSink,
-    FileId, HasVisibility, HirDatabase, Ty, Visibility,
+    diagnostics::{
+        ExportedPrivate, InvalidRefType, InvalidSizeAssertion, InvalidSliceType, StructSizeMismatch,
+    },
+    resolve::HasResolver,
+    visibility::RawVisibility,
+    DiagnosticSink, FileId, HasVisibility, HirDatabase, InFile, Ty, Visibility,
 };
 
 #[cfg(test)]
@@ -57,4 +61,64 @@ impl<'a> StructValidator<'a> {
                 });
             });
     }
+
+    /// Validates that none of the fields of the struct have a slice type.
+    pub fn validate_slice_types(&self, sink: &mut DiagnosticSink<'_>) {
+        let struct_data = self.strukt.data(self.db.upcast());
+        struct_data.type_ref_map().slices().for_each(|type_ref| {
+            sink.push(InvalidSliceType {
+                file: self.file_id,
+                type_ref: struct_data
+                    .type_ref_source_map()
+                    .type_ref_syntax(type_ref)
+                    .unwrap(),
+            });
+        });
+    }
+
+    /// Validates that none of the fields of the struct have a mutable reference
+    /// type.
+    pub fn validate_ref_types(&self, sink: &mut DiagnosticSink<'_>) {
+        let struct_data = self.strukt.data(self.db.upcast());
+        struct_data.type_ref_map().refs().for_each(|type_ref| {
+            sink.push(InvalidRefType {
+                file: self.file_id,
+                type_ref: struct_data
+                    .type_ref_source_map()
+                    .type_ref_syntax(type_ref)
+                    .unwrap(),
+            });
+        });
+    }
+
+    /// Validates that the struct has the size asserted by an
+    /// `#[assert_size(N)]` attribute on the current target.
+    pub fn validate_size_assertion(&self, sink: &mut DiagnosticSink<'_>) {
+        let struct_data = self.strukt.data(self.db.upcast());
+        let Some(assertion) = &struct_data.size_assertion else {
+            return;
+        };
+
+        let attr = InFile::new(self.file_id, assertion.attr.clone());
+        let Some(expected) = assertion.size else {
+            sink.push(InvalidSizeAssertion { attr });
+            return;
+        };
+
+        // If the layout cannot be determined, an error has already been reported
+        // for the offending field.
+        let Some(layout) = self.strukt.layout(self.db) else {
+            return;
+        };
+
+        let actual = layout.size.bytes();
+        if actual != expected {
+            sink.push(StructSizeMismatch {
+                attr,
+                name: struct_data.name.clone(),
+                expected,
+                actual,
+            });
+        }
+    }
 }
@@ -40,3 +40,29 @@ fn test_private_leak_struct_fields() {
     394..397: can't leak private type
     "###);
 }
+
+#[test]
+fn test_struct_size_assertion() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    #[assert_size(8)]
+    struct(value) Foo {
+        a: u8,
+        b: u32,
+    }
+
+    #[assert_size(16)]
+    struct(value) Bar(u32, u32, u8);
+
+    #[assert_size(4)]
+    struct(value) Baz(u8, u8);
+
+    #[assert_size(foo)]
+    struct Qux;
+    "#),
+    @r###"
+    64..82: struct `Bar` is asserted to be 16 bytes but is 12 bytes on the current target
+    117..134: struct `Baz` is asserted to be 4 bytes but is 2 bytes on the current target
+    163..182: expected a single integer literal, e.g. `#[assert_size(16)]`
+    "###);
+}
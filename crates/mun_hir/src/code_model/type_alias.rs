@@ -80,6 +80,8 @@ impl TypeAlias {
         let validator = TypeAliasValidator::new(self, db);
         validator.validate_target_type_existence(sink);
         validator.validate_target_type_privacy(sink);
+        validator.validate_slice_types(sink);
+        validator.validate_ref_types(sink);
         validator.validate_acyclic(sink);
     }
 }
@@ -0,0 +1,426 @@
+//! Evaluation of the initializers of constants at compile time.
+//!
+//! The initializer of a constant may consist of literals, references to other
+//! constants, unary and binary operators and `if` expressions. Integer
+//! arithmetic is checked; a value that does not fit in the type of the
+//! operation is an error rather than wrapping around.
+
+use std::{cmp, fmt};
+
+use crate::{
+    expr::{ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, Literal, LogicOp, Ordering, UnaryOp},
+    ids::ConstId,
+    primitive_type::{FloatBitness, IntBitness, Signedness},
+    resolve::{resolver_for_expr, ValueNs},
+    Const, HirDatabase, InferenceResult, IntTy, ResolveBitness, TyKind,
+};
+
+/// The value of a constant.
+#[derive(Debug, Clone, Copy)]
+pub enum ConstValue {
+    Bool(bool),
+    /// The value of an integer. Unsigned integers are stored in the same
+    /// bits, e.g. `u128::MAX` is stored as `-1`.
+    Int(i128),
+    /// The value of a floating-point number. The value of an `f32` is
+    /// rounded to single precision.
+    Float(f64),
+}
+
+impl PartialEq for ConstValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ConstValue::Bool(a), ConstValue::Bool(b)) => a == b,
+            (ConstValue::Int(a), ConstValue::Int(b)) => a == b,
+            (ConstValue::Float(a), ConstValue::Float(b)) => a.to_bits() == b.to_bits(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ConstValue {}
+
+/// An error that occurred while evaluating the initializer of a constant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstEvalError {
+    /// The initializer contains errors that have already been reported, or it
+    /// refers to a constant whose evaluation failed.
+    InvalidInitializer,
+    /// The constant is not of a primitive type.
+    UnsupportedType,
+    /// The expression cannot be evaluated at compile time.
+    NotConst(ExprId),
+    /// The value of the constant depends on itself through the referenced
+    /// constant.
+    Cycle(ExprId),
+    /// The result of the operation does not fit in its type.
+    Overflow(ExprId),
+    /// The operation divides by zero.
+    DivisionByZero(ExprId),
+}
+
+impl ConstEvalError {
+    /// Returns the expression that caused the error, if the error originates
+    /// from the initializer itself.
+    pub fn expr(self) -> Option<ExprId> {
+        match self {
+            ConstEvalError::NotConst(expr)
+            | ConstEvalError::Cycle(expr)
+            | ConstEvalError::Overflow(expr)
+            | ConstEvalError::DivisionByZero(expr) => Some(expr),
+            ConstEvalError::InvalidInitializer | ConstEvalError::UnsupportedType => None,
+        }
+    }
+}
+
+impl fmt::Display for ConstEvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ConstEvalError::InvalidInitializer => "the initializer contains errors",
+            ConstEvalError::UnsupportedType => "constants of this type are not supported",
+            ConstEvalError::NotConst(_) => "the expression cannot be evaluated at compile time",
+            ConstEvalError::Cycle(_) => "the value of the constant depends on itself",
+            ConstEvalError::Overflow(_) => "attempt to compute a value that overflows its type",
+            ConstEvalError::DivisionByZero(_) => "attempt to divide by zero",
+        })
+    }
+}
+
+pub(crate) fn const_eval_query(
+    db: &dyn HirDatabase,
+    def: Const,
+) -> Result<ConstValue, ConstEvalError> {
+    ConstEvaluator {
+        db,
+        stack: Vec::new(),
+    }
+    .eval_const(def.id)
+}
+
+/// Evaluates constants. Constants that are referenced are evaluated by the
+/// same evaluator rather than through the database, which allows detecting
+/// constants that depend on themselves.
+struct ConstEvaluator<'a> {
+    db: &'a dyn HirDatabase,
+    /// The constants that are currently being evaluated
+    stack: Vec<ConstId>,
+}
+
+impl ConstEvaluator<'_> {
+    fn eval_const(&mut self, id: ConstId) -> Result<ConstValue, ConstEvalError> {
+        let konst = Const::from(id);
+        if !matches!(
+            konst.ty(self.db).interned(),
+            TyKind::Bool | TyKind::Int(_) | TyKind::Float(_)
+        ) {
+            return Err(ConstEvalError::UnsupportedType);
+        }
+
+        let body = konst.body(self.db);
+        let infer = konst.infer(self.db);
+        if body.has_diagnostics() || !infer.diagnostics.is_empty() {
+            return Err(ConstEvalError::InvalidInitializer);
+        }
+
+        self.stack.push(id);
+        let value = self.eval_expr(&body, &infer, body.body_expr());
+        self.stack.pop();
+        value
+    }
+
+    /// Evaluates the constant `id` that is referenced by `expr`.
+    fn eval_referenced_const(
+        &mut self,
+        id: ConstId,
+        expr: ExprId,
+    ) -> Result<ConstValue, ConstEvalError> {
+        if self.stack.contains(&id) {
+            return Err(ConstEvalError::Cycle(expr));
+        }
+
+        match self.eval_const(id) {
+            Ok(value) => Ok(value),
+            Err(ConstEvalError::Cycle(_)) => Err(ConstEvalError::Cycle(expr)),
+            Err(_) => Err(ConstEvalError::InvalidInitializer),
+        }
+    }
+
+    fn eval_expr(
+        &mut self,
+        body: &Body,
+        infer: &InferenceResult,
+        expr: ExprId,
+    ) -> Result<ConstValue, ConstEvalError> {
+        match &body[expr] {
+            Expr::Literal(Literal::Bool(value)) => Ok(ConstValue::Bool(*value)),
+            Expr::Literal(Literal::Int(lit)) => Ok(ConstValue::Int(lit.value as i128)),
+            Expr::Literal(Literal::Float(lit)) => {
+                Ok(ConstValue::Float(self.round_float(infer, expr, lit.value)))
+            }
+            Expr::Literal(Literal::String(_)) => Err(ConstEvalError::NotConst(expr)),
+            Expr::Path(path) => {
+                let resolver = resolver_for_expr(self.db.upcast(), body.owner(), expr);
+                match resolver.resolve_path_as_value_fully(self.db.upcast(), path) {
+                    Some((ValueNs::ConstId(id), _)) => self.eval_referenced_const(id, expr),
+                    Some((ValueNs::BuiltinConstant(constant), _)) => {
+                        Ok(constant.value(&self.db.target_data_layout()))
+                    }
+                    Some(_) => Err(ConstEvalError::NotConst(expr)),
+                    None => Err(ConstEvalError::InvalidInitializer),
+                }
+            }
+            Expr::UnaryOp { expr: operand, op } => {
+                let value = self.eval_expr(body, infer, *operand)?;
+                match (op, value) {
+                    (UnaryOp::Not, ConstValue::Bool(value)) => Ok(ConstValue::Bool(!value)),
+                    (UnaryOp::Not, ConstValue::Int(value)) => {
+                        let ty = self.int_ty(infer, expr)?;
+                        Ok(ConstValue::Int(truncate(!value, ty)))
+                    }
+                    (UnaryOp::Neg, ConstValue::Int(value)) => {
+                        let ty = self.int_ty(infer, expr)?;
+                        value
+                            .checked_neg()
+                            .filter(|value| fits(*value, ty))
+                            .map(ConstValue::Int)
+                            .ok_or(ConstEvalError::Overflow(expr))
+                    }
+                    (UnaryOp::Neg, ConstValue::Float(value)) => Ok(ConstValue::Float(-value)),
+                    (UnaryOp::AddrOf | UnaryOp::Deref, _) => Err(ConstEvalError::NotConst(expr)),
+                    _ => Err(ConstEvalError::InvalidInitializer),
+                }
+            }
+            Expr::Cast { expr: operand, .. } => {
+                let value = self.eval_expr(body, infer, *operand)?;
+                match (value, infer[expr].interned()) {
+                    (ConstValue::Int(value), TyKind::Int(_)) => {
+                        Ok(ConstValue::Int(truncate(value, self.int_ty(infer, expr)?)))
+                    }
+                    (ConstValue::Int(value), TyKind::Float(_)) => {
+                        let value = match self.int_ty(infer, *operand)?.signedness {
+                            Signedness::Signed => value as f64,
+                            Signedness::Unsigned => value as u128 as f64,
+                        };
+                        Ok(ConstValue::Float(self.round_float(infer, expr, value)))
+                    }
+                    (ConstValue::Float(value), TyKind::Int(_)) => {
+                        Ok(ConstValue::Int(saturate(value, self.int_ty(infer, expr)?)))
+                    }
+                    (ConstValue::Float(value), TyKind::Float(_)) => {
+                        Ok(ConstValue::Float(self.round_float(infer, expr, value)))
+                    }
+                    _ => Err(ConstEvalError::InvalidInitializer),
+                }
+            }
+            Expr::BinaryOp {
+                lhs,
+                rhs,
+                op: Some(op),
+            } => match op {
+                BinaryOp::LogicOp(op) => {
+                    let ConstValue::Bool(lhs) = self.eval_expr(body, infer, *lhs)? else {
+                        return Err(ConstEvalError::InvalidInitializer);
+                    };
+                    match (op, lhs) {
+                        (LogicOp::And, false) => Ok(ConstValue::Bool(false)),
+                        (LogicOp::Or, true) => Ok(ConstValue::Bool(true)),
+                        _ => self.eval_expr(body, infer, *rhs),
+                    }
+                }
+                BinaryOp::ArithOp(op) => {
+                    let lhs = self.eval_expr(body, infer, *lhs)?;
+                    let rhs = self.eval_expr(body, infer, *rhs)?;
+                    match (lhs, rhs) {
+                        (ConstValue::Int(lhs), ConstValue::Int(rhs)) => {
+                            let ty = self.int_ty(infer, expr)?;
+                            int_arith(*op, lhs, rhs, ty, expr).map(ConstValue::Int)
+                        }
+                        (ConstValue::Float(lhs), ConstValue::Float(rhs)) => {
+                            let value = float_arith(*op, lhs, rhs, expr)?;
+                            Ok(ConstValue::Float(self.round_float(infer, expr, value)))
+                        }
+                        (ConstValue::Bool(lhs), ConstValue::Bool(rhs)) => match op {
+                            ArithOp::BitAnd => Ok(ConstValue::Bool(lhs & rhs)),
+                            ArithOp::BitOr => Ok(ConstValue::Bool(lhs | rhs)),
+                            ArithOp::BitXor => Ok(ConstValue::Bool(lhs ^ rhs)),
+                            _ => Err(ConstEvalError::InvalidInitializer),
+                        },
+                        _ => Err(ConstEvalError::InvalidInitializer),
+                    }
+                }
+                BinaryOp::CmpOp(op) => {
+                    let lhs_expr = *lhs;
+                    let lhs = self.eval_expr(body, infer, *lhs)?;
+                    let rhs = self.eval_expr(body, infer, *rhs)?;
+                    let ordering = match (lhs, rhs) {
+                        (ConstValue::Bool(lhs), ConstValue::Bool(rhs)) => Some(lhs.cmp(&rhs)),
+                        (ConstValue::Int(lhs), ConstValue::Int(rhs)) => {
+                            match self.int_ty(infer, lhs_expr)?.signedness {
+                                Signedness::Signed => Some(lhs.cmp(&rhs)),
+                                Signedness::Unsigned => Some((lhs as u128).cmp(&(rhs as u128))),
+                            }
+                        }
+                        (ConstValue::Float(lhs), ConstValue::Float(rhs)) => lhs.partial_cmp(&rhs),
+                        _ => return Err(ConstEvalError::InvalidInitializer),
+                    };
+                    Ok(ConstValue::Bool(compare(*op, ordering)))
+                }
+                BinaryOp::Assignment { .. } => Err(ConstEvalError::NotConst(expr)),
+            },
+            Expr::If {
+                condition,
+                then_branch,
+                else_branch: Some(else_branch),
+            } => match self.eval_expr(body, infer, *condition)? {
+                ConstValue::Bool(true) => self.eval_expr(body, infer, *then_branch),
+                ConstValue::Bool(false) => self.eval_expr(body, infer, *else_branch),
+                _ => Err(ConstEvalError::InvalidInitializer),
+            },
+            Expr::Block {
+                statements,
+                tail: Some(tail),
+            } if statements.is_empty() => self.eval_expr(body, infer, *tail),
+            Expr::Missing | Expr::BinaryOp { op: None, .. } => {
+                Err(ConstEvalError::InvalidInitializer)
+            }
+            _ => Err(ConstEvalError::NotConst(expr)),
+        }
+    }
+
+    /// Returns the integer type of `expr` with its bitness resolved for the
+    /// target.
+    fn int_ty(&self, infer: &InferenceResult, expr: ExprId) -> Result<IntTy, ConstEvalError> {
+        match infer[expr].interned() {
+            TyKind::Int(ty) => Ok(ty.resolve(&self.db.target_data_layout())),
+            _ => Err(ConstEvalError::InvalidInitializer),
+        }
+    }
+
+    /// Rounds `value` to the precision of the floating-point type of `expr`.
+    fn round_float(&self, infer: &InferenceResult, expr: ExprId, value: f64) -> f64 {
+        match infer[expr].interned() {
+            TyKind::Float(ty) if ty.bitness == FloatBitness::X32 => f64::from(value as f32),
+            _ => value,
+        }
+    }
+}
+
+/// Returns the number of bits of an integer type whose bitness is resolved.
+fn bits(ty: IntTy) -> u32 {
+    match ty.bitness {
+        IntBitness::X8 => 8,
+        IntBitness::X16 => 16,
+        IntBitness::X32 => 32,
+        IntBitness::X64 => 64,
+        IntBitness::X128 | IntBitness::Xsize => 128,
+    }
+}
+
+/// Returns true if `value` can be represented by `ty`.
+fn fits(value: i128, ty: IntTy) -> bool {
+    truncate(value, ty) == value
+}
+
+/// Truncates `value` to the bits of `ty`, sign extending signed integers.
+fn truncate(value: i128, ty: IntTy) -> i128 {
+    let shift = 128 - bits(ty);
+    match ty.signedness {
+        Signedness::Signed => (value << shift) >> shift,
+        Signedness::Unsigned => ((value as u128) << shift >> shift) as i128,
+    }
+}
+
+/// Converts `value` to an integer of type `ty`. Values that are out of the range
+/// of `ty` saturate to its bounds and NaN converts to zero, like in Rust.
+fn saturate(value: f64, ty: IntTy) -> i128 {
+    match (ty.signedness, bits(ty)) {
+        (Signedness::Signed, 128) => value as i128,
+        (Signedness::Unsigned, 128) => value as u128 as i128,
+        (Signedness::Signed, bits) => {
+            (value as i128).clamp(-(1 << (bits - 1)), (1 << (bits - 1)) - 1)
+        }
+        (Signedness::Unsigned, bits) => (value as i128).clamp(0, (1 << bits) - 1),
+    }
+}
+
+/// Applies an arithmetic operator to two integers of type `ty`.
+fn int_arith(
+    op: ArithOp,
+    lhs: i128,
+    rhs: i128,
+    ty: IntTy,
+    expr: ExprId,
+) -> Result<i128, ConstEvalError> {
+    let overflow = ConstEvalError::Overflow(expr);
+    if matches!(op, ArithOp::Divide | ArithOp::Remainder) && rhs == 0 {
+        return Err(ConstEvalError::DivisionByZero(expr));
+    }
+
+    let value = match op {
+        ArithOp::LeftShift | ArithOp::RightShift => {
+            let shift = u32::try_from(rhs)
+                .ok()
+                .filter(|shift| *shift < bits(ty))
+                .ok_or(overflow)?;
+            return Ok(match (op, ty.signedness) {
+                (ArithOp::LeftShift, _) => truncate(lhs << shift, ty),
+                (_, Signedness::Signed) => lhs >> shift,
+                (_, Signedness::Unsigned) => ((lhs as u128) >> shift) as i128,
+            });
+        }
+        ArithOp::BitAnd => Some(lhs & rhs),
+        ArithOp::BitOr => Some(lhs | rhs),
+        ArithOp::BitXor => Some(lhs ^ rhs),
+        _ => match ty.signedness {
+            Signedness::Signed => match op {
+                ArithOp::Add => lhs.checked_add(rhs),
+                ArithOp::Subtract => lhs.checked_sub(rhs),
+                ArithOp::Multiply => lhs.checked_mul(rhs),
+                ArithOp::Divide => lhs.checked_div(rhs),
+                _ => lhs.checked_rem(rhs),
+            },
+            Signedness::Unsigned => {
+                let (lhs, rhs) = (lhs as u128, rhs as u128);
+                match op {
+                    ArithOp::Add => lhs.checked_add(rhs),
+                    ArithOp::Subtract => lhs.checked_sub(rhs),
+                    ArithOp::Multiply => lhs.checked_mul(rhs),
+                    ArithOp::Divide => lhs.checked_div(rhs),
+                    _ => lhs.checked_rem(rhs),
+                }
+                .map(|value| value as i128)
+            }
+        },
+    };
+
+    value.filter(|value| fits(*value, ty)).ok_or(overflow)
+}
+
+/// Applies an arithmetic operator to two floating-point numbers.
+fn float_arith(op: ArithOp, lhs: f64, rhs: f64, expr: ExprId) -> Result<f64, ConstEvalError> {
+    match op {
+        ArithOp::Add => Ok(lhs + rhs),
+        ArithOp::Subtract => Ok(lhs - rhs),
+        ArithOp::Multiply => Ok(lhs * rhs),
+        ArithOp::Divide => Ok(lhs / rhs),
+        ArithOp::Remainder => Ok(lhs % rhs),
+        _ => Err(ConstEvalError::NotConst(expr)),
+    }
+}
+
+/// Returns the result of a comparison of two values that are ordered as
+/// `ordering`, or unordered if `ordering` is `None`.
+fn compare(op: CmpOp, ordering: Option<cmp::Ordering>) -> bool {
+    match op {
+        CmpOp::Eq { negated } => (ordering == Some(cmp::Ordering::Equal)) != negated,
+        CmpOp::Ord {
+            ordering: expected,
+            strict,
+        } => match (ordering, expected) {
+            (Some(cmp::Ordering::Equal), _) => !strict,
+            (Some(cmp::Ordering::Less), Ordering::Less)
+            | (Some(cmp::Ordering::Greater), Ordering::Greater) => true,
+            _ => false,
+        },
+    }
+}
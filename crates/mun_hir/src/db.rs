@@ -7,7 +7,9 @@ use mun_syntax::{ast, Parse, SourceFile};
 use mun_target::{abi, spec::Target};
 
 use crate::{
-    code_model::{FunctionData, ImplData, StructData, TypeAliasData},
+    call_graph::CallGraph,
+    code_model::{ConstData, FunctionData, ImplData, StructData, TypeAliasData},
+    const_eval::{ConstEvalError, ConstValue},
     expr::BodySourceMap,
     ids,
     ids::{DefWithBodyId, FunctionId, ImplId},
@@ -19,8 +21,8 @@ use crate::{
     name_resolution::Namespace,
     package_defs::PackageDefs,
     ty::{lower::LowerTyMap, CallableDef, FnSig, InferenceResult, Ty, TypableDef},
-    visibility, AstIdMap, Body, ExprScopes, FileId, PackageId, PackageSet, Struct, TypeAlias,
-    Visibility,
+    visibility, AstIdMap, Body, Const, ExprScopes, FileId, PackageId, PackageSet, Struct,
+    TypeAlias, Visibility,
 };
 
 // TODO(bas): In the future maybe move this to a seperate crate (mun_db?)
@@ -86,11 +88,18 @@ pub trait InternDatabase: SourceDatabase {
     #[salsa::interned]
     fn intern_type_alias(&self, loc: ids::TypeAliasLoc) -> ids::TypeAliasId;
     #[salsa::interned]
+    fn intern_const(&self, loc: ids::ConstLoc) -> ids::ConstId;
+    #[salsa::interned]
     fn intern_impl(self, loc: ids::ImplLoc) -> ids::ImplId;
 }
 
 #[salsa::query_group(DefDatabaseStorage)]
 pub trait DefDatabase: InternDatabase + AstDatabase + Upcast<dyn AstDatabase> {
+    /// Returns the target for code generation. Items can be conditionally
+    /// compiled based on the features of the target.
+    #[salsa::input]
+    fn target(&self) -> Target;
+
     /// Returns the `ItemTree` for a specific file. An `ItemTree` represents all
     /// the top level declarations within a file.
     #[salsa::invoke(item_tree::ItemTree::item_tree_query)]
@@ -102,6 +111,9 @@ pub trait DefDatabase: InternDatabase + AstDatabase + Upcast<dyn AstDatabase> {
     #[salsa::invoke(TypeAliasData::type_alias_data_query)]
     fn type_alias_data(&self, id: ids::TypeAliasId) -> Arc<TypeAliasData>;
 
+    #[salsa::invoke(ConstData::const_data_query)]
+    fn const_data(&self, id: ids::ConstId) -> Arc<ConstData>;
+
     #[salsa::invoke(crate::FunctionData::fn_data_query)]
     fn fn_data(&self, func: FunctionId) -> Arc<FunctionData>;
 
@@ -129,10 +141,6 @@ pub trait DefDatabase: InternDatabase + AstDatabase + Upcast<dyn AstDatabase> {
 
 #[salsa::query_group(HirDatabaseStorage)]
 pub trait HirDatabase: DefDatabase + Upcast<dyn DefDatabase> {
-    /// Returns the target for code generation.
-    #[salsa::input]
-    fn target(&self) -> Target;
-
     /// Returns the `TargetDataLayout` for the current target
     #[salsa::invoke(target_data_layout)]
     fn target_data_layout(&self) -> Arc<abi::TargetDataLayout>;
@@ -146,6 +154,10 @@ pub trait HirDatabase: DefDatabase + Upcast<dyn DefDatabase> {
     #[salsa::invoke(crate::ty::lower::lower_type_alias_query)]
     fn lower_type_alias(&self, def: TypeAlias) -> Arc<LowerTyMap>;
 
+    /// Returns the value of a constant, evaluated at compile time
+    #[salsa::invoke(crate::const_eval::const_eval_query)]
+    fn const_eval(&self, def: Const) -> Result<ConstValue, ConstEvalError>;
+
     #[salsa::invoke(crate::ty::callable_item_sig)]
     fn callable_sig(&self, def: CallableDef) -> FnSig;
 
@@ -157,6 +169,10 @@ pub trait HirDatabase: DefDatabase + Upcast<dyn DefDatabase> {
 
     #[salsa::invoke(InherentImpls::inherent_impls_in_package_query)]
     fn inherent_impls_in_package(&self, package: PackageId) -> Arc<InherentImpls>;
+
+    /// Returns the call graph of all functions in all packages
+    #[salsa::invoke(CallGraph::call_graph_query)]
+    fn call_graph(&self) -> Arc<CallGraph>;
 }
 
 fn parse_query(db: &dyn AstDatabase, file_id: FileId) -> Parse<SourceFile> {
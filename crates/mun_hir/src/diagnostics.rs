@@ -1,8 +1,11 @@
 use std::{any::Any, fmt};
 
-use mun_syntax::{ast, AstPtr, SmolStr, SyntaxNode, SyntaxNodePtr, TextRange};
+use mun_syntax::{ast, AstPtr, SmolStr, SyntaxNode, SyntaxNodePtr, TextRange, TextSize};
 
-use crate::{code_model::StructKind, in_file::InFile, FileId, HirDatabase, IntTy, Name, Ty};
+use crate::{
+    code_model::StructKind, const_eval::ConstEvalError, in_file::InFile, CmpOp, FileId,
+    HirDatabase, IntTy, Name, Ty,
+};
 
 /// Diagnostic defines `mun_hir` API for errors and warnings.
 ///
@@ -20,9 +23,23 @@ pub trait Diagnostic: Any + Send + Sync + fmt::Debug + 'static {
     fn highlight_range(&self) -> TextRange {
         self.source().value.range()
     }
+    /// Returns whether this diagnostic prevents compilation. Unless
+    /// overridden, every diagnostic is an error.
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
     fn as_any(&self) -> &(dyn Any + Send + 'static);
 }
 
+/// The severity of a [`Diagnostic`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Severity {
+    /// The code cannot be compiled
+    Error,
+    /// The code can be compiled but is likely not what was intended
+    Warning,
+}
+
 pub trait AstDiagnostic {
     type AST;
     fn ast(&self, db: &dyn HirDatabase) -> Self::AST;
@@ -82,6 +99,8 @@ impl<'a> DiagnosticSink<'a> {
 pub struct UnresolvedValue {
     pub file: FileId,
     pub expr: SyntaxNodePtr,
+    /// The name of a value in scope with a similar name, if any
+    pub suggestion: Option<Name>,
 }
 
 impl Diagnostic for UnresolvedValue {
@@ -246,6 +265,64 @@ impl Diagnostic for MismatchedType {
     }
 }
 
+/// An error that is emitted when a numeric value is used where a value of
+/// another numeric type is expected, e.g. `let a: i32 = b;` where `b` is an
+/// `i64`. Numeric values are never converted implicitly, not even when the
+/// conversion is lossless; it has to be spelled out with a cast.
+#[derive(Debug)]
+pub struct MismatchedNumericType {
+    pub file: FileId,
+    /// The expression that has to be cast to the expected type
+    pub expr: SyntaxNodePtr,
+    pub expected: Ty,
+    pub found: Ty,
+    /// Whether converting the value to the expected type may lose
+    /// information, e.g. from `i64` to `i32` or from `f64` to `f32`
+    pub is_narrowing: bool,
+}
+
+impl Diagnostic for MismatchedNumericType {
+    fn message(&self) -> String {
+        if self.is_narrowing {
+            "mismatched numeric type, the conversion may lose information".to_string()
+        } else {
+            "mismatched numeric type, numeric types are not converted implicitly".to_string()
+        }
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when a value is cast to a type it cannot be
+/// converted to. Only numeric values can be cast, to other numeric types.
+#[derive(Debug)]
+pub struct InvalidCast {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub expr_ty: Ty,
+    pub cast_ty: Ty,
+}
+
+impl Diagnostic for InvalidCast {
+    fn message(&self) -> String {
+        "invalid cast, only numeric values can be cast to other numeric types".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct IncompatibleBranch {
     pub file: FileId,
@@ -294,6 +371,41 @@ impl Diagnostic for InvalidLhs {
     }
 }
 
+/// An error that is emitted when a function-local static that is not declared
+/// `mut` is assigned to.
+///
+/// ```mun
+/// fn counter() -> i64 {
+///     static N: i64 = 0;
+///     N += 1; // cannot assign to immutable static `N`
+///     N
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AssignToImmutableStatic {
+    pub file: FileId,
+
+    /// The left-hand side of the assignment
+    pub lhs: SyntaxNodePtr,
+
+    /// The pattern that binds the static
+    pub binding: SyntaxNodePtr,
+}
+
+impl Diagnostic for AssignToImmutableStatic {
+    fn message(&self) -> String {
+        "cannot assign to immutable static".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.lhs.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct MissingElseBranch {
     pub file: FileId,
@@ -337,6 +449,34 @@ impl Diagnostic for CannotApplyBinaryOp {
     }
 }
 
+/// An error that is emitted when two values of incompatible types are compared,
+/// or when values of a type are compared with an operator the type does not
+/// support.
+#[derive(Debug)]
+pub struct IncompatibleComparison {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub op: CmpOp,
+    pub lhs: SyntaxNodePtr,
+    pub lhs_ty: Ty,
+    pub rhs: SyntaxNodePtr,
+    pub rhs_ty: Ty,
+}
+
+impl Diagnostic for IncompatibleComparison {
+    fn message(&self) -> String {
+        format!("cannot compare values with `{}`", self.op)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct CannotApplyUnaryOp {
     pub file: FileId,
@@ -379,19 +519,24 @@ impl Diagnostic for DuplicateDefinition {
     }
 }
 
+/// An error that is emitted when none of the conditionally compiled definitions
+/// of a name are enabled for the target.
 #[derive(Debug)]
-pub struct ReturnMissingExpression {
-    pub file: FileId,
-    pub return_expr: SyntaxNodePtr,
+pub struct NoCfgAlternativeEnabled {
+    pub name: String,
+    pub definition: InFile<SyntaxNodePtr>,
 }
 
-impl Diagnostic for ReturnMissingExpression {
+impl Diagnostic for NoCfgAlternativeEnabled {
     fn message(&self) -> String {
-        "`return;` in a function whose return type is not `()`".to_owned()
+        format!(
+            "none of the `#[cfg]` definitions of `{}` are enabled for the target",
+            self.name
+        )
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.return_expr.clone())
+        self.definition.clone()
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -399,19 +544,21 @@ impl Diagnostic for ReturnMissingExpression {
     }
 }
 
+/// An error that is emitted when the predicate of a `#[cfg(..)]` attribute
+/// cannot be parsed.
 #[derive(Debug)]
-pub struct BreakOutsideLoop {
-    pub file: FileId,
-    pub break_expr: SyntaxNodePtr,
+pub struct MalformedCfgAttribute {
+    pub attr: InFile<SyntaxNodePtr>,
 }
 
-impl Diagnostic for BreakOutsideLoop {
+impl Diagnostic for MalformedCfgAttribute {
     fn message(&self) -> String {
-        "`break` outside of a loop".to_owned()
+        "malformed `cfg` attribute, expected a predicate like `target_feature = \"avx2\"`"
+            .to_owned()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.break_expr.clone())
+        self.attr.clone()
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -419,19 +566,21 @@ impl Diagnostic for BreakOutsideLoop {
     }
 }
 
+/// An error that is emitted when an item has an attribute that is not
+/// recognized for that kind of item.
 #[derive(Debug)]
-pub struct BreakWithValueOutsideLoop {
-    pub file: FileId,
-    pub break_expr: SyntaxNodePtr,
+pub struct UnknownAttribute {
+    pub name: String,
+    pub attr: InFile<SyntaxNodePtr>,
 }
 
-impl Diagnostic for BreakWithValueOutsideLoop {
+impl Diagnostic for UnknownAttribute {
     fn message(&self) -> String {
-        "`break` with value can only appear in a `loop`".to_owned()
+        format!("unknown attribute `{}`", self.name)
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.break_expr.clone())
+        self.attr.clone()
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -439,21 +588,25 @@ impl Diagnostic for BreakWithValueOutsideLoop {
     }
 }
 
+/// An error that is emitted when two `#[no_mangle]` functions export the same
+/// symbol.
 #[derive(Debug)]
-pub struct AccessUnknownField {
-    pub file: FileId,
-    pub expr: SyntaxNodePtr,
-    pub receiver_ty: Ty,
-    pub name: Name,
+pub struct DuplicateNoMangleSymbol {
+    pub name: String,
+    pub first_definition: InFile<SyntaxNodePtr>,
+    pub definition: InFile<SyntaxNodePtr>,
 }
 
-impl Diagnostic for AccessUnknownField {
+impl Diagnostic for DuplicateNoMangleSymbol {
     fn message(&self) -> String {
-        "attempted to access a non-existent field in a struct.".to_string()
+        format!(
+            "the symbol `{}` is already exported by another `#[no_mangle]` function",
+            self.name
+        )
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.expr.clone())
+        self.definition.clone()
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -461,27 +614,21 @@ impl Diagnostic for AccessUnknownField {
     }
 }
 
+/// An error that is emitted when an `#[init]` function takes arguments,
+/// returns a value or is extern. The runtime calls `#[init]` functions without
+/// arguments and ignores their result.
 #[derive(Debug)]
-pub struct FieldCountMismatch {
-    pub file: FileId,
-    pub expr: SyntaxNodePtr,
-    pub expected: usize,
-    pub found: usize,
+pub struct InvalidInitFunction {
+    pub definition: InFile<SyntaxNodePtr>,
 }
 
-impl Diagnostic for FieldCountMismatch {
+impl Diagnostic for InvalidInitFunction {
     fn message(&self) -> String {
-        format!(
-            "this tuple struct literal has {} field{} but {} field{} supplied",
-            self.expected,
-            if self.expected == 1 { "" } else { "s" },
-            self.found,
-            if self.found == 1 { " was" } else { "s were" },
-        )
+        "`#[init]` functions cannot be extern, take arguments or return a value".to_owned()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.expr.clone())
+        self.definition.clone()
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -490,25 +637,18 @@ impl Diagnostic for FieldCountMismatch {
 }
 
 #[derive(Debug)]
-pub struct MissingFields {
+pub struct ReturnMissingExpression {
     pub file: FileId,
-    pub fields: SyntaxNodePtr,
-    pub struct_ty: Ty,
-    pub field_names: Vec<Name>,
+    pub return_expr: SyntaxNodePtr,
 }
 
-impl Diagnostic for MissingFields {
+impl Diagnostic for ReturnMissingExpression {
     fn message(&self) -> String {
-        use std::fmt::Write;
-        let mut message = "missing record fields:\n".to_string();
-        for field in &self.field_names {
-            writeln!(message, "- {field}").unwrap();
-        }
-        message
+        "`return;` in a function whose return type is not `()`".to_owned()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.fields.clone())
+        InFile::new(self.file, self.return_expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -517,19 +657,15 @@ impl Diagnostic for MissingFields {
 }
 
 #[derive(Debug)]
-pub struct MismatchedStructLit {
+pub struct NotAllPathsReturnValue {
     pub file: FileId,
     pub expr: SyntaxNodePtr,
-    pub expected: StructKind,
-    pub found: StructKind,
+    pub expected: Ty,
 }
 
-impl Diagnostic for MismatchedStructLit {
+impl Diagnostic for NotAllPathsReturnValue {
     fn message(&self) -> String {
-        format!(
-            "mismatched struct literal kind. expected `{}`, found `{}`",
-            self.expected, self.found
-        )
+        "not all paths return a value".to_owned()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
@@ -541,39 +677,46 @@ impl Diagnostic for MismatchedStructLit {
     }
 }
 
+/// An error that is emitted when a function without a return type returns a
+/// value from its body, e.g. `fn foo() { 5 }`.
 #[derive(Debug)]
-pub struct NoFields {
+pub struct MissingReturnType {
     pub file: FileId,
-    pub receiver_expr: SyntaxNodePtr,
+    pub expr: SyntaxNodePtr,
     pub found: Ty,
 }
 
-impl Diagnostic for NoFields {
+impl Diagnostic for MissingReturnType {
     fn message(&self) -> String {
-        "attempted to access a field on a primitive type.".to_string()
+        "function without a return type returns a value".to_owned()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.receiver_expr.clone())
+        InFile::new(self.file, self.expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
         self
     }
 }
+
 #[derive(Debug)]
-pub struct NoSuchField {
+pub struct BreakOutsideLoop {
     pub file: FileId,
-    pub field: SyntaxNodePtr,
+    pub break_expr: SyntaxNodePtr,
 }
 
-impl Diagnostic for NoSuchField {
+impl Diagnostic for BreakOutsideLoop {
     fn message(&self) -> String {
-        "no such field".to_string()
+        "`break` outside of a loop".to_owned()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.field.clone())
+        InFile::new(self.file, self.break_expr.clone())
+    }
+
+    fn highlight_range(&self) -> TextRange {
+        TextRange::at(self.break_expr.range().start(), TextSize::of("break"))
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -582,18 +725,18 @@ impl Diagnostic for NoSuchField {
 }
 
 #[derive(Debug)]
-pub struct PossiblyUninitializedVariable {
+pub struct BreakWithValueOutsideLoop {
     pub file: FileId,
-    pub pat: SyntaxNodePtr,
+    pub break_expr: SyntaxNodePtr,
 }
 
-impl Diagnostic for PossiblyUninitializedVariable {
+impl Diagnostic for BreakWithValueOutsideLoop {
     fn message(&self) -> String {
-        "use of possibly-uninitialized variable".to_string()
+        "`break` with value can only appear in a `loop`".to_owned()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        InFile::new(self.file, self.pat.clone())
+        InFile::new(self.file, self.break_expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -602,17 +745,22 @@ impl Diagnostic for PossiblyUninitializedVariable {
 }
 
 #[derive(Debug)]
-pub struct ExternCannotHaveBody {
-    pub func: InFile<SyntaxNodePtr>,
+pub struct ContinueOutsideLoop {
+    pub file: FileId,
+    pub continue_expr: SyntaxNodePtr,
 }
 
-impl Diagnostic for ExternCannotHaveBody {
+impl Diagnostic for ContinueOutsideLoop {
     fn message(&self) -> String {
-        "extern functions cannot have bodies".to_string()
+        "`continue` outside of a loop".to_owned()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.func.clone()
+        InFile::new(self.file, self.continue_expr.clone())
+    }
+
+    fn highlight_range(&self) -> TextRange {
+        TextRange::at(self.continue_expr.range().start(), TextSize::of("continue"))
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -620,18 +768,29 @@ impl Diagnostic for ExternCannotHaveBody {
     }
 }
 
+/// An error that is emitted for a `break` or `continue` whose label does not
+/// refer to any of the loops that enclose it.
 #[derive(Debug)]
-pub struct ExternNonPrimitiveParam {
-    pub param: InFile<SyntaxNodePtr>,
+pub struct UnresolvedLabel {
+    pub file: FileId,
+    /// The `break` or `continue` expression
+    pub expr: SyntaxNodePtr,
+    pub label: Name,
+    /// The range of the label within the expression
+    pub label_range: TextRange,
 }
 
-impl Diagnostic for ExternNonPrimitiveParam {
+impl Diagnostic for UnresolvedLabel {
     fn message(&self) -> String {
-        "extern functions can only have primitives as parameter- and return types".to_string()
+        format!("cannot find loop label `{}`", self.label)
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.param.clone()
+        InFile::new(self.file, self.expr.clone())
+    }
+
+    fn highlight_range(&self) -> TextRange {
+        self.label_range
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -639,19 +798,39 @@ impl Diagnostic for ExternNonPrimitiveParam {
     }
 }
 
-/// An error that is emitted if a literal is too large to even parse
+/// An error that is emitted when the `break` expressions of a `loop` don't
+/// agree on the value of the loop, e.g. because `break` with and without a
+/// value are mixed.
 #[derive(Debug)]
-pub struct IntLiteralTooLarge {
-    pub literal: InFile<AstPtr<ast::Literal>>,
+pub struct MismatchedBreakValues {
+    pub file: FileId,
+    /// The first `break` whose value conflicts with an earlier `break`
+    pub break_expr: SyntaxNodePtr,
+    /// All `break` expressions of the loop with the type of their value, or
+    /// `None` if the `break` has no value
+    pub breaks: Vec<(SyntaxNodePtr, Option<Ty>)>,
 }
 
-impl Diagnostic for IntLiteralTooLarge {
+impl MismatchedBreakValues {
+    /// Returns true if the loop contains `break` expressions both with and
+    /// without a value.
+    pub fn is_mixed(&self) -> bool {
+        self.breaks.iter().any(|(_, ty)| ty.is_some())
+            && self.breaks.iter().any(|(_, ty)| ty.is_none())
+    }
+}
+
+impl Diagnostic for MismatchedBreakValues {
     fn message(&self) -> String {
-        "int literal is too large".to_owned()
+        if self.is_mixed() {
+            "`break` with and without a value in the same `loop`".to_owned()
+        } else {
+            "`break` values of the same `loop` have different types".to_owned()
+        }
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.literal.clone().map(Into::into)
+        InFile::new(self.file, self.break_expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -659,20 +838,21 @@ impl Diagnostic for IntLiteralTooLarge {
     }
 }
 
-/// An error that is emitted if a literal is too large for its suffix
 #[derive(Debug)]
-pub struct LiteralOutOfRange {
-    pub literal: InFile<AstPtr<ast::Literal>>,
-    pub int_ty: IntTy,
+pub struct AccessUnknownField {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub receiver_ty: Ty,
+    pub name: Name,
 }
 
-impl Diagnostic for LiteralOutOfRange {
+impl Diagnostic for AccessUnknownField {
     fn message(&self) -> String {
-        format!("literal out of range for `{}`", self.int_ty.as_str())
+        "attempted to access a non-existent field in a struct.".to_string()
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.literal.clone().map(Into::into)
+        InFile::new(self.file, self.expr.clone())
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -680,21 +860,364 @@ impl Diagnostic for LiteralOutOfRange {
     }
 }
 
-/// An error that is emitted for a literal with an invalid suffix (e.g.
-/// `123_foo`)
 #[derive(Debug)]
-pub struct InvalidLiteralSuffix {
-    pub literal: InFile<AstPtr<ast::Literal>>,
-    pub suffix: SmolStr,
+pub struct FieldCountMismatch {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub expected: usize,
+    pub found: usize,
 }
 
-impl Diagnostic for InvalidLiteralSuffix {
+impl Diagnostic for FieldCountMismatch {
     fn message(&self) -> String {
-        format!("invalid suffix `{}`", self.suffix)
+        format!(
+            "this tuple struct literal has {} field{} but {} field{} supplied",
+            self.expected,
+            if self.expected == 1 { "" } else { "s" },
+            self.found,
+            if self.found == 1 { " was" } else { "s were" },
+        )
     }
 
     fn source(&self) -> InFile<SyntaxNodePtr> {
-        self.literal.clone().map(Into::into)
+        InFile::new(self.file, self.expr.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct MissingFields {
+    pub file: FileId,
+    pub fields: SyntaxNodePtr,
+    pub struct_ty: Ty,
+    pub field_names: Vec<Name>,
+}
+
+impl Diagnostic for MissingFields {
+    fn message(&self) -> String {
+        use std::fmt::Write;
+        let mut message = "missing record fields:\n".to_string();
+        for field in &self.field_names {
+            writeln!(message, "- {field}").unwrap();
+        }
+        message
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.fields.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct MismatchedStructLit {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub expected: StructKind,
+    pub found: StructKind,
+}
+
+impl Diagnostic for MismatchedStructLit {
+    fn message(&self) -> String {
+        format!(
+            "mismatched struct literal kind. expected `{}`, found `{}`",
+            self.expected, self.found
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct NoFields {
+    pub file: FileId,
+    pub receiver_expr: SyntaxNodePtr,
+    pub found: Ty,
+}
+
+impl Diagnostic for NoFields {
+    fn message(&self) -> String {
+        "attempted to access a field on a primitive type.".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.receiver_expr.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+#[derive(Debug)]
+pub struct NoSuchField {
+    pub file: FileId,
+    pub field: SyntaxNodePtr,
+    pub name: Name,
+    pub struct_ty: Ty,
+}
+
+impl Diagnostic for NoSuchField {
+    fn message(&self) -> String {
+        format!("no such field `{}`", self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.field.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct PossiblyUninitializedVariable {
+    pub file: FileId,
+    pub pat: SyntaxNodePtr,
+}
+
+impl Diagnostic for PossiblyUninitializedVariable {
+    fn message(&self) -> String {
+        "use of possibly-uninitialized variable".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.pat.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct ExternCannotHaveBody {
+    pub func: InFile<SyntaxNodePtr>,
+}
+
+impl Diagnostic for ExternCannotHaveBody {
+    fn message(&self) -> String {
+        "extern functions cannot have bodies".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.func.clone()
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct ExternNonPrimitiveParam {
+    pub param: InFile<SyntaxNodePtr>,
+}
+
+impl Diagnostic for ExternNonPrimitiveParam {
+    fn message(&self) -> String {
+        "extern functions can only have primitives as parameter- and return types".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.param.clone()
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when a slice type is used anywhere other than as
+/// the type of a parameter of an extern function.
+#[derive(Debug)]
+pub struct InvalidSliceType {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+}
+
+impl Diagnostic for InvalidSliceType {
+    fn message(&self) -> String {
+        "slice types can only be used for parameters of extern functions".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when a mutable reference type is used anywhere
+/// other than as the type of a function parameter, or when it refers to a type
+/// that is not a primitive.
+#[derive(Debug)]
+pub struct InvalidRefType {
+    pub file: FileId,
+    pub type_ref: AstPtr<ast::TypeRef>,
+}
+
+impl Diagnostic for InvalidRefType {
+    fn message(&self) -> String {
+        "mutable references can only be used for function parameters of primitive types".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.syntax_node_ptr())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when the type of a function-local static is not a
+/// primitive type.
+#[derive(Debug)]
+pub struct InvalidStaticType {
+    pub file: FileId,
+    pub pat: SyntaxNodePtr,
+}
+
+impl Diagnostic for InvalidStaticType {
+    fn message(&self) -> String {
+        "statics can only be of primitive types".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.pat.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when the type of a constant is not a primitive
+/// type.
+#[derive(Debug)]
+pub struct InvalidConstType {
+    pub file: FileId,
+    pub type_ref: SyntaxNodePtr,
+}
+
+impl Diagnostic for InvalidConstType {
+    fn message(&self) -> String {
+        "constants can only be of type `bool` or of an integer or floating-point type".to_string()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.type_ref.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when the value of a constant cannot be computed
+/// at compile time.
+#[derive(Debug)]
+pub struct ConstEvalFailed {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+    pub error: ConstEvalError,
+}
+
+impl Diagnostic for ConstEvalFailed {
+    fn message(&self) -> String {
+        format!("evaluation of constant value failed: {}", self.error)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted if a literal is too large to even parse
+#[derive(Debug)]
+pub struct IntLiteralTooLarge {
+    pub literal: InFile<AstPtr<ast::Literal>>,
+}
+
+impl Diagnostic for IntLiteralTooLarge {
+    fn message(&self) -> String {
+        "int literal is too large".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.literal.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted if a literal is too large for its suffix or, if the
+/// literal was not constrained by its usage, for the type it defaulted to.
+#[derive(Debug)]
+pub struct LiteralOutOfRange {
+    pub literal: InFile<AstPtr<ast::Literal>>,
+    pub int_ty: IntTy,
+    pub is_defaulted: bool,
+}
+
+impl Diagnostic for LiteralOutOfRange {
+    fn message(&self) -> String {
+        if self.is_defaulted {
+            format!(
+                "literal out of range for `{}`, the type of integer literals that are not \
+                 otherwise constrained; consider adding a type annotation or suffix",
+                self.int_ty.as_str()
+            )
+        } else {
+            format!("literal out of range for `{}`", self.int_ty.as_str())
+        }
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.literal.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for a literal with an invalid suffix (e.g.
+/// `123_foo`)
+#[derive(Debug)]
+pub struct InvalidLiteralSuffix {
+    pub literal: InFile<AstPtr<ast::Literal>>,
+    pub suffix: SmolStr,
+}
+
+impl Diagnostic for InvalidLiteralSuffix {
+    fn message(&self) -> String {
+        format!("invalid suffix `{}`", self.suffix)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.literal.clone().map(Into::into)
     }
 
     fn as_any(&self) -> &(dyn Any + Send + 'static) {
@@ -864,3 +1387,330 @@ impl Diagnostic for InvalidSelfTyImpl {
         self
     }
 }
+
+#[derive(Debug)]
+pub struct InvalidSizeAssertion {
+    pub attr: InFile<AstPtr<ast::Attr>>,
+}
+
+impl Diagnostic for InvalidSizeAssertion {
+    fn message(&self) -> String {
+        String::from("expected a single integer literal, e.g. `#[assert_size(16)]`")
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.attr.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for a `#[link_section]` attribute whose value is
+/// not a string literal.
+#[derive(Debug)]
+pub struct MalformedLinkSection {
+    pub attr: InFile<AstPtr<ast::Attr>>,
+}
+
+impl Diagnostic for MalformedLinkSection {
+    fn message(&self) -> String {
+        String::from("expected a string literal, e.g. `#[link_section = \".config\"]`")
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.attr.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when a global is placed in a section that the
+/// object format of the target does not support, or that cannot hold the
+/// global.
+#[derive(Debug)]
+pub struct InvalidLinkSection {
+    pub attr: InFile<AstPtr<ast::Attr>>,
+    pub name: String,
+    pub reason: String,
+}
+
+impl Diagnostic for InvalidLinkSection {
+    fn message(&self) -> String {
+        format!(
+            "cannot place the global in section `{}` on the current target: {}",
+            self.name, self.reason
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.attr.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted when a global is placed in several sections, or
+/// when constants and statics are placed in the same section.
+#[derive(Debug)]
+pub struct ConflictingLinkSection {
+    pub attr: InFile<AstPtr<ast::Attr>>,
+    pub message: String,
+}
+
+impl Diagnostic for ConflictingLinkSection {
+    fn message(&self) -> String {
+        self.message.clone()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.attr.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct StructSizeMismatch {
+    pub attr: InFile<AstPtr<ast::Attr>>,
+    pub name: Name,
+    pub expected: u64,
+    pub actual: u64,
+}
+
+impl Diagnostic for StructSizeMismatch {
+    fn message(&self) -> String {
+        format!(
+            "struct `{}` is asserted to be {} bytes but is {} bytes on the current target",
+            self.name, self.expected, self.actual
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.attr.clone().map(Into::into)
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A warning that is emitted for a loop that can never terminate and whose
+/// body has no observable effects, e.g. `loop {}` or `while true {}`. The
+/// warning is suppressed with `#[allow(infinite_loop)]` on the function.
+#[derive(Debug)]
+pub struct InfiniteLoop {
+    pub file: FileId,
+    pub loop_expr: SyntaxNodePtr,
+}
+
+impl InfiniteLoop {
+    pub const LINT_NAME: &'static str = "infinite_loop";
+}
+
+impl Diagnostic for InfiniteLoop {
+    fn message(&self) -> String {
+        "this loop never terminates and has no side effects".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.loop_expr.clone())
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A warning that is emitted for an assignment of a place to itself, e.g.
+/// `x = x;` or `a.b = a.b;`, which has no effect. The warning is suppressed
+/// with `#[allow(self_assignment)]` on the function.
+#[derive(Debug)]
+pub struct SelfAssignment {
+    pub file: FileId,
+    pub assignment: SyntaxNodePtr,
+}
+
+impl SelfAssignment {
+    pub const LINT_NAME: &'static str = "self_assignment";
+}
+
+impl Diagnostic for SelfAssignment {
+    fn message(&self) -> String {
+        "assigning a value to itself has no effect".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.assignment.clone())
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A warning that is emitted for an `if` expression of which all branches are
+/// empty, e.g. `if cond {} else {}`. The warning is suppressed with
+/// `#[allow(empty_if)]` on the function.
+#[derive(Debug)]
+pub struct EmptyIf {
+    pub file: FileId,
+    pub if_expr: SyntaxNodePtr,
+}
+
+impl EmptyIf {
+    pub const LINT_NAME: &'static str = "empty_if";
+}
+
+impl Diagnostic for EmptyIf {
+    fn message(&self) -> String {
+        "all branches of this `if` are empty".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.if_expr.clone())
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A warning that is emitted for a statement that evaluates an expression
+/// without any observable effects and discards its value, e.g. `a + b;`. The
+/// warning is suppressed with `#[allow(no_effect)]` on the function.
+#[derive(Debug)]
+pub struct NoEffect {
+    pub file: FileId,
+    pub expr: SyntaxNodePtr,
+}
+
+impl NoEffect {
+    pub const LINT_NAME: &'static str = "no_effect";
+}
+
+impl Diagnostic for NoEffect {
+    fn message(&self) -> String {
+        "this statement has no effect".to_owned()
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.expr.clone())
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// A warning that is emitted for a function that can never be called, because
+/// it is not reachable from any exported function. The warning is suppressed
+/// with `#[allow(dead_code)]` on the function.
+#[derive(Debug)]
+pub struct UnusedFunction {
+    pub name: String,
+    pub definition: InFile<SyntaxNodePtr>,
+}
+
+impl UnusedFunction {
+    pub const LINT_NAME: &'static str = "dead_code";
+}
+
+impl Diagnostic for UnusedFunction {
+    fn message(&self) -> String {
+        format!("function `{}` is never used", self.name)
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        self.definition.clone()
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for a call of a `#[tail_call]` function to itself
+/// that is not in tail position, e.g. `1 + fib(n - 1)`. Such a call cannot
+/// reuse the stack frame of the function.
+#[derive(Debug)]
+pub struct NonTailRecursiveCall {
+    pub file: FileId,
+    pub call: SyntaxNodePtr,
+    pub name: String,
+}
+
+impl Diagnostic for NonTailRecursiveCall {
+    fn message(&self) -> String {
+        format!(
+            "recursive call to `#[tail_call]` function `{}` is not in tail position; its result \
+             must be returned directly, e.g. `return {}(..)`, for the call to reuse the stack frame",
+            self.name, self.name
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.call.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
+
+/// An error that is emitted for a call in tail position of a `#[tail_call]`
+/// function whose callee has a different signature than the function itself.
+/// The callee can only reuse the stack frame of the caller if the parameters
+/// and return values are passed in the same way.
+#[derive(Debug)]
+pub struct TailCallSignatureMismatch {
+    pub file: FileId,
+    pub call: SyntaxNodePtr,
+    pub caller_signature: String,
+    pub callee_signature: String,
+}
+
+impl Diagnostic for TailCallSignatureMismatch {
+    fn message(&self) -> String {
+        format!(
+            "cannot compile tail call: the callee's signature `{}` does not match the signature \
+             `{}` of the `#[tail_call]` function, so it cannot reuse the caller's stack frame",
+            self.callee_signature, self.caller_signature
+        )
+    }
+
+    fn source(&self) -> InFile<SyntaxNodePtr> {
+        InFile::new(self.file, self.call.clone())
+    }
+
+    fn as_any(&self) -> &(dyn Any + Send + 'static) {
+        self
+    }
+}
@@ -116,6 +116,15 @@ fn write_type_ref(
             write_type_ref(*element_ty, container, f)?;
             write!(f, "]")
         }
+        TypeRef::Slice(element_ty) => {
+            write!(f, "&[")?;
+            write_type_ref(*element_ty, container, f)?;
+            write!(f, "]")
+        }
+        TypeRef::Ref(inner) => {
+            write!(f, "&mut ")?;
+            write_type_ref(*inner, container, f)
+        }
         TypeRef::Never => write!(f, "!"),
         TypeRef::Tuple(elems) => {
             write!(f, "(")?;
@@ -127,6 +136,21 @@ fn write_type_ref(
             }
             write!(f, ")")
         }
+        TypeRef::FnPtr { params, ret } => {
+            write!(f, "fn(")?;
+            for (idx, param) in params.iter().enumerate() {
+                if idx != 0 {
+                    write!(f, ", ")?;
+                }
+                write_type_ref(*param, container, f)?;
+            }
+            write!(f, ")")?;
+            if !matches!(&container[*ret], TypeRef::Tuple(elems) if elems.is_empty()) {
+                write!(f, " -> ")?;
+                write_type_ref(*ret, container, f)?;
+            }
+            Ok(())
+        }
         TypeRef::Error => write!(f, "{{error}}"),
     }
 }
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, ops::Index, str::FromStr, sync::Arc};
+use std::{borrow::Cow, fmt, ops::Index, str::FromStr, sync::Arc};
 
 use either::Either;
 use la_arena::{Arena, ArenaMap, Idx};
@@ -6,13 +6,13 @@ pub use mun_syntax::ast::PrefixOp as UnaryOp;
 use mun_syntax::{
     ast,
     ast::{ArgListOwner, BinOp, LoopBodyOwner, NameOwner, TypeAscriptionOwner},
-    AstNode, AstPtr,
+    AstNode, AstPtr, SyntaxToken,
 };
 use rustc_hash::FxHashMap;
 
 pub use self::scope::ExprScopes;
 use crate::{
-    code_model::{src::HasSource, DefWithBody},
+    code_model::{src::HasSource, DefWithBody, LinkSection},
     diagnostics::DiagnosticSink,
     ids::{DefWithBodyId, Lookup},
     in_file::InFile,
@@ -27,10 +27,14 @@ pub(crate) mod validator;
 
 pub type ExprId = Idx<Expr>;
 pub type PatId = Idx<Pat>;
+pub type LabelId = Idx<Label>;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum ExprDiagnostic {
     LiteralError { expr: ExprId, err: LiteralError },
+    BreakOutsideLoop { expr: ExprId },
+    ContinueOutsideLoop { expr: ExprId },
+    UnresolvedLabel { expr: ExprId, label: Name },
 }
 
 /// The body of an item (function, const etc.).
@@ -39,6 +43,7 @@ pub struct Body {
     owner: DefWithBodyId,
     exprs: Arena<Expr>,
     pats: Arena<Pat>,
+    labels: Arena<Label>,
     type_refs: TypeRefMap,
     /// The patterns for the function's parameters. While the parameter types
     /// are part of the function signature, the patterns are not (they don't
@@ -72,6 +77,12 @@ impl Body {
                 collector = ExprCollector::new(def, src.file_id, db);
                 collector.collect_fn_body(&src.value);
             }
+            DefWithBodyId::ConstId(c) => {
+                let c = c.lookup(db);
+                let src = c.source(db);
+                collector = ExprCollector::new(def, src.file_id, db);
+                collector.collect_const_body(&src.value);
+            }
         }
 
         let (body, source_map) = collector.finish();
@@ -106,6 +117,46 @@ impl Body {
         self.ret_type
     }
 
+    /// Returns the expressions in tail position: the expressions whose value
+    /// is returned from the body without any further evaluation. These are
+    /// the body expression and the operands of `return` expressions, and
+    /// recursively the tails of blocks and both branches of an `if`/`else`.
+    pub fn tail_exprs(&self) -> Vec<ExprId> {
+        let mut tail_exprs = Vec::new();
+        let mut queue: Vec<ExprId> = self
+            .exprs()
+            .filter_map(|(_, expr)| match expr {
+                Expr::Return { expr } => *expr,
+                _ => None,
+            })
+            .collect();
+        queue.push(self.body_expr);
+
+        while let Some(expr_id) = queue.pop() {
+            match &self[expr_id] {
+                Expr::Block {
+                    tail: Some(tail), ..
+                } => queue.push(*tail),
+                Expr::If {
+                    then_branch,
+                    else_branch: Some(else_branch),
+                    ..
+                } => {
+                    queue.push(*then_branch);
+                    queue.push(*else_branch);
+                }
+                _ => tail_exprs.push(expr_id),
+            }
+        }
+
+        tail_exprs
+    }
+
+    /// Returns true if errors were encountered when lowering the body.
+    pub(crate) fn has_diagnostics(&self) -> bool {
+        !self.diagnostics.is_empty()
+    }
+
     /// Adds all the `InferenceDiagnostic`s of the result to the
     /// `DiagnosticSink`.
     pub(crate) fn add_diagnostics(
@@ -128,6 +179,14 @@ impl Index<ExprId> for Body {
     }
 }
 
+impl Index<LabelId> for Body {
+    type Output = Label;
+
+    fn index(&self, label: LabelId) -> &Label {
+        &self.labels[label]
+    }
+}
+
 impl Index<PatId> for Body {
     type Output = Pat;
 
@@ -207,6 +266,12 @@ pub struct RecordLitField {
     pub expr: ExprId,
 }
 
+/// The label of a loop, e.g. `'outer` in `'outer: loop {}`
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Label {
+    pub name: Name,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Statement {
     Let {
@@ -214,6 +279,18 @@ pub enum Statement {
         type_ref: Option<LocalTypeRefId>,
         initializer: Option<ExprId>,
     },
+    /// A function-local static, e.g. `static mut N: i64 = 0;`. The value of
+    /// the static persists across calls of the function and its initializer
+    /// is evaluated only once, the first time the statement is reached. Only
+    /// statics that are declared `mut` can be assigned to.
+    Static {
+        pat: PatId,
+        type_ref: Option<LocalTypeRefId>,
+        initializer: ExprId,
+        is_mut: bool,
+        /// The `#[link_section]` attributes of the static, in order
+        link_sections: Vec<LinkSection>,
+    },
     Expr(ExprId),
 }
 
@@ -298,6 +375,12 @@ pub enum Expr {
         base: ExprId,
         index: ExprId,
     },
+    /// A conversion of a numeric value to another numeric type, e.g. `a as
+    /// i32`
+    Cast {
+        expr: ExprId,
+        type_ref: LocalTypeRefId,
+    },
     Block {
         statements: Vec<Statement>,
         tail: Option<ExprId>,
@@ -305,15 +388,36 @@ pub enum Expr {
     Return {
         expr: Option<ExprId>,
     },
+    /// A `break` out of the innermost loop, or out of the loop with the
+    /// specified label
     Break {
         expr: Option<ExprId>,
+        label: Option<LabelId>,
+    },
+    /// A `continue` of the innermost loop, or of the loop with the specified
+    /// label
+    Continue {
+        label: Option<LabelId>,
     },
     Loop {
         body: ExprId,
+        label: Option<LabelId>,
     },
     While {
         condition: ExprId,
         body: ExprId,
+        label: Option<LabelId>,
+    },
+    /// A loop over the elements of an array, e.g. `for x in arr { .. }`. The
+    /// loop is equivalent to a `while` loop that indexes the array from `0` up
+    /// to the length of the array when the loop is entered, and binds `pat` to
+    /// a copy of each element in turn. Elements of a `struct(gc)` type are
+    /// references, so the binding refers to the same object as the array.
+    For {
+        pat: PatId,
+        iterable: ExprId,
+        body: ExprId,
+        label: Option<LabelId>,
     },
     RecordLit {
         type_id: LocalTypeRefId,
@@ -325,6 +429,8 @@ pub enum Expr {
         name: Name,
     },
     Array(Vec<ExprId>),
+    /// A tuple of values, currently only the unit value `()`
+    Tuple(Vec<ExprId>),
     Literal(Literal),
 }
 
@@ -348,6 +454,32 @@ pub enum CmpOp {
     Ord { ordering: Ordering, strict: bool },
 }
 
+impl fmt::Display for CmpOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self {
+            CmpOp::Eq { negated: false } => "==",
+            CmpOp::Eq { negated: true } => "!=",
+            CmpOp::Ord {
+                ordering: Ordering::Less,
+                strict: true,
+            } => "<",
+            CmpOp::Ord {
+                ordering: Ordering::Less,
+                strict: false,
+            } => "<=",
+            CmpOp::Ord {
+                ordering: Ordering::Greater,
+                strict: true,
+            } => ">",
+            CmpOp::Ord {
+                ordering: Ordering::Greater,
+                strict: false,
+            } => ">=",
+        };
+        f.write_str(op)
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Ordering {
     Less,
@@ -371,7 +503,7 @@ pub enum ArithOp {
 impl Expr {
     pub fn walk_child_exprs(&self, mut f: impl FnMut(ExprId)) {
         match self {
-            Expr::Missing | Expr::Path(_) | Expr::Literal(_) => {}
+            Expr::Missing | Expr::Path(_) | Expr::Literal(_) | Expr::Continue { .. } => {}
             Expr::Block { statements, tail } => {
                 for stmt in statements {
                     match stmt {
@@ -380,6 +512,7 @@ impl Expr {
                                 f(*expr);
                             }
                         }
+                        Statement::Static { initializer, .. } => f(*initializer),
                         Statement::Expr(e) => f(*e),
                     }
                 }
@@ -397,7 +530,7 @@ impl Expr {
                 f(*lhs);
                 f(*rhs);
             }
-            Expr::Field { expr, .. } | Expr::UnaryOp { expr, .. } => {
+            Expr::Field { expr, .. } | Expr::UnaryOp { expr, .. } | Expr::Cast { expr, .. } => {
                 f(*expr);
             }
             Expr::If {
@@ -411,18 +544,24 @@ impl Expr {
                     f(*else_expr);
                 }
             }
-            Expr::Return { expr } | Expr::Break { expr } => {
+            Expr::Return { expr } | Expr::Break { expr, .. } => {
                 if let Some(expr) = expr {
                     f(*expr);
                 }
             }
-            Expr::Loop { body } => {
+            Expr::Loop { body, .. } => {
                 f(*body);
             }
-            Expr::While { condition, body } => {
+            Expr::While {
+                condition, body, ..
+            } => {
                 f(*condition);
                 f(*body);
             }
+            Expr::For { iterable, body, .. } => {
+                f(*iterable);
+                f(*body);
+            }
             Expr::RecordLit { fields, spread, .. } => {
                 for field in fields {
                     f(field.expr);
@@ -435,7 +574,7 @@ impl Expr {
                 f(*base);
                 f(*index);
             }
-            Expr::Array(exprs) => {
+            Expr::Array(exprs) | Expr::Tuple(exprs) => {
                 for expr in exprs {
                     f(*expr);
                 }
@@ -464,6 +603,10 @@ pub(crate) struct ExprCollector<'a> {
     owner: DefWithBodyId,
     exprs: Arena<Expr>,
     pats: Arena<Pat>,
+    labels: Arena<Label>,
+    /// The labels of the loops that enclose the expression that is being
+    /// collected, innermost last
+    loops: Vec<Option<LabelId>>,
     source_map: BodySourceMap,
     params: Vec<(PatId, LocalTypeRefId)>,
     body_expr: Option<ExprId>,
@@ -480,6 +623,8 @@ impl<'a> ExprCollector<'a> {
             db,
             exprs: Arena::default(),
             pats: Arena::default(),
+            labels: Arena::default(),
+            loops: Vec::new(),
             source_map: BodySourceMap::default(),
             params: Vec::new(),
             body_expr: None,
@@ -550,6 +695,16 @@ impl<'a> ExprCollector<'a> {
         self.ret_type = Some(ret_type);
     }
 
+    fn collect_const_body(&mut self, node: &ast::ConstDef) {
+        let body = self.collect_expr_opt(node.initializer());
+        self.body_expr = Some(body);
+
+        let ty = self
+            .type_ref_builder
+            .alloc_from_node_opt(node.ascribed_type().as_ref());
+        self.ret_type = Some(ty);
+    }
+
     fn collect_block_opt(&mut self, block: Option<ast::BlockExpr>) -> ExprId {
         if let Some(block) = block {
             self.collect_block(block)
@@ -575,6 +730,20 @@ impl<'a> ExprCollector<'a> {
                         initializer,
                     }
                 }
+                ast::StmtKind::StaticStmt(stmt) => {
+                    let pat = self.collect_pat_opt(stmt.pat());
+                    let type_ref = stmt
+                        .ascribed_type()
+                        .map(|t| self.type_ref_builder.alloc_from_node(&t));
+                    let initializer = self.collect_expr_opt(stmt.initializer());
+                    Statement::Static {
+                        pat,
+                        type_ref,
+                        initializer,
+                        is_mut: stmt.is_mut(),
+                        link_sections: LinkSection::from_attrs(&stmt),
+                    }
+                }
                 ast::StmtKind::ExprStmt(stmt) => {
                     Statement::Expr(self.collect_expr_opt(stmt.expr()))
                 }
@@ -605,8 +774,10 @@ impl<'a> ExprCollector<'a> {
         match expr.kind() {
             ast::ExprKind::LoopExpr(expr) => self.collect_loop(expr),
             ast::ExprKind::WhileExpr(expr) => self.collect_while(expr),
+            ast::ExprKind::ForExpr(expr) => self.collect_for(expr),
             ast::ExprKind::ReturnExpr(r) => self.collect_return(r),
             ast::ExprKind::BreakExpr(r) => self.collect_break(r),
+            ast::ExprKind::ContinueExpr(e) => self.collect_continue(e),
             ast::ExprKind::BlockExpr(b) => self.collect_block(b),
             ast::ExprKind::Literal(e) => match e.kind() {
                 ast::LiteralKind::Bool(value) => {
@@ -863,11 +1034,19 @@ impl<'a> ExprCollector<'a> {
                 let exprs = e.exprs().map(|expr| self.collect_expr(expr)).collect();
                 self.alloc_expr(Expr::Array(exprs), syntax_ptr)
             }
+            ast::ExprKind::TupleExpr(_) => self.alloc_expr(Expr::Tuple(Vec::new()), syntax_ptr),
             ast::ExprKind::IndexExpr(e) => {
                 let base = self.collect_expr_opt(e.base());
                 let index = self.collect_expr_opt(e.index());
                 self.alloc_expr(Expr::Index { base, index }, syntax_ptr)
             }
+            ast::ExprKind::CastExpr(e) => {
+                let expr = self.collect_expr_opt(e.expr());
+                let type_ref = self
+                    .type_ref_builder
+                    .alloc_from_node_opt(e.type_ref().as_ref());
+                self.alloc_expr(Expr::Cast { expr, type_ref }, syntax_ptr)
+            }
         }
     }
 
@@ -906,21 +1085,124 @@ impl<'a> ExprCollector<'a> {
 
     fn collect_break(&mut self, expr: ast::BreakExpr) -> ExprId {
         let syntax_node_ptr = AstPtr::new(&expr.clone().into());
-        let expr = expr.expr().map(|e| self.collect_expr(e));
-        self.alloc_expr(Expr::Break { expr }, syntax_node_ptr)
+        let target = self.resolve_loop_target(expr.lifetime());
+        let value = expr.expr().map(|e| self.collect_expr(e));
+        let label = target.as_ref().ok().copied().flatten();
+        let id = self.alloc_expr(Expr::Break { expr: value, label }, syntax_node_ptr);
+        match target {
+            Ok(_) => {}
+            Err(None) => self
+                .diagnostics
+                .push(ExprDiagnostic::BreakOutsideLoop { expr: id }),
+            Err(Some(label)) => self
+                .diagnostics
+                .push(ExprDiagnostic::UnresolvedLabel { expr: id, label }),
+        }
+        id
+    }
+
+    fn collect_continue(&mut self, expr: ast::ContinueExpr) -> ExprId {
+        let syntax_node_ptr = AstPtr::new(&expr.clone().into());
+        let target = self.resolve_loop_target(expr.lifetime());
+        let label = target.as_ref().ok().copied().flatten();
+        let id = self.alloc_expr(Expr::Continue { label }, syntax_node_ptr);
+        match target {
+            Ok(_) => {}
+            Err(None) => self
+                .diagnostics
+                .push(ExprDiagnostic::ContinueOutsideLoop { expr: id }),
+            Err(Some(label)) => self
+                .diagnostics
+                .push(ExprDiagnostic::UnresolvedLabel { expr: id, label }),
+        }
+        id
+    }
+
+    /// Resolves the loop that a `break` or `continue` with the optional label
+    /// `lifetime` refers to. Returns the label of the loop if the expression
+    /// refers to it by its label, or `None` for the innermost loop. Only the
+    /// loops of the body that is being collected are considered.
+    ///
+    /// Returns an error with the name of the label if no enclosing loop has
+    /// that label, or an error without a name if the expression is not inside
+    /// a loop at all.
+    fn resolve_loop_target(
+        &self,
+        lifetime: Option<SyntaxToken>,
+    ) -> Result<Option<LabelId>, Option<Name>> {
+        match lifetime {
+            Some(lifetime) => {
+                let name = Name::new(lifetime.text());
+                self.loops
+                    .iter()
+                    .rev()
+                    .flatten()
+                    .copied()
+                    .find(|&label| self.labels[label].name == name)
+                    .map(Some)
+                    .ok_or(Some(name))
+            }
+            None if self.loops.is_empty() => Err(None),
+            None => Ok(None),
+        }
+    }
+
+    fn collect_label(&mut self, label: Option<ast::Label>) -> Option<LabelId> {
+        let lifetime = label?.lifetime()?;
+        Some(self.labels.alloc(Label {
+            name: Name::new(lifetime.text()),
+        }))
+    }
+
+    /// Collects the body of a loop with the optional `label`.
+    fn collect_loop_body(
+        &mut self,
+        label: Option<LabelId>,
+        body: Option<ast::BlockExpr>,
+    ) -> ExprId {
+        self.loops.push(label);
+        let body = self.collect_block_opt(body);
+        self.loops.pop();
+        body
     }
 
     fn collect_loop(&mut self, expr: ast::LoopExpr) -> ExprId {
         let syntax_node_ptr = AstPtr::new(&expr.clone().into());
-        let body = self.collect_block_opt(expr.loop_body());
-        self.alloc_expr(Expr::Loop { body }, syntax_node_ptr)
+        let label = self.collect_label(expr.label());
+        let body = self.collect_loop_body(label, expr.loop_body());
+        self.alloc_expr(Expr::Loop { body, label }, syntax_node_ptr)
     }
 
     fn collect_while(&mut self, expr: ast::WhileExpr) -> ExprId {
         let syntax_node_ptr = AstPtr::new(&expr.clone().into());
+        let label = self.collect_label(expr.label());
         let condition = self.collect_condition_opt(expr.condition());
-        let body = self.collect_block_opt(expr.loop_body());
-        self.alloc_expr(Expr::While { condition, body }, syntax_node_ptr)
+        let body = self.collect_loop_body(label, expr.loop_body());
+        self.alloc_expr(
+            Expr::While {
+                condition,
+                body,
+                label,
+            },
+            syntax_node_ptr,
+        )
+    }
+
+    fn collect_for(&mut self, expr: ast::ForExpr) -> ExprId {
+        let syntax_node_ptr = AstPtr::new(&expr.clone().into());
+        let label = self.collect_label(expr.label());
+        let iterable = self.collect_expr_opt(expr.iterable());
+        let pat = self.collect_pat_opt(expr.pat());
+        let body = self.collect_loop_body(label, expr.loop_body());
+        self.alloc_expr(
+            Expr::For {
+                pat,
+                iterable,
+                body,
+                label,
+            },
+            syntax_node_ptr,
+        )
     }
 
     fn finish(mut self) -> (Body, BodySourceMap) {
@@ -929,6 +1211,7 @@ impl<'a> ExprCollector<'a> {
             owner: self.owner,
             exprs: self.exprs,
             pats: self.pats,
+            labels: self.labels,
             params: self.params,
             body_expr: self.body_expr.expect("A body should have been collected"),
             type_refs,
@@ -1362,14 +1645,14 @@ mod test {
 }
 
 mod diagnostics {
-    use mun_syntax::SmolStr;
+    use mun_syntax::{SmolStr, SyntaxKind};
 
     use super::{ExprDiagnostic, LiteralError};
     use crate::{
         code_model::DefWithBody,
         diagnostics::{
-            DiagnosticSink, IntLiteralTooLarge, InvalidFloatingPointLiteral, InvalidLiteral,
-            InvalidLiteralSuffix,
+            BreakOutsideLoop, ContinueOutsideLoop, DiagnosticSink, IntLiteralTooLarge,
+            InvalidFloatingPointLiteral, InvalidLiteral, InvalidLiteralSuffix, UnresolvedLabel,
         },
         HirDatabase,
     };
@@ -1382,6 +1665,12 @@ mod diagnostics {
             sink: &mut DiagnosticSink<'_>,
         ) {
             let source_map = owner.body_source_map(db);
+            let syntax_ptr = |expr| {
+                source_map
+                    .expr_syntax(expr)
+                    .expect("could not retrieve expr from source map")
+                    .map(|ptr| ptr.either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr()))
+            };
 
             match self {
                 ExprDiagnostic::LiteralError { expr, err } => {
@@ -1416,6 +1705,34 @@ mod diagnostics {
                         }
                     }
                 }
+                ExprDiagnostic::BreakOutsideLoop { expr } => {
+                    let break_expr = syntax_ptr(*expr);
+                    sink.push(BreakOutsideLoop {
+                        file: break_expr.file_id,
+                        break_expr: break_expr.value,
+                    });
+                }
+                ExprDiagnostic::ContinueOutsideLoop { expr } => {
+                    let continue_expr = syntax_ptr(*expr);
+                    sink.push(ContinueOutsideLoop {
+                        file: continue_expr.file_id,
+                        continue_expr: continue_expr.value,
+                    });
+                }
+                ExprDiagnostic::UnresolvedLabel { expr, label } => {
+                    let expr = syntax_ptr(*expr);
+                    let node = expr.value.to_node(&expr.file_syntax(db.upcast()));
+                    let label_range = node
+                        .children_with_tokens()
+                        .find(|it| it.kind() == SyntaxKind::LIFETIME)
+                        .map_or_else(|| node.text_range(), |it| it.text_range());
+                    sink.push(UnresolvedLabel {
+                        file: expr.file_id,
+                        expr: expr.value,
+                        label: label.clone(),
+                        label_range,
+                    });
+                }
             }
         }
     }
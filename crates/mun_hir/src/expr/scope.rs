@@ -138,6 +138,14 @@ fn compute_block_scopes(
                 scope = scopes.new_scope(scope);
                 scopes.add_bindings(body, scope, *pat);
             }
+            Statement::Static {
+                pat, initializer, ..
+            } => {
+                scopes.set_scope(*initializer, scope);
+                compute_expr_scopes(*initializer, body, scopes, scope);
+                scope = scopes.new_scope(scope);
+                scopes.add_bindings(body, scope, *pat);
+            }
             Statement::Expr(expr) => {
                 scopes.set_scope(*expr, scope);
                 compute_expr_scopes(*expr, body, scopes, scope);
@@ -155,6 +163,17 @@ fn compute_expr_scopes(expr: ExprId, body: &Body, scopes: &mut ExprScopes, scope
         Expr::Block { statements, tail } => {
             compute_block_scopes(statements, *tail, body, scopes, scope);
         }
+        Expr::For {
+            pat,
+            iterable,
+            body: loop_body,
+            ..
+        } => {
+            compute_expr_scopes(*iterable, body, scopes, scope);
+            let scope = scopes.new_scope(scope);
+            scopes.add_bindings(body, scope, *pat);
+            compute_expr_scopes(*loop_body, body, scopes, scope);
+        }
         e => e.walk_child_exprs(|e| compute_expr_scopes(e, body, scopes, scope)),
     };
 }
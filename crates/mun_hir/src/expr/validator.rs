@@ -3,19 +3,23 @@ use std::sync::Arc;
 use mun_syntax::{AstNode, SyntaxNodePtr};
 
 use crate::{
-    code_model::src::HasSource,
+    code_model::{link_section, src::HasSource},
     diagnostics::{
         CyclicType, DiagnosticSink, ExportedPrivate, ExternCannotHaveBody, ExternNonPrimitiveParam,
-        FreeTypeAliasWithoutTypeRef, PrivateTypeAlias,
+        FreeTypeAliasWithoutTypeRef, InvalidRefType, InvalidSliceType, InvalidStaticType,
+        PrivateTypeAlias,
     },
     expr::BodySourceMap,
     in_file::InFile,
     resolve::HasResolver,
-    Body, Expr, Function, HasVisibility, HirDatabase, InferenceResult, Ty, TyKind, TypeAlias,
-    Visibility,
+    Body, Expr, Function, HasVisibility, HirDatabase, InferenceResult, Statement, Ty, TyKind,
+    TypeAlias, Visibility,
 };
 
+mod infinite_loop;
 mod literal_out_of_range;
+mod no_effect;
+mod tail_call;
 mod uninitialized_access;
 
 #[cfg(test)]
@@ -44,10 +48,126 @@ impl<'a> ExprValidator<'a> {
     pub fn validate_body(&self, sink: &mut DiagnosticSink<'_>) {
         self.validate_literal_ranges(sink);
         self.validate_uninitialized_access(sink);
+        self.validate_infinite_loops(sink);
+        self.validate_no_effect(sink);
+        self.validate_tail_calls(sink);
         self.validate_extern(sink);
+        self.validate_slice_types(sink);
+        self.validate_ref_types(sink);
+        self.validate_static_types(sink);
+        self.validate_static_link_sections(sink);
         self.validate_privacy(sink);
     }
 
+    /// Validates that slice types are only used for the parameters of extern
+    /// functions.
+    pub fn validate_slice_types(&self, sink: &mut DiagnosticSink<'_>) {
+        let is_extern = self.func.is_extern(self.db);
+        let file_id = self.func.source(self.db.upcast()).file_id;
+        let is_param = |type_ref| {
+            self.body
+                .params()
+                .iter()
+                .any(|(_, param)| *param == type_ref)
+        };
+
+        self.body
+            .type_refs()
+            .slices()
+            .filter(|&type_ref| !(is_extern && is_param(type_ref)))
+            .for_each(|type_ref| {
+                sink.push(InvalidSliceType {
+                    file: file_id,
+                    type_ref: self.body_source_map.type_ref_syntax(type_ref).unwrap(),
+                });
+            });
+    }
+
+    /// Validates that mutable reference types are only used for the parameters
+    /// of functions and that they refer to primitive types.
+    pub fn validate_ref_types(&self, sink: &mut DiagnosticSink<'_>) {
+        let file_id = self.func.source(self.db.upcast()).file_id;
+        let is_valid_param = |type_ref| {
+            self.body
+                .params()
+                .iter()
+                .find(|(_, param)| *param == type_ref)
+                .map_or(false, |(pat, _)| {
+                    self.infer[*pat].as_mut_ref().map_or(true, |ty| {
+                        matches!(
+                            ty.interned(),
+                            TyKind::Bool | TyKind::Int(_) | TyKind::Float(_) | TyKind::Unknown
+                        )
+                    })
+                })
+        };
+
+        self.body
+            .type_refs()
+            .refs()
+            .filter(|&type_ref| !is_valid_param(type_ref))
+            .for_each(|type_ref| {
+                sink.push(InvalidRefType {
+                    file: file_id,
+                    type_ref: self.body_source_map.type_ref_syntax(type_ref).unwrap(),
+                });
+            });
+    }
+
+    /// Validates that function-local statics are of primitive types. Statics
+    /// are stored outside of the garbage collected heap, so they cannot refer
+    /// to garbage collected objects.
+    pub fn validate_static_types(&self, sink: &mut DiagnosticSink<'_>) {
+        let file_id = self.func.source(self.db.upcast()).file_id;
+        self.body
+            .exprs()
+            .filter_map(|(_, expr)| match expr {
+                Expr::Block { statements, .. } => Some(statements),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|statement| match statement {
+                Statement::Static { pat, .. } => Some(*pat),
+                _ => None,
+            })
+            .filter(|&pat| {
+                !matches!(
+                    self.infer[pat].interned(),
+                    TyKind::Bool | TyKind::Int(_) | TyKind::Float(_) | TyKind::Unknown
+                )
+            })
+            .for_each(|pat| {
+                sink.push(InvalidStaticType {
+                    file: file_id,
+                    pat: self
+                        .body_source_map
+                        .pat_syntax(pat)
+                        .unwrap()
+                        .value
+                        .syntax_node_ptr(),
+                });
+            });
+    }
+
+    /// Validates the sections in which function-local statics are placed by
+    /// `#[link_section]` attributes.
+    pub fn validate_static_link_sections(&self, sink: &mut DiagnosticSink<'_>) {
+        let file_id = self.func.source(self.db.upcast()).file_id;
+        let package = self.func.module(self.db).package();
+        self.body
+            .exprs()
+            .filter_map(|(_, expr)| match expr {
+                Expr::Block { statements, .. } => Some(statements),
+                _ => None,
+            })
+            .flatten()
+            .for_each(|statement| {
+                if let Statement::Static { link_sections, .. } = statement {
+                    link_section::validate(self.db, package, file_id, link_sections, true, sink);
+                }
+            });
+    }
+
     pub fn validate_privacy(&self, sink: &mut DiagnosticSink<'_>) {
         let resolver = self.func.id.resolver(self.db.upcast());
         let fn_data = self.func.data(self.db.upcast());
@@ -102,7 +222,10 @@ impl<'a> ExprValidator<'a> {
         if let Some(sig) = self.func.ty(self.db).callable_sig(self.db) {
             let fn_data = self.func.data(self.db.upcast());
             for (arg_ty, ty_ref) in sig.params().iter().zip(fn_data.params()) {
-                if arg_ty.as_struct().is_some() {
+                // The elements of a slice are passed by reference, so they must be primitives as
+                // well
+                let element_ty = arg_ty.as_slice().unwrap_or(arg_ty);
+                if element_ty.as_struct().is_some() {
                     let arg_ptr = fn_data
                         .type_ref_source_map()
                         .type_ref_syntax(*ty_ref)
@@ -182,6 +305,38 @@ impl<'a> TypeAliasValidator<'a> {
         }
     }
 
+    /// Validates that the target type of the provided `TypeAlias` does not
+    /// contain a slice type.
+    pub fn validate_slice_types(&self, sink: &mut DiagnosticSink<'_>) {
+        let data = self.type_alias.data(self.db.upcast());
+        let file_id = self.type_alias.source(self.db.upcast()).file_id;
+        data.type_ref_map().slices().for_each(|type_ref| {
+            sink.push(InvalidSliceType {
+                file: file_id,
+                type_ref: data
+                    .type_ref_source_map()
+                    .type_ref_syntax(type_ref)
+                    .unwrap(),
+            });
+        });
+    }
+
+    /// Validates that the target type of the provided `TypeAlias` does not
+    /// contain a mutable reference type.
+    pub fn validate_ref_types(&self, sink: &mut DiagnosticSink<'_>) {
+        let data = self.type_alias.data(self.db.upcast());
+        let file_id = self.type_alias.source(self.db.upcast()).file_id;
+        data.type_ref_map().refs().for_each(|type_ref| {
+            sink.push(InvalidRefType {
+                file: file_id,
+                type_ref: data
+                    .type_ref_source_map()
+                    .type_ref_syntax(type_ref)
+                    .unwrap(),
+            });
+        });
+    }
+
     /// Validates the provided `TypeAlias` is not cyclic.
     pub fn validate_acyclic(&self, sink: &mut DiagnosticSink<'_>) {
         let mut next_alias = Some(self.type_alias);
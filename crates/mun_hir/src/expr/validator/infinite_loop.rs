@@ -0,0 +1,108 @@
+use super::ExprValidator;
+use crate::{
+    diagnostics::{DiagnosticSink, InfiniteLoop},
+    expr::{BinaryOp, ExprId, Statement},
+    type_ref::TypeRef,
+    Expr, Literal,
+};
+
+impl<'a> ExprValidator<'a> {
+    /// Reports loops that can never terminate and whose body has no
+    /// observable effects. Loops that are suppressed with
+    /// `#[allow(infinite_loop)]`, or that form the tail of a function that
+    /// returns `never`, are intentional and are not reported.
+    pub(super) fn validate_infinite_loops(&self, sink: &mut DiagnosticSink<'_>) {
+        let fn_data = self.func.data(self.db.upcast());
+        if fn_data.is_lint_allowed(InfiniteLoop::LINT_NAME) {
+            return;
+        }
+
+        // A function that never returns may end in a loop to park the caller
+        let parking_loop = if fn_data.type_ref_map()[*fn_data.ret_type()] == TypeRef::Never {
+            match &self.body[self.body.body_expr] {
+                Expr::Block {
+                    tail: Some(tail), ..
+                } => Some(*tail),
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        for (expr_id, expr) in self.body.exprs() {
+            if Some(expr_id) == parking_loop {
+                continue;
+            }
+
+            let is_infinite = match expr {
+                Expr::Loop { body, .. } => self.is_side_effect_free(*body),
+                Expr::While {
+                    condition, body, ..
+                } => {
+                    self.body[*condition] == Expr::Literal(Literal::Bool(true))
+                        && self.is_side_effect_free(*body)
+                }
+                _ => false,
+            };
+
+            if is_infinite {
+                sink.push(InfiniteLoop {
+                    file: self.func.file_id(self.db),
+                    loop_expr: self
+                        .body_source_map
+                        .expr_syntax(expr_id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr()),
+                });
+            }
+        }
+    }
+
+    /// Returns true if evaluating the expression `expr_id` cannot have any
+    /// observable effect. This is a conservative approximation: calls may
+    /// have effects, assignments and indexing are treated as effects, and
+    /// `break`, `return` and a labeled `continue` may leave the loop.
+    pub(super) fn is_side_effect_free(&self, expr_id: ExprId) -> bool {
+        match &self.body[expr_id] {
+            Expr::Literal(_) | Expr::Path(_) | Expr::Continue { label: None } => true,
+            Expr::Block { statements, tail } => {
+                statements.iter().all(|statement| match statement {
+                    Statement::Let { initializer, .. } => {
+                        initializer.map_or(true, |expr| self.is_side_effect_free(expr))
+                    }
+                    // The state of a static persists across iterations
+                    Statement::Static { .. } => false,
+                    Statement::Expr(expr) => self.is_side_effect_free(*expr),
+                }) && tail.map_or(true, |expr| self.is_side_effect_free(expr))
+            }
+            Expr::BinaryOp {
+                op: Some(BinaryOp::Assignment { .. }),
+                ..
+            } => false,
+            Expr::UnaryOp { .. }
+            | Expr::BinaryOp { .. }
+            | Expr::Field { .. }
+            | Expr::Cast { .. }
+            | Expr::Tuple(_)
+            | Expr::If { .. }
+            | Expr::Loop { .. }
+            | Expr::While { .. } => {
+                let mut is_side_effect_free = true;
+                self.body[expr_id].walk_child_exprs(|expr| {
+                    is_side_effect_free &= self.is_side_effect_free(expr);
+                });
+                is_side_effect_free
+            }
+            Expr::Missing
+            | Expr::Continue { label: Some(_) }
+            | Expr::Call { .. }
+            | Expr::Index { .. }
+            | Expr::For { .. }
+            | Expr::Return { .. }
+            | Expr::Break { .. }
+            | Expr::RecordLit { .. }
+            | Expr::Array(_) => false,
+        }
+    }
+}
@@ -1,21 +1,41 @@
 use super::ExprValidator;
 use crate::{
     diagnostics::{DiagnosticSink, LiteralOutOfRange},
+    expr::{ExprId, UnaryOp},
     ty::{ResolveBitness, TyKind},
-    Expr, HirDisplay, Literal,
+    Expr, HirDisplay, Literal, Signedness,
 };
 
 impl<'a> ExprValidator<'a> {
     /// Iterates over all expressions to determine if one of the literals has a
     /// value that is out of range of its type.
     pub fn validate_literal_ranges(&self, sink: &mut DiagnosticSink<'_>) {
-        self.body[self.body.body_expr].walk_child_exprs(move |expr_id| {
-            let expr = &self.body[expr_id];
-            if let Expr::Literal(Literal::Int(lit)) = &expr {
+        self.validate_expr_literal_ranges(self.body.body_expr, false, sink);
+    }
+
+    /// Recursively validates the ranges of the literals in the expression
+    /// `expr_id`. If `is_negated` is true, the expression is the operand of a
+    /// negation. A negated signed literal can be one larger than the maximum
+    /// value of its type, e.g. `-128i8`.
+    fn validate_expr_literal_ranges(
+        &self,
+        expr_id: ExprId,
+        is_negated: bool,
+        sink: &mut DiagnosticSink<'_>,
+    ) {
+        let expr = &self.body[expr_id];
+        match expr {
+            Expr::Literal(Literal::Int(lit)) => {
                 let ty = &self.infer[expr_id];
                 match ty.interned() {
                     TyKind::Int(int_ty) => {
-                        if lit.value > int_ty.resolve(&self.db.target_data_layout()).max() {
+                        let max = int_ty.resolve(&self.db.target_data_layout()).max();
+                        let max = if is_negated && int_ty.signedness == Signedness::Signed {
+                            max + 1
+                        } else {
+                            max
+                        };
+                        if lit.value > max {
                             let literal = self
                                 .body_source_map
                                 .expr_syntax(expr_id)
@@ -30,6 +50,7 @@ impl<'a> ExprValidator<'a> {
                             sink.push(LiteralOutOfRange {
                                 literal,
                                 int_ty: *int_ty,
+                                is_defaulted: self.infer.is_defaulted_literal(expr_id),
                             });
                         }
                     }
@@ -39,6 +60,13 @@ impl<'a> ExprValidator<'a> {
                     ),
                 }
             }
-        });
+            Expr::UnaryOp {
+                expr,
+                op: UnaryOp::Neg,
+            } => self.validate_expr_literal_ranges(*expr, true, sink),
+            _ => {
+                expr.walk_child_exprs(|child| self.validate_expr_literal_ranges(child, false, sink))
+            }
+        }
     }
 }
@@ -0,0 +1,132 @@
+use mun_syntax::SyntaxNodePtr;
+
+use super::ExprValidator;
+use crate::{
+    diagnostics::{DiagnosticSink, EmptyIf, NoEffect, SelfAssignment},
+    expr::{BinaryOp, ExprId, Statement},
+    resolve::{resolver_for_expr, ValueNs},
+    Expr, Path,
+};
+
+impl<'a> ExprValidator<'a> {
+    /// Reports code that obviously does nothing: assignments of a place to
+    /// itself, `if` expressions of which all branches are empty, and
+    /// statements that discard the value of an expression without any
+    /// observable effects. Each lint is suppressed with `#[allow(..)]` on the
+    /// function.
+    pub(super) fn validate_no_effect(&self, sink: &mut DiagnosticSink<'_>) {
+        let fn_data = self.func.data(self.db.upcast());
+        let allow_self_assignment = fn_data.is_lint_allowed(SelfAssignment::LINT_NAME);
+        let allow_empty_if = fn_data.is_lint_allowed(EmptyIf::LINT_NAME);
+        let allow_no_effect = fn_data.is_lint_allowed(NoEffect::LINT_NAME);
+
+        for (expr_id, expr) in self.body.exprs() {
+            match expr {
+                Expr::BinaryOp {
+                    lhs,
+                    rhs,
+                    op: Some(BinaryOp::Assignment { op: None }),
+                } if !allow_self_assignment && self.is_same_place(*lhs, *rhs) => {
+                    sink.push(SelfAssignment {
+                        file: self.func.file_id(self.db),
+                        assignment: self.expr_syntax(expr_id),
+                    });
+                }
+                Expr::If {
+                    then_branch,
+                    else_branch,
+                    ..
+                } if !allow_empty_if
+                    && self.is_empty_block(*then_branch)
+                    && else_branch.map_or(true, |expr| self.is_empty_block(expr)) =>
+                {
+                    sink.push(EmptyIf {
+                        file: self.func.file_id(self.db),
+                        if_expr: self.expr_syntax(expr_id),
+                    });
+                }
+                Expr::Block { statements, .. } if !allow_no_effect => {
+                    for statement in statements.iter() {
+                        if let Statement::Expr(expr) = statement {
+                            if self.has_no_effect(*expr) {
+                                sink.push(NoEffect {
+                                    file: self.func.file_id(self.db),
+                                    expr: self.expr_syntax(*expr),
+                                });
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns true if `lhs` and `rhs` refer to the same local binding, or to
+    /// the same field of the same local binding.
+    fn is_same_place(&self, lhs: ExprId, rhs: ExprId) -> bool {
+        match (&self.body[lhs], &self.body[rhs]) {
+            (Expr::Path(lhs_path), Expr::Path(rhs_path)) => {
+                let resolve = |expr: ExprId, path: &Path| match resolver_for_expr(
+                    self.db.upcast(),
+                    self.body.owner(),
+                    expr,
+                )
+                .resolve_path_as_value_fully(self.db.upcast(), path)
+                {
+                    Some((ValueNs::LocalBinding(pat), _)) => Some(pat),
+                    _ => None,
+                };
+                let lhs_pat = resolve(lhs, lhs_path);
+                lhs_pat.is_some() && lhs_pat == resolve(rhs, rhs_path)
+            }
+            (
+                Expr::Field {
+                    expr: lhs_expr,
+                    name: lhs_name,
+                },
+                Expr::Field {
+                    expr: rhs_expr,
+                    name: rhs_name,
+                },
+            ) => lhs_name == rhs_name && self.is_same_place(*lhs_expr, *rhs_expr),
+            _ => false,
+        }
+    }
+
+    /// Returns true if `expr` is a block without any statements or tail.
+    fn is_empty_block(&self, expr: ExprId) -> bool {
+        matches!(
+            &self.body[expr],
+            Expr::Block { statements, tail: None } if statements.is_empty()
+        )
+    }
+
+    /// Returns true if discarding the value of `expr` makes it pointless to
+    /// evaluate. Control flow expressions are excluded; empty `if`s and loops
+    /// are reported by their own lints.
+    fn has_no_effect(&self, expr: ExprId) -> bool {
+        match &self.body[expr] {
+            Expr::BinaryOp {
+                op: Some(BinaryOp::Assignment { .. }),
+                ..
+            } => false,
+            Expr::Literal(_)
+            | Expr::Path(_)
+            | Expr::UnaryOp { .. }
+            | Expr::BinaryOp { .. }
+            | Expr::Field { .. }
+            | Expr::Cast { .. }
+            | Expr::Tuple(_) => self.is_side_effect_free(expr),
+            _ => false,
+        }
+    }
+
+    fn expr_syntax(&self, expr: ExprId) -> SyntaxNodePtr {
+        self.body_source_map
+            .expr_syntax(expr)
+            .unwrap()
+            .value
+            .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())
+    }
+}
@@ -0,0 +1,69 @@
+use super::ExprValidator;
+use crate::{
+    diagnostics::{DiagnosticSink, NonTailRecursiveCall, TailCallSignatureMismatch},
+    CallableDef, Expr, HirDisplay, TyKind,
+};
+
+impl<'a> ExprValidator<'a> {
+    /// Validates the calls of a `#[tail_call]` function. Recursive calls must
+    /// be in tail position, and the callees of calls in tail position must
+    /// have the same signature as the function, so that every call in tail
+    /// position can reuse the stack frame of the function.
+    pub(super) fn validate_tail_calls(&self, sink: &mut DiagnosticSink<'_>) {
+        if !self.func.is_tail_call(self.db) {
+            return;
+        }
+
+        let file = self.func.file_id(self.db);
+        let syntax_node_ptr = |expr_id| {
+            self.body_source_map
+                .expr_syntax(expr_id)
+                .unwrap()
+                .value
+                .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())
+        };
+
+        let caller_sig = self.func.ty(self.db).callable_sig(self.db).unwrap();
+        let tail_calls = self.func.tail_calls(self.db);
+        for (expr_id, expr) in self.body.exprs() {
+            let Expr::Call { callee, .. } = expr else {
+                continue;
+            };
+
+            if !tail_calls.contains(&expr_id) {
+                if self.infer[*callee].as_callable_def() == Some(CallableDef::Function(self.func)) {
+                    sink.push(NonTailRecursiveCall {
+                        file,
+                        call: syntax_node_ptr(expr_id),
+                        name: self.func.name(self.db).to_string(),
+                    });
+                }
+                continue;
+            }
+
+            // Type errors are reported by type inference
+            let Some(callee_sig) = self.infer[*callee].callable_sig(self.db) else {
+                continue;
+            };
+            let has_unknown_types = caller_sig
+                .params_and_return()
+                .iter()
+                .chain(callee_sig.params_and_return())
+                .any(|ty| ty.is_unknown());
+            if callee_sig != caller_sig && !has_unknown_types {
+                sink.push(TailCallSignatureMismatch {
+                    file,
+                    call: syntax_node_ptr(expr_id),
+                    caller_signature: TyKind::FnPtr(caller_sig.clone())
+                        .intern()
+                        .display(self.db)
+                        .to_string(),
+                    callee_signature: TyKind::FnPtr(callee_sig)
+                        .intern()
+                        .display(self.db)
+                        .to_string(),
+                });
+            }
+        }
+    }
+}
@@ -181,3 +181,178 @@ fn test_type_alias_with_private_type_alias() {
     "#,
     ), @"17..36: type alias `Foo` is private");
 }
+
+#[test]
+fn test_literal_out_of_range() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    fn foo(a: u8) -> u8 { a }
+
+    fn main() {
+        let a = -128i8;
+        let b = -129i8;
+        let c = 1i8 + 128i8;
+        let d = foo(256u8);
+    }
+    "#,
+    ), @r###"
+    72..77: literal out of range for `i8`
+    97..102: literal out of range for `i8`
+    120..125: literal out of range for `u8`
+    "###);
+}
+
+#[test]
+fn test_infinite_loop() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    fn spin() {
+        loop {}
+    }
+
+    fn busy() {
+        while true {
+            let a = 1 + 2;
+        }
+    }
+
+    fn poll() {
+        loop { poll() }
+    }
+
+    fn wait(ready: bool) {
+        while !ready {}
+        loop { if ready { break } }
+    }
+
+    #[allow(infinite_loop)]
+    fn idle() {
+        loop {}
+    }
+
+    fn halt() -> never {
+        loop {}
+    }
+    "#,
+    ), @r###"
+    16..23: this loop never terminates and has no side effects
+    43..84: this loop never terminates and has no side effects
+    "###);
+}
+
+#[test]
+fn test_no_effect() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    struct Point { x: i32, y: i32 }
+
+    fn assign(a: i32, p: Point) {
+        let b = a;
+        b = b;
+        p.x = p.x;
+        p.x = p.y;
+        b += b;
+    }
+
+    fn branches(cond: bool) {
+        if cond {} else {}
+        if cond {}
+        if cond { assign(1, Point { x: 1, y: 2 }) } else {}
+    }
+
+    fn pure(a: i32, b: i32) -> i32 {
+        a + b;
+        -a;
+        a == b;
+        assign(a, Point { x: a, y: b });
+        a + b
+    }
+
+    fn side_effect() -> i32 { 1 }
+
+    fn effects(a: i32) {
+        a + side_effect();
+    }
+
+    #[allow(self_assignment, empty_if, no_effect)]
+    fn allowed(a: i32) {
+        let b = a;
+        b = b;
+        if true {} else {}
+        a + 1;
+    }
+    "#,
+    ), @r###"
+    82..87: assigning a value to itself has no effect
+    93..102: assigning a value to itself has no effect
+    164..182: all branches of this `if` are empty
+    187..197: all branches of this `if` are empty
+    294..299: this statement has no effect
+    305..307: this statement has no effect
+    313..319: this statement has no effect
+    "###);
+}
+
+#[test]
+fn test_tail_call() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    #[tail_call]
+    fn count(n: i32, acc: i32) -> i32 {
+        if n == 0 {
+            return acc;
+        }
+        count(n - 1, acc + 1)
+    }
+
+    #[tail_call]
+    fn sum(n: i32) -> i32 {
+        if n == 0 { 0 } else { n + sum(n - 1) }
+    }
+
+    fn half(n: i32) -> i32 { n / 2 }
+
+    #[tail_call]
+    fn step(n: i32, acc: i32) -> i32 {
+        if n == 0 { return acc } else { return half(n) }
+    }
+
+    fn sum_plain(n: i32) -> i32 {
+        if n == 0 { 0 } else { n + sum_plain(n - 1) }
+    }
+    "#,
+    ), @r###"
+    188..198: recursive call to `#[tail_call]` function `sum` is not in tail position; its result must be returned directly, e.g. `return sum(..)`, for the call to reuse the stack frame
+    329..336: cannot compile tail call: the callee's signature `fn(i32) -> i32` does not match the signature `fn(i32, i32) -> i32` of the `#[tail_call]` function, so it cannot reuse the caller's stack frame
+    "###);
+}
+
+#[test]
+fn test_link_section() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    #[link_section = 1]
+    pub const A: i32 = 1;
+
+    #[link_section = ""]
+    pub const B: i32 = 2;
+
+    #[link_section = "cfg,cfg"]
+    #[link_section = "cfg,other"]
+    pub const C: i32 = 3;
+
+    fn foo() -> i32 {
+        #[link_section = "cfg,cfg"]
+        static mut N: i32 = 0;
+        #[link_section = "cfg,data"]
+        static mut M: i32 = 0;
+        N + M
+    }
+    "#,
+    ), @r###"
+    0..19: expected a string literal, e.g. `#[link_section = ".config"]`
+    43..63: cannot place the global in section `` on the current target: the section name is empty
+    115..144: the global is already placed in section `cfg,cfg`, not `cfg,other`
+    190..217: section `cfg,cfg` already holds constants, it cannot also hold statics
+    "###);
+}
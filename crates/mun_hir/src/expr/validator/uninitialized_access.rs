@@ -100,7 +100,7 @@ impl<'d> ExprValidator<'d> {
                     };
                 }
             }
-            Expr::UnaryOp { expr, .. } | Expr::Field { expr, .. } => {
+            Expr::UnaryOp { expr, .. } | Expr::Field { expr, .. } | Expr::Cast { expr, .. } => {
                 self.validate_expr_access(sink, initialized_patterns, *expr, ExprKind::Normal);
             }
             Expr::BinaryOp { lhs, rhs, op } => {
@@ -128,6 +128,17 @@ impl<'d> ExprValidator<'d> {
                                 initialized_patterns.insert(*pat);
                             }
                         }
+                        Statement::Static {
+                            pat, initializer, ..
+                        } => {
+                            self.validate_expr_access(
+                                sink,
+                                initialized_patterns,
+                                *initializer,
+                                ExprKind::Normal,
+                            );
+                            initialized_patterns.insert(*pat);
+                        }
                         Statement::Expr(expr) => {
                             self.validate_expr_access(
                                 sink,
@@ -145,15 +156,17 @@ impl<'d> ExprValidator<'d> {
                     self.validate_expr_access(sink, initialized_patterns, *tail, ExprKind::Normal);
                 }
             }
-            Expr::Return { expr } | Expr::Break { expr } => {
+            Expr::Return { expr } | Expr::Break { expr, .. } => {
                 if let Some(expr) = expr {
                     self.validate_expr_access(sink, initialized_patterns, *expr, ExprKind::Normal);
                 }
             }
-            Expr::Loop { body } => {
+            Expr::Loop { body, .. } => {
                 self.validate_expr_access(sink, initialized_patterns, *body, ExprKind::Normal);
             }
-            Expr::While { condition, body } => {
+            Expr::While {
+                condition, body, ..
+            } => {
                 self.validate_expr_access(sink, initialized_patterns, *condition, ExprKind::Normal);
                 self.validate_expr_access(
                     sink,
@@ -162,6 +175,22 @@ impl<'d> ExprValidator<'d> {
                     ExprKind::Normal,
                 );
             }
+            Expr::For {
+                pat,
+                iterable,
+                body,
+                ..
+            } => {
+                self.validate_expr_access(sink, initialized_patterns, *iterable, ExprKind::Normal);
+                let mut body_initialized_patterns = initialized_patterns.clone();
+                body_initialized_patterns.insert(*pat);
+                self.validate_expr_access(
+                    sink,
+                    &mut body_initialized_patterns,
+                    *body,
+                    ExprKind::Normal,
+                );
+            }
             Expr::RecordLit { fields, spread, .. } => {
                 for field in fields.iter() {
                     self.validate_expr_access(
@@ -179,12 +208,12 @@ impl<'d> ExprValidator<'d> {
                 self.validate_expr_access(sink, initialized_patterns, *base, ExprKind::Normal);
                 self.validate_expr_access(sink, initialized_patterns, *index, ExprKind::Normal);
             }
-            Expr::Array(exprs) => {
+            Expr::Array(exprs) | Expr::Tuple(exprs) => {
                 for expr in exprs {
                     self.validate_expr_access(sink, initialized_patterns, *expr, ExprKind::Normal);
                 }
             }
-            Expr::Literal(_) | Expr::Missing => {}
+            Expr::Literal(_) | Expr::Missing | Expr::Continue { .. } => {}
         }
     }
 
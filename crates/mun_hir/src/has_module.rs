@@ -1,6 +1,6 @@
 use crate::{
     ids::{
-        AssocItemId, AssocItemLoc, FunctionId, ImplId, ItemContainerId, Lookup, StructId,
+        AssocItemId, AssocItemLoc, ConstId, FunctionId, ImplId, ItemContainerId, Lookup, StructId,
         TypeAliasId,
     },
     item_tree::ItemTreeNode,
@@ -51,6 +51,12 @@ impl HasModule for TypeAliasId {
     }
 }
 
+impl HasModule for ConstId {
+    fn module(&self, db: &dyn DefDatabase) -> ModuleId {
+        self.lookup(db).module
+    }
+}
+
 impl HasModule for AssocItemId {
     fn module(&self, db: &dyn DefDatabase) -> ModuleId {
         match self {
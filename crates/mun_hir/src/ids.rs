@@ -1,7 +1,8 @@
 use std::hash::{Hash, Hasher};
 
 use crate::{
-    item_tree::{Function, Impl, ItemTreeId, ItemTreeNode, Struct, TypeAlias},
+    builtin_constant::BuiltinConstant,
+    item_tree::{Const, Function, Impl, ItemTreeId, ItemTreeNode, Struct, TypeAlias},
     module_tree::LocalModuleId,
     primitive_type::PrimitiveType,
     DefDatabase, PackageId,
@@ -140,6 +141,12 @@ impl_intern!(
     lookup_intern_type_alias
 );
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConstId(salsa::InternId);
+
+pub(crate) type ConstLoc = ItemLoc<Const>;
+impl_intern!(ConstId, ConstLoc, intern_const, lookup_intern_const);
+
 pub trait Intern {
     type ID;
     fn intern(self, db: &dyn DefDatabase) -> Self::ID;
@@ -156,7 +163,9 @@ pub enum ItemDefinitionId {
     FunctionId(FunctionId),
     StructId(StructId),
     TypeAliasId(TypeAliasId),
+    ConstId(ConstId),
     PrimitiveType(PrimitiveType),
+    BuiltinConstant(BuiltinConstant),
 }
 
 impl From<ModuleId> for ItemDefinitionId {
@@ -183,12 +192,24 @@ impl From<TypeAliasId> for ItemDefinitionId {
     }
 }
 
+impl From<ConstId> for ItemDefinitionId {
+    fn from(id: ConstId) -> Self {
+        ItemDefinitionId::ConstId(id)
+    }
+}
+
 impl From<PrimitiveType> for ItemDefinitionId {
     fn from(id: PrimitiveType) -> Self {
         ItemDefinitionId::PrimitiveType(id)
     }
 }
 
+impl From<BuiltinConstant> for ItemDefinitionId {
+    fn from(id: BuiltinConstant) -> Self {
+        ItemDefinitionId::BuiltinConstant(id)
+    }
+}
+
 /// Items that are associated with an `impl`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum AssocItemId {
@@ -199,6 +220,7 @@ pub enum AssocItemId {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DefWithBodyId {
     FunctionId(FunctionId),
+    ConstId(ConstId),
 }
 
 impl From<FunctionId> for DefWithBodyId {
@@ -206,3 +228,9 @@ impl From<FunctionId> for DefWithBodyId {
         DefWithBodyId::FunctionId(id)
     }
 }
+
+impl From<ConstId> for DefWithBodyId {
+    fn from(id: ConstId) -> Self {
+        DefWithBodyId::ConstId(id)
+    }
+}
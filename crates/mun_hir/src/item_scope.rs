@@ -4,6 +4,7 @@ use once_cell::sync::Lazy;
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
+    builtin_constant::BuiltinConstant,
     ids::{ImplId, ItemDefinitionId},
     module_tree::LocalModuleId,
     primitive_type::PrimitiveType,
@@ -71,6 +72,12 @@ pub(crate) static BUILTIN_SCOPE: Lazy<FxHashMap<Name, PerNs<(ItemDefinitionId, V
                     PerNs::types(((*ty).into(), Visibility::Public)),
                 )
             })
+            .chain(BuiltinConstant::ALL.iter().map(|(name, constant)| {
+                (
+                    name.clone(),
+                    PerNs::values(((*constant).into(), Visibility::Public)),
+                )
+            }))
             .collect()
     });
 
@@ -226,7 +233,9 @@ impl PerNs<(ItemDefinitionId, Visibility)> {
         has_constructor: bool,
     ) -> PerNs<(ItemDefinitionId, Visibility)> {
         match def {
-            ItemDefinitionId::FunctionId(_) => PerNs::values((def, vis)),
+            ItemDefinitionId::FunctionId(_)
+            | ItemDefinitionId::ConstId(_)
+            | ItemDefinitionId::BuiltinConstant(_) => PerNs::values((def, vis)),
             ItemDefinitionId::StructId(_) => {
                 if has_constructor {
                     PerNs::both((def, vis), (def, vis))
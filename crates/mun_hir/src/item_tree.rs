@@ -116,6 +116,7 @@ struct ItemTreeData {
     structs: Arena<Struct>,
     fields: Arena<Field>,
     type_aliases: Arena<TypeAlias>,
+    consts: Arena<Const>,
     impls: Arena<Impl>,
 
     visibilities: ItemVisibilities,
@@ -227,6 +228,7 @@ mod_items! {
     Function in functions -> ast::FunctionDef,
     Struct in structs -> ast::StructDef,
     TypeAlias in type_aliases -> ast::TypeAliasDef,
+    Const in consts -> ast::ConstDef,
     Import in imports -> ast::Use,
     Impl in impls -> ast::Impl,
 }
@@ -302,6 +304,12 @@ pub struct Function {
     pub name: Name,
     pub visibility: RawVisibilityId,
     pub is_extern: bool,
+    pub is_cold: bool,
+    pub is_no_mangle: bool,
+    pub is_no_instrument: bool,
+    pub is_reflect: bool,
+    pub is_tail_call: bool,
+    pub is_init: bool,
     pub types: TypeRefMap,
     pub params: IdRange<Param>,
     pub ret_type: LocalTypeRefId,
@@ -345,6 +353,15 @@ pub struct TypeAlias {
     pub ast_id: FileAstId<ast::TypeAliasDef>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Const {
+    pub name: Name,
+    pub visibility: RawVisibilityId,
+    pub types: TypeRefMap,
+    pub type_ref: LocalTypeRefId,
+    pub ast_id: FileAstId<ast::ConstDef>,
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum AssociatedItem {
     Function(LocalItemTreeId<Function>),
@@ -442,12 +459,18 @@ impl<T> PartialEq for IdRange<T> {
 impl<T> Eq for IdRange<T> {}
 
 mod diagnostics {
-    use mun_syntax::{AstNode, SyntaxNodePtr};
+    use mun_syntax::{
+        ast::{self, AttrsOwner},
+        AstNode, SyntaxNodePtr,
+    };
 
     use super::{ItemTree, ModItem};
     use crate::{
-        diagnostics::DuplicateDefinition, DefDatabase, DiagnosticSink, HirDatabase, InFile, Name,
-        Path,
+        diagnostics::{
+            DuplicateDefinition, MalformedCfgAttribute, NoCfgAlternativeEnabled, UnknownAttribute,
+        },
+        source_id::FileAstId,
+        DefDatabase, DiagnosticSink, HirDatabase, InFile, Name, Path,
     };
 
     #[derive(Clone, Debug, Eq, PartialEq)]
@@ -457,6 +480,19 @@ mod diagnostics {
             first: ModItem,
             second: ModItem,
         },
+        NoCfgAlternativeEnabled {
+            name: Name,
+            first: FileAstId<ast::ModuleItem>,
+        },
+        MalformedCfgAttribute {
+            item: FileAstId<ast::ModuleItem>,
+            attr_index: usize,
+        },
+        UnknownAttribute {
+            name: Name,
+            item: FileAstId<ast::ModuleItem>,
+            attr_index: usize,
+        },
     }
 
     impl ItemTreeDiagnostic {
@@ -484,6 +520,10 @@ mod diagnostics {
                         item_tree.file_id,
                         SyntaxNodePtr::new(item_tree.source(db, item).syntax()),
                     ),
+                    ModItem::Const(item) => InFile::new(
+                        item_tree.file_id,
+                        SyntaxNodePtr::new(item_tree.source(db, item).syntax()),
+                    ),
                     ModItem::Import(it) => {
                         let import = &item_tree[it];
                         let import_src = item_tree.source(db, it);
@@ -504,6 +544,31 @@ mod diagnostics {
                 }
             }
 
+            fn module_item(
+                db: &dyn DefDatabase,
+                item_tree: &ItemTree,
+                ast_id: FileAstId<ast::ModuleItem>,
+            ) -> ast::ModuleItem {
+                let root = db.parse(item_tree.file_id);
+                db.ast_id_map(item_tree.file_id)
+                    .get(ast_id)
+                    .to_node(&root.syntax_node())
+            }
+
+            fn item_attr(
+                db: &dyn DefDatabase,
+                item_tree: &ItemTree,
+                ast_id: FileAstId<ast::ModuleItem>,
+                attr_index: usize,
+            ) -> Option<ast::Attr> {
+                match module_item(db, item_tree, ast_id).kind() {
+                    ast::ModuleItemKind::FunctionDef(it) => it.attrs().nth(attr_index),
+                    ast::ModuleItemKind::StructDef(it) => it.attrs().nth(attr_index),
+                    ast::ModuleItemKind::ConstDef(it) => it.attrs().nth(attr_index),
+                    _ => None,
+                }
+            }
+
             match self {
                 ItemTreeDiagnostic::DuplicateDefinition {
                     name,
@@ -514,6 +579,35 @@ mod diagnostics {
                     first_definition: ast_ptr_from_mod(db.upcast(), item_tree, *first),
                     definition: ast_ptr_from_mod(db.upcast(), item_tree, *second),
                 }),
+                ItemTreeDiagnostic::NoCfgAlternativeEnabled { name, first } => {
+                    let item = module_item(db.upcast(), item_tree, *first);
+                    sink.push(NoCfgAlternativeEnabled {
+                        name: name.to_string(),
+                        definition: InFile::new(
+                            item_tree.file_id,
+                            SyntaxNodePtr::new(item.syntax()),
+                        ),
+                    });
+                }
+                ItemTreeDiagnostic::MalformedCfgAttribute { item, attr_index } => {
+                    let attr = item_attr(db.upcast(), item_tree, *item, *attr_index)
+                        .expect("cannot find cfg attribute");
+                    sink.push(MalformedCfgAttribute {
+                        attr: InFile::new(item_tree.file_id, SyntaxNodePtr::new(attr.syntax())),
+                    });
+                }
+                ItemTreeDiagnostic::UnknownAttribute {
+                    name,
+                    item,
+                    attr_index,
+                } => {
+                    let attr = item_attr(db.upcast(), item_tree, *item, *attr_index)
+                        .expect("cannot find attribute");
+                    sink.push(UnknownAttribute {
+                        name: name.to_string(),
+                        attr: InFile::new(item_tree.file_id, SyntaxNodePtr::new(attr.syntax())),
+                    });
+                }
             };
         }
     }
@@ -1,27 +1,54 @@
 //! This module implements the logic to convert an AST to an `ItemTree`.
 
-use std::{collections::HashMap, convert::TryInto, marker::PhantomData, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryInto,
+    marker::PhantomData,
+    sync::Arc,
+};
 
 use la_arena::{Idx, RawIdx};
 use mun_syntax::ast::{
-    self, ExternOwner, ModuleItemOwner, NameOwner, StructKind, TypeAscriptionOwner,
+    self, AttrsOwner, ExternOwner, ModuleItemOwner, NameOwner, StructKind, TypeAscriptionOwner,
 };
 use smallvec::SmallVec;
 
 use super::{
-    diagnostics, AssociatedItem, Field, Fields, Function, IdRange, Impl, ItemTree, ItemTreeData,
-    ItemTreeNode, ItemVisibilities, LocalItemTreeId, ModItem, Param, ParamAstId, RawVisibilityId,
-    Struct, TypeAlias,
+    diagnostics, AssociatedItem, Const, Field, Fields, Function, IdRange, Impl, ItemTree,
+    ItemTreeData, ItemTreeNode, ItemVisibilities, LocalItemTreeId, ModItem, Param, ParamAstId,
+    RawVisibilityId, Struct, TypeAlias,
 };
 use crate::{
+    cfg::{CfgExpr, TargetFeatures, CFG_ATTR_NAME},
+    code_model::{r#struct::SizeAssertion, LinkSection},
     item_tree::Import,
     name::AsName,
-    source_id::AstIdMap,
+    source_id::{AstIdMap, FileAstId},
     type_ref::{TypeRefMap, TypeRefMapBuilder},
     visibility::RawVisibility,
     DefDatabase, FileId, Name, Path,
 };
 
+/// The attributes that are recognized on functions
+const FUNCTION_ATTRS: &[&str] = &[
+    CFG_ATTR_NAME,
+    "allow",
+    "cold",
+    "init",
+    "inline",
+    "no_instrument",
+    "no_mangle",
+    "optimize",
+    "reflect",
+    "tail_call",
+];
+
+/// The attributes that are recognized on structs
+const STRUCT_ATTRS: &[&str] = &[CFG_ATTR_NAME, SizeAssertion::ATTR_NAME];
+
+/// The attributes that are recognized on constants
+const CONST_ATTRS: &[&str] = &[LinkSection::ATTR_NAME];
+
 struct ModItems(SmallVec<[ModItem; 1]>);
 
 impl<T> From<T> for ModItems
@@ -47,6 +74,8 @@ pub(super) struct Context {
     source_ast_id_map: Arc<AstIdMap>,
     data: ItemTreeData,
     diagnostics: Vec<diagnostics::ItemTreeDiagnostic>,
+    target_features: TargetFeatures,
+    cfg_disabled_items: Vec<(Name, FileAstId<ast::ModuleItem>)>,
 }
 
 impl Context {
@@ -57,6 +86,8 @@ impl Context {
             source_ast_id_map: db.ast_id_map(file),
             data: ItemTreeData::default(),
             diagnostics: Vec::new(),
+            target_features: TargetFeatures::parse(&db.target().options.features),
+            cfg_disabled_items: Vec::new(),
         }
     }
 
@@ -76,6 +107,7 @@ impl Context {
                 ModItem::Function(item) => Some(&self.data.functions[item.index].name),
                 ModItem::Struct(item) => Some(&self.data.structs[item.index].name),
                 ModItem::TypeAlias(item) => Some(&self.data.type_aliases[item.index].name),
+                ModItem::Const(item) => Some(&self.data.consts[item.index].name),
                 ModItem::Import(item) => {
                     let import = &self.data.imports[item.index];
                     if import.is_glob {
@@ -103,6 +135,28 @@ impl Context {
             }
         }
 
+        // Check that at least one of the alternatives of a conditionally compiled item is
+        // enabled
+        let mut reported = HashSet::new();
+        for (name, ast_id) in self.cfg_disabled_items.iter() {
+            if set.contains_key(name) || reported.contains(&name) {
+                continue;
+            }
+            let alternatives = self
+                .cfg_disabled_items
+                .iter()
+                .filter(|(other, _)| other == name)
+                .count();
+            if alternatives > 1 {
+                reported.insert(name);
+                self.diagnostics
+                    .push(diagnostics::ItemTreeDiagnostic::NoCfgAlternativeEnabled {
+                        name: name.clone(),
+                        first: *ast_id,
+                    });
+            }
+        }
+
         ItemTree {
             file_id: self.file,
             top_level,
@@ -113,10 +167,16 @@ impl Context {
 
     /// Lowers a single module item
     fn lower_mod_item(&mut self, item: &ast::ModuleItem) -> Option<ModItems> {
+        if !self.is_cfg_enabled(item) {
+            return None;
+        }
+        self.check_attrs(item);
+
         match item.kind() {
             ast::ModuleItemKind::FunctionDef(ast) => self.lower_function(&ast).map(Into::into),
             ast::ModuleItemKind::StructDef(ast) => self.lower_struct(&ast).map(Into::into),
             ast::ModuleItemKind::TypeAliasDef(ast) => self.lower_type_alias(&ast).map(Into::into),
+            ast::ModuleItemKind::ConstDef(ast) => self.lower_const(&ast).map(Into::into),
             ast::ModuleItemKind::Use(ast) => Some(ModItems(
                 self.lower_use(&ast).into_iter().map(Into::into).collect(),
             )),
@@ -124,6 +184,73 @@ impl Context {
         }
     }
 
+    /// Returns true if the predicates of all `#[cfg(..)]` attributes of the
+    /// specified item hold for the target. Items that are disabled are
+    /// remembered to be able to report names for which no alternative is
+    /// enabled.
+    fn is_cfg_enabled(&mut self, item: &ast::ModuleItem) -> bool {
+        let (name, attrs) = match item.kind() {
+            ast::ModuleItemKind::FunctionDef(it) => (it.name(), it.attrs()),
+            ast::ModuleItemKind::StructDef(it) => (it.name(), it.attrs()),
+            _ => return true,
+        };
+
+        let mut is_enabled = true;
+        for (attr_index, attr) in attrs.enumerate() {
+            if attr
+                .name_ref()
+                .map_or(true, |name_ref| name_ref.text() != CFG_ATTR_NAME)
+            {
+                continue;
+            }
+
+            match CfgExpr::from_attr(&attr) {
+                Some(cfg) => is_enabled &= cfg.eval(&self.target_features),
+                None => {
+                    self.diagnostics
+                        .push(diagnostics::ItemTreeDiagnostic::MalformedCfgAttribute {
+                            item: self.source_ast_id_map.ast_id(item),
+                            attr_index,
+                        })
+                }
+            }
+        }
+
+        if !is_enabled {
+            if let Some(name) = name {
+                self.cfg_disabled_items
+                    .push((name.as_name(), self.source_ast_id_map.ast_id(item)));
+            }
+        }
+
+        is_enabled
+    }
+
+    /// Reports the attributes of the specified item that are not recognized
+    /// for its kind of item.
+    fn check_attrs(&mut self, item: &ast::ModuleItem) {
+        let (attrs, known) = match item.kind() {
+            ast::ModuleItemKind::FunctionDef(it) => (it.attrs(), FUNCTION_ATTRS),
+            ast::ModuleItemKind::StructDef(it) => (it.attrs(), STRUCT_ATTRS),
+            ast::ModuleItemKind::ConstDef(it) => (it.attrs(), CONST_ATTRS),
+            _ => return,
+        };
+
+        for (attr_index, attr) in attrs.enumerate() {
+            let Some(name_ref) = attr.name_ref() else {
+                continue;
+            };
+            if !known.contains(&name_ref.text().as_str()) {
+                self.diagnostics
+                    .push(diagnostics::ItemTreeDiagnostic::UnknownAttribute {
+                        name: name_ref.as_name(),
+                        item: self.source_ast_id_map.ast_id(item),
+                        attr_index,
+                    });
+            }
+        }
+    }
+
     /// Lowers a `use` statement
     fn lower_use(&mut self, use_item: &ast::Use) -> Vec<LocalItemTreeId<Import>> {
         let visibility = lower_visibility(use_item);
@@ -178,6 +305,12 @@ impl Context {
         };
 
         let is_extern = func.is_extern();
+        let is_cold = func.has_atom_attr("cold");
+        let is_no_mangle = func.has_atom_attr("no_mangle");
+        let is_no_instrument = func.has_atom_attr("no_instrument");
+        let is_reflect = func.has_atom_attr("reflect");
+        let is_tail_call = func.has_atom_attr("tail_call");
+        let is_init = func.has_atom_attr("init");
 
         let (types, _types_source_map) = types.finish();
         let ast_id = self.source_ast_id_map.ast_id(func);
@@ -185,6 +318,12 @@ impl Context {
             name,
             visibility,
             is_extern,
+            is_cold,
+            is_no_mangle,
+            is_no_instrument,
+            is_reflect,
+            is_tail_call,
+            is_init,
             types,
             params,
             ret_type,
@@ -284,6 +423,25 @@ impl Context {
         Some(self.data.type_aliases.alloc(res).into())
     }
 
+    /// Lowers a constant (e.g. `const MAX: i64 = 10;`). The initializer is
+    /// not part of the item tree, it is evaluated on demand.
+    fn lower_const(&mut self, const_def: &ast::ConstDef) -> Option<LocalItemTreeId<Const>> {
+        let name = const_def.name()?.as_name();
+        let visibility = lower_visibility(const_def);
+        let mut types = TypeRefMap::builder();
+        let type_ref = types.alloc_from_node_opt(const_def.ascribed_type().as_ref());
+        let ast_id = self.source_ast_id_map.ast_id(const_def);
+        let (types, _types_source_map) = types.finish();
+        let res = Const {
+            name,
+            visibility,
+            types,
+            type_ref,
+            ast_id,
+        };
+        Some(self.data.consts.alloc(res).into())
+    }
+
     fn lower_impl(&mut self, impl_def: &ast::Impl) -> Option<LocalItemTreeId<Impl>> {
         let ast_id = self.source_ast_id_map.ast_id(impl_def);
         let mut types = TypeRefMap::builder();
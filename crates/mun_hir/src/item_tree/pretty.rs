@@ -2,8 +2,8 @@ use std::{fmt, fmt::Write};
 
 use crate::{
     item_tree::{
-        Fields, Function, Impl, Import, ItemTree, LocalItemTreeId, ModItem, Param, RawVisibilityId,
-        Struct, TypeAlias,
+        Const, Fields, Function, Impl, Import, ItemTree, LocalItemTreeId, ModItem, Param,
+        RawVisibilityId, Struct, TypeAlias,
     },
     path::ImportAlias,
     pretty::{print_path, print_type_ref},
@@ -68,6 +68,7 @@ impl Printer<'_> {
             ModItem::Function(it) => self.print_function(it),
             ModItem::Struct(it) => self.print_struct(it),
             ModItem::TypeAlias(it) => self.print_type_alias(it),
+            ModItem::Const(it) => self.print_const(it),
             ModItem::Import(it) => self.print_use(it),
             ModItem::Impl(it) => self.print_impl(it),
         }
@@ -115,6 +116,21 @@ impl Printer<'_> {
         writeln!(self, ";")
     }
 
+    /// Prints a constant to the buffer.
+    fn print_const(&mut self, it: LocalItemTreeId<Const>) -> fmt::Result {
+        let Const {
+            name,
+            visibility,
+            types,
+            type_ref,
+            ast_id: _,
+        } = &self.tree[it];
+        self.print_visibility(*visibility)?;
+        write!(self, "const {name}: ")?;
+        self.print_type_ref(*type_ref, types)?;
+        writeln!(self, " = ..;")
+    }
+
     /// Prints a struct to the buffer.
     fn print_struct(&mut self, it: LocalItemTreeId<Struct>) -> fmt::Result {
         let Struct {
@@ -168,11 +184,35 @@ impl Printer<'_> {
             name,
             visibility,
             is_extern,
+            is_cold,
+            is_no_mangle,
+            is_no_instrument,
+            is_reflect,
+            is_tail_call,
+            is_init,
             types,
             params,
             ret_type,
             ast_id: _,
         } = &self.tree[it];
+        if *is_cold {
+            writeln!(self, "#[cold]")?;
+        }
+        if *is_no_mangle {
+            writeln!(self, "#[no_mangle]")?;
+        }
+        if *is_no_instrument {
+            writeln!(self, "#[no_instrument]")?;
+        }
+        if *is_reflect {
+            writeln!(self, "#[reflect]")?;
+        }
+        if *is_tail_call {
+            writeln!(self, "#[tail_call]")?;
+        }
+        if *is_init {
+            writeln!(self, "#[init]")?;
+        }
         self.print_visibility(*visibility)?;
         if *is_extern {
             write!(self, "extern ")?;
@@ -1,9 +1,23 @@
 use std::fmt;
 
-use crate::{mock::MockDatabase, with_fixture::WithFixture, DefDatabase, DiagnosticSink, Upcast};
+use crate::{
+    mock::MockDatabase, with_fixture::WithFixture, DefDatabase, DiagnosticSink, FileId, Upcast,
+};
 
 fn print_item_tree(text: &str) -> Result<String, fmt::Error> {
     let (db, file_id) = MockDatabase::with_single_file(text);
+    print_file_item_tree(&db, file_id)
+}
+
+fn print_item_tree_with_target_features(text: &str, features: &str) -> Result<String, fmt::Error> {
+    let (mut db, file_id) = MockDatabase::with_single_file(text);
+    let mut target = db.target();
+    target.options.features = features.to_owned();
+    db.set_target(target);
+    print_file_item_tree(&db, file_id)
+}
+
+fn print_file_item_tree(db: &MockDatabase, file_id: FileId) -> Result<String, fmt::Error> {
     let item_tree = db.item_tree(file_id);
     let mut result_str = super::pretty::print_item_tree(db.upcast(), &item_tree)?;
     let mut sink = DiagnosticSink::new(|diag| {
@@ -17,7 +31,7 @@ fn print_item_tree(text: &str) -> Result<String, fmt::Error> {
     item_tree
         .diagnostics
         .iter()
-        .for_each(|diag| diag.add_to(&db, &item_tree, &mut sink));
+        .for_each(|diag| diag.add_to(db, &item_tree, &mut sink));
 
     drop(sink);
     Ok(result_str)
@@ -91,3 +105,77 @@ fn test_duplicate_import() {
     )
     .unwrap());
 }
+
+#[test]
+fn test_attributes() {
+    insta::assert_snapshot!(print_item_tree(
+        r#"
+    #[cold]
+    fn foo() {}
+    #[cold]
+    pub fn bar(a: i32) -> i32 {}
+    fn baz() {}
+    #[no_mangle]
+    fn qux() {}
+    #[no_instrument]
+    fn quux() {}
+    #[reflect]
+    fn corge() {}
+    #[tail_call]
+    fn grault() {}
+    #[init]
+    fn garply() {}
+    "#
+    )
+    .unwrap());
+}
+
+#[test]
+fn test_unknown_attributes() {
+    insta::assert_snapshot!(print_item_tree(
+        r#"
+    #[cold]
+    #[hot]
+    fn foo() {}
+    #[assert_size(4)]
+    #[no_mangle]
+    struct Bar(i32);
+    #[link_section = "data"]
+    #[inline]
+    const BAZ: i32 = 1;
+    "#
+    )
+    .unwrap());
+}
+
+#[test]
+fn test_cfg_target_feature() {
+    insta::assert_snapshot!(print_item_tree_with_target_features(
+        r#"
+    #[cfg(target_feature = "avx2")]
+    fn foo() -> i32 { 1 }
+    #[cfg(not(target_feature = "avx2"))]
+    fn foo() -> i32 { 2 }
+
+    #[cfg(all(target_feature = "avx2", target_feature = "fma"))]
+    fn bar() {}
+    #[cfg(any(target_feature = "sse4.1", not(target_feature = "avx2")))]
+    fn bar() {}
+
+    #[cfg(target_feature = "sse4.1")]
+    fn baz() {}
+    #[cfg(not(target_feature = "fma"))]
+    fn baz() {}
+
+    #[cfg(target_feature = "avx2")]
+    fn quux() {}
+    #[cfg(target_feature = "fma")]
+    fn quux() {}
+
+    #[cfg(target_feature)]
+    fn qux() {}
+    "#,
+        "+avx2,+fma"
+    )
+    .unwrap());
+}
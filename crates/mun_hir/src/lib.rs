@@ -9,20 +9,24 @@
 pub use salsa;
 
 pub use self::code_model::{
-    Field, Function, FunctionData, HasSource, Module, ModuleDef, Package, Struct, StructMemoryKind,
-    TypeAlias,
+    Const, Field, Function, FunctionData, HasSource, InlineAttr, LinkSection, Module, ModuleDef,
+    OptimizeAttr, Package, Struct, StructMemoryKind, TypeAlias,
 };
 pub use crate::{
+    builtin_constant::BuiltinConstant,
+    call_graph::CallGraph,
+    const_eval::{ConstEvalError, ConstValue},
     db::{
-        AstDatabase, AstDatabaseStorage, DefDatabase, DefDatabaseStorage, HirDatabase,
-        HirDatabaseStorage, InternDatabase, InternDatabaseStorage, SourceDatabase,
-        SourceDatabaseStorage, Upcast,
+        AstDatabase, AstDatabaseStorage, BodyQuery, BodyWithSourceMapQuery, DefDatabase,
+        DefDatabaseStorage, HirDatabase, HirDatabaseStorage, InferQuery, InternDatabase,
+        InternDatabaseStorage, ItemTreeQuery, ParseQuery, SourceDatabase, SourceDatabaseStorage,
+        Upcast,
     },
     diagnostics::{Diagnostic, DiagnosticSink},
     display::HirDisplay,
     expr::{
-        ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, ExprScopes, Literal, LogicOp, Ordering, Pat,
-        PatId, RecordLitField, Statement, UnaryOp,
+        ArithOp, BinaryOp, Body, CmpOp, Expr, ExprId, ExprScopes, Label, LabelId, Literal, LogicOp,
+        Ordering, Pat, PatId, RecordLitField, Statement, UnaryOp,
     },
     ids::{ItemLoc, ModuleId},
     in_file::InFile,
@@ -34,8 +38,8 @@ pub use crate::{
     primitive_type::{FloatBitness, IntBitness, Signedness},
     resolve::{resolver_for_expr, resolver_for_scope, Resolver, TypeNs, ValueNs},
     ty::{
-        lower::CallableDef, FloatTy, InferenceResult, IntTy, ResolveBitness, Substitution, Ty,
-        TyKind,
+        lower::CallableDef, FloatTy, FnSig, InferenceResult, IntTy, Layout, ResolveBitness,
+        Substitution, Ty, TyKind,
     },
     visibility::{HasVisibility, Visibility},
 };
@@ -43,7 +47,11 @@ use crate::{name::AsName, source_id::AstIdMap};
 
 #[macro_use]
 mod macros;
+mod builtin_constant;
+mod call_graph;
+mod cfg;
 mod code_model;
+mod const_eval;
 mod db;
 pub mod diagnostics;
 mod display;
@@ -4,7 +4,7 @@ use mun_target::spec::Target;
 use parking_lot::Mutex;
 
 use crate::{
-    db::{AstDatabase, HirDatabase, SourceDatabase, Upcast},
+    db::{AstDatabase, SourceDatabase, Upcast},
     DefDatabase,
 };
 
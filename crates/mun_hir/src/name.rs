@@ -116,6 +116,13 @@ pub mod known {
         bool,
     );
 
+    known_names!(
+        // Builtin constants
+        TARGET_LITTLE_ENDIAN,
+        TARGET_BIG_ENDIAN,
+        POINTER_WIDTH,
+    );
+
     #[macro_export]
     macro_rules! name {
         ($ident:ident) => {
@@ -2,8 +2,8 @@ use rustc_hash::FxHashSet;
 
 use crate::{
     db::DefDatabase, ids::ItemDefinitionId, mock::MockDatabase, package_defs::PackageDefs,
-    with_fixture::WithFixture, DiagnosticSink, Function, HirDatabase, Module, Package, Struct,
-    TypeAlias,
+    with_fixture::WithFixture, Const, DiagnosticSink, Function, HirDatabase, Module, Package,
+    Struct, TypeAlias,
 };
 
 #[test]
@@ -324,7 +324,21 @@ fn tree_for_module(
                     node.push(format!("use type {fully_qualified_name}"));
                 }
             }
-            ItemDefinitionId::PrimitiveType(_) => {}
+            ItemDefinitionId::ConstId(konst) => {
+                let konst: Const = (*konst).into();
+                let name = konst.name(db);
+                if is_local {
+                    node.push(format!("const {name}"));
+                } else {
+                    let fully_qualified_name = format!(
+                        "{}::{}",
+                        fully_qualified_module_path(db, konst.module(db)),
+                        name
+                    );
+                    node.push(format!("use const {fully_qualified_name}"));
+                }
+            }
+            ItemDefinitionId::PrimitiveType(_) | ItemDefinitionId::BuiltinConstant(_) => {}
         }
     }
 
@@ -22,6 +22,15 @@ pub(crate) fn print_type_ref<W: Write>(
             print_type_ref(db, type_ref, *elem, write)?;
             write!(write, "]")
         }
+        TypeRef::Slice(elem) => {
+            write!(write, "&[")?;
+            print_type_ref(db, type_ref, *elem, write)?;
+            write!(write, "]")
+        }
+        TypeRef::Ref(inner) => {
+            write!(write, "&mut ")?;
+            print_type_ref(db, type_ref, *inner, write)
+        }
         TypeRef::Tuple(elems) => {
             write!(write, "(")?;
             for (i, elem) in elems.iter().enumerate() {
@@ -32,6 +41,21 @@ pub(crate) fn print_type_ref<W: Write>(
             }
             write!(write, ")")
         }
+        TypeRef::FnPtr { params, ret } => {
+            write!(write, "fn(")?;
+            for (i, param) in params.iter().enumerate() {
+                if i != 0 {
+                    write!(write, ", ")?;
+                }
+                print_type_ref(db, type_ref, *param, write)?;
+            }
+            write!(write, ")")?;
+            if !matches!(&type_ref[*ret], TypeRef::Tuple(elems) if elems.is_empty()) {
+                write!(write, " -> ")?;
+                print_type_ref(db, type_ref, *ret, write)?;
+            }
+            Ok(())
+        }
         TypeRef::Error => write!(write, "{{unknown}}"),
     }
 }
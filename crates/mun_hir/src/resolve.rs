@@ -1,11 +1,12 @@
 use std::sync::Arc;
 
 use crate::{
+    builtin_constant::BuiltinConstant,
     expr::{scope::LocalScopeId, PatId},
     has_module::HasModule,
     ids::{
-        DefWithBodyId, FunctionId, ImplId, ItemContainerId, ItemDefinitionId, Lookup, ModuleId,
-        StructId, TypeAliasId,
+        ConstId, DefWithBodyId, FunctionId, ImplId, ItemContainerId, ItemDefinitionId, Lookup,
+        ModuleId, StructId, TypeAliasId,
     },
     item_scope::BUILTIN_SCOPE,
     module_tree::LocalModuleId,
@@ -53,6 +54,8 @@ pub enum ValueNs {
     LocalBinding(PatId),
     FunctionId(FunctionId),
     StructId(StructId),
+    ConstId(ConstId),
+    BuiltinConstant(BuiltinConstant),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -160,6 +163,8 @@ impl Resolver {
             let (res, vis) = match per_ns.take_values()? {
                 (ItemDefinitionId::FunctionId(id), vis) => (ValueNs::FunctionId(id), vis),
                 (ItemDefinitionId::StructId(id), vis) => (ValueNs::StructId(id), vis),
+                (ItemDefinitionId::ConstId(id), vis) => (ValueNs::ConstId(id), vis),
+                (ItemDefinitionId::BuiltinConstant(id), vis) => (ValueNs::BuiltinConstant(id), vis),
                 (
                     ItemDefinitionId::ModuleId(_)
                     | ItemDefinitionId::TypeAliasId(_)
@@ -206,7 +211,10 @@ impl Resolver {
                                     TypeNs::PrimitiveType(id)
                                 }
                                 (
-                                    ItemDefinitionId::ModuleId(_) | ItemDefinitionId::FunctionId(_),
+                                    ItemDefinitionId::ModuleId(_)
+                                    | ItemDefinitionId::FunctionId(_)
+                                    | ItemDefinitionId::ConstId(_)
+                                    | ItemDefinitionId::BuiltinConstant(_),
                                     _,
                                 ) => return None,
                             };
@@ -247,7 +255,13 @@ impl Resolver {
                 (ItemDefinitionId::StructId(id), vis) => (TypeNs::StructId(id), vis),
                 (ItemDefinitionId::TypeAliasId(id), vis) => (TypeNs::TypeAliasId(id), vis),
                 (ItemDefinitionId::PrimitiveType(id), vis) => (TypeNs::PrimitiveType(id), vis),
-                (ItemDefinitionId::ModuleId(_) | ItemDefinitionId::FunctionId(_), _) => {
+                (
+                    ItemDefinitionId::ModuleId(_)
+                    | ItemDefinitionId::FunctionId(_)
+                    | ItemDefinitionId::ConstId(_)
+                    | ItemDefinitionId::BuiltinConstant(_),
+                    _,
+                ) => {
                     return None;
                 }
             };
@@ -381,10 +395,17 @@ impl HasResolver for TypeAliasId {
     }
 }
 
+impl HasResolver for ConstId {
+    fn resolver(self, db: &dyn DefDatabase) -> Resolver {
+        self.module(db).resolver(db)
+    }
+}
+
 impl HasResolver for DefWithBodyId {
     fn resolver(self, db: &dyn DefDatabase) -> Resolver {
         match self {
             DefWithBodyId::FunctionId(f) => f.resolver(db),
+            DefWithBodyId::ConstId(c) => c.resolver(db),
         }
     }
 }
@@ -3,7 +3,7 @@ use rustc_hash::FxHashMap;
 
 use crate::{
     code_model::src::HasSource,
-    ids::{DefWithBodyId, FunctionId, ItemDefinitionId, Lookup, StructId, TypeAliasId},
+    ids::{ConstId, DefWithBodyId, FunctionId, ItemDefinitionId, Lookup, StructId, TypeAliasId},
     item_scope::ItemScope,
     DefDatabase, FileId, HirDatabase, InFile, ModuleId,
 };
@@ -34,6 +34,10 @@ impl SourceToDefContext<'_, '_> {
                         let def = self.fn_to_def(container.with_value(it))?;
                         DefWithBodyId::from(def).into()
                     },
+                    ast::ConstDef(it) => {
+                        let def = self.const_to_def(container.with_value(it))?;
+                        DefWithBodyId::from(def).into()
+                    },
                     _ => continue,
                 }
             };
@@ -55,6 +59,17 @@ impl SourceToDefContext<'_, '_> {
         def_map.functions.get(&src).copied()
     }
 
+    /// Find the `ConstId` associated with the specified syntax tree node.
+    fn const_to_def(&mut self, src: InFile<ast::ConstDef>) -> Option<ConstId> {
+        let container = self.find_container(src.as_ref().map(AstNode::syntax))?;
+        let db = self.db;
+        let def_map = &*self
+            .cache
+            .entry(container)
+            .or_insert_with(|| container.source_to_def_map(db));
+        def_map.consts.get(&src).copied()
+    }
+
     /// Finds the `ModuleId` associated with the specified `file`
     fn file_to_def(&self, file_id: FileId) -> Option<ModuleId> {
         let source_root_id = self.db.file_source_root(file_id);
@@ -139,6 +154,10 @@ impl SourceToDef for ItemScope {
                     let src = id.lookup(db).source(db);
                     map.type_aliases.insert(src, id);
                 }
+                ItemDefinitionId::ConstId(id) => {
+                    let src = id.lookup(db).source(db);
+                    map.consts.insert(src, id);
+                }
                 _ => {}
             }
         }
@@ -157,4 +176,5 @@ pub(crate) struct SourceToDefMap {
     functions: FxHashMap<InFile<ast::FunctionDef>, FunctionId>,
     structs: FxHashMap<InFile<ast::StructDef>, StructId>,
     type_aliases: FxHashMap<InFile<ast::TypeAliasDef>, TypeAliasId>,
+    consts: FxHashMap<InFile<ast::ConstDef>, ConstId>,
 }
@@ -70,6 +70,7 @@ register_ast_id_node! {
         StructDef,
         Impl,
         TypeAliasDef,
+        ConstDef,
     Param
 }
 
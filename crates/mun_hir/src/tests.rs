@@ -2,9 +2,10 @@ use std::sync::Arc;
 
 use crate::{
     db::{DefDatabase, SourceDatabase},
+    diagnostics::DiagnosticSink,
     mock::MockDatabase,
     with_fixture::WithFixture,
-    PackageId,
+    Package, PackageId,
 };
 
 /// This function tests that the `ModuleData` of a module does not change if the
@@ -49,3 +50,96 @@ fn check_package_defs_does_not_change() {
         );
     }
 }
+
+/// Tests that two `#[no_mangle]` functions in different modules cannot export
+/// the same symbol, while regular functions with the same name can.
+#[test]
+fn no_mangle_duplicate_symbol() {
+    let db = MockDatabase::with_files(
+        r#"
+    //- /foo.mun
+    #[no_mangle]
+    fn bar() {}
+    fn baz() {}
+
+    //- /mod.mun
+    #[no_mangle]
+    fn bar() {}
+    fn baz() {}
+    "#,
+    );
+
+    let mut messages = Vec::new();
+    let mut sink = DiagnosticSink::new(|diag| messages.push(diag.message()));
+    for module in Package::all(&db).iter().flat_map(|pkg| pkg.modules(&db)) {
+        module.diagnostics(&db, &mut sink);
+    }
+    drop(sink);
+
+    assert_eq!(
+        messages,
+        vec!["the symbol `bar` is already exported by another `#[no_mangle]` function"]
+    );
+}
+
+/// Tests that `#[reflect]` functions with the same name in different modules
+/// are allowed, because they are retained under the path of the function.
+#[test]
+fn reflect_same_name() {
+    let db = MockDatabase::with_files(
+        r#"
+    //- /foo.mun
+    #[reflect]
+    fn bar() {}
+    fn baz() {}
+
+    //- /mod.mun
+    #[reflect]
+    fn bar() {}
+    #[reflect]
+    fn baz() {}
+    "#,
+    );
+
+    let mut messages = Vec::new();
+    let mut sink = DiagnosticSink::new(|diag| messages.push(diag.message()));
+    for module in Package::all(&db).iter().flat_map(|pkg| pkg.modules(&db)) {
+        module.diagnostics(&db, &mut sink);
+    }
+    drop(sink);
+
+    assert!(messages.is_empty(), "{messages:?}");
+}
+
+/// Tests that the runtime must be able to call `#[init]` functions without
+/// arguments.
+#[test]
+fn init_function_signature() {
+    let db = MockDatabase::with_files(
+        r#"
+    //- /mod.mun
+    #[init]
+    pub fn setup() {}
+    #[init]
+    fn setup_private() {}
+    #[init]
+    fn with_arg(a: i32) {}
+    #[init]
+    fn with_return() -> i32 { 0 }
+    #[init]
+    extern fn external();
+    "#,
+    );
+
+    let mut messages = Vec::new();
+    let mut sink = DiagnosticSink::new(|diag| messages.push(diag.message()));
+    for module in Package::all(&db).iter().flat_map(|pkg| pkg.modules(&db)) {
+        module.diagnostics(&db, &mut sink);
+    }
+    drop(sink);
+
+    assert_eq!(
+        messages,
+        vec!["`#[init]` functions cannot be extern, take arguments or return a value"; 3]
+    );
+}
@@ -1,4 +1,5 @@
 mod infer;
+mod layout;
 pub(super) mod lower;
 mod op;
 mod primitives;
@@ -8,6 +9,7 @@ use std::{fmt, iter::FromIterator, mem, ops::Deref, sync::Arc};
 
 pub(crate) use infer::infer_query;
 pub use infer::InferenceResult;
+pub use layout::Layout;
 pub(crate) use lower::{callable_item_sig, fn_sig_for_fn, type_for_def, CallableDef, TypableDef};
 pub use primitives::{FloatTy, IntTy};
 pub use resolve::ResolveBitness;
@@ -16,6 +18,7 @@ use smallvec::SmallVec;
 use crate::{
     display::{HirDisplay, HirFormatter},
     ty::{infer::InferTy, lower::fn_sig_for_struct_constructor},
+    utils::make_mut_slice,
     HasVisibility, HirDatabase, Struct, StructMemoryKind, TypeAlias, Visibility,
 };
 
@@ -65,9 +68,42 @@ pub enum TyKind {
     /// ```
     FnDef(CallableDef, Substitution),
 
+    /// A pointer to a function with the given signature. Written as
+    /// `fn(i64) -> i64`.
+    ///
+    /// For example the type of `f` here:
+    ///
+    /// ```mun
+    /// fn apply(f: fn(i64) -> i64, x: i64) -> i64 { f(x) }
+    /// ```
+    FnPtr(FnSig),
+
     /// An dynamically sized array type
     Array(Ty),
 
+    /// A slice that borrows the elements of an array. Written as `&[T]`.
+    ///
+    /// Slices can only be passed to extern functions, which receive them as a
+    /// pointer to the first element and the number of elements:
+    ///
+    /// ```mun
+    /// extern fn fill(buffer: &[i64]);
+    /// fn main() { let a = [0, 0, 0]; fill(&a); }
+    /// ```
+    Slice(Ty),
+
+    /// A mutable reference to a value of a primitive type. Written as
+    /// `&mut T`.
+    ///
+    /// Mutable references can only be used for the parameters of functions.
+    /// They are passed as raw pointers, which enables a host to receive
+    /// values through out-parameters:
+    ///
+    /// ```mun
+    /// pub fn div_rem(a: i64, b: i64, rem: &mut i64) -> i64 { *rem = a % b; a / b }
+    /// ```
+    Ref(Ty),
+
     /// A placeholder for a type which could not be computed; this is propagated
     /// to avoid useless error messages. Doubles as a placeholder where type
     /// variables are inserted before type checking, since we want to try to
@@ -158,6 +194,24 @@ impl Ty {
         }
     }
 
+    /// If this type represents a slice type, returns a reference to the
+    /// element type.
+    pub fn as_slice(&self) -> Option<&Ty> {
+        match self.interned() {
+            TyKind::Slice(element_ty) => Some(element_ty),
+            _ => None,
+        }
+    }
+
+    /// If this type represents a mutable reference, returns a reference to the
+    /// referenced type.
+    pub fn as_mut_ref(&self) -> Option<&Ty> {
+        match self.interned() {
+            TyKind::Ref(inner_ty) => Some(inner_ty),
+            _ => None,
+        }
+    }
+
     /// Returns true if this type represents the empty tuple type
     pub fn is_empty(&self) -> bool {
         matches!(self.interned(), TyKind::Tuple(0, _))
@@ -181,6 +235,15 @@ impl Ty {
     pub fn callable_sig(&self, db: &dyn HirDatabase) -> Option<FnSig> {
         match self.interned() {
             TyKind::FnDef(def, _) => Some(db.callable_sig(*def)),
+            TyKind::FnPtr(sig) => Some(sig.clone()),
+            _ => None,
+        }
+    }
+
+    /// If this type represents a function pointer, returns its signature.
+    pub fn as_fn_ptr(&self) -> Option<&FnSig> {
+        match self.interned() {
+            TyKind::FnPtr(sig) => Some(sig),
             _ => None,
         }
     }
@@ -219,6 +282,9 @@ impl Ty {
             TyKind::Float(ty) => Some(format!("core::{}", ty.as_str())),
             TyKind::Int(ty) => Some(format!("core::{}", ty.as_str())),
             TyKind::Array(ty) => Some(format!("[{}]", ty.display(db))),
+            TyKind::Slice(ty) => Some(format!("&[{}]", ty.display(db))),
+            TyKind::Ref(ty) => Some(format!("&mut {}", ty.display(db))),
+            TyKind::FnPtr(_) => Some(format!("{}", self.display(db))),
             _ => None,
         }
     }
@@ -258,10 +324,16 @@ impl Ty {
         match (self.interned(), other.interned()) {
             (TyKind::Struct(s1), TyKind::Struct(s2)) => s1 == s2,
             (TyKind::Tuple(_, substs1), TyKind::Tuple(_, substs2)) => substs1 == substs2,
-            (TyKind::Array(_), TyKind::Array(_)) | (TyKind::Bool, TyKind::Bool) => true,
+            (TyKind::Array(_), TyKind::Array(_))
+            | (TyKind::Slice(_), TyKind::Slice(_))
+            | (TyKind::Ref(_), TyKind::Ref(_))
+            | (TyKind::Bool, TyKind::Bool) => true,
             (TyKind::Float(f1), TyKind::Float(f2)) => f1 == f2,
             (TyKind::Int(i1), TyKind::Int(i2)) => i1 == i2,
             (TyKind::FnDef(def, _), TyKind::FnDef(def2, _)) => def == def2,
+            (TyKind::FnPtr(sig1), TyKind::FnPtr(sig2)) => {
+                sig1.params().len() == sig2.params().len()
+            }
             _ => false,
         }
     }
@@ -329,7 +401,7 @@ impl TypeWalk for Substitution {
 
 /// A function signature as seen by type inference: Several parameter types and
 /// one return type.
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
 pub struct FnSig {
     params_and_return: Arc<[Ty]>,
 }
@@ -350,6 +422,11 @@ impl FnSig {
         &self.params_and_return[self.params_and_return.len() - 1]
     }
 
+    /// Returns all parameter types followed by the return type.
+    pub fn params_and_return(&self) -> &[Ty] {
+        &self.params_and_return
+    }
+
     pub fn marshallable(&self, db: &dyn HirDatabase) -> bool {
         for ty in self.params_and_return.iter() {
             if let Some(s) = ty.as_struct() {
@@ -400,7 +477,18 @@ impl HirDisplay for Ty {
                 f.write_joined(sig.params(), ", ")?;
                 write!(f, ") -> {}", sig.ret().display(f.db))
             }
+            TyKind::FnPtr(sig) => {
+                write!(f, "fn(")?;
+                f.write_joined(sig.params(), ", ")?;
+                write!(f, ")")?;
+                if !sig.ret().is_empty() {
+                    write!(f, " -> {}", sig.ret().display(f.db))?;
+                }
+                Ok(())
+            }
             TyKind::Array(elem_ty) => write!(f, "[{}]", elem_ty.display(f.db)),
+            TyKind::Slice(elem_ty) => write!(f, "&[{}]", elem_ty.display(f.db)),
+            TyKind::Ref(inner_ty) => write!(f, "&mut {}", inner_ty.display(f.db)),
             TyKind::Unknown => write!(f, "{{unknown}}"),
         }
     }
@@ -438,7 +526,12 @@ pub trait TypeWalk {
 impl TypeWalk for Ty {
     fn walk(&self, f: &mut impl FnMut(&Ty)) {
         match self.interned() {
-            TyKind::Array(elem_ty) => f(elem_ty),
+            TyKind::Array(elem_ty) | TyKind::Slice(elem_ty) | TyKind::Ref(elem_ty) => f(elem_ty),
+            TyKind::FnPtr(sig) => {
+                for ty in sig.params_and_return.iter() {
+                    ty.walk(f);
+                }
+            }
             _ => {
                 if let Some(substs) = self.type_parameters() {
                     substs.walk(f);
@@ -450,7 +543,12 @@ impl TypeWalk for Ty {
 
     fn walk_mut(&mut self, f: &mut impl FnMut(&mut Ty)) {
         match self.interned_mut() {
-            TyKind::Array(elem_ty) => f(elem_ty),
+            TyKind::Array(elem_ty) | TyKind::Slice(elem_ty) | TyKind::Ref(elem_ty) => f(elem_ty),
+            TyKind::FnPtr(sig) => {
+                for ty in make_mut_slice(&mut sig.params_and_return) {
+                    ty.walk_mut(f);
+                }
+            }
             _ => {
                 if let Some(substs) = self.type_parameters_mut() {
                     substs.walk_mut(f);
@@ -1,21 +1,22 @@
-use std::{ops::Index, sync::Arc};
+use std::{cmp, ops::Index, sync::Arc};
 
 use la_arena::ArenaMap;
 use rustc_hash::FxHashSet;
 
 use crate::{
-    code_model::{Struct, StructKind},
+    code_model::{Const, DefWithBody, Struct, StructKind},
     diagnostics::DiagnosticSink,
-    expr::{Body, Expr, ExprId, Literal, Pat, PatId, RecordLitField, Statement, UnaryOp},
+    expr::{Body, Expr, ExprId, LabelId, Literal, Pat, PatId, RecordLitField, Statement, UnaryOp},
+    ids::ItemDefinitionId,
     name_resolution::Namespace,
-    resolve::{Resolver, TypeNs, ValueNs},
+    resolve::{Resolver, ScopeDef, TypeNs, ValueNs},
     ty::{
         infer::{diagnostics::InferenceDiagnostic, type_variable::TypeVariableTable},
         lower::LowerDiagnostic,
-        op, Ty, TypableDef,
+        op, CallableDef, Substitution, Ty, TypableDef,
     },
     type_ref::LocalTypeRefId,
-    BinaryOp, Function, HirDatabase, Name, Path,
+    BinaryOp, CmpOp, FloatBitness, HirDatabase, IntBitness, Name, Path, Signedness,
 };
 
 mod place_expr;
@@ -28,8 +29,10 @@ use crate::{
     resolve::{resolver_for_expr, HasResolver},
     ty::{
         primitives::{FloatTy, IntTy},
+        resolve::ResolveBitness,
         TyKind,
     },
+    utils::lev_distance,
 };
 
 mod coerce;
@@ -56,6 +59,10 @@ pub struct InferenceResult {
     pub(crate) type_of_pat: ArenaMap<PatId, Ty>,
     pub(crate) diagnostics: Vec<diagnostics::InferenceDiagnostic>,
 
+    /// Literals whose type was not constrained by the body and therefore fell
+    /// back to the default integer or floating-point type.
+    pub(crate) defaulted_literals: FxHashSet<ExprId>,
+
     /// Interned Unknown to return references to.
     standard_types: InternedStandardTypes,
 }
@@ -79,12 +86,18 @@ impl Index<PatId> for InferenceResult {
 }
 
 impl InferenceResult {
+    /// Returns true if the type of the specified literal expression was not
+    /// constrained by its usage and fell back to the default type.
+    pub(crate) fn is_defaulted_literal(&self, expr: ExprId) -> bool {
+        self.defaulted_literals.contains(&expr)
+    }
+
     /// Adds all the `InferenceDiagnostic`s of the result to the
     /// `DiagnosticSink`.
     pub(crate) fn add_diagnostics(
         &self,
         db: &dyn HirDatabase,
-        owner: Function,
+        owner: DefWithBody,
         sink: &mut DiagnosticSink<'_>,
     ) {
         self.diagnostics
@@ -102,10 +115,11 @@ pub fn infer_query(db: &dyn HirDatabase, def: DefWithBodyId) -> Arc<InferenceRes
     let mut ctx = InferenceResultBuilder::new(db, &body, resolver);
 
     match def {
-        DefWithBodyId::FunctionId(_) => ctx.infer_signature(),
+        DefWithBodyId::FunctionId(_) | DefWithBodyId::ConstId(_) => ctx.infer_signature(),
     }
 
     ctx.infer_body();
+    ctx.fallback_numeric_literals();
 
     Arc::new(ctx.resolve_all())
 }
@@ -128,6 +142,10 @@ impl InferTy {
         }
     }
 
+    /// Returns the type of an unconstrained literal. Like in Rust, integer
+    /// literals fall back to `i32` and floating-point literals to `f64`.
+    /// Changing the integer fallback would silently change the layout and
+    /// overflow behavior of existing programs, so it is deliberately kept.
     fn fallback_value(self) -> Ty {
         match self {
             InferTy::Type(..) => TyKind::Unknown,
@@ -139,11 +157,24 @@ impl InferTy {
 }
 
 enum ActiveLoop {
-    Loop(Ty, Expectation),
+    Loop(LoopInfo),
     While,
     For,
 }
 
+/// Information on a `loop` expression that is being inferred.
+struct LoopInfo {
+    /// The current type of the loop (initially `never`)
+    ty: Ty,
+    /// The expected type of the loop
+    expected: Expectation,
+    /// The `break` expressions of the loop
+    breaks: Vec<ExprId>,
+    /// The first `break` expression whose value conflicts with the value of an
+    /// earlier `break` expression
+    conflicting_break: Option<ExprId>,
+}
+
 /// The inference context contains all information needed during type inference.
 struct InferenceResultBuilder<'a> {
     db: &'a dyn HirDatabase,
@@ -153,18 +184,28 @@ struct InferenceResultBuilder<'a> {
     type_of_expr: ArenaMap<ExprId, Ty>,
     type_of_pat: ArenaMap<PatId, Ty>,
     diagnostics: Vec<InferenceDiagnostic>,
+    defaulted_literals: FxHashSet<ExprId>,
+
+    /// The bindings of the function-local statics that are not declared `mut`
+    /// and can therefore not be assigned to.
+    immutable_statics: FxHashSet<PatId>,
 
     type_variables: TypeVariableTable,
 
-    /// Information on the current loop that we're processing (or None if we're
-    /// not in a loop) the entry contains the current type of the loop
-    /// statement (initially `never`) and the expected type of the loop
-    /// expression. Both these values are updated when a break statement is
-    /// encountered.
-    active_loop: Option<ActiveLoop>,
+    /// Information on the loops that we're processing, innermost last,
+    /// together with their labels. For a `loop` expression the entry contains
+    /// the current type of the loop statement (initially `never`) and the
+    /// expected type of the loop expression. Both these values are updated
+    /// when a break statement is encountered.
+    active_loops: Vec<(Option<LabelId>, ActiveLoop)>,
 
     /// The return type of the function being inferred.
     return_ty: Ty,
+
+    /// Expressions whose value is returned from the function when evaluated;
+    /// the body itself, the tail of a block in return position and the
+    /// branches of an `if` in return position.
+    return_positions: FxHashSet<ExprId>,
 }
 
 impl<'a> InferenceResultBuilder<'a> {
@@ -175,12 +216,15 @@ impl<'a> InferenceResultBuilder<'a> {
             type_of_expr: ArenaMap::default(),
             type_of_pat: ArenaMap::default(),
             diagnostics: Vec::default(),
-            active_loop: None,
+            defaulted_literals: FxHashSet::default(),
+            immutable_statics: FxHashSet::default(),
+            active_loops: Vec::new(),
             type_variables: TypeVariableTable::default(),
             db,
             body,
             resolver,
             return_ty: TyKind::Unknown.intern(), // set in collect_fn_signature
+            return_positions: FxHashSet::default(),
         }
     }
 
@@ -251,21 +295,155 @@ impl<'a> InferenceResultBuilder<'a> {
 
     /// Infer the types of all the expressions and sub-expressions in the body.
     fn infer_body(&mut self) {
+        self.collect_return_positions(self.body.body_expr());
         self.infer_expr_coerce(
             self.body.body_expr(),
             &Expectation::has_type(self.return_ty.clone()),
         );
     }
 
+    /// Marks `expr` and all expressions whose value becomes the value of
+    /// `expr` as return positions.
+    fn collect_return_positions(&mut self, expr: ExprId) {
+        self.return_positions.insert(expr);
+        match &self.body[expr] {
+            Expr::Block {
+                tail: Some(tail), ..
+            } => self.collect_return_positions(*tail),
+            Expr::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                self.collect_return_positions(*then_branch);
+                if let Some(else_branch) = else_branch {
+                    self.collect_return_positions(*else_branch);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns true if falling through `expr` without producing a value is an
+    /// error because `expr` is in return position of a function that
+    /// returns a value.
+    fn must_return_value(&self, expr: ExprId) -> bool {
+        self.return_positions.contains(&expr)
+            && !self.return_ty.is_empty()
+            && !self.return_ty.is_unknown()
+    }
+
+    /// Reports that `expr` has type `found` where a value of type `expected`
+    /// was expected. A value that is returned from a function that does not
+    /// return anything is reported separately, because most likely the
+    /// return type of the function was omitted.
+    fn push_mismatched_types(&mut self, expr: ExprId, expected: Ty, found: Ty) {
+        let diagnostic = if self.return_positions.contains(&expr) && self.return_ty.is_empty() {
+            // The value of a block or `if` expression is the value of its tail or
+            // branches, which have already been reported.
+            if matches!(
+                self.body[expr],
+                Expr::Block { tail: Some(_), .. } | Expr::If { .. }
+            ) {
+                return;
+            }
+            InferenceDiagnostic::MissingReturnType { id: expr, found }
+        } else if let Some(is_lossless) = self.is_lossless_numeric_conversion(&found, &expected) {
+            InferenceDiagnostic::MismatchedNumericType {
+                id: expr,
+                expected,
+                found,
+                is_narrowing: !is_lossless,
+            }
+        } else {
+            InferenceDiagnostic::MismatchedTypes {
+                id: expr,
+                expected,
+                found,
+            }
+        };
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Returns whether every value of the numeric type `from` can be
+    /// represented by the numeric type `to`, or `None` if either type is not
+    /// numeric.
+    fn is_lossless_numeric_conversion(&self, from: &Ty, to: &Ty) -> Option<bool> {
+        let data_layout = self.db.target_data_layout();
+        let int_bits = |ty: &IntTy| match ty.resolve(&data_layout).bitness {
+            IntBitness::X8 => 8,
+            IntBitness::X16 => 16,
+            IntBitness::X32 => 32,
+            IntBitness::X64 => 64,
+            IntBitness::X128 | IntBitness::Xsize => 128,
+        };
+
+        let is_lossless = match (from.interned(), to.interned()) {
+            (TyKind::Int(from), TyKind::Int(to)) => {
+                let (from_bits, to_bits) = (int_bits(from), int_bits(to));
+                match (from.signedness, to.signedness) {
+                    (Signedness::Signed, Signedness::Unsigned) => false,
+                    (Signedness::Unsigned, Signedness::Signed) => to_bits > from_bits,
+                    _ => to_bits >= from_bits,
+                }
+            }
+            (TyKind::Float(from), TyKind::Float(to)) => {
+                from.bitness == FloatBitness::X32 || to.bitness == FloatBitness::X64
+            }
+            (TyKind::Int(from), TyKind::Float(to)) => {
+                // An integer fits if its magnitude fits in the significand of the float
+                let significand_bits = match to.bitness {
+                    FloatBitness::X32 => 24,
+                    FloatBitness::X64 => 53,
+                };
+                let magnitude_bits = match from.signedness {
+                    Signedness::Signed => int_bits(from) - 1,
+                    Signedness::Unsigned => int_bits(from),
+                };
+                magnitude_bits <= significand_bits
+            }
+            (TyKind::Float(_), TyKind::Int(_)) => false,
+            _ => return None,
+        };
+        Some(is_lossless)
+    }
+
+    /// Instantiates the types of all numeric literals that were not
+    /// constrained by their usage in the body with their fallback type. Up
+    /// until this point the type of an unsuffixed literal is an inference
+    /// variable that is unified with every use throughout the body; e.g.
+    /// passing it to a function that expects an `i8` makes it an `i8`.
+    fn fallback_numeric_literals(&mut self) {
+        // Collect all unconstrained literals before instantiating any of them,
+        // literals that were unified with each other are all defaulted.
+        let body = self.body;
+        let unconstrained = body
+            .exprs()
+            .filter(|(_, expr)| matches!(expr, Expr::Literal(Literal::Int(_) | Literal::Float(_))))
+            .filter_map(|(expr, _)| {
+                let ty = self.type_of_expr.get(expr)?.clone();
+                let ty = self.resolve_ty_as_far_as_possible(ty);
+                match ty.interned() {
+                    TyKind::InferenceVar(InferTy::Int(_) | InferTy::Float(_)) => Some((expr, ty)),
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for (expr, ty) in unconstrained {
+            if let TyKind::InferenceVar(tv) = ty.interned() {
+                let fallback = tv.fallback_value();
+                self.unify(&ty, &fallback);
+            }
+            self.defaulted_literals.insert(expr);
+        }
+    }
+
     /// Infers the type of the `tgt_expr`
     fn infer_expr(&mut self, tgt_expr: ExprId, expected: &Expectation) -> Ty {
         let ty = self.infer_expr_inner(tgt_expr, expected, &CheckParams::default());
         if !self.unify(&ty, &expected.ty) {
-            self.diagnostics.push(InferenceDiagnostic::MismatchedTypes {
-                expected: expected.ty.clone(),
-                found: ty.clone(),
-                id: tgt_expr,
-            });
+            self.push_mismatched_types(tgt_expr, expected.ty.clone(), ty.clone());
         };
 
         self.resolve_ty_as_far_as_possible(ty)
@@ -284,11 +462,7 @@ impl<'a> InferenceResultBuilder<'a> {
     /// if coercion failed.
     fn coerce_expr_ty(&mut self, expr: ExprId, ty: Ty, expected: &Expectation) -> Ty {
         let ty = if !self.coerce(&ty, &expected.ty) {
-            self.diagnostics.push(InferenceDiagnostic::MismatchedTypes {
-                expected: expected.ty.clone(),
-                found: ty.clone(),
-                id: expr,
-            });
+            self.push_mismatched_types(expr, expected.ty.clone(), ty.clone());
             ty
         } else if expected.ty.is_unknown() {
             ty
@@ -321,6 +495,7 @@ impl<'a> InferenceResultBuilder<'a> {
                 else_branch,
             } => self.infer_if(tgt_expr, expected, *condition, *then_branch, *else_branch),
             Expr::BinaryOp { lhs, rhs, op } => match op {
+                Some(BinaryOp::CmpOp(op)) => self.infer_cmp_op(tgt_expr, *lhs, *rhs, *op),
                 Some(op) => {
                     let lhs_expected = match op {
                         BinaryOp::LogicOp(..) => Expectation::has_type(TyKind::Bool.intern()),
@@ -335,6 +510,13 @@ impl<'a> InferenceResultBuilder<'a> {
                                 id: tgt_expr,
                                 lhs: *lhs,
                             });
+                        } else if let Some(binding) = self.immutable_static_binding(&resolver, *lhs)
+                        {
+                            self.diagnostics
+                                .push(InferenceDiagnostic::AssignToImmutableStatic {
+                                    lhs: *lhs,
+                                    binding,
+                                });
                         }
                     };
                     let rhs_expected = op::binary_op_rhs_expectation(*op, lhs_ty.clone());
@@ -351,7 +533,9 @@ impl<'a> InferenceResultBuilder<'a> {
                 }
                 _ => error_type(),
             },
-            Expr::Block { statements, tail } => self.infer_block(statements, *tail, expected),
+            Expr::Block { statements, tail } => {
+                self.infer_block(tgt_expr, statements, *tail, expected)
+            }
             Expr::Call { callee: call, args } => self.infer_call(tgt_expr, *call, args, expected),
             Expr::Literal(lit) => match lit {
                 Literal::String(_) => TyKind::Unknown.intern(),
@@ -390,11 +574,20 @@ impl<'a> InferenceResultBuilder<'a> {
 
                 TyKind::Never.intern()
             }
-            Expr::Break { expr } => self.infer_break(tgt_expr, *expr),
-            Expr::Loop { body } => self.infer_loop_expr(tgt_expr, *body, expected),
-            Expr::While { condition, body } => {
-                self.infer_while_expr(tgt_expr, *condition, *body, expected)
-            }
+            Expr::Break { expr, label } => self.infer_break(tgt_expr, *expr, *label),
+            Expr::Continue { .. } => TyKind::Never.intern(),
+            Expr::Loop { body, label } => self.infer_loop_expr(tgt_expr, *body, *label, expected),
+            Expr::While {
+                condition,
+                body,
+                label,
+            } => self.infer_while_expr(tgt_expr, *condition, *body, *label, expected),
+            Expr::For {
+                pat,
+                iterable,
+                body,
+                label,
+            } => self.infer_for_expr(tgt_expr, *pat, *iterable, *body, *label),
             Expr::RecordLit {
                 type_id,
                 fields,
@@ -414,6 +607,8 @@ impl<'a> InferenceResultBuilder<'a> {
                                 self.diagnostics.push(InferenceDiagnostic::NoSuchField {
                                     id: tgt_expr,
                                     field: idx,
+                                    name: field.name.clone(),
+                                    struct_ty: ty.clone(),
                                 });
                                 None
                             }
@@ -425,7 +620,7 @@ impl<'a> InferenceResultBuilder<'a> {
                     self.infer_expr(*expr, &Expectation::has_type(ty.clone()));
                 }
                 if let Some(s) = ty.as_struct() {
-                    self.check_record_lit(tgt_expr, &ty, s, fields);
+                    self.check_record_lit(tgt_expr, &ty, s, fields, spread.is_some());
                 }
                 ty
             }
@@ -487,6 +682,37 @@ impl<'a> InferenceResultBuilder<'a> {
                             error_type()
                         }
                     },
+                    // Taking the address of a function results in a pointer to the function.
+                    // Taking the address of an array borrows its elements as a slice, which is
+                    // only possible where a slice is expected.
+                    UnaryOp::AddrOf => match inner_ty.interned() {
+                        TyKind::FnDef(CallableDef::Function(def), _) => {
+                            TyKind::FnPtr(self.db.callable_sig(CallableDef::Function(*def)))
+                                .intern()
+                        }
+                        TyKind::Array(elem_ty) if expected.ty.as_slice().is_some() => {
+                            TyKind::Slice(elem_ty.clone()).intern()
+                        }
+                        _ => {
+                            self.diagnostics
+                                .push(InferenceDiagnostic::CannotApplyUnaryOp {
+                                    id: *expr,
+                                    ty: inner_ty,
+                                });
+                            error_type()
+                        }
+                    },
+                    UnaryOp::Deref => match inner_ty.as_mut_ref() {
+                        Some(referenced_ty) => referenced_ty.clone(),
+                        None => {
+                            self.diagnostics
+                                .push(InferenceDiagnostic::CannotApplyUnaryOp {
+                                    id: *expr,
+                                    ty: inner_ty,
+                                });
+                            error_type()
+                        }
+                    },
                 }
             }
             Expr::Array(array) => {
@@ -501,6 +727,36 @@ impl<'a> InferenceResultBuilder<'a> {
 
                 TyKind::Array(elem_ty).intern()
             }
+            Expr::Tuple(exprs) => {
+                let tys: Substitution = exprs
+                    .iter()
+                    .map(|expr| self.infer_expr(*expr, &Expectation::none()))
+                    .collect();
+                TyKind::Tuple(tys.len(), tys).intern()
+            }
+            Expr::Cast { expr, type_ref } => {
+                let cast_ty = self.resolve_type(*type_ref);
+                let expr_ty = self.infer_expr(*expr, &Expectation::none());
+                let is_numeric = |ty: &Ty| {
+                    matches!(
+                        ty.interned(),
+                        TyKind::Int(_)
+                            | TyKind::Float(_)
+                            | TyKind::InferenceVar(InferTy::Int(_) | InferTy::Float(_))
+                    )
+                };
+                if expr_ty.is_known()
+                    && cast_ty.is_known()
+                    && !(is_numeric(&expr_ty) && is_numeric(&cast_ty))
+                {
+                    self.diagnostics.push(InferenceDiagnostic::InvalidCast {
+                        id: tgt_expr,
+                        expr_ty,
+                        cast_ty: cast_ty.clone(),
+                    });
+                }
+                cast_ty
+            }
             Expr::Index { base, index } => {
                 let elem_ty = if expected.ty.is_unknown() {
                     self.type_variables.new_type_var()
@@ -552,6 +808,14 @@ impl<'a> InferenceResultBuilder<'a> {
                     });
                 then_ty
             }
+        } else if self.must_return_value(tgt_expr) {
+            // The implicit else branch falls through without returning a value
+            self.diagnostics
+                .push(InferenceDiagnostic::NotAllPathsReturnValue {
+                    id: tgt_expr,
+                    expected: self.return_ty.clone(),
+                });
+            error_type()
         } else {
             if !self.coerce(&then_ty, &Ty::unit()) {
                 self.diagnostics
@@ -564,6 +828,46 @@ impl<'a> InferenceResultBuilder<'a> {
         }
     }
 
+    /// Infers the type of a comparison. Both operands must be of the same type
+    /// and that type must support the comparison operator.
+    fn infer_cmp_op(&mut self, tgt_expr: ExprId, lhs: ExprId, rhs: ExprId, op: CmpOp) -> Ty {
+        let lhs_ty = self.infer_expr(lhs, &Expectation::none());
+        if lhs_ty.is_known() && !op::is_comparable(&lhs_ty) {
+            self.diagnostics
+                .push(InferenceDiagnostic::CannotApplyBinaryOp {
+                    id: tgt_expr,
+                    lhs: lhs_ty,
+                    rhs: error_type(),
+                });
+            self.infer_expr(rhs, &Expectation::none());
+            return TyKind::Bool.intern();
+        }
+
+        // The types of the operands are checked explicitly, instead of reporting a type mismatch
+        // for the right hand side.
+        let rhs_ty = self.infer_expr_inner(
+            rhs,
+            &Expectation::has_type(lhs_ty.clone()),
+            &CheckParams::default(),
+        );
+        let is_compatible = self.unify(&lhs_ty, &rhs_ty);
+        let lhs_ty = self.resolve_ty_as_far_as_possible(lhs_ty);
+        let rhs_ty = self.resolve_ty_as_far_as_possible(rhs_ty);
+        if !is_compatible || (lhs_ty.is_known() && !op::cmp_op_supported(op, &lhs_ty)) {
+            self.diagnostics
+                .push(InferenceDiagnostic::IncompatibleComparison {
+                    id: tgt_expr,
+                    op,
+                    lhs,
+                    lhs_ty,
+                    rhs,
+                    rhs_ty,
+                });
+        }
+
+        TyKind::Bool.intern()
+    }
+
     /// Inferences the type of a call expression.
     fn infer_call(
         &mut self,
@@ -617,6 +921,16 @@ impl<'a> InferenceResultBuilder<'a> {
 
                 ret_ty
             }
+            TyKind::FnPtr(sig) => {
+                // Call through a function pointer
+                let (param_tys, ret_ty) = (sig.params().to_vec(), sig.ret().clone());
+                self.check_call_argument_count(tgt_expr, false, args.len(), param_tys.len());
+                for (&arg, param_ty) in args.iter().zip(param_tys.iter()) {
+                    self.infer_expr_coerce(arg, &Expectation::has_type(param_ty.clone()));
+                }
+
+                ret_ty
+            }
             TyKind::Unknown => {
                 // Error has already been emitted somewhere else
                 error_type()
@@ -678,6 +992,7 @@ impl<'a> InferenceResultBuilder<'a> {
         ty: &Ty,
         expected: Struct,
         fields: &[RecordLitField],
+        has_spread: bool,
     ) {
         let struct_data = expected.data(self.db.upcast());
         if struct_data.kind != StructKind::Record {
@@ -690,6 +1005,11 @@ impl<'a> InferenceResultBuilder<'a> {
             return;
         }
 
+        // All fields that are not explicitly specified are taken from the base expression
+        if has_spread {
+            return;
+        }
+
         let lit_fields: FxHashSet<_> = fields.iter().map(|f| &f.name).collect();
         let missed_fields: Vec<Name> = struct_data
             .fields
@@ -750,6 +1070,8 @@ impl<'a> InferenceResultBuilder<'a> {
                         .type_for_def(TypableDef::Struct(s.into()), Namespace::Values);
                     Some(ty)
                 }
+                ValueNs::ConstId(c) => Some(Const::from(c).ty(self.db)),
+                ValueNs::BuiltinConstant(c) => Some(c.ty()),
             }
         } else {
             // If no value was found, try to resolve the path as a type. This will always
@@ -788,12 +1110,54 @@ impl<'a> InferenceResultBuilder<'a> {
 
             // If the path also cannot be resolved as type, it must be considered an invalid
             // value and there is nothing we can make of this path.
-            self.diagnostics
-                .push(InferenceDiagnostic::UnresolvedValue { id: id.into() });
+            let suggestion = path
+                .as_ident()
+                .and_then(|name| self.find_similar_value_name(resolver, name));
+            self.diagnostics.push(InferenceDiagnostic::UnresolvedValue {
+                id: id.into(),
+                suggestion,
+            });
             None
         }
     }
 
+    /// Returns the name of a value in the scope of `resolver` that is similar to
+    /// `name`. Only locals, parameters, functions and builtin constants are
+    /// considered; types are never suggested where a value is expected.
+    fn find_similar_value_name(&self, resolver: &Resolver, name: &Name) -> Option<Name> {
+        let name = name.to_string();
+
+        // As a loose rule to avoid obviously incorrect suggestions, the edit distance can be at
+        // most one-third of the length of the name.
+        let max_dist = cmp::max(name.len(), 3) / 3;
+
+        // Inner scopes are visited first, so on a tie the innermost name is suggested
+        let mut best_match: Option<(Name, usize)> = None;
+        resolver.visit_all_names(self.db.upcast(), &mut |candidate, def| {
+            let is_value = match def {
+                ScopeDef::Local(_) => true,
+                ScopeDef::PerNs(per_ns) => matches!(
+                    per_ns.take_values(),
+                    Some((
+                        ItemDefinitionId::FunctionId(_)
+                            | ItemDefinitionId::ConstId(_)
+                            | ItemDefinitionId::BuiltinConstant(_),
+                        _
+                    ))
+                ),
+            };
+            if !is_value {
+                return;
+            }
+
+            let dist = lev_distance(&name, &candidate.to_string());
+            if dist <= max_dist && best_match.as_ref().map_or(true, |(_, best)| dist < *best) {
+                best_match = Some((candidate, dist));
+            }
+        });
+        best_match.map(|(name, _)| name)
+    }
+
     fn resolve_all(mut self) -> InferenceResult {
         // FIXME resolve obligations as well (use Guidance if necessary)
         //let mut tv_stack = Vec::new();
@@ -815,6 +1179,21 @@ impl<'a> InferenceResultBuilder<'a> {
             }
             *ty = resolved;
         }
+        // The returned value and the values of `break`s are reported with their type,
+        // which might not have been known yet when the diagnostic was created.
+        for diagnostic in self.diagnostics.iter_mut() {
+            match diagnostic {
+                InferenceDiagnostic::MissingReturnType { found, .. } => {
+                    *found = self.type_variables.resolve_ty_completely(found.clone());
+                }
+                InferenceDiagnostic::MismatchedBreakValues { breaks, .. } => {
+                    for ty in breaks.iter_mut().filter_map(|(_, ty)| ty.as_mut()) {
+                        *ty = self.type_variables.resolve_ty_completely(ty.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
         InferenceResult {
             //            method_resolutions: self.method_resolutions,
             //            field_resolutions: self.field_resolutions,
@@ -823,12 +1202,14 @@ impl<'a> InferenceResultBuilder<'a> {
             type_of_expr: expr_types,
             type_of_pat: pat_types,
             diagnostics: self.diagnostics,
+            defaulted_literals: self.defaulted_literals,
             standard_types: InternedStandardTypes::default(),
         }
     }
 
     fn infer_block(
         &mut self,
+        tgt_expr: ExprId,
         statements: &[Statement],
         tail: Option<ExprId>,
         expected: &Expectation,
@@ -854,6 +1235,22 @@ impl<'a> InferenceResultBuilder<'a> {
                     let ty = self.resolve_ty_as_far_as_possible(ty);
                     self.infer_pat(*pat, ty);
                 }
+                Statement::Static {
+                    pat,
+                    type_ref,
+                    initializer,
+                    is_mut,
+                } => {
+                    let decl_ty = type_ref
+                        .as_ref()
+                        .map_or_else(error_type, |tr| self.resolve_type(*tr));
+                    let ty = self.infer_expr_coerce(*initializer, &Expectation::has_type(decl_ty));
+                    let ty = self.resolve_ty_as_far_as_possible(ty);
+                    self.infer_pat(*pat, ty);
+                    if !is_mut {
+                        self.immutable_statics.insert(*pat);
+                    }
+                }
                 Statement::Expr(expr) => {
                     if self.infer_expr(*expr, &Expectation::none()).is_never() {
                         diverges = true;
@@ -871,6 +1268,14 @@ impl<'a> InferenceResultBuilder<'a> {
             } else {
                 self.coerce_expr_ty(expr, ty, expected)
             }
+        } else if !diverges && self.must_return_value(tgt_expr) {
+            // The block falls through without returning a value
+            self.diagnostics
+                .push(InferenceDiagnostic::NotAllPathsReturnValue {
+                    id: tgt_expr,
+                    expected: self.return_ty.clone(),
+                });
+            error_type()
         } else {
             Ty::unit()
         };
@@ -882,21 +1287,25 @@ impl<'a> InferenceResultBuilder<'a> {
         }
     }
 
-    fn infer_break(&mut self, tgt_expr: ExprId, expr: Option<ExprId>) -> Ty {
-        let expected = match &self.active_loop {
-            Some(ActiveLoop::Loop(_, info)) => info.clone(),
-            Some(_) => {
+    fn infer_break(
+        &mut self,
+        tgt_expr: ExprId,
+        expr: Option<ExprId>,
+        label: Option<LabelId>,
+    ) -> Ty {
+        // A `break` outside of a loop is reported when the body is lowered
+        let Some(target) = self.loop_index(label) else {
+            return TyKind::Never.intern();
+        };
+        let (expected, is_first_break) = match &self.active_loops[target].1 {
+            ActiveLoop::Loop(info) => (info.expected.clone(), info.breaks.is_empty()),
+            _ => {
                 if expr.is_some() {
                     self.diagnostics
                         .push(InferenceDiagnostic::BreakWithValueOutsideLoop { id: tgt_expr });
                 }
                 return TyKind::Never.intern();
             }
-            None => {
-                self.diagnostics
-                    .push(InferenceDiagnostic::BreakOutsideLoop { id: tgt_expr });
-                return TyKind::Never.intern();
-            }
         };
 
         // Infer the type of the break expression
@@ -906,43 +1315,97 @@ impl<'a> InferenceResultBuilder<'a> {
             Ty::unit()
         };
 
-        // Verify that it matches what we expected
-        let ty = if self.unify(&ty, &expected.ty) {
-            ty
-        } else {
+        // Verify that it matches what we expected. The first `break` is checked against
+        // the expected type of the loop, every other `break` against the value of the
+        // earlier `break`s. Conflicts between `break`s are reported when the whole loop
+        // has been inferred.
+        let is_mismatch = !self.unify(&ty, &expected.ty);
+        if is_mismatch && is_first_break {
             self.diagnostics.push(InferenceDiagnostic::MismatchedTypes {
                 expected: expected.ty.clone(),
-                found: ty,
+                found: ty.clone(),
                 id: tgt_expr,
             });
-            expected.ty
-        };
+        }
+        let ty = if is_mismatch { expected.ty } else { ty };
 
         // Update the expected type for the rest of the loop
-        self.active_loop = Some(ActiveLoop::Loop(ty.clone(), Expectation::has_type(ty)));
+        let ActiveLoop::Loop(info) = &mut self.active_loops[target].1 else {
+            unreachable!("break values can only be inferred in a loop")
+        };
+        if is_mismatch && !is_first_break && info.conflicting_break.is_none() {
+            info.conflicting_break = Some(tgt_expr);
+        }
+        info.breaks.push(tgt_expr);
+        info.ty = ty.clone();
+        info.expected = Expectation::has_type(ty);
 
         TyKind::Never.intern()
     }
 
-    fn infer_loop_expr(&mut self, _tgt_expr: ExprId, body: ExprId, expected: &Expectation) -> Ty {
-        if let ActiveLoop::Loop(ty, _) = self.infer_loop_block(
+    /// Returns the index in `active_loops` of the loop with the specified
+    /// label, or of the innermost loop if there is no label.
+    fn loop_index(&self, label: Option<LabelId>) -> Option<usize> {
+        match label {
+            Some(label) => self
+                .active_loops
+                .iter()
+                .rposition(|(loop_label, _)| *loop_label == Some(label)),
+            None => self.active_loops.len().checked_sub(1),
+        }
+    }
+
+    fn infer_loop_expr(
+        &mut self,
+        _tgt_expr: ExprId,
+        body: ExprId,
+        label: Option<LabelId>,
+        expected: &Expectation,
+    ) -> Ty {
+        let ActiveLoop::Loop(info) = self.infer_loop_block(
             body,
-            ActiveLoop::Loop(TyKind::Never.intern(), expected.clone()),
-        ) {
-            ty
-        } else {
+            label,
+            ActiveLoop::Loop(LoopInfo {
+                ty: TyKind::Never.intern(),
+                expected: expected.clone(),
+                breaks: Vec::new(),
+                conflicting_break: None,
+            }),
+        ) else {
             panic!("returned active loop must be a loop")
+        };
+
+        if let Some(id) = info.conflicting_break {
+            let breaks = info
+                .breaks
+                .iter()
+                .map(|&id| {
+                    let Expr::Break { expr, .. } = &self.body[id] else {
+                        unreachable!("expected a break expression")
+                    };
+                    (id, expr.map(|expr| self.type_of_expr[expr].clone()))
+                })
+                .collect();
+            self.diagnostics
+                .push(InferenceDiagnostic::MismatchedBreakValues { id, breaks });
         }
+
+        info.ty
     }
 
-    fn infer_loop_block(&mut self, body: ExprId, lp: ActiveLoop) -> ActiveLoop {
-        let top_level_loop = std::mem::replace(&mut self.active_loop, Some(lp));
+    fn infer_loop_block(
+        &mut self,
+        body: ExprId,
+        label: Option<LabelId>,
+        lp: ActiveLoop,
+    ) -> ActiveLoop {
+        self.active_loops.push((label, lp));
 
         // Infer the body of the loop
         self.infer_expr_coerce(body, &Expectation::has_type(Ty::unit()));
 
-        // Take the result of the loop information and replace with top level loop
-        std::mem::replace(&mut self.active_loop, top_level_loop).unwrap()
+        // Take the result of the loop information
+        self.active_loops.pop().unwrap().1
     }
 
     fn infer_while_expr(
@@ -950,10 +1413,36 @@ impl<'a> InferenceResultBuilder<'a> {
         _tgt_expr: ExprId,
         condition: ExprId,
         body: ExprId,
+        label: Option<LabelId>,
         _expected: &Expectation,
     ) -> Ty {
         self.infer_expr(condition, &Expectation::has_type(TyKind::Bool.intern()));
-        self.infer_loop_block(body, ActiveLoop::While);
+        self.infer_loop_block(body, label, ActiveLoop::While);
+        Ty::unit()
+    }
+
+    /// Infers the type of a `for` loop. The iterable must be an array, of
+    /// which each element is bound to `pat`.
+    fn infer_for_expr(
+        &mut self,
+        _tgt_expr: ExprId,
+        pat: PatId,
+        iterable: ExprId,
+        body: ExprId,
+        label: Option<LabelId>,
+    ) -> Ty {
+        let elem_ty = self.type_variables.new_type_var();
+        let iterable_ty = self.infer_expr(
+            iterable,
+            &Expectation::has_type(TyKind::Array(elem_ty).intern()),
+        );
+        let elem_ty = match iterable_ty.interned() {
+            TyKind::Array(ty) => self.resolve_ty_as_far_as_possible(ty.clone()),
+            _ => error_type(),
+        };
+        self.infer_pat(pat, elem_ty);
+
+        self.infer_loop_block(body, label, ActiveLoop::For);
         Ty::unit()
     }
 
@@ -1046,23 +1535,27 @@ impl From<PatId> for ExprOrPatId {
 
 mod diagnostics {
     use crate::{
-        code_model::{src::HasSource, StructKind},
+        code_model::{DefWithBody, StructKind},
         diagnostics::{
-            AccessUnknownField, BreakOutsideLoop, BreakWithValueOutsideLoop, CannotApplyBinaryOp,
-            CannotApplyUnaryOp, CyclicType, DiagnosticSink, ExpectedFunction, FieldCountMismatch,
-            IncompatibleBranch, InvalidLhs, LiteralOutOfRange, MismatchedStructLit, MismatchedType,
-            MissingElseBranch, MissingFields, NoFields, NoSuchField, ParameterCountMismatch,
-            PrivateAccess, ReturnMissingExpression, UnresolvedType, UnresolvedValue,
+            AccessUnknownField, AssignToImmutableStatic, BreakWithValueOutsideLoop,
+            CannotApplyBinaryOp, CannotApplyUnaryOp, CyclicType, DiagnosticSink, ExpectedFunction,
+            FieldCountMismatch, IncompatibleBranch, IncompatibleComparison, InvalidCast,
+            InvalidLhs, LiteralOutOfRange, MismatchedBreakValues, MismatchedNumericType,
+            MismatchedStructLit, MismatchedType, MissingElseBranch, MissingFields,
+            MissingReturnType, NoFields, NoSuchField, NotAllPathsReturnValue,
+            ParameterCountMismatch, PrivateAccess, ReturnMissingExpression, UnresolvedType,
+            UnresolvedValue,
         },
         ty::infer::ExprOrPatId,
         type_ref::LocalTypeRefId,
-        ExprId, Function, HirDatabase, IntTy, Name, Ty,
+        CmpOp, ExprId, HirDatabase, IntTy, Name, PatId, Ty,
     };
 
     #[derive(Debug, PartialEq, Eq, Clone)]
     pub(crate) enum InferenceDiagnostic {
         UnresolvedValue {
             id: ExprOrPatId,
+            suggestion: Option<Name>,
         },
         UnresolvedType {
             id: LocalTypeRefId,
@@ -1084,6 +1577,17 @@ mod diagnostics {
             expected: Ty,
             found: Ty,
         },
+        MismatchedNumericType {
+            id: ExprId,
+            expected: Ty,
+            found: Ty,
+            is_narrowing: bool,
+        },
+        InvalidCast {
+            id: ExprId,
+            expr_ty: Ty,
+            cast_ty: Ty,
+        },
         IncompatibleBranches {
             id: ExprId,
             then_ty: Ty,
@@ -1102,19 +1606,40 @@ mod diagnostics {
             id: ExprId,
             ty: Ty,
         },
+        IncompatibleComparison {
+            id: ExprId,
+            op: CmpOp,
+            lhs: ExprId,
+            lhs_ty: Ty,
+            rhs: ExprId,
+            rhs_ty: Ty,
+        },
         InvalidLhs {
             id: ExprId,
             lhs: ExprId,
         },
+        AssignToImmutableStatic {
+            lhs: ExprId,
+            binding: PatId,
+        },
         ReturnMissingExpression {
             id: ExprId,
         },
-        BreakOutsideLoop {
+        MissingReturnType {
+            id: ExprId,
+            found: Ty,
+        },
+        NotAllPathsReturnValue {
             id: ExprId,
+            expected: Ty,
         },
         BreakWithValueOutsideLoop {
             id: ExprId,
         },
+        MismatchedBreakValues {
+            id: ExprId,
+            breaks: Vec<(ExprId, Option<Ty>)>,
+        },
         AccessUnknownField {
             id: ExprId,
             receiver_ty: Ty,
@@ -1142,6 +1667,8 @@ mod diagnostics {
         NoSuchField {
             id: ExprId,
             field: usize,
+            name: Name,
+            struct_ty: Ty,
         },
         LiteralOutOfRange {
             id: ExprId,
@@ -1159,13 +1686,13 @@ mod diagnostics {
         pub(crate) fn add_to(
             &self,
             db: &dyn HirDatabase,
-            owner: Function,
+            owner: DefWithBody,
             sink: &mut DiagnosticSink<'_>,
         ) {
-            let file = owner.source(db.upcast()).file_id;
+            let file = owner.file_id(db);
             let body = owner.body_source_map(db);
             match self {
-                InferenceDiagnostic::UnresolvedValue { id } => {
+                InferenceDiagnostic::UnresolvedValue { id, suggestion } => {
                     let expr = match id {
                         ExprOrPatId::ExprId(id) => body.expr_syntax(*id).map(|ptr| {
                             ptr.value
@@ -1177,7 +1704,11 @@ mod diagnostics {
                     }
                     .unwrap();
 
-                    sink.push(UnresolvedValue { file, expr });
+                    sink.push(UnresolvedValue {
+                        file,
+                        expr,
+                        suggestion: suggestion.clone(),
+                    });
                 }
                 InferenceDiagnostic::UnresolvedType { id } => {
                     let type_ref = body.type_ref_syntax(*id).expect("If this is not found, it must be a type ref generated by the library which should never be unresolved.");
@@ -1253,6 +1784,42 @@ mod diagnostics {
                         expected: expected.clone(),
                     });
                 }
+                InferenceDiagnostic::MismatchedNumericType {
+                    id,
+                    expected,
+                    found,
+                    is_narrowing,
+                } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(MismatchedNumericType {
+                        file,
+                        expr,
+                        expected: expected.clone(),
+                        found: found.clone(),
+                        is_narrowing: *is_narrowing,
+                    });
+                }
+                InferenceDiagnostic::InvalidCast {
+                    id,
+                    expr_ty,
+                    cast_ty,
+                } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(InvalidCast {
+                        file,
+                        expr,
+                        expr_ty: expr_ty.clone(),
+                        cast_ty: cast_ty.clone(),
+                    });
+                }
                 InferenceDiagnostic::IncompatibleBranches {
                     id,
                     then_ty,
@@ -1295,6 +1862,30 @@ mod diagnostics {
                         rhs: rhs.clone(),
                     });
                 }
+                InferenceDiagnostic::IncompatibleComparison {
+                    id,
+                    op,
+                    lhs,
+                    lhs_ty,
+                    rhs,
+                    rhs_ty,
+                } => {
+                    let expr_syntax = |id: ExprId| {
+                        body.expr_syntax(id)
+                            .unwrap()
+                            .value
+                            .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())
+                    };
+                    sink.push(IncompatibleComparison {
+                        file,
+                        expr: expr_syntax(*id),
+                        op: *op,
+                        lhs: expr_syntax(*lhs),
+                        lhs_ty: lhs_ty.clone(),
+                        rhs: expr_syntax(*rhs),
+                        rhs_ty: rhs_ty.clone(),
+                    });
+                }
                 InferenceDiagnostic::CannotApplyUnaryOp { id, ty } => {
                     let expr = body
                         .expr_syntax(*id)
@@ -1324,6 +1915,15 @@ mod diagnostics {
                         lhs,
                     });
                 }
+                InferenceDiagnostic::AssignToImmutableStatic { lhs, binding } => {
+                    let lhs = body
+                        .expr_syntax(*lhs)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    let binding = body.pat_syntax(*binding).unwrap().value.syntax_node_ptr();
+                    sink.push(AssignToImmutableStatic { file, lhs, binding });
+                }
                 InferenceDiagnostic::ReturnMissingExpression { id } => {
                     let id = body
                         .expr_syntax(*id)
@@ -1335,15 +1935,28 @@ mod diagnostics {
                         return_expr: id,
                     });
                 }
-                InferenceDiagnostic::BreakOutsideLoop { id } => {
+                InferenceDiagnostic::MissingReturnType { id, found } => {
+                    let expr = body
+                        .expr_syntax(*id)
+                        .unwrap()
+                        .value
+                        .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
+                    sink.push(MissingReturnType {
+                        file,
+                        expr,
+                        found: found.clone(),
+                    });
+                }
+                InferenceDiagnostic::NotAllPathsReturnValue { id, expected } => {
                     let id = body
                         .expr_syntax(*id)
                         .unwrap()
                         .value
                         .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr());
-                    sink.push(BreakOutsideLoop {
+                    sink.push(NotAllPathsReturnValue {
                         file,
-                        break_expr: id,
+                        expr: id,
+                        expected: expected.clone(),
                     });
                 }
                 InferenceDiagnostic::BreakWithValueOutsideLoop { id } => {
@@ -1357,6 +1970,23 @@ mod diagnostics {
                         break_expr: id,
                     });
                 }
+                InferenceDiagnostic::MismatchedBreakValues { id, breaks } => {
+                    let syntax_ptr = |id: ExprId| {
+                        body.expr_syntax(id)
+                            .unwrap()
+                            .value
+                            .either(|it| it.syntax_node_ptr(), |it| it.syntax_node_ptr())
+                    };
+                    let breaks = breaks
+                        .iter()
+                        .map(|(id, ty)| (syntax_ptr(*id), ty.clone()))
+                        .collect();
+                    sink.push(MismatchedBreakValues {
+                        file,
+                        break_expr: syntax_ptr(*id),
+                        breaks,
+                    });
+                }
                 InferenceDiagnostic::AccessUnknownField {
                     id,
                     receiver_ty,
@@ -1438,9 +2068,19 @@ mod diagnostics {
                         found: found.clone(),
                     });
                 }
-                InferenceDiagnostic::NoSuchField { id, field } => {
+                InferenceDiagnostic::NoSuchField {
+                    id,
+                    field,
+                    name,
+                    struct_ty,
+                } => {
                     let field = owner.body_source_map(db).field_syntax(*id, *field).into();
-                    sink.push(NoSuchField { file, field });
+                    sink.push(NoSuchField {
+                        file,
+                        field,
+                        name: name.clone(),
+                        struct_ty: struct_ty.clone(),
+                    });
                 }
                 InferenceDiagnostic::LiteralOutOfRange { id, literal_ty } => {
                     let literal = body
@@ -1456,6 +2096,7 @@ mod diagnostics {
                     sink.push(LiteralOutOfRange {
                         literal,
                         int_ty: *literal_ty,
+                        is_defaulted: false,
                     });
                 }
             }
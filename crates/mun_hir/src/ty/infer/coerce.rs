@@ -1,5 +1,8 @@
 use super::InferenceResultBuilder;
-use crate::{ty::TyKind, Ty};
+use crate::{
+    ty::{CallableDef, TyKind},
+    Ty,
+};
 
 impl<'a> InferenceResultBuilder<'a> {
     /// Unify two types, but may coerce the first one to the second using
@@ -24,6 +27,13 @@ impl<'a> InferenceResultBuilder<'a> {
     fn coerce_inner(&mut self, from_ty: Ty, to_ty: &Ty) -> bool {
         match (from_ty.interned(), to_ty.interned()) {
             (TyKind::Never, ..) => return true,
+            // A function definition can be coerced into a pointer to a function with the same
+            // signature.
+            (TyKind::FnDef(CallableDef::Function(def), _), TyKind::FnPtr(_)) => {
+                let fn_ptr =
+                    TyKind::FnPtr(self.db.callable_sig(CallableDef::Function(*def))).intern();
+                return self.unify(&fn_ptr, to_ty);
+            }
             _ => {
                 if self.type_variables.unify_inner_trivial(&from_ty, to_ty) {
                     return true;
@@ -1,4 +1,7 @@
-use crate::{resolve::ValueNs, ty::infer::InferenceResultBuilder, Expr, ExprId, Path, Resolver};
+use crate::{
+    resolve::ValueNs, ty::infer::InferenceResultBuilder, Expr, ExprId, PatId, Path, Resolver,
+    UnaryOp,
+};
 
 impl<'a> InferenceResultBuilder<'a> {
     /// Checks if the specified expression is a place-expression. A place
@@ -7,16 +10,47 @@ impl<'a> InferenceResultBuilder<'a> {
         match &self.body[expr] {
             Expr::Path(p) => self.check_place_path(resolver, p),
             Expr::Index { base, .. } => self.check_place_expression(resolver, *base),
-            Expr::Field { .. } | Expr::Array(_) => true,
+            Expr::Field { .. }
+            | Expr::Array(_)
+            | Expr::UnaryOp {
+                op: UnaryOp::Deref, ..
+            } => true,
             _ => false,
         }
     }
 
+    /// Returns the pattern that binds the function-local static the specified
+    /// expression refers to, if that static is not declared `mut` and can
+    /// therefore not be assigned to.
+    pub(super) fn immutable_static_binding(
+        &self,
+        resolver: &Resolver,
+        expr: ExprId,
+    ) -> Option<PatId> {
+        let Expr::Path(path) = &self.body[expr] else {
+            return None;
+        };
+        let Some((ValueNs::LocalBinding(binding), _)) =
+            resolver.resolve_path_as_value_fully(self.db.upcast(), path)
+        else {
+            return None;
+        };
+
+        self.immutable_statics.contains(&binding).then_some(binding)
+    }
+
     /// Checks if the specified path references a memory location.
     fn check_place_path(&mut self, resolver: &Resolver, path: &Path) -> bool {
         match resolver.resolve_path_as_value_fully(self.db.upcast(), path) {
             Some((ValueNs::LocalBinding(_), _)) => true,
-            Some((ValueNs::FunctionId(_) | ValueNs::StructId(_), _)) | None => false,
+            Some((
+                ValueNs::FunctionId(_)
+                | ValueNs::StructId(_)
+                | ValueNs::ConstId(_)
+                | ValueNs::BuiltinConstant(_),
+                _,
+            ))
+            | None => false,
         }
     }
 }
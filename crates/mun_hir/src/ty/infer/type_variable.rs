@@ -139,7 +139,14 @@ impl TypeVariableTable {
         if a.equals_ctor(&b) {
             match (a.interned(), b.interned()) {
                 (TyKind::Tuple(_, a), TyKind::Tuple(_, b)) => self.unify_substitutions(db, a, b),
-                (TyKind::Array(t1), TyKind::Array(t2)) => self.unify_inner(db, t1, t2),
+                (TyKind::Array(t1), TyKind::Array(t2))
+                | (TyKind::Slice(t1), TyKind::Slice(t2))
+                | (TyKind::Ref(t1), TyKind::Ref(t2)) => self.unify_inner(db, t1, t2),
+                (TyKind::FnPtr(sig1), TyKind::FnPtr(sig2)) => sig1
+                    .params_and_return()
+                    .iter()
+                    .zip(sig2.params_and_return().iter())
+                    .all(|(t1, t2)| self.unify_inner(db, t1, t2)),
                 _ => true,
             }
         } else {
@@ -0,0 +1,147 @@
+use std::sync::Arc;
+
+use mun_target::abi::{Align, Size, TargetDataLayout};
+
+use super::resolve::ResolveBitness;
+use crate::{FloatBitness, HirDatabase, IntBitness, Struct, StructMemoryKind, Ty, TyKind};
+
+/// The size and alignment of a type in memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    pub size: Size,
+    pub align: Align,
+}
+
+impl Layout {
+    /// Constructs the layout of an aggregate whose fields are placed
+    /// sequentially in declaration order. This matches how LLVM lays out
+    /// non-packed struct types.
+    fn aggregate(data_layout: &TargetDataLayout, fields: impl IntoIterator<Item = Layout>) -> Self {
+        let mut size = Size::ZERO;
+        let mut align = data_layout.aggregate_align.abi;
+        for field in fields {
+            align = align.max(field.align);
+            size = Size::from_bytes(size.align_to(field.align).bytes() + field.size.bytes());
+        }
+
+        Layout {
+            size: size.align_to(align),
+            align,
+        }
+    }
+}
+
+impl Ty {
+    /// Returns the layout of a value of this type for the current target, or
+    /// `None` if the type does not have a representation in memory.
+    pub fn layout(&self, db: &dyn HirDatabase) -> Option<Layout> {
+        LayoutContext::new(db).ty_layout(self)
+    }
+}
+
+impl Struct {
+    /// Returns the layout of the struct's fields for the current target. For
+    /// garbage collected structs this is the layout of the object on the heap.
+    /// Returns `None` if the layout cannot be determined, e.g. because a field
+    /// has an unknown type.
+    pub fn layout(self, db: &dyn HirDatabase) -> Option<Layout> {
+        LayoutContext::new(db).struct_layout(self)
+    }
+}
+
+/// Computes layouts for the current target.
+struct LayoutContext<'a> {
+    db: &'a dyn HirDatabase,
+    data_layout: Arc<TargetDataLayout>,
+    /// The value structs whose layouts are currently being computed, used to
+    /// bail out of recursive value structs.
+    visiting: Vec<Struct>,
+}
+
+impl<'a> LayoutContext<'a> {
+    fn new(db: &'a dyn HirDatabase) -> Self {
+        LayoutContext {
+            db,
+            data_layout: db.target_data_layout(),
+            visiting: Vec::new(),
+        }
+    }
+
+    fn pointer_layout(&self) -> Layout {
+        Layout {
+            size: self.data_layout.pointer_size,
+            align: self.data_layout.pointer_align.abi,
+        }
+    }
+
+    fn ty_layout(&mut self, ty: &Ty) -> Option<Layout> {
+        let dl = self.data_layout.clone();
+        let layout = match ty.interned() {
+            TyKind::Bool => Layout {
+                size: Size::from_bytes(1),
+                align: dl.i1_align.abi,
+            },
+            TyKind::Int(int_ty) => {
+                let (bytes, align) = match int_ty.bitness.resolve(&dl) {
+                    IntBitness::X8 => (1, dl.i8_align),
+                    IntBitness::X16 => (2, dl.i16_align),
+                    IntBitness::X32 => (4, dl.i32_align),
+                    IntBitness::X64 => (8, dl.i64_align),
+                    IntBitness::X128 => (16, dl.i128_align),
+                    IntBitness::Xsize => unreachable!("bitness should have been resolved"),
+                };
+                Layout {
+                    size: Size::from_bytes(bytes),
+                    align: align.abi,
+                }
+            }
+            TyKind::Float(float_ty) => {
+                let (bytes, align) = match float_ty.bitness.resolve(&dl) {
+                    FloatBitness::X32 => (4, dl.f32_align),
+                    FloatBitness::X64 => (8, dl.f64_align),
+                };
+                Layout {
+                    size: Size::from_bytes(bytes),
+                    align: align.abi,
+                }
+            }
+            TyKind::Struct(s) => match s.data(self.db.upcast()).memory_kind {
+                // Garbage collected structs are referenced through a handle
+                StructMemoryKind::Gc => self.pointer_layout(),
+                StructMemoryKind::Value => self.struct_layout(*s)?,
+            },
+            TyKind::Tuple(_, substs) => {
+                let fields = substs
+                    .iter()
+                    .map(|ty| self.ty_layout(ty))
+                    .collect::<Option<Vec<_>>>()?;
+                Layout::aggregate(&dl, fields)
+            }
+            TyKind::Array(_) | TyKind::Ref(_) | TyKind::FnPtr(_) | TyKind::FnDef(..) => {
+                self.pointer_layout()
+            }
+            // A slice consists of a pointer to its elements and the number of elements
+            TyKind::Slice(_) => Layout::aggregate(&dl, [self.pointer_layout(); 2]),
+            TyKind::InferenceVar(_) | TyKind::TypeAlias(_) | TyKind::Never | TyKind::Unknown => {
+                return None
+            }
+        };
+        Some(layout)
+    }
+
+    fn struct_layout(&mut self, s: Struct) -> Option<Layout> {
+        if self.visiting.contains(&s) {
+            return None;
+        }
+
+        self.visiting.push(s);
+        let fields = s
+            .fields(self.db)
+            .into_iter()
+            .map(|field| self.ty_layout(&field.ty(self.db)))
+            .collect::<Option<Vec<_>>>();
+        self.visiting.pop();
+
+        Some(Layout::aggregate(&self.data_layout, fields?))
+    }
+}
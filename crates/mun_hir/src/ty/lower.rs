@@ -104,6 +104,43 @@ impl Ty {
                 );
                 Some(TyKind::Array(inner).intern())
             }
+            TypeRef::Slice(inner) => {
+                let inner = Self::from_hir_with_diagnostics(
+                    db,
+                    resolver,
+                    type_ref_map,
+                    diagnostics,
+                    *inner,
+                );
+                Some(TyKind::Slice(inner).intern())
+            }
+            TypeRef::Ref(inner) => {
+                let inner = Self::from_hir_with_diagnostics(
+                    db,
+                    resolver,
+                    type_ref_map,
+                    diagnostics,
+                    *inner,
+                );
+                Some(TyKind::Ref(inner).intern())
+            }
+            TypeRef::FnPtr { params, ret } => {
+                let params = params
+                    .iter()
+                    .map(|tr| {
+                        Self::from_hir_with_diagnostics(
+                            db,
+                            resolver,
+                            type_ref_map,
+                            diagnostics,
+                            *tr,
+                        )
+                    })
+                    .collect();
+                let ret =
+                    Self::from_hir_with_diagnostics(db, resolver, type_ref_map, diagnostics, *ret);
+                Some(TyKind::FnPtr(FnSig::from_params_and_return(params, ret)).intern())
+            }
         };
         if let Some(ty) = res {
             ty
@@ -201,7 +238,7 @@ impl From<ModuleDef> for Option<TypableDef> {
             ModuleDef::PrimitiveType(t) => Some(TypableDef::PrimitiveType(t)),
             ModuleDef::Struct(t) => Some(TypableDef::Struct(t)),
             ModuleDef::TypeAlias(t) => Some(TypableDef::TypeAlias(t)),
-            ModuleDef::Module(_) => None,
+            ModuleDef::Module(_) | ModuleDef::BuiltinConstant(_) | ModuleDef::Const(_) => None,
         }
     }
 }
@@ -1,8 +1,29 @@
 use crate::{
     ty::{infer::InferTy, TyKind},
-    ArithOp, BinaryOp, Ty,
+    ArithOp, BinaryOp, CmpOp, Ty,
 };
 
+/// Returns true if values of the specified type can be compared with at least
+/// one of the comparison operators.
+pub(super) fn is_comparable(ty: &Ty) -> bool {
+    matches!(
+        ty.interned(),
+        TyKind::Int(_)
+            | TyKind::Float(_)
+            | TyKind::Bool
+            | TyKind::InferenceVar(InferTy::Float(_) | InferTy::Int(_))
+    )
+}
+
+/// Returns true if values of the specified type can be compared with the given
+/// comparison operator. Booleans can only be compared for equality.
+pub(super) fn cmp_op_supported(op: CmpOp, ty: &Ty) -> bool {
+    match ty.interned() {
+        TyKind::Bool => matches!(op, CmpOp::Eq { .. }),
+        _ => is_comparable(ty),
+    }
+}
+
 /// Given a binary operation and the type on the left of that operation, returns
 /// the expected type for the right hand side of the operation or `Ty::Unknown`
 /// if such an operation is invalid.
@@ -11,13 +32,8 @@ pub(super) fn binary_op_rhs_expectation(op: BinaryOp, lhs_ty: Ty) -> Ty {
         BinaryOp::LogicOp(..) => TyKind::Bool.intern(),
 
         // Compare operations are allowed for all scalar types
-        BinaryOp::CmpOp(..) => match lhs_ty.interned() {
-            TyKind::Int(_)
-            | TyKind::Float(_)
-            | TyKind::Bool
-            | TyKind::InferenceVar(InferTy::Float(_) | InferTy::Int(_)) => lhs_ty,
-            _ => TyKind::Unknown.intern(),
-        },
+        BinaryOp::CmpOp(..) if is_comparable(&lhs_ty) => lhs_ty,
+        BinaryOp::CmpOp(..) => TyKind::Unknown.intern(),
 
         BinaryOp::Assignment { op: None } => match lhs_ty.interned() {
             TyKind::Int(_)
@@ -25,6 +41,8 @@ pub(super) fn binary_op_rhs_expectation(op: BinaryOp, lhs_ty: Ty) -> Ty {
             | TyKind::Bool
             | TyKind::Struct(_)
             | TyKind::Array(_)
+            | TyKind::FnPtr(_)
+            | TyKind::Tuple(..)
             | TyKind::InferenceVar(InferTy::Float(_) | InferTy::Int(_)) => lhs_ty,
             _ => TyKind::Unknown.intern(),
         },
@@ -2,7 +2,8 @@ use std::{fmt::Write, sync::Arc};
 
 use crate::{
     diagnostics::DiagnosticSink, expr::BodySourceMap, mock::MockDatabase,
-    with_fixture::WithFixture, HirDisplay, InferenceResult, ModuleDef, Package,
+    utils::tests::diagnostics, with_fixture::WithFixture, HirDisplay, InferenceResult, ModuleDef,
+    Package,
 };
 
 #[test]
@@ -31,6 +32,34 @@ fn issue_354() {
     "###);
 }
 
+#[test]
+fn infer_literal_from_usage() {
+    insta::assert_snapshot!(infer(
+        r"
+    fn foo(a: i8) {}
+
+    fn main() {
+        let x = 1;
+        foo(x);
+        let y = 2;
+        let z = 3.0;
+    }",
+    ), @r###"
+    7..8 'a': i8
+    14..16 '{}': ()
+    28..90 '{     ...3.0; }': ()
+    38..39 'x': i8
+    42..43 '1': i8
+    49..52 'foo': function foo(i8) -> ()
+    49..55 'foo(x)': ()
+    53..54 'x': i8
+    65..66 'y': i32
+    69..70 '2': i32
+    80..81 'z': f64
+    84..87 '3.0': f64
+    "###);
+}
+
 #[test]
 fn array_element_assignment() {
     insta::assert_snapshot!(infer(
@@ -455,6 +484,49 @@ fn comparison_not_implemented_for_struct() {
     "###);
 }
 
+#[test]
+fn comparison_of_incompatible_types() {
+    insta::assert_snapshot!(infer(
+        r"
+    fn foo(a: i32, b: bool, c: f64) {
+        let x = a == b;
+        let y = b < b;
+        let z = b != true;
+        let w = a >= 3;
+        let v = c > a;
+    }
+    "),
+    @r###"
+    46..52: cannot compare values with `==`
+    66..71: cannot compare values with `<`
+    128..133: cannot compare values with `>`
+    7..8 'a': i32
+    15..16 'b': bool
+    24..25 'c': f64
+    32..136 '{     ...> a; }': ()
+    42..43 'x': bool
+    46..47 'a': i32
+    46..52 'a == b': bool
+    51..52 'b': bool
+    62..63 'y': bool
+    66..67 'b': bool
+    66..71 'b < b': bool
+    70..71 'b': bool
+    81..82 'z': bool
+    85..86 'b': bool
+    85..94 'b != true': bool
+    90..94 'true': bool
+    104..105 'w': bool
+    108..109 'a': i32
+    108..114 'a >= 3': bool
+    113..114 '3': i32
+    124..125 'v': bool
+    128..129 'c': f64
+    128..133 'c > a': bool
+    132..133 'a': i32
+    "###);
+}
+
 #[test]
 fn infer_literals() {
     insta::assert_snapshot!(infer(
@@ -529,6 +601,23 @@ fn infer_suffix_literals() {
     }
     "),
     @r###"
+    15..18: this statement has no effect
+    24..29: this statement has no effect
+    35..41: this statement has no effect
+    47..53: this statement has no effect
+    59..65: this statement has no effect
+    71..78: this statement has no effect
+    84..97: this statement has no effect
+    103..108: this statement has no effect
+    114..120: this statement has no effect
+    126..132: this statement has no effect
+    138..144: this statement has no effect
+    150..157: this statement has no effect
+    163..176: this statement has no effect
+    182..196: this statement has no effect
+    202..219: this statement has no effect
+    225..242: this statement has no effect
+    248..295: this statement has no effect
     358..365: invalid literal value
     390..401: binary float literal is not supported
     426..437: octal float literal is not supported
@@ -538,7 +627,17 @@ fn infer_suffix_literals() {
     590..596: literal out of range for `u8`
     635..641: literal out of range for `i8`
     680..698: literal out of range for `u32`
-    734..759: literal out of range for `i32`
+    734..759: literal out of range for `i32`, the type of integer literals that are not otherwise constrained; consider adding a type annotation or suffix
+    358..365: this statement has no effect
+    390..401: this statement has no effect
+    426..437: this statement has no effect
+    462..470: this statement has no effect
+    494..504: this statement has no effect
+    528..571: this statement has no effect
+    590..596: this statement has no effect
+    635..641: this statement has no effect
+    680..698: this statement has no effect
+    734..759: this statement has no effect
     9..298 '{     ...f64; }': ()
     15..18 '123': i32
     24..29 '123u8': u8
@@ -656,6 +755,63 @@ fn infer_return() {
     "###);
 }
 
+#[test]
+fn infer_return_paths() {
+    insta::assert_snapshot!(infer(
+        r#"
+    extern fn panic() -> never;
+    fn foo(a: bool) -> i64 {
+        if a {
+            return 1;
+        }
+    }
+    fn bar(a: bool) -> i64 {
+        if a {
+            return 1;
+        } else {
+            panic();
+        }
+    }
+    fn baz(a: bool) -> i64 {
+        if a {
+            2
+        } else {
+            let b = 3;
+        }
+    }
+    "#),
+    @r###"
+    57..87: not all paths return a value
+    239..265: not all paths return a value
+    35..36 'a': bool
+    51..89 '{     ...   } }': i64
+    57..87 'if a {...     }': {unknown}
+    60..61 'a': bool
+    62..87 '{     ...     }': never
+    72..80 'return 1': never
+    79..80 '1': i64
+    97..98 'a': bool
+    113..181 '{     ...   } }': i64
+    119..179 'if a {...     }': i64
+    122..123 'a': bool
+    124..149 '{     ...     }': never
+    134..142 'return 1': never
+    141..142 '1': i64
+    155..179 '{     ...     }': never
+    165..170 'panic': function panic() -> never
+    165..172 'panic()': never
+    189..190 'a': bool
+    205..267 '{     ...   } }': i64
+    211..265 'if a {...     }': i64
+    214..215 'a': bool
+    216..233 '{     ...     }': i64
+    226..227 '2': i64
+    239..265 '{     ...     }': {unknown}
+    253..254 'b': i32
+    257..258 '3': i32
+    "###);
+}
+
 #[test]
 fn infer_basics() {
     insta::assert_snapshot!(infer(
@@ -668,6 +824,9 @@ fn infer_basics() {
     }
     "#),
     @r###"
+    53..54: this statement has no effect
+    60..61: this statement has no effect
+    67..68: this statement has no effect
     8..9 'a': i32
     15..16 'b': f64
     22..23 'c': never
@@ -695,6 +854,7 @@ fn infer_branching() {
     @r###"
     61..74: missing else branch
     208..234: mismatched branches
+    134..135: this statement has no effect
     10..260 '{     ...ches }': ()
     20..21 'a': i32
     24..48 'if tru... { 4 }': i32
@@ -757,6 +917,50 @@ fn void_return() {
     "###);
 }
 
+#[test]
+fn infer_unit() {
+    insta::assert_snapshot!(infer(
+        r#"
+    struct Foo { a: (), b: i32 }
+    fn bar() {}
+    fn foo(a: ()) -> () {
+        let x = ();
+        x = bar();
+        x = if true { bar() };
+        let f = Foo { a: (), b: 3 };
+        f.a = a;
+        ()
+    }
+    "#),
+    @r###"
+    38..40 '{}': ()
+    48..49 'a': ()
+    61..175 '{     ...  () }': ()
+    71..72 'x': ()
+    75..77 '()': ()
+    83..84 'x': ()
+    83..92 'x = bar()': ()
+    87..90 'bar': function bar() -> ()
+    87..92 'bar()': ()
+    98..99 'x': ()
+    98..119 'x = if...ar() }': ()
+    102..119 'if tru...ar() }': ()
+    105..109 'true': bool
+    110..119 '{ bar() }': ()
+    112..115 'bar': function bar() -> ()
+    112..117 'bar()': ()
+    129..130 'f': Foo
+    133..152 'Foo { ...b: 3 }': Foo
+    142..144 '()': ()
+    149..150 '3': i32
+    158..159 'f': Foo
+    158..161 'f.a': ()
+    158..165 'f.a = a': ()
+    164..165 'a': ()
+    171..173 '()': ()
+    "###);
+}
+
 #[test]
 fn place_expressions() {
     insta::assert_snapshot!(infer(
@@ -902,6 +1106,7 @@ fn infer_loop() {
     }
     "#),
     @r###"
+    15..22: this loop never terminates and has no side effects
     9..24 '{     loop {} }': never
     15..22 'loop {}': never
     20..22 '{}': ()
@@ -914,7 +1119,7 @@ fn infer_break() {
         r#"
     fn foo()->i32 {
         break; // error: not in a loop
-        loop { break 3; break 3.0; } // error: mismatched type
+        loop { break 3; break 3.0; } // error: mismatched break values
         let a:i32 = loop { break 3.0; } // error: mismatched type
         loop { break 3; }
         let a:i32 = loop { break loop { break 3; } }
@@ -923,7 +1128,7 @@ fn infer_break() {
     "#),
     @r###"
     20..25: `break` outside of a loop
-    71..80: mismatched type
+    71..80: `break` values of the same `loop` have different types
     133..142: mismatched type
     267..276: mismatched type
     14..309 '{     ...type }': never
@@ -961,6 +1166,80 @@ fn infer_break() {
     "###);
 }
 
+#[test]
+fn infer_break_values() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    fn foo(a: bool) -> i32 {
+        loop {
+            if a {
+                break 5;
+            }
+            break; // error: `break` with and without a value
+        }
+    }
+
+    fn bar(a: bool) {
+        let x = loop {
+            if a { break 5; }
+            if a { continue; }
+            break true; // error: mismatched `break` values
+        };
+    }
+
+    fn baz(a: bool) {
+        let x = loop {
+            if a { break; }
+            break;
+        };
+    }
+    "#),
+    @r###"
+    90..95: `break` with and without a value in the same `loop`
+    247..257: `break` values of the same `loop` have different types
+    "###);
+}
+
+#[test]
+fn infer_continue() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    fn foo() {
+        continue; // error: not in a loop
+        loop { continue; }
+        while true { continue; }
+    }
+    "#),
+    @r###"
+    15..23: `continue` outside of a loop
+    53..71: this loop never terminates and has no side effects
+    76..100: this loop never terminates and has no side effects
+    "###);
+}
+
+#[test]
+fn infer_labeled_loops() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    fn foo(cond: bool) {
+        'outer: loop {
+            loop { break 'outer; }
+        }
+        'a: while cond { continue 'b; }
+        break 'a;
+    }
+
+    fn bar() {
+        break 3;
+    }
+    "#),
+    @r###"
+    107..109: cannot find loop label `'b`
+    123..125: cannot find loop label `'a`
+    145..150: `break` outside of a loop
+    "###);
+}
+
 #[test]
 fn infer_while() {
     insta::assert_snapshot!(infer(
@@ -1013,6 +1292,41 @@ fn infer_while() {
     "###);
 }
 
+#[test]
+fn infer_for() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn foo(a: [i64]) -> i64 {
+        let sum = 0;
+        for x in a { sum += x; };
+        for y in [1u8, 2] { break 3; };
+        sum
+    }
+    "#),
+    @r###"
+    97..104: `break` with value can only appear in a `loop`
+    7..8 'a': [i64]
+    24..118 '{     ... sum }': i64
+    34..37 'sum': i64
+    40..41 '0': i64
+    47..71 'for x ...= x; }': ()
+    51..52 'x': i64
+    56..57 'a': [i64]
+    58..71 '{ sum += x; }': ()
+    60..63 'sum': i64
+    60..68 'sum += x': ()
+    67..68 'x': i64
+    77..107 'for y ...k 3; }': ()
+    81..82 'y': u8
+    86..94 '[1u8, 2]': [u8]
+    87..90 '1u8': u8
+    92..93 '2': u8
+    95..107 '{ break 3; }': never
+    97..104 'break 3': never
+    113..116 'sum': i64
+    "###);
+}
+
 #[test]
 fn invalid_binary_ops() {
     insta::assert_snapshot!(infer(
@@ -1131,6 +1445,65 @@ fn struct_lit() {
     "###);
 }
 
+#[test]
+fn struct_lit_fields() {
+    insta::assert_snapshot!(infer(
+        r#"
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    fn main() {
+        let a = Point { x: 1 };
+        let b = Point { x: 1, y: 2, z: 3 };
+    }
+    "#),
+    @r###"
+    66..80: missing record fields:
+    - y
+
+    114..118: no such field `z`
+    52..123 '{     ...3 }; }': ()
+    62..63 'a': Point
+    66..80 'Point { x: 1 }': Point
+    77..78 '1': i32
+    90..91 'b': Point
+    94..120 'Point ...z: 3 }': Point
+    105..106 '1': i32
+    111..112 '2': i32
+    117..118 '3': i32
+    "###);
+}
+
+#[test]
+fn struct_lit_field_shorthand() {
+    insta::assert_snapshot!(infer(
+        r#"
+    struct Foo { a: i32, b: bool }
+
+    fn main() {
+        let a = 1;
+        let foo = Foo { a, b: true };
+        let bar = Foo { b, a }; // undefined value
+    }
+    "#),
+    @r###"
+    113..114: undefined value
+    42..141 '{     ...alue }': ()
+    52..53 'a': i32
+    56..57 '1': i32
+    67..70 'foo': Foo
+    73..91 'Foo { ...true }': Foo
+    79..80 'a': i32
+    85..89 'true': bool
+    101..104 'bar': Foo
+    107..119 'Foo { b, a }': Foo
+    113..114 'b': {unknown}
+    116..117 'a': i32
+    "###);
+}
+
 #[test]
 fn struct_field_index() {
     insta::assert_snapshot!(infer(
@@ -1261,6 +1634,203 @@ fn extern_fn() {
     "###);
 }
 
+#[test]
+fn extern_fn_slice() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    extern fn fill(buf: &[i64]);
+    extern fn fill_structs(buf: &[Foo]);
+    extern fn nested(buf: &[&[i64]]);
+    extern fn returns_slice() -> &[i64];
+
+    struct Foo;
+    struct Bar { values: &[i64] }
+    type Values = &[i64];
+
+    fn main() {
+        let a = [1, 2, 3];
+        fill(&a);
+        let b = [1.0, 2.0];
+        fill(&b);
+        let c = &a;
+    }
+
+    fn sum(values: &[i64]) {}
+    "#),
+    @r###"
+    57..63: extern functions can only have primitives as parameter- and return types
+    90..96: slice types can only be used for parameters of extern functions
+    129..135: slice types can only be used for parameters of extern functions
+    171..177: slice types can only be used for parameters of extern functions
+    194..200: slice types can only be used for parameters of extern functions
+    285..287: mismatched type
+    303..304: cannot apply unary operator
+    324..330: slice types can only be used for parameters of extern functions
+    "###);
+}
+
+#[test]
+fn mut_ref_params() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    extern fn get(out: &mut f64);
+    fn div_rem(a: i64, b: i64, rem: &mut i64) -> i64 {
+        *rem = a % b;
+        *rem += 0;
+        a / b
+    }
+
+    struct Foo;
+    struct Bar { value: &mut i64 }
+    type Out = &mut i64;
+
+    fn structs(foo: &mut Foo) {}
+    fn nested(a: &mut &mut i64) {}
+    fn returns(a: &mut i64) -> &mut i64 { a }
+
+    fn main() {
+        let a = 5;
+        *a = 3;
+        let b: &mut i64;
+    }
+    "#),
+    @r###"
+    159..167: mutable references can only be used for function parameters of primitive types
+    181..189: mutable references can only be used for function parameters of primitive types
+    208..216: mutable references can only be used for function parameters of primitive types
+    239..247: mutable references can only be used for function parameters of primitive types
+    234..247: mutable references can only be used for function parameters of primitive types
+    279..287: mutable references can only be used for function parameters of primitive types
+    327..328: cannot apply unary operator
+    345..353: mutable references can only be used for function parameters of primitive types
+    "###);
+}
+
+#[test]
+fn static_stmts() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    fn counter() -> i64 {
+        static mut N: i64 = 0;
+        N += 1;
+        N
+    }
+
+    struct Foo;
+
+    fn main() {
+        static FOO: Foo = Foo;
+        static mut COUNT = counter();
+    }
+    "#),
+    @r###"
+    106..109: statics can only be of primitive types
+    "###);
+}
+
+#[test]
+fn assign_to_immutable_static() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    fn counter() -> i64 {
+        static N: i64 = 0;
+        N += 1;
+        static mut M: i64 = 0;
+        M = N;
+        1 = 2;
+        N
+    }
+    "#),
+    @r###"
+    49..50: cannot assign to immutable static
+    99..100: invalid left hand side of expression
+    "###);
+}
+
+#[test]
+fn infer_fn_ptr() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn double(x: i64) -> i64 { x * 2 }
+    fn negate(x: bool) -> bool { !x }
+    fn apply(f: fn(i64) -> i64, x: i64) -> i64 { f(x) }
+    fn main() {
+        apply(double, 3);
+        apply(negate, 3); // mismatched type
+    }
+    "#),
+    @r###"
+    165..171: mismatched type
+    10..11 'x': i64
+    25..34 '{ x * 2 }': i64
+    27..28 'x': i64
+    27..32 'x * 2': i64
+    31..32 '2': i64
+    45..46 'x': bool
+    62..68 '{ !x }': bool
+    64..66 '!x': bool
+    65..66 'x': bool
+    78..79 'f': fn(i64) -> i64
+    97..98 'x': i64
+    112..120 '{ f(x) }': i64
+    114..115 'f': fn(i64) -> i64
+    114..118 'f(x)': i64
+    116..117 'x': i64
+    131..197 '{     ...type }': ()
+    137..142 'apply': function apply(fn(i64) -> i64, i64) -> i64
+    137..153 'apply(...le, 3)': i64
+    143..149 'double': function double(i64) -> i64
+    151..152 '3': i64
+    159..164 'apply': function apply(fn(i64) -> i64, i64) -> i64
+    159..175 'apply(...te, 3)': i64
+    165..171 'negate': function negate(bool) -> bool
+    173..174 '3': i64
+    "###);
+}
+
+#[test]
+fn infer_fn_address() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn double(x: i64) -> i64 { x * 2 }
+    fn apply(f: fn(i64) -> i64, x: i64) -> i64 { f(x) }
+    fn main() {
+        let f = &double;
+        apply(&double, 3);
+        let a = 3;
+        let b = &a; // cannot apply unary operator
+    }
+    "#),
+    @r###"
+    171..172: cannot apply unary operator
+    10..11 'x': i64
+    25..34 '{ x * 2 }': i64
+    27..28 'x': i64
+    27..32 'x * 2': i64
+    31..32 '2': i64
+    44..45 'f': fn(i64) -> i64
+    63..64 'x': i64
+    78..86 '{ f(x) }': i64
+    80..81 'f': fn(i64) -> i64
+    80..84 'f(x)': i64
+    82..83 'x': i64
+    97..206 '{     ...ator }': ()
+    107..108 'f': fn(i64) -> i64
+    111..118 '&double': fn(i64) -> i64
+    112..118 'double': function double(i64) -> i64
+    124..129 'apply': function apply(fn(i64) -> i64, i64) -> i64
+    124..141 'apply(...le, 3)': i64
+    130..137 '&double': fn(i64) -> i64
+    131..137 'double': function double(i64) -> i64
+    139..140 '3': i64
+    151..152 'a': i32
+    155..156 '3': i32
+    166..167 'b': {unknown}
+    170..172 '&a': {unknown}
+    171..172 'a': i32
+    "###);
+}
+
 #[test]
 fn infer_type_alias() {
     insta::assert_snapshot!(infer(
@@ -1282,6 +1852,37 @@ fn infer_type_alias() {
     "###);
 }
 
+#[test]
+fn infer_builtin_constants() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn main() -> i32 {
+        if TARGET_LITTLE_ENDIAN { 1 } else { 2 }
+    }
+
+    fn foo() -> bool {
+        TARGET_BIG_ENDIAN
+    }
+
+    fn bar() -> u32 {
+        POINTER_WIDTH
+    }
+    "#),
+    @r###"
+    17..65 '{     ... 2 } }': i32
+    23..63 'if TAR... { 2 }': i32
+    26..46 'TARGET...ENDIAN': bool
+    47..52 '{ 1 }': i32
+    49..50 '1': i32
+    58..63 '{ 2 }': i32
+    60..61 '2': i32
+    84..109 '{     ...DIAN }': bool
+    90..107 'TARGET...ENDIAN': bool
+    127..148 '{     ...IDTH }': u32
+    133..146 'POINTER_WIDTH': u32
+    "###);
+}
+
 #[test]
 fn recursive_alias() {
     insta::assert_snapshot!(infer(
@@ -1309,6 +1910,136 @@ fn recursive_alias() {
     "###);
 }
 
+#[test]
+fn infer_missing_return_type() {
+    insta::assert_snapshot!(infer(
+        r#"
+    fn foo() {
+        5
+    }
+
+    fn bar() {
+        if true { 1.0 } else { 2.0 }
+    }
+
+    fn baz() -> i32 {
+        true
+    }
+    "#),
+    @r###"
+    15..16: function without a return type returns a value
+    45..48: function without a return type returns a value
+    58..61: function without a return type returns a value
+    89..93: mismatched type
+    83..95: mismatched type
+    9..18 '{     5 }': i32
+    15..16 '5': i32
+    29..65 '{     ....0 } }': f64
+    35..63 'if tru... 2.0 }': f64
+    38..42 'true': bool
+    43..50 '{ 1.0 }': f64
+    45..48 '1.0': f64
+    56..63 '{ 2.0 }': f64
+    58..61 '2.0': f64
+    83..95 '{     true }': bool
+    89..93 'true': bool
+    "###);
+}
+
+#[test]
+fn numeric_casts() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    fn foo(a: i64, b: f64, c: u8) {
+        let x: i32 = a;
+        let y: i64 = c;
+        let z: f32 = b;
+        bar(a + 1);
+        let w: i32 = a as i32;
+        let v: f64 = c as f64;
+        let u = true as i32;
+    }
+
+    fn bar(v: u8) {}
+    "#),
+    @r###"
+    49..50: mismatched numeric type, the conversion may lose information
+    69..70: mismatched numeric type, numeric types are not converted implicitly
+    89..90: mismatched numeric type, the conversion may lose information
+    100..105: mismatched numeric type, the conversion may lose information
+    174..185: invalid cast, only numeric values can be cast to other numeric types
+    "###);
+}
+
+#[test]
+fn const_values() {
+    let (db, _file_id) = MockDatabase::with_single_file(
+        r#"
+    const A: i64 = 40 + 2;
+    const B: u8 = 255;
+    const C: i8 = -127;
+    const D: f32 = 0.1;
+    const E: bool = A > 41 && !false;
+    const F: u32 = if E { 1 << 31 } else { 0 };
+    const G: u32 = POINTER_WIDTH / 8;
+    const H: u8 = A as u8;
+    const I: u16 = -1 as u16;
+    const J: i8 = 300.5 as i8;
+    const K: f32 = B as f32;
+    "#,
+    );
+
+    let values = Package::all(&db)
+        .iter()
+        .flat_map(|pkg| pkg.modules(&db))
+        .flat_map(|module| module.declarations(&db))
+        .filter_map(|def| match def {
+            ModuleDef::Const(konst) => {
+                Some(format!("{} = {:?}", konst.name(&db), konst.value(&db)))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    insta::assert_snapshot!(values, @r###"
+    A = Ok(Int(42))
+    B = Ok(Int(255))
+    C = Ok(Int(-127))
+    D = Ok(Float(0.10000000149011612))
+    E = Ok(Bool(true))
+    F = Ok(Int(2147483648))
+    G = Ok(Int(8))
+    H = Ok(Int(42))
+    I = Ok(Int(65535))
+    J = Ok(Int(127))
+    K = Ok(Float(255.0))
+    "###);
+}
+
+#[test]
+fn const_eval_errors() {
+    insta::assert_snapshot!(diagnostics(
+        r#"
+    const A: i64 = 1 / 0;
+    const B: i64 = C;
+    const C: i64 = B;
+    struct Foo;
+    const D: Foo = Foo;
+    const E: i8 = 100 + 100;
+    fn foo() -> i64 { 1 }
+    const F: i64 = foo();
+    "#),
+    @r###"
+    15..20: evaluation of constant value failed: attempt to divide by zero
+    37..38: evaluation of constant value failed: the value of the constant depends on itself
+    55..56: evaluation of constant value failed: the value of the constant depends on itself
+    79..82: constants can only be of type `bool` or of an integer or floating-point type
+    104..113: evaluation of constant value failed: attempt to compute a value that overflows its type
+    152..157: evaluation of constant value failed: the expression cannot be evaluated at compile time
+    "###);
+}
+
 fn infer(content: &str) -> String {
     let db = MockDatabase::with_files(content);
 
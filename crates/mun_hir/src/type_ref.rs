@@ -4,7 +4,7 @@
 use std::ops::Index;
 
 use la_arena::{Arena, ArenaMap, Idx};
-use mun_syntax::{ast, AstPtr};
+use mun_syntax::{ast, ast::TypeAscriptionOwner, AstPtr};
 use rustc_hash::FxHashMap;
 
 use crate::Path;
@@ -17,8 +17,14 @@ pub type LocalTypeRefId = Idx<TypeRef>;
 pub enum TypeRef {
     Path(Path),
     Array(LocalTypeRefId),
+    Slice(LocalTypeRefId),
+    Ref(LocalTypeRefId),
     Never,
     Tuple(Vec<LocalTypeRefId>),
+    FnPtr {
+        params: Vec<LocalTypeRefId>,
+        ret: LocalTypeRefId,
+    },
     Error,
 }
 
@@ -60,6 +66,20 @@ impl TypeRefMap {
     pub fn iter(&self) -> impl Iterator<Item = (LocalTypeRefId, &TypeRef)> {
         self.type_refs.iter()
     }
+
+    /// Returns an iterator over all slice types in this instance
+    pub fn slices(&self) -> impl Iterator<Item = LocalTypeRefId> + '_ {
+        self.iter()
+            .filter(|(_, type_ref)| matches!(type_ref, TypeRef::Slice(_)))
+            .map(|(id, _)| id)
+    }
+
+    /// Returns an iterator over all mutable reference types in this instance
+    pub fn refs(&self) -> impl Iterator<Item = LocalTypeRefId> + '_ {
+        self.iter()
+            .filter(|(_, type_ref)| matches!(type_ref, TypeRef::Ref(_)))
+            .map(|(id, _)| id)
+    }
 }
 
 impl Index<LocalTypeRefId> for TypeRefMap {
@@ -102,7 +122,9 @@ impl TypeRefMapBuilder {
     /// Lowers the given AST type references and returns the Id of the resulting
     /// `TypeRef`.
     pub fn alloc_from_node(&mut self, node: &ast::TypeRef) -> LocalTypeRefId {
-        use mun_syntax::ast::TypeRefKind::{ArrayType, NeverType, PathType};
+        use mun_syntax::ast::TypeRefKind::{
+            ArrayType, FnPointerType, NeverType, PathType, RefType, SliceType, TupleType,
+        };
 
         let ptr = AstPtr::new(node);
         let type_ref = match node.kind() {
@@ -111,7 +133,23 @@ impl TypeRefMapBuilder {
                 .and_then(Path::from_ast)
                 .map_or(TypeRef::Error, TypeRef::Path),
             NeverType(_) => TypeRef::Never,
+            TupleType(_) => TypeRef::Tuple(vec![]),
             ArrayType(inner) => TypeRef::Array(self.alloc_from_node_opt(inner.type_ref().as_ref())),
+            SliceType(inner) => TypeRef::Slice(self.alloc_from_node_opt(inner.type_ref().as_ref())),
+            RefType(inner) => TypeRef::Ref(self.alloc_from_node_opt(inner.type_ref().as_ref())),
+            FnPointerType(inner) => {
+                let params = inner
+                    .param_list()
+                    .into_iter()
+                    .flat_map(|param_list| param_list.params())
+                    .map(|param| self.alloc_from_node_opt(param.ascribed_type().as_ref()))
+                    .collect();
+                let ret = match inner.ret_type().and_then(|ret_type| ret_type.type_ref()) {
+                    Some(type_ref) => self.alloc_from_node(&type_ref),
+                    None => self.unit(),
+                };
+                TypeRef::FnPtr { params, ret }
+            }
         };
         self.alloc_type_ref(type_ref, ptr)
     }
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{cmp, sync::Arc};
 
 /// Helper for mutating `Arc<[T]>` (i.e. `Arc::make_mut` for Arc slices).
 /// The underlying values are cloned if there are other strong references.
@@ -9,6 +9,24 @@ pub(crate) fn make_mut_slice<T: Clone>(a: &mut Arc<[T]>) -> &mut [T] {
     Arc::get_mut(a).unwrap()
 }
 
+/// Returns the Levenshtein distance between `a` and `b`: the minimum number of
+/// single-character edits (insertions, deletions or substitutions) required to
+/// change one into the other.
+pub(crate) fn lev_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut distances: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut previous_diagonal = distances[0];
+        distances[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_diagonal + usize::from(a_char != *b_char);
+            previous_diagonal = distances[j + 1];
+            distances[j + 1] = cmp::min(substitution, cmp::min(distances[j], distances[j + 1]) + 1);
+        }
+    }
+    distances[b.len()]
+}
+
 #[cfg(test)]
 pub mod tests {
     use crate::{
@@ -1,6 +1,7 @@
 use lsp_types::{
-    ClientCapabilities, CompletionOptions, OneOf, ServerCapabilities, TextDocumentSyncCapability,
-    TextDocumentSyncKind, TextDocumentSyncOptions, WorkDoneProgressOptions,
+    ClientCapabilities, CodeActionProviderCapability, CompletionOptions, OneOf, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
+    WorkDoneProgressOptions,
 };
 
 /// Returns the capabilities of this LSP server implementation given the
@@ -26,6 +27,7 @@ pub fn server_capabilities(_client_caps: &ClientCapabilities) -> ServerCapabilit
             },
             completion_item: None,
         }),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
         ..Default::default()
     }
 }
@@ -22,6 +22,7 @@ pub enum CompletionKind {
     /// Your usual "complete all valid identifiers".
     Reference,
     BuiltinType,
+    BuiltinConstant,
 }
 
 /// Type of completion used to provide hints to the user.
@@ -46,6 +47,7 @@ impl CompletionItemKind {
     pub(crate) fn tag(&self) -> &'static str {
         match self {
             CompletionItemKind::SymbolKind(kind) => match kind {
+                SymbolKind::Constant => "ct",
                 SymbolKind::Field => "fd",
                 SymbolKind::Function => "fn",
                 SymbolKind::Local => "lc",
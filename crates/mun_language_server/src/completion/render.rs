@@ -57,10 +57,13 @@ impl<'a> Render<'a> {
         local_name: String,
         resolution: &ScopeDef,
     ) -> Option<CompletionItem> {
-        use mun_hir::ModuleDef::{Function, Module, PrimitiveType, Struct, TypeAlias};
+        use mun_hir::ModuleDef::{
+            BuiltinConstant, Const, Function, Module, PrimitiveType, Struct, TypeAlias,
+        };
 
         let completion_kind = match resolution {
             ScopeDef::ModuleDef(PrimitiveType(..)) => CompletionKind::BuiltinType,
+            ScopeDef::ModuleDef(BuiltinConstant(..)) => CompletionKind::BuiltinConstant,
             _ => CompletionKind::Reference,
         };
 
@@ -70,6 +73,9 @@ impl<'a> Render<'a> {
                 return render_fn(self.ctx, Some(local_name), *func)
             }
             ScopeDef::ModuleDef(PrimitiveType(_)) => CompletionItemKind::BuiltinType,
+            ScopeDef::ModuleDef(BuiltinConstant(_)) | ScopeDef::ModuleDef(Const(_)) => {
+                CompletionItemKind::SymbolKind(SymbolKind::Constant)
+            }
             ScopeDef::ModuleDef(Struct(_)) => CompletionItemKind::SymbolKind(SymbolKind::Struct),
             ScopeDef::ModuleDef(TypeAlias(_)) => {
                 CompletionItemKind::SymbolKind(SymbolKind::TypeAlias)
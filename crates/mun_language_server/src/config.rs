@@ -11,6 +11,11 @@ pub struct Config {
 
     /// A collection of projects discovered within the workspace
     pub discovered_projects: Option<Vec<ProjectManifest>>,
+
+    /// If true, diagnostics are only published for files whose diagnostics
+    /// changed since they were last published, instead of for all files on
+    /// every change.
+    pub check_only_changed: bool,
 }
 
 impl Config {
@@ -20,6 +25,7 @@ impl Config {
             watcher: FilesWatcher::Notify,
             root_dir: root_path,
             discovered_projects: None,
+            check_only_changed: false,
         }
     }
 }
@@ -2,7 +2,7 @@
 
 use std::panic;
 
-use mun_hir::{HirDatabase, Upcast};
+use mun_hir::{DefDatabase, Upcast};
 use mun_target::spec::Target;
 use salsa::{Database, Durability, Snapshot};
 
@@ -1,7 +1,7 @@
 use std::cell::RefCell;
 
-use mun_diagnostics::DiagnosticForWith;
-use mun_hir::{AstDatabase, InFile, ModuleId, PackageId, SourceDatabase};
+use mun_diagnostics::{DiagnosticForWith, Suggestion};
+use mun_hir::{diagnostics::Severity, AstDatabase, InFile, ModuleId, PackageId, SourceDatabase};
 use mun_syntax::{Location, TextRange};
 
 use crate::db::AnalysisDatabase;
@@ -17,8 +17,9 @@ pub struct Diagnostic {
     pub message: String,
     pub range: TextRange,
     pub additional_annotations: Vec<SourceAnnotation>,
-    // pub fix: Option<SourceChange>,
-    // pub severity: Severity,
+    pub severity: Severity,
+    /// Machine-applicable edits that fix the diagnostic
+    pub fixes: Vec<Suggestion>,
 }
 
 /// Converts a location to a a range for use in diagnostics
@@ -39,16 +40,24 @@ pub(crate) fn diagnostics(db: &AnalysisDatabase, file_id: mun_hir::FileId) -> Ve
         message: format!("parse error: {err}"),
         range: location_to_range(err.location()),
         additional_annotations: vec![],
+        severity: Severity::Error,
+        fixes: vec![],
     }));
 
     // Add all HIR diagnostics
     let result = RefCell::new(result);
     let mut sink = mun_hir::diagnostics::DiagnosticSink::new(|d| {
+        let severity = d.severity();
         result.borrow_mut().push(d.with_diagnostic(db, |d| {
+            let fixes = d.suggestions();
             Diagnostic {
-                message: format!("{}\n{}", d.title(), d.footer().join("\n"))
-                    .trim()
-                    .to_owned(),
+                message: std::iter::once(d.title())
+                    .chain(d.footer())
+                    .chain(fixes.iter().map(|suggestion| {
+                        format!("help: {}: `{}`", suggestion.message, suggestion.replacement)
+                    }))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
                 range: d.range(),
                 additional_annotations: d
                     .secondary_annotations()
@@ -58,6 +67,8 @@ pub(crate) fn diagnostics(db: &AnalysisDatabase, file_id: mun_hir::FileId) -> Ve
                         range: annotation.range,
                     })
                     .collect(),
+                severity,
+                fixes,
             }
         }));
     });
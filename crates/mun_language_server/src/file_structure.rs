@@ -129,6 +129,7 @@ fn try_convert_to_structure_node(node: &SyntaxNode) -> Option<StructureNode> {
             },
             ast::StructDef(it) => decl(it, SymbolKind::Struct),
             ast::TypeAliasDef(it) => decl_with_type_ref(&it, it.type_ref(), SymbolKind::TypeAlias),
+            ast::ConstDef(it) => decl_with_type_ref(&it, it.ascribed_type(), SymbolKind::Constant),
             _ => None
         }
     }
@@ -86,6 +86,48 @@ pub(crate) fn handle_completion(
     Ok(Some(items.into()))
 }
 
+/// Computes the quick fixes for the diagnostics that overlap the requested
+/// range of a document. Every machine-applicable suggestion of a diagnostic is
+/// returned as a code action that applies it as a text edit.
+pub(crate) fn handle_code_action(
+    snapshot: LanguageServerSnapshot,
+    params: lsp_types::CodeActionParams,
+) -> anyhow::Result<Option<lsp_types::CodeActionResponse>> {
+    let file_id = from_lsp::file_id(&snapshot, &params.text_document.uri)?;
+    let line_index = snapshot.analysis.file_line_index(file_id)?;
+    let range = from_lsp::text_range(&line_index, params.range);
+
+    let mut actions = Vec::new();
+    for diagnostic in snapshot.analysis.diagnostics(file_id)? {
+        if diagnostic.range.intersect(range).is_none() {
+            continue;
+        }
+
+        for fix in diagnostic.fixes {
+            let edit = lsp_types::TextEdit {
+                range: to_lsp::range(fix.range, &line_index),
+                new_text: fix.replacement,
+            };
+            actions.push(lsp_types::CodeActionOrCommand::CodeAction(
+                lsp_types::CodeAction {
+                    title: fix.message,
+                    kind: Some(lsp_types::CodeActionKind::QUICKFIX),
+                    edit: Some(lsp_types::WorkspaceEdit {
+                        changes: Some(
+                            std::iter::once((params.text_document.uri.clone(), vec![edit]))
+                                .collect(),
+                        ),
+                        ..lsp_types::WorkspaceEdit::default()
+                    }),
+                    ..lsp_types::CodeAction::default()
+                },
+            ));
+        }
+    }
+
+    Ok(Some(actions))
+}
+
 /// Constructs a hierarchy of `DocumentSymbols` for a list of symbols that
 /// specify which index is the parent of a symbol. The parent index must always
 /// be smaller than the current index.
@@ -56,8 +56,9 @@ pub fn to_json<T: Serialize>(value: T) -> anyhow::Result<serde_json::Value> {
     serde_json::to_value(value).map_err(|e| anyhow::anyhow!("could not serialize to json: {}", e))
 }
 
-/// Main entry point for the language server
-pub fn run_server() -> anyhow::Result<()> {
+/// Main entry point for the language server. If `check_only_changed` is true,
+/// diagnostics are only published for files whose diagnostics changed.
+pub fn run_server(check_only_changed: bool) -> anyhow::Result<()> {
     log::info!("language server started");
 
     // Setup IO connections
@@ -106,7 +107,10 @@ pub fn run_server() -> anyhow::Result<()> {
                 .expect("could not convert current directory to an absolute path")
         };
 
-        let mut config = Config::new(root_dir);
+        let mut config = Config {
+            check_only_changed,
+            ..Config::new(root_dir)
+        };
 
         // Determine type of watcher to use
         let supports_file_watcher_dynamic_registration = initialize_params
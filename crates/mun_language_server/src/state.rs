@@ -6,6 +6,7 @@ use lsp_types::{
     notification::{Notification, PublishDiagnostics},
     PublishDiagnosticsParams,
 };
+use mun_hir::diagnostics::Severity;
 use mun_paths::AbsPathBuf;
 use mun_vfs::VirtualFileSystem;
 use parking_lot::RwLock;
@@ -15,10 +16,11 @@ use crate::{
     analysis::{Analysis, AnalysisSnapshot},
     change::AnalysisChange,
     config::Config,
-    state::utils::Progress,
-    to_json, to_lsp,
+    state::{diagnostics::DiagnosticCollection, utils::Progress},
+    to_lsp,
 };
 
+mod diagnostics;
 mod protocol;
 mod utils;
 mod workspace;
@@ -30,6 +32,8 @@ mod workspace;
 pub(crate) enum Task {
     Response(Response),
     Notify(lsp_server::Notification),
+    /// The diagnostics of all files of a revision have been computed
+    Diagnostics(Vec<(lsp_types::Url, Vec<lsp_types::Diagnostic>)>),
 }
 
 #[derive(Debug)]
@@ -79,6 +83,9 @@ pub(crate) struct LanguageServerState {
     /// All the packages known to the server
     pub packages: Arc<Vec<mun_project::Package>>,
 
+    /// The diagnostics that have been published to the client
+    pub diagnostics: DiagnosticCollection,
+
     /// True if the client requested that we shut down
     pub shutdown_requested: bool,
 }
@@ -130,6 +137,7 @@ impl LanguageServerState {
             task_receiver,
             analysis,
             packages: Arc::new(Vec::new()),
+            diagnostics: DiagnosticCollection::default(),
             shutdown_requested: false,
         }
     }
@@ -201,10 +209,34 @@ impl LanguageServerState {
                 self.send(notification.into());
             }
             Task::Response(response) => self.respond(response),
+            Task::Diagnostics(diagnostics) => self.publish_diagnostics(diagnostics),
         }
         Ok(())
     }
 
+    /// Publishes the diagnostics of a revision to the client. If the client
+    /// only wants to be informed about changes, only the diagnostics of files
+    /// whose diagnostics changed since the previous revision are published.
+    fn publish_diagnostics(
+        &mut self,
+        diagnostics: Vec<(lsp_types::Url, Vec<lsp_types::Diagnostic>)>,
+    ) {
+        let changed = self.diagnostics.update(diagnostics.clone());
+        let diagnostics = if self.config.check_only_changed {
+            changed
+        } else {
+            diagnostics
+        };
+
+        for (uri, diagnostics) in diagnostics {
+            self.send_notification::<PublishDiagnostics>(PublishDiagnosticsParams {
+                uri,
+                diagnostics,
+                version: None,
+            });
+        }
+    }
+
     /// Handles a change to the underlying virtual file system.
     #[allow(clippy::unnecessary_wraps)]
     fn handle_vfs_task(&mut self, mut task: mun_vfs::MonitorMessage) -> anyhow::Result<()> {
@@ -243,8 +275,10 @@ impl LanguageServerState {
     }
 }
 
-/// Sends all diagnostics of all files
+/// Computes the diagnostics of all files and sends them to the main loop
 fn handle_diagnostics(state: LanguageServerSnapshot, sender: Sender<Task>) -> anyhow::Result<()> {
+    let mut result = Vec::new();
+
     // Iterate over all files
     for (idx, _package) in state.packages.iter().enumerate() {
         let package_id = mun_hir::PackageId(idx as u32);
@@ -263,7 +297,10 @@ fn handle_diagnostics(state: LanguageServerSnapshot, sender: Sender<Task>) -> an
                 for d in diagnostics {
                     lsp_diagnostics.push(lsp_types::Diagnostic {
                         range: to_lsp::range(d.range, &line_index),
-                        severity: Some(lsp_types::DiagnosticSeverity::ERROR),
+                        severity: Some(match d.severity {
+                            Severity::Error => lsp_types::DiagnosticSeverity::ERROR,
+                            Severity::Warning => lsp_types::DiagnosticSeverity::WARNING,
+                        }),
                         code: None,
                         code_description: None,
                         source: Some("mun".to_string()),
@@ -298,19 +335,11 @@ fn handle_diagnostics(state: LanguageServerSnapshot, sender: Sender<Task>) -> an
                 lsp_diagnostics
             };
 
-            sender
-                .send(Task::Notify(lsp_server::Notification {
-                    method: PublishDiagnostics::METHOD.to_owned(),
-                    params: to_json(PublishDiagnosticsParams {
-                        uri,
-                        diagnostics,
-                        version: None,
-                    })
-                    .unwrap(),
-                }))
-                .unwrap();
+            result.push((uri, diagnostics));
         }
     }
+
+    sender.send(Task::Diagnostics(result)).unwrap();
     Ok(())
 }
 
@@ -0,0 +1,102 @@
+use lsp_types::{Diagnostic, Url};
+use rustc_hash::FxHashMap;
+
+/// Keeps track of the diagnostics that have been published to the client.
+/// This enables publishing only the diagnostics of files that changed
+/// between two revisions.
+#[derive(Debug, Default)]
+pub(crate) struct DiagnosticCollection {
+    /// The diagnostics of all files that have at least one diagnostic
+    files: FxHashMap<Url, Vec<Diagnostic>>,
+}
+
+impl DiagnosticCollection {
+    /// Replaces the diagnostics with those of a new revision, which contains
+    /// the diagnostics of all files. Returns the files whose diagnostics
+    /// differ from the previous revision, together with their new
+    /// diagnostics. Files that no longer have any diagnostics are returned
+    /// with an empty set, which clears the diagnostics at the client.
+    pub fn update(
+        &mut self,
+        diagnostics: Vec<(Url, Vec<Diagnostic>)>,
+    ) -> Vec<(Url, Vec<Diagnostic>)> {
+        let mut previous = std::mem::take(&mut self.files);
+        let mut changed = Vec::new();
+        for (uri, diagnostics) in diagnostics {
+            let previous_diagnostics = previous.remove(&uri).unwrap_or_default();
+            if previous_diagnostics != diagnostics {
+                changed.push((uri.clone(), diagnostics.clone()));
+            }
+            if !diagnostics.is_empty() {
+                self.files.insert(uri, diagnostics);
+            }
+        }
+
+        // Files that are no longer part of the revision, e.g. because they were
+        // removed, no longer have any diagnostics.
+        changed.extend(previous.into_keys().map(|uri| (uri, Vec::new())));
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::{Diagnostic, Url};
+
+    use super::DiagnosticCollection;
+
+    fn url(path: &str) -> Url {
+        Url::parse(&format!("file:///{path}")).unwrap()
+    }
+
+    fn diagnostic(message: &str) -> Diagnostic {
+        Diagnostic {
+            message: message.to_owned(),
+            ..Diagnostic::default()
+        }
+    }
+
+    #[test]
+    fn only_changed_files_are_returned() {
+        let mut collection = DiagnosticCollection::default();
+
+        let changed = collection.update(vec![
+            (url("a.mun"), vec![diagnostic("error")]),
+            (url("b.mun"), vec![]),
+        ]);
+        assert_eq!(changed, vec![(url("a.mun"), vec![diagnostic("error")])]);
+
+        // Nothing changed
+        let changed = collection.update(vec![
+            (url("a.mun"), vec![diagnostic("error")]),
+            (url("b.mun"), vec![]),
+        ]);
+        assert!(changed.is_empty());
+
+        let changed = collection.update(vec![
+            (url("a.mun"), vec![diagnostic("error")]),
+            (url("b.mun"), vec![diagnostic("warning")]),
+        ]);
+        assert_eq!(changed, vec![(url("b.mun"), vec![diagnostic("warning")])]);
+    }
+
+    #[test]
+    fn cleared_diagnostics_are_returned() {
+        let mut collection = DiagnosticCollection::default();
+        collection.update(vec![
+            (url("a.mun"), vec![diagnostic("error")]),
+            (url("b.mun"), vec![diagnostic("error")]),
+        ]);
+
+        // The error in `a.mun` was fixed and `b.mun` was removed
+        let mut changed = collection.update(vec![(url("a.mun"), vec![])]);
+        changed.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+        assert_eq!(
+            changed,
+            vec![(url("a.mun"), vec![]), (url("b.mun"), vec![])]
+        );
+
+        let changed = collection.update(vec![(url("a.mun"), vec![])]);
+        assert!(changed.is_empty());
+    }
+}
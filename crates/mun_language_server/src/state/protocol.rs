@@ -96,6 +96,7 @@ impl LanguageServerState {
             })?
             .on::<lsp_types::request::DocumentSymbolRequest>(handlers::handle_document_symbol)?
             .on::<lsp_types::request::Completion>(handlers::handle_completion)?
+            .on::<lsp_types::request::CodeActionRequest>(handlers::handle_code_action)?
             .finish();
 
         Ok(())
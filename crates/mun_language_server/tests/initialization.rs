@@ -50,3 +50,66 @@ fn test_document_symbols() {
 
     insta::assert_debug_snapshot!(symbols);
 }
+
+#[test]
+fn test_code_action_add_mut() {
+    let server = Project::with_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name = "foo"
+    version = "0.0.0"
+
+    //- /src/mod.mun
+    fn counter() -> i64 {
+        static N: i64 = 0;
+        N += 1;
+        1 = 2;
+        N
+    }
+    "#,
+    )
+    .server()
+    .wait_until_workspace_is_loaded();
+
+    let position = |line, character| lsp_types::Position { line, character };
+    let actions = server
+        .send_request::<lsp_types::request::CodeActionRequest>(lsp_types::CodeActionParams {
+            text_document: server.doc_id("src/mod.mun"),
+            range: lsp_types::Range::new(position(2, 4), position(2, 5)),
+            context: lsp_types::CodeActionContext::default(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        })
+        .expect("expected code actions");
+
+    let [lsp_types::CodeActionOrCommand::CodeAction(action)] = actions.as_slice() else {
+        panic!("expected a single code action, found: {actions:?}");
+    };
+    assert_eq!(action.title, "consider making this static mutable");
+    assert_eq!(action.kind, Some(lsp_types::CodeActionKind::QUICKFIX));
+
+    let changes = action.edit.as_ref().and_then(|edit| edit.changes.as_ref());
+    let edits = changes
+        .and_then(|changes| changes.get(&server.doc_id("src/mod.mun").uri))
+        .expect("expected an edit of the document");
+    assert_eq!(
+        edits,
+        &vec![lsp_types::TextEdit {
+            range: lsp_types::Range::new(position(1, 11), position(1, 11)),
+            new_text: "mut ".to_owned(),
+        }]
+    );
+
+    // Assigning to a literal cannot be fixed by adding `mut`
+    let actions = server
+        .send_request::<lsp_types::request::CodeActionRequest>(lsp_types::CodeActionParams {
+            text_document: server.doc_id("src/mod.mun"),
+            range: lsp_types::Range::new(position(3, 4), position(3, 5)),
+            context: lsp_types::CodeActionContext::default(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        })
+        .expect("expected code actions");
+    assert!(actions.is_empty());
+}
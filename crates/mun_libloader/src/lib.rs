@@ -1,4 +1,4 @@
-use std::{ffi::c_void, path::Path};
+use std::{ffi::c_void, path::Path, ptr::NonNull};
 
 use mun_abi as abi;
 pub use temp_library::TempLibrary;
@@ -12,16 +12,32 @@ pub enum InitError {
     FailedToCreateTempLibrary(#[from] temp_library::InitError),
     #[error("Missing symbol for retrieving ABI version: {0}")]
     MissingGetAbiVersionFn(libloading::Error),
-    #[error("Missing symbol for retrieving ABI information: {0}")]
-    MissingGetInfoFn(libloading::Error),
+    #[error("Missing entry symbol `{name}`. {}", entry_symbol_hint(.name))]
+    MissingEntrySymbol {
+        name: String,
+        source: libloading::Error,
+    },
     #[error("Missing symbol for setting allocator handle: {0}")]
     MissingSetAllocatorHandleFn(libloading::Error),
 }
 
-pub struct MunLibrary(TempLibrary);
+/// Returns a hint on how to resolve a missing entry symbol.
+fn entry_symbol_hint(name: &str) -> String {
+    if name == abi::GET_INFO_FN_NAME {
+        String::from("Was the munlib built with a custom entry symbol?")
+    } else {
+        format!("Make sure the munlib was built with the entry symbol `{name}`")
+    }
+}
+
+pub struct MunLibrary {
+    library: TempLibrary,
+    symbols: abi::EntrySymbols,
+}
 
 impl MunLibrary {
-    /// Loads a munlib library from disk.
+    /// Loads a munlib library from disk that exports the default entry
+    /// symbols.
     ///
     /// # Safety
     ///
@@ -38,34 +54,105 @@ impl MunLibrary {
     ///
     /// See [`libloading::Library::new`] for more information.
     pub unsafe fn new(library_path: &Path) -> Result<Self, InitError> {
+        Self::with_entry_symbols(library_path, abi::EntrySymbols::default())
+    }
+
+    /// Loads a munlib library from disk that exports the specified entry
+    /// symbols.
+    ///
+    /// # Safety
+    ///
+    /// See [`MunLibrary::new`].
+    pub unsafe fn with_entry_symbols(
+        library_path: &Path,
+        symbols: abi::EntrySymbols,
+    ) -> Result<Self, InitError> {
         // Although loading a library is technically unsafe, we assume here that this is
         // not the case for munlibs.
         let library = TempLibrary::new(library_path)?;
 
         // Verify that the `*.munlib` contains all required functions. Note that this is
         // an unsafe operation because the loaded symbols don't actually contain
-        // type information. Casting is therefore unsafe.
-        let _get_abi_version_fn: libloading::Symbol<'_, extern "C" fn() -> u32> = library
-            .library()
-            .get(abi::GET_VERSION_FN_NAME.as_bytes())
-            .map_err(InitError::MissingGetAbiVersionFn)?;
-
+        // type information. Casting is therefore unsafe. The entry symbol is verified
+        // first, a munlib that was built with a different entry symbol misses all
+        // other symbols as well.
         let _get_info_fn: libloading::Symbol<'_, extern "C" fn() -> abi::AssemblyInfo<'static>> =
             library
                 .library()
-                .get(abi::GET_INFO_FN_NAME.as_bytes())
-                .map_err(InitError::MissingGetInfoFn)?;
+                .get(symbols.get_info.as_bytes())
+                .map_err(|source| InitError::MissingEntrySymbol {
+                    name: symbols.get_info.clone(),
+                    source,
+                })?;
+
+        let _get_abi_version_fn: libloading::Symbol<'_, extern "C" fn() -> u32> = library
+            .library()
+            .get(symbols.get_version.as_bytes())
+            .map_err(InitError::MissingGetAbiVersionFn)?;
 
         let _set_allocator_handle_fn: libloading::Symbol<'_, extern "C" fn(*mut c_void)> = library
             .library()
-            .get(abi::SET_ALLOCATOR_HANDLE_FN_NAME.as_bytes())
+            .get(symbols.set_allocator_handle.as_bytes())
             .map_err(InitError::MissingSetAllocatorHandleFn)?;
 
-        Ok(MunLibrary(library))
+        Ok(MunLibrary { library, symbols })
     }
 
     pub fn into_inner(self) -> TempLibrary {
-        self.0
+        self.library
+    }
+
+    /// Returns the format header of this mun library, or `None` if the library
+    /// does not export one. Munlibs generated by compilers that predate the
+    /// format header don't contain it.
+    ///
+    /// # Safety
+    ///
+    /// This operations reads a global from the munlib. There is no guarantee
+    /// that the global actually contains a valid header.
+    pub unsafe fn get_format_header(&self) -> Option<abi::FormatHeader> {
+        let format_header: libloading::Symbol<'_, *const abi::FormatHeader> = self
+            .library
+            .library()
+            .get(self.symbols.format_header.as_bytes())
+            .ok()?;
+
+        Some(**format_header)
+    }
+
+    /// Returns information on the toolchain that produced this mun library, or
+    /// `None` if the library does not export it. Like the format header, this
+    /// does not require any code in the library to be executed.
+    ///
+    /// # Safety
+    ///
+    /// This operations reads a global from the munlib. There is no guarantee
+    /// that the global actually contains valid compiler information.
+    pub unsafe fn get_compiler_info(&self) -> Option<abi::CompilerInfo> {
+        let compiler_info: libloading::Symbol<'_, *const abi::CompilerInfo> = self
+            .library
+            .library()
+            .get(self.symbols.compiler_info.as_bytes())
+            .ok()?;
+
+        Some(**compiler_info)
+    }
+
+    /// Returns the call trace of this mun library, or `None` if the library
+    /// was built without a call trace ring.
+    ///
+    /// # Safety
+    ///
+    /// This operations reads a global from the munlib. There is no guarantee
+    /// that the global actually contains a valid call trace.
+    pub unsafe fn get_call_trace(&self) -> Option<NonNull<abi::CallTrace>> {
+        let call_trace: libloading::Symbol<'_, *mut abi::CallTrace> = self
+            .library
+            .library()
+            .get(self.symbols.call_trace.as_bytes())
+            .ok()?;
+
+        NonNull::new(*call_trace)
     }
 
     /// Returns the ABI version of this mun library.
@@ -77,9 +164,9 @@ impl MunLibrary {
     /// behavior.
     pub unsafe fn get_abi_version(&self) -> u32 {
         let get_abi_version_fn: libloading::Symbol<'_, extern "C" fn() -> u32> = self
-            .0
+            .library
             .library()
-            .get(abi::GET_VERSION_FN_NAME.as_bytes())
+            .get(self.symbols.get_version.as_bytes())
             .unwrap();
 
         get_abi_version_fn()
@@ -94,9 +181,9 @@ impl MunLibrary {
     /// behavior.
     pub unsafe fn get_info(&self) -> abi::AssemblyInfo<'static> {
         let get_info_fn: libloading::Symbol<'_, extern "C" fn() -> abi::AssemblyInfo<'static>> =
-            self.0
+            self.library
                 .library()
-                .get(abi::GET_INFO_FN_NAME.as_bytes())
+                .get(self.symbols.get_info.as_bytes())
                 .unwrap();
 
         get_info_fn()
@@ -112,9 +199,9 @@ impl MunLibrary {
     /// behavior.
     pub unsafe fn set_allocator_handle(&mut self, allocator_ptr: *mut c_void) {
         let set_allocator_handle_fn: libloading::Symbol<'_, extern "C" fn(*mut c_void)> = self
-            .0
+            .library
             .library()
-            .get(abi::SET_ALLOCATOR_HANDLE_FN_NAME.as_bytes())
+            .get(self.symbols.set_allocator_handle.as_bytes())
             .unwrap();
 
         set_allocator_handle_fn(allocator_ptr);
@@ -2,12 +2,14 @@ mod array;
 mod mark_sweep;
 mod ptr;
 mod root_ptr;
+mod snapshot;
 
 use std::{marker::PhantomData, ptr::NonNull};
 
 pub use mark_sweep::MarkSweep;
 pub use ptr::{GcPtr, HasIndirectionPtr, RawGcPtr};
 pub use root_ptr::GcRootPtr;
+pub use snapshot::HeapSnapshot;
 
 use crate::r#type::Type;
 
@@ -15,6 +17,45 @@ use crate::r#type::Type;
 #[derive(Debug, Clone, Default)]
 pub struct Stats {
     pub allocated_memory: usize,
+    /// The maximum number of bytes the heap is allowed to grow to, if any.
+    pub max_heap: Option<usize>,
+}
+
+impl Stats {
+    /// Returns true if more memory is allocated than the heap is allowed to
+    /// hold.
+    pub fn exceeds_max_heap(&self) -> bool {
+        self.max_heap
+            .map_or(false, |max_heap| self.allocated_memory > max_heap)
+    }
+}
+
+/// Configures the heap of a GC implementation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcConfig {
+    /// A hint of the number of bytes the heap is expected to hold. It is only
+    /// used to presize the bookkeeping of the collector; no memory for objects
+    /// is reserved up front.
+    pub initial_heap: usize,
+    /// The maximum number of bytes that can be allocated on the heap, or `None`
+    /// if the heap is unbounded. Allocations that would exceed it fail.
+    pub max_heap: Option<usize>,
+}
+
+/// An error that is returned when an allocation would grow the heap beyond its
+/// maximum size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "allocating {size} bytes exceeds the maximum heap size of {max_heap} bytes, \
+     {allocated_memory} bytes are already allocated"
+)]
+pub struct HeapLimitExceeded {
+    /// The size of the allocation in bytes
+    pub size: usize,
+    /// The number of bytes that were allocated at the time
+    pub allocated_memory: usize,
+    /// The maximum number of bytes that can be allocated on the heap
+    pub max_heap: usize,
 }
 
 /// A trait used to trace an object type.
@@ -50,11 +91,23 @@ pub trait Array: Sized {
 pub trait GcRuntime: Send + Sync {
     type Array: Array;
 
-    /// Allocates an object of the given type returning a [`GcPtr`]
-    fn alloc(&self, ty: &Type) -> GcPtr;
+    /// Allocates an object of the given type returning a [`GcPtr`]. Fails if
+    /// the object does not fit within the maximum size of the heap.
+    fn alloc(&self, ty: &Type) -> Result<GcPtr, HeapLimitExceeded>;
+
+    /// Allocates an object of the given type returning a [`GcPtr`], without
+    /// zero-initializing its memory. The caller must initialize the object
+    /// before it is used. Only the fields that the collector traces are
+    /// zeroed, such that a collection never observes dangling references.
+    ///
+    /// By default, this zero-initializes the entire object.
+    fn alloc_uninit(&self, ty: &Type) -> Result<GcPtr, HeapLimitExceeded> {
+        self.alloc(ty)
+    }
 
-    /// Allocates an array of the given type. `ty` must be an array type.
-    fn alloc_array(&self, ty: &Type, n: usize) -> Self::Array;
+    /// Allocates an array of the given type. `ty` must be an array type. Fails
+    /// if the array does not fit within the maximum size of the heap.
+    fn alloc_array(&self, ty: &Type, n: usize) -> Result<Self::Array, HeapLimitExceeded>;
 
     /// Returns the type of the specified `obj`.
     fn ptr_type(&self, obj: GcPtr) -> Type;
@@ -2,8 +2,10 @@ use std::{
     alloc::{Layout, LayoutError},
     borrow::Cow,
     collections::{HashMap, VecDeque},
+    ffi::c_void,
     pin::Pin,
     ptr::NonNull,
+    sync::Arc,
 };
 
 use mapping::{Mapping, StructMapping};
@@ -12,8 +14,10 @@ use parking_lot::RwLock;
 use crate::{
     cast,
     gc::{
-        array::ArrayHeader, Array as GcArray, Event, GcPtr, GcRuntime, Observer, RawGcPtr, Stats,
-        TypeTrace,
+        array::ArrayHeader,
+        snapshot::{self, HeapSnapshot, ObjectSnapshot, Slot},
+        Array as GcArray, Event, GcConfig, GcPtr, GcRootPtr, GcRuntime, HeapLimitExceeded,
+        Observer, RawGcPtr, Stats, TypeTrace,
     },
     mapping::{self, resolve_struct_to_struct_edit, Action, FieldMapping, MemoryMapper},
     r#type::Type,
@@ -32,7 +36,7 @@ impl Trace {
         };
         let obj_ref = unsafe { obj.as_ref() };
         match obj_ref.ty.kind() {
-            TypeKind::Primitive(_) | TypeKind::Pointer(_) => {}
+            TypeKind::Primitive(_) | TypeKind::Pointer(_) | TypeKind::Slice(_) => {}
             TypeKind::Struct(_) => {
                 trace.stack.push_back(CompositeTrace::Struct(StructTrace {
                     struct_ptr: unsafe { obj_ref.data.ptr },
@@ -96,11 +100,12 @@ impl TraceEvent {
     /// specified location.
     pub fn new(ptr: NonNull<u8>, ty: Cow<'_, Type>) -> Option<TraceEvent> {
         match ty.kind() {
-            TypeKind::Primitive(_) | TypeKind::Pointer(_) => None,
+            TypeKind::Primitive(_) | TypeKind::Pointer(_) | TypeKind::Slice(_) => None,
             TypeKind::Struct(s) => {
                 return if s.is_gc_struct() {
-                    let deref_ptr = unsafe { ptr.cast::<NonNull<ObjectInfo>>().as_ref() };
-                    Some(TraceEvent::Reference(*deref_ptr))
+                    // References that were not initialized yet are null
+                    let deref_ptr = unsafe { ptr.cast::<Option<NonNull<ObjectInfo>>>().as_ref() };
+                    deref_ptr.map(TraceEvent::Reference)
                 } else {
                     Some(TraceEvent::InlineStruct(StructTrace {
                         struct_ptr: ptr.cast(),
@@ -192,11 +197,18 @@ where
     O: Observer<Event = Event> + Default,
 {
     fn default() -> Self {
-        MarkSweep {
-            objects: RwLock::new(HashMap::new()),
-            observer: O::default(),
-            stats: RwLock::new(Stats::default()),
-        }
+        Self::with_config(GcConfig::default())
+    }
+}
+
+impl<O> MarkSweep<O>
+where
+    O: Observer<Event = Event> + Default,
+{
+    /// Creates a `MarkSweep` memory collector with the specified heap
+    /// configuration.
+    pub fn with_config(config: GcConfig) -> Self {
+        Self::with_observer_and_config(O::default(), config)
     }
 }
 
@@ -206,11 +218,112 @@ where
 {
     /// Creates a `MarkSweep` memory collector with the specified `Observer`.
     pub fn with_observer(observer: O) -> Self {
+        Self::with_observer_and_config(observer, GcConfig::default())
+    }
+
+    /// Creates a `MarkSweep` memory collector with the specified `Observer`
+    /// and heap configuration.
+    pub fn with_observer_and_config(observer: O, config: GcConfig) -> Self {
+        // Every object occupies at least its `ObjectInfo`, so this is an upper
+        // bound on the number of objects that fit in the initial heap.
+        let capacity = config.initial_heap / std::mem::size_of::<ObjectInfo>();
         Self {
-            objects: RwLock::new(HashMap::new()),
+            objects: RwLock::new(HashMap::with_capacity(capacity)),
             observer,
-            stats: RwLock::new(Stats::default()),
+            stats: RwLock::new(Stats {
+                max_heap: config.max_heap,
+                ..Stats::default()
+            }),
+        }
+    }
+
+    /// Allocates an object of the given type. If `zeroed` is false, only the
+    /// references to other objects are zero-initialized. If `enforce_limit` is
+    /// false, the heap grows beyond its maximum size instead of failing.
+    fn alloc_impl(
+        &self,
+        ty: &Type,
+        zeroed: bool,
+        enforce_limit: bool,
+    ) -> Result<GcPtr, HeapLimitExceeded> {
+        assert!(ty.is_concrete());
+
+        self.reserve(ty.value_layout().size(), enforce_limit)?;
+        let object = alloc_obj(ty.clone(), zeroed);
+
+        // We want to return a pointer to the `ObjectInfo`, to be used as handle.
+        let handle = (&*object.as_ref() as *const _ as RawGcPtr).into();
+
+        {
+            let mut objects = self.objects.write();
+            objects.insert(handle, object);
+        }
+
+        self.observer.event(Event::Allocation(handle));
+        Ok(handle)
+    }
+
+    /// Allocates an array of the given type. If `enforce_limit` is false, the
+    /// heap grows beyond its maximum size instead of failing.
+    fn alloc_array_impl(
+        &self,
+        ty: &Type,
+        n: usize,
+        enforce_limit: bool,
+    ) -> Result<ArrayHandle, HeapLimitExceeded> {
+        let element_ty = ty
+            .as_array()
+            .expect("array type doesnt have an element type")
+            .element_type();
+        self.reserve(array_layout(&element_ty, n).size(), enforce_limit)?;
+        let object = alloc_array(ty.clone(), n);
+
+        // We want to return a pointer to the `ObjectInfo`, to be used as handle.
+        let handle = (&*object.as_ref() as *const _ as RawGcPtr).into();
+
+        {
+            let mut objects = self.objects.write();
+            objects.insert(handle, object);
+        }
+
+        self.observer.event(Event::Allocation(handle));
+        Ok(ArrayHandle {
+            obj: unsafe { NonNull::new_unchecked(handle.into()) },
+        })
+    }
+
+    /// Allocates an object of the given type like [`GcRuntime::alloc`] or,
+    /// if `zeroed` is false, [`GcRuntime::alloc_uninit`], but grows the heap
+    /// beyond its maximum size instead of failing. This is meant for
+    /// allocations that cannot fail, like those of compiled code, whose caller
+    /// reports that the limit was exceeded in another way.
+    pub fn alloc_beyond_limit(&self, ty: &Type, zeroed: bool) -> GcPtr {
+        self.alloc_impl(ty, zeroed, false)
+            .expect("the heap limit is not enforced")
+    }
+
+    /// Allocates an array of the given type like [`GcRuntime::alloc_array`],
+    /// but grows the heap beyond its maximum size instead of failing.
+    pub fn alloc_array_beyond_limit(&self, ty: &Type, n: usize) -> ArrayHandle {
+        self.alloc_array_impl(ty, n, false)
+            .expect("the heap limit is not enforced")
+    }
+
+    /// Accounts for an allocation of `size` bytes, unless `enforce_limit` is
+    /// true and it would grow the heap beyond its maximum size.
+    fn reserve(&self, size: usize, enforce_limit: bool) -> Result<(), HeapLimitExceeded> {
+        let mut stats = self.stats.write();
+        if let Some(max_heap) = stats.max_heap.filter(|_| enforce_limit) {
+            if stats.allocated_memory.saturating_add(size) > max_heap {
+                return Err(HeapLimitExceeded {
+                    size,
+                    allocated_memory: stats.allocated_memory,
+                    max_heap,
+                });
+            }
         }
+        stats.allocated_memory += size;
+        Ok(())
     }
 
     /// Logs an allocation
@@ -229,9 +342,19 @@ where
     }
 }
 
-fn alloc_obj(ty: Type) -> Pin<Box<ObjectInfo>> {
-    let ptr = NonNull::new(unsafe { std::alloc::alloc_zeroed(ty.value_layout()) })
-        .expect("failed to allocate memory for new object");
+/// Allocates the memory of an object of type `ty`. If `zeroed` is false, only
+/// the references to other objects are zero-initialized.
+fn alloc_obj(ty: Type, zeroed: bool) -> Pin<Box<ObjectInfo>> {
+    let ptr = if zeroed {
+        unsafe { std::alloc::alloc_zeroed(ty.value_layout()) }
+    } else {
+        unsafe { std::alloc::alloc(ty.value_layout()) }
+    };
+    let ptr = NonNull::new(ptr).expect("failed to allocate memory for new object");
+    if !zeroed {
+        // Safety: the memory was just allocated with the layout of `ty`
+        unsafe { zero_references(&ty, ptr.as_ptr()) };
+    }
     Box::pin(ObjectInfo {
         data: ObjectInfoData { ptr },
         ty,
@@ -240,6 +363,28 @@ fn alloc_obj(ty: Type) -> Pin<Box<ObjectInfo>> {
     })
 }
 
+/// Zero-initializes all references to other objects, stored in the value of
+/// type `ty` at `ptr`, including those in fields of value structs.
+///
+/// # Safety
+///
+/// `ptr` must point to writable memory with the layout of a value of type `ty`.
+unsafe fn zero_references(ty: &Type, ptr: *mut u8) {
+    let Some(s) = ty.as_struct() else {
+        return;
+    };
+
+    for field in s.fields().iter() {
+        let field_ty = field.ty();
+        let field_ptr = ptr.add(field.offset());
+        if field_ty.is_reference_type() {
+            std::ptr::write_bytes(field_ptr, 0, field_ty.reference_layout().size());
+        } else if field_ty.is_struct() {
+            zero_references(&field_ty, field_ptr);
+        }
+    }
+}
+
 /// An error that might occur when requesting memory layout of a type
 #[derive(Debug)]
 pub enum MemoryLayoutError {
@@ -468,40 +613,16 @@ where
 {
     type Array = ArrayHandle;
 
-    fn alloc(&self, ty: &Type) -> GcPtr {
-        assert!(ty.is_concrete());
-
-        let object = alloc_obj(ty.clone());
-        let size = object.layout().size();
-
-        // We want to return a pointer to the `ObjectInfo`, to be used as handle.
-        let handle = (&*object.as_ref() as *const _ as RawGcPtr).into();
-
-        {
-            let mut objects = self.objects.write();
-            objects.insert(handle, object);
-        }
-
-        self.log_alloc(handle, size);
-        handle
+    fn alloc(&self, ty: &Type) -> Result<GcPtr, HeapLimitExceeded> {
+        self.alloc_impl(ty, true, true)
     }
 
-    fn alloc_array(&self, ty: &Type, n: usize) -> Self::Array {
-        let object = alloc_array(ty.clone(), n);
-        let size = object.layout().size();
-
-        // We want to return a pointer to the `ObjectInfo`, to be used as handle.
-        let handle = (&*object.as_ref() as *const _ as RawGcPtr).into();
-
-        {
-            let mut objects = self.objects.write();
-            objects.insert(handle, object);
-        }
+    fn alloc_uninit(&self, ty: &Type) -> Result<GcPtr, HeapLimitExceeded> {
+        self.alloc_impl(ty, false, true)
+    }
 
-        self.log_alloc(handle, size);
-        ArrayHandle {
-            obj: unsafe { NonNull::new_unchecked(handle.into()) },
-        }
+    fn alloc_array(&self, ty: &Type, n: usize) -> Result<Self::Array, HeapLimitExceeded> {
+        self.alloc_array_impl(ty, n, true)
     }
 
     fn ptr_type(&self, handle: GcPtr) -> Type {
@@ -622,6 +743,251 @@ where
     }
 }
 
+impl<O> MarkSweep<O>
+where
+    O: Observer<Event = Event>,
+{
+    /// Takes a snapshot of all objects that are reachable from rooted objects.
+    pub fn snapshot(&self) -> HeapSnapshot {
+        let objects = self.objects.read();
+
+        let mut handles: Vec<GcPtr> = objects
+            .iter()
+            .filter_map(|(handle, obj)| if obj.roots > 0 { Some(*handle) } else { None })
+            .collect();
+        let mut indices: HashMap<GcPtr, usize> = handles
+            .iter()
+            .enumerate()
+            .map(|(index, handle)| (*handle, index))
+            .collect();
+
+        // Visit all reachable objects, assigning an index to every newly found reference
+        let mut snapshots = Vec::with_capacity(handles.len());
+        while let Some(&handle) = handles.get(snapshots.len()) {
+            let obj = objects.get(&handle).expect("found invalid reference");
+            let obj_ptr = obj.as_ref().get_ref() as *const _ as *mut ObjectInfo;
+
+            let mut slots = Vec::new();
+            let (data_ptr, size, length) = match obj.ty.kind() {
+                TypeKind::Array(_) => {
+                    let array = ArrayHandle {
+                        obj: unsafe { NonNull::new_unchecked(obj_ptr) },
+                    };
+                    let length = array.length();
+                    let stride = array.element_stride();
+                    let element_ty = array.element_type();
+                    for index in 0..length {
+                        snapshot::value_slots(&element_ty, index * stride, &mut slots);
+                    }
+                    (array.data(), length * stride, Some(length))
+                }
+                _ => {
+                    snapshot::object_slots(&obj.ty, &mut slots);
+                    (unsafe { obj.data.ptr }, obj.ty.value_layout().size(), None)
+                }
+            };
+
+            let mut data = unsafe { std::slice::from_raw_parts(data_ptr.as_ptr(), size) }.to_vec();
+            let mut references = Vec::new();
+            let mut pointers = Vec::new();
+            for slot in slots {
+                match slot {
+                    Slot::Reference(offset) => {
+                        let reference =
+                            unsafe { data_ptr.as_ptr().add(offset).cast::<GcPtr>().read() };
+                        if reference.as_ptr().is_null() {
+                            continue;
+                        }
+
+                        let index = *indices.entry(reference).or_insert_with(|| {
+                            handles.push(reference);
+                            handles.len() - 1
+                        });
+                        references.push((offset, index));
+                    }
+                    Slot::Pointer(offset) => {
+                        data[offset..offset + std::mem::size_of::<usize>()].fill(0);
+                        pointers.push(offset);
+                    }
+                }
+            }
+
+            snapshots.push(ObjectSnapshot {
+                handle,
+                ty: obj.ty.clone(),
+                is_root: obj.roots > 0,
+                length,
+                data,
+                references,
+                pointers,
+            });
+        }
+
+        HeapSnapshot { objects: snapshots }
+    }
+
+    /// Restores all objects stored in `snapshot`.
+    ///
+    /// Objects are identified by their handle. An object that is still alive
+    /// and has the same type is restored in place, which keeps existing
+    /// handles to it valid. Otherwise, a new object is allocated. Objects that
+    /// were allocated after the snapshot was taken are left untouched; they
+    /// are collected once they are no longer reachable.
+    ///
+    /// Objects that had to be reallocated get a new handle, so the handles in
+    /// the snapshot no longer refer to them. Returns the objects that were
+    /// rooted when the snapshot was taken, keyed by their handle in the
+    /// snapshot. They are rooted again, such that they are not collected
+    /// before the caller takes ownership of them.
+    ///
+    /// The caller must ensure that the types in the snapshot are still valid,
+    /// e.g. that their layout wasn't changed by a hot reload.
+    pub fn restore(self: &Arc<Self>, snapshot: &HeapSnapshot) -> HashMap<GcPtr, GcRootPtr<Self>> {
+        let handles = self.restore_objects(snapshot);
+        snapshot
+            .objects
+            .iter()
+            .zip(handles)
+            .filter(|(object, _)| object.is_root)
+            .map(|(object, handle)| (object.handle, GcRootPtr::new(self, handle)))
+            .collect()
+    }
+
+    /// Restores all objects stored in `snapshot` and returns the handles of the
+    /// restored objects, in the order of the snapshot.
+    fn restore_objects(&self, snapshot: &HeapSnapshot) -> Vec<GcPtr> {
+        let mut objects = self.objects.write();
+
+        // Determine which objects can be restored in place before allocating any new
+        // objects, a new allocation might otherwise reuse the handle of another
+        // object in the snapshot.
+        let in_place: Vec<bool> = snapshot
+            .objects
+            .iter()
+            .map(|object| {
+                objects
+                    .get(&object.handle)
+                    .map_or(false, |obj| obj.ty == object.ty)
+            })
+            .collect();
+
+        let handles: Vec<GcPtr> = snapshot
+            .objects
+            .iter()
+            .zip(in_place)
+            .map(|(object, in_place)| {
+                if in_place {
+                    return object.handle;
+                }
+
+                let obj = match object.length {
+                    Some(length) => alloc_array(object.ty.clone(), length),
+                    None => alloc_obj(object.ty.clone(), true),
+                };
+                let size = obj.layout().size();
+
+                // We want to return a pointer to the `ObjectInfo`, to be used as handle.
+                let handle = (&*obj.as_ref() as *const _ as RawGcPtr).into();
+                objects.insert(handle, obj);
+
+                self.log_alloc(handle, size);
+                handle
+            })
+            .collect();
+
+        for (object, handle) in snapshot.objects.iter().zip(handles.iter()) {
+            let obj = objects.get_mut(handle).expect("found invalid reference");
+            let obj = unsafe { obj.as_mut().get_unchecked_mut() };
+
+            let data_ptr = match object.length {
+                Some(length) => {
+                    // Grow the array if the elements don't fit
+                    if unsafe { obj.data.array.as_ref().capacity } < length {
+                        let old_layout = obj.layout();
+                        unsafe {
+                            std::alloc::dealloc(obj.data.ptr.as_ptr(), old_layout);
+                            obj.data.array = array_header(&object.ty, length);
+                        }
+
+                        let mut stats = self.stats.write();
+                        stats.allocated_memory =
+                            stats.allocated_memory + obj.layout().size() - old_layout.size();
+                    }
+
+                    let mut array = ArrayHandle {
+                        obj: NonNull::from(&mut *obj),
+                    };
+                    unsafe { array.set_length(length) };
+                    array.data()
+                }
+                None => unsafe { obj.data.ptr },
+            };
+
+            unsafe {
+                let ptr = data_ptr.as_ptr();
+
+                // Raw pointers are not part of the snapshot, so preserve their current value
+                let pointers: Vec<usize> = object
+                    .pointers
+                    .iter()
+                    .map(|&offset| ptr.add(offset).cast::<usize>().read())
+                    .collect();
+
+                std::ptr::copy_nonoverlapping(object.data.as_ptr(), ptr, object.data.len());
+
+                for (&offset, value) in object.pointers.iter().zip(pointers) {
+                    ptr.add(offset).cast::<usize>().write(value);
+                }
+                for &(offset, index) in object.references.iter() {
+                    ptr.add(offset).cast::<GcPtr>().write(handles[index]);
+                }
+            }
+        }
+
+        handles
+    }
+
+    /// Replaces the raw pointers stored in objects for which `remap` returns a
+    /// new value. This allows pointers into code that is about to be unloaded,
+    /// like function pointers, to be updated when the code is hot reloaded.
+    pub fn remap_pointers(&self, remap: impl Fn(*const c_void) -> Option<*const c_void>) {
+        let objects = self.objects.write();
+        for obj in objects.values() {
+            let obj_ptr = obj.as_ref().get_ref() as *const _ as *mut ObjectInfo;
+
+            let mut slots = Vec::new();
+            let data_ptr = match obj.ty.kind() {
+                TypeKind::Array(_) => {
+                    let array = ArrayHandle {
+                        obj: unsafe { NonNull::new_unchecked(obj_ptr) },
+                    };
+                    let stride = array.element_stride();
+                    let element_ty = array.element_type();
+                    for index in 0..array.length() {
+                        snapshot::value_slots(&element_ty, index * stride, &mut slots);
+                    }
+                    array.data()
+                }
+                _ => {
+                    snapshot::object_slots(&obj.ty, &mut slots);
+                    unsafe { obj.data.ptr }
+                }
+            };
+
+            for slot in slots {
+                if let Slot::Pointer(offset) = slot {
+                    unsafe {
+                        let pointer = data_ptr.as_ptr().add(offset).cast::<*const c_void>();
+                        if let Some(remapped) = remap(*pointer) {
+                            *pointer = remapped;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 impl<O> MemoryMapper for MarkSweep<O>
 where
     O: Observer<Event = Event>,
@@ -806,7 +1172,7 @@ where
                     }
                 }
                 mapping::Action::StructAlloc => {
-                    let object = alloc_obj(new_ty.clone());
+                    let object = alloc_obj(new_ty.clone(), true);
 
                     // We want to return a pointer to the `ObjectInfo`, to be used as handle.
                     let handle = (&*object.as_ref() as *const _ as RawGcPtr).into();
@@ -842,7 +1208,7 @@ where
                     );
                 }
                 mapping::Action::StructMapFromValue { old_ty, old_offset } => {
-                    let object = alloc_obj(new_ty.clone());
+                    let object = alloc_obj(new_ty.clone(), true);
 
                     let conversion = conversions.get(old_ty).unwrap_or_else(|| {
                         panic!(
@@ -1115,18 +1481,25 @@ impl ObjectInfo {
     /// Returns the layout of the data pointed to by data
     pub fn layout(&self) -> Layout {
         match self.ty.kind() {
-            TypeKind::Struct(_) | TypeKind::Primitive(_) | TypeKind::Pointer(_) => {
-                self.ty.value_layout()
-            }
+            TypeKind::Struct(_)
+            | TypeKind::Primitive(_)
+            | TypeKind::Pointer(_)
+            | TypeKind::Slice(_) => self.ty.value_layout(),
             TypeKind::Array(array) => {
                 let elem_count = unsafe { self.data.array.as_ref().capacity };
-                let elem_layout = repeat_layout(array.element_type().value_layout(), elem_count)
-                    .expect("unable to determine layout of array elements");
-                let (layout, _) = Layout::new::<ArrayHeader>()
-                    .extend(elem_layout)
-                    .expect("unable to determine layout of array");
-                layout
+                array_layout(&array.element_type(), elem_count)
             }
         }
     }
 }
+
+/// Returns the layout of the data of an array with room for `capacity`
+/// elements of type `element_ty`.
+fn array_layout(element_ty: &Type, capacity: usize) -> Layout {
+    let elem_layout = repeat_layout(element_ty.value_layout(), capacity)
+        .expect("unable to determine layout of array elements");
+    let (layout, _) = Layout::new::<ArrayHeader>()
+        .extend(elem_layout)
+        .expect("unable to determine layout of array");
+    layout
+}
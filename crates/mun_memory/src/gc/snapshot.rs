@@ -0,0 +1,109 @@
+use crate::{
+    gc::GcPtr,
+    r#type::{Type, TypeKind},
+};
+
+/// A copy of all objects that were reachable from rooted objects at the time
+/// the snapshot was taken.
+///
+/// References between objects are stored as indices into the snapshot instead
+/// of raw pointers, which allows objects to be relocated when the snapshot is
+/// restored. Cyclic object graphs are stored as-is.
+///
+/// Raw pointers (e.g. handles to extern data) are opaque to the garbage
+/// collector and are therefore excluded from a snapshot. When an object is
+/// restored in place its raw pointers keep their current value, when an
+/// object is reallocated its raw pointers are zeroed.
+pub struct HeapSnapshot {
+    pub(super) objects: Vec<ObjectSnapshot>,
+}
+
+/// A copy of a single object in a [`HeapSnapshot`].
+pub(super) struct ObjectSnapshot {
+    /// The handle of the object at the time the snapshot was taken
+    pub handle: GcPtr,
+
+    /// The type of the object
+    pub ty: Type,
+
+    /// Whether the object was rooted at the time the snapshot was taken
+    pub is_root: bool,
+
+    /// The number of elements if the object is an array
+    pub length: Option<usize>,
+
+    /// The raw bytes of the object. For arrays, these are the bytes of the
+    /// elements. Raw pointers are zeroed.
+    pub data: Vec<u8>,
+
+    /// The offsets of all references to other objects in `data` together with
+    /// the index of the referenced object
+    pub references: Vec<(usize, usize)>,
+
+    /// The offsets of all raw pointers in `data`
+    pub pointers: Vec<usize>,
+}
+
+impl HeapSnapshot {
+    /// Returns the number of objects in the snapshot
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Returns true if the snapshot doesn't contain any objects
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Returns the handles that were rooted at the time the snapshot was taken
+    pub fn roots(&self) -> impl Iterator<Item = GcPtr> + '_ {
+        self.objects
+            .iter()
+            .filter(|object| object.is_root)
+            .map(|object| object.handle)
+    }
+
+    /// Returns the types of all objects in the snapshot
+    pub fn types(&self) -> impl Iterator<Item = &Type> {
+        self.objects.iter().map(|object| &object.ty)
+    }
+}
+
+/// A location in the memory of an object that can't be copied verbatim.
+pub(super) enum Slot {
+    /// A reference to another garbage collected object
+    Reference(usize),
+
+    /// A raw pointer
+    Pointer(usize),
+}
+
+/// Collects all slots of a value of type `ty` that is stored inline at
+/// `offset`.
+pub(super) fn value_slots(ty: &Type, offset: usize, slots: &mut Vec<Slot>) {
+    match ty.kind() {
+        TypeKind::Primitive(_) => {}
+        // A slice starts with a raw pointer to its elements
+        TypeKind::Pointer(_) | TypeKind::Slice(_) => slots.push(Slot::Pointer(offset)),
+        TypeKind::Array(_) => slots.push(Slot::Reference(offset)),
+        TypeKind::Struct(s) if s.is_gc_struct() => slots.push(Slot::Reference(offset)),
+        TypeKind::Struct(s) => {
+            for field in s.fields().iter() {
+                value_slots(&field.ty(), offset + field.offset(), slots);
+            }
+        }
+    }
+}
+
+/// Collects all slots of a heap allocated object of type `ty`. Arrays are
+/// handled by calling [`value_slots`] for every element.
+pub(super) fn object_slots(ty: &Type, slots: &mut Vec<Slot>) {
+    match ty.kind() {
+        TypeKind::Struct(s) => {
+            for field in s.fields().iter() {
+                value_slots(&field.ty(), field.offset(), slots);
+            }
+        }
+        _ => value_slots(ty, 0, slots),
+    }
+}
@@ -1,6 +1,6 @@
 pub use r#type::{
-    ArrayType, Field, FieldData, HasStaticType, PointerType, StructType, StructTypeBuilder, Type,
-    TypeCollectionStats, TypeKind,
+    ArrayType, Field, FieldData, HasStaticType, PointerType, SliceType, StructType,
+    StructTypeBuilder, Type, TypeCollectionStats, TypeKind,
 };
 
 pub mod ffi {
@@ -29,4 +29,6 @@ pub mod prelude {
 pub enum TryFromAbiError<'a> {
     #[error("unknown TypeId '{0}'")]
     UnknownTypeId(abi::TypeId<'a>),
+    #[error("type '{0}' has conflicting definitions")]
+    ConflictingDefinitions(String),
 }
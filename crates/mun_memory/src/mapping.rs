@@ -321,7 +321,7 @@ pub fn resolve_edit(old_ty: &Type, new_ty: &Type, old_offset: usize) -> Action {
             resolve_primitive_edit(old_ty, new_ty, old_guid, old_offset)
         }
         TypeKind::Struct(_) => resolve_struct_edit(old_ty, new_ty, old_offset),
-        TypeKind::Pointer(_) => resolve_pointer_edit(old_ty, new_ty),
+        TypeKind::Pointer(_) | TypeKind::Slice(_) => resolve_pointer_edit(old_ty, new_ty),
         TypeKind::Array(old_array) => resolve_array_edit(old_array, new_ty, old_offset),
     }
 }
@@ -343,7 +343,7 @@ fn resolve_primitive_edit(
                 Action::StructAlloc
             }
         }
-        TypeKind::Pointer(_) => unreachable!(),
+        TypeKind::Pointer(_) | TypeKind::Slice(_) => unreachable!(),
         TypeKind::Array(new_array) => {
             resolve_primitive_to_array_edit(old_ty, new_array, old_offset)
         }
@@ -384,7 +384,7 @@ fn resolve_struct_edit(old_ty: &Type, new_ty: &Type, old_offset: usize) -> Actio
     match &new_ty.kind() {
         TypeKind::Primitive(_) => Action::ZeroInitialize,
         TypeKind::Struct(_) => resolve_struct_to_struct_edit(old_ty, new_ty, old_offset),
-        TypeKind::Pointer(_) => unreachable!(),
+        TypeKind::Pointer(_) | TypeKind::Slice(_) => unreachable!(),
         TypeKind::Array(new_array) => resolve_struct_to_array_edit(old_ty, new_array, old_offset),
     }
 }
@@ -465,7 +465,7 @@ fn resolve_array_edit(old_array: &ArrayType<'_>, new_ty: &Type, old_offset: usiz
     match &new_ty.kind() {
         TypeKind::Primitive(_) => resolve_array_to_primitive_edit(old_array, new_ty, old_offset),
         TypeKind::Struct(_) => resolve_array_to_struct_edit(old_array, new_ty, old_offset),
-        TypeKind::Pointer(_) => unreachable!(),
+        TypeKind::Pointer(_) | TypeKind::Slice(_) => unreachable!(),
         TypeKind::Array(new_array) => resolve_array_to_array_edit(old_array, new_array, old_offset),
     }
 }
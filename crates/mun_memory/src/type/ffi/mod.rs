@@ -16,13 +16,17 @@ use mun_abi::Guid;
 use mun_capi_utils::{mun_error_try, try_deref_mut, ErrorHandle};
 pub use r#array::ArrayInfo;
 pub use r#pointer::PointerInfo;
+pub use r#slice::SliceInfo;
 pub use r#struct::{Field, Fields, StructInfo};
 
-use crate::r#type::{ArrayData, PointerData, StructData, TypeData, TypeDataKind, TypeDataStore};
+use crate::r#type::{
+    ArrayData, PointerData, SliceData, StructData, TypeData, TypeDataKind, TypeDataStore,
+};
 
 mod array;
 mod pointer;
 mod primitive;
+mod r#slice;
 mod r#struct;
 
 /// A [`Type`] holds information about a mun type.
@@ -234,6 +238,25 @@ pub unsafe extern "C" fn mun_type_array_type(ty: Type, array_ty: *mut Type) -> E
     ErrorHandle::default()
 }
 
+/// Returns a new [`Type`] that is a slice of the specified type.
+///
+/// # Safety
+///
+/// This function results in undefined behavior if the passed in `Type`s have
+/// been deallocated in a previous call to [`mun_type_release`].
+#[no_mangle]
+pub unsafe extern "C" fn mun_type_slice_type(ty: Type, slice_ty: *mut Type) -> ErrorHandle {
+    let slice_ty = try_deref_mut!(slice_ty);
+    let store = mun_error_try!(ty
+        .store()
+        .map_err(|e| format!("invalid argument 'ty': {e}")));
+    let inner = mun_error_try!(ty
+        .inner()
+        .map_err(|e| format!("invalid argument 'ty': {e}")));
+    *slice_ty = inner.slice_type(&store).into();
+    ErrorHandle::default()
+}
+
 /// An enum that defines the kind of type.
 #[repr(u8)]
 pub enum TypeKind {
@@ -241,6 +264,7 @@ pub enum TypeKind {
     Pointer(r#pointer::PointerInfo),
     Struct(r#struct::StructInfo),
     Array(r#array::ArrayInfo),
+    Slice(r#slice::SliceInfo),
 }
 
 /// Returns information about what kind of type this is.
@@ -273,6 +297,10 @@ pub unsafe extern "C" fn mun_type_kind(ty: Type, kind: *mut TypeKind) -> ErrorHa
             (a as *const ArrayData).cast(),
             Arc::as_ptr(ManuallyDrop::deref(&store)).cast(),
         )),
+        TypeDataKind::Slice(s) => TypeKind::Slice(SliceInfo(
+            (s as *const SliceData).cast(),
+            Arc::as_ptr(ManuallyDrop::deref(&store)).cast(),
+        )),
         TypeDataKind::Uninitialized => unreachable!(),
     };
 
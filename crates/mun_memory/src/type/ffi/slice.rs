@@ -0,0 +1,136 @@
+use std::{ffi::c_void, mem::ManuallyDrop, ops::Deref, sync::Arc};
+
+use mun_capi_utils::{mun_error_try, try_deref_mut, ErrorHandle};
+
+use crate::{
+    ffi::Type,
+    r#type::{SliceData, Type as RustType, TypeDataStore},
+};
+
+/// Additional information of a slice [`Type`].
+///
+/// Ownership of this type lies with the [`Type`] that created this instance. As
+/// long as the original type is not released through [`mun_type_release`] this
+/// type stays alive.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct SliceInfo(pub(super) *const c_void, pub(super) *const c_void);
+
+impl<'t> From<crate::SliceType<'t>> for SliceInfo {
+    fn from(ty: crate::SliceType<'t>) -> Self {
+        SliceInfo(
+            (ty.inner as *const SliceData).cast(),
+            (&ty.store as *const &Arc<TypeDataStore>).cast(),
+        )
+    }
+}
+
+impl SliceInfo {
+    /// Returns the store associated with this instance
+    unsafe fn store(&self) -> Result<ManuallyDrop<Arc<TypeDataStore>>, String> {
+        if self.1.is_null() {
+            return Err(String::from("null pointer"));
+        }
+
+        Ok(ManuallyDrop::new(Arc::from_raw(
+            self.1.cast::<TypeDataStore>(),
+        )))
+    }
+
+    /// Returns the struct info associated with the Type
+    unsafe fn inner(&self) -> Result<&SliceData, String> {
+        match self.0.cast::<SliceData>().as_ref() {
+            Some(store) => Ok(store),
+            None => Err(String::from("null pointer")),
+        }
+    }
+}
+
+/// Returns the type of the elements referred to by this type. Ownership is
+/// transferred if this function returns successfully.
+///
+/// # Safety
+///
+/// This function results in undefined behavior if the passed in `SliceInfo` has
+/// been deallocated by a previous call to [`mun_type_release`].
+#[no_mangle]
+pub unsafe extern "C" fn mun_slice_type_element_type(
+    ty: SliceInfo,
+    element_ty: *mut Type,
+) -> ErrorHandle {
+    let store = mun_error_try!(ty
+        .store()
+        .map_err(|e| format!("invalid argument 'ty': {e}")));
+    let ty = mun_error_try!(ty
+        .inner()
+        .map_err(|e| format!("invalid argument 'ty': {e}")));
+    let element_ty = try_deref_mut!(element_ty);
+    *element_ty =
+        RustType::new_unchecked(ty.element_ty, ManuallyDrop::deref(&store).clone()).into();
+    ErrorHandle::default()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{mem::MaybeUninit, ptr};
+
+    use mun_capi_utils::{assert_error_snapshot, assert_getter1};
+
+    use super::{mun_slice_type_element_type, SliceInfo};
+    use crate::{
+        ffi::{
+            mun_type_equal, mun_type_kind, mun_type_release, mun_type_slice_type, Type, TypeKind,
+        },
+        r#type::ffi::primitive::{mun_type_primitive, PrimitiveType},
+    };
+
+    /// Returns the slice type of the specified type. Asserts if that fails.
+    unsafe fn slice_type(ty: Type) -> (Type, SliceInfo) {
+        assert_getter1!(mun_type_slice_type(ty, slice_ty));
+
+        assert_getter1!(mun_type_kind(slice_ty, ty_kind));
+        let slice_ty = match ty_kind {
+            TypeKind::Slice(s) => s,
+            _ => panic!("invalid type kind for slice"),
+        };
+
+        (ty, slice_ty)
+    }
+
+    #[test]
+    fn test_mun_slice_type_element_type() {
+        let ffi_f32 = mun_type_primitive(PrimitiveType::F32);
+        let (ffi_f32_ptr, slice_info) = unsafe { slice_type(ffi_f32) };
+
+        assert_getter1!(mun_slice_type_element_type(slice_info, element_ty));
+        assert!(unsafe { mun_type_equal(element_ty, ffi_f32) });
+
+        unsafe { mun_type_release(element_ty) };
+        unsafe { mun_type_release(ffi_f32_ptr) };
+        unsafe { mun_type_release(ffi_f32) };
+    }
+
+    #[test]
+    fn test_mun_slice_type_element_type_invalid_null() {
+        let mut pointee_ty = MaybeUninit::uninit();
+        assert_error_snapshot!(
+            unsafe {
+                mun_slice_type_element_type(
+                    SliceInfo(ptr::null(), ptr::null()),
+                    pointee_ty.as_mut_ptr(),
+                )
+            },
+            @r###""invalid argument \'ty\': null pointer""###
+        );
+
+        let ffi_f32 = mun_type_primitive(PrimitiveType::F32);
+        let (ffi_f32_ptr, ptr_info) = unsafe { slice_type(ffi_f32) };
+        assert_error_snapshot!(
+            unsafe { mun_slice_type_element_type(ptr_info, ptr::null_mut()) },
+            @r###""invalid argument \'element_ty\': null pointer""###
+        );
+
+        unsafe { mun_type_release(ffi_f32_ptr) };
+        unsafe { mun_type_release(ffi_f32) };
+    }
+}
@@ -116,6 +116,14 @@ impl TypeDataStore {
                         queue.push_back(a.element_ty);
                     }
                 }
+                TypeDataKind::Slice(s) => {
+                    let mut element_ty = s.element_ty;
+                    let element_ty = unsafe { element_ty.as_mut() };
+                    if element_ty.mark == Mark::Unused {
+                        element_ty.mark = Mark::Used;
+                        queue.push_back(s.element_ty);
+                    }
+                }
                 TypeDataKind::Primitive(_) | TypeDataKind::Uninitialized => {}
             }
 
@@ -127,6 +135,7 @@ impl TypeDataStore {
                 &ty.mutable_pointer_type,
                 &ty.immutable_pointer_type,
                 &ty.array_type,
+                &ty.slice_type,
             ] {
                 let read_lock = indirection.read();
                 if let &Some(mut indirection_ref) = &*read_lock {
@@ -161,6 +170,12 @@ impl TypeDataStore {
     /// Tries to convert multiple [`abi::TypeDefinition`] to internal type
     /// representations. If the conversion succeeds an updated [`TypeTable`]
     /// is returned
+    ///
+    /// Definitions with the same [`abi::Guid`] as a type that is already known,
+    /// e.g. an identical struct that is defined by multiple assemblies, share
+    /// the existing type. An error is returned if such definitions differ
+    /// structurally or if a type with the same name has a different
+    /// [`abi::Guid`].
     pub fn try_from_abi<'abi>(
         self: &Arc<Self>,
         definitions: impl Iterator<Item = &'abi abi::TypeDefinition<'abi>>,
@@ -169,35 +184,73 @@ impl TypeDataStore {
         // Acquire a lock in the type entries
         let mut entries = self.types.lock();
 
-        // Create uninitialized types for all the definitions
+        // Create uninitialized types for all the definitions that are not yet known
         let mut types = Vec::new();
         let mut definition_and_type = Vec::with_capacity(definitions.size_hint().0);
         for type_def in definitions {
-            let ty = self.allocate_inner(
-                type_def.name().to_owned(),
-                Layout::from_size_align(type_def.size_in_bytes(), type_def.alignment())
-                    .expect("invalid abi type definition layout"),
-                TypeDataKind::Uninitialized,
-                &mut entries,
-            );
-            type_table.insert_concrete_type(*type_def.as_concrete(), ty.clone());
+            let guid = type_def.as_concrete();
+            let layout = Layout::from_size_align(type_def.size_in_bytes(), type_def.alignment())
+                .expect("invalid abi type definition layout");
+
+            if let Some(existing) = type_table.find_type_info_by_name(type_def.name()) {
+                if existing.as_concrete() != Some(guid) {
+                    return Err(TryFromAbiError::ConflictingDefinitions(
+                        type_def.name().to_owned(),
+                    ));
+                }
+            }
+
+            let (ty, is_shared) = match type_table.find_type_info_by_guid(guid) {
+                Some(existing) => {
+                    if existing.inner().layout != layout {
+                        return Err(TryFromAbiError::ConflictingDefinitions(
+                            type_def.name().to_owned(),
+                        ));
+                    }
+                    type_table.insert_shared_definition(*guid);
+                    (existing, true)
+                }
+                None => {
+                    let ty = self.allocate_inner(
+                        type_def.name().to_owned(),
+                        layout,
+                        TypeDataKind::Uninitialized,
+                        &mut entries,
+                    );
+                    type_table.insert_concrete_type(*guid, ty.clone());
+                    (ty, false)
+                }
+            };
             types.push(ty.clone());
-            definition_and_type.push((type_def, ty));
+            definition_and_type.push((type_def, ty, is_shared));
         }
 
         std::mem::drop(entries);
 
         // Next, initialize the types.
-        for (type_def, mut ty) in definition_and_type {
+        for (type_def, mut ty, is_shared) in definition_and_type {
+            let type_data = match &type_def.data {
+                abi::TypeDefinitionData::Struct(s) => StructData::try_from_abi(s, &type_table)?,
+            };
+
+            // A shared type has already been initialized by the first definition. The other
+            // definitions must be structurally identical.
+            if is_shared {
+                let is_identical = matches!(&ty.inner().data, TypeDataKind::Struct(existing)
+                    if existing.memory_kind == type_data.memory_kind
+                        && existing.fields == type_data.fields);
+                if !is_identical {
+                    return Err(TryFromAbiError::ConflictingDefinitions(
+                        type_def.name().to_owned(),
+                    ));
+                }
+                continue;
+            }
+
             // Safety: we are modifying the inner data of the type here. At this point this
             // is safe because the type cannot be used by anything else yet.
             let inner_ty = unsafe { ty.inner.as_mut() };
-            let type_data = match &type_def.data {
-                abi::TypeDefinitionData::Struct(s) => {
-                    StructData::try_from_abi(s, &type_table)?.into()
-                }
-            };
-            inner_ty.data = type_data;
+            inner_ty.data = type_data.into();
 
             // Mark the entry as used. This should be safe because the `type_table` also
             // still holds a strong reference to the type. After that type is
@@ -224,6 +277,7 @@ impl TypeDataStore {
             immutable_pointer_type: RwLock::default(),
             mutable_pointer_type: RwLock::default(),
             array_type: RwLock::default(),
+            slice_type: RwLock::default(),
             mark: Mark::Initializing,
         }));
 
@@ -319,6 +373,7 @@ impl Display for Type {
             TypeKind::Struct(s) => std::fmt::Display::fmt(&s, f),
             TypeKind::Pointer(p) => std::fmt::Display::fmt(&p, f),
             TypeKind::Array(a) => std::fmt::Display::fmt(&a, f),
+            TypeKind::Slice(s) => std::fmt::Display::fmt(&s, f),
         }
     }
 }
@@ -385,6 +440,9 @@ pub struct TypeData {
     /// The type of an array of this type
     array_type: RwLock<Option<NonNull<TypeData>>>,
 
+    /// The type of a slice of this type
+    slice_type: RwLock<Option<NonNull<TypeData>>>,
+
     /// The state of instance with regards to its usage.
     mark: Mark,
 }
@@ -494,6 +552,57 @@ impl TypeData {
 
         ty
     }
+
+    /// Returns the type that represents a slice of this type
+    fn slice_type(&self, store: &Arc<TypeDataStore>) -> Type {
+        let cache_key = &self.slice_type;
+
+        {
+            let read_lock = cache_key.read();
+
+            // Fast path, the type already exists, return it immediately.
+            if let Some(ty) = read_lock.deref().as_ref() {
+                return Type {
+                    inner: *ty,
+                    store: store.clone(),
+                };
+            }
+        }
+
+        // No type is currently stored, allocate a new one. A slice is passed by value as a
+        // pointer to its first element and the number of elements.
+        let mut ty = store.allocate_uninitialized(
+            format!("&[{}]", self.name),
+            Layout::new::<(*const std::ffi::c_void, usize)>(),
+            SliceData {
+                element_ty: self.into(),
+            }
+            .into(),
+        );
+
+        // Acquire the write lock
+        let mut write_lock = cache_key.write();
+
+        // Get the reference to the inner data, we need this to mark it properly.
+        let inner = unsafe { ty.inner.as_mut() };
+
+        // Recheck if another thread acquired the write lock in the mean time
+        if let Some(element_ty) = &*write_lock {
+            inner.mark = Mark::Used;
+            return Type {
+                inner: *element_ty,
+                store: store.clone(),
+            };
+        }
+
+        // We store the reference to the slice type in the current type. After which we
+        // mark the type as used. This ensures that the garbage collector never
+        // removes the type from under our noses.
+        *write_lock = Some(ty.inner);
+        inner.mark = Mark::Used;
+
+        ty
+    }
 }
 
 impl PartialEq for TypeData {
@@ -519,6 +628,8 @@ enum TypeDataKind {
     Pointer(PointerData),
     /// An array
     Array(ArrayData),
+    /// A slice
+    Slice(SliceData),
     /// Indicates that the type has been allocated but it has not yet been
     /// initialized, this indicates that it still needs to be properly
     /// initialized.
@@ -535,6 +646,8 @@ pub enum TypeKind<'t> {
     Pointer(PointerType<'t>),
     /// An array of values
     Array(ArrayType<'t>),
+    /// A slice of values
+    Slice(SliceType<'t>),
 }
 
 /// A linked version of [`mun_abi::StructInfo`] that has resolved all
@@ -757,6 +870,35 @@ impl<'t> Display for ArrayType<'t> {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+struct SliceData {
+    pub element_ty: NonNull<TypeData>,
+}
+
+/// Reference information of a slice
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct SliceType<'t> {
+    inner: &'t SliceData,
+    store: &'t Arc<TypeDataStore>,
+}
+
+impl<'t> SliceType<'t> {
+    /// Returns the type of elements this slice refers to
+    pub fn element_type(&self) -> Type {
+        // Safety: this operation is safe due to the lifetime constraints on this type
+        unsafe { Type::new_unchecked(self.inner.element_ty, self.store.clone()) }
+    }
+}
+
+impl<'t> Display for SliceType<'t> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("&[")?;
+        std::fmt::Display::fmt(&self.element_type(), f)?;
+        f.write_str("]")
+    }
+}
+
 impl From<StructData> for TypeDataKind {
     fn from(s: StructData) -> Self {
         TypeDataKind::Struct(s)
@@ -775,6 +917,12 @@ impl From<ArrayData> for TypeDataKind {
     }
 }
 
+impl From<SliceData> for TypeDataKind {
+    fn from(s: SliceData) -> Self {
+        TypeDataKind::Slice(s)
+    }
+}
+
 impl Hash for TypeData {
     fn hash<H: Hasher>(&self, state: &mut H) {
         Hash::hash(&self.data, state);
@@ -849,7 +997,7 @@ impl Type {
     /// value types directly contain their data.
     pub fn is_reference_type(&self) -> bool {
         match self.kind() {
-            TypeKind::Primitive(_) | TypeKind::Pointer(_) => false,
+            TypeKind::Primitive(_) | TypeKind::Pointer(_) | TypeKind::Slice(_) => false,
             TypeKind::Array(_) => true,
             TypeKind::Struct(s) => s.is_gc_struct(),
         }
@@ -860,7 +1008,7 @@ impl Type {
     /// types directly contain their data.
     pub fn is_value_type(&self) -> bool {
         match self.kind() {
-            TypeKind::Primitive(_) | TypeKind::Pointer(_) => true,
+            TypeKind::Primitive(_) | TypeKind::Pointer(_) | TypeKind::Slice(_) => true,
             TypeKind::Array(_) => false,
             TypeKind::Struct(s) => s.is_value_struct(),
         }
@@ -898,6 +1046,11 @@ impl Type {
         matches!(self.kind(), TypeKind::Array(_))
     }
 
+    /// Returns whether this is a slice type.
+    pub fn is_slice(&self) -> bool {
+        matches!(self.kind(), TypeKind::Slice(_))
+    }
+
     /// Returns the kind of the type
     pub fn kind(&self) -> TypeKind<'_> {
         match &self.inner().data {
@@ -914,6 +1067,10 @@ impl Type {
                 inner: a,
                 store: &self.store,
             }),
+            TypeDataKind::Slice(s) => TypeKind::Slice(SliceType {
+                inner: s,
+                store: &self.store,
+            }),
             TypeDataKind::Uninitialized => {
                 unreachable!("should never be able to query the kind of an uninitialized type")
             }
@@ -925,7 +1082,7 @@ impl Type {
     pub fn is_concrete(&self) -> bool {
         match self.kind() {
             TypeKind::Primitive(_) | TypeKind::Struct(_) => true,
-            TypeKind::Pointer(_) | TypeKind::Array(_) => false,
+            TypeKind::Pointer(_) | TypeKind::Array(_) | TypeKind::Slice(_) => false,
         }
     }
 
@@ -935,7 +1092,7 @@ impl Type {
         match self.kind() {
             TypeKind::Primitive(g) => Some(g),
             TypeKind::Struct(s) => Some(s.guid()),
-            TypeKind::Pointer(_) | TypeKind::Array(_) => None,
+            TypeKind::Pointer(_) | TypeKind::Array(_) | TypeKind::Slice(_) => None,
         }
     }
 
@@ -966,6 +1123,15 @@ impl Type {
         }
     }
 
+    /// Retrieves the type's slice information, if available.
+    pub fn as_slice(&self) -> Option<SliceType<'_>> {
+        if let TypeKind::Slice(s) = self.kind() {
+            Some(s)
+        } else {
+            None
+        }
+    }
+
     /// Tries to convert multiple [`abi::TypeDefinition`] to internal type
     /// representations. If the conversion succeeds an updated [`TypeTable`]
     /// is returned.
@@ -986,6 +1152,11 @@ impl Type {
         self.inner().array_type(&self.store)
     }
 
+    /// Returns the type that represents a slice of this type
+    pub fn slice_type(&self) -> Type {
+        self.inner().slice_type(&self.store)
+    }
+
     /// Consumes the `Type`, returning a wrapped raw pointer.
     ///
     /// After calling this function, the caller is responsible for the memory
@@ -1219,7 +1390,9 @@ fn build_type_guid_string(ty: &Type) -> String {
                 )
             }
         }
-        TypeKind::Array(_) | TypeKind::Primitive(_) | TypeKind::Pointer(_) => ty.name().to_owned(),
+        TypeKind::Array(_) | TypeKind::Slice(_) | TypeKind::Primitive(_) | TypeKind::Pointer(_) => {
+            ty.name().to_owned()
+        }
     }
 }
 
@@ -7,6 +7,9 @@ use crate::r#type::{HasStaticType, Type};
 pub struct TypeTable {
     concrete: FxHashMap<Guid, Type>,
     type_name_to_type_info: FxHashMap<String, Type>,
+    /// The number of additional definitions of concrete types that are
+    /// defined by multiple assemblies.
+    shared_definitions: FxHashMap<Guid, usize>,
 }
 
 impl TypeTable {
@@ -15,6 +18,11 @@ impl TypeTable {
         self.type_name_to_type_info.get(name.as_ref()).cloned()
     }
 
+    /// Returns the [`TypeInfo`] of the concrete type with the given [`Guid`].
+    pub fn find_type_info_by_guid(&self, guid: &Guid) -> Option<Type> {
+        self.concrete.get(guid).cloned()
+    }
+
     /// Returns the [`TypeInfo`] referenced by the given [`abi::TypeId`].
     pub fn find_type_info_by_id<'abi>(&self, type_id: &'abi abi::TypeId<'abi>) -> Option<Type> {
         match type_id {
@@ -25,6 +33,9 @@ impl TypeTable {
             abi::TypeId::Array(a) => self
                 .find_type_info_by_id(a.element)
                 .map(|ty| ty.array_type()),
+            abi::TypeId::Slice(s) => self
+                .find_type_info_by_id(s.element)
+                .map(|ty| ty.slice_type()),
         }
     }
 
@@ -58,12 +69,22 @@ impl TypeTable {
         self.concrete.insert(guid, ty)
     }
 
+    /// Records another definition of the concrete type with the given
+    /// [`Guid`], e.g. because multiple assemblies define an identical struct.
+    /// The type is shared by all definitions and remains in the type table
+    /// until all definitions have been removed.
+    pub fn insert_shared_definition(&mut self, guid: Guid) {
+        debug_assert!(self.concrete.contains_key(&guid));
+        *self.shared_definitions.entry(guid).or_default() += 1;
+    }
+
     /// Removes the specified [`TypeInfo`] from the lookup table.
     pub fn remove_type(&mut self, ty: &Type) -> Option<Type> {
         match ty.as_concrete() {
             None => panic!("can only remove concrete types"),
             Some(guid) => {
                 self.type_name_to_type_info.remove(ty.name());
+                self.shared_definitions.remove(guid);
                 self.concrete.remove(guid)
             }
         }
@@ -71,11 +92,23 @@ impl TypeTable {
 
     /// Removes a type described by the given [`abi::TypeInfo`]. Returns `None`
     /// if this instance doesn't hold any type that matches `type_info`.
+    ///
+    /// If the type is shared with other definitions, only this definition is
+    /// removed and the shared type is returned.
     pub fn remove_type_by_type_info<'abi>(
         &mut self,
         type_info: &'abi abi::TypeDefinition<'abi>,
     ) -> Option<Type> {
-        let ty = self.concrete.remove(type_info.as_concrete())?;
+        let guid = type_info.as_concrete();
+        if let Some(count) = self.shared_definitions.get_mut(guid) {
+            *count -= 1;
+            if *count == 0 {
+                self.shared_definitions.remove(guid);
+            }
+            return self.concrete.get(guid).cloned();
+        }
+
+        let ty = self.concrete.remove(guid)?;
         self.type_name_to_type_info.remove(ty.name())
     }
 
@@ -95,6 +128,7 @@ impl Default for TypeTable {
         let mut type_table = Self {
             concrete: FxHashMap::default(),
             type_name_to_type_info: FxHashMap::default(),
+            shared_definitions: FxHashMap::default(),
         };
 
         // Add all primitive types
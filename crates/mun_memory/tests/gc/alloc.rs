@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use mun_memory::{
-    gc::{Event, GcRootPtr, GcRuntime, MarkSweep},
+    gc::{Event, GcConfig, GcRootPtr, GcRuntime, HeapLimitExceeded, MarkSweep},
     HasStaticType,
 };
 
@@ -10,7 +10,7 @@ use super::util::EventAggregator;
 #[test]
 fn alloc() {
     let runtime = MarkSweep::<EventAggregator<Event>>::default();
-    let handle = runtime.alloc(i64::type_info());
+    let handle = runtime.alloc(i64::type_info()).unwrap();
 
     assert_eq!(&runtime.ptr_type(handle), i64::type_info());
 
@@ -22,7 +22,7 @@ fn alloc() {
 #[test]
 fn collect_simple() {
     let runtime = MarkSweep::<EventAggregator<Event>>::default();
-    let handle = runtime.alloc(i64::type_info());
+    let handle = runtime.alloc(i64::type_info()).unwrap();
 
     runtime.collect();
 
@@ -39,8 +39,8 @@ fn collect_rooted() {
     let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
 
     // Allocate simple object and rooted object
-    let handle = runtime.alloc(i64::type_info());
-    let rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()));
+    let handle = runtime.alloc(i64::type_info()).unwrap();
+    let rooted = GcRootPtr::new(&runtime, runtime.alloc(i64::type_info()).unwrap());
 
     // Collect unreachable objects, should not collect the root handle
     runtime.collect();
@@ -68,3 +68,39 @@ fn collect_rooted() {
     assert_eq!(events.next(), Some(Event::End));
     assert_eq!(events.next(), None);
 }
+
+#[test]
+fn max_heap() {
+    let runtime = MarkSweep::<EventAggregator<Event>>::with_config(GcConfig {
+        initial_heap: 64,
+        max_heap: Some(8),
+    });
+    assert_eq!(runtime.stats().max_heap, Some(8));
+
+    // Allocating up to the limit is allowed
+    runtime.alloc(i64::type_info()).unwrap();
+    assert!(!runtime.stats().exceeds_max_heap());
+
+    // Allocating past the limit fails
+    assert_eq!(
+        runtime.alloc(i64::type_info()),
+        Err(HeapLimitExceeded {
+            size: 8,
+            allocated_memory: 8,
+            max_heap: 8,
+        })
+    );
+    let array_type = i64::type_info().array_type();
+    assert!(runtime.alloc_array(&array_type, 0).is_err());
+    assert_eq!(runtime.stats().allocated_memory, 8);
+
+    // Allocations that cannot fail grow the heap beyond its limit
+    runtime.alloc_beyond_limit(i64::type_info(), true);
+    runtime.alloc_array_beyond_limit(&array_type, 0);
+    assert!(runtime.stats().exceeds_max_heap());
+
+    // Collecting the unreachable objects makes room for new allocations
+    runtime.collect();
+    assert_eq!(runtime.stats().allocated_memory, 0);
+    runtime.alloc(i64::type_info()).unwrap();
+}
@@ -1,4 +1,5 @@
 mod alloc;
+mod snapshot;
 mod structs;
 #[macro_use]
 mod util;
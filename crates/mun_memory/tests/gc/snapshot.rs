@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use mun_memory::{
+    gc::{Event, GcPtr, GcRootPtr, GcRuntime, HasIndirectionPtr, MarkSweep},
+    type_table::TypeTable,
+};
+
+use super::util::EventAggregator;
+use crate::fake_struct;
+
+struct FooObject {
+    bar: GcPtr,
+}
+
+struct BarObject {
+    a: i64,
+}
+
+#[test]
+fn snapshot_reachable() {
+    let mut type_table = TypeTable::default();
+
+    let bar_type_info = fake_struct!(type_table, "core::Bar", "a" => i64);
+    type_table.insert_type(bar_type_info.clone());
+
+    let foo_type_info = fake_struct!(type_table, "core::Foo", "bar" => Bar);
+    type_table.insert_type(foo_type_info.clone());
+
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+    let mut foo = GcRootPtr::new(&runtime, runtime.alloc(&foo_type_info).unwrap());
+    let bar = runtime.alloc(&bar_type_info).unwrap();
+    let _unreachable = runtime.alloc(&bar_type_info).unwrap();
+
+    unsafe {
+        (*foo.deref_mut::<FooObject>()).bar = bar;
+    }
+
+    // Only objects that are reachable from a root are part of the snapshot
+    let snapshot = runtime.snapshot();
+    assert_eq!(snapshot.len(), 2);
+    assert_eq!(snapshot.roots().collect::<Vec<_>>(), vec![foo.handle()]);
+}
+
+#[test]
+fn restore_in_place() {
+    let mut type_table = TypeTable::default();
+
+    let bar_type_info = fake_struct!(type_table, "core::Bar", "a" => i64);
+    type_table.insert_type(bar_type_info.clone());
+
+    let foo_type_info = fake_struct!(type_table, "core::Foo", "bar" => Bar);
+    type_table.insert_type(foo_type_info.clone());
+
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+    let mut foo = GcRootPtr::new(&runtime, runtime.alloc(&foo_type_info).unwrap());
+    let mut bar = runtime.alloc(&bar_type_info).unwrap();
+
+    unsafe {
+        (*foo.deref_mut::<FooObject>()).bar = bar;
+        (*bar.deref_mut::<BarObject>()).a = 1;
+    }
+
+    let snapshot = runtime.snapshot();
+
+    // Modify the object graph
+    let mut other_bar = runtime.alloc(&bar_type_info).unwrap();
+    unsafe {
+        (*bar.deref_mut::<BarObject>()).a = 2;
+        (*other_bar.deref_mut::<BarObject>()).a = 3;
+        (*foo.deref_mut::<FooObject>()).bar = other_bar;
+    }
+
+    let restored = runtime.restore(&snapshot);
+    assert_eq!(restored.len(), 1);
+    assert_eq!(restored[&foo.handle()].handle(), foo.handle());
+
+    // Existing handles are restored in place
+    unsafe {
+        assert_eq!((*foo.deref::<FooObject>()).bar, bar);
+        assert_eq!((*bar.deref::<BarObject>()).a, 1);
+        assert_eq!((*other_bar.deref::<BarObject>()).a, 3);
+    }
+
+    // The object that was allocated after the snapshot is no longer reachable
+    runtime.observer().take_all();
+    runtime.collect();
+
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::Deallocation(other_bar)));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), None);
+}
+
+#[test]
+fn restore_cycle_after_collect() {
+    let mut type_table = TypeTable::default();
+
+    let bar_type_info = fake_struct!(type_table, "core::Bar", "a" => i64);
+    type_table.insert_type(bar_type_info);
+
+    let foo_type_info = fake_struct!(type_table, "core::Foo", "bar" => Bar);
+    type_table.insert_type(foo_type_info.clone());
+
+    let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
+    let mut foo = GcRootPtr::new(&runtime, runtime.alloc(&foo_type_info).unwrap());
+
+    // Assign foo to foo.bar
+    unsafe {
+        (*foo.deref_mut::<FooObject>()).bar = foo.handle();
+    }
+
+    let snapshot = runtime.snapshot();
+    assert_eq!(snapshot.len(), 1);
+
+    // Drop foo and collect it
+    drop(foo);
+    runtime.collect();
+    runtime.observer().take_all();
+
+    // Restoring reallocates the object and relocates the reference to itself
+    let roots = runtime.restore(&snapshot);
+
+    let mut events = runtime.observer().take_all().into_iter();
+    let restored = match events.next() {
+        Some(Event::Allocation(handle)) => handle,
+        event => panic!("expected an allocation, found {event:?}"),
+    };
+    assert_eq!(events.next(), None);
+
+    // The reallocated object is rooted under its new handle
+    let snapshot_handle = snapshot.roots().next().unwrap();
+    assert_eq!(roots.len(), 1);
+    assert_eq!(roots[&snapshot_handle].handle(), restored);
+
+    // Collecting after the restore keeps the restored object alive
+    runtime.collect();
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), None);
+
+    assert_eq!(runtime.ptr_type(restored), foo_type_info);
+    unsafe {
+        assert_eq!((*restored.deref::<FooObject>()).bar, restored);
+    }
+
+    // Dropping the root allows the restored object to be collected
+    drop(roots);
+    runtime.collect();
+    let mut events = runtime.observer().take_all().into_iter();
+    assert_eq!(events.next(), Some(Event::Start));
+    assert_eq!(events.next(), Some(Event::Deallocation(restored)));
+    assert_eq!(events.next(), Some(Event::End));
+    assert_eq!(events.next(), None);
+}
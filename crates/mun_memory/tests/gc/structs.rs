@@ -29,8 +29,8 @@ fn test_trace() {
     type_table.insert_type(foo_type_info.clone());
 
     let runtime = MarkSweep::<EventAggregator<Event>>::default();
-    let mut foo_handle = runtime.alloc(&foo_type_info);
-    let bar_handle = runtime.alloc(&bar_type_info);
+    let mut foo_handle = runtime.alloc(&foo_type_info).unwrap();
+    let bar_handle = runtime.alloc(&bar_type_info).unwrap();
 
     // Assign bar to foo.bar
     unsafe {
@@ -55,8 +55,8 @@ fn trace_collect() {
     type_table.insert_type(foo_type_info.clone());
 
     let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
-    let mut foo_ptr = GcRootPtr::new(&runtime, runtime.alloc(&foo_type_info));
-    let bar = runtime.alloc(&bar_type_info);
+    let mut foo_ptr = GcRootPtr::new(&runtime, runtime.alloc(&foo_type_info).unwrap());
+    let bar = runtime.alloc(&bar_type_info).unwrap();
 
     // Assign bar to foo.bar
     unsafe {
@@ -95,7 +95,7 @@ fn trace_cycle() {
     type_table.insert_type(foo_type_info.clone());
 
     let runtime = Arc::new(MarkSweep::<EventAggregator<Event>>::default());
-    let mut foo_ptr = GcRootPtr::new(&runtime, runtime.alloc(&foo_type_info));
+    let mut foo_ptr = GcRootPtr::new(&runtime, runtime.alloc(&foo_type_info).unwrap());
 
     // Assign foo to foo.bar
     unsafe {
@@ -120,3 +120,24 @@ fn trace_cycle() {
     assert_eq!(events.next(), Some(Event::End));
     assert_eq!(events.next(), None);
 }
+
+#[test]
+fn alloc_uninit_zeroes_references() {
+    let mut type_table = TypeTable::default();
+
+    let bar_type_info = fake_struct!(type_table, "core::Bar", "a" => i64);
+    type_table.insert_type(bar_type_info.clone());
+
+    let foo_type_info = fake_struct!(type_table, "core::Foo", "a" => i64, "bar" => Bar);
+    type_table.insert_type(foo_type_info.clone());
+
+    let runtime = MarkSweep::<EventAggregator<Event>>::default();
+    let foo_handle = runtime.alloc_uninit(&foo_type_info).unwrap();
+
+    // The reference to bar is zeroed, so tracing foo does not yield an object
+    let mut trace = foo_type_info.trace(foo_handle);
+    assert_eq!(trace.next(), None);
+
+    // Collecting the uninitialized object is safe
+    runtime.collect();
+}
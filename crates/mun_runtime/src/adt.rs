@@ -233,8 +233,16 @@ impl<'s> Marshal<'s> for StructRef<'s> {
         let gc_handle = if struct_info.is_value_struct() {
             // For a value struct, `ptr` points to a struct value.
 
-            // Create a new object using the runtime's intrinsic
-            let mut gc_handle = runtime.gc().alloc(type_info);
+            // Create a new object using the runtime's intrinsic. Marshalling cannot
+            // fail and garbage cannot be collected while the references in the struct
+            // are not rooted, so if the object does not fit within the maximum heap
+            // size the heap grows beyond its limit. The error is reported by the
+            // invocation that returns next.
+            let gc = runtime.gc();
+            let mut gc_handle = gc.alloc(type_info).unwrap_or_else(|error| {
+                gc.observer().heap_limit.record(error);
+                gc.alloc_beyond_limit(type_info, true)
+            });
 
             // Construct
             let src = ptr.cast::<u8>().as_ptr() as *const _;
@@ -0,0 +1,113 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display, Formatter},
+    sync::Arc,
+};
+
+use mun_memory::gc::{self, GcPtr};
+use parking_lot::Mutex;
+
+/// Records the source locations of allocations made by assemblies that were
+/// compiled with allocation debugging enabled. Assemblies compiled without it
+/// never record a location, in which case the tracker does nothing.
+#[derive(Default)]
+pub struct AllocationTracker {
+    /// Interned source locations. The locations are copied out of the
+    /// assemblies, so they remain valid when an assembly is hot reloaded.
+    locations: Mutex<HashSet<Arc<str>>>,
+    /// The source location of every live object that was allocated with one
+    allocations: Mutex<HashMap<GcPtr, Arc<str>>>,
+}
+
+impl AllocationTracker {
+    /// Records `location` as the source location of the object referred to by
+    /// `handle`.
+    pub fn record(&self, handle: GcPtr, location: &str) {
+        let location = {
+            let mut locations = self.locations.lock();
+            match locations.get(location) {
+                Some(location) => location.clone(),
+                None => {
+                    let location: Arc<str> = Arc::from(location);
+                    locations.insert(location.clone());
+                    location
+                }
+            }
+        };
+
+        self.allocations.lock().insert(handle, location);
+    }
+
+    /// Returns the live allocations grouped by their source location.
+    pub fn report(&self) -> AllocationReport {
+        let mut counts: HashMap<&Arc<str>, usize> = HashMap::new();
+        let allocations = self.allocations.lock();
+        for location in allocations.values() {
+            *counts.entry(location).or_default() += 1;
+        }
+
+        let mut sites: Vec<AllocationSite> = counts
+            .into_iter()
+            .map(|(location, count)| AllocationSite {
+                location: location.to_string(),
+                count,
+            })
+            .collect();
+        sites.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.location.cmp(&b.location))
+        });
+
+        AllocationReport { sites }
+    }
+}
+
+impl gc::Observer for AllocationTracker {
+    type Event = gc::Event;
+
+    fn event(&self, event: gc::Event) {
+        if let gc::Event::Deallocation(handle) = event {
+            let mut allocations = self.allocations.lock();
+            if !allocations.is_empty() {
+                allocations.remove(&handle);
+            }
+        }
+    }
+}
+
+/// The live allocations of a [`Runtime`](crate::Runtime), grouped by the
+/// source location of the allocating expression.
+///
+/// Only allocations made by assemblies that were compiled with allocation
+/// debugging enabled are included.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AllocationReport {
+    /// The allocation sites, ordered by descending number of live allocations
+    pub sites: Vec<AllocationSite>,
+}
+
+/// The live allocations of a single source location.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AllocationSite {
+    /// The source location of the allocating expression, e.g. `mod.mun:3:5`
+    pub location: String,
+    /// The number of live objects that were allocated at the location
+    pub count: usize,
+}
+
+impl AllocationReport {
+    /// Returns the total number of live allocations in the report.
+    pub fn total(&self) -> usize {
+        self.sites.iter().map(|site| site.count).sum()
+    }
+}
+
+impl Display for AllocationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for site in &self.sites {
+            writeln!(f, "{:>8} {}", site.count, site.location)?;
+        }
+        Ok(())
+    }
+}
@@ -3,7 +3,10 @@ use std::{
     ffi::c_void,
     io,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
 };
 
 use itertools::Itertools;
@@ -13,7 +16,7 @@ use mun_libloader::{MunLibrary, TempLibrary};
 use mun_memory::{
     mapping::{Mapping, MemoryMapper},
     type_table::TypeTable,
-    Type,
+    Type, TypeKind,
 };
 
 use crate::{garbage_collector::GarbageCollector, DispatchTable};
@@ -25,12 +28,35 @@ pub enum LoadError {
     AlreadyExists,
     #[error(transparent)]
     FailedToLoadSharedLibrary(#[from] mun_libloader::InitError),
-    #[error("ABI version mismatch. munlib is `{actual}` but runtime is `{expected}`")]
+    #[error("ABI version mismatch. munlib is `{actual}` but runtime is `{expected}`. {}", rebuild_hint(.expected, .actual))]
     MismatchedAbiVersions { expected: u32, actual: u32 },
+    #[error("munlib does not contain a format header. It was likely built with an older compiler, rebuild it with a newer compiler")]
+    MissingFormatHeader,
+    #[error("munlib contains an invalid format header")]
+    InvalidFormatHeader,
+    #[error("munlib format version mismatch. munlib is `{actual}` but runtime is `{expected}`. {}", rebuild_hint(.expected, .actual))]
+    MismatchedFormatVersions { expected: u32, actual: u32 },
+    #[error("munlib does not contain compiler information")]
+    MissingCompilerInfo,
+    #[error("ABI changed. munlib has ABI fingerprint `{actual}` but the host expects `{expected}`. Rebuild the host against the munlib")]
+    AbiChanged {
+        expected: abi::AbiFingerprint,
+        actual: abi::AbiFingerprint,
+    },
     #[error(transparent)]
     Other(#[from] io::Error),
 }
 
+/// Returns a hint on how to resolve a version mismatch between a munlib and the
+/// runtime.
+fn rebuild_hint(expected: &u32, actual: &u32) -> &'static str {
+    if actual < expected {
+        "Rebuild the munlib with a newer compiler"
+    } else {
+        "Upgrade the runtime or rebuild the munlib with an older compiler"
+    }
+}
+
 /// An error that occurs upon linking of a Mun assembly.
 #[derive(Debug, thiserror::Error)]
 pub enum LinkError {
@@ -85,18 +111,25 @@ pub enum LinkFunctionsError {
     },
 }
 
+/// The identifier of the next assembly that is loaded
+static NEXT_LOAD_ID: AtomicU64 = AtomicU64::new(0);
+
 /// An assembly is a hot reloadable compilation unit, consisting of one or more
 /// Mun modules.
 pub struct Assembly {
+    /// Uniquely identifies this load of the assembly within the process
+    load_id: u64,
     library_path: PathBuf,
     library: TempLibrary,
     info: abi::AssemblyInfo<'static>,
+    compiler_info: abi::CompilerInfo,
     allocator: Arc<GarbageCollector>,
 }
 
 impl Assembly {
     /// Loads an assembly and its information for the shared library at
-    /// `library_path`. The resulting `Assembly` is ensured to be linkable.
+    /// `library_path`, which exports the specified `entry_symbols`. The
+    /// resulting `Assembly` is ensured to be linkable.
     ///
     /// # Safety
     ///
@@ -112,22 +145,61 @@ impl Assembly {
     /// unloaded.
     ///
     /// See [`libloading::Library::new`] for more information.
-    pub unsafe fn load(library_path: &Path, gc: Arc<GarbageCollector>) -> Result<Self, LoadError> {
-        let mut library = MunLibrary::new(library_path)?;
+    pub unsafe fn load(
+        library_path: &Path,
+        gc: Arc<GarbageCollector>,
+        entry_symbols: &abi::EntrySymbols,
+    ) -> Result<Self, LoadError> {
+        let mut library = MunLibrary::with_entry_symbols(library_path, entry_symbols.clone())?;
+
+        // Validate the format header before reading any other information from the
+        // library. The ABI version is still queried separately because munlibs
+        // that predate the format header can report their version that way.
+        let Some(header) = library.get_format_header() else {
+            let version = library.get_abi_version();
+            return Err(if abi::ABI_VERSION != version {
+                LoadError::MismatchedAbiVersions {
+                    expected: abi::ABI_VERSION,
+                    actual: version,
+                }
+            } else {
+                LoadError::MissingFormatHeader
+            });
+        };
 
-        let version = library.get_abi_version();
-        if abi::ABI_VERSION != version {
+        if !header.has_valid_magic() {
+            return Err(LoadError::InvalidFormatHeader);
+        }
+
+        if abi::FORMAT_VERSION != header.format_version {
+            return Err(LoadError::MismatchedFormatVersions {
+                expected: abi::FORMAT_VERSION,
+                actual: header.format_version,
+            });
+        }
+
+        if abi::ABI_VERSION != header.abi_version {
             return Err(LoadError::MismatchedAbiVersions {
                 expected: abi::ABI_VERSION,
-                actual: version,
+                actual: header.abi_version,
             });
         }
 
+        let compiler_info = library
+            .get_compiler_info()
+            .ok_or(LoadError::MissingCompilerInfo)?;
+
         let allocator_ptr = Arc::into_raw(gc.clone()) as *mut std::ffi::c_void;
         library.set_allocator_handle(allocator_ptr);
 
+        if let Some(mut call_trace) = library.get_call_trace() {
+            gc.observer().call_trace.attach(call_trace.as_mut());
+        }
+
         let assembly = Assembly {
+            load_id: NEXT_LOAD_ID.fetch_add(1, Ordering::Relaxed),
             info: library.get_info(),
+            compiler_info,
             library_path: library_path.to_path_buf(),
             library: library.into_inner(),
             allocator: gc,
@@ -309,12 +381,15 @@ impl Assembly {
 
     /// Tries to link the `unlinked_assemblies`, resulting in a new
     /// [`DispatchTable`] on success. This leaves the original
-    /// `dispatch_table` intact, in case of linking errors.
+    /// `dispatch_table` intact, in case of linking errors. If
+    /// `preserve_statics` is true, the values of the function-local statics of
+    /// the replaced assemblies are copied to the new assemblies.
     pub(super) fn relink_all(
         unlinked_assemblies: &mut HashMap<PathBuf, Assembly>,
         linked_assemblies: &mut HashMap<PathBuf, Assembly>,
         dispatch_table: &DispatchTable,
         type_table: &TypeTable,
+        preserve_statics: bool,
     ) -> Result<(DispatchTable, TypeTable), LinkError> {
         let mut dependencies: HashMap<String, Vec<String>> = unlinked_assemblies
             .values()
@@ -339,6 +414,9 @@ impl Assembly {
         // Clone the dispatch table, such that we can roll back if linking fails
         let mut dispatch_table = dispatch_table.clone();
 
+        // The new addresses of the functions of the replaced assemblies
+        let mut remapped_fn_ptrs = HashMap::new();
+
         while let Some(mut entry) = assemblies_to_link.pop_front() {
             let (ref old_assembly, ref mut new_assembly) = entry;
 
@@ -422,6 +500,16 @@ impl Assembly {
             // (missing) or the signature of a function doesnt match.
             Assembly::link_all_functions(&dispatch_table, &type_table, functions_to_link)?;
 
+            if let Some(old_assembly) = old_assembly {
+                remapped_fn_ptrs.extend(old_assembly.remapped_fn_ptrs(new_assembly));
+            }
+
+            if preserve_statics {
+                if let Some(old_assembly) = old_assembly {
+                    new_assembly.preserve_statics(old_assembly, &type_table);
+                }
+            }
+
             // Remove this assembly from the dependencies
             dependencies
                 .values_mut()
@@ -444,12 +532,137 @@ impl Assembly {
             linked_assemblies.insert(new_path, new_assembly);
         }
 
+        // Update the function pointers that are stored in memory, which would otherwise
+        // refer to the code of the replaced assemblies once they are unloaded
+        if !remapped_fn_ptrs.is_empty() {
+            let remap = |ptr: *const c_void| remapped_fn_ptrs.get(&ptr).copied();
+            for assembly in linked_assemblies.values() {
+                assembly.remap_static_pointers(&type_table, remap);
+            }
+            if let Some(assembly) = linked_assemblies.values().next() {
+                assembly.allocator.remap_pointers(remap);
+            }
+        }
+
         // Collect types
         Type::collect_unreferenced_type_data();
 
         Ok((dispatch_table, type_table))
     }
 
+    /// Copies the values of the function-local statics of `old_assembly` to
+    /// the statics of this assembly that have the same name and type. The
+    /// copied statics are marked as initialized, so their initializers are
+    /// not evaluated again.
+    fn preserve_statics(&self, old_assembly: &Assembly, type_table: &TypeTable) {
+        let old_statics = old_assembly.info.symbols.statics();
+        for new_static in self.info.symbols.statics() {
+            let Some(old_static) = old_statics.iter().find(|old_static| {
+                old_static.name() == new_static.name() && old_static.type_id == new_static.type_id
+            }) else {
+                continue;
+            };
+
+            // SAFETY: The initialization state is only modified by the assembly itself, and
+            // no Mun code is executed while assemblies are being relinked.
+            let old_initialized = old_static.init_state_ptr.is_null()
+                || unsafe { *old_static.init_state_ptr } == abi::STATIC_INITIALIZED;
+            if !old_initialized {
+                continue;
+            }
+
+            let Some(ty) = type_table.find_type_info_by_id(&new_static.type_id) else {
+                continue;
+            };
+
+            // SAFETY: Both statics have the same type, and thus the same size. No Mun code is
+            // executed while assemblies are being relinked.
+            unsafe {
+                std::ptr::copy_nonoverlapping(
+                    old_static.value_ptr.cast::<u8>(),
+                    new_static.value_ptr.cast::<u8>(),
+                    ty.value_layout().size(),
+                );
+                if !new_static.init_state_ptr.is_null() {
+                    *new_static.init_state_ptr = abi::STATIC_INITIALIZED;
+                }
+            }
+        }
+    }
+
+    /// Maps the addresses of the functions of this assembly to the addresses of
+    /// the functions with the same name in `new_assembly`. A function pointer
+    /// holds either the address from the dispatch table or that of the
+    /// function definition, so both are mapped.
+    fn remapped_fn_ptrs(&self, new_assembly: &Assembly) -> Vec<(*const c_void, *const c_void)> {
+        let new_entries: HashMap<&str, *const c_void> = new_assembly
+            .info
+            .dispatch_table
+            .iter()
+            .map(|(ptr, prototype)| (prototype.name(), *ptr))
+            .collect();
+        let new_functions: HashMap<&str, *const c_void> = new_assembly
+            .info
+            .symbols
+            .functions()
+            .iter()
+            .map(|function| (function.prototype.name(), function.fn_ptr))
+            .collect();
+
+        let entries = self
+            .info
+            .dispatch_table
+            .iter()
+            .filter_map(|(ptr, prototype)| Some((*ptr, *new_entries.get(prototype.name())?)));
+        let functions = self.info.symbols.functions().iter().filter_map(|function| {
+            Some((
+                function.fn_ptr,
+                *new_functions.get(function.prototype.name())?,
+            ))
+        });
+        entries
+            .chain(functions)
+            .filter(|(old_ptr, new_ptr)| !old_ptr.is_null() && old_ptr != new_ptr)
+            .collect()
+    }
+
+    /// Replaces the raw pointers stored in the statics of this assembly for
+    /// which `remap` returns a new value.
+    fn remap_static_pointers(
+        &self,
+        type_table: &TypeTable,
+        remap: impl Fn(*const c_void) -> Option<*const c_void>,
+    ) {
+        for static_def in self.info.symbols.statics() {
+            let Some(ty) = type_table.find_type_info_by_id(&static_def.type_id) else {
+                continue;
+            };
+
+            let mut offsets = Vec::new();
+            pointer_offsets(&ty, 0, &mut offsets);
+            for offset in offsets {
+                // SAFETY: The offset lies within the value of the static, and no Mun code is
+                // executed while assemblies are being relinked.
+                unsafe {
+                    let pointer = static_def
+                        .value_ptr
+                        .cast::<u8>()
+                        .add(offset)
+                        .cast::<*const c_void>();
+                    if let Some(remapped) = remap(*pointer) {
+                        *pointer = remapped;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns an identifier that is unique to this load of the assembly. A
+    /// reloaded assembly receives a new identifier.
+    pub fn load_id(&self) -> u64 {
+        self.load_id
+    }
+
     /// Returns the assembly's information.
     pub fn info(&self) -> &abi::AssemblyInfo<'_> {
         &self.info
@@ -461,6 +674,37 @@ impl Assembly {
         unsafe { std::mem::transmute(&mut self.info) }
     }
 
+    /// Returns the fingerprint of the assembly's ABI, computed from the
+    /// signatures of its functions and the layouts of its types.
+    pub fn abi_fingerprint(&self) -> abi::AbiFingerprint {
+        abi::AbiFingerprint::from_assembly_info(&self.info)
+    }
+
+    /// Returns information on how the assembly was compiled, e.g. the
+    /// optimization level and target triple it was built with.
+    pub fn build_info(&self) -> &abi::BuildInfo {
+        &self.info.build_info
+    }
+
+    /// Returns information on the toolchain that produced the assembly, e.g.
+    /// the compiler and LLVM versions it was built with.
+    pub fn compiler_info(&self) -> &abi::CompilerInfo {
+        &self.compiler_info
+    }
+
+    /// Returns the value of the public constant with the specified `name`,
+    /// e.g. `config::MAX`, if it exists and is of type `T`. The value was
+    /// evaluated at compile time and embedded in the assembly, so no Mun code
+    /// is run.
+    pub fn constant<T: abi::HasStaticTypeId + Copy>(&self, name: &str) -> Option<T> {
+        self.info
+            .symbols
+            .constants()
+            .iter()
+            .find(|constant| constant.name() == name)
+            .and_then(abi::ConstDefinition::value)
+    }
+
     /// Returns the path corresponding to the assembly's library.
     pub fn library_path(&self) -> &Path {
         self.library_path.as_path()
@@ -472,3 +716,17 @@ impl Assembly {
         self.library
     }
 }
+
+/// Collects the offsets of the raw pointers in a value of type `ty` that is
+/// stored at `offset`. Garbage collected objects are not visited.
+fn pointer_offsets(ty: &Type, offset: usize, offsets: &mut Vec<usize>) {
+    match ty.kind() {
+        TypeKind::Pointer(_) => offsets.push(offset),
+        TypeKind::Struct(s) if !s.is_gc_struct() => {
+            for field in s.fields().iter() {
+                pointer_offsets(&field.ty(), offset + field.offset(), offsets);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,144 @@
+use std::{collections::HashMap, sync::Arc};
+
+use mun_abi as abi;
+use parking_lot::Mutex;
+
+/// Owns the ring into which the functions of assemblies that were compiled
+/// with a call trace ring record their calls. All assemblies share the same
+/// ring, which holds at least as many calls as the largest capacity requested
+/// by any of them. Assemblies compiled without a call trace ring never record
+/// their calls, in which case the ring remains empty.
+pub struct CallTraceRecorder {
+    state: Mutex<CallTraceState>,
+}
+
+struct CallTraceState {
+    /// The ring that the assemblies refer to. It is boxed so its address
+    /// remains the same for as long as the recorder lives.
+    ring: Box<abi::CallTraceRing>,
+    /// The entries of the ring
+    ids: Vec<u32>,
+    /// The names of the functions per id. The names are copied out of the
+    /// assemblies, so calls of hot reloaded assemblies can still be resolved.
+    names: HashMap<u32, Arc<str>>,
+}
+
+// The ring only points into the entries that are owned by the state itself.
+unsafe impl Send for CallTraceState {}
+
+impl Default for CallTraceRecorder {
+    fn default() -> Self {
+        let mut ids = vec![0];
+        let ring = Box::new(abi::CallTraceRing {
+            ids: ids.as_mut_ptr(),
+            mask: 0,
+            next: 0,
+        });
+
+        CallTraceRecorder {
+            state: Mutex::new(CallTraceState {
+                ring,
+                ids,
+                names: HashMap::new(),
+            }),
+        }
+    }
+}
+
+impl CallTraceRecorder {
+    /// Points the call trace of an assembly to the ring, growing the ring if
+    /// the assembly requests a larger capacity.
+    ///
+    /// # Safety
+    ///
+    /// The ring must outlive the assembly, and the functions of the assembly
+    /// must not be called while the ring is accessed on another thread.
+    pub unsafe fn attach(&self, call_trace: &mut abi::CallTrace) {
+        let mut state = self.state.lock();
+        state.reserve(call_trace.capacity);
+        for (id, name) in call_trace.functions() {
+            state.names.insert(id, Arc::from(name));
+        }
+        call_trace.ring = &mut *state.ring;
+    }
+
+    /// Returns the full names of the most recently called functions, ordered
+    /// from the oldest to the most recent call.
+    pub fn calls(&self) -> Vec<String> {
+        let state = self.state.lock();
+        state
+            .recent_ids()
+            .into_iter()
+            .map(|id| match state.names.get(&id) {
+                Some(name) => name.to_string(),
+                None => format!("<unknown function {id:#010x}>"),
+            })
+            .collect()
+    }
+}
+
+impl CallTraceState {
+    /// Grows the ring such that it holds at least `capacity` calls, keeping
+    /// the calls that were already recorded.
+    fn reserve(&mut self, capacity: u32) {
+        let capacity = (capacity as usize).next_power_of_two();
+        if capacity <= self.ids.len() {
+            return;
+        }
+
+        let recent = self.recent_ids();
+        let mut ids = vec![0; capacity];
+        ids[..recent.len()].copy_from_slice(&recent);
+        self.ids = ids;
+        self.ring.ids = self.ids.as_mut_ptr();
+        self.ring.mask = capacity as u64 - 1;
+        self.ring.next = recent.len() as u64;
+    }
+
+    /// Returns the ids of the calls in the ring, oldest first.
+    fn recent_ids(&self) -> Vec<u32> {
+        let next = self.ring.next;
+        let start = next.saturating_sub(self.ids.len() as u64);
+        (start..next)
+            .map(|idx| self.ids[(idx & self.ring.mask) as usize])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CallTraceRecorder;
+
+    /// Records a call in the same way that instrumented functions do.
+    fn record(recorder: &CallTraceRecorder, id: u32) {
+        let mut state = recorder.state.lock();
+        let index = (state.ring.next & state.ring.mask) as usize;
+        state.ids[index] = id;
+        state.ring.next += 1;
+    }
+
+    #[test]
+    fn ring_keeps_most_recent_calls() {
+        let recorder = CallTraceRecorder::default();
+        recorder.state.lock().reserve(3);
+        for id in 1..=6 {
+            record(&recorder, id);
+        }
+
+        assert_eq!(recorder.state.lock().recent_ids(), vec![3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn growing_keeps_recorded_calls() {
+        let recorder = CallTraceRecorder::default();
+        recorder.state.lock().reserve(2);
+        for id in 1..=3 {
+            record(&recorder, id);
+        }
+        recorder.state.lock().reserve(8);
+        record(&recorder, 4);
+
+        assert_eq!(recorder.state.lock().recent_ids(), vec![2, 3, 4]);
+        assert_eq!(recorder.calls().len(), 3);
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{ffi::c_void, sync::Arc};
 
 use mun_abi as abi;
 use mun_memory::type_table::TypeTable;
@@ -11,6 +11,44 @@ use crate::function_info::FunctionDefinition;
 #[derive(Clone, Default)]
 pub struct DispatchTable {
     functions: FxHashMap<String, Arc<FunctionDefinition>>,
+    /// Information about every function that was ever inserted, which is
+    /// retained when the function is removed during a hot reload.
+    slots: FxHashMap<String, SlotInfo>,
+}
+
+#[derive(Clone, Copy)]
+struct SlotInfo {
+    kind: DispatchTableEntryKind,
+    /// The address of the function when it was first inserted
+    initial_fn_ptr: usize,
+}
+
+/// Describes where the function of a [`DispatchTableEntry`] is defined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DispatchTableEntryKind {
+    /// The function is defined in a loaded assembly
+    Assembly,
+    /// The function is registered by the host, e.g. using
+    /// [`RuntimeBuilder::insert_fn`](crate::RuntimeBuilder::insert_fn)
+    Extern,
+    /// The function is provided by the runtime itself, e.g. to allocate
+    /// objects
+    Intrinsic,
+}
+
+/// A snapshot of a single entry in the dispatch table of a
+/// [`Runtime`](crate::Runtime).
+#[derive(Clone, Debug)]
+pub struct DispatchTableEntry {
+    /// The full path of the function
+    pub name: String,
+    /// The address that calls to the function are currently dispatched to
+    pub fn_ptr: *const c_void,
+    /// Whether the function was patched by a hot reload since it was first
+    /// loaded
+    pub is_patched: bool,
+    /// Where the function is defined
+    pub kind: DispatchTableEntryKind,
 }
 
 impl DispatchTable {
@@ -20,12 +58,41 @@ impl DispatchTable {
         self.functions.get(fn_path).cloned()
     }
 
+    /// Returns where the function corresponding to `fn_path` is defined, if it
+    /// exists.
+    pub fn kind(&self, fn_path: &str) -> Option<DispatchTableEntryKind> {
+        self.functions
+            .contains_key(fn_path)
+            .then(|| self.slots[fn_path].kind)
+    }
+
     /// Retrieves the name of all available functions.
     pub fn get_fn_names(&self) -> impl Iterator<Item = &str> {
         self.functions.keys().map(String::as_str)
     }
 
-    /// Inserts the `fn_info` for `fn_path` into the dispatch table.
+    /// Returns a snapshot of all entries of the dispatch table, ordered by
+    /// name.
+    pub fn entries(&self) -> Vec<DispatchTableEntry> {
+        let mut entries: Vec<_> = self
+            .functions
+            .iter()
+            .map(|(name, fn_info)| {
+                let slot = self.slots[name];
+                DispatchTableEntry {
+                    name: name.clone(),
+                    fn_ptr: fn_info.fn_ptr,
+                    is_patched: slot.initial_fn_ptr != fn_info.fn_ptr as usize,
+                    kind: slot.kind,
+                }
+            })
+            .collect();
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        entries
+    }
+
+    /// Inserts the `fn_info` for `fn_path` into the dispatch table as a
+    /// function that is registered by the host.
     ///
     /// If the dispatch table already contained this `fn_path`, the value is
     /// updated, and the old value is returned.
@@ -34,7 +101,38 @@ impl DispatchTable {
         fn_path: S,
         fn_info: Arc<FunctionDefinition>,
     ) -> Option<Arc<FunctionDefinition>> {
-        self.functions.insert(fn_path.to_string(), fn_info)
+        self.insert_fn_with_kind(fn_path.to_string(), fn_info, DispatchTableEntryKind::Extern)
+    }
+
+    /// Inserts the `fn_info` for `fn_path` into the dispatch table as a
+    /// function that is provided by the runtime itself.
+    ///
+    /// If the dispatch table already contained this `fn_path`, the value is
+    /// updated, and the old value is returned.
+    pub fn insert_intrinsic<S: ToString>(
+        &mut self,
+        fn_path: S,
+        fn_info: Arc<FunctionDefinition>,
+    ) -> Option<Arc<FunctionDefinition>> {
+        self.insert_fn_with_kind(
+            fn_path.to_string(),
+            fn_info,
+            DispatchTableEntryKind::Intrinsic,
+        )
+    }
+
+    fn insert_fn_with_kind(
+        &mut self,
+        fn_path: String,
+        fn_info: Arc<FunctionDefinition>,
+        kind: DispatchTableEntryKind,
+    ) -> Option<Arc<FunctionDefinition>> {
+        let slot = self.slots.entry(fn_path.clone()).or_insert(SlotInfo {
+            kind,
+            initial_fn_ptr: fn_info.fn_ptr as usize,
+        });
+        slot.kind = kind;
+        self.functions.insert(fn_path, fn_info)
     }
 
     // /// Removes and returns the `fn_info` corresponding to `fn_path`, if it
@@ -61,7 +159,11 @@ impl DispatchTable {
             let fn_def = FunctionDefinition::try_from_abi(fn_def, type_table)
                 .expect("All types from a loaded assembly must exist in the type table.");
 
-            self.insert_fn(fn_def.prototype.name.clone(), Arc::new(fn_def));
+            self.insert_fn_with_kind(
+                fn_def.prototype.name.clone(),
+                Arc::new(fn_def),
+                DispatchTableEntryKind::Assembly,
+            );
         }
     }
 }
@@ -0,0 +1,275 @@
+use std::{fmt::Write, iter, ops::Range};
+
+use mun_abi as abi;
+use mun_compiler::{
+    diagnostics::{FileDiagnostic, Severity},
+    Config, Driver, PathOrInline, RelativePathBuf,
+};
+use mun_memory::Type;
+
+use crate::{
+    ArgumentReflection, Assembly, InvokeArgs, LinkError, Marshal, ReturnTypeReflection, Runtime,
+};
+
+/// The name of the function that is generated to evaluate an expression
+const EVAL_FN_NAME: &str = "__eval";
+
+/// The primitive types that can be used in an evaluated expression
+const PRIMITIVE_TYPE_NAMES: &[&str] = &[
+    "bool", "i8", "i16", "i32", "i64", "i128", "u8", "u16", "u32", "u64", "u128", "f32", "f64",
+];
+
+/// An error that might occur when evaluating an expression with
+/// [`Runtime::eval_expr`].
+#[derive(Debug, thiserror::Error)]
+pub enum EvalError {
+    /// A variable or the result has a type that cannot be used in an evaluated
+    /// expression
+    #[error("the type `{0}` cannot be used in an evaluated expression")]
+    UnsupportedType(String),
+    /// The expression contains errors. The ranges of the diagnostics are
+    /// relative to the start of the expression.
+    #[error("the expression contains errors: {}", display_diagnostics(.0))]
+    Compile(Vec<FileDiagnostic>),
+    /// Failed to build the assembly of the expression
+    #[error("failed to build the expression: {0}")]
+    Build(String),
+    /// Failed to link the assembly of the expression
+    #[error(transparent)]
+    Link(#[from] LinkError),
+    /// Failed to invoke the expression
+    #[error("failed to invoke the expression: {0}")]
+    Invoke(String),
+}
+
+fn display_diagnostics(diagnostics: &[FileDiagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.message.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The variables that are in scope of an expression that is evaluated with
+/// [`Runtime::eval_expr`]. This trait is implemented for tuples of up to and
+/// including 20 `(name, value)` pairs.
+pub trait EvalEnv {
+    /// The arguments with which the evaluated expression is invoked
+    type Args: InvokeArgs;
+
+    /// Returns the names and types of the variables
+    fn bindings(&self, runtime: &Runtime) -> Vec<(&str, Type)>;
+
+    /// Returns the values of the variables
+    fn to_args(&self) -> Self::Args;
+}
+
+// Implement `EvalEnv` for tuples up to and including 20 elements
+seq_macro::seq!(N in 0..=20 {#(
+seq_macro::seq!(I in 0..N {
+    #[allow(clippy::extra_unused_lifetimes)]
+    impl<'name, 'arg, #(T~I: ArgumentReflection + Marshal<'arg> + Clone,)*> EvalEnv for (#((&'name str, T~I),)*) {
+        type Args = (#(T~I,)*);
+
+        #[allow(unused_variables)]
+        fn bindings(&self, runtime: &Runtime) -> Vec<(&str, Type)> {
+            vec![#((self.I.0, self.I.1.type_info(runtime)),)*]
+        }
+
+        #[allow(clippy::unused_unit)]
+        fn to_args(&self) -> Self::Args {
+            (#(self.I.1.clone(),)*)
+        }
+    }
+});
+)*});
+
+/// Returns the name by which the Mun type with the specified runtime name is
+/// referred to in Mun code, if it can be used in an evaluated expression.
+fn mun_type_name(name: &str) -> Option<&'static str> {
+    if name == <() as abi::PrimitiveType>::name() {
+        return Some("()");
+    }
+
+    let name = name.strip_prefix("core::")?;
+    PRIMITIVE_TYPE_NAMES
+        .iter()
+        .find(|primitive| **primitive == name)
+        .copied()
+}
+
+/// Returns true if `name` can be used as a function name in Mun code.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl Runtime {
+    /// Evaluates the Mun expression `expr` and returns its value. The variables
+    /// of `env` are in scope of the expression, as are the functions of the
+    /// runtime, including the functions that were registered by the host.
+    ///
+    /// The expression is compiled into a temporary assembly that is linked
+    /// against the runtime, invoked, and unloaded again. The assemblies of the
+    /// runtime are not affected.
+    ///
+    /// Only primitive types can be used for the variables and the result.
+    ///
+    /// ```ignore
+    /// let result: i64 = runtime.eval_expr("a + b * 2", &(("a", 1i64), ("b", 2i64)))?;
+    /// ```
+    pub fn eval_expr<'r, T, E>(&'r self, expr: &str, env: &E) -> Result<T, EvalError>
+    where
+        T: ReturnTypeReflection + Marshal<'r> + 'r,
+        E: EvalEnv,
+    {
+        let return_type = mun_type_name(T::type_hint())
+            .ok_or_else(|| EvalError::UnsupportedType(T::type_hint().to_owned()))?;
+
+        let bindings = env.bindings(self);
+        let mut params = Vec::with_capacity(bindings.len());
+        for (name, ty) in bindings.iter() {
+            let ty_name = mun_type_name(ty.name())
+                .ok_or_else(|| EvalError::UnsupportedType(ty.name().to_owned()))?;
+            params.push(format!("{name}: {ty_name}"));
+        }
+
+        let (source, expr_range) = self.eval_source(expr, &params, &bindings, return_type);
+
+        let out_dir = tempfile::tempdir().map_err(|e| EvalError::Build(e.to_string()))?;
+        let config = Config {
+            out_dir: Some(out_dir.path().to_path_buf()),
+            ..Config::default()
+        };
+        let (mut driver, file_id) = Driver::with_file(
+            config,
+            PathOrInline::Inline {
+                rel_path: RelativePathBuf::from("mod.mun"),
+                contents: source,
+            },
+        )
+        .map_err(|e| EvalError::Build(e.to_string()))?;
+
+        let errors: Vec<_> = driver
+            .file_diagnostics(file_id)
+            .into_iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+            .map(|diagnostic| FileDiagnostic {
+                range: diagnostic.range.start.saturating_sub(expr_range.start)
+                    ..diagnostic.range.end.saturating_sub(expr_range.start),
+                ..diagnostic
+            })
+            .collect();
+        if !errors.is_empty() {
+            return Err(EvalError::Compile(errors));
+        }
+
+        driver
+            .write_file_assemblies(file_id, true)
+            .map_err(|e| EvalError::Build(e.to_string()))?;
+        let assembly_path = driver.assembly_output_path_from_file(file_id);
+
+        // Safety: the assembly was just produced by the compiler from the generated
+        // source, so its initialisation and termination routines are those of
+        // any other munlib.
+        let mut assembly = unsafe {
+            Assembly::load(
+                &assembly_path,
+                self.gc.clone(),
+                &abi::EntrySymbols::default(),
+            )
+        }
+        .map_err(LinkError::from)?;
+
+        // The resulting tables are only used to invoke the expression, the tables of
+        // the runtime remain untouched.
+        let (dispatch_table, _) = Assembly::link_all(
+            iter::once(&mut assembly),
+            &self.dispatch_table,
+            &self.type_table,
+        )?;
+        let function_info = dispatch_table
+            .get_fn(EVAL_FN_NAME)
+            .expect("the evaluated expression must be in the dispatch table");
+
+        let result = self
+            .invoke_definition(&function_info, EVAL_FN_NAME, env.to_args())
+            .map_err(|e| EvalError::Invoke(e.to_string()));
+
+        // Unload the assembly before its directory is removed
+        drop(dispatch_table);
+        drop(assembly);
+        drop(out_dir);
+
+        result
+    }
+
+    /// Generates the source of a module that evaluates `expr` in a function
+    /// with the specified `params`. The functions of the runtime are declared
+    /// as extern functions, unless they are shadowed by a binding or use
+    /// types that cannot be used in an evaluated expression.
+    ///
+    /// Returns the source and the range of `expr` within it.
+    fn eval_source(
+        &self,
+        expr: &str,
+        params: &[String],
+        bindings: &[(&str, Type)],
+        return_type: &str,
+    ) -> (String, Range<usize>) {
+        let mut fn_names: Vec<_> = self
+            .dispatch_table
+            .get_fn_names()
+            .filter(|name| {
+                *name != EVAL_FN_NAME
+                    && is_identifier(name)
+                    && !bindings.iter().any(|(binding, _)| binding == name)
+            })
+            .collect();
+        fn_names.sort_unstable();
+
+        let mut source = String::new();
+        for name in fn_names {
+            let signature = &self
+                .dispatch_table
+                .get_fn(name)
+                .expect("the function must be in the dispatch table")
+                .prototype
+                .signature;
+
+            let arg_types: Option<Vec<_>> = signature
+                .arg_types
+                .iter()
+                .map(|ty| mun_type_name(ty.name()))
+                .collect();
+            let (Some(arg_types), Some(return_type)) =
+                (arg_types, mun_type_name(signature.return_type.name()))
+            else {
+                continue;
+            };
+
+            let args = arg_types
+                .iter()
+                .enumerate()
+                .map(|(idx, ty)| format!("arg{idx}: {ty}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = writeln!(source, "extern fn {name}({args}) -> {return_type};");
+        }
+
+        let _ = writeln!(
+            source,
+            "pub fn {EVAL_FN_NAME}({}) -> {return_type} {{",
+            params.join(", ")
+        );
+        let start = source.len();
+        source.push_str(expr);
+        let expr_range = start..source.len();
+        source.push_str("\n}\n");
+
+        (source, expr_range)
+    }
+}
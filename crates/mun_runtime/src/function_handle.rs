@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use crate::function_info::FunctionDefinition;
+
+/// A handle to a function that was resolved through [`Runtime::resolve`].
+///
+/// Invoking a function through a handle with [`Runtime::invoke_handle`]
+/// bypasses the name lookup in the dispatch table. A handle is only valid for
+/// the runtime and the generation it was resolved in; after the runtime
+/// reloads its assemblies the handle becomes stale and invoking it results in
+/// an error instead of calling into unloaded code.
+///
+/// [`Runtime::resolve`]: crate::Runtime::resolve
+/// [`Runtime::invoke_handle`]: crate::Runtime::invoke_handle
+#[derive(Clone)]
+pub struct FunctionHandle {
+    pub(crate) definition: Arc<FunctionDefinition>,
+    pub(crate) runtime_id: u64,
+    pub(crate) generation: u64,
+}
+
+impl FunctionHandle {
+    /// Returns the name of the function this handle refers to.
+    pub fn name(&self) -> &str {
+        &self.definition.prototype.name
+    }
+
+    /// Returns the function definition this handle refers to.
+    pub fn definition(&self) -> &FunctionDefinition {
+        &self.definition
+    }
+}
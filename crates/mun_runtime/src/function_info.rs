@@ -11,6 +11,8 @@ pub struct FunctionDefinition {
     pub prototype: FunctionPrototype,
     /// Function pointer
     pub fn_ptr: *const c_void,
+    /// Function attributes, if the assembly was compiled with them
+    pub attributes: Option<abi::FunctionAttributes>,
 }
 
 impl FunctionDefinition {
@@ -39,8 +41,16 @@ impl FunctionDefinition {
         Ok(Self {
             prototype,
             fn_ptr: fn_def.fn_ptr,
+            attributes: fn_def.attributes().copied(),
         })
     }
+
+    /// Returns the effective attributes of the function, or `None` if the
+    /// function was not compiled with function attributes, e.g. because it
+    /// was defined by the host.
+    pub fn attributes(&self) -> Option<&abi::FunctionAttributes> {
+        self.attributes.as_ref()
+    }
 }
 
 /// A linked version of [`mun_abi::FunctionPrototype`] that has resolved all
@@ -74,6 +84,8 @@ impl FunctionPrototype {
 pub struct FunctionSignature {
     /// Argument types
     pub arg_types: Vec<Type>,
+    /// Argument names, if the assembly was compiled with them
+    pub arg_names: Option<Vec<String>>,
     /// Return type
     pub return_type: Type,
 }
@@ -98,11 +110,22 @@ impl FunctionSignature {
             .find_type_info_by_id(&fn_sig.return_type)
             .ok_or_else(|| TryFromAbiError::UnknownTypeId(fn_sig.return_type.clone()))?;
 
+        let arg_names = fn_sig
+            .arg_names()
+            .map(|arg_names| arg_names.map(ToOwned::to_owned).collect());
+
         Ok(Self {
             arg_types,
+            arg_names,
             return_type,
         })
     }
+
+    /// Returns the names of the arguments, or `None` if the assembly was
+    /// compiled without argument names. Unnamed arguments are named `_`.
+    pub fn arg_names(&self) -> Option<&[String]> {
+        self.arg_names.as_deref()
+    }
 }
 
 /// A value-to-`FunctionDefinition` conversion that consumes the input value.
@@ -126,9 +149,11 @@ macro_rules! into_function_info_impl {
                             name: name.into(),
                             signature: FunctionSignature {
                                 arg_types: vec![$(<$T as mun_memory::HasStaticType>::type_info().clone(),)*],
+                                arg_names: None,
                                 return_type: <R as mun_memory::HasStaticType>::type_info().clone(),
                             }
-                        }
+                        },
+                        attributes: None,
                     }
                 }
             }
@@ -193,10 +218,12 @@ impl FunctionDefinitionBuilder {
                 name: self.name,
                 signature: FunctionSignature {
                     arg_types: self.arg_types,
+                    arg_names: None,
                     return_type: self.return_type,
                 },
             },
             fn_ptr: self.fn_ptr,
+            attributes: None,
         })
     }
 }
@@ -1,6 +1,34 @@
-use mun_memory::gc;
+use mun_memory::gc::{self, Observer};
+
+use crate::{
+    alloc_report::AllocationTracker, call_trace::CallTraceRecorder, heap_limit::HeapLimitTracker,
+    timing::TimingRecorder,
+};
+
+/// Observes the garbage collector used by the `Runtime`. It also holds the
+/// debugging information that assemblies report through their allocator
+/// handle.
+#[derive(Default)]
+pub struct RuntimeObserver {
+    /// The source locations of live allocations
+    pub allocations: AllocationTracker,
+    /// The execution time of instrumented functions
+    pub timings: TimingRecorder,
+    /// The ring of the most recent calls of instrumented functions
+    pub call_trace: CallTraceRecorder,
+    /// Whether Mun code is running and exceeded the maximum heap size
+    pub heap_limit: HeapLimitTracker,
+}
+
+impl Observer for RuntimeObserver {
+    type Event = gc::Event;
+
+    fn event(&self, event: gc::Event) {
+        self.allocations.event(event);
+    }
+}
 
 /// Defines the garbage collector used by the `Runtime`.
-pub type GarbageCollector = gc::MarkSweep<gc::NoopObserver<gc::Event>>;
+pub type GarbageCollector = gc::MarkSweep<RuntimeObserver>;
 
 pub type GcRootPtr = gc::GcRootPtr<GarbageCollector>;
@@ -0,0 +1,73 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use mun_memory::gc::HeapLimitExceeded;
+use parking_lot::Mutex;
+
+use crate::garbage_collector::GarbageCollector;
+
+/// Tracks whether Mun code is running and whether it exceeded the maximum heap
+/// size.
+///
+/// Compiled Mun code does not root the objects that it references from its
+/// stack, so garbage can only be collected while no Mun code is running.
+/// Allocations that cannot fail, like those of compiled code, grow the heap
+/// beyond its limit instead. The error is recorded and returned by the
+/// invocation during which it occurred.
+#[derive(Default)]
+pub struct HeapLimitTracker {
+    /// The number of invocations of Mun functions that have not returned yet
+    active_invocations: AtomicUsize,
+    /// The first allocation that exceeded the maximum heap size since the
+    /// error was last taken
+    exceeded: Mutex<Option<HeapLimitExceeded>>,
+}
+
+/// Marks that Mun code is running until it is dropped.
+pub struct InvocationGuard<'a>(&'a HeapLimitTracker);
+
+impl Drop for InvocationGuard<'_> {
+    fn drop(&mut self) {
+        self.0.active_invocations.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl HeapLimitTracker {
+    /// Marks that Mun code runs until the returned guard is dropped.
+    pub fn enter(&self) -> InvocationGuard<'_> {
+        self.active_invocations.fetch_add(1, Ordering::AcqRel);
+        InvocationGuard(self)
+    }
+
+    /// Returns true if garbage can be collected, i.e. if no Mun code is
+    /// running.
+    pub fn can_collect(&self) -> bool {
+        self.active_invocations.load(Ordering::Acquire) == 0
+    }
+
+    /// Records that an allocation exceeded the maximum heap size. Only the
+    /// first error is kept until it is taken.
+    pub fn record(&self, error: HeapLimitExceeded) {
+        self.exceeded.lock().get_or_insert(error);
+    }
+
+    /// Takes the error that was recorded, if any.
+    pub fn take(&self) -> Option<HeapLimitExceeded> {
+        self.exceeded.lock().take()
+    }
+}
+
+/// Allocates an object with `alloc`. If it does not fit within the maximum
+/// heap size and no Mun code is running, garbage is collected and the
+/// allocation is retried.
+pub(crate) fn alloc_or_collect<T>(
+    gc: &GarbageCollector,
+    alloc: impl Fn(&GarbageCollector) -> Result<T, HeapLimitExceeded>,
+) -> Result<T, HeapLimitExceeded> {
+    alloc(gc).or_else(|error| {
+        if gc.observer().heap_limit.can_collect() && gc.collect() {
+            alloc(gc)
+        } else {
+            Err(error)
+        }
+    })
+}
@@ -8,16 +8,25 @@ mod assembly;
 #[macro_use]
 mod garbage_collector;
 mod adt;
+mod alloc_report;
 mod array;
+mod call_trace;
 mod dispatch_table;
+#[cfg(feature = "eval")]
+mod eval;
+mod function_handle;
 mod function_info;
+mod heap_limit;
 mod marshal;
 mod reflection;
+mod slice;
+mod timing;
 mod utils;
+mod warmup;
 
 use std::{
     cmp,
-    collections::{BTreeMap, HashMap, VecDeque},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     ffi,
     ffi::c_void,
     fmt::{Debug, Display, Formatter},
@@ -25,6 +34,7 @@ use std::{
     path::{Path, PathBuf},
     ptr::NonNull,
     sync::{
+        atomic::{AtomicU64, Ordering},
         mpsc::{channel, Receiver},
         Arc,
     },
@@ -32,27 +42,40 @@ use std::{
 
 use assembly::LoadError;
 use dispatch_table::DispatchTable;
-use garbage_collector::GarbageCollector;
+pub use dispatch_table::{DispatchTableEntry, DispatchTableEntryKind};
+use garbage_collector::{GarbageCollector, GcRootPtr};
+use itertools::Itertools;
 use log::{debug, error, info};
 use mun_abi as abi;
 use mun_memory::{
-    gc::{self, Array, GcRuntime},
+    gc::{self, Array, GcPtr, GcRuntime},
     type_table::TypeTable,
+    TypeKind,
 };
 // Re-export some useful types so crates dont have to depend on mun_memory as well.
-pub use mun_memory::{Field, FieldData, HasStaticType, PointerType, StructType, Type};
+pub use mun_memory::{
+    gc::{GcConfig, HeapLimitExceeded, HeapSnapshot},
+    Field, FieldData, HasStaticType, PointerType, StructType, Type,
+};
 use mun_project::LOCKFILE_NAME;
 use notify::{event::ModifyKind, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 
+#[cfg(feature = "eval")]
+pub use crate::eval::{EvalEnv, EvalError};
 pub use crate::{
     adt::{RootedStruct, StructRef},
+    alloc_report::{AllocationReport, AllocationSite},
     array::{ArrayRef, RawArray, RootedArray},
     assembly::{Assembly, LinkError, LinkFunctionsError},
+    function_handle::FunctionHandle,
     function_info::{
         FunctionDefinition, FunctionPrototype, FunctionSignature, IntoFunctionDefinition,
     },
     marshal::Marshal,
     reflection::{ArgumentReflection, ReturnTypeReflection},
+    slice::Slice,
+    timing::FunctionTiming,
+    warmup::{WarmupError, WarmupOptions, WarmupReport},
 };
 
 /// Options for the construction of a [`Runtime`].
@@ -63,6 +86,16 @@ pub struct RuntimeOptions {
     pub type_table: TypeTable,
     /// Custom user injected functions
     pub user_functions: Vec<FunctionDefinition>,
+    /// Configuration of the garbage collected heap
+    pub gc_config: GcConfig,
+    /// The names of the symbols through which assemblies are loaded
+    pub entry_symbols: abi::EntrySymbols,
+    /// Whether the values of function-local statics are preserved when an
+    /// assembly is hot reloaded
+    pub preserve_statics: bool,
+    /// The ABI fingerprint that the entry point library must have, if it is
+    /// verified
+    pub expected_abi_fingerprint: Option<abi::AbiFingerprint>,
 }
 
 /// Retrieve the allocator using the provided handle.
@@ -85,6 +118,15 @@ unsafe fn get_type_info(type_handle: *const ffi::c_void) -> Type {
     Type::from_raw(type_handle)
 }
 
+/// Records that compiled Mun code tried to allocate more memory than fits
+/// within the maximum heap size. Compiled code cannot recover from a failed
+/// allocation and garbage cannot be collected while it runs, so the allocation
+/// grows the heap beyond its limit and the invocation fails once it returns.
+fn record_heap_limit(allocator: &GarbageCollector, error: HeapLimitExceeded) {
+    error!("{error}");
+    allocator.observer().heap_limit.record(error);
+}
+
 extern "C" fn new(
     type_handle: *const ffi::c_void,
     alloc_handle: *mut ffi::c_void,
@@ -101,7 +143,34 @@ extern "C" fn new(
 
     // Safety: the Mun Compiler guarantees that `new` is never called with
     // `ptr::null()`.
-    let handle = allocator.as_ref().alloc(&type_info);
+    let handle = allocator.alloc(&type_info).unwrap_or_else(|error| {
+        record_heap_limit(&allocator, error);
+        allocator.alloc_beyond_limit(&type_info, true)
+    });
+
+    handle.into()
+}
+
+extern "C" fn new_uninit(
+    type_handle: *const ffi::c_void,
+    alloc_handle: *mut ffi::c_void,
+) -> *const *mut ffi::c_void {
+    // SAFETY: The runtime always constructs and uses `Arc<TypeInfo>::into_raw` to
+    // set the type type handles in the type LUT.
+    let type_info = ManuallyDrop::new(unsafe { get_type_info(type_handle) });
+
+    // Safety: `new_uninit` is only called from within Mun assemblies' core logic,
+    // so we are guaranteed that the `Runtime` and its `GarbageCollector` still
+    // exist if this function is called, and will continue to do so for the
+    // duration of this function.
+    let allocator = ManuallyDrop::new(unsafe { get_allocator(alloc_handle) });
+
+    // Safety: the Mun Compiler guarantees that `new_uninit` is never called with
+    // `ptr::null()` and that the object is initialized before it is used.
+    let handle = allocator.alloc_uninit(&type_info).unwrap_or_else(|error| {
+        record_heap_limit(&allocator, error);
+        allocator.alloc_beyond_limit(&type_info, false)
+    });
 
     handle.into()
 }
@@ -121,11 +190,121 @@ extern "C" fn new_array(
     // of this function.
     let allocator = ManuallyDrop::new(unsafe { get_allocator(alloc_handle) });
 
-    let handle = allocator.as_ref().alloc_array(&type_info, length);
+    let handle = allocator
+        .alloc_array(&type_info, length)
+        .unwrap_or_else(|error| {
+            record_heap_limit(&allocator, error);
+            allocator.alloc_array_beyond_limit(&type_info, length)
+        });
 
     handle.as_raw().into()
 }
 
+extern "C" fn new_with_location(
+    type_handle: *const ffi::c_void,
+    alloc_handle: *mut ffi::c_void,
+    location: *const u8,
+) -> *const *mut ffi::c_void {
+    let handle = new(type_handle, alloc_handle);
+
+    // Safety: `new_with_location` is only called from within Mun assemblies' core
+    // logic, so the allocator still exists for the duration of this function.
+    let allocator = ManuallyDrop::new(unsafe { get_allocator(alloc_handle) });
+
+    // Safety: the Mun Compiler guarantees that `location` is a null-terminated
+    // string.
+    let location = unsafe { ffi::CStr::from_ptr(location.cast()) };
+    allocator
+        .observer()
+        .allocations
+        .record(handle.into(), &location.to_string_lossy());
+
+    handle
+}
+
+extern "C" fn new_array_with_location(
+    type_handle: *const ffi::c_void,
+    length: usize,
+    alloc_handle: *mut ffi::c_void,
+    location: *const u8,
+) -> *const *mut ffi::c_void {
+    let handle = new_array(type_handle, length, alloc_handle);
+
+    // Safety: `new_array_with_location` is only called from within Mun
+    // assemblies' core logic, so the allocator still exists for the duration of
+    // this function.
+    let allocator = ManuallyDrop::new(unsafe { get_allocator(alloc_handle) });
+
+    // Safety: the Mun Compiler guarantees that `location` is a null-terminated
+    // string.
+    let location = unsafe { ffi::CStr::from_ptr(location.cast()) };
+    allocator
+        .observer()
+        .allocations
+        .record(handle.into(), &location.to_string_lossy());
+
+    handle
+}
+
+extern "C" fn timing_enter(name: *const u8, alloc_handle: *mut ffi::c_void) {
+    // Safety: `timing_enter` is only called from within Mun assemblies' core
+    // logic, so the allocator still exists for the duration of this function.
+    let allocator = ManuallyDrop::new(unsafe { get_allocator(alloc_handle) });
+
+    // Safety: the Mun Compiler guarantees that `name` is a null-terminated
+    // string.
+    let name = unsafe { ffi::CStr::from_ptr(name.cast()) };
+    allocator.observer().timings.enter(&name.to_string_lossy());
+}
+
+extern "C" fn timing_exit(name: *const u8, alloc_handle: *mut ffi::c_void) {
+    // Safety: `timing_exit` is only called from within Mun assemblies' core
+    // logic, so the allocator still exists for the duration of this function.
+    let allocator = ManuallyDrop::new(unsafe { get_allocator(alloc_handle) });
+
+    // Safety: the Mun Compiler guarantees that `name` is a null-terminated
+    // string.
+    let name = unsafe { ffi::CStr::from_ptr(name.cast()) };
+    allocator.observer().timings.exit(&name.to_string_lossy());
+}
+
+extern "C" fn root(obj: *const *mut ffi::c_void, alloc_handle: *mut ffi::c_void) {
+    // Safety: `root` is only called from within Mun assemblies' core logic, so
+    // the allocator still exists for the duration of this function.
+    let allocator = ManuallyDrop::new(unsafe { get_allocator(alloc_handle) });
+    allocator.as_ref().root(obj.into());
+}
+
+extern "C" fn unroot(obj: *const *mut ffi::c_void, alloc_handle: *mut ffi::c_void) {
+    // Safety: `unroot` is only called from within Mun assemblies' core logic, so
+    // the allocator still exists for the duration of this function.
+    let allocator = ManuallyDrop::new(unsafe { get_allocator(alloc_handle) });
+    allocator.as_ref().unroot(obj.into());
+}
+
+/// Checks that the assembly loaded for the library at `library_path` has the
+/// expected ABI fingerprint, if it is the entry point library and its
+/// fingerprint is verified.
+fn verify_abi_fingerprint(
+    expected: Option<&(PathBuf, abi::AbiFingerprint)>,
+    library_path: &Path,
+    assembly: &Assembly,
+) -> Result<(), LoadError> {
+    match expected {
+        Some((path, expected)) if path == library_path => {
+            let actual = assembly.abi_fingerprint();
+            if actual != *expected {
+                return Err(LoadError::AbiChanged {
+                    expected: *expected,
+                    actual,
+                });
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
 /// A builder for the [`Runtime`].
 pub struct RuntimeBuilder {
     options: RuntimeOptions,
@@ -140,6 +319,10 @@ impl RuntimeBuilder {
                 library_path: library_path.into(),
                 type_table: TypeTable::default(),
                 user_functions: Vec::default(),
+                gc_config: GcConfig::default(),
+                entry_symbols: abi::EntrySymbols::default(),
+                preserve_statics: false,
+                expected_abi_fingerprint: None,
             },
         }
     }
@@ -154,6 +337,69 @@ impl RuntimeBuilder {
         self
     }
 
+    /// Sets the configuration of the garbage collected heap, e.g. to limit
+    /// the amount of memory that scripts can allocate.
+    ///
+    /// Compiled Mun code cannot recover from a failed allocation, so if it
+    /// allocates more memory than the maximum heap size allows, the heap grows
+    /// beyond its limit and the invocation fails with an [`InvokeErr`] once
+    /// the function returns. Garbage is collected before the next invocation.
+    pub fn gc_config(mut self, config: GcConfig) -> Self {
+        self.options.gc_config = config;
+        self
+    }
+
+    /// Sets the entry symbol of the assemblies to load. This must match the
+    /// entry symbol that the assemblies were built with, e.g. with
+    /// `mun build --entry-symbol`.
+    pub fn entry_symbol(mut self, entry_symbol: impl AsRef<str>) -> Self {
+        self.options.entry_symbols = abi::EntrySymbols::with_version(
+            entry_symbol.as_ref(),
+            self.options.entry_symbols.version(),
+        );
+        self
+    }
+
+    /// Sets the symbol version of the assemblies to load. This must match the
+    /// version that the assemblies were built with, e.g. with
+    /// `mun build --symbol-version`.
+    pub fn symbol_version(mut self, version: impl AsRef<str>) -> Self {
+        self.options.entry_symbols = abi::EntrySymbols::with_version(
+            self.options.entry_symbols.entry_symbol(),
+            Some(version.as_ref()),
+        );
+        self
+    }
+
+    /// Sets whether the values of function-local statics, e.g. `static mut N:
+    /// i64 = 0;`, are preserved when an assembly is hot reloaded.
+    ///
+    /// By default, the statics of a reloaded assembly start out
+    /// uninitialized, so their initializers are evaluated again the first
+    /// time they are reached. When enabled, a static of the reloaded assembly
+    /// takes over the value of the static of the previous assembly with the
+    /// same name (prefixed with the path of its function) and type, and its
+    /// initializer is not evaluated again. Statics that were renamed, whose
+    /// type changed, or that were not initialized yet, still start out
+    /// uninitialized.
+    pub fn preserve_statics(mut self, preserve_statics: bool) -> Self {
+        self.options.preserve_statics = preserve_statics;
+        self
+    }
+
+    /// Sets the ABI fingerprint that the entry point library must have, see
+    /// [`abi::AbiFingerprint`]. The runtime refuses to load or hot reload the
+    /// library if its fingerprint differs, i.e. if the signatures of its
+    /// functions or the layouts of its types changed since the host was
+    /// built.
+    ///
+    /// By default the fingerprint is not verified, such that hot reloading
+    /// changes to the ABI during development is not blocked.
+    pub fn expected_abi_fingerprint(mut self, fingerprint: abi::AbiFingerprint) -> Self {
+        self.options.expected_abi_fingerprint = Some(fingerprint);
+        self
+    }
+
     /// Constructs a [`Runtime`] with the builder's options.
     ///
     /// # Safety
@@ -186,6 +432,34 @@ pub enum InitError {
     Watcher(#[from] notify::Error),
 }
 
+/// An error that occurs when rebinding an extern function with
+/// [`Runtime::rebind_extern`].
+#[derive(Debug, thiserror::Error)]
+pub enum RebindError {
+    /// No extern function with the specified name was registered
+    #[error("no extern function named `{0}` was registered")]
+    UnknownExtern(String),
+    /// The signature of the new function differs from the signature of the
+    /// extern function it replaces
+    #[error("mismatched function signature. Expected: `{expected}`, found: `{found}`")]
+    MismatchedSignature { expected: String, found: String },
+}
+
+/// An error that occurs when restoring a [`HeapSnapshot`].
+#[derive(Debug, thiserror::Error)]
+pub enum RestoreError {
+    /// The layout of a type changed since the snapshot was taken, e.g. because
+    /// of a hot reload
+    #[error("the layout of type `{0}` changed since the snapshot was taken")]
+    TypeChanged(String),
+    /// A type was removed since the snapshot was taken
+    #[error("the type `{0}` no longer exists")]
+    TypeRemoved(String),
+}
+
+/// The identifier of the next runtime that is constructed
+static NEXT_RUNTIME_ID: AtomicU64 = AtomicU64::new(0);
+
 /// A runtime for the Mun language.
 ///
 /// # Logging
@@ -207,6 +481,22 @@ pub struct Runtime {
     watcher_rx: Receiver<notify::Result<Event>>,
     renamed_files: HashMap<usize, PathBuf>,
     gc: Arc<GarbageCollector>,
+    /// The names of the symbols through which assemblies are loaded
+    entry_symbols: abi::EntrySymbols,
+    /// Uniquely identifies the runtime within the process. Used to detect
+    /// [`FunctionHandle`]s that were resolved by another runtime.
+    id: u64,
+    /// Incremented every time the assemblies are relinked. Used to detect stale
+    /// [`FunctionHandle`]s.
+    generation: u64,
+    /// Whether the values of function-local statics are preserved when an
+    /// assembly is hot reloaded
+    preserve_statics: bool,
+    /// The state of the previous calls to [`Runtime::warmup`]
+    warmup: warmup::WarmupState,
+    /// The canonical path of the entry point library and the ABI fingerprint
+    /// it must have, if it is verified
+    expected_abi_fingerprint: Option<(PathBuf, abi::AbiFingerprint)>,
 }
 
 impl Runtime {
@@ -234,31 +524,81 @@ impl Runtime {
     /// unloaded.
     ///
     /// See [`Assembly::load`] for more information.
-    pub unsafe fn new(mut options: RuntimeOptions) -> Result<Runtime, InitError> {
+    pub unsafe fn new(options: RuntimeOptions) -> Result<Runtime, InitError> {
         let (tx, rx) = channel();
 
         let mut dispatch_table = DispatchTable::default();
         let type_table = options.type_table;
 
         // Add internal functions
-        options.user_functions.push(IntoFunctionDefinition::into(
-            new as extern "C" fn(*const ffi::c_void, *mut ffi::c_void) -> *const *mut ffi::c_void,
-            "new",
-        ));
-
-        options.user_functions.push(IntoFunctionDefinition::into(
-            new_array
-                as extern "C" fn(
+        let intrinsics: Vec<FunctionDefinition> = vec![
+            IntoFunctionDefinition::into(
+                new as extern "C" fn(
                     *const ffi::c_void,
-                    usize,
                     *mut ffi::c_void,
                 ) -> *const *mut ffi::c_void,
-            "new_array",
-        ));
+                "new",
+            ),
+            IntoFunctionDefinition::into(
+                new_uninit
+                    as extern "C" fn(
+                        *const ffi::c_void,
+                        *mut ffi::c_void,
+                    ) -> *const *mut ffi::c_void,
+                "new_uninit",
+            ),
+            IntoFunctionDefinition::into(
+                new_array
+                    as extern "C" fn(
+                        *const ffi::c_void,
+                        usize,
+                        *mut ffi::c_void,
+                    ) -> *const *mut ffi::c_void,
+                "new_array",
+            ),
+            IntoFunctionDefinition::into(
+                new_with_location
+                    as extern "C" fn(
+                        *const ffi::c_void,
+                        *mut ffi::c_void,
+                        *const u8,
+                    ) -> *const *mut ffi::c_void,
+                "new_with_location",
+            ),
+            IntoFunctionDefinition::into(
+                new_array_with_location
+                    as extern "C" fn(
+                        *const ffi::c_void,
+                        usize,
+                        *mut ffi::c_void,
+                        *const u8,
+                    ) -> *const *mut ffi::c_void,
+                "new_array_with_location",
+            ),
+            IntoFunctionDefinition::into(
+                timing_enter as extern "C" fn(*const u8, *mut ffi::c_void),
+                "timing_enter",
+            ),
+            IntoFunctionDefinition::into(
+                timing_exit as extern "C" fn(*const u8, *mut ffi::c_void),
+                "timing_exit",
+            ),
+            IntoFunctionDefinition::into(
+                root as extern "C" fn(*const *mut ffi::c_void, *mut ffi::c_void),
+                "root",
+            ),
+            IntoFunctionDefinition::into(
+                unroot as extern "C" fn(*const *mut ffi::c_void, *mut ffi::c_void),
+                "unroot",
+            ),
+        ];
 
         options.user_functions.into_iter().for_each(|fn_def| {
             dispatch_table.insert_fn(fn_def.prototype.name.clone(), Arc::new(fn_def));
         });
+        intrinsics.into_iter().for_each(|fn_def| {
+            dispatch_table.insert_intrinsic(fn_def.prototype.name.clone(), Arc::new(fn_def));
+        });
 
         let watcher: RecommendedWatcher = notify::recommended_watcher(move |res| {
             tx.send(res).expect("Failed to send filesystem event.");
@@ -271,7 +611,21 @@ impl Runtime {
             watcher,
             watcher_rx: rx,
             renamed_files: HashMap::new(),
-            gc: Arc::new(self::garbage_collector::GarbageCollector::default()),
+            gc: Arc::new(self::garbage_collector::GarbageCollector::with_config(
+                options.gc_config,
+            )),
+            entry_symbols: options.entry_symbols,
+            id: NEXT_RUNTIME_ID.fetch_add(1, Ordering::Relaxed),
+            generation: 0,
+            preserve_statics: options.preserve_statics,
+            warmup: warmup::WarmupState::default(),
+            expected_abi_fingerprint: options.expected_abi_fingerprint.map(|fingerprint| {
+                let library_path = options
+                    .library_path
+                    .canonicalize()
+                    .unwrap_or_else(|_| options.library_path.clone());
+                (library_path, fingerprint)
+            }),
         };
 
         runtime.add_assembly(&options.library_path)?;
@@ -315,7 +669,12 @@ impl Runtime {
                 continue;
             }
 
-            let assembly = Assembly::load(&library_path, self.gc.clone())?;
+            let assembly = Assembly::load(&library_path, self.gc.clone(), &self.entry_symbols)?;
+            verify_abi_fingerprint(
+                self.expected_abi_fingerprint.as_ref(),
+                &library_path,
+                &assembly,
+            )?;
 
             let parent = library_path.parent().expect("Invalid library path");
             let extension = library_path.extension();
@@ -358,6 +717,95 @@ impl Runtime {
         self.dispatch_table.get_fn(function_name)
     }
 
+    /// Resolves the function called `function_name` into a [`FunctionHandle`]
+    /// that can be invoked through [`Runtime::invoke_handle`] without looking
+    /// up the name again.
+    ///
+    /// The handle becomes stale when the runtime reloads its assemblies, after
+    /// which the function has to be resolved again.
+    pub fn resolve(&self, function_name: &str) -> Option<FunctionHandle> {
+        self.dispatch_table
+            .get_fn(function_name)
+            .map(|definition| FunctionHandle {
+                definition,
+                runtime_id: self.id,
+                generation: self.generation,
+            })
+    }
+
+    /// Returns whether `handle` was resolved by this runtime and still refers
+    /// to a function of the currently loaded assemblies.
+    pub fn is_handle_valid(&self, handle: &FunctionHandle) -> bool {
+        handle.runtime_id == self.id && handle.generation == self.generation
+    }
+
+    /// Returns a snapshot of the entries of the dispatch table through which
+    /// calls between functions are resolved, ordered by name. This includes
+    /// the functions of all loaded assemblies, as well as the functions that
+    /// were registered by the host.
+    ///
+    /// Assemblies are only reloaded by [`Runtime::update`], which requires
+    /// exclusive access to the runtime, so the snapshot always reflects a
+    /// consistent state of the dispatch table.
+    pub fn dispatch_table_entries(&self) -> Vec<DispatchTableEntry> {
+        self.dispatch_table.entries()
+    }
+
+    /// Replaces the extern function called `name`, that was registered by the
+    /// host, with `func`, without reloading any assembly. Calls from loaded
+    /// assemblies are dispatched to `func` from then on.
+    ///
+    /// The signature of `func` must match the signature of the function it
+    /// replaces, which was validated against the extern declarations of the
+    /// assemblies when they were linked. Functions that are provided by the
+    /// runtime itself cannot be rebound.
+    ///
+    /// Rebinding requires exclusive access to the runtime, so no call through
+    /// the runtime can be in flight while the dispatch table is updated.
+    /// Handles that were resolved before are invalidated.
+    pub fn rebind_extern<F: IntoFunctionDefinition>(
+        &mut self,
+        name: &str,
+        func: F,
+    ) -> Result<(), RebindError> {
+        let existing_fn_def = self
+            .dispatch_table
+            .get_fn(name)
+            .filter(|_| self.dispatch_table.kind(name) == Some(DispatchTableEntryKind::Extern))
+            .ok_or_else(|| RebindError::UnknownExtern(name.to_owned()))?;
+
+        let fn_def = func.into(name);
+        let expected = &existing_fn_def.prototype.signature;
+        let found = &fn_def.prototype.signature;
+        if expected.arg_types != found.arg_types || expected.return_type != found.return_type {
+            let format_signature = |signature: &FunctionSignature| {
+                format!(
+                    "{name}({}) -> {}",
+                    signature.arg_types.iter().map(Type::name).join(", "),
+                    signature.return_type.name()
+                )
+            };
+            return Err(RebindError::MismatchedSignature {
+                expected: format_signature(expected),
+                found: format_signature(found),
+            });
+        }
+
+        // Patch the dispatch table of every assembly that calls the function
+        for assembly in self.assemblies.values_mut() {
+            for (dispatch_ptr, fn_prototype) in assembly.info_mut().dispatch_table.iter_mut() {
+                if fn_prototype.name() == name {
+                    *dispatch_ptr = fn_def.fn_ptr;
+                }
+            }
+        }
+
+        self.dispatch_table.insert_fn(name, Arc::new(fn_def));
+        self.generation += 1;
+
+        Ok(())
+    }
+
     /// For a given `fn_name`, find the most similar name in `fn_names`
     fn find_best_match_for_fn_name<'a>(
         fn_name: &'a str,
@@ -394,6 +842,11 @@ impl Runtime {
         self.type_table.find_type_info_by_id(type_id)
     }
 
+    /// Returns an iterator over all assemblies that are currently loaded.
+    pub fn assemblies(&self) -> impl Iterator<Item = &Assembly> {
+        self.assemblies.values()
+    }
+
     /// Updates the state of the runtime. This includes checking for file
     /// changes, and reloading compiled assemblies.
     /// # Safety
@@ -438,7 +891,13 @@ impl Runtime {
                     continue;
                 }
 
-                let assembly = Assembly::load(&new_path, runtime.gc.clone())?;
+                let assembly =
+                    Assembly::load(&new_path, runtime.gc.clone(), &runtime.entry_symbols)?;
+                verify_abi_fingerprint(
+                    runtime.expected_abi_fingerprint.as_ref(),
+                    &old_path,
+                    &assembly,
+                )?;
 
                 let parent = new_path.parent().expect("Invalid library path");
                 let extension = new_path.extension();
@@ -466,6 +925,7 @@ impl Runtime {
                 &mut runtime.assemblies,
                 &runtime.dispatch_table,
                 &runtime.type_table,
+                runtime.preserve_statics,
             )
         }
 
@@ -518,6 +978,7 @@ impl Runtime {
                         self.dispatch_table = dispatch_table;
                         self.type_table = type_table;
                         self.assemblies_to_relink.clear();
+                        self.generation += 1;
 
                         return true;
                     }
@@ -549,7 +1010,85 @@ impl Runtime {
         self.gc.stats()
     }
 
+    /// Returns the live allocations grouped by the source location at which
+    /// they were allocated. Only assemblies that were compiled with allocation
+    /// debugging enabled record source locations; the report is empty
+    /// otherwise.
+    pub fn alloc_report(&self) -> AllocationReport {
+        self.gc.observer().allocations.report()
+    }
+
+    /// Returns the accumulated execution time of every function that was
+    /// called, ordered by descending total time. Only assemblies that were
+    /// compiled with timing instrumentation report the execution time of their
+    /// functions; the timings are empty otherwise.
+    ///
+    /// The instrumentation perturbs the performance of the functions it
+    /// measures, see [`FunctionTiming`] for how the time of nested and
+    /// recursive calls is accounted for.
+    pub fn timings(&self) -> Vec<FunctionTiming> {
+        self.gc.observer().timings.timings()
+    }
+
+    /// Returns the full names of the most recently called functions, ordered
+    /// from the oldest to the most recent call. Only assemblies that were
+    /// compiled with a call trace ring record their calls, the ring holds at
+    /// least as many calls as the largest capacity that any of them requested.
+    /// The call trace is empty otherwise.
+    ///
+    /// This is intended to be dumped by a host after a crash. Calls of
+    /// assemblies that were hot reloaded in the meantime are still resolved.
+    pub fn call_trace(&self) -> Vec<String> {
+        self.gc.observer().call_trace.calls()
+    }
+
+    /// Takes a snapshot of all objects that are reachable from rooted objects.
+    pub fn snapshot(&self) -> HeapSnapshot {
+        self.gc.snapshot()
+    }
+
+    /// Restores all objects stored in `snapshot`. Objects that are still alive
+    /// are restored in place, so existing handles to them remain valid.
+    /// Objects that were collected in the meantime are reallocated.
+    ///
+    /// Returns the objects that were rooted when the snapshot was taken, keyed
+    /// by their handle in the snapshot. The returned roots keep the restored
+    /// objects alive; dropping them allows the objects to be collected.
+    ///
+    /// Returns an error and leaves the heap untouched if the type of an object
+    /// in the snapshot changed since the snapshot was taken.
+    pub fn restore(
+        &self,
+        snapshot: &HeapSnapshot,
+    ) -> Result<HashMap<GcPtr, GcRootPtr>, RestoreError> {
+        let types: HashSet<&Type> = snapshot.types().collect();
+        for ty in types {
+            self.check_snapshot_type(ty)?;
+        }
+
+        Ok(self.gc.restore(snapshot))
+    }
+
+    /// Checks whether `ty` still matches the type known to the runtime.
+    fn check_snapshot_type(&self, ty: &Type) -> Result<(), RestoreError> {
+        match ty.kind() {
+            TypeKind::Primitive(_) => Ok(()),
+            TypeKind::Pointer(pointer) => self.check_snapshot_type(&pointer.pointee()),
+            TypeKind::Array(array) => self.check_snapshot_type(&array.element_type()),
+            TypeKind::Slice(slice) => self.check_snapshot_type(&slice.element_type()),
+            TypeKind::Struct(_) => match self.type_table.find_type_info_by_name(ty.name()) {
+                Some(current) if &current == ty => Ok(()),
+                Some(_) => Err(RestoreError::TypeChanged(ty.name().to_owned())),
+                None => Err(RestoreError::TypeRemoved(ty.name().to_owned())),
+            },
+        }
+    }
+
     /// Constructs an array with a predefined element type.
+    ///
+    /// If the array does not fit within the maximum heap size, garbage is
+    /// collected first. Fails if it still does not fit, or if garbage cannot be
+    /// collected because Mun code is running.
     pub fn construct_typed_array<
         't,
         T: 't + Marshal<'t> + ArgumentReflection,
@@ -558,7 +1097,7 @@ impl Runtime {
         &'t self,
         element_type: &Type,
         iter: I,
-    ) -> ArrayRef<'t, T>
+    ) -> Result<ArrayRef<'t, T>, HeapLimitExceeded>
     where
         I::IntoIter: ExactSizeIterator,
     {
@@ -568,7 +1107,9 @@ impl Runtime {
             .size_hint()
             .1
             .expect("iterator doesn't return upper bound");
-        let mut array_handle = self.gc.alloc_array(&array_type, array_capacity);
+        let mut array_handle = heap_limit::alloc_or_collect(&self.gc, |gc| {
+            gc.alloc_array(&array_type, array_capacity)
+        })?;
 
         let mut element_ptr = array_handle.data().as_ptr();
         let element_stride = array_handle.element_stride();
@@ -599,14 +1140,18 @@ impl Runtime {
             array_handle.set_length(size);
         }
 
-        ArrayRef::new(RawArray(array_handle.as_raw()), self)
+        Ok(ArrayRef::new(RawArray(array_handle.as_raw()), self))
     }
 
     /// Constructs an array from an iterator
+    ///
+    /// If the array does not fit within the maximum heap size, garbage is
+    /// collected first. Fails if it still does not fit, or if garbage cannot be
+    /// collected because Mun code is running.
     pub fn construct_array<'t, T: 't + Marshal<'t> + HasStaticType, I: IntoIterator<Item = T>>(
         &'t self,
         iter: I,
-    ) -> ArrayRef<'t, T>
+    ) -> Result<ArrayRef<'t, T>, HeapLimitExceeded>
     where
         I::IntoIter: ExactSizeIterator,
     {
@@ -617,7 +1162,9 @@ impl Runtime {
             .size_hint()
             .1
             .expect("iterator doesn't return upper bound");
-        let mut array_handle = self.gc.alloc_array(&array_type, array_capacity);
+        let mut array_handle = heap_limit::alloc_or_collect(&self.gc, |gc| {
+            gc.alloc_array(&array_type, array_capacity)
+        })?;
 
         let mut element_ptr = array_handle.data().as_ptr();
         let element_stride = array_handle.element_stride();
@@ -647,7 +1194,7 @@ impl Runtime {
             array_handle.set_length(size);
         }
 
-        ArrayRef::new(RawArray(array_handle.as_raw()), self)
+        Ok(ArrayRef::new(RawArray(array_handle.as_raw()), self))
     }
 }
 
@@ -655,7 +1202,28 @@ impl Runtime {
 pub struct InvokeErr<'name, T> {
     msg: String,
     function_name: &'name str,
-    arguments: T,
+    /// The arguments of the invocation, unless they were consumed by calling
+    /// the function
+    arguments: Option<T>,
+    heap_limit_exceeded: Option<HeapLimitExceeded>,
+}
+
+impl<'name, T> InvokeErr<'name, T> {
+    /// Constructs an error that occurred before the function was called.
+    fn new(msg: String, function_name: &'name str, arguments: T) -> Self {
+        InvokeErr {
+            msg,
+            function_name,
+            arguments: Some(arguments),
+            heap_limit_exceeded: None,
+        }
+    }
+
+    /// Returns the allocation that exceeded the maximum heap size while the
+    /// function was running, if that is why the invocation failed.
+    pub fn heap_limit_exceeded(&self) -> Option<&HeapLimitExceeded> {
+        self.heap_limit_exceeded.as_ref()
+    }
 }
 
 impl<'name, T> Debug for InvokeErr<'name, T> {
@@ -723,7 +1291,11 @@ impl<'name, T: InvokeArgs> InvokeErr<'name, T> {
             // Wait until there has been an update that might fix the error
         }
 
-        runtime.invoke(self.function_name, self.arguments)
+        // The arguments were consumed if the function was called
+        match self.arguments {
+            Some(arguments) => runtime.invoke(self.function_name, arguments),
+            None => Err(self),
+        }
     }
 }
 
@@ -813,40 +1385,124 @@ impl Runtime {
                     |name| format!("{msg} There is a function with a similar name: {name}"),
                 );
 
-                return Err(InvokeErr {
-                    msg: suggested_message,
-                    function_name,
-                    arguments,
-                });
+                return Err(InvokeErr::new(suggested_message, function_name, arguments));
             }
         };
 
+        self.invoke_definition(&function_info, function_name, arguments)
+    }
+
+    /// Invokes the Mun function referred to by `handle` with the specified
+    /// `arguments`.
+    ///
+    /// Returns an error if the handle is stale because the runtime reloaded its
+    /// assemblies since the handle was resolved.
+    pub fn invoke_handle<
+        'runtime,
+        'ret,
+        'name,
+        ReturnType: ReturnTypeReflection + Marshal<'ret> + 'ret,
+        ArgTypes: InvokeArgs,
+    >(
+        &'runtime self,
+        handle: &'name FunctionHandle,
+        arguments: ArgTypes,
+    ) -> Result<ReturnType, InvokeErr<'name, ArgTypes>>
+    where
+        'runtime: 'ret,
+    {
+        let function_name = handle.name();
+        if !self.is_handle_valid(handle) {
+            return Err(InvokeErr::new(
+                format!(
+                    "failed to invoke function '{function_name}', the handle was resolved by another runtime or is stale because the runtime was reloaded. Resolve the function again."
+                ),
+                function_name,
+                arguments,
+            ));
+        }
+
+        self.invoke_definition(&handle.definition, function_name, arguments)
+    }
+
+    /// Validates the `arguments` and return type against the signature of
+    /// `function_info` and invokes it.
+    fn invoke_definition<
+        'runtime,
+        'ret,
+        'name,
+        ReturnType: ReturnTypeReflection + Marshal<'ret> + 'ret,
+        ArgTypes: InvokeArgs,
+    >(
+        &'runtime self,
+        function_info: &FunctionDefinition,
+        function_name: &'name str,
+        arguments: ArgTypes,
+    ) -> Result<ReturnType, InvokeErr<'name, ArgTypes>>
+    where
+        'runtime: 'ret,
+    {
         // Validate the arguments
         match arguments.can_invoke(self, &function_info.prototype.signature) {
             Ok(_) => {}
-            Err(msg) => {
-                return Err(InvokeErr {
-                    msg,
-                    function_name,
-                    arguments,
-                })
-            }
+            Err(msg) => return Err(InvokeErr::new(msg, function_name, arguments)),
         };
 
         // Validate the return type
         if !ReturnType::accepts_type(&function_info.prototype.signature.return_type) {
-            return Err(InvokeErr {
-                msg: format!(
+            return Err(InvokeErr::new(
+                format!(
                     "unexpected return type, got '{}', expected '{}",
                     &function_info.prototype.signature.return_type.name(),
                     ReturnType::type_hint()
                 ),
                 function_name,
                 arguments,
+            ));
+        }
+
+        // Mapping the memory of a hot reloaded assembly cannot fail, so it can grow
+        // the heap beyond its limit. No more code is run until garbage has been
+        // collected to bring the heap back within its limit.
+        let heap_limit = &self.gc.observer().heap_limit;
+        if self.gc.stats().exceeds_max_heap() && heap_limit.can_collect() {
+            self.gc.collect();
+        }
+        let gc_stats = self.gc.stats();
+        if gc_stats.exceeds_max_heap() {
+            return Err(InvokeErr::new(
+                format!(
+                    "failed to invoke function '{function_name}', {} bytes are allocated which exceeds the maximum heap size of {} bytes.",
+                    gc_stats.allocated_memory,
+                    gc_stats.max_heap.unwrap_or_default(),
+                ),
+                function_name,
+                arguments,
+            ));
+        }
+
+        let result: ReturnType::MunType = {
+            let _invocation = heap_limit.enter();
+            unsafe { arguments.invoke(function_info.fn_ptr) }
+        };
+        let result = Marshal::marshal_from(result, self);
+
+        // Allocations of the function that exceeded the heap limit grew the heap
+        // beyond it. Its result is discarded, such that the garbage can be
+        // collected.
+        if let Some(error) = heap_limit.take() {
+            drop(result);
+            if heap_limit.can_collect() {
+                self.gc.collect();
+            }
+            return Err(InvokeErr {
+                msg: format!("failed to invoke function '{function_name}', {error}"),
+                function_name,
+                arguments: None,
+                heap_limit_exceeded: Some(error),
             });
         }
 
-        let result: ReturnType::MunType = unsafe { arguments.invoke(function_info.fn_ptr) };
-        Ok(Marshal::marshal_from(result, self))
+        Ok(result)
     }
 }
@@ -119,6 +119,38 @@ where
     }
 }
 
+impl<'t, T> Marshal<'t> for *const T {
+    type MunType = *const T;
+
+    fn marshal_from<'r>(value: Self::MunType, _runtime: &'r Runtime) -> Self
+    where
+        Self: 't,
+        'r: 't,
+    {
+        value
+    }
+
+    fn marshal_into(self) -> Self::MunType {
+        self
+    }
+
+    fn marshal_from_ptr<'r>(
+        ptr: std::ptr::NonNull<Self::MunType>,
+        _runtime: &'r Runtime,
+        _type_info: &Type,
+    ) -> Self
+    where
+        Self: 't,
+        'r: 't,
+    {
+        unsafe { ptr.as_ptr().read() }
+    }
+
+    fn marshal_to_ptr(value: Self, mut ptr: std::ptr::NonNull<Self::MunType>, _type_info: &Type) {
+        unsafe { *ptr.as_mut() = value };
+    }
+}
+
 impl<T> ArgumentReflection for *mut T
 where
     *mut T: HasStaticType,
@@ -140,3 +172,35 @@ where
         <*mut T as HasStaticType>::type_info().name()
     }
 }
+
+impl<'t, T> Marshal<'t> for *mut T {
+    type MunType = *mut T;
+
+    fn marshal_from<'r>(value: Self::MunType, _runtime: &'r Runtime) -> Self
+    where
+        Self: 't,
+        'r: 't,
+    {
+        value
+    }
+
+    fn marshal_into(self) -> Self::MunType {
+        self
+    }
+
+    fn marshal_from_ptr<'r>(
+        ptr: std::ptr::NonNull<Self::MunType>,
+        _runtime: &'r Runtime,
+        _type_info: &Type,
+    ) -> Self
+    where
+        Self: 't,
+        'r: 't,
+    {
+        unsafe { ptr.as_ptr().read() }
+    }
+
+    fn marshal_to_ptr(value: Self, mut ptr: std::ptr::NonNull<Self::MunType>, _type_info: &Type) {
+        unsafe { *ptr.as_mut() = value };
+    }
+}
@@ -0,0 +1,65 @@
+use std::marker::PhantomData;
+
+use mun_abi::static_type_map::StaticTypeMap;
+use mun_memory::{HasStaticType, Type};
+use once_cell::sync::OnceCell;
+
+/// A borrowed, contiguous sequence of values that Mun passes to an extern
+/// function with a `&[T]` parameter. A slice is passed by value as a pointer
+/// to its first element and the number of elements.
+///
+/// The elements are owned by Mun and are only valid for the duration of the
+/// call; a slice must never be stored by the host.
+#[repr(C)]
+pub struct Slice<'a, T> {
+    ptr: *mut T,
+    len: usize,
+    _phantom: PhantomData<&'a mut [T]>,
+}
+
+impl<'a, T> Slice<'a, T> {
+    /// Returns the number of elements in the slice.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the slice contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a raw pointer to the first element of the slice.
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr
+    }
+
+    /// Returns the elements of the slice.
+    pub fn as_slice(&self) -> &[T] {
+        if self.len == 0 {
+            &[]
+        } else {
+            // Safety: Mun guarantees that `ptr` points to `len` initialized elements for the
+            // duration of the call.
+            unsafe { std::slice::from_raw_parts(self.ptr, self.len) }
+        }
+    }
+
+    /// Returns the elements of the slice, which allows the host to fill them.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        if self.len == 0 {
+            &mut []
+        } else {
+            // Safety: Mun guarantees that `ptr` points to `len` initialized elements for the
+            // duration of the call.
+            unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }
+        }
+    }
+}
+
+impl<'a, T: HasStaticType + 'static> HasStaticType for Slice<'a, T> {
+    fn type_info() -> &'static Type {
+        static VALUE: OnceCell<StaticTypeMap<Type>> = OnceCell::new();
+        let map = VALUE.get_or_init(Default::default);
+        map.call_once::<T, _>(|| T::type_info().slice_type())
+    }
+}
@@ -0,0 +1,187 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    thread::{self, ThreadId},
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+/// Accumulates the execution time of the functions of assemblies that were
+/// compiled with timing instrumentation. Instrumented functions report their
+/// entry and exit, assemblies compiled without it never do, in which case the
+/// recorder does nothing.
+#[derive(Default)]
+pub struct TimingRecorder {
+    state: Mutex<TimingState>,
+}
+
+#[derive(Default)]
+struct TimingState {
+    /// The accumulated timings per function. The names are copied out of the
+    /// assemblies, so they remain valid when an assembly is hot reloaded.
+    functions: HashMap<Arc<str>, FunctionStats>,
+    /// The instrumented calls that are in progress, per thread
+    call_stacks: HashMap<ThreadId, Vec<Frame>>,
+}
+
+#[derive(Default)]
+struct FunctionStats {
+    calls: u64,
+    total: Duration,
+    self_time: Duration,
+    /// The number of calls of the function that are in progress. Used to only
+    /// add the time of the outermost call of a recursive function to its total.
+    active: usize,
+}
+
+struct Frame {
+    name: Arc<str>,
+    start: Instant,
+    /// The time spent in instrumented functions called from this frame
+    callee_time: Duration,
+}
+
+impl TimingRecorder {
+    /// Records that the function `name` was entered on the current thread.
+    pub fn enter(&self, name: &str) {
+        let mut state = self.state.lock();
+        let name = match state.functions.get_key_value(name) {
+            Some((name, _)) => name.clone(),
+            None => Arc::from(name),
+        };
+
+        let stats = state.functions.entry(name.clone()).or_default();
+        stats.calls += 1;
+        stats.active += 1;
+
+        state
+            .call_stacks
+            .entry(thread::current().id())
+            .or_default()
+            .push(Frame {
+                name,
+                start: Instant::now(),
+                callee_time: Duration::ZERO,
+            });
+    }
+
+    /// Records that the function `name` returns on the current thread.
+    pub fn exit(&self, name: &str) {
+        let end = Instant::now();
+        let mut state = self.state.lock();
+        let state = &mut *state;
+
+        let Some(stack) = state.call_stacks.get_mut(&thread::current().id()) else {
+            return;
+        };
+
+        // Frames of calls that never returned, e.g. because the timings were
+        // not recorded when they were entered, are discarded.
+        let Some(position) = stack.iter().rposition(|frame| &*frame.name == name) else {
+            return;
+        };
+        let mut frames = stack.split_off(position).into_iter();
+        let frame = frames.next().expect("frame must exist");
+        for abandoned in frames {
+            if let Some(stats) = state.functions.get_mut(&abandoned.name) {
+                stats.active -= 1;
+            }
+        }
+
+        let elapsed = end.saturating_duration_since(frame.start);
+        if let Some(caller) = stack.last_mut() {
+            caller.callee_time += elapsed;
+        }
+        if stack.is_empty() {
+            state.call_stacks.remove(&thread::current().id());
+        }
+
+        let stats = state
+            .functions
+            .get_mut(&frame.name)
+            .expect("entered function has no stats");
+        stats.active -= 1;
+        stats.self_time += elapsed.saturating_sub(frame.callee_time);
+        if stats.active == 0 {
+            stats.total += elapsed;
+        }
+    }
+
+    /// Returns the accumulated timings of all functions that were called.
+    pub fn timings(&self) -> Vec<FunctionTiming> {
+        let state = self.state.lock();
+        let mut timings: Vec<FunctionTiming> = state
+            .functions
+            .iter()
+            .map(|(name, stats)| FunctionTiming {
+                name: name.to_string(),
+                calls: stats.calls,
+                total: stats.total,
+                self_time: stats.self_time,
+            })
+            .collect();
+        timings.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.name.cmp(&b.name)));
+        timings
+    }
+}
+
+/// The accumulated execution time of a single function.
+///
+/// Only functions of assemblies that were compiled with timing instrumentation
+/// are measured. The instrumentation itself takes time, which is included in
+/// the measurements, so the timings are only meaningful relative to each
+/// other.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionTiming {
+    /// The full name of the function, e.g. `foo::bar`
+    pub name: String,
+    /// The number of times the function was called
+    pub calls: u64,
+    /// The inclusive time spent in the function, including the time spent in
+    /// the functions it calls. For recursive functions only the outermost call
+    /// is counted, so that nested calls are not counted twice.
+    pub total: Duration,
+    /// The exclusive time spent in the function itself. The time spent in
+    /// instrumented functions that it calls is excluded, the time spent in
+    /// other functions is not. The self time of all calls of a recursive
+    /// function is summed.
+    pub self_time: Duration,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TimingRecorder;
+
+    #[test]
+    fn recursive_calls_are_counted_once() {
+        let recorder = TimingRecorder::default();
+        recorder.enter("fib");
+        recorder.enter("fib");
+        recorder.enter("leaf");
+        recorder.exit("leaf");
+        recorder.exit("fib");
+        recorder.exit("fib");
+
+        let timings = recorder.timings();
+        let fib = timings.iter().find(|t| t.name == "fib").unwrap();
+        let leaf = timings.iter().find(|t| t.name == "leaf").unwrap();
+        assert_eq!(fib.calls, 2);
+        assert_eq!(leaf.calls, 1);
+        assert!(fib.total >= leaf.total);
+        assert!(fib.self_time <= fib.total);
+    }
+
+    #[test]
+    fn unbalanced_exit_is_ignored() {
+        let recorder = TimingRecorder::default();
+        recorder.exit("foo");
+        recorder.enter("foo");
+        recorder.enter("bar");
+        recorder.exit("foo");
+
+        let timings = recorder.timings();
+        assert_eq!(timings.len(), 2);
+        assert!(timings.iter().all(|t| t.calls == 1));
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    collections::HashSet,
+    ptr,
+    time::{Duration, Instant},
+};
+
+use crate::{assembly::Assembly, FunctionHandle, Runtime};
+
+/// Options for [`Runtime::warmup`].
+#[derive(Clone, Debug, Default)]
+pub struct WarmupOptions {
+    /// Whether the `#[init]` functions of the loaded assemblies are called,
+    /// ordered by name. They are called once per load of an assembly, so
+    /// after a hot reload the `#[init]` functions of the reloaded assemblies
+    /// are called again by the next warmup.
+    pub call_init_functions: bool,
+}
+
+/// Describes the work done by [`Runtime::warmup`].
+#[derive(Clone)]
+pub struct WarmupReport {
+    /// Handles to all functions of the loaded assemblies, ordered by name
+    pub functions: Vec<FunctionHandle>,
+    /// The names of the `#[init]` functions that were called by this warmup
+    pub init_functions_called: Vec<String>,
+    /// How long the warmup took
+    pub duration: Duration,
+}
+
+/// An error that occurs when an `#[init]` function cannot be called by
+/// [`Runtime::warmup`].
+#[derive(Debug, thiserror::Error)]
+#[error("failed to call init function `{name}`: {msg}")]
+pub struct WarmupError {
+    /// The name of the `#[init]` function
+    pub name: String,
+    /// Why the function could not be called
+    pub msg: String,
+}
+
+/// The warmup of the assemblies of the runtime.
+#[derive(Default)]
+pub(crate) struct WarmupState {
+    /// The generation of the runtime the handles were resolved for, and the
+    /// handles themselves
+    functions: Option<(u64, Vec<FunctionHandle>)>,
+    /// The load ids of the assemblies whose `#[init]` functions were called.
+    /// Unlike the handles, these survive a rebind of an extern function.
+    initialized: HashSet<u64>,
+}
+
+impl Runtime {
+    /// Prepares the loaded assemblies such that the first call into Mun code is
+    /// not slower than subsequent calls. This resolves a [`FunctionHandle`] for
+    /// every function of the loaded assemblies and touches the code of each
+    /// function, which pages it in from the shared object. Optionally, the
+    /// `#[init]` functions of the assemblies are called.
+    ///
+    /// Warming up is idempotent: subsequent calls return the cached handles
+    /// until assemblies are reloaded or an extern function is rebound, and the
+    /// `#[init]` functions of an assembly are only called once per load.
+    pub fn warmup(&mut self, options: &WarmupOptions) -> Result<WarmupReport, WarmupError> {
+        let start = Instant::now();
+
+        let is_warm = self
+            .warmup
+            .functions
+            .as_ref()
+            .map_or(false, |(generation, _)| *generation == self.generation);
+        if !is_warm {
+            self.warmup.functions = Some((self.generation, self.resolve_and_touch_functions()));
+        }
+
+        let mut init_functions_called = Vec::new();
+        if options.call_init_functions {
+            let loaded = self
+                .assemblies
+                .values()
+                .map(Assembly::load_id)
+                .collect::<HashSet<_>>();
+            self.warmup.initialized.retain(|id| loaded.contains(id));
+
+            let uninitialized = loaded
+                .difference(&self.warmup.initialized)
+                .copied()
+                .collect::<HashSet<_>>();
+            for name in self.init_function_names(&uninitialized) {
+                self.invoke::<(), ()>(&name, ()).map_err(|e| WarmupError {
+                    name: name.clone(),
+                    msg: e.to_string(),
+                })?;
+                init_functions_called.push(name);
+            }
+            self.warmup.initialized.extend(uninitialized);
+        }
+
+        let functions = self
+            .warmup
+            .functions
+            .as_ref()
+            .map(|(_, functions)| functions.clone())
+            .expect("the runtime was warmed up");
+        Ok(WarmupReport {
+            functions,
+            init_functions_called,
+            duration: start.elapsed(),
+        })
+    }
+
+    /// Resolves handles to all functions of the loaded assemblies and reads
+    /// the first byte of each function, such that the page that contains it is
+    /// loaded into memory.
+    fn resolve_and_touch_functions(&self) -> Vec<FunctionHandle> {
+        let mut names = self
+            .assemblies
+            .values()
+            .flat_map(|assembly| assembly.info().symbols.functions())
+            .map(|function| function.prototype.name())
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names.dedup();
+
+        names
+            .into_iter()
+            .filter_map(|name| self.resolve(name))
+            .inspect(|handle| {
+                // Safety: the function pointer points to the code of a function in a loaded
+                // assembly, which is readable.
+                unsafe { ptr::read_volatile(handle.definition.fn_ptr.cast::<u8>()) };
+            })
+            .collect()
+    }
+
+    /// Returns the names of the `#[init]` functions of the loaded assemblies
+    /// with the specified load ids, ordered by name.
+    fn init_function_names(&self, load_ids: &HashSet<u64>) -> Vec<String> {
+        let mut names = self
+            .assemblies
+            .values()
+            .filter(|assembly| load_ids.contains(&assembly.load_id()))
+            .flat_map(|assembly| assembly.info().symbols.init_functions())
+            .map(String::from)
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names
+    }
+}
@@ -1,5 +1,7 @@
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+
 use itertools::Itertools;
-use mun_runtime::{ArrayRef, StructRef};
+use mun_runtime::{ArrayRef, Runtime, Slice, StructRef};
 use mun_test::CompileAndRunTestDriver;
 
 #[test]
@@ -82,6 +84,81 @@ fn arrays_as_argument() {
     assert_eq!(result_array.iter().collect::<Vec<_>>(), vec![6, 5, 4, 3, 2]);
 }
 
+#[test]
+fn for_loop_over_array() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub struct Number { value: i32 };
+    pub struct(value) Value { value: i32 };
+
+    pub fn sum(array: [i32]) -> i32 {
+        let sum = 0;
+        for x in array {
+            sum += x;
+        }
+        sum
+    }
+
+    pub fn sum_while_modifying(array: [i32]) -> i32 {
+        let sum = 0;
+        for x in array {
+            array[1] = 0;
+            sum += x;
+        }
+        sum
+    }
+
+    pub fn increment_numbers() -> [Number] {
+        let array = [Number { value: 1 }, Number { value: 2 }];
+        for number in array {
+            number.value += 1;
+        }
+        array
+    }
+
+    pub fn increment_values() -> [Value] {
+        let array = [Value { value: 1 }, Value { value: 2 }];
+        for value in array {
+            value.value += 1;
+        }
+        array
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let array = driver.runtime.construct_array(vec![5, 4, 3, 2, 1]).unwrap();
+    let result: i32 = driver.runtime.invoke("sum", (array,)).unwrap();
+    assert_eq!(result, 15);
+
+    // Elements that have not been visited yet are read when they are reached
+    let array = driver.runtime.construct_array(vec![5, 4, 3, 2, 1]).unwrap();
+    let result: i32 = driver
+        .runtime
+        .invoke("sum_while_modifying", (array,))
+        .unwrap();
+    assert_eq!(result, 11);
+
+    // Elements of a `struct(gc)` type refer to the objects in the array
+    let result: ArrayRef<'_, StructRef<'_>> =
+        driver.runtime.invoke("increment_numbers", ()).unwrap();
+    let values: Vec<i32> = result
+        .iter()
+        .map(|number| number.get("value").unwrap())
+        .collect();
+    assert_eq!(values, vec![2, 3]);
+
+    // Elements of a `struct(value)` type are copied out of the array
+    let result: ArrayRef<'_, StructRef<'_>> =
+        driver.runtime.invoke("increment_values", ()).unwrap();
+    let values: Vec<i32> = result
+        .iter()
+        .map(|value| value.get("value").unwrap())
+        .collect();
+    assert_eq!(values, vec![1, 2]);
+}
+
 #[test]
 fn root_array() {
     let driver = CompileAndRunTestDriver::new(
@@ -103,13 +180,66 @@ fn root_array() {
     assert_eq!(result.iter().collect::<Vec<_>>(), vec![5, 4, 3, 2, 1]);
 }
 
+#[test]
+fn extern_fills_borrowed_array() {
+    static RUNTIME: AtomicPtr<Runtime> = AtomicPtr::new(std::ptr::null_mut());
+    static ROOTS: AtomicUsize = AtomicUsize::new(0);
+
+    extern "C" fn fill(mut buffer: Slice<'_, i64>) {
+        // Safety: the runtime outlives the invocation that calls `fill`
+        let runtime = unsafe { &*RUNTIME.load(Ordering::SeqCst) };
+
+        // The borrowed array is rooted for the duration of the call, so a
+        // collection does not reclaim it
+        assert_eq!(
+            runtime.snapshot().roots().count(),
+            ROOTS.load(Ordering::SeqCst) + 1
+        );
+        runtime.gc_collect();
+
+        assert_eq!(buffer.len(), 3);
+        buffer.as_mut_slice().copy_from_slice(&[1, 2, 3]);
+    }
+
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    extern fn fill(buffer: &[i64]);
+
+    pub fn main() -> i64 {
+        let a = [0, 0, 0];
+        fill(&a);
+        a[0] * 100 + a[1] * 10 + a[2]
+    }
+    ",
+        |builder| builder.insert_fn("fill", fill as extern "C" fn(Slice<'_, i64>)),
+    )
+    .expect("Failed to build test driver");
+
+    let roots = driver.runtime.snapshot().roots().count();
+    RUNTIME.store(
+        &driver.runtime as *const Runtime as *mut Runtime,
+        Ordering::SeqCst,
+    );
+    ROOTS.store(roots, Ordering::SeqCst);
+
+    // Writes of the host are visible to Mun after the call
+    let result: i64 = driver.runtime.invoke("main", ()).unwrap();
+    assert_eq!(result, 123);
+
+    // The array is unrooted when the call returns
+    assert_eq!(driver.runtime.snapshot().roots().count(), roots);
+}
+
 #[test]
 fn construct_array_simple() {
     let driver =
         CompileAndRunTestDriver::new(r"", |builder| builder).expect("Failed to build test driver");
 
     let test_data = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 147294028];
-    let array = driver.runtime.construct_array(test_data.iter().copied());
+    let array = driver
+        .runtime
+        .construct_array(test_data.iter().copied())
+        .unwrap();
     assert!(array.capacity() >= test_data.len());
     assert_eq!(array.len(), test_data.len());
     assert_eq!(array.iter().collect_vec(), test_data);
@@ -0,0 +1,86 @@
+use mun_compiler::diagnostics::Severity;
+use mun_runtime::EvalError;
+use mun_test::CompileAndRunTestDriver;
+
+#[test]
+fn eval_expr() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn sum(a: i64, b: i64) -> i64 { a + b }
+        "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: i64 = driver
+        .runtime
+        .eval_expr("a + b * 2", &(("a", 1i64), ("b", 2i64)))
+        .unwrap();
+    assert_eq!(result, 5);
+
+    let result: i64 = driver
+        .runtime
+        .eval_expr("sum(a, 3) * 2", &(("a", 4i64),))
+        .unwrap();
+    assert_eq!(result, 14);
+
+    let result: bool = driver.runtime.eval_expr("1 < 2", &()).unwrap();
+    assert!(result);
+}
+
+#[test]
+fn eval_expr_compile_error() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn main() {}
+        "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result = driver
+        .runtime
+        .eval_expr::<i64, _>("a + true", &(("a", 1i64),));
+    let diagnostics = match result {
+        Err(EvalError::Compile(diagnostics)) => diagnostics,
+        result => panic!("expected a compile error, found: {result:?}"),
+    };
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[0].range, 4..8);
+}
+
+#[test]
+fn eval_expr_unsupported_type() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn main() {}
+        "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result = driver.runtime.eval_expr::<isize, _>("1", &());
+    assert!(matches!(result, Err(EvalError::UnsupportedType(_))));
+}
+
+#[test]
+fn eval_expr_extern_fn() {
+    extern "C" fn add_int(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn main() {}
+        "#,
+        |builder| builder.insert_fn("add", add_int as extern "C" fn(i32, i32) -> i32),
+    )
+    .expect("Failed to build test driver");
+
+    let result: i32 = driver
+        .runtime
+        .eval_expr("add(a, 2)", &(("a", 3i32),))
+        .unwrap();
+    assert_eq!(result, 5);
+}
@@ -1,6 +1,11 @@
 #[macro_use]
 mod util;
 
+use std::num::NonZeroU32;
+
+use mun_abi as abi;
+use mun_compiler::{Config, OptimizationLevel};
+use mun_runtime::Slice;
 use mun_test::CompileAndRunTestDriver;
 
 #[test]
@@ -121,3 +126,309 @@ fn multiple_match_exists_function() {
         )
     );
 }
+
+#[test]
+fn reflected_arg_names() {
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r"
+    pub fn sum(lhs: i32, _: i32, Rhs_2: i32) -> i32 { lhs + Rhs_2 }
+    pub fn zero() -> i32 { 0 }
+    ",
+        |config| Config {
+            reflect_arg_names: true,
+            ..config
+        },
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let sum = driver.runtime.get_function_definition("sum").unwrap();
+    assert_eq!(
+        sum.prototype.signature.arg_names(),
+        Some(&["lhs".to_owned(), "_".to_owned(), "Rhs_2".to_owned()][..])
+    );
+
+    let zero = driver.runtime.get_function_definition("zero").unwrap();
+    assert_eq!(zero.prototype.signature.arg_names(), Some(&[][..]));
+}
+
+#[test]
+fn omitted_arg_names() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn sum(lhs: i32, rhs: i32) -> i32 { lhs + rhs }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let sum = driver.runtime.get_function_definition("sum").unwrap();
+    assert_eq!(sum.prototype.signature.arg_names(), None);
+}
+
+#[test]
+fn function_timings() {
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r"
+    pub fn fib(n: i64) -> i64 {
+        if n <= 1 {
+            return n;
+        }
+        fib(n - 1) + fib(n - 2)
+    }
+
+    pub fn main() -> i64 {
+        fib(5) + untimed()
+    }
+
+    #[no_instrument]
+    fn untimed() -> i64 { 1 }
+    ",
+        |config| Config {
+            instrument_timing: true,
+            ..config
+        },
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: i64 = driver.runtime.invoke("main", ()).unwrap();
+    assert_eq!(result, 6);
+
+    let timings = driver.runtime.timings();
+    assert_eq!(timings.len(), 2);
+    let main = timings.iter().find(|timing| timing.name == "main").unwrap();
+    let fib = timings.iter().find(|timing| timing.name == "fib").unwrap();
+    assert_eq!(main.calls, 1);
+    assert_eq!(fib.calls, 15);
+
+    // The time of recursive calls is only counted once
+    assert!(fib.total <= main.total);
+    assert!(fib.self_time <= fib.total);
+    assert!(main.self_time <= main.total);
+}
+
+#[test]
+fn function_timings_disabled() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() -> i64 { 1 }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let _: i64 = driver.runtime.invoke("main", ()).unwrap();
+    assert!(driver.runtime.timings().is_empty());
+}
+
+#[test]
+fn tail_call_recursion() {
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r"
+    #[tail_call]
+    pub fn count(n: i64, acc: i64) -> i64 {
+        if n == 0 {
+            return acc;
+        }
+        count(n - 1, acc + 1)
+    }
+    ",
+        |config| Config {
+            optimization_lvl: OptimizationLevel::None,
+            ..config
+        },
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    // Recursion this deep would overflow the stack without tail calls
+    let result: i64 = driver
+        .runtime
+        .invoke("count", (10_000_000i64, 0i64))
+        .unwrap();
+    assert_eq!(result, 10_000_000);
+}
+
+#[test]
+fn tail_call_borrowing_array_is_unrooted() {
+    extern "C" fn sum(values: Slice<'_, i64>) -> i64 {
+        values.as_slice().iter().sum()
+    }
+
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r"
+    extern fn sum(values: &[i64]) -> i64;
+
+    #[tail_call]
+    pub fn sum_down(n: i64, acc: i64) -> i64 {
+        let values = [n, acc];
+        if n == 0 {
+            return sum(&values);
+        }
+        sum_down(n - 1, acc + 1)
+    }
+    ",
+        |config| Config {
+            optimization_lvl: OptimizationLevel::None,
+            ..config
+        },
+        |builder| builder.insert_fn("sum", sum as extern "C" fn(Slice<'_, i64>) -> i64),
+    )
+    .expect("Failed to build test driver");
+
+    let roots = driver.runtime.snapshot().roots().count();
+    for _ in 0..100 {
+        let result: i64 = driver.runtime.invoke("sum_down", (1_000i64, 0i64)).unwrap();
+        assert_eq!(result, 1_000);
+    }
+
+    // The array borrowed by the final call to `sum` is unrooted after every
+    // invocation
+    assert_eq!(driver.runtime.snapshot().roots().count(), roots);
+}
+
+#[test]
+fn call_trace_ring() {
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r"
+    pub fn fib(n: i64) -> i64 {
+        if n <= 1 {
+            return n;
+        }
+        fib(n - 1) + fib(n - 2)
+    }
+
+    pub fn main() -> i64 {
+        fib(2) + untraced() + private()
+    }
+
+    #[no_instrument]
+    fn untraced() -> i64 { 1 }
+
+    fn private() -> i64 { 2 }
+    ",
+        |config| Config {
+            call_trace_ring: NonZeroU32::new(4),
+            ..config
+        },
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert!(driver.runtime.call_trace().is_empty());
+
+    let result: i64 = driver.runtime.invoke("main", ()).unwrap();
+    assert_eq!(result, 4);
+
+    // The ring holds the last four calls, private functions included
+    assert_eq!(
+        driver.runtime.call_trace(),
+        vec!["fib", "fib", "fib", "private"]
+    );
+}
+
+#[test]
+fn call_trace_ring_disabled() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() -> i64 { 1 }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let _: i64 = driver.runtime.invoke("main", ()).unwrap();
+    assert!(driver.runtime.call_trace().is_empty());
+}
+
+#[test]
+fn function_local_statics() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    fn start() -> i64 { 10 }
+    pub fn counter() -> i64 {
+        static mut N: i64 = 0;
+        N += 1;
+        N
+    }
+    pub fn counter_from_start() -> i64 {
+        static mut N: i64 = start();
+        N += 1;
+        N
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i64, 1, driver, "counter");
+    assert_invoke_eq!(i64, 2, driver, "counter");
+    assert_invoke_eq!(i64, 3, driver, "counter");
+
+    assert_invoke_eq!(i64, 11, driver, "counter_from_start");
+    assert_invoke_eq!(i64, 12, driver, "counter_from_start");
+}
+
+#[test]
+fn reflected_fn_attributes() {
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r"
+    #[inline(always)]
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+
+    #[cold]
+    #[inline(never)]
+    #[optimize(size)]
+    pub fn fail() -> i32 { -1 }
+
+    pub fn main() -> i32 { add(1, 2) }
+    ",
+        |config| Config {
+            reflect_fn_attributes: true,
+            ..config
+        },
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let add = driver.runtime.get_function_definition("add").unwrap();
+    assert_eq!(
+        add.attributes(),
+        Some(&abi::FunctionAttributes {
+            inline: abi::InlineAttribute::Always,
+            ..abi::FunctionAttributes::default()
+        })
+    );
+
+    let fail = driver.runtime.get_function_definition("fail").unwrap();
+    assert_eq!(
+        fail.attributes(),
+        Some(&abi::FunctionAttributes {
+            inline: abi::InlineAttribute::Never,
+            optimize: abi::OptimizeAttribute::Size,
+            is_cold: true,
+            ..abi::FunctionAttributes::default()
+        })
+    );
+
+    // Functions without attributes report the defaults
+    let main = driver.runtime.get_function_definition("main").unwrap();
+    assert_eq!(main.attributes(), Some(&abi::FunctionAttributes::default()));
+    assert_invoke_eq!(i32, 3, driver, "main");
+}
+
+#[test]
+fn omitted_fn_attributes() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    #[cold]
+    pub fn fail() -> i32 { -1 }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let fail = driver.runtime.get_function_definition("fail").unwrap();
+    assert_eq!(fail.attributes(), None);
+}
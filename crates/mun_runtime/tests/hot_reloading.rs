@@ -1,7 +1,9 @@
 #[macro_use]
 mod util;
 
-use mun_runtime::StructRef;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use mun_runtime::{DispatchTableEntryKind, StructRef, WarmupOptions};
 use mun_test::CompileAndRunTestDriver;
 
 #[test]
@@ -24,6 +26,74 @@ fn reloadable_function_single_file() {
     assert_invoke_eq!(i32, 10, driver, "main");
 }
 
+#[test]
+fn stale_function_handle() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() -> i32 { 5 }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let handle = driver
+        .runtime
+        .resolve("main")
+        .expect("Failed to resolve `main`");
+    let result: i32 = driver.runtime.invoke_handle(&handle, ()).unwrap();
+    assert_eq!(result, 5);
+
+    driver.update_file(
+        "mod.mun",
+        r"
+    pub fn main() -> i32 { 10 }
+    ",
+    );
+    assert!(!driver.runtime.is_handle_valid(&handle));
+    assert!(driver
+        .runtime
+        .invoke_handle::<i32, ()>(&handle, ())
+        .is_err());
+
+    let handle = driver
+        .runtime
+        .resolve("main")
+        .expect("Failed to resolve `main`");
+    let result: i32 = driver.runtime.invoke_handle(&handle, ()).unwrap();
+    assert_eq!(result, 10);
+}
+
+#[test]
+fn function_handle_of_other_runtime() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() -> i32 { 5 }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    let other_driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn main() -> i32 { 10 }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    // Both runtimes are at the same generation, but a handle is only valid for
+    // the runtime that resolved it
+    let handle = driver
+        .runtime
+        .resolve("main")
+        .expect("Failed to resolve `main`");
+    assert!(driver.runtime.is_handle_valid(&handle));
+    assert!(!other_driver.runtime.is_handle_valid(&handle));
+    assert!(other_driver
+        .runtime
+        .invoke_handle::<i32, ()>(&handle, ())
+        .is_err());
+}
+
 #[test]
 fn reloadable_function_multi_file() {
     let mut driver = CompileAndRunTestDriver::from_fixture(
@@ -54,6 +124,82 @@ fn reloadable_function_multi_file() {
     assert_invoke_eq!(i32, 10, driver, "main");
 }
 
+#[test]
+fn reloadable_function_pointer() {
+    let mut driver = CompileAndRunTestDriver::from_fixture(
+        r#"
+    //- /mun.toml
+    [package]
+    name="foo"
+    version="0.0.0"
+
+    //- /src/mod.mun
+    use package::foo::double;
+    fn apply(f: fn(i32) -> i32, x: i32) -> i32 { f(x) }
+    pub fn main() -> i32 { apply(double, 3) }
+
+    //- /src/foo.mun
+    pub fn double(x: i32) -> i32 { x * 2 }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    assert_invoke_eq!(i32, 6, driver, "main");
+
+    driver.update_file(
+        "foo.mun",
+        r#"
+    pub fn double(x: i32) -> i32 { x + x + 1 }
+    "#,
+    );
+    assert_invoke_eq!(i32, 7, driver, "main");
+}
+
+#[test]
+fn reloadable_function_pointer_in_struct() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Callback { f: fn(i32) -> i32 }
+    fn double(x: i32) -> i32 { x * 2 }
+    pub fn new_callback() -> Callback { Callback { f: double } }
+    pub fn call(callback: Callback, x: i32) -> i32 {
+        let f = callback.f;
+        f(x)
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let callback: StructRef<'_> = driver.runtime.invoke("new_callback", ()).unwrap();
+    let callback = callback.root();
+    let result: i32 = driver
+        .runtime
+        .invoke("call", (callback.as_ref(&driver.runtime), 3i32))
+        .unwrap();
+    assert_eq!(result, 6);
+
+    driver.update_file(
+        "mod.mun",
+        r#"
+    pub struct Callback { f: fn(i32) -> i32 }
+    fn double(x: i32) -> i32 { x * 2 + 1 }
+    pub fn new_callback() -> Callback { Callback { f: double } }
+    pub fn call(callback: Callback, x: i32) -> i32 {
+        let f = callback.f;
+        f(x)
+    }
+    "#,
+    );
+
+    // The function pointer that was stored before the reload calls the new code
+    let result: i32 = driver
+        .runtime
+        .invoke("call", (callback.as_ref(&driver.runtime), 3i32))
+        .unwrap();
+    assert_eq!(result, 7);
+}
+
 #[test]
 fn reloadable_struct_decl_single_file() {
     let mut driver = CompileAndRunTestDriver::new(
@@ -190,3 +336,213 @@ fn reloadable_struct_decl_multi_file() {
         1
     );
 }
+
+#[test]
+fn statics_are_reset_on_reload() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    pub fn counter() -> i64 {
+        static mut N: i64 = 0;
+        N += 1;
+        N
+    }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    assert_invoke_eq!(i64, 1, driver, "counter");
+    assert_invoke_eq!(i64, 2, driver, "counter");
+
+    driver.update_file(
+        "mod.mun",
+        r"
+    pub fn counter() -> i64 {
+        static mut N: i64 = 0;
+        N += 2;
+        N
+    }
+    ",
+    );
+    assert_invoke_eq!(i64, 2, driver, "counter");
+}
+
+#[test]
+fn statics_are_preserved_on_reload() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    fn start() -> i64 { 10 }
+    pub fn counter() -> i64 {
+        static mut N: i64 = start();
+        N += 1;
+        N
+    }
+    pub fn other() -> i64 {
+        static mut N: i64 = 0;
+        N += 1;
+        N
+    }
+    ",
+        |builder| builder.preserve_statics(true),
+    )
+    .expect("Failed to build test driver");
+    assert_invoke_eq!(i64, 11, driver, "counter");
+    assert_invoke_eq!(i64, 1, driver, "other");
+
+    driver.update_file(
+        "mod.mun",
+        r"
+    fn start() -> i64 { 100 }
+    pub fn counter() -> i64 {
+        static mut N: i64 = start();
+        N += 2;
+        N
+    }
+    pub fn other() -> i32 {
+        static mut N: i32 = 5;
+        N += 1;
+        N
+    }
+    ",
+    );
+
+    // The initializer is not evaluated again
+    assert_invoke_eq!(i64, 13, driver, "counter");
+    // The type of the static changed, so it starts out uninitialized
+    assert_invoke_eq!(i32, 6, driver, "other");
+}
+
+#[test]
+fn warmup() {
+    static INIT_CALLS: AtomicU32 = AtomicU32::new(0);
+    extern "C" fn on_init() {
+        INIT_CALLS.fetch_add(1, Ordering::SeqCst);
+    }
+    extern "C" fn on_init_rebound() {
+        INIT_CALLS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    extern fn on_init();
+    #[init]
+    fn setup() { on_init(); }
+    pub fn main() -> i32 { 5 }
+    ",
+        |builder| builder.insert_fn("on_init", on_init as extern "C" fn()),
+    )
+    .expect("Failed to build test driver");
+
+    let options = WarmupOptions {
+        call_init_functions: true,
+    };
+    let report = driver.runtime.warmup(&options).unwrap();
+    let names = report
+        .functions
+        .iter()
+        .map(|handle| handle.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["main", "setup"]);
+    assert_eq!(report.init_functions_called, ["setup"]);
+    assert_eq!(INIT_CALLS.load(Ordering::SeqCst), 1);
+
+    // Warming up again does not call the init functions again
+    let report = driver.runtime.warmup(&options).unwrap();
+    assert_eq!(report.functions.len(), 2);
+    assert!(report.init_functions_called.is_empty());
+    assert_eq!(INIT_CALLS.load(Ordering::SeqCst), 1);
+
+    let result: i32 = driver
+        .runtime
+        .invoke_handle(&report.functions[0], ())
+        .unwrap();
+    assert_eq!(result, 5);
+
+    // Rebinding an extern refreshes the handles, but does not call the init
+    // functions again
+    driver
+        .runtime
+        .rebind_extern("on_init", on_init_rebound as extern "C" fn())
+        .expect("failed to rebind extern");
+    assert!(!driver.runtime.is_handle_valid(&report.functions[0]));
+    let report = driver.runtime.warmup(&options).unwrap();
+    assert!(driver.runtime.is_handle_valid(&report.functions[0]));
+    assert!(report.init_functions_called.is_empty());
+    assert_eq!(INIT_CALLS.load(Ordering::SeqCst), 1);
+
+    // After a reload the handles are resolved and the init functions are called
+    // again
+    driver.update_file(
+        "mod.mun",
+        r"
+    extern fn on_init();
+    #[init]
+    fn setup() { on_init(); }
+    pub fn main() -> i32 { 10 }
+    ",
+    );
+    let report = driver.runtime.warmup(&options).unwrap();
+    assert!(driver.runtime.is_handle_valid(&report.functions[0]));
+    assert_eq!(report.init_functions_called, ["setup"]);
+    assert_eq!(INIT_CALLS.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn dispatch_table_entries() {
+    extern "C" fn add_int(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    let mut driver = CompileAndRunTestDriver::new(
+        r"
+    extern fn add(a: i32, b: i32) -> i32;
+    pub fn main() -> i32 { add(1, 2) }
+    ",
+        |builder| builder.insert_fn("add", add_int as extern "C" fn(i32, i32) -> i32),
+    )
+    .expect("Failed to build test driver");
+
+    let entries = driver.runtime.dispatch_table_entries();
+    let main = entries
+        .iter()
+        .find(|entry| entry.name == "main")
+        .expect("missing entry for `main`");
+    assert_eq!(main.kind, DispatchTableEntryKind::Assembly);
+    assert!(!main.is_patched);
+    let add = entries
+        .iter()
+        .find(|entry| entry.name == "add")
+        .expect("missing entry for `add`");
+    assert_eq!(add.kind, DispatchTableEntryKind::Extern);
+    assert_eq!(
+        add.fn_ptr,
+        add_int as extern "C" fn(i32, i32) -> i32 as *const std::ffi::c_void
+    );
+    assert!(!add.is_patched);
+    let new = entries
+        .iter()
+        .find(|entry| entry.name == "new")
+        .expect("missing entry for `new`");
+    assert_eq!(new.kind, DispatchTableEntryKind::Intrinsic);
+
+    driver.update_file(
+        "mod.mun",
+        r"
+    extern fn add(a: i32, b: i32) -> i32;
+    pub fn main() -> i32 { add(2, 3) }
+    ",
+    );
+    assert_invoke_eq!(i32, 5, driver, "main");
+
+    let entries = driver.runtime.dispatch_table_entries();
+    let patched_main = entries
+        .iter()
+        .find(|entry| entry.name == "main")
+        .expect("missing entry for `main`");
+    assert!(patched_main.is_patched);
+    assert_ne!(patched_main.fn_ptr, main.fn_ptr);
+    let add = entries
+        .iter()
+        .find(|entry| entry.name == "add")
+        .expect("missing entry for `add`");
+    assert!(!add.is_patched);
+}
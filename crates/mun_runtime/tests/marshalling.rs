@@ -1,4 +1,4 @@
-use mun_runtime::{ArgumentReflection, Marshal, ReturnTypeReflection, StructRef};
+use mun_runtime::{ArgumentReflection, Marshal, RebindError, ReturnTypeReflection, StructRef};
 use mun_test::CompileAndRunTestDriver;
 
 #[macro_use]
@@ -45,6 +45,28 @@ fn arguments() {
     assert_invoke_eq!(i32, a + b, driver, "main", a, b);
 }
 
+#[test]
+fn out_params() {
+    let driver = CompileAndRunTestDriver::new(
+        r"
+        pub fn div_rem(a: i64, b: i64, rem: &mut i64) -> i64 {
+            *rem = a % b;
+            a / b
+        }
+        pub fn main(a: i64, b: i64, rem: &mut i64) -> i64 { div_rem(a, b, rem) }
+    ",
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let mut rem: i64 = 0;
+    assert_invoke_eq!(i64, 2, driver, "div_rem", 7i64, 3i64, &mut rem as *mut i64);
+    assert_eq!(rem, 1);
+
+    assert_invoke_eq!(i64, 3, driver, "main", 17i64, 5i64, &mut rem as *mut i64);
+    assert_eq!(rem, 2);
+}
+
 #[test]
 fn dispatch_table() {
     let driver = CompileAndRunTestDriver::new(
@@ -318,6 +340,49 @@ fn fields() {
     assert_invoke_eq!(bool, true, driver, "main", 48i32);
 }
 
+#[test]
+fn field_shorthand() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+        struct(value) Foo { a: i32, b: i32 };
+        pub fn main(a: i32, c: i32) -> i32 {
+            let foo = Foo { b: c, a };
+            foo.a - foo.b
+        }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 4, driver, "main", 7i32, 3i32);
+}
+
+#[test]
+fn field_spread() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+        struct(gc) Foo { a: i32, b: i32, c: i32 };
+        struct(value) Bar { a: i32, b: i32 };
+        pub fn foo(a: i32, b: i32, c: i32) -> i32 {
+            let base = Foo { a, b, c };
+            let foo = Foo { b: 10, ..base };
+            base.a = 0;
+            foo.a * 100 + foo.b * 10 + foo.c
+        }
+        pub fn bar(a: i32, b: i32) -> i32 {
+            let bar = Bar { a: 5, ..Bar { a, b } };
+            bar.a * 10 + bar.b
+        }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    // Fields that are not written out are copied from the base
+    assert_invoke_eq!(i32, 203, driver, "foo", 1i32, 2i32, 3i32);
+    assert_invoke_eq!(i32, 57, driver, "bar", 1i32, 7i32);
+}
+
 #[test]
 fn field_crash() {
     let driver = CompileAndRunTestDriver::new(
@@ -625,6 +690,74 @@ fn extern_fn_invalid_sig() {
     assert_invoke_eq!(isize, 16, driver, "main");
 }
 
+#[test]
+fn extern_fn_rebind() {
+    extern "C" fn add_int(a: i32, b: i32) -> i32 {
+        a + b
+    }
+
+    extern "C" fn mul_int(a: i32, b: i32) -> i32 {
+        a * b
+    }
+
+    extern "C" fn add_float(a: f32, b: f32) -> f32 {
+        a + b
+    }
+
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+    extern fn add(a: i32, b: i32) -> i32;
+    pub fn main() -> i32 { add(3,4) }
+    "#,
+        |builder| builder.insert_fn("add", add_int as extern "C" fn(i32, i32) -> i32),
+    )
+    .expect("Failed to build test driver");
+
+    assert_invoke_eq!(i32, 7, driver, "main");
+    let handle = driver.runtime.resolve("main").unwrap();
+
+    driver
+        .runtime
+        .rebind_extern("add", mul_int as extern "C" fn(i32, i32) -> i32)
+        .expect("failed to rebind extern");
+    assert_invoke_eq!(i32, 12, driver, "main");
+    assert!(!driver.runtime.is_handle_valid(&handle));
+
+    let add = driver
+        .runtime
+        .dispatch_table_entries()
+        .into_iter()
+        .find(|entry| entry.name == "add")
+        .expect("missing entry for `add`");
+    assert!(add.is_patched);
+
+    assert!(matches!(
+        driver
+            .runtime
+            .rebind_extern("sub", mul_int as extern "C" fn(i32, i32) -> i32),
+        Err(RebindError::UnknownExtern(name)) if name == "sub"
+    ));
+    assert!(matches!(
+        driver
+            .runtime
+            .rebind_extern("main", mul_int as extern "C" fn(i32, i32) -> i32),
+        Err(RebindError::UnknownExtern(_))
+    ));
+    assert!(matches!(
+        driver
+            .runtime
+            .rebind_extern("new", mul_int as extern "C" fn(i32, i32) -> i32),
+        Err(RebindError::UnknownExtern(name)) if name == "new"
+    ));
+    assert!(matches!(
+        driver
+            .runtime
+            .rebind_extern("add", add_float as extern "C" fn(f32, f32) -> f32),
+        Err(RebindError::MismatchedSignature { .. })
+    ));
+    assert_invoke_eq!(i32, 12, driver, "main");
+}
+
 #[test]
 fn test_primitive_types() {
     fn test_field<
@@ -1,5 +1,12 @@
-use mun_runtime::{ArrayRef, StructRef};
-use mun_test::CompileAndRunTestDriver;
+use mun_compiler::Config;
+use mun_libloader::MunLibrary;
+use mun_memory::{
+    gc::{GcPtr, HasIndirectionPtr},
+    type_table::TypeTable,
+    TryFromAbiError, Type,
+};
+use mun_runtime::{AllocationSite, ArrayRef, GcConfig, HeapLimitExceeded, RestoreError, StructRef};
+use mun_test::{CompileAndRunTestDriver, CompileTestDriver};
 
 #[macro_use]
 mod util;
@@ -43,6 +50,73 @@ fn gc_trace() {
     assert_eq!(runtime.gc_stats().allocated_memory, 0);
 }
 
+#[test]
+fn gc_max_heap() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo {
+        a: i64,
+        b: i64,
+    }
+
+    pub fn new_foo() -> Foo {
+        Foo { a: 1, b: 2 }
+    }
+
+    pub fn new_foos(n: i64) -> i64 {
+        let i = 0;
+        while i < n {
+            let foo = Foo { a: i, b: i };
+            i += 1;
+        }
+        n
+    }
+    "#,
+        |builder| {
+            builder.gc_config(GcConfig {
+                initial_heap: 0,
+                max_heap: Some(48),
+            })
+        },
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+    let value: StructRef<'_> = runtime.invoke("new_foo", ()).unwrap();
+    let value = value.root();
+    let _: StructRef<'_> = runtime.invoke("new_foo", ()).unwrap();
+    assert_eq!(runtime.gc_stats().allocated_memory, 32);
+
+    // The unreferenced struct is collected to make room for the array
+    let array = runtime.construct_array([1i64]).unwrap();
+    let array_size = runtime.gc_stats().allocated_memory - 16;
+    assert!(array_size > 16);
+
+    // An allocation that still exceeds the heap limit fails
+    let error = runtime.construct_array([1i64]).unwrap_err();
+    assert_eq!(
+        error,
+        HeapLimitExceeded {
+            size: array_size,
+            allocated_memory: 16 + array_size,
+            max_heap: 48,
+        }
+    );
+
+    // Mun code that exceeds the heap limit fails the invocation, after which its
+    // garbage is collected
+    let result: Result<i64, _> = runtime.invoke("new_foos", (10i64,));
+    let error = result.unwrap_err();
+    assert_eq!(error.heap_limit_exceeded().map(|e| e.size), Some(16));
+    assert_eq!(runtime.gc_stats().allocated_memory, 16 + array_size);
+
+    drop(array);
+    let result: i64 = runtime.invoke("new_foos", (1i64,)).unwrap();
+    assert_eq!(result, 1);
+
+    drop(value);
+}
+
 #[test]
 fn map_struct_insert_field1() {
     let mut driver = CompileAndRunTestDriver::new(
@@ -2511,3 +2585,269 @@ fn test_type_table() {
     // These types should be equal
     assert_eq!(foo_bar_field_type, bar_type);
 }
+
+#[test]
+fn struct_definitions_across_assemblies() {
+    let drivers = [
+        "pub struct(value) Vec2 { x: f32, y: f32 }",
+        "pub struct(value) Vec2 { x: f32, y: f32 }",
+        "pub struct(value) Vec2 { x: f64, y: f64 }",
+    ]
+    .map(CompileTestDriver::from_file);
+
+    // Safety: the libraries were just produced by the compiler
+    let libraries = drivers
+        .iter()
+        .map(|driver| unsafe { MunLibrary::new(driver.lib_path()) }.expect("failed to load munlib"))
+        .collect::<Vec<_>>();
+    let [first, identical, different] = [0, 1, 2].map(|idx| unsafe { libraries[idx].get_info() });
+
+    let (type_table, first_types) =
+        Type::try_from_abi(first.symbols.types(), TypeTable::default()).unwrap();
+
+    // An identical definition shares the existing type
+    let (type_table, identical_types) =
+        Type::try_from_abi(identical.symbols.types(), type_table).unwrap();
+    assert_eq!(first_types, identical_types);
+
+    // A different definition with the same name conflicts with the existing type
+    let error = Type::try_from_abi(different.symbols.types(), type_table)
+        .err()
+        .expect("expected conflicting definitions");
+    assert!(
+        matches!(&error, TryFromAbiError::ConflictingDefinitions(name) if name == "Vec2"),
+        "{error}"
+    );
+}
+
+#[test]
+fn snapshot_restore() {
+    let mut driver = CompileAndRunTestDriver::new(
+        r#"
+        pub struct Foo {
+            a: i64,
+            bar: Bar,
+        }
+
+        pub struct Bar {
+            b: f64,
+        }
+
+        pub fn foo_new(a: i64, b: f64) -> Foo {
+            Foo { a, bar: Bar { b } }
+        }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let foo_struct: StructRef<'_> = driver.runtime.invoke("foo_new", (1i64, 2.0f64)).unwrap();
+    let foo_struct = foo_struct.root();
+
+    let runtime = &driver.runtime;
+    let snapshot = runtime.snapshot();
+    assert_eq!(snapshot.len(), 2);
+
+    let mut foo = foo_struct.as_ref(runtime);
+    foo.set("a", 3i64).unwrap();
+    let mut bar: StructRef<'_> = foo.get("bar").unwrap();
+    bar.set("b", 4.0f64).unwrap();
+
+    let restored = runtime.restore(&snapshot).unwrap();
+    assert_eq!(restored.len(), 1);
+
+    let foo = foo_struct.as_ref(runtime);
+    assert_eq!(foo.get::<i64>("a").unwrap(), 1);
+    let bar: StructRef<'_> = foo.get("bar").unwrap();
+    assert_eq!(bar.get::<f64>("b").unwrap(), 2.0);
+
+    // Changing the layout of `Foo` invalidates the snapshot
+    driver.update_file(
+        "mod.mun",
+        r#"
+        pub struct Foo {
+            a: i64,
+            c: i64,
+            bar: Bar,
+        }
+
+        pub struct Bar {
+            b: f64,
+        }
+    "#,
+    );
+
+    assert!(matches!(
+        driver.runtime.restore(&snapshot),
+        Err(RestoreError::TypeChanged(_))
+    ));
+}
+
+#[test]
+fn snapshot_restore_after_collect() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+        pub struct Foo {
+            a: i64,
+            bar: Bar,
+        }
+
+        pub struct Bar {
+            b: f64,
+        }
+
+        pub fn foo_new(a: i64, b: f64) -> Foo {
+            Foo { a, bar: Bar { b } }
+        }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+    let foo_struct: StructRef<'_> = runtime.invoke("foo_new", (1i64, 2.0f64)).unwrap();
+    let foo_struct = foo_struct.root();
+
+    let snapshot = runtime.snapshot();
+    let foo_handle = snapshot.roots().next().unwrap();
+
+    // Collect the objects, so restoring has to reallocate them
+    drop(foo_struct);
+    assert!(runtime.gc_collect());
+    assert_eq!(runtime.gc_stats().allocated_memory, 0);
+
+    let restored = runtime.restore(&snapshot).unwrap();
+    let foo = &restored[&foo_handle];
+
+    // The restored objects are rooted, so collecting doesn't free them
+    assert!(!runtime.gc_collect());
+
+    #[repr(C)]
+    struct Foo {
+        a: i64,
+        bar: GcPtr,
+    }
+
+    unsafe {
+        let foo = &*foo.deref::<Foo>();
+        assert_eq!(foo.a, 1);
+        assert_eq!(*foo.bar.deref::<f64>(), 2.0);
+    }
+
+    // Dropping the roots allows the restored objects to be collected
+    drop(restored);
+    assert!(runtime.gc_collect());
+    assert_eq!(runtime.gc_stats().allocated_memory, 0);
+}
+
+#[test]
+fn alloc_report() {
+    let mut driver = CompileAndRunTestDriver::with_compiler_config(
+        r#"
+    pub struct Foo { a: i64 }
+    pub fn new_foo() -> Foo {
+        Foo { a: 1 }
+    }
+    pub fn new_array() -> [i64] {
+        [1, 2, 3]
+    }
+    "#,
+        |config| Config {
+            alloc_debug: true,
+            ..config
+        },
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+    let foo: StructRef<'_> = runtime.invoke("new_foo", ()).unwrap();
+    let foo = foo.root();
+    let other_foo: StructRef<'_> = runtime.invoke("new_foo", ()).unwrap();
+    let other_foo = other_foo.root();
+    let array: ArrayRef<'_, i64> = runtime.invoke("new_array", ()).unwrap();
+    let array = array.root();
+
+    let expected = vec![
+        AllocationSite {
+            location: String::from("mod.mun:4:9"),
+            count: 2,
+        },
+        AllocationSite {
+            location: String::from("mod.mun:7:9"),
+            count: 1,
+        },
+    ];
+    assert_eq!(runtime.alloc_report().sites, expected);
+
+    // The recorded locations survive hot reloading
+    driver.update_file(
+        "mod.mun",
+        r#"
+    pub struct Foo { a: i64, b: f64 }
+    "#,
+    );
+    assert_eq!(driver.runtime.alloc_report().sites, expected);
+
+    // Collected objects are no longer reported
+    drop(other_foo);
+    assert!(driver.runtime.gc_collect());
+    let report = driver.runtime.alloc_report();
+    assert_eq!(report.total(), 2);
+    assert!(report.sites.iter().all(|site| site.count == 1));
+
+    drop(foo);
+    drop(array);
+}
+
+#[test]
+fn alloc_report_disabled() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo { a: i64 }
+    pub fn new_foo() -> Foo {
+        Foo { a: 1 }
+    }
+    "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+    let foo: StructRef<'_> = runtime.invoke("new_foo", ()).unwrap();
+    let _foo = foo.root();
+
+    assert!(runtime.alloc_report().sites.is_empty());
+}
+
+#[test]
+fn uninit_structs() {
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r#"
+    pub struct Bar { a: i64 }
+    pub struct Foo { a: i64, bar: Bar, b: f64 }
+    pub fn new_foo() -> Foo {
+        Foo { a: 1, bar: Bar { a: 2 }, b: 3.0 }
+    }
+    "#,
+        |config| Config {
+            init_structs: false,
+            ..config
+        },
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let runtime = &driver.runtime;
+    let foo: StructRef<'_> = runtime.invoke("new_foo", ()).unwrap();
+    let foo = foo.root();
+
+    // Collecting keeps the objects that are referenced by the rooted struct alive
+    runtime.gc_collect();
+
+    let foo = foo.as_ref(runtime);
+    assert_eq!(foo.get::<i64>("a").unwrap(), 1);
+    assert_eq!(foo.get::<f64>("b").unwrap(), 3.0);
+    let bar = foo.get::<StructRef<'_>>("bar").unwrap();
+    assert_eq!(bar.get::<i64>("a").unwrap(), 2);
+}
@@ -1,3 +1,5 @@
+use mun_abi as abi;
+use mun_compiler::Config;
 use mun_runtime::LinkFunctionsError;
 use mun_test::CompileAndRunTestDriver;
 
@@ -18,6 +20,324 @@ fn invoke() {
     assert_eq!(123 + 456, result);
 }
 
+#[test]
+fn unit_values() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    struct Foo { a: (), b: i32 }
+    fn bar() {}
+    fn baz(a: ()) -> () { return a; }
+    pub fn main() -> i32 {
+        let x = ();
+        x = bar();
+        x = if true { bar() };
+        let foo = Foo { a: x, b: 5 };
+        foo.a = baz(());
+        foo.b
+    }
+        "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: i32 = driver.runtime.invoke("main", ()).unwrap();
+    assert_eq!(result, 5);
+}
+
+#[test]
+fn build_info() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn main() {}
+        "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let assembly = driver
+        .runtime
+        .assemblies()
+        .next()
+        .expect("no assembly was loaded");
+    let build_info = assembly.build_info();
+    assert_eq!(
+        build_info.optimization_level,
+        abi::OptimizationLevel::Default
+    );
+    assert!(!build_info.has_debug_info);
+    assert!(!build_info.target_triple().is_empty());
+}
+
+#[test]
+fn build_info_minimize_size() {
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r#"
+    pub fn main() {}
+        "#,
+        |config| Config {
+            minimize_size: true,
+            ..config
+        },
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let assembly = driver
+        .runtime
+        .assemblies()
+        .next()
+        .expect("no assembly was loaded");
+    assert_eq!(
+        assembly.build_info().optimization_level,
+        abi::OptimizationLevel::Size
+    );
+}
+
+#[test]
+fn compiler_info() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn main() {}
+        "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let assembly = driver
+        .runtime
+        .assemblies()
+        .next()
+        .expect("no assembly was loaded");
+    let compiler_info = assembly.compiler_info();
+    assert!(!compiler_info.compiler_version().is_empty());
+    assert!(!compiler_info.llvm_version().is_empty());
+}
+
+#[test]
+fn constants() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub const MAX: i64 = 10 * 4 + 2;
+    pub const SCALE: f64 = 1.5;
+    pub const ENABLED: bool = MAX > 40;
+    const HIDDEN: i64 = 1;
+
+    pub fn main() -> i64 { MAX + HIDDEN }
+        "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let assembly = driver
+        .runtime
+        .assemblies()
+        .next()
+        .expect("no assembly was loaded");
+    assert_eq!(assembly.constant::<i64>("MAX"), Some(42));
+    assert_eq!(assembly.constant::<f64>("SCALE"), Some(1.5));
+    assert_eq!(assembly.constant::<bool>("ENABLED"), Some(true));
+    assert_eq!(assembly.constant::<i32>("MAX"), None);
+    assert_eq!(assembly.constant::<i64>("HIDDEN"), None);
+
+    let result: i64 = driver.runtime.invoke("main", ()).unwrap();
+    assert_eq!(result, 43);
+}
+
+#[test]
+fn loop_continue() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub fn sum_odd(n: i32) -> i32 {
+        let i = 0;
+        let sum = 0;
+        loop {
+            i += 1;
+            if i > n {
+                break sum;
+            }
+            if i % 2 == 0 {
+                continue;
+            }
+            sum += i;
+        }
+    }
+
+    pub fn count_not_divisible_by_three(n: i32) -> i32 {
+        let i = 0;
+        let count = 0;
+        while i < n {
+            i += 1;
+            if i % 3 == 0 {
+                continue;
+            }
+            count += 1;
+        }
+        count
+    }
+
+    fn nothing() {}
+
+    pub fn unit_breaks(a: bool) {
+        loop {
+            if a {
+                break nothing();
+            }
+            break;
+        }
+    }
+        "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+
+    let result: i32 = driver.runtime.invoke("sum_odd", (10i32,)).unwrap();
+    assert_eq!(result, 1 + 3 + 5 + 7 + 9);
+    let result: i32 = driver
+        .runtime
+        .invoke("count_not_divisible_by_three", (10i32,))
+        .unwrap();
+    assert_eq!(result, 7);
+    let _: () = driver.runtime.invoke("unit_breaks", (true,)).unwrap();
+    let _: () = driver.runtime.invoke("unit_breaks", (false,)).unwrap();
+}
+
+#[test]
+fn custom_entry_symbol() {
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r#"
+    pub fn sum(a: i32, b: i32) -> i32 { a + b }
+        "#,
+        |config| Config {
+            entry_symbol: Some(String::from("my_scripts")),
+            ..config
+        },
+        |builder| builder.entry_symbol("my_scripts"),
+    )
+    .expect("Failed to build test driver");
+
+    let result: i32 = driver.runtime.invoke("sum", (123i32, 456i32)).unwrap();
+    assert_eq!(123 + 456, result);
+}
+
+#[test]
+fn mismatched_entry_symbol() {
+    let compile_with_entry_symbol = |config| Config {
+        entry_symbol: Some(String::from("my_scripts")),
+        ..config
+    };
+
+    let err = CompileAndRunTestDriver::with_compiler_config(
+        r#"
+    pub fn main() {}
+        "#,
+        compile_with_entry_symbol,
+        |builder| builder,
+    )
+    .expect_err("loading a munlib with a different entry symbol should fail");
+    assert!(err.to_string().contains("entry symbol `get_info`"));
+
+    let err = CompileAndRunTestDriver::new(
+        r#"
+    pub fn main() {}
+        "#,
+        |builder| builder.entry_symbol("my_scripts"),
+    )
+    .expect_err("loading a munlib with a different entry symbol should fail");
+    assert!(err.to_string().contains("entry symbol `my_scripts`"));
+}
+
+#[test]
+fn versioned_symbols() {
+    let compile_with_version = |version: &'static str| {
+        move |config: Config| Config {
+            entry_symbol: Some(String::from("my_scripts")),
+            symbol_version: Some(String::from(version)),
+            ..config
+        }
+    };
+
+    let driver = CompileAndRunTestDriver::with_compiler_config(
+        r#"
+    pub fn sum(a: i32, b: i32) -> i32 { a + b }
+        "#,
+        compile_with_version("1.2.0"),
+        |builder| builder.entry_symbol("my_scripts").symbol_version("1.2.0"),
+    )
+    .expect("Failed to build test driver");
+
+    let result: i32 = driver.runtime.invoke("sum", (123i32, 456i32)).unwrap();
+    assert_eq!(123 + 456, result);
+
+    let err = CompileAndRunTestDriver::with_compiler_config(
+        r#"
+    pub fn main() {}
+        "#,
+        compile_with_version("1.2.0"),
+        |builder| builder.entry_symbol("my_scripts").symbol_version("1.3.0"),
+    )
+    .expect_err("loading a munlib with a different symbol version should fail");
+    assert!(err.to_string().contains("entry symbol `my_scripts_v1_3_0`"));
+}
+
+#[test]
+fn abi_fingerprint() {
+    let driver = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo { a: i32 }
+    pub fn sum(a: i32, b: i32) -> i32 { a + b }
+        "#,
+        |builder| builder,
+    )
+    .expect("Failed to build test driver");
+    let fingerprint = driver
+        .runtime
+        .assemblies()
+        .next()
+        .expect("no assembly was loaded")
+        .abi_fingerprint();
+
+    // Changing a function body does not change the ABI
+    CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo { a: i32 }
+    pub fn sum(a: i32, b: i32) -> i32 { b + a }
+        "#,
+        |builder| builder.expected_abi_fingerprint(fingerprint),
+    )
+    .expect("loading a munlib with the expected ABI should succeed");
+
+    // Neither do optimization hints
+    CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo { a: i32 }
+    #[cold]
+    pub fn sum(a: i32, b: i32) -> i32 { a + b }
+        "#,
+        |builder| builder.expected_abi_fingerprint(fingerprint),
+    )
+    .expect("loading a munlib with the expected ABI should succeed");
+
+    let err = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo { a: i32 }
+    pub fn sum(a: i64, b: i64) -> i64 { a + b }
+        "#,
+        |builder| builder.expected_abi_fingerprint(fingerprint),
+    )
+    .expect_err("loading a munlib with a different signature should fail");
+    assert!(err.to_string().contains("ABI changed"), "{err}");
+
+    let err = CompileAndRunTestDriver::new(
+        r#"
+    pub struct Foo { a: i32, b: i32 }
+    pub fn sum(a: i32, b: i32) -> i32 { a + b }
+        "#,
+        |builder| builder.expected_abi_fingerprint(fingerprint),
+    )
+    .expect_err("loading a munlib with a different struct layout should fail");
+    assert!(err.to_string().contains("ABI changed"), "{err}");
+}
+
 #[test]
 fn arrays_are_collected() {
     let driver = CompileAndRunTestDriver::new(
@@ -0,0 +1,89 @@
+use mun_compiler::Config;
+use mun_test::CompileAndRunTestDriver;
+
+/// The bindings that the compiler generated for `rust_bindings/fixture.mun`
+#[allow(dead_code)]
+mod bindings {
+    include!("rust_bindings/fixture.rs");
+}
+
+const FIXTURE: &str = include_str!("rust_bindings/fixture.mun");
+
+fn emit_rust_bindings(config: Config) -> Config {
+    Config {
+        emit_rust_bindings: true,
+        ..config
+    }
+}
+
+/// Reads the bindings that were generated next to the assembly of `driver`.
+fn generated_bindings(driver: &CompileAndRunTestDriver) -> String {
+    std::fs::read_to_string(driver.lib_path().with_extension("rs"))
+        .expect("the compiler did not generate Rust bindings")
+}
+
+#[test]
+fn bindings_are_up_to_date() {
+    let driver =
+        CompileAndRunTestDriver::with_compiler_config(FIXTURE, emit_rust_bindings, |builder| {
+            builder
+        })
+        .expect("Failed to build test driver");
+
+    assert_eq!(
+        generated_bindings(&driver),
+        include_str!("rust_bindings/fixture.rs"),
+        "the bindings in `rust_bindings/fixture.rs` are stale, regenerate them from \
+         `rust_bindings/fixture.mun`"
+    );
+}
+
+#[test]
+fn invoke_bindings() {
+    let driver = CompileAndRunTestDriver::new(FIXTURE, |builder| builder)
+        .expect("Failed to build test driver");
+    let runtime = &driver.runtime;
+
+    assert_eq!(bindings::add(runtime, 1, 2.5).unwrap(), 3.5);
+
+    // Value structs are copied out of and into structs that are owned by the
+    // runtime
+    let mut value = bindings::new_vec2(runtime, 1.0, 2.0).unwrap();
+    assert_eq!(
+        bindings::Vec2::from_struct_ref(&value).unwrap(),
+        bindings::Vec2 { x: 1.0, y: 2.0 }
+    );
+    bindings::Vec2 { x: 3.0, y: 4.0 }
+        .to_struct_ref(&mut value)
+        .unwrap();
+    assert_eq!(bindings::length_squared(runtime, value).unwrap(), 25.0);
+}
+
+#[test]
+fn regenerate_bindings_after_signature_change() {
+    let mut driver =
+        CompileAndRunTestDriver::with_compiler_config(FIXTURE, emit_rust_bindings, |builder| {
+            builder
+        })
+        .expect("Failed to build test driver");
+
+    let changed = FIXTURE.replace(
+        "pub fn add(a: i64, b: f64) -> f64 {\n    a as f64 + b\n}",
+        "pub fn add(a: i64, b: i64) -> i64 {\n    a + b\n}",
+    );
+    assert_ne!(changed, FIXTURE);
+    driver.update_file("mod.mun", &changed);
+
+    // The runtime rejects calls through the stale bindings
+    assert!(bindings::add(&driver.runtime, 1, 2.5).is_err());
+
+    // The regenerated bindings match the new signature
+    let regenerated = generated_bindings(&driver);
+    assert!(regenerated.contains(
+        "pub fn add(\n    runtime: &Runtime,\n    a: i64,\n    b: i64,\n) -> Result<i64, \
+         InvokeErr<'static, (i64, i64)>> {\n    runtime.invoke(\"add\", (a, b))\n}"
+    ));
+    assert!(!regenerated.contains("b: f64"));
+    let result: i64 = driver.runtime.invoke("add", (1i64, 2i64)).unwrap();
+    assert_eq!(result, 3);
+}
@@ -0,0 +1,62 @@
+// Generated by the Mun compiler from the `mod` assembly. Do not edit.
+//
+// The functions invoke the assembly through a `Runtime` that loaded it. The
+// runtime checks every invocation against the signature in the assembly, so
+// the bindings have to be regenerated when the assembly changes.
+
+#[allow(unused_imports)]
+use mun_runtime::{ArrayRef, InvokeErr, Runtime, StructRef};
+
+/// A copy of a value of the Mun value struct `Vec2`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vec2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+const _: () = assert!(std::mem::size_of::<Vec2>() == 8);
+const _: () = assert!(std::mem::align_of::<Vec2>() == 4);
+
+impl Vec2 {
+    /// Copies the fields of a `Vec2` that is owned by the runtime.
+    pub fn from_struct_ref(value: &StructRef<'_>) -> Result<Self, String> {
+        Ok(Self {
+            x: value.get::<f32>("x")?,
+            y: value.get::<f32>("y")?,
+        })
+    }
+
+    /// Copies the fields into a `Vec2` that is owned by the runtime.
+    pub fn to_struct_ref(&self, value: &mut StructRef<'_>) -> Result<(), String> {
+        value.set("x", self.x)?;
+        value.set("y", self.y)?;
+        Ok(())
+    }
+}
+
+/// Invokes the Mun function `add`.
+pub fn add(
+    runtime: &Runtime,
+    a: i64,
+    b: f64,
+) -> Result<f64, InvokeErr<'static, (i64, f64)>> {
+    runtime.invoke("add", (a, b))
+}
+
+/// Invokes the Mun function `length_squared`.
+pub fn length_squared<'r>(
+    runtime: &'r Runtime,
+    v: StructRef<'r>,
+) -> Result<f32, InvokeErr<'static, (StructRef<'r>,)>> {
+    runtime.invoke("length_squared", (v,))
+}
+
+/// Invokes the Mun function `new_vec2`.
+pub fn new_vec2<'r>(
+    runtime: &'r Runtime,
+    x: f32,
+    y: f32,
+) -> Result<StructRef<'r>, InvokeErr<'static, (f32, f32)>> {
+    runtime.invoke("new_vec2", (x, y))
+}
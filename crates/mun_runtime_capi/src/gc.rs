@@ -29,7 +29,7 @@ pub unsafe extern "C" fn mun_gc_alloc(runtime: Runtime, ty: Type, obj: *mut GcPt
         .map_err(|e| format!("invalid argument 'obj': {e}"))
         .map(ManuallyDrop::new));
     let obj = try_deref_mut!(obj);
-    *obj = runtime.gc().alloc(&ty);
+    *obj = mun_error_try!(runtime.gc().alloc(&ty).map_err(|e| e.to_string()));
     ErrorHandle::default()
 }
 
@@ -171,10 +171,12 @@ pub unsafe extern "C" fn mun_runtime_create(
                 name: name.to_owned(),
                 signature: FunctionSignature {
                     arg_types,
+                    arg_names: None,
                     return_type,
                 },
             },
             fn_ptr: def.fn_ptr,
+            attributes: None,
         })
     })
     .collect::<Result<_, _>>());
@@ -183,6 +185,10 @@ pub unsafe extern "C" fn mun_runtime_create(
         library_path: library_path.into(),
         user_functions,
         type_table,
+        gc_config: Default::default(),
+        entry_symbols: Default::default(),
+        preserve_statics: false,
+        expected_abi_fingerprint: None,
     };
 
     let runtime = match mun_runtime::Runtime::new(runtime_options) {
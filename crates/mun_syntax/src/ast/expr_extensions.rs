@@ -2,7 +2,7 @@ use std::ops::Add;
 
 use crate::{
     ast::{self, child_opt, children, AstChildren, AstToken, BinExpr, Literal},
-    AstNode, SyntaxKind, SyntaxToken, TextRange, TextSize,
+    AstNode, SyntaxKind, SyntaxNode, SyntaxToken, TextRange, TextSize,
 };
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -11,6 +11,10 @@ pub enum PrefixOp {
     Not,
     /// The `-` operator for negation
     Neg,
+    /// The `&` operator for taking the address of a function
+    AddrOf,
+    /// The `*` operator for dereferencing a mutable reference
+    Deref,
 }
 
 impl ast::PrefixExpr {
@@ -18,6 +22,8 @@ impl ast::PrefixExpr {
         match self.op_token()?.kind() {
             T![!] => Some(PrefixOp::Not),
             T![-] => Some(PrefixOp::Neg),
+            T![&] => Some(PrefixOp::AddrOf),
+            T![*] => Some(PrefixOp::Deref),
             _ => None,
         }
     }
@@ -265,3 +271,29 @@ impl ast::IndexExpr {
         children(self).nth(1)
     }
 }
+
+/// Returns the `'label` token of a labeled loop or of a `break` or `continue`
+/// expression that refers to one.
+fn lifetime_token(node: &SyntaxNode) -> Option<SyntaxToken> {
+    node.children_with_tokens()
+        .find(|e| e.kind() == SyntaxKind::LIFETIME)
+        .and_then(rowan::NodeOrToken::into_token)
+}
+
+impl ast::Label {
+    pub fn lifetime(&self) -> Option<SyntaxToken> {
+        lifetime_token(self.syntax())
+    }
+}
+
+impl ast::BreakExpr {
+    pub fn lifetime(&self) -> Option<SyntaxToken> {
+        lifetime_token(self.syntax())
+    }
+}
+
+impl ast::ContinueExpr {
+    pub fn lifetime(&self) -> Option<SyntaxToken> {
+        lifetime_token(self.syntax())
+    }
+}
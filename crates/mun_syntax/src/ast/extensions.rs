@@ -222,6 +222,16 @@ impl ast::Visibility {
     }
 }
 
+impl ast::StaticStmt {
+    /// Returns true if the static is declared `mut`, e.g. `static mut N: i64 =
+    /// 0;`.
+    pub fn is_mut(&self) -> bool {
+        self.syntax()
+            .children_with_tokens()
+            .any(|it| it.kind() == T![mut])
+    }
+}
+
 impl ast::UseTree {
     pub fn has_star_token(&self) -> bool {
         self.syntax()
@@ -173,6 +173,42 @@ impl AssociatedItemList {
     }
 }
 
+// Attr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Attr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Attr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, ATTR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Attr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl Attr {
+    pub fn name_ref(&self) -> Option<NameRef> {
+        super::child_opt(self)
+    }
+
+    pub fn arg_list(&self) -> Option<ArgList> {
+        super::child_opt(self)
+    }
+
+    pub fn literal(&self) -> Option<Literal> {
+        super::child_opt(self)
+    }
+}
+
 // BinExpr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -315,6 +351,38 @@ impl CallExpr {
     }
 }
 
+// CastExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CastExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for CastExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, CAST_EXPR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(CastExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl CastExpr {
+    pub fn expr(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+
+    pub fn type_ref(&self) -> Option<TypeRef> {
+        super::child_opt(self)
+    }
+}
+
 // Condition
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -347,6 +415,63 @@ impl Condition {
     }
 }
 
+// ConstDef
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConstDef {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ConstDef {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, CONST_DEF)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ConstDef { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::NameOwner for ConstDef {}
+impl ast::VisibilityOwner for ConstDef {}
+impl ast::DocCommentsOwner for ConstDef {}
+impl ast::TypeAscriptionOwner for ConstDef {}
+impl ast::AttrsOwner for ConstDef {}
+impl ConstDef {
+    pub fn initializer(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
+// ContinueExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContinueExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ContinueExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, CONTINUE_EXPR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ContinueExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ContinueExpr {}
+
 // Expr
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -368,12 +493,16 @@ impl AstNode for Expr {
                 | IF_EXPR
                 | LOOP_EXPR
                 | WHILE_EXPR
+                | FOR_EXPR
                 | RETURN_EXPR
                 | BREAK_EXPR
+                | CONTINUE_EXPR
                 | BLOCK_EXPR
                 | ARRAY_EXPR
                 | INDEX_EXPR
                 | RECORD_LIT
+                | TUPLE_EXPR
+                | CAST_EXPR
         )
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
@@ -399,12 +528,16 @@ pub enum ExprKind {
     IfExpr(IfExpr),
     LoopExpr(LoopExpr),
     WhileExpr(WhileExpr),
+    ForExpr(ForExpr),
     ReturnExpr(ReturnExpr),
     BreakExpr(BreakExpr),
+    ContinueExpr(ContinueExpr),
     BlockExpr(BlockExpr),
     ArrayExpr(ArrayExpr),
     IndexExpr(IndexExpr),
     RecordLit(RecordLit),
+    TupleExpr(TupleExpr),
+    CastExpr(CastExpr),
 }
 impl From<Literal> for Expr {
     fn from(n: Literal) -> Expr {
@@ -456,6 +589,11 @@ impl From<WhileExpr> for Expr {
         Expr { syntax: n.syntax }
     }
 }
+impl From<ForExpr> for Expr {
+    fn from(n: ForExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
 impl From<ReturnExpr> for Expr {
     fn from(n: ReturnExpr) -> Expr {
         Expr { syntax: n.syntax }
@@ -466,6 +604,11 @@ impl From<BreakExpr> for Expr {
         Expr { syntax: n.syntax }
     }
 }
+impl From<ContinueExpr> for Expr {
+    fn from(n: ContinueExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
 impl From<BlockExpr> for Expr {
     fn from(n: BlockExpr) -> Expr {
         Expr { syntax: n.syntax }
@@ -486,6 +629,16 @@ impl From<RecordLit> for Expr {
         Expr { syntax: n.syntax }
     }
 }
+impl From<TupleExpr> for Expr {
+    fn from(n: TupleExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
+impl From<CastExpr> for Expr {
+    fn from(n: CastExpr) -> Expr {
+        Expr { syntax: n.syntax }
+    }
+}
 
 impl Expr {
     pub fn kind(&self) -> ExprKind {
@@ -500,12 +653,18 @@ impl Expr {
             IF_EXPR => ExprKind::IfExpr(IfExpr::cast(self.syntax.clone()).unwrap()),
             LOOP_EXPR => ExprKind::LoopExpr(LoopExpr::cast(self.syntax.clone()).unwrap()),
             WHILE_EXPR => ExprKind::WhileExpr(WhileExpr::cast(self.syntax.clone()).unwrap()),
+            FOR_EXPR => ExprKind::ForExpr(ForExpr::cast(self.syntax.clone()).unwrap()),
             RETURN_EXPR => ExprKind::ReturnExpr(ReturnExpr::cast(self.syntax.clone()).unwrap()),
             BREAK_EXPR => ExprKind::BreakExpr(BreakExpr::cast(self.syntax.clone()).unwrap()),
+            CONTINUE_EXPR => {
+                ExprKind::ContinueExpr(ContinueExpr::cast(self.syntax.clone()).unwrap())
+            }
             BLOCK_EXPR => ExprKind::BlockExpr(BlockExpr::cast(self.syntax.clone()).unwrap()),
             ARRAY_EXPR => ExprKind::ArrayExpr(ArrayExpr::cast(self.syntax.clone()).unwrap()),
             INDEX_EXPR => ExprKind::IndexExpr(IndexExpr::cast(self.syntax.clone()).unwrap()),
             RECORD_LIT => ExprKind::RecordLit(RecordLit::cast(self.syntax.clone()).unwrap()),
+            TUPLE_EXPR => ExprKind::TupleExpr(TupleExpr::cast(self.syntax.clone()).unwrap()),
+            CAST_EXPR => ExprKind::CastExpr(CastExpr::cast(self.syntax.clone()).unwrap()),
             _ => unreachable!(),
         }
     }
@@ -573,6 +732,75 @@ impl FieldExpr {
     }
 }
 
+// FnPointerType
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FnPointerType {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for FnPointerType {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, FN_POINTER_TYPE)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(FnPointerType { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl FnPointerType {
+    pub fn param_list(&self) -> Option<ParamList> {
+        super::child_opt(self)
+    }
+
+    pub fn ret_type(&self) -> Option<RetType> {
+        super::child_opt(self)
+    }
+}
+
+// ForExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ForExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for ForExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, FOR_EXPR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(ForExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::LoopBodyOwner for ForExpr {}
+impl ForExpr {
+    pub fn pat(&self) -> Option<Pat> {
+        super::child_opt(self)
+    }
+
+    pub fn iterable(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+
+    pub fn label(&self) -> Option<Label> {
+        super::child_opt(self)
+    }
+}
+
 // FunctionDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -599,6 +827,7 @@ impl ast::NameOwner for FunctionDef {}
 impl ast::VisibilityOwner for FunctionDef {}
 impl ast::DocCommentsOwner for FunctionDef {}
 impl ast::ExternOwner for FunctionDef {}
+impl ast::AttrsOwner for FunctionDef {}
 impl FunctionDef {
     pub fn param_list(&self) -> Option<ParamList> {
         super::child_opt(self)
@@ -699,6 +928,30 @@ impl AstNode for IndexExpr {
 }
 impl IndexExpr {}
 
+// Label
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Label {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for Label {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, LABEL)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(Label { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl Label {}
+
 // LetStmt
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -779,7 +1032,11 @@ impl AstNode for LoopExpr {
     }
 }
 impl ast::LoopBodyOwner for LoopExpr {}
-impl LoopExpr {}
+impl LoopExpr {
+    pub fn label(&self) -> Option<Label> {
+        super::child_opt(self)
+    }
+}
 
 // MemoryTypeSpecifier
 
@@ -816,7 +1073,7 @@ impl AstNode for ModuleItem {
     fn can_cast(kind: SyntaxKind) -> bool {
         matches!(
             kind,
-            USE | FUNCTION_DEF | STRUCT_DEF | TYPE_ALIAS_DEF | IMPL
+            USE | FUNCTION_DEF | STRUCT_DEF | TYPE_ALIAS_DEF | CONST_DEF | IMPL
         )
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
@@ -836,6 +1093,7 @@ pub enum ModuleItemKind {
     FunctionDef(FunctionDef),
     StructDef(StructDef),
     TypeAliasDef(TypeAliasDef),
+    ConstDef(ConstDef),
     Impl(Impl),
 }
 impl From<Use> for ModuleItem {
@@ -858,6 +1116,11 @@ impl From<TypeAliasDef> for ModuleItem {
         ModuleItem { syntax: n.syntax }
     }
 }
+impl From<ConstDef> for ModuleItem {
+    fn from(n: ConstDef) -> ModuleItem {
+        ModuleItem { syntax: n.syntax }
+    }
+}
 impl From<Impl> for ModuleItem {
     fn from(n: Impl) -> ModuleItem {
         ModuleItem { syntax: n.syntax }
@@ -875,6 +1138,7 @@ impl ModuleItem {
             TYPE_ALIAS_DEF => {
                 ModuleItemKind::TypeAliasDef(TypeAliasDef::cast(self.syntax.clone()).unwrap())
             }
+            CONST_DEF => ModuleItemKind::ConstDef(ConstDef::cast(self.syntax.clone()).unwrap()),
             IMPL => ModuleItemKind::Impl(Impl::cast(self.syntax.clone()).unwrap()),
             _ => unreachable!(),
         }
@@ -1412,6 +1676,34 @@ impl RecordLit {
     }
 }
 
+// RefType
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RefType {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for RefType {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, REF_TYPE)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(RefType { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl RefType {
+    pub fn type_ref(&self) -> Option<TypeRef> {
+        super::child_opt(self)
+    }
+}
+
 // Rename
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1518,6 +1810,34 @@ impl AstNode for SelfParam {
 impl ast::TypeAscriptionOwner for SelfParam {}
 impl SelfParam {}
 
+// SliceType
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SliceType {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for SliceType {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, SLICE_TYPE)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(SliceType { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl SliceType {
+    pub fn type_ref(&self) -> Option<TypeRef> {
+        super::child_opt(self)
+    }
+}
+
 // SourceFile
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1544,6 +1864,40 @@ impl ast::ModuleItemOwner for SourceFile {}
 impl ast::FunctionDefOwner for SourceFile {}
 impl SourceFile {}
 
+// StaticStmt
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StaticStmt {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for StaticStmt {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, STATIC_STMT)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(StaticStmt { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl ast::TypeAscriptionOwner for StaticStmt {}
+impl ast::AttrsOwner for StaticStmt {}
+impl StaticStmt {
+    pub fn pat(&self) -> Option<Pat> {
+        super::child_opt(self)
+    }
+
+    pub fn initializer(&self) -> Option<Expr> {
+        super::child_opt(self)
+    }
+}
+
 // Stmt
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1553,7 +1907,7 @@ pub struct Stmt {
 
 impl AstNode for Stmt {
     fn can_cast(kind: SyntaxKind) -> bool {
-        matches!(kind, LET_STMT | EXPR_STMT)
+        matches!(kind, LET_STMT | STATIC_STMT | EXPR_STMT)
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
@@ -1569,6 +1923,7 @@ impl AstNode for Stmt {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StmtKind {
     LetStmt(LetStmt),
+    StaticStmt(StaticStmt),
     ExprStmt(ExprStmt),
 }
 impl From<LetStmt> for Stmt {
@@ -1576,6 +1931,11 @@ impl From<LetStmt> for Stmt {
         Stmt { syntax: n.syntax }
     }
 }
+impl From<StaticStmt> for Stmt {
+    fn from(n: StaticStmt) -> Stmt {
+        Stmt { syntax: n.syntax }
+    }
+}
 impl From<ExprStmt> for Stmt {
     fn from(n: ExprStmt) -> Stmt {
         Stmt { syntax: n.syntax }
@@ -1586,6 +1946,7 @@ impl Stmt {
     pub fn kind(&self) -> StmtKind {
         match self.syntax.kind() {
             LET_STMT => StmtKind::LetStmt(LetStmt::cast(self.syntax.clone()).unwrap()),
+            STATIC_STMT => StmtKind::StaticStmt(StaticStmt::cast(self.syntax.clone()).unwrap()),
             EXPR_STMT => StmtKind::ExprStmt(ExprStmt::cast(self.syntax.clone()).unwrap()),
             _ => unreachable!(),
         }
@@ -1619,12 +1980,37 @@ impl AstNode for StructDef {
 impl ast::NameOwner for StructDef {}
 impl ast::VisibilityOwner for StructDef {}
 impl ast::DocCommentsOwner for StructDef {}
+impl ast::AttrsOwner for StructDef {}
 impl StructDef {
     pub fn memory_type_specifier(&self) -> Option<MemoryTypeSpecifier> {
         super::child_opt(self)
     }
 }
 
+// TupleExpr
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TupleExpr {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TupleExpr {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TUPLE_EXPR)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TupleExpr { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl TupleExpr {}
+
 // TupleFieldDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1682,6 +2068,30 @@ impl TupleFieldDefList {
     }
 }
 
+// TupleType
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TupleType {
+    pub(crate) syntax: SyntaxNode,
+}
+
+impl AstNode for TupleType {
+    fn can_cast(kind: SyntaxKind) -> bool {
+        matches!(kind, TUPLE_TYPE)
+    }
+    fn cast(syntax: SyntaxNode) -> Option<Self> {
+        if Self::can_cast(syntax.kind()) {
+            Some(TupleType { syntax })
+        } else {
+            None
+        }
+    }
+    fn syntax(&self) -> &SyntaxNode {
+        &self.syntax
+    }
+}
+impl TupleType {}
+
 // TypeAliasDef
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1722,7 +2132,16 @@ pub struct TypeRef {
 
 impl AstNode for TypeRef {
     fn can_cast(kind: SyntaxKind) -> bool {
-        matches!(kind, PATH_TYPE | ARRAY_TYPE | NEVER_TYPE)
+        matches!(
+            kind,
+            PATH_TYPE
+                | ARRAY_TYPE
+                | NEVER_TYPE
+                | FN_POINTER_TYPE
+                | SLICE_TYPE
+                | TUPLE_TYPE
+                | REF_TYPE
+        )
     }
     fn cast(syntax: SyntaxNode) -> Option<Self> {
         if Self::can_cast(syntax.kind()) {
@@ -1740,6 +2159,10 @@ pub enum TypeRefKind {
     PathType(PathType),
     ArrayType(ArrayType),
     NeverType(NeverType),
+    FnPointerType(FnPointerType),
+    SliceType(SliceType),
+    TupleType(TupleType),
+    RefType(RefType),
 }
 impl From<PathType> for TypeRef {
     fn from(n: PathType) -> TypeRef {
@@ -1756,6 +2179,26 @@ impl From<NeverType> for TypeRef {
         TypeRef { syntax: n.syntax }
     }
 }
+impl From<FnPointerType> for TypeRef {
+    fn from(n: FnPointerType) -> TypeRef {
+        TypeRef { syntax: n.syntax }
+    }
+}
+impl From<SliceType> for TypeRef {
+    fn from(n: SliceType) -> TypeRef {
+        TypeRef { syntax: n.syntax }
+    }
+}
+impl From<TupleType> for TypeRef {
+    fn from(n: TupleType) -> TypeRef {
+        TypeRef { syntax: n.syntax }
+    }
+}
+impl From<RefType> for TypeRef {
+    fn from(n: RefType) -> TypeRef {
+        TypeRef { syntax: n.syntax }
+    }
+}
 
 impl TypeRef {
     pub fn kind(&self) -> TypeRefKind {
@@ -1763,6 +2206,12 @@ impl TypeRef {
             PATH_TYPE => TypeRefKind::PathType(PathType::cast(self.syntax.clone()).unwrap()),
             ARRAY_TYPE => TypeRefKind::ArrayType(ArrayType::cast(self.syntax.clone()).unwrap()),
             NEVER_TYPE => TypeRefKind::NeverType(NeverType::cast(self.syntax.clone()).unwrap()),
+            FN_POINTER_TYPE => {
+                TypeRefKind::FnPointerType(FnPointerType::cast(self.syntax.clone()).unwrap())
+            }
+            SLICE_TYPE => TypeRefKind::SliceType(SliceType::cast(self.syntax.clone()).unwrap()),
+            TUPLE_TYPE => TypeRefKind::TupleType(TupleType::cast(self.syntax.clone()).unwrap()),
+            REF_TYPE => TypeRefKind::RefType(RefType::cast(self.syntax.clone()).unwrap()),
             _ => unreachable!(),
         }
     }
@@ -1914,6 +2363,10 @@ impl WhileExpr {
     pub fn condition(&self) -> Option<Condition> {
         super::child_opt(self)
     }
+
+    pub fn label(&self) -> Option<Label> {
+        super::child_opt(self)
+    }
 }
 
 // IntNumber
@@ -35,6 +35,32 @@ impl Comment {
     pub fn prefix(&self) -> &'static str {
         prefix_by_kind(self.kind())
     }
+
+    /// Returns true if this is a block comment that is not closed before the
+    /// end of the file. Block comments can be nested, so every `/*` requires a
+    /// matching `*/`.
+    pub fn is_unterminated_block(&self) -> bool {
+        if !self.kind().shape.is_block() {
+            return false;
+        }
+
+        let mut rest = &self.text()["/*".len()..];
+        let mut depth: u32 = 1;
+        while depth > 0 {
+            if let Some(tail) = rest.strip_prefix("*/") {
+                depth -= 1;
+                rest = tail;
+            } else if let Some(tail) = rest.strip_prefix("/*") {
+                depth += 1;
+                rest = tail;
+            } else if let Some(c) = rest.chars().next() {
+                rest = &rest[c.len_utf8()..];
+            } else {
+                break;
+            }
+        }
+        depth > 0
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -115,6 +141,13 @@ const COMMENT_PREFIX_TO_KIND: &[(&str, CommentKind)] = {
 };
 
 fn kind_by_prefix(text: &str) -> CommentKind {
+    // An empty block comment also starts with the prefix of an outer doc comment
+    if text == "/**/" {
+        return CommentKind {
+            shape: CommentShape::Block,
+            doc: None,
+        };
+    }
     for (prefix, kind) in COMMENT_PREFIX_TO_KIND.iter() {
         if text.starts_with(prefix) {
             return *kind;
@@ -73,6 +73,29 @@ pub trait DefaultTypeParamOwner: AstNode {
     }
 }
 
+pub trait AttrsOwner: AstNode {
+    fn attrs(&self) -> AstChildren<ast::Attr> {
+        children(self)
+    }
+
+    /// Returns true if this node has an attribute with the specified name,
+    /// e.g. `#[cold]`.
+    fn has_atom_attr(&self, name: &str) -> bool {
+        self.attrs()
+            .filter_map(|attr| attr.name_ref())
+            .any(|name_ref| name_ref.text() == name)
+    }
+
+    /// Returns the first attribute with the specified name, e.g. the
+    /// `#[assert_size(16)]` attribute for `assert_size`.
+    fn attr(&self, name: &str) -> Option<ast::Attr> {
+        self.attrs().find(|attr| {
+            attr.name_ref()
+                .map_or(false, |name_ref| name_ref.text() == name)
+        })
+    }
+}
+
 pub trait ExternOwner: AstNode {
     fn is_extern(&self) -> bool {
         self.syntax()
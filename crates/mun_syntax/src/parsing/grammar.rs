@@ -1,4 +1,5 @@
 mod adt;
+mod attributes;
 mod declarations;
 mod expressions;
 mod params;
@@ -11,15 +12,15 @@ use super::{
     parser::{CompletedMarker, Marker, Parser},
     token_set::TokenSet,
     SyntaxKind::{
-        self, ARG_LIST, ARRAY_EXPR, ARRAY_TYPE, BIND_PAT, BIN_EXPR, BLOCK_EXPR, BREAK_EXPR,
-        CALL_EXPR, CONDITION, EOF, ERROR, EXPR_STMT, EXTERN, FIELD_EXPR, FLOAT_NUMBER,
-        FUNCTION_DEF, GC_KW, IDENT, IF_EXPR, INDEX, INDEX_EXPR, INT_NUMBER, LET_STMT, LITERAL,
-        LOOP_EXPR, MEMORY_TYPE_SPECIFIER, NAME, NAME_REF, NEVER_TYPE, PARAM, PARAM_LIST,
-        PAREN_EXPR, PATH, PATH_EXPR, PATH_SEGMENT, PATH_TYPE, PLACEHOLDER_PAT, PREFIX_EXPR,
-        RECORD_FIELD, RECORD_FIELD_DEF, RECORD_FIELD_DEF_LIST, RECORD_FIELD_LIST, RECORD_LIT,
-        RENAME, RETURN_EXPR, RET_TYPE, SELF_PARAM, SOURCE_FILE, STRING, STRUCT_DEF,
-        TUPLE_FIELD_DEF, TUPLE_FIELD_DEF_LIST, TYPE_ALIAS_DEF, USE, USE_TREE, USE_TREE_LIST,
-        VALUE_KW, VISIBILITY, WHILE_EXPR,
+        self, ARG_LIST, ARRAY_EXPR, ARRAY_TYPE, ATTR, BIND_PAT, BIN_EXPR, BLOCK_EXPR, BREAK_EXPR,
+        CALL_EXPR, CONDITION, CONST_DEF, EOF, ERROR, EXPR_STMT, EXTERN, FIELD_EXPR, FLOAT_NUMBER,
+        FN_POINTER_TYPE, FUNCTION_DEF, GC_KW, IDENT, IF_EXPR, INDEX, INDEX_EXPR, INT_NUMBER, LABEL,
+        LET_STMT, LIFETIME, LITERAL, LOOP_EXPR, MEMORY_TYPE_SPECIFIER, NAME, NAME_REF, NEVER_TYPE,
+        PARAM, PARAM_LIST, PAREN_EXPR, PATH, PATH_EXPR, PATH_SEGMENT, PATH_TYPE, PLACEHOLDER_PAT,
+        PREFIX_EXPR, RECORD_FIELD, RECORD_FIELD_DEF, RECORD_FIELD_DEF_LIST, RECORD_FIELD_LIST,
+        RECORD_LIT, REF_TYPE, RENAME, RETURN_EXPR, RET_TYPE, SELF_PARAM, SLICE_TYPE, SOURCE_FILE,
+        STATIC_STMT, STRING, STRUCT_DEF, TUPLE_EXPR, TUPLE_FIELD_DEF, TUPLE_FIELD_DEF_LIST,
+        TUPLE_TYPE, TYPE_ALIAS_DEF, USE, USE_TREE, USE_TREE_LIST, VALUE_KW, VISIBILITY, WHILE_EXPR,
     },
 };
 
@@ -0,0 +1,25 @@
+use super::{expressions, name_ref, Parser, ATTR};
+
+/// Parses all attributes that precede an item, e.g. `#[cold]` or
+/// `#[assert_size(16)]`.
+pub(super) fn outer_attributes(p: &mut Parser<'_>) {
+    while p.at(T![#]) {
+        attribute(p);
+    }
+}
+
+fn attribute(p: &mut Parser<'_>) {
+    assert!(p.at(T![#]));
+    let m = p.start();
+    p.bump(T![#]);
+    if p.expect(T!['[']) {
+        name_ref(p);
+        if p.at(T!['(']) {
+            expressions::arg_list(p);
+        } else if p.eat(T![=]) && expressions::literal(p).is_none() {
+            p.error("expected a literal");
+        }
+        p.expect(T![']']);
+    }
+    m.complete(p, ATTR);
+}
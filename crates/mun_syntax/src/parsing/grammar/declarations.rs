@@ -1,12 +1,20 @@
 use super::{
-    adt, error_block, expressions, name, name_recovery, opt_visibility, params, paths, traits,
-    types, Marker, Parser, TokenSet, EOF, ERROR, EXTERN, FUNCTION_DEF, RENAME, RET_TYPE, USE,
-    USE_TREE, USE_TREE_LIST,
+    adt, attributes, error_block, expressions, name, name_recovery, opt_visibility, params, paths,
+    traits, types, Marker, Parser, TokenSet, CONST_DEF, EOF, ERROR, EXTERN, FUNCTION_DEF, RENAME,
+    RET_TYPE, USE, USE_TREE, USE_TREE_LIST,
 };
 use crate::{parsing::grammar::paths::is_use_path_start, T};
 
-pub(super) const DECLARATION_RECOVERY_SET: TokenSet =
-    TokenSet::new(&[T![fn], T![pub], T![struct], T![use], T![;], T![impl]]);
+pub(super) const DECLARATION_RECOVERY_SET: TokenSet = TokenSet::new(&[
+    T![fn],
+    T![pub],
+    T![struct],
+    T![use],
+    T![;],
+    T![impl],
+    T![const],
+    T![#],
+]);
 
 pub(super) fn mod_contents(p: &mut Parser<'_>) {
     while !p.at(EOF) {
@@ -36,6 +44,7 @@ pub(super) fn declaration(p: &mut Parser<'_>, stop_on_r_curly: bool) {
 }
 
 pub(super) fn maybe_declaration(p: &mut Parser<'_>, m: Marker) -> Result<(), Marker> {
+    attributes::outer_attributes(p);
     opt_visibility(p);
 
     let m = match declarations_without_modifiers(p, m) {
@@ -78,6 +87,9 @@ fn declarations_without_modifiers(p: &mut Parser<'_>, m: Marker) -> Result<(), M
         T![impl] => {
             traits::impl_(p, m);
         }
+        T![const] => {
+            const_def(p, m);
+        }
         _ => return Err(m),
     };
     Ok(())
@@ -104,7 +116,7 @@ pub(super) fn fn_def(p: &mut Parser<'_>) {
     }
 }
 
-fn opt_fn_ret_type(p: &mut Parser<'_>) -> bool {
+pub(super) fn opt_fn_ret_type(p: &mut Parser<'_>) -> bool {
     if p.at(T![->]) {
         let m = p.start();
         p.bump(T![->]);
@@ -116,6 +128,18 @@ fn opt_fn_ret_type(p: &mut Parser<'_>) -> bool {
     }
 }
 
+fn const_def(p: &mut Parser<'_>, m: Marker) {
+    assert!(p.at(T![const]));
+    p.bump(T![const]);
+    name(p);
+    types::ascription(p);
+    if p.expect(T![=]) {
+        expressions::expr(p);
+    }
+    p.expect(T![;]);
+    m.complete(p, CONST_DEF);
+}
+
 fn use_(p: &mut Parser<'_>, m: Marker) {
     assert!(p.at(T![use]));
     p.bump(T![use]);
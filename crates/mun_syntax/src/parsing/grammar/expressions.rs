@@ -1,10 +1,10 @@
 use super::{
-    error_block, expressions, name_ref_or_index, paths, patterns, types, BlockLike,
+    attributes, error_block, expressions, name_ref_or_index, paths, patterns, types, BlockLike,
     CompletedMarker, Marker, Parser, SyntaxKind, TokenSet, ARG_LIST, ARRAY_EXPR, BIN_EXPR,
-    BLOCK_EXPR, BREAK_EXPR, CALL_EXPR, CONDITION, EOF, ERROR, EXPR_STMT, FIELD_EXPR, FLOAT_NUMBER,
-    IDENT, IF_EXPR, INDEX, INDEX_EXPR, INT_NUMBER, LET_STMT, LITERAL, LOOP_EXPR, PAREN_EXPR,
-    PATH_EXPR, PATH_TYPE, PREFIX_EXPR, RECORD_FIELD, RECORD_FIELD_LIST, RECORD_LIT, RETURN_EXPR,
-    STRING, WHILE_EXPR,
+    BLOCK_EXPR, BREAK_EXPR, CALL_EXPR, CAST_EXPR, CONDITION, EOF, ERROR, EXPR_STMT, FIELD_EXPR,
+    FLOAT_NUMBER, FOR_EXPR, IDENT, IF_EXPR, INDEX, INDEX_EXPR, INT_NUMBER, LABEL, LET_STMT,
+    LIFETIME, LITERAL, LOOP_EXPR, PAREN_EXPR, PATH_EXPR, PATH_TYPE, PREFIX_EXPR, RECORD_FIELD,
+    RECORD_FIELD_LIST, RECORD_LIT, RETURN_EXPR, STATIC_STMT, STRING, TUPLE_EXPR, WHILE_EXPR,
 };
 use crate::parsing::grammar::paths::PATH_FIRST;
 
@@ -22,10 +22,13 @@ const ATOM_EXPR_FIRST: TokenSet = LITERAL_FIRST.union(PATH_FIRST).union(TokenSet
     T![loop],
     T![return],
     T![break],
+    T![continue],
     T![while],
+    T![for],
+    LIFETIME,
 ]));
 
-const LHS_FIRST: TokenSet = ATOM_EXPR_FIRST.union(TokenSet::new(&[T![!], T![-]]));
+const LHS_FIRST: TokenSet = ATOM_EXPR_FIRST.union(TokenSet::new(&[T![!], T![-], T![*]]));
 
 const EXPR_FIRST: TokenSet = LHS_FIRST;
 
@@ -83,6 +86,24 @@ pub(super) fn stmt(p: &mut Parser<'_>) {
         return;
     }
 
+    // Encounters static keyword, so we know it's a function-local static
+    if p.at(T![static]) {
+        static_stmt(p, m);
+        return;
+    }
+
+    // Only function-local statics can have attributes
+    if p.at(T![#]) {
+        attributes::outer_attributes(p);
+        if p.at(T![static]) {
+            static_stmt(p, m);
+        } else {
+            p.error("expected a static after attributes");
+            m.complete(p, ERROR);
+        }
+        return;
+    }
+
     let (cm, _blocklike) = expr_stmt(p);
     let kind = cm.as_ref().map_or(ERROR, CompletedMarker::kind);
 
@@ -114,6 +135,20 @@ fn let_stmt(p: &mut Parser<'_>, m: Marker) {
     m.complete(p, LET_STMT);
 }
 
+fn static_stmt(p: &mut Parser<'_>, m: Marker) {
+    assert!(p.at(T![static]));
+    p.bump(T![static]);
+    p.eat(T![mut]);
+    patterns::pattern(p);
+    types::ascription(p);
+    if p.expect(T![=]) {
+        expressions::expr(p);
+    }
+
+    p.eat(T![;]); // Semicolon at the end of statement belongs to the statement
+    m.complete(p, STATIC_STMT);
+}
+
 pub(super) fn expr(p: &mut Parser<'_>) {
     let r = Restrictions {
         forbid_structs: false,
@@ -153,6 +188,11 @@ fn expr_bp(p: &mut Parser<'_>, r: Restrictions, bp: u8) -> (Option<CompletedMark
             break;
         }
 
+        if op == T![as] {
+            lhs = cast_expr(p, lhs);
+            continue;
+        }
+
         let m = lhs.precede(p);
         p.bump(op);
 
@@ -194,6 +234,7 @@ fn current_op(p: &Parser<'_>) -> (u8, SyntaxKind) {
         T![<] if p.at(T![<<=]) => (1, T![<<=]),
         T![<] if p.at(T![<<]) => (9, T![<<]),
         T![<] => (5, T![<]),
+        T![as] => (12, T![as]),
         _ => (0, T![_]),
     }
 }
@@ -201,7 +242,7 @@ fn current_op(p: &Parser<'_>) -> (u8, SyntaxKind) {
 fn lhs(p: &mut Parser<'_>, r: Restrictions) -> Option<(CompletedMarker, BlockLike)> {
     let m;
     let kind = match p.current() {
-        T![-] | T![!] => {
+        T![-] | T![!] | T![&] | T![*] => {
             m = p.start();
             p.bump_any();
             PREFIX_EXPR
@@ -238,6 +279,14 @@ fn postfix_expr(
     (lhs, blocklike)
 }
 
+fn cast_expr(p: &mut Parser<'_>, lhs: CompletedMarker) -> CompletedMarker {
+    assert!(p.at(T![as]));
+    let m = lhs.precede(p);
+    p.bump(T![as]);
+    types::type_(p);
+    m.complete(p, CAST_EXPR)
+}
+
 fn call_expr(p: &mut Parser<'_>, lhs: CompletedMarker) -> CompletedMarker {
     assert!(p.at(T!['(']));
     let m = lhs.precede(p);
@@ -254,7 +303,7 @@ fn index_expr(p: &mut Parser<'_>, lhs: CompletedMarker) -> CompletedMarker {
     m.complete(p, INDEX_EXPR)
 }
 
-fn arg_list(p: &mut Parser<'_>) {
+pub(super) fn arg_list(p: &mut Parser<'_>) {
     assert!(p.at(T!['(']));
     let m = p.start();
     p.bump(T!['(']);
@@ -315,17 +364,32 @@ fn atom_expr(p: &mut Parser<'_>, r: Restrictions) -> Option<(CompletedMarker, Bl
         T!['{'] => block_expr(p),
         T!['['] => array_expr(p),
         T![if] => if_expr(p),
-        T![loop] => loop_expr(p),
+        T![loop] => loop_expr(p, None),
         T![return] => ret_expr(p),
-        T![while] => while_expr(p),
+        T![while] => while_expr(p, None),
+        T![for] => for_expr(p, None),
+        LIFETIME if p.nth_at(1, T![:]) => {
+            let m = p.start();
+            label(p);
+            match p.current() {
+                T![loop] => loop_expr(p, Some(m)),
+                T![while] => while_expr(p, Some(m)),
+                T![for] => for_expr(p, Some(m)),
+                _ => {
+                    p.error("expected a loop");
+                    m.complete(p, ERROR)
+                }
+            }
+        }
         T![break] => break_expr(p, r),
+        T![continue] => continue_expr(p),
         _ => {
             p.error_recover("expected expression", EXPR_RECOVERY_SET);
             return None;
         }
     };
     let blocklike = match marker.kind() {
-        IF_EXPR | WHILE_EXPR | LOOP_EXPR | BLOCK_EXPR => BlockLike::Block,
+        IF_EXPR | WHILE_EXPR | LOOP_EXPR | FOR_EXPR | BLOCK_EXPR => BlockLike::Block,
         _ => BlockLike::NotBlock,
     };
     Some((marker, blocklike))
@@ -345,7 +409,7 @@ fn path_expr(p: &mut Parser<'_>, r: Restrictions) -> (CompletedMarker, BlockLike
     }
 }
 
-fn literal(p: &mut Parser<'_>) -> Option<CompletedMarker> {
+pub(super) fn literal(p: &mut Parser<'_>) -> Option<CompletedMarker> {
     if !p.at_ts(LITERAL_FIRST) {
         return None;
     }
@@ -358,6 +422,12 @@ fn paren_expr(p: &mut Parser<'_>) -> CompletedMarker {
     assert!(p.at(T!['(']));
     let m = p.start();
     p.bump(T!['(']);
+
+    // An empty pair of parentheses is the unit value `()`
+    if p.eat(T![')']) {
+        return m.complete(p, TUPLE_EXPR);
+    }
+
     expr(p);
     p.expect(T![')']);
     m.complete(p, PAREN_EXPR)
@@ -380,9 +450,17 @@ fn if_expr(p: &mut Parser<'_>) -> CompletedMarker {
     m.complete(p, IF_EXPR)
 }
 
-fn loop_expr(p: &mut Parser<'_>) -> CompletedMarker {
-    assert!(p.at(T![loop]));
+fn label(p: &mut Parser<'_>) {
+    assert!(p.at(LIFETIME) && p.nth_at(1, T![:]));
     let m = p.start();
+    p.bump(LIFETIME);
+    p.bump(T![:]);
+    m.complete(p, LABEL);
+}
+
+fn loop_expr(p: &mut Parser<'_>, m: Option<Marker>) -> CompletedMarker {
+    assert!(p.at(T![loop]));
+    let m = m.unwrap_or_else(|| p.start());
     p.bump(T![loop]);
     block(p);
     m.complete(p, LOOP_EXPR)
@@ -408,21 +486,45 @@ fn break_expr(p: &mut Parser<'_>, r: Restrictions) -> CompletedMarker {
     assert!(p.at(T![break]));
     let m = p.start();
     p.bump(T![break]);
+    if p.at(LIFETIME) {
+        p.bump(LIFETIME);
+    }
     if p.at_ts(EXPR_FIRST) && !(r.forbid_structs && p.at(T!['{'])) {
         expr(p);
     }
     m.complete(p, BREAK_EXPR)
 }
 
-fn while_expr(p: &mut Parser<'_>) -> CompletedMarker {
-    assert!(p.at(T![while]));
+fn continue_expr(p: &mut Parser<'_>) -> CompletedMarker {
+    assert!(p.at(T![continue]));
     let m = p.start();
+    p.bump(T![continue]);
+    if p.at(LIFETIME) {
+        p.bump(LIFETIME);
+    }
+    m.complete(p, CONTINUE_EXPR)
+}
+
+fn while_expr(p: &mut Parser<'_>, m: Option<Marker>) -> CompletedMarker {
+    assert!(p.at(T![while]));
+    let m = m.unwrap_or_else(|| p.start());
     p.bump(T![while]);
     cond(p);
     block(p);
     m.complete(p, WHILE_EXPR)
 }
 
+fn for_expr(p: &mut Parser<'_>, m: Option<Marker>) -> CompletedMarker {
+    assert!(p.at(T![for]));
+    let m = m.unwrap_or_else(|| p.start());
+    p.bump(T![for]);
+    patterns::pattern(p);
+    p.expect(T![in]);
+    expr_no_struct(p);
+    block(p);
+    m.complete(p, FOR_EXPR)
+}
+
 fn record_field_list(p: &mut Parser<'_>) {
     assert!(p.at(T!['{']));
     let m = p.start();
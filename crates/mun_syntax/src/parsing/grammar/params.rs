@@ -26,6 +26,31 @@ fn list(p: &mut Parser<'_>) {
     m.complete(p, PARAM_LIST);
 }
 
+/// Parses the parameter list of a function pointer type, e.g. `(i32, f64)`.
+/// Unlike the parameters of a function definition these do not have a
+/// pattern.
+pub(super) fn fn_pointer_param_list(p: &mut Parser<'_>) {
+    assert!(p.at(T!['(']));
+
+    let m = p.start();
+    p.bump(T!['(']);
+
+    while !p.at(EOF) && !p.at(T![')']) {
+        if !p.at_ts(types::TYPE_FIRST) {
+            p.error("expected type");
+            break;
+        }
+        let param = p.start();
+        types::type_(p);
+        param.complete(p, PARAM);
+        if !p.at(T![')']) {
+            p.expect(T![,]);
+        }
+    }
+    p.expect(T![')']);
+    m.complete(p, PARAM_LIST);
+}
+
 const VALUE_PARAMETER_FIRST: TokenSet = patterns::PATTERN_FIRST;
 
 fn param(p: &mut Parser<'_>) {
@@ -1,7 +1,10 @@
-use super::{paths, Parser, TokenSet, ARRAY_TYPE, NEVER_TYPE, PATH_TYPE};
+use super::{
+    declarations, params, paths, Parser, TokenSet, ARRAY_TYPE, FN_POINTER_TYPE, NEVER_TYPE,
+    PATH_TYPE, REF_TYPE, SLICE_TYPE, TUPLE_TYPE,
+};
 
 pub(super) const TYPE_FIRST: TokenSet =
-    paths::PATH_FIRST.union(TokenSet::new(&[T![never], T!['[']]));
+    paths::PATH_FIRST.union(TokenSet::new(&[T![never], T!['['], T![fn], T![&], T!['(']]));
 
 pub(super) const TYPE_RECOVERY_SET: TokenSet = TokenSet::new(&[T!['('], T![,], T![pub]]);
 
@@ -14,6 +17,10 @@ pub(super) fn type_(p: &mut Parser<'_>) {
     match p.current() {
         T!['['] => array_type(p),
         T![never] => never_type(p),
+        T![fn] => fn_pointer_type(p),
+        T![&] if p.nth(1) == T![mut] => ref_type(p),
+        T![&] => slice_type(p),
+        T!['('] => tuple_type(p),
         _ if paths::is_path_start(p) => path_type(p),
         _ => {
             p.error_recover("expected type", TYPE_RECOVERY_SET);
@@ -42,3 +49,45 @@ fn array_type(p: &mut Parser<'_>) {
     p.expect(T![']']);
     m.complete(p, ARRAY_TYPE);
 }
+
+fn fn_pointer_type(p: &mut Parser<'_>) {
+    assert!(p.at(T![fn]));
+    let m = p.start();
+    p.bump(T![fn]);
+    if p.at(T!['(']) {
+        params::fn_pointer_param_list(p);
+    } else {
+        p.error("expected parameters");
+    }
+    declarations::opt_fn_ret_type(p);
+    m.complete(p, FN_POINTER_TYPE);
+}
+
+fn slice_type(p: &mut Parser<'_>) {
+    assert!(p.at(T![&]));
+    let m = p.start();
+    p.bump(T![&]);
+    p.expect(T!['[']);
+    type_(p);
+    p.expect(T![']']);
+    m.complete(p, SLICE_TYPE);
+}
+
+/// Parses a mutable reference type, e.g. `&mut i64`.
+fn ref_type(p: &mut Parser<'_>) {
+    assert!(p.at(T![&]));
+    let m = p.start();
+    p.bump(T![&]);
+    p.bump(T![mut]);
+    type_(p);
+    m.complete(p, REF_TYPE);
+}
+
+/// Parses a tuple type. Only the empty tuple `()` is currently supported.
+fn tuple_type(p: &mut Parser<'_>) {
+    assert!(p.at(T!['(']));
+    let m = p.start();
+    p.bump(T!['(']);
+    p.expect(T![')']);
+    m.complete(p, TUPLE_TYPE);
+}
@@ -9,10 +9,10 @@ use self::{
     comments::scan_comment,
     cursor::Cursor,
     numbers::scan_number,
-    strings::scan_string,
+    strings::{is_lifetime_start, scan_string},
 };
 use crate::{
-    SyntaxKind::{self, ERROR, IDENT, NEQ, STRING, UNDERSCORE, WHITESPACE},
+    SyntaxKind::{self, ERROR, IDENT, LIFETIME, NEQ, STRING, UNDERSCORE, WHITESPACE},
     TextSize,
 };
 
@@ -83,6 +83,10 @@ fn next_token_inner(c: char, cursor: &mut Cursor<'_>) -> SyntaxKind {
             cursor.bump();
             return NEQ;
         }
+        '\'' if is_lifetime_start(cursor) => {
+            cursor.bump_while(is_ident_continue);
+            return LIFETIME;
+        }
         '"' | '\'' => {
             scan_string(c, cursor);
             return STRING;
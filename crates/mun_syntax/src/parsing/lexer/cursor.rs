@@ -79,6 +79,13 @@ impl<'s> Cursor<'s> {
         &self.text[..len as usize]
     }
 
+    /// Returns the remaining text up to the end of the current line.
+    pub fn rest_of_line(&self) -> &str {
+        let len: u32 = self.len.into();
+        let rest = &self.text[len as usize..];
+        rest.split('\n').next().unwrap_or(rest)
+    }
+
     /// Returns an iterator over the remaining characters.
     fn chars(&self) -> Chars<'_> {
         let len: u32 = self.len.into();
@@ -1,4 +1,29 @@
-use crate::parsing::lexer::cursor::Cursor;
+use crate::parsing::lexer::{
+    classes::{is_ident_continue, is_ident_start},
+    cursor::Cursor,
+};
+
+/// Returns true if the `'` that was just consumed starts a lifetime, e.g.
+/// `'outer`, instead of a single-quoted string. A lifetime is an identifier
+/// that is not closed by another `'` on the same line, as in `break 'outer;`.
+/// A `'` closes a string if it directly follows the identifier, as in `'a'`, or
+/// if it does not start another lifetime, as in `'note: x'`.
+pub(crate) fn is_lifetime_start(cursor: &Cursor<'_>) -> bool {
+    let rest = cursor.rest_of_line();
+    if !rest.chars().next().map_or(false, is_ident_start) {
+        return false;
+    }
+
+    let after_ident = rest.trim_start_matches(is_ident_continue);
+    match after_ident.find('\'') {
+        None => true,
+        Some(0) => false,
+        Some(idx) => after_ident[idx + 1..]
+            .chars()
+            .next()
+            .map_or(false, is_ident_start),
+    }
+}
 
 pub(crate) fn scan_string(c: char, cursor: &mut Cursor<'_>) {
     let quote_type = c;
@@ -86,6 +86,7 @@ pub enum SyntaxKind {
     TRUE_KW,
     WHILE_KW,
     LOOP_KW,
+    CONTINUE_KW,
     LET_KW,
     MUT_KW,
     CLASS_KW,
@@ -98,6 +99,8 @@ pub enum SyntaxKind {
     SELF_KW,
     EXTERN_KW,
     IMPL_KW,
+    STATIC_KW,
+    CONST_KW,
     INT_NUMBER,
     FLOAT_NUMBER,
     STRING,
@@ -106,11 +109,13 @@ pub enum SyntaxKind {
     INDEX,
     WHITESPACE,
     COMMENT,
+    LIFETIME,
     GC_KW,
     VALUE_KW,
     SOURCE_FILE,
     FUNCTION_DEF,
     EXTERN,
+    ATTR,
     RET_TYPE,
     VISIBILITY,
     PARAM_LIST,
@@ -118,6 +123,7 @@ pub enum SyntaxKind {
     SELF_PARAM,
     STRUCT_DEF,
     TYPE_ALIAS_DEF,
+    CONST_DEF,
     MEMORY_TYPE_SPECIFIER,
     RECORD_FIELD_DEF_LIST,
     RECORD_FIELD_DEF,
@@ -126,7 +132,12 @@ pub enum SyntaxKind {
     PATH_TYPE,
     ARRAY_TYPE,
     NEVER_TYPE,
+    FN_POINTER_TYPE,
+    SLICE_TYPE,
+    TUPLE_TYPE,
+    REF_TYPE,
     LET_STMT,
+    STATIC_STMT,
     EXPR_STMT,
     PATH_EXPR,
     PREFIX_EXPR,
@@ -141,9 +152,14 @@ pub enum SyntaxKind {
     RETURN_EXPR,
     WHILE_EXPR,
     LOOP_EXPR,
+    FOR_EXPR,
     BREAK_EXPR,
+    CONTINUE_EXPR,
     ARRAY_EXPR,
+    TUPLE_EXPR,
+    CAST_EXPR,
     CONDITION,
+    LABEL,
     BIND_PAT,
     PLACEHOLDER_PAT,
     ARG_LIST,
@@ -355,6 +371,9 @@ macro_rules! T {
     (loop) => {
         $crate::SyntaxKind::LOOP_KW
     };
+    (continue) => {
+        $crate::SyntaxKind::CONTINUE_KW
+    };
     (let) => {
         $crate::SyntaxKind::LET_KW
     };
@@ -391,6 +410,12 @@ macro_rules! T {
     (impl) => {
         $crate::SyntaxKind::IMPL_KW
     };
+    (static) => {
+        $crate::SyntaxKind::STATIC_KW
+    };
+    (const) => {
+        $crate::SyntaxKind::CONST_KW
+    };
 }
 
 impl From<u16> for SyntaxKind {
@@ -425,6 +450,7 @@ impl SyntaxKind {
         | TRUE_KW
         | WHILE_KW
         | LOOP_KW
+        | CONTINUE_KW
         | LET_KW
         | MUT_KW
         | CLASS_KW
@@ -437,6 +463,8 @@ impl SyntaxKind {
         | SELF_KW
         | EXTERN_KW
         | IMPL_KW
+        | STATIC_KW
+        | CONST_KW
         )
     }
 
@@ -567,6 +595,7 @@ impl SyntaxKind {
             TRUE_KW => &SyntaxInfo { name: "TRUE_KW" },
             WHILE_KW => &SyntaxInfo { name: "WHILE_KW" },
             LOOP_KW => &SyntaxInfo { name: "LOOP_KW" },
+            CONTINUE_KW => &SyntaxInfo { name: "CONTINUE_KW" },
             LET_KW => &SyntaxInfo { name: "LET_KW" },
             MUT_KW => &SyntaxInfo { name: "MUT_KW" },
             CLASS_KW => &SyntaxInfo { name: "CLASS_KW" },
@@ -579,6 +608,8 @@ impl SyntaxKind {
             SELF_KW => &SyntaxInfo { name: "SELF_KW" },
             EXTERN_KW => &SyntaxInfo { name: "EXTERN_KW" },
             IMPL_KW => &SyntaxInfo { name: "IMPL_KW" },
+            STATIC_KW => &SyntaxInfo { name: "STATIC_KW" },
+            CONST_KW => &SyntaxInfo { name: "CONST_KW" },
             INT_NUMBER => &SyntaxInfo { name: "INT_NUMBER" },
             FLOAT_NUMBER => &SyntaxInfo { name: "FLOAT_NUMBER" },
             STRING => &SyntaxInfo { name: "STRING" },
@@ -587,11 +618,13 @@ impl SyntaxKind {
             INDEX => &SyntaxInfo { name: "INDEX" },
             WHITESPACE => &SyntaxInfo { name: "WHITESPACE" },
             COMMENT => &SyntaxInfo { name: "COMMENT" },
+            LIFETIME => &SyntaxInfo { name: "LIFETIME" },
             GC_KW => &SyntaxInfo { name: "GC_KW" },
             VALUE_KW => &SyntaxInfo { name: "VALUE_KW" },
             SOURCE_FILE => &SyntaxInfo { name: "SOURCE_FILE" },
             FUNCTION_DEF => &SyntaxInfo { name: "FUNCTION_DEF" },
             EXTERN => &SyntaxInfo { name: "EXTERN" },
+            ATTR => &SyntaxInfo { name: "ATTR" },
             RET_TYPE => &SyntaxInfo { name: "RET_TYPE" },
             VISIBILITY => &SyntaxInfo { name: "VISIBILITY" },
             PARAM_LIST => &SyntaxInfo { name: "PARAM_LIST" },
@@ -599,6 +632,7 @@ impl SyntaxKind {
             SELF_PARAM => &SyntaxInfo { name: "SELF_PARAM" },
             STRUCT_DEF => &SyntaxInfo { name: "STRUCT_DEF" },
             TYPE_ALIAS_DEF => &SyntaxInfo { name: "TYPE_ALIAS_DEF" },
+            CONST_DEF => &SyntaxInfo { name: "CONST_DEF" },
             MEMORY_TYPE_SPECIFIER => &SyntaxInfo { name: "MEMORY_TYPE_SPECIFIER" },
             RECORD_FIELD_DEF_LIST => &SyntaxInfo { name: "RECORD_FIELD_DEF_LIST" },
             RECORD_FIELD_DEF => &SyntaxInfo { name: "RECORD_FIELD_DEF" },
@@ -607,7 +641,12 @@ impl SyntaxKind {
             PATH_TYPE => &SyntaxInfo { name: "PATH_TYPE" },
             ARRAY_TYPE => &SyntaxInfo { name: "ARRAY_TYPE" },
             NEVER_TYPE => &SyntaxInfo { name: "NEVER_TYPE" },
+            FN_POINTER_TYPE => &SyntaxInfo { name: "FN_POINTER_TYPE" },
+            SLICE_TYPE => &SyntaxInfo { name: "SLICE_TYPE" },
+            TUPLE_TYPE => &SyntaxInfo { name: "TUPLE_TYPE" },
+            REF_TYPE => &SyntaxInfo { name: "REF_TYPE" },
             LET_STMT => &SyntaxInfo { name: "LET_STMT" },
+            STATIC_STMT => &SyntaxInfo { name: "STATIC_STMT" },
             EXPR_STMT => &SyntaxInfo { name: "EXPR_STMT" },
             PATH_EXPR => &SyntaxInfo { name: "PATH_EXPR" },
             PREFIX_EXPR => &SyntaxInfo { name: "PREFIX_EXPR" },
@@ -622,9 +661,14 @@ impl SyntaxKind {
             RETURN_EXPR => &SyntaxInfo { name: "RETURN_EXPR" },
             WHILE_EXPR => &SyntaxInfo { name: "WHILE_EXPR" },
             LOOP_EXPR => &SyntaxInfo { name: "LOOP_EXPR" },
+            FOR_EXPR => &SyntaxInfo { name: "FOR_EXPR" },
             BREAK_EXPR => &SyntaxInfo { name: "BREAK_EXPR" },
+            CONTINUE_EXPR => &SyntaxInfo { name: "CONTINUE_EXPR" },
             ARRAY_EXPR => &SyntaxInfo { name: "ARRAY_EXPR" },
+            TUPLE_EXPR => &SyntaxInfo { name: "TUPLE_EXPR" },
+            CAST_EXPR => &SyntaxInfo { name: "CAST_EXPR" },
             CONDITION => &SyntaxInfo { name: "CONDITION" },
+            LABEL => &SyntaxInfo { name: "LABEL" },
             BIND_PAT => &SyntaxInfo { name: "BIND_PAT" },
             PLACEHOLDER_PAT => &SyntaxInfo { name: "PLACEHOLDER_PAT" },
             ARG_LIST => &SyntaxInfo { name: "ARG_LIST" },
@@ -665,6 +709,7 @@ impl SyntaxKind {
             "true" => TRUE_KW,
             "while" => WHILE_KW,
             "loop" => LOOP_KW,
+            "continue" => CONTINUE_KW,
             "let" => LET_KW,
             "mut" => MUT_KW,
             "class" => CLASS_KW,
@@ -677,6 +722,8 @@ impl SyntaxKind {
             "self" => SELF_KW,
             "extern" => EXTERN_KW,
             "impl" => IMPL_KW,
+            "static" => STATIC_KW,
+            "const" => CONST_KW,
             _ => return None,
         };
         Some(kw)
@@ -295,6 +295,39 @@ fn strings() {
     "#);
 }
 
+#[test]
+fn lifetimes() {
+    insta::assert_snapshot!(dump_text_tokens(
+        r#"
+    'outer: loop { break 'outer; }
+    'a'
+    'note: x'
+    'note {x}'
+    "#), @r#"
+    WHITESPACE 5 "\n    "
+    LIFETIME 6 "'outer"
+    COLON 1 ":"
+    WHITESPACE 1 " "
+    LOOP_KW 4 "loop"
+    WHITESPACE 1 " "
+    L_CURLY 1 "{"
+    WHITESPACE 1 " "
+    BREAK_KW 5 "break"
+    WHITESPACE 1 " "
+    LIFETIME 6 "'outer"
+    SEMI 1 ";"
+    WHITESPACE 1 " "
+    R_CURLY 1 "}"
+    WHITESPACE 5 "\n    "
+    STRING 3 "'a'"
+    WHITESPACE 5 "\n    "
+    STRING 9 "'note: x'"
+    WHITESPACE 5 "\n    "
+    STRING 10 "'note {x}'"
+    WHITESPACE 5 "\n    "
+    "#);
+}
+
 #[test]
 fn keywords() {
     insta::assert_snapshot!(dump_text_tokens(
@@ -302,7 +335,7 @@ fn keywords() {
     break do else false for fn if in nil
     return true while let mut struct class
     never loop pub super self package type
-    impl
+    impl continue
     "#), @r###"
     WHITESPACE 5 "\n    "
     BREAK_KW 5 "break"
@@ -352,6 +385,8 @@ fn keywords() {
     TYPE_KW 4 "type"
     WHITESPACE 5 "\n    "
     IMPL_KW 4 "impl"
+    WHITESPACE 1 " "
+    CONTINUE_KW 8 "continue"
     WHITESPACE 5 "\n    "
     "###);
 }
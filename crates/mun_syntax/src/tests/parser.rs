@@ -145,6 +145,578 @@ fn array_type() {
     );
 }
 
+#[test]
+fn fn_pointer_type() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn apply(f: fn(i64) -> i64, g: fn()) {}"#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..44
+      FUNCTION_DEF@0..44
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..13
+          IDENT@8..13 "apply"
+        PARAM_LIST@13..41
+          L_PAREN@13..14 "("
+          PARAM@14..31
+            BIND_PAT@14..15
+              NAME@14..15
+                IDENT@14..15 "f"
+            COLON@15..16 ":"
+            WHITESPACE@16..17 " "
+            FN_POINTER_TYPE@17..31
+              FN_KW@17..19 "fn"
+              PARAM_LIST@19..24
+                L_PAREN@19..20 "("
+                PARAM@20..23
+                  PATH_TYPE@20..23
+                    PATH@20..23
+                      PATH_SEGMENT@20..23
+                        NAME_REF@20..23
+                          IDENT@20..23 "i64"
+                R_PAREN@23..24 ")"
+              WHITESPACE@24..25 " "
+              RET_TYPE@25..31
+                THIN_ARROW@25..27 "->"
+                WHITESPACE@27..28 " "
+                PATH_TYPE@28..31
+                  PATH@28..31
+                    PATH_SEGMENT@28..31
+                      NAME_REF@28..31
+                        IDENT@28..31 "i64"
+          COMMA@31..32 ","
+          WHITESPACE@32..33 " "
+          PARAM@33..40
+            BIND_PAT@33..34
+              NAME@33..34
+                IDENT@33..34 "g"
+            COLON@34..35 ":"
+            WHITESPACE@35..36 " "
+            FN_POINTER_TYPE@36..40
+              FN_KW@36..38 "fn"
+              PARAM_LIST@38..40
+                L_PAREN@38..39 "("
+                R_PAREN@39..40 ")"
+          R_PAREN@40..41 ")"
+        WHITESPACE@41..42 " "
+        BLOCK_EXPR@42..44
+          L_CURLY@42..43 "{"
+          R_CURLY@43..44 "}"
+    "#
+    );
+}
+
+#[test]
+fn attributes() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    #[cold]
+    fn foo() {}"#,
+    ).debug_dump(), @r###"
+    SOURCE_FILE@0..28
+      FUNCTION_DEF@0..28
+        WHITESPACE@0..5 "\n    "
+        ATTR@5..12
+          HASH@5..6 "#"
+          L_BRACKET@6..7 "["
+          NAME_REF@7..11
+            IDENT@7..11 "cold"
+          R_BRACKET@11..12 "]"
+        WHITESPACE@12..17 "\n    "
+        FN_KW@17..19 "fn"
+        WHITESPACE@19..20 " "
+        NAME@20..23
+          IDENT@20..23 "foo"
+        PARAM_LIST@23..25
+          L_PAREN@23..24 "("
+          R_PAREN@24..25 ")"
+        WHITESPACE@25..26 " "
+        BLOCK_EXPR@26..28
+          L_CURLY@26..27 "{"
+          R_CURLY@27..28 "}"
+    "###
+    );
+}
+
+#[test]
+fn attribute_args() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    #[assert_size(16)]
+    struct Foo;"#,
+    ).debug_dump(), @r###"
+    SOURCE_FILE@0..39
+      STRUCT_DEF@0..39
+        WHITESPACE@0..5 "\n    "
+        ATTR@5..23
+          HASH@5..6 "#"
+          L_BRACKET@6..7 "["
+          NAME_REF@7..18
+            IDENT@7..18 "assert_size"
+          ARG_LIST@18..22
+            L_PAREN@18..19 "("
+            LITERAL@19..21
+              INT_NUMBER@19..21 "16"
+            R_PAREN@21..22 ")"
+          R_BRACKET@22..23 "]"
+        WHITESPACE@23..28 "\n    "
+        STRUCT_KW@28..34 "struct"
+        WHITESPACE@34..35 " "
+        NAME@35..38
+          IDENT@35..38 "Foo"
+        SEMI@38..39 ";"
+    "###
+    );
+}
+
+#[test]
+fn attribute_value() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    #[link_section = ".config"]
+    const FOO: i32 = 1;"#,
+    ).debug_dump(), @r###"
+    SOURCE_FILE@0..56
+      WHITESPACE@0..5 "\n    "
+      CONST_DEF@5..56
+        ATTR@5..32
+          HASH@5..6 "#"
+          L_BRACKET@6..7 "["
+          NAME_REF@7..19
+            IDENT@7..19 "link_section"
+          WHITESPACE@19..20 " "
+          EQ@20..21 "="
+          WHITESPACE@21..22 " "
+          LITERAL@22..31
+            STRING@22..31 "\".config\""
+          R_BRACKET@31..32 "]"
+        WHITESPACE@32..37 "\n    "
+        CONST_KW@37..42 "const"
+        WHITESPACE@42..43 " "
+        NAME@43..46
+          IDENT@43..46 "FOO"
+        COLON@46..47 ":"
+        WHITESPACE@47..48 " "
+        PATH_TYPE@48..51
+          PATH@48..51
+            PATH_SEGMENT@48..51
+              NAME_REF@48..51
+                IDENT@48..51 "i32"
+        WHITESPACE@51..52 " "
+        EQ@52..53 "="
+        WHITESPACE@53..54 " "
+        LITERAL@54..55
+          INT_NUMBER@54..55 "1"
+        SEMI@55..56 ";"
+    "###
+    );
+}
+
+#[test]
+fn static_stmt_attributes() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn f() { #[link_section = ".cfg"] static mut N: i32 = 0; }"#,
+    ).debug_dump(), @r###"
+    SOURCE_FILE@0..63
+      FUNCTION_DEF@0..63
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..9
+          IDENT@8..9 "f"
+        PARAM_LIST@9..11
+          L_PAREN@9..10 "("
+          R_PAREN@10..11 ")"
+        WHITESPACE@11..12 " "
+        BLOCK_EXPR@12..63
+          L_CURLY@12..13 "{"
+          WHITESPACE@13..14 " "
+          STATIC_STMT@14..61
+            ATTR@14..38
+              HASH@14..15 "#"
+              L_BRACKET@15..16 "["
+              NAME_REF@16..28
+                IDENT@16..28 "link_section"
+              WHITESPACE@28..29 " "
+              EQ@29..30 "="
+              WHITESPACE@30..31 " "
+              LITERAL@31..37
+                STRING@31..37 "\".cfg\""
+              R_BRACKET@37..38 "]"
+            WHITESPACE@38..39 " "
+            STATIC_KW@39..45 "static"
+            WHITESPACE@45..46 " "
+            MUT_KW@46..49 "mut"
+            WHITESPACE@49..50 " "
+            BIND_PAT@50..51
+              NAME@50..51
+                IDENT@50..51 "N"
+            COLON@51..52 ":"
+            WHITESPACE@52..53 " "
+            PATH_TYPE@53..56
+              PATH@53..56
+                PATH_SEGMENT@53..56
+                  NAME_REF@53..56
+                    IDENT@53..56 "i32"
+            WHITESPACE@56..57 " "
+            EQ@57..58 "="
+            WHITESPACE@58..59 " "
+            LITERAL@59..60
+              INT_NUMBER@59..60 "0"
+            SEMI@60..61 ";"
+          WHITESPACE@61..62 " "
+          R_CURLY@62..63 "}"
+    "###
+    );
+}
+
+#[test]
+fn block_comments() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    /** Documentation */
+    fn foo() {}
+    /* /* nested */ */
+    /* unterminated"#,
+    ).debug_dump(), @r###"
+    SOURCE_FILE@0..84
+      FUNCTION_DEF@0..41
+        WHITESPACE@0..5 "\n    "
+        COMMENT@5..25 "/** Documentation */"
+        WHITESPACE@25..30 "\n    "
+        FN_KW@30..32 "fn"
+        WHITESPACE@32..33 " "
+        NAME@33..36
+          IDENT@33..36 "foo"
+        PARAM_LIST@36..38
+          L_PAREN@36..37 "("
+          R_PAREN@37..38 ")"
+        WHITESPACE@38..39 " "
+        BLOCK_EXPR@39..41
+          L_CURLY@39..40 "{"
+          R_CURLY@40..41 "}"
+      WHITESPACE@41..46 "\n    "
+      COMMENT@46..64 "/* /* nested */ */"
+      WHITESPACE@64..69 "\n    "
+      COMMENT@69..84 "/* unterminated"
+    error Range(69..71): unterminated block comment
+    "###
+    );
+}
+
+#[test]
+fn slice_type() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn fill(buf: &[i64]) {}"#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..28
+      FUNCTION_DEF@0..28
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..12
+          IDENT@8..12 "fill"
+        PARAM_LIST@12..25
+          L_PAREN@12..13 "("
+          PARAM@13..24
+            BIND_PAT@13..16
+              NAME@13..16
+                IDENT@13..16 "buf"
+            COLON@16..17 ":"
+            WHITESPACE@17..18 " "
+            SLICE_TYPE@18..24
+              AMP@18..19 "&"
+              L_BRACKET@19..20 "["
+              PATH_TYPE@20..23
+                PATH@20..23
+                  PATH_SEGMENT@20..23
+                    NAME_REF@20..23
+                      IDENT@20..23 "i64"
+              R_BRACKET@23..24 "]"
+          R_PAREN@24..25 ")"
+        WHITESPACE@25..26 " "
+        BLOCK_EXPR@26..28
+          L_CURLY@26..27 "{"
+          R_CURLY@27..28 "}"
+    "#
+    );
+}
+
+#[test]
+fn unit() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn foo(a: ()) -> () {
+        let x = ();
+    }"#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..52
+      FUNCTION_DEF@0..52
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..11
+          IDENT@8..11 "foo"
+        PARAM_LIST@11..18
+          L_PAREN@11..12 "("
+          PARAM@12..17
+            BIND_PAT@12..13
+              NAME@12..13
+                IDENT@12..13 "a"
+            COLON@13..14 ":"
+            WHITESPACE@14..15 " "
+            TUPLE_TYPE@15..17
+              L_PAREN@15..16 "("
+              R_PAREN@16..17 ")"
+          R_PAREN@17..18 ")"
+        WHITESPACE@18..19 " "
+        RET_TYPE@19..24
+          THIN_ARROW@19..21 "->"
+          WHITESPACE@21..22 " "
+          TUPLE_TYPE@22..24
+            L_PAREN@22..23 "("
+            R_PAREN@23..24 ")"
+        WHITESPACE@24..25 " "
+        BLOCK_EXPR@25..52
+          L_CURLY@25..26 "{"
+          WHITESPACE@26..35 "\n        "
+          LET_STMT@35..46
+            LET_KW@35..38 "let"
+            WHITESPACE@38..39 " "
+            BIND_PAT@39..40
+              NAME@39..40
+                IDENT@39..40 "x"
+            WHITESPACE@40..41 " "
+            EQ@41..42 "="
+            WHITESPACE@42..43 " "
+            TUPLE_EXPR@43..45
+              L_PAREN@43..44 "("
+              R_PAREN@44..45 ")"
+            SEMI@45..46 ";"
+          WHITESPACE@46..51 "\n    "
+          R_CURLY@51..52 "}"
+    "#
+    );
+}
+
+#[test]
+fn static_stmt() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn counter() -> i64 { static mut N: i64 = 0; N += 1; N }"#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..61
+      FUNCTION_DEF@0..61
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..15
+          IDENT@8..15 "counter"
+        PARAM_LIST@15..17
+          L_PAREN@15..16 "("
+          R_PAREN@16..17 ")"
+        WHITESPACE@17..18 " "
+        RET_TYPE@18..24
+          THIN_ARROW@18..20 "->"
+          WHITESPACE@20..21 " "
+          PATH_TYPE@21..24
+            PATH@21..24
+              PATH_SEGMENT@21..24
+                NAME_REF@21..24
+                  IDENT@21..24 "i64"
+        WHITESPACE@24..25 " "
+        BLOCK_EXPR@25..61
+          L_CURLY@25..26 "{"
+          WHITESPACE@26..27 " "
+          STATIC_STMT@27..49
+            STATIC_KW@27..33 "static"
+            WHITESPACE@33..34 " "
+            MUT_KW@34..37 "mut"
+            WHITESPACE@37..38 " "
+            BIND_PAT@38..39
+              NAME@38..39
+                IDENT@38..39 "N"
+            COLON@39..40 ":"
+            WHITESPACE@40..41 " "
+            PATH_TYPE@41..44
+              PATH@41..44
+                PATH_SEGMENT@41..44
+                  NAME_REF@41..44
+                    IDENT@41..44 "i64"
+            WHITESPACE@44..45 " "
+            EQ@45..46 "="
+            WHITESPACE@46..47 " "
+            LITERAL@47..48
+              INT_NUMBER@47..48 "0"
+            SEMI@48..49 ";"
+          WHITESPACE@49..50 " "
+          EXPR_STMT@50..57
+            BIN_EXPR@50..56
+              PATH_EXPR@50..51
+                PATH@50..51
+                  PATH_SEGMENT@50..51
+                    NAME_REF@50..51
+                      IDENT@50..51 "N"
+              WHITESPACE@51..52 " "
+              PLUSEQ@52..54 "+="
+              WHITESPACE@54..55 " "
+              LITERAL@55..56
+                INT_NUMBER@55..56 "1"
+            SEMI@56..57 ";"
+          WHITESPACE@57..58 " "
+          PATH_EXPR@58..59
+            PATH@58..59
+              PATH_SEGMENT@58..59
+                NAME_REF@58..59
+                  IDENT@58..59 "N"
+          WHITESPACE@59..60 " "
+          R_CURLY@60..61 "}"
+    "#
+    );
+}
+
+#[test]
+fn ref_type() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn write(out: &mut i64) { *out = 5; }"#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..42
+      FUNCTION_DEF@0..42
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..13
+          IDENT@8..13 "write"
+        PARAM_LIST@13..28
+          L_PAREN@13..14 "("
+          PARAM@14..27
+            BIND_PAT@14..17
+              NAME@14..17
+                IDENT@14..17 "out"
+            COLON@17..18 ":"
+            WHITESPACE@18..19 " "
+            REF_TYPE@19..27
+              AMP@19..20 "&"
+              MUT_KW@20..23 "mut"
+              WHITESPACE@23..24 " "
+              PATH_TYPE@24..27
+                PATH@24..27
+                  PATH_SEGMENT@24..27
+                    NAME_REF@24..27
+                      IDENT@24..27 "i64"
+          R_PAREN@27..28 ")"
+        WHITESPACE@28..29 " "
+        BLOCK_EXPR@29..42
+          L_CURLY@29..30 "{"
+          WHITESPACE@30..31 " "
+          EXPR_STMT@31..40
+            BIN_EXPR@31..39
+              PREFIX_EXPR@31..35
+                STAR@31..32 "*"
+                PATH_EXPR@32..35
+                  PATH@32..35
+                    PATH_SEGMENT@32..35
+                      NAME_REF@32..35
+                        IDENT@32..35 "out"
+              WHITESPACE@35..36 " "
+              EQ@36..37 "="
+              WHITESPACE@37..38 " "
+              LITERAL@38..39
+                INT_NUMBER@38..39 "5"
+            SEMI@39..40 ";"
+          WHITESPACE@40..41 " "
+          R_CURLY@41..42 "}"
+    "#
+    );
+}
+
+#[test]
+fn cast_expr() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn f(a: i64) { a as i32 + -a as u8 as f32; }"#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..49
+      FUNCTION_DEF@0..49
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..9
+          IDENT@8..9 "f"
+        PARAM_LIST@9..17
+          L_PAREN@9..10 "("
+          PARAM@10..16
+            BIND_PAT@10..11
+              NAME@10..11
+                IDENT@10..11 "a"
+            COLON@11..12 ":"
+            WHITESPACE@12..13 " "
+            PATH_TYPE@13..16
+              PATH@13..16
+                PATH_SEGMENT@13..16
+                  NAME_REF@13..16
+                    IDENT@13..16 "i64"
+          R_PAREN@16..17 ")"
+        WHITESPACE@17..18 " "
+        BLOCK_EXPR@18..49
+          L_CURLY@18..19 "{"
+          WHITESPACE@19..20 " "
+          EXPR_STMT@20..47
+            BIN_EXPR@20..46
+              CAST_EXPR@20..28
+                PATH_EXPR@20..21
+                  PATH@20..21
+                    PATH_SEGMENT@20..21
+                      NAME_REF@20..21
+                        IDENT@20..21 "a"
+                WHITESPACE@21..22 " "
+                AS_KW@22..24 "as"
+                WHITESPACE@24..25 " "
+                PATH_TYPE@25..28
+                  PATH@25..28
+                    PATH_SEGMENT@25..28
+                      NAME_REF@25..28
+                        IDENT@25..28 "i32"
+              WHITESPACE@28..29 " "
+              PLUS@29..30 "+"
+              WHITESPACE@30..31 " "
+              CAST_EXPR@31..46
+                CAST_EXPR@31..39
+                  PREFIX_EXPR@31..33
+                    MINUS@31..32 "-"
+                    PATH_EXPR@32..33
+                      PATH@32..33
+                        PATH_SEGMENT@32..33
+                          NAME_REF@32..33
+                            IDENT@32..33 "a"
+                  WHITESPACE@33..34 " "
+                  AS_KW@34..36 "as"
+                  WHITESPACE@36..37 " "
+                  PATH_TYPE@37..39
+                    PATH@37..39
+                      PATH_SEGMENT@37..39
+                        NAME_REF@37..39
+                          IDENT@37..39 "u8"
+                WHITESPACE@39..40 " "
+                AS_KW@40..42 "as"
+                WHITESPACE@42..43 " "
+                PATH_TYPE@43..46
+                  PATH@43..46
+                    PATH_SEGMENT@43..46
+                      NAME_REF@43..46
+                        IDENT@43..46 "f32"
+            SEMI@46..47 ";"
+          WHITESPACE@47..48 " "
+          R_CURLY@48..49 "}"
+    "#
+    );
+}
+
 #[test]
 fn index_expr() {
     insta::assert_snapshot!(SourceFile::parse(
@@ -2546,6 +3118,114 @@ fn break_expr() {
     "#);
 }
 
+#[test]
+fn continue_expr() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn foo() {
+        loop { continue; }
+    }
+    "#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..53
+      FUNCTION_DEF@0..48
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..11
+          IDENT@8..11 "foo"
+        PARAM_LIST@11..13
+          L_PAREN@11..12 "("
+          R_PAREN@12..13 ")"
+        WHITESPACE@13..14 " "
+        BLOCK_EXPR@14..48
+          L_CURLY@14..15 "{"
+          WHITESPACE@15..24 "\n        "
+          LOOP_EXPR@24..42
+            LOOP_KW@24..28 "loop"
+            WHITESPACE@28..29 " "
+            BLOCK_EXPR@29..42
+              L_CURLY@29..30 "{"
+              WHITESPACE@30..31 " "
+              EXPR_STMT@31..40
+                CONTINUE_EXPR@31..39
+                  CONTINUE_KW@31..39 "continue"
+                SEMI@39..40 ";"
+              WHITESPACE@40..41 " "
+              R_CURLY@41..42 "}"
+          WHITESPACE@42..47 "\n    "
+          R_CURLY@47..48 "}"
+      WHITESPACE@48..53 "\n    "
+    "#);
+}
+
+#[test]
+fn labeled_loop_expr() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn foo() {
+        'outer: loop { while true { break 'outer; } continue 'outer; }
+    }
+    "#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..97
+      FUNCTION_DEF@0..92
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..11
+          IDENT@8..11 "foo"
+        PARAM_LIST@11..13
+          L_PAREN@11..12 "("
+          R_PAREN@12..13 ")"
+        WHITESPACE@13..14 " "
+        BLOCK_EXPR@14..92
+          L_CURLY@14..15 "{"
+          WHITESPACE@15..24 "\n        "
+          LOOP_EXPR@24..86
+            LABEL@24..31
+              LIFETIME@24..30 "'outer"
+              COLON@30..31 ":"
+            WHITESPACE@31..32 " "
+            LOOP_KW@32..36 "loop"
+            WHITESPACE@36..37 " "
+            BLOCK_EXPR@37..86
+              L_CURLY@37..38 "{"
+              WHITESPACE@38..39 " "
+              EXPR_STMT@39..67
+                WHILE_EXPR@39..67
+                  WHILE_KW@39..44 "while"
+                  WHITESPACE@44..45 " "
+                  CONDITION@45..49
+                    LITERAL@45..49
+                      TRUE_KW@45..49 "true"
+                  WHITESPACE@49..50 " "
+                  BLOCK_EXPR@50..67
+                    L_CURLY@50..51 "{"
+                    WHITESPACE@51..52 " "
+                    EXPR_STMT@52..65
+                      BREAK_EXPR@52..64
+                        BREAK_KW@52..57 "break"
+                        WHITESPACE@57..58 " "
+                        LIFETIME@58..64 "'outer"
+                      SEMI@64..65 ";"
+                    WHITESPACE@65..66 " "
+                    R_CURLY@66..67 "}"
+              WHITESPACE@67..68 " "
+              EXPR_STMT@68..84
+                CONTINUE_EXPR@68..83
+                  CONTINUE_KW@68..76 "continue"
+                  WHITESPACE@76..77 " "
+                  LIFETIME@77..83 "'outer"
+                SEMI@83..84 ";"
+              WHITESPACE@84..85 " "
+              R_CURLY@85..86 "}"
+          WHITESPACE@86..91 "\n    "
+          R_CURLY@91..92 "}"
+      WHITESPACE@92..97 "\n    "
+    "#);
+}
+
 #[test]
 fn while_expr() {
     insta::assert_snapshot!(SourceFile::parse(
@@ -2606,6 +3286,55 @@ fn while_expr() {
     "#);
 }
 
+#[test]
+fn for_expr() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    fn foo() {
+        for x in a {};
+    }
+    "#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..49
+      FUNCTION_DEF@0..44
+        WHITESPACE@0..5 "\n    "
+        FN_KW@5..7 "fn"
+        WHITESPACE@7..8 " "
+        NAME@8..11
+          IDENT@8..11 "foo"
+        PARAM_LIST@11..13
+          L_PAREN@11..12 "("
+          R_PAREN@12..13 ")"
+        WHITESPACE@13..14 " "
+        BLOCK_EXPR@14..44
+          L_CURLY@14..15 "{"
+          WHITESPACE@15..24 "\n        "
+          EXPR_STMT@24..38
+            FOR_EXPR@24..37
+              FOR_KW@24..27 "for"
+              WHITESPACE@27..28 " "
+              BIND_PAT@28..29
+                NAME@28..29
+                  IDENT@28..29 "x"
+              WHITESPACE@29..30 " "
+              IN_KW@30..32 "in"
+              WHITESPACE@32..33 " "
+              PATH_EXPR@33..34
+                PATH@33..34
+                  PATH_SEGMENT@33..34
+                    NAME_REF@33..34
+                      IDENT@33..34 "a"
+              WHITESPACE@34..35 " "
+              BLOCK_EXPR@35..37
+                L_CURLY@35..36 "{"
+                R_CURLY@36..37 "}"
+            SEMI@37..38 ";"
+          WHITESPACE@38..43 "\n    "
+          R_CURLY@43..44 "}"
+      WHITESPACE@44..49 "\n    "
+    "#);
+}
+
 #[test]
 fn struct_lit() {
     insta::assert_snapshot!(SourceFile::parse(
@@ -3173,6 +3902,40 @@ fn type_alias_def() {
     "#);
 }
 
+#[test]
+fn const_def() {
+    insta::assert_snapshot!(SourceFile::parse(
+        r#"
+    pub const MAX: i64 = 10;
+    "#,
+    ).debug_dump(), @r#"
+    SOURCE_FILE@0..34
+      WHITESPACE@0..5 "\n    "
+      CONST_DEF@5..29
+        VISIBILITY@5..8
+          PUB_KW@5..8 "pub"
+        WHITESPACE@8..9 " "
+        CONST_KW@9..14 "const"
+        WHITESPACE@14..15 " "
+        NAME@15..18
+          IDENT@15..18 "MAX"
+        COLON@18..19 ":"
+        WHITESPACE@19..20 " "
+        PATH_TYPE@20..23
+          PATH@20..23
+            PATH_SEGMENT@20..23
+              NAME_REF@20..23
+                IDENT@20..23 "i64"
+        WHITESPACE@23..24 " "
+        EQ@24..25 "="
+        WHITESPACE@25..26 " "
+        LITERAL@26..28
+          INT_NUMBER@26..28 "10"
+        SEMI@28..29 ";"
+      WHITESPACE@29..34 "\n    "
+    "#);
+}
+
 #[test]
 fn function_return_path() {
     insta::assert_snapshot!(SourceFile::parse(
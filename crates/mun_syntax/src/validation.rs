@@ -3,8 +3,8 @@
 
 use crate::{
     ast,
-    ast::{AstNode, VisibilityOwner},
-    match_ast, SyntaxError, SyntaxNode,
+    ast::{AstNode, AstToken, VisibilityOwner},
+    match_ast, SyntaxError, SyntaxNode, TextRange, TextSize,
 };
 
 /// A validation pass that checks that the AST is valid.
@@ -23,9 +23,29 @@ pub(crate) fn validate(root: &SyntaxNode) -> Vec<SyntaxError> {
         }
     }
 
+    for comment in root
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter_map(ast::Comment::cast)
+    {
+        validate_comment(comment, &mut errors);
+    }
+
     errors
 }
 
+/// Validates that a block comment is closed. The error points at the opening
+/// `/*` of the comment.
+fn validate_comment(comment: ast::Comment, errors: &mut Vec<SyntaxError>) {
+    if comment.is_unterminated_block() {
+        let start = comment.syntax().text_range().start();
+        errors.push(SyntaxError::parse_error(
+            "unterminated block comment",
+            TextRange::at(start, TextSize::of("/*")),
+        ));
+    }
+}
+
 /// Validates the semantic validity of an `impl` block.
 fn validate_impl(node: ast::Impl, errors: &mut Vec<SyntaxError>) {
     validate_impl_visibility(node.clone(), errors);
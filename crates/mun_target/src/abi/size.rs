@@ -3,6 +3,8 @@
 
 // use crate::abi::{Align, HasDataLayout};
 use std::convert::TryInto;
+
+use super::Align;
 // use std::ops::{Add, AddAssign, Mul, Sub};
 
 /// Size of a type in bytes.
@@ -52,13 +54,13 @@ impl Size {
     // pub fn bits_usize(self) -> usize {
     //     self.bits().try_into().unwrap()
     // }
-    //
-    // #[inline]
-    // pub fn align_to(self, align: Align) -> Size {
-    //     let mask = align.bytes() - 1;
-    //     Size::from_bytes((self.bytes() + mask) & !mask)
-    // }
-    //
+
+    #[inline]
+    pub fn align_to(self, align: Align) -> Size {
+        let mask = align.bytes() - 1;
+        Size::from_bytes((self.bytes() + mask) & !mask)
+    }
+
     // #[inline]
     // pub fn is_aligned(self, align: Align) -> bool {
     //     let mask = align.bytes() - 1;
@@ -86,6 +86,11 @@ pub struct TargetOptions {
     /// against iOS/macOS, in particular running dsymutil and some other
     /// stuff like `-dead_strip`. Defaults to false.
     pub is_like_osx: bool,
+
+    /// Whether code for this target can be instrumented with LLVM's
+    /// AddressSanitizer, i.e. whether the sanitizer runtime is available for
+    /// it. Defaults to false.
+    pub supports_address_sanitizer: bool,
 }
 
 impl Default for TargetOptions {
@@ -106,6 +111,7 @@ impl Default for TargetOptions {
             is_like_windows: false,
             is_like_msvc: false,
             is_like_osx: false,
+            supports_address_sanitizer: false,
         }
     }
 }
@@ -108,6 +108,7 @@ pub fn opts(os: &'static str, arch: Arch) -> TargetOptions {
         linker_flavor: LinkerFlavor::Ld64,
         dll_prefix: "lib".to_string(),
         is_like_osx: os == "macos",
+        supports_address_sanitizer: os == "macos",
         pre_link_args: pre_link_args(os, arch, abi),
         ..Default::default()
     }
@@ -6,6 +6,7 @@ pub fn opts() -> TargetOptions {
         env: "gnu".to_string(),
         vendor: "unknown".to_string(),
         linker_flavor: LinkerFlavor::Ld,
+        supports_address_sanitizer: true,
         ..Default::default()
     }
 }
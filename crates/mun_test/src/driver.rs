@@ -64,11 +64,17 @@ impl CompileTestDriver {
 
     /// Constructs a new `CompileTestDriver` from a single Mun source.
     pub fn from_file(text: &str) -> Self {
+        Self::from_file_with_config(text, |config| config)
+    }
+
+    /// Constructs a new `CompileTestDriver` from a single Mun source and a
+    /// `config_fn` that allows modification of the compiler's [`Config`].
+    pub fn from_file_with_config(text: &str, config_fn: impl FnOnce(Config) -> Config) -> Self {
         let temp_dir = tempfile::TempDir::new().unwrap();
-        let config = Config {
+        let config = config_fn(Config {
             out_dir: Some(temp_dir.path().to_path_buf()),
             ..Config::default()
-        };
+        });
         let input = PathOrInline::Inline {
             rel_path: RelativePathBuf::from("mod.mun"),
             contents: text.to_owned(),
@@ -165,7 +171,19 @@ impl CompileAndRunTestDriver {
         text: &str,
         config_fn: impl FnOnce(RuntimeBuilder) -> RuntimeBuilder,
     ) -> Result<Self, InitError> {
-        let driver = CompileTestDriver::from_file(text);
+        Self::with_compiler_config(text, |config| config, config_fn)
+    }
+
+    /// Constructs a `CompileAndRunTestDriver` from a single Mun source file, a
+    /// `compiler_config_fn` that allows modification of the compiler's
+    /// [`Config`], and a `config_fn` that allows modification of a
+    /// [`RuntimeBuilder`].
+    pub fn with_compiler_config(
+        text: &str,
+        compiler_config_fn: impl FnOnce(Config) -> Config,
+        config_fn: impl FnOnce(RuntimeBuilder) -> RuntimeBuilder,
+    ) -> Result<Self, InitError> {
+        let driver = CompileTestDriver::from_file_with_config(text, compiler_config_fn);
         let builder = Runtime::builder(driver.lib_path());
 
         // Safety: We compiled the library ourselves, therefor loading the munlib is
@@ -176,6 +194,11 @@ impl CompileAndRunTestDriver {
         Ok(Self { driver, runtime })
     }
 
+    /// Returns the path to the generated `*.munlib` library.
+    pub fn lib_path(&self) -> &Path {
+        self.driver.lib_path()
+    }
+
     /// Updates the text of the Mun source and ensures that the generated
     /// assembly has been reloaded.
     ///
@@ -212,15 +212,18 @@ impl State for SpaceshipGame {
         }
 
         // Rockets update
-        let rockets = self.mun_runtime.construct_typed_array(
-            &self
-                .mun_runtime
-                .get_type_info_by_name("Rocket")
-                .expect("could not find Rocket type"),
-            self.rockets
-                .iter()
-                .map(|rocket| rocket.as_ref(&self.mun_runtime)),
-        );
+        let rockets = self
+            .mun_runtime
+            .construct_typed_array(
+                &self
+                    .mun_runtime
+                    .get_type_info_by_name("Rocket")
+                    .expect("could not find Rocket type"),
+                self.rockets
+                    .iter()
+                    .map(|rocket| rocket.as_ref(&self.mun_runtime)),
+            )
+            .unwrap();
 
         let _: () = self
             .mun_runtime
@@ -236,15 +239,18 @@ impl State for SpaceshipGame {
         });
 
         // Asteroids update
-        let asteroids = self.mun_runtime.construct_typed_array(
-            &self
-                .mun_runtime
-                .get_type_info_by_name("Asteroid")
-                .expect("could not find Asteroid type"),
-            self.asteroids
-                .iter()
-                .map(|asteroid| asteroid.as_ref(&self.mun_runtime)),
-        );
+        let asteroids = self
+            .mun_runtime
+            .construct_typed_array(
+                &self
+                    .mun_runtime
+                    .get_type_info_by_name("Asteroid")
+                    .expect("could not find Asteroid type"),
+                self.asteroids
+                    .iter()
+                    .map(|asteroid| asteroid.as_ref(&self.mun_runtime)),
+            )
+            .unwrap();
 
         let _: () = self
             .mun_runtime